@@ -4,27 +4,35 @@
 use std::{collections::HashSet, sync::Arc};
 
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use sui_config::{
     transaction_deny_config::TransactionDenyConfig, verifier_signing_config::VerifierSigningConfig,
 };
 use sui_execution::Executor;
 use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
 use sui_types::{
+    base_types::ObjectID,
     committee::{Committee, EpochId},
-    effects::TransactionEffects,
+    digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI},
+    error::SuiError,
     gas::SuiGasStatus,
+    gas_coin::MIST_PER_SUI,
     inner_temporary_store::InnerTemporaryStore,
     metrics::BytecodeVerifierMetrics,
     metrics::LimitsMetrics,
+    object::{MoveObject, Object, Owner, OBJECT_START_VERSION},
     sui_system_state::{
         epoch_start_sui_system_state::{EpochStartSystemState, EpochStartSystemStateTrait},
         SuiSystemState, SuiSystemStateTrait,
     },
-    transaction::{TransactionDataAPI, VerifiedTransaction},
+    transaction::{TransactionData, TransactionDataAPI, VerifiedTransaction},
+    transaction_executor::SimulateTransactionResult,
 };
 
 use crate::SimulatorStore;
 
+#[derive(Clone)]
 pub struct EpochState {
     epoch_start_state: EpochStartSystemState,
     committee: Committee,
@@ -32,17 +40,42 @@ pub struct EpochState {
     limits_metrics: Arc<LimitsMetrics>,
     bytecode_verifier_metrics: Arc<BytecodeVerifierMetrics>,
     executor: Arc<dyn Executor + Send + Sync>,
+    chain: Chain,
     /// A counter that advances each time we advance the clock in order to ensure that each update
     /// txn has a unique digest. This is reset on epoch changes
     next_consensus_round: u64,
 }
 
+/// The parts of an [`EpochState`] that can't be derived from the others, which is all that needs
+/// to be saved in a snapshot to recreate it.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct EpochStateSnapshot {
+    epoch_start_state: EpochStartSystemState,
+    committee: Committee,
+    chain: Chain,
+    next_consensus_round: u64,
+}
+
 impl EpochState {
     pub fn new(system_state: SuiSystemState) -> Self {
+        Self::new_for_chain(system_state, Chain::Unknown)
+    }
+
+    /// Creates the state of the epoch described by `system_state`, with the protocol config `chain`
+    /// uses for the epoch's protocol version.
+    pub fn new_for_chain(system_state: SuiSystemState, chain: Chain) -> Self {
         let epoch_start_state = system_state.into_epoch_start_state();
         let committee = epoch_start_state.get_sui_committee();
+        Self::new_with_committee(epoch_start_state, committee, chain)
+    }
+
+    fn new_with_committee(
+        epoch_start_state: EpochStartSystemState,
+        committee: Committee,
+        chain: Chain,
+    ) -> Self {
         let protocol_config =
-            ProtocolConfig::get_for_version(epoch_start_state.protocol_version(), Chain::Unknown);
+            ProtocolConfig::get_for_version(epoch_start_state.protocol_version(), chain);
         let registry = prometheus::Registry::new();
         let limits_metrics = Arc::new(LimitsMetrics::new(&registry));
         let bytecode_verifier_metrics = Arc::new(BytecodeVerifierMetrics::new(&registry));
@@ -55,10 +88,18 @@ impl EpochState {
             limits_metrics,
             bytecode_verifier_metrics,
             executor,
+            chain,
             next_consensus_round: 0,
         }
     }
 
+    /// Replaces the committee derived from the system state, for chains whose validators are not
+    /// the ones signing checkpoints locally.
+    pub fn with_committee(mut self, committee: Committee) -> Self {
+        self.committee = committee;
+        self
+    }
+
     pub fn epoch(&self) -> EpochId {
         self.epoch_start_state.epoch()
     }
@@ -89,6 +130,32 @@ impl EpochState {
         &self.protocol_config
     }
 
+    pub fn chain(&self) -> Chain {
+        self.chain
+    }
+
+    pub(crate) fn snapshot(&self) -> EpochStateSnapshot {
+        EpochStateSnapshot {
+            epoch_start_state: self.epoch_start_state.clone(),
+            committee: self.committee.clone(),
+            chain: self.chain,
+            next_consensus_round: self.next_consensus_round,
+        }
+    }
+
+    pub(crate) fn from_snapshot(snapshot: EpochStateSnapshot) -> Self {
+        let EpochStateSnapshot {
+            epoch_start_state,
+            committee,
+            chain,
+            next_consensus_round,
+        } = snapshot;
+
+        let mut state = Self::new_with_committee(epoch_start_state, committee, chain);
+        state.next_consensus_round = next_consensus_round;
+        state
+    }
+
     pub fn execute_transaction(
         &self,
         store: &dyn SimulatorStore,
@@ -154,4 +221,115 @@ impl EpochState {
             );
         Ok((inner_temp_store, gas_status, effects, result))
     }
+
+    /// Executes `transaction` against `store` without signatures, and without committing its
+    /// effects.
+    ///
+    /// If the transaction doesn't provide any gas coins, it is paid for with a mock coin, whose id
+    /// is returned as part of the result.
+    pub fn simulate_transaction(
+        &self,
+        store: &dyn SimulatorStore,
+        deny_config: &TransactionDenyConfig,
+        verifier_signing_config: &VerifierSigningConfig,
+        transaction: TransactionData,
+    ) -> Result<SimulateTransactionResult> {
+        if transaction.kind().is_system_tx() {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: "simulate does not support system transactions".to_string(),
+            }
+            .into());
+        }
+
+        transaction.validity_check_no_gas_check(&self.protocol_config)?;
+
+        let tx_digest = transaction.digest();
+        let input_object_kinds = transaction.input_objects()?;
+        let receiving_object_refs = transaction.receiving_objects();
+
+        sui_transaction_checks::deny::check_transaction_for_signing(
+            &transaction,
+            &[],
+            &input_object_kinds,
+            &receiving_object_refs,
+            deny_config,
+            &store,
+        )?;
+
+        let (input_objects, receiving_objects) = store.read_objects_for_synchronous_execution(
+            &tx_digest,
+            &input_object_kinds,
+            &receiving_object_refs,
+        )?;
+
+        // Make a gas coin if one was not provided
+        let mut gas_data = transaction.gas_data().clone();
+        let ((gas_status, checked_input_objects), mock_gas_id) = if transaction.gas().is_empty() {
+            const SIMULATE_SUI: u64 = 1_000_000_000;
+            let gas_object_id = ObjectID::MAX;
+            let gas_object = Object::new_move(
+                MoveObject::new_gas_coin(
+                    OBJECT_START_VERSION,
+                    gas_object_id,
+                    SIMULATE_SUI * MIST_PER_SUI,
+                ),
+                Owner::AddressOwner(transaction.sender()),
+                TransactionDigest::genesis_marker(),
+            );
+            gas_data.payment = vec![gas_object.compute_object_reference()];
+            (
+                sui_transaction_checks::check_transaction_input_with_given_gas(
+                    &self.protocol_config,
+                    self.epoch_start_state.reference_gas_price(),
+                    &transaction,
+                    input_objects,
+                    receiving_objects,
+                    gas_object,
+                    &self.bytecode_verifier_metrics,
+                    verifier_signing_config,
+                )?,
+                Some(gas_object_id),
+            )
+        } else {
+            (
+                sui_transaction_checks::check_transaction_input(
+                    &self.protocol_config,
+                    self.epoch_start_state.reference_gas_price(),
+                    &transaction,
+                    input_objects,
+                    &receiving_objects,
+                    &self.bytecode_verifier_metrics,
+                    verifier_signing_config,
+                )?,
+                None,
+            )
+        };
+
+        let (kind, signer, _) = transaction.execution_parts();
+        let (inner_temp_store, _, effects, _timings, _execution_error) =
+            self.executor.execute_transaction_to_effects(
+                store.backing_store(),
+                &self.protocol_config,
+                self.limits_metrics.clone(),
+                false,           // enable_expensive_checks
+                &HashSet::new(), // certificate_deny_set
+                &self.epoch_start_state.epoch(),
+                self.epoch_start_state.epoch_start_timestamp_ms(),
+                checked_input_objects,
+                gas_data,
+                gas_status,
+                kind,
+                signer,
+                tx_digest,
+                &mut None,
+            );
+
+        Ok(SimulateTransactionResult {
+            input_objects: inner_temp_store.input_objects,
+            output_objects: inner_temp_store.written,
+            events: effects.events_digest().map(|_| inner_temp_store.events),
+            effects,
+            mock_gas_id,
+        })
+    }
 }
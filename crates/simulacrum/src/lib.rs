@@ -14,25 +14,29 @@ use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use anyhow::{anyhow, ensure, Context, Result};
+use anyhow::{anyhow, bail, ensure, Context, Result};
 use fastcrypto::traits::Signer;
+use futures::executor::block_on;
 use rand::rngs::OsRng;
 use sui_config::verifier_signing_config::VerifierSigningConfig;
 use sui_config::{genesis, transaction_deny_config::TransactionDenyConfig};
 use sui_protocol_config::ProtocolVersion;
+use sui_replay::data_fetcher::{DataFetcher, Fetchers};
 use sui_storage::blob::{Blob, BlobEncoding};
 use sui_swarm_config::genesis_config::AccountConfig;
 use sui_swarm_config::network_config::NetworkConfig;
 use sui_swarm_config::network_config_builder::ConfigBuilder;
 use sui_types::base_types::{AuthorityName, ObjectID, ObjectRef, VersionNumber};
 use sui_types::crypto::{get_account_key_pair, AccountKeyPair, AuthoritySignature};
-use sui_types::digests::ConsensusCommitDigest;
+use sui_types::digests::{ChainIdentifier, ConsensusCommitDigest};
 use sui_types::effects::TransactionEffectsAPI;
 use sui_types::messages_consensus::ConsensusDeterminedVersionAssignments;
 use sui_types::object::{Object, Owner};
 use sui_types::storage::{ObjectStore, ReadStore, RpcStateReader};
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemState;
+use sui_types::sui_system_state::SuiSystemStateTrait;
 use sui_types::transaction::EndOfEpochTransactionKind;
+use sui_types::transaction_executor::SimulateTransactionResult;
 use sui_types::{
     base_types::SuiAddress,
     committee::Committee,
@@ -46,10 +50,15 @@ use sui_types::{
 };
 
 use self::epoch_state::EpochState;
+pub use self::snapshot::{RngState, SimulacrumSnapshot, SnapshotRng};
+pub use self::store::fork_store::ForkingStore;
 pub use self::store::in_mem_store::InMemoryStore;
 use self::store::in_mem_store::KeyStore;
 pub use self::store::SimulatorStore;
-use sui_types::messages_checkpoint::{CheckpointContents, CheckpointSequenceNumber};
+use sui_types::messages_checkpoint::{
+    CheckpointContents, CheckpointSequenceNumber, CheckpointSummary, CheckpointVersionSpecificData,
+    FullCheckpointContents,
+};
 use sui_types::mock_checkpoint_builder::{MockCheckpointBuilder, ValidatorKeypairProvider};
 use sui_types::{
     gas_coin::GasCoin,
@@ -58,6 +67,7 @@ use sui_types::{
 };
 
 mod epoch_state;
+mod snapshot;
 pub mod store;
 
 /// A `Simulacrum` of Sui.
@@ -84,6 +94,9 @@ pub struct Simulacrum<R = OsRng, Store: SimulatorStore = InMemoryStore> {
     deny_config: TransactionDenyConfig,
     data_ingestion_path: Option<PathBuf>,
     verifier_signing_config: VerifierSigningConfig,
+    chain_identifier: ChainIdentifier,
+    /// The checkpoint of the source network this Simulacrum was forked at, if it is a fork.
+    fork_checkpoint: Option<CheckpointSequenceNumber>,
 }
 
 impl Simulacrum {
@@ -143,6 +156,151 @@ where
     }
 }
 
+impl<R> Simulacrum<R, ForkingStore>
+where
+    R: rand::RngCore + rand::CryptoRng,
+{
+    /// Create a Simulacrum that forks the network `source` at its latest checkpoint. If `checkpoint`
+    /// is given, it must be the latest checkpoint of `source`.
+    ///
+    /// Only archive sources ([`Fetchers::Archive`]) can be forked at a historical checkpoint, by
+    /// reconstructing the archive up to that checkpoint. Other sources are rejected if
+    /// `checkpoint` is not their latest checkpoint: objects are fetched by walking back from their
+    /// latest version on the source, which can't recover the objects that have been deleted or
+    /// wrapped since the fork checkpoint.
+    ///
+    /// Objects, packages and the system state are fetched from `source` as they are read, at the
+    /// version they had at the fork checkpoint (see [`ForkingStore`]), while transactions are
+    /// executed and checkpoints created locally, on top of a stand-in for the checkpoint the fork
+    /// starts from. That checkpoint keeps the sequence number and timestamp of the original, but
+    /// its contents are not fetched.
+    ///
+    /// The validators of the source network are replaced by a single local validator which signs
+    /// checkpoints, and the accounts in [`Simulacrum::keystore`] are funded with the gas coins they
+    /// would get in a fresh genesis.
+    ///
+    /// This must be called from a multi-threaded tokio runtime, which is used to query `source`.
+    #[allow(clippy::disallowed_methods)]
+    pub fn new_fork(
+        source: Fetchers,
+        checkpoint: Option<CheckpointSequenceNumber>,
+        mut rng: R,
+    ) -> Result<Self> {
+        // A local network provides the validator and the accounts the fork is driven with.
+        let config = ConfigBuilder::new_with_temp_dir()
+            .rng(&mut rng)
+            .deterministic_committee_size(NonZeroUsize::new(1).unwrap())
+            .build();
+        let keystore = KeyStore::from_network_config(&config);
+
+        let chain_identifier = fetch_chain_identifier(&source)?;
+        let latest_checkpoint = block_on(source.get_latest_checkpoint_sequence_number())
+            .map_err(|e| anyhow!("failed to fetch the latest checkpoint: {e}"))?;
+        let fork_checkpoint = match checkpoint {
+            None => latest_checkpoint,
+            Some(checkpoint) if checkpoint == latest_checkpoint => checkpoint,
+            Some(checkpoint) if matches!(source, Fetchers::Archive(_)) => bail!(
+                "The archive is reconstructed up to checkpoint {latest_checkpoint}, so it can't \
+                 be forked at checkpoint {checkpoint}. Reconstruct it up to checkpoint \
+                 {checkpoint} instead."
+            ),
+            Some(checkpoint) => bail!(
+                "Only archives can be forked at a historical checkpoint: this source can only be \
+                 forked at its latest checkpoint ({latest_checkpoint}), not at {checkpoint}."
+            ),
+        };
+        let fork_point = block_on(source.get_checkpoint(fork_checkpoint))
+            .map_err(|e| anyhow!("failed to fetch checkpoint {fork_checkpoint}: {e}"))?;
+
+        let mut store = ForkingStore::new(source, fork_checkpoint);
+        let system_state = store.get_system_state();
+        let epoch = system_state.epoch();
+        let committee = Committee::new(
+            epoch,
+            config
+                .genesis
+                .committee()?
+                .voting_rights
+                .into_iter()
+                .collect(),
+        );
+        let epoch_state = EpochState::new_for_chain(system_state, chain_identifier.chain())
+            .with_committee(committee.clone());
+
+        // The system state is read as of the end of the fork checkpoint, which is in the next
+        // epoch if the fork checkpoint ends its epoch, so the epoch of the fork comes from the
+        // system state rather than from the checkpoint.
+        let epoch_rolling_gas_cost_summary = if fork_point.epoch == epoch {
+            fork_point.epoch_rolling_gas_cost_summary
+        } else {
+            Default::default()
+        };
+        let contents = CheckpointContents::new_with_digests_only_for_tests([]);
+        let summary = CheckpointSummary {
+            epoch,
+            sequence_number: fork_point.sequence_number,
+            network_total_transactions: fork_point.network_total_transactions,
+            content_digest: *contents.digest(),
+            previous_digest: fork_point.previous_digest,
+            epoch_rolling_gas_cost_summary,
+            end_of_epoch_data: None,
+            timestamp_ms: fork_point.timestamp_ms,
+            version_specific_data: bcs::to_bytes(&CheckpointVersionSpecificData::empty_for_tests())
+                .unwrap(),
+            checkpoint_commitments: Default::default(),
+        };
+        let checkpoint = MockCheckpointBuilder::create_certified_checkpoint(
+            &CommitteeWithKeys::new(&keystore, &committee),
+            summary,
+        );
+
+        store.insert_committee(committee);
+        store.insert_checkpoint(checkpoint.clone());
+        store.insert_checkpoint_contents(contents);
+        let gas_coins = config
+            .genesis
+            .objects()
+            .iter()
+            .filter(|object| {
+                object.is_gas_coin()
+                    && matches!(object.owner, Owner::AddressOwner(owner)
+                        if keystore.accounts().any(|(address, _)| *address == owner))
+            })
+            .map(|object| (object.id(), object.clone()))
+            .collect();
+        store.update_objects(gas_coins, vec![]);
+
+        Ok(Self {
+            rng,
+            keystore,
+            genesis: config.genesis.clone(),
+            store,
+            checkpoint_builder: MockCheckpointBuilder::new(checkpoint),
+            epoch_state,
+            deny_config: TransactionDenyConfig::default(),
+            verifier_signing_config: VerifierSigningConfig::default(),
+            data_ingestion_path: None,
+            chain_identifier,
+            fork_checkpoint: Some(fork_checkpoint),
+        })
+    }
+}
+
+/// Identifies the chain `source` belongs to from its short chain id, falling back to the digest of
+/// its genesis checkpoint for chains other than mainnet and testnet.
+#[allow(clippy::disallowed_methods)]
+fn fetch_chain_identifier(source: &Fetchers) -> Result<ChainIdentifier> {
+    let chain_id = block_on(source.get_chain_id())
+        .map_err(|e| anyhow!("failed to fetch the chain id: {e}"))?;
+    if let Some(chain_identifier) = ChainIdentifier::from_chain_short_id(&chain_id) {
+        return Ok(chain_identifier);
+    }
+
+    let genesis = block_on(source.get_checkpoint(0))
+        .map_err(|e| anyhow!("failed to fetch the genesis checkpoint: {e}"))?;
+    Ok(ChainIdentifier::from(genesis.digest))
+}
+
 impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
     pub fn new_with_network_config_store(config: &NetworkConfig, rng: R, store: S) -> Self {
         let keystore = KeyStore::from_network_config(config);
@@ -161,6 +319,8 @@ impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
             deny_config: TransactionDenyConfig::default(),
             verifier_signing_config: VerifierSigningConfig::default(),
             data_ingestion_path: None,
+            chain_identifier: ChainIdentifier::from(*genesis.checkpoint().digest()),
+            fork_checkpoint: None,
         }
     }
 
@@ -207,6 +367,23 @@ impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
         Ok((effects, execution_error_opt.err()))
     }
 
+    /// Executes the provided TransactionData without signatures and without committing its
+    /// effects, which are returned along with the objects it read and wrote.
+    ///
+    /// The transaction undergoes the same input checks as in [`Simulacrum::execute_transaction`],
+    /// except that it can omit gas coins, in which case it is paid for with a mock coin.
+    pub fn simulate_transaction(
+        &self,
+        transaction: TransactionData,
+    ) -> anyhow::Result<SimulateTransactionResult> {
+        self.epoch_state.simulate_transaction(
+            &self.store,
+            &self.deny_config,
+            &self.verifier_signing_config,
+            transaction,
+        )
+    }
+
     /// Creates the next Checkpoint using the Transactions enqueued since the last checkpoint was
     /// created.
     pub fn create_checkpoint(&mut self) -> VerifiedCheckpoint {
@@ -284,7 +461,21 @@ impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
         self.execute_transaction(tx.into())
             .expect("advancing the epoch cannot fail");
 
-        let new_epoch_state = EpochState::new(self.store.get_system_state());
+        let mut new_epoch_state =
+            EpochState::new_for_chain(self.store.get_system_state(), self.epoch_state.chain());
+        if self.fork_checkpoint.is_some() {
+            // The validators of the source network are not around to sign checkpoints, so the
+            // local ones carry on into the next epoch.
+            new_epoch_state = new_epoch_state.with_committee(Committee::new(
+                next_epoch,
+                self.epoch_state
+                    .committee()
+                    .voting_rights
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ));
+        }
         let end_of_epoch_data = EndOfEpochData {
             next_epoch_committee: new_epoch_state.committee().voting_rights.clone(),
             next_epoch_protocol_version,
@@ -316,6 +507,12 @@ impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
         self.epoch_state.epoch_start_state()
     }
 
+    /// The checkpoint of the source network this Simulacrum was forked at, or `None` if it was
+    /// started from its own genesis.
+    pub fn fork_checkpoint(&self) -> Option<CheckpointSequenceNumber> {
+        self.fork_checkpoint
+    }
+
     /// Return a handle to the internally held RNG.
     ///
     /// Returns a handle to the RNG used to create this Simulacrum for use as a source of
@@ -420,7 +617,10 @@ impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
 
     pub fn set_data_ingestion_path(&mut self, data_ingestion_path: PathBuf) {
         self.data_ingestion_path = Some(data_ingestion_path);
-        let checkpoint = self.store.get_checkpoint_by_sequence_number(0).unwrap();
+        let checkpoint = self
+            .store
+            .get_checkpoint_by_sequence_number(self.fork_checkpoint.unwrap_or(0))
+            .unwrap();
         let contents = self
             .store
             .get_checkpoint_contents(&checkpoint.content_digest);
@@ -491,9 +691,9 @@ impl<T, V: store::SimulatorStore> ObjectStore for Simulacrum<T, V> {
 impl<T, V: store::SimulatorStore> ReadStore for Simulacrum<T, V> {
     fn get_committee(
         &self,
-        _epoch: sui_types::committee::EpochId,
+        epoch: sui_types::committee::EpochId,
     ) -> Option<std::sync::Arc<Committee>> {
-        todo!()
+        self.store().get_committee_by_epoch(epoch).map(Arc::new)
     }
 
     fn get_latest_checkpoint(&self) -> sui_types::storage::error::Result<VerifiedCheckpoint> {
@@ -503,13 +703,14 @@ impl<T, V: store::SimulatorStore> ReadStore for Simulacrum<T, V> {
     fn get_highest_verified_checkpoint(
         &self,
     ) -> sui_types::storage::error::Result<VerifiedCheckpoint> {
-        todo!()
+        // Every checkpoint is created, and therefore verified and synced, locally
+        self.get_latest_checkpoint()
     }
 
     fn get_highest_synced_checkpoint(
         &self,
     ) -> sui_types::storage::error::Result<VerifiedCheckpoint> {
-        todo!()
+        self.get_latest_checkpoint()
     }
 
     fn get_lowest_available_checkpoint(
@@ -518,7 +719,7 @@ impl<T, V: store::SimulatorStore> ReadStore for Simulacrum<T, V> {
     {
         // TODO wire this up to the underlying sim store, for now this will work since we never
         // prune the sim store
-        Ok(0)
+        Ok(self.fork_checkpoint.unwrap_or(0))
     }
 
     fn get_checkpoint_by_digest(
@@ -545,9 +746,13 @@ impl<T, V: store::SimulatorStore> ReadStore for Simulacrum<T, V> {
 
     fn get_checkpoint_contents_by_sequence_number(
         &self,
-        _sequence_number: sui_types::messages_checkpoint::CheckpointSequenceNumber,
+        sequence_number: sui_types::messages_checkpoint::CheckpointSequenceNumber,
     ) -> Option<sui_types::messages_checkpoint::CheckpointContents> {
-        todo!()
+        let checkpoint = self
+            .store()
+            .get_checkpoint_by_sequence_number(sequence_number)?;
+        self.store()
+            .get_checkpoint_contents(&checkpoint.content_digest)
     }
 
     fn get_transaction(
@@ -573,16 +778,18 @@ impl<T, V: store::SimulatorStore> ReadStore for Simulacrum<T, V> {
 
     fn get_full_checkpoint_contents_by_sequence_number(
         &self,
-        _sequence_number: sui_types::messages_checkpoint::CheckpointSequenceNumber,
+        sequence_number: sui_types::messages_checkpoint::CheckpointSequenceNumber,
     ) -> Option<sui_types::messages_checkpoint::FullCheckpointContents> {
-        todo!()
+        let contents = self.get_checkpoint_contents_by_sequence_number(sequence_number)?;
+        FullCheckpointContents::from_checkpoint_contents(self, contents)
     }
 
     fn get_full_checkpoint_contents(
         &self,
-        _digest: &sui_types::messages_checkpoint::CheckpointContentsDigest,
+        digest: &sui_types::messages_checkpoint::CheckpointContentsDigest,
     ) -> Option<sui_types::messages_checkpoint::FullCheckpointContents> {
-        todo!()
+        let contents = self.store().get_checkpoint_contents(digest)?;
+        FullCheckpointContents::from_checkpoint_contents(self, contents)
     }
 }
 
@@ -590,19 +797,13 @@ impl<T: Send + Sync, V: store::SimulatorStore + Send + Sync> RpcStateReader for
     fn get_lowest_available_checkpoint_objects(
         &self,
     ) -> sui_types::storage::error::Result<CheckpointSequenceNumber> {
-        Ok(0)
+        Ok(self.fork_checkpoint.unwrap_or(0))
     }
 
     fn get_chain_identifier(
         &self,
     ) -> sui_types::storage::error::Result<sui_types::digests::ChainIdentifier> {
-        Ok(self
-            .store()
-            .get_checkpoint_by_sequence_number(0)
-            .unwrap()
-            .digest()
-            .to_owned()
-            .into())
+        Ok(self.chain_identifier)
     }
 
     fn indexes(&self) -> Option<&dyn sui_types::storage::RpcIndexes> {
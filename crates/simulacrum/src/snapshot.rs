@@ -0,0 +1,384 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Snapshots of a [`Simulacrum`]'s state.
+//!
+//! A snapshot captures everything needed to recreate a Simulacrum that behaves exactly like the
+//! original from the point the snapshot was taken: the contents of its store, the state of the
+//! current epoch (which includes the on-chain clock, as an object), its RNG, its keys, the
+//! transactions waiting to be included in the next checkpoint, and the configs it checks
+//! transactions against. This allows test fixtures, which are expensive to set-up (e.g. by
+//! publishing packages), to be built once and then restored for each test, either from disk, via
+//! [`SimulacrumSnapshot`], or in memory, via [`Simulacrum::branch`].
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rand::rngs::OsRng;
+use rand_chacha::{ChaCha12Rng, ChaCha20Rng, ChaCha8Rng};
+use serde::{Deserialize, Serialize};
+use sui_config::genesis::Genesis;
+use sui_config::verifier_signing_config::VerifierSigningConfig;
+use sui_types::base_types::{ExecutionData, VerifiedExecutionData};
+use sui_types::digests::ChainIdentifier;
+use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, VerifiedCheckpoint};
+use sui_types::mock_checkpoint_builder::MockCheckpointBuilder;
+use tracing::trace;
+
+use crate::epoch_state::{EpochState, EpochStateSnapshot};
+use crate::store::in_mem_store::{InMemoryStoreSnapshot, KeyStore, KeyStoreSnapshot};
+use crate::{InMemoryStore, Simulacrum};
+
+/// A source of randomness for a [`Simulacrum`] whose state can be captured in a snapshot.
+///
+/// This is implemented for the ChaCha RNGs, whose state can be read without disturbing it, but not
+/// for [`StdRng`](rand::rngs::StdRng), whose state is hidden. Simulacrums that need to be
+/// snapshotted should use [`ChaCha12Rng`], which is what `StdRng` is currently based on.
+pub trait SnapshotRng: Sized {
+    /// Capture the state of the RNG, without advancing it. The RNG and the one that
+    /// [`SnapshotRng::restore`] recreates from this state produce the same values from here on.
+    /// Returns `None` for RNGs that have no state.
+    fn snapshot(&self) -> Option<RngState>;
+
+    /// Recreate the RNG from a state returned by [`SnapshotRng::snapshot`].
+    fn restore(state: Option<RngState>) -> Result<Self>;
+}
+
+/// The state of a ChaCha RNG: its seed, and its position in its output stream.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RngState {
+    seed: [u8; 32],
+    stream: u64,
+    word_pos: u128,
+}
+
+/// The state of a [`Simulacrum`] backed by an [`InMemoryStore`], which can be saved to disk and
+/// restored with [`Simulacrum::from_snapshot`].
+///
+/// Snapshots do not include the Simulacrum's data ingestion path, as two Simulacrums writing
+/// checkpoints to the same directory would overwrite each other's.
+#[derive(Serialize, Deserialize)]
+pub struct SimulacrumSnapshot {
+    rng_state: Option<RngState>,
+    keystore: KeyStoreSnapshot,
+    genesis: Genesis,
+    store: InMemoryStoreSnapshot,
+    checkpoint_builder: CheckpointBuilderSnapshot,
+    epoch_state: EpochStateSnapshot,
+    chain_identifier: ChainIdentifier,
+    /// The transaction deny config, as YAML: its serialized form omits empty lists, which BCS
+    /// can't read back.
+    deny_config: String,
+    verifier_signing_config: VerifierSigningConfig,
+}
+
+/// The state of a [`MockCheckpointBuilder`]: the last checkpoint it built, and the transactions
+/// executed since then.
+#[derive(Serialize, Deserialize)]
+struct CheckpointBuilderSnapshot {
+    previous_checkpoint: Option<CertifiedCheckpointSummary>,
+    transactions: Vec<ExecutionData>,
+    epoch_rolling_gas_cost_summary: GasCostSummary,
+    epoch: u64,
+}
+
+impl SimulacrumSnapshot {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        trace!("Reading Simulacrum snapshot from {}", path.display());
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Unable to load snapshot from {}", path.display()))?;
+        bcs::from_bytes(&bytes)
+            .with_context(|| format!("Unable to parse snapshot from {}", path.display()))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        trace!("Writing Simulacrum snapshot to {}", path.display());
+        let bytes = bcs::to_bytes(&self)?;
+        std::fs::write(path, bytes)
+            .with_context(|| format!("Unable to save snapshot to {}", path.display()))?;
+        Ok(())
+    }
+}
+
+impl<R: SnapshotRng> Simulacrum<R, InMemoryStore> {
+    /// Capture the state of this Simulacrum. The Simulacrum and any Simulacrum restored from the
+    /// snapshot evolve identically if they are driven the same way.
+    pub fn snapshot(&self) -> SimulacrumSnapshot {
+        let builder = &self.checkpoint_builder;
+        let checkpoint_builder = CheckpointBuilderSnapshot {
+            previous_checkpoint: builder
+                .previous_checkpoint()
+                .map(|checkpoint| checkpoint.clone().into_inner()),
+            transactions: builder
+                .pending_transactions()
+                .iter()
+                .map(|data| data.clone().into_inner())
+                .collect(),
+            epoch_rolling_gas_cost_summary: builder.epoch_rolling_gas_cost_summary().clone(),
+            epoch: builder.epoch(),
+        };
+
+        SimulacrumSnapshot {
+            rng_state: self.rng.snapshot(),
+            keystore: self.keystore.snapshot(),
+            genesis: self.genesis.clone(),
+            store: self.store.snapshot(),
+            checkpoint_builder,
+            epoch_state: self.epoch_state.snapshot(),
+            chain_identifier: self.chain_identifier,
+            deny_config: serde_yaml::to_string(&self.deny_config)
+                .expect("TransactionDenyConfig can always be serialized"),
+            verifier_signing_config: self.verifier_signing_config.clone(),
+        }
+    }
+
+    /// Recreate a Simulacrum from a snapshot taken by [`Simulacrum::snapshot`].
+    pub fn from_snapshot(snapshot: SimulacrumSnapshot) -> Result<Self> {
+        let SimulacrumSnapshot {
+            rng_state,
+            keystore,
+            genesis,
+            store,
+            checkpoint_builder,
+            epoch_state,
+            chain_identifier,
+            deny_config,
+            verifier_signing_config,
+        } = snapshot;
+
+        let CheckpointBuilderSnapshot {
+            previous_checkpoint,
+            transactions,
+            epoch_rolling_gas_cost_summary,
+            epoch,
+        } = checkpoint_builder;
+
+        let checkpoint_builder = MockCheckpointBuilder::from_parts(
+            previous_checkpoint.map(VerifiedCheckpoint::new_unchecked),
+            transactions
+                .into_iter()
+                .map(VerifiedExecutionData::new_unchecked)
+                .collect(),
+            epoch_rolling_gas_cost_summary,
+            epoch,
+        );
+
+        Ok(Self {
+            rng: R::restore(rng_state).context("Failed to restore RNG")?,
+            keystore: KeyStore::from_snapshot(keystore).context("Failed to restore keys")?,
+            genesis,
+            store: InMemoryStore::from_snapshot(store),
+            checkpoint_builder,
+            epoch_state: EpochState::from_snapshot(epoch_state),
+            deny_config: serde_yaml::from_str(&deny_config)
+                .context("Failed to restore transaction deny config")?,
+            data_ingestion_path: None,
+            verifier_signing_config,
+            chain_identifier,
+            fork_checkpoint: None,
+        })
+    }
+}
+
+impl<R: Clone> Simulacrum<R, InMemoryStore> {
+    /// Create an independent copy of this Simulacrum in memory, without going through a
+    /// [`SimulacrumSnapshot`]. Transactions executed against one copy are not visible to the other.
+    ///
+    /// The copy shares the contents of this Simulacrum's store, rather than copying them (see
+    /// [`InMemoryStore`]), and starts with a clone of its RNG. As with [`Simulacrum::snapshot`], the
+    /// copy does not inherit the data ingestion path.
+    pub fn branch(&self) -> Self {
+        Self {
+            rng: self.rng.clone(),
+            keystore: self.keystore.clone(),
+            genesis: self.genesis.clone(),
+            store: self.store.clone(),
+            checkpoint_builder: self.checkpoint_builder.clone(),
+            epoch_state: self.epoch_state.clone(),
+            deny_config: self.deny_config.clone(),
+            data_ingestion_path: None,
+            verifier_signing_config: self.verifier_signing_config.clone(),
+            chain_identifier: self.chain_identifier,
+            fork_checkpoint: self.fork_checkpoint,
+        }
+    }
+}
+
+macro_rules! chacha_snapshot_rng {
+    ($($rng:ty),*) => {$(
+        impl SnapshotRng for $rng {
+            fn snapshot(&self) -> Option<RngState> {
+                Some(RngState {
+                    seed: self.get_seed(),
+                    stream: self.get_stream(),
+                    word_pos: self.get_word_pos(),
+                })
+            }
+
+            fn restore(state: Option<RngState>) -> Result<Self> {
+                let RngState {
+                    seed,
+                    stream,
+                    word_pos,
+                } = state.context(concat!("Missing state for ", stringify!($rng)))?;
+
+                // The position is set last, because that is what refills the RNG's buffered output,
+                // which depends on the stream.
+                let mut rng = <$rng as rand::SeedableRng>::from_seed(seed);
+                rng.set_stream(stream);
+                rng.set_word_pos(word_pos);
+                Ok(rng)
+            }
+        }
+    )*};
+}
+
+chacha_snapshot_rng!(ChaCha8Rng, ChaCha12Rng, ChaCha20Rng);
+
+impl SnapshotRng for OsRng {
+    fn snapshot(&self) -> Option<RngState> {
+        None
+    }
+
+    fn restore(_state: Option<RngState>) -> Result<Self> {
+        Ok(OsRng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rand::{rngs::StdRng, SeedableRng};
+    use sui_config::transaction_deny_config::TransactionDenyConfigBuilder;
+    use sui_types::{
+        base_types::SuiAddress, effects::TransactionEffectsAPI, gas_coin::MIST_PER_SUI,
+    };
+
+    use super::*;
+    use crate::SimulatorStore;
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let mut sim = Simulacrum::new_with_rng(ChaCha12Rng::from_seed([1; 32]));
+        sim.advance_clock(Duration::from_millis(10));
+        sim.create_checkpoint();
+
+        // Leave a transaction waiting to be checkpointed.
+        let (_, _, gas) = sim.funded_account(MIST_PER_SUI).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot");
+        sim.snapshot().save(&path).unwrap();
+        let mut restored =
+            Simulacrum::<ChaCha12Rng>::from_snapshot(SimulacrumSnapshot::load(&path).unwrap())
+                .unwrap();
+
+        assert_eq!(
+            sim.store().get_clock().timestamp_ms(),
+            restored.store().get_clock().timestamp_ms(),
+        );
+        assert_eq!(
+            SimulatorStore::get_object(sim.store(), &gas.0),
+            SimulatorStore::get_object(restored.store(), &gas.0),
+        );
+
+        // The pending transaction and the state of the RNG carry over, so both chains evolve
+        // identically from here on.
+        assert_eq!(
+            sim.create_checkpoint().digest(),
+            restored.create_checkpoint().digest(),
+        );
+
+        let address = SuiAddress::generate(sim.rng());
+        let restored_address = SuiAddress::generate(restored.rng());
+        assert_eq!(address, restored_address);
+
+        let effects = sim.request_gas(address, MIST_PER_SUI).unwrap();
+        let restored_effects = restored.request_gas(address, MIST_PER_SUI).unwrap();
+        assert_eq!(effects, restored_effects);
+
+        sim.advance_epoch(/* create_random_state */ false);
+        restored.advance_epoch(/* create_random_state */ false);
+        assert_eq!(
+            sim.store().get_highest_checkpint().unwrap().digest(),
+            restored.store().get_highest_checkpint().unwrap().digest(),
+        );
+    }
+
+    #[test]
+    fn snapshot_keeps_configs() {
+        let mut sim = Simulacrum::new_with_rng(ChaCha12Rng::from_seed([4; 32]));
+        let denied = SuiAddress::random_for_testing_only();
+        sim.deny_config = TransactionDenyConfigBuilder::new()
+            .add_denied_address(denied)
+            .disable_package_publish()
+            .build();
+        sim.verifier_signing_config =
+            serde_yaml::from_str("max-per-fun-meter-units: 1000").unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot");
+        sim.snapshot().save(&path).unwrap();
+        let restored =
+            Simulacrum::<ChaCha12Rng>::from_snapshot(SimulacrumSnapshot::load(&path).unwrap())
+                .unwrap();
+
+        assert!(restored
+            .deny_config
+            .get_address_deny_set()
+            .contains(&denied));
+        assert!(restored.deny_config.package_publish_disabled());
+        assert!(restored.deny_config.get_object_deny_set().is_empty());
+        assert_eq!(
+            restored.verifier_signing_config.max_per_fun_meter_units(),
+            1000
+        );
+    }
+
+    #[test]
+    fn snapshot_does_not_disturb_rng() {
+        let mut sim = Simulacrum::new_with_rng(ChaCha12Rng::from_seed([3; 32]));
+        let mut control = Simulacrum::new_with_rng(ChaCha12Rng::from_seed([3; 32]));
+
+        let rng_state = sim.rng().snapshot();
+        sim.snapshot();
+
+        assert_eq!(sim.rng().snapshot(), rng_state);
+        assert_eq!(
+            SuiAddress::generate(sim.rng()),
+            SuiAddress::generate(control.rng()),
+        );
+    }
+
+    #[test]
+    fn branch_is_independent() {
+        let mut sim = Simulacrum::new_with_rng(StdRng::from_seed([2; 32]));
+        let mut branch = sim.branch();
+
+        // The branch starts with the same randomness as the original.
+        assert_eq!(
+            SuiAddress::generate(sim.rng()),
+            SuiAddress::generate(branch.rng()),
+        );
+
+        let recipient = SuiAddress::random_for_testing_only();
+        let effects = branch.request_gas(recipient, MIST_PER_SUI).unwrap();
+        assert!(effects.status().is_ok());
+
+        assert_eq!(sim.store().owned_objects(recipient).count(), 0);
+        assert_eq!(branch.store().owned_objects(recipient).count(), 1);
+
+        // Both copies carry on checkpointing from the same place.
+        let checkpoint = sim.create_checkpoint();
+        let branch_checkpoint = branch.create_checkpoint();
+        assert_eq!(
+            checkpoint.sequence_number,
+            branch_checkpoint.sequence_number
+        );
+        assert_ne!(checkpoint.digest(), branch_checkpoint.digest());
+    }
+}
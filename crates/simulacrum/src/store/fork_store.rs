@@ -0,0 +1,941 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::executor::block_on;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::RwLock;
+use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
+use sui_replay::data_fetcher::DataFetcher;
+use sui_replay::types::ReplayEngineError;
+use sui_types::storage::{load_package_object_from_object_store, PackageObject};
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber, SuiAddress},
+    committee::{Committee, EpochId},
+    digests::{ObjectDigest, TransactionDigest},
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    error::SuiError,
+    messages_checkpoint::{
+        CheckpointContents, CheckpointContentsDigest, CheckpointDigest, CheckpointSequenceNumber,
+        VerifiedCheckpoint,
+    },
+    object::{Object, Owner},
+    storage::{BackingPackageStore, ChildObjectResolver, ObjectStore, ParentSync},
+    transaction::VerifiedTransaction,
+};
+use tracing::{error, warn};
+
+use super::SimulatorStore;
+
+/// A store that forks the state of a live network.
+///
+/// Checkpoints, transactions and objects written locally are kept in memory, like in
+/// [`InMemoryStore`](super::in_mem_store::InMemoryStore). Objects that have not been written
+/// locally are fetched from the `source` network the first time they are read, and cached from
+/// then on.
+///
+/// Objects are fetched at the version they had as of the fork checkpoint: if the latest version
+/// on the source was written after the fork checkpoint, the store walks back through the effects
+/// of the transactions that wrote it, to the version it had at the fork. Objects that were created
+/// after the fork checkpoint are treated as missing. Objects deleted or wrapped on the source after
+/// the fork checkpoint can't be recovered this way, and are treated as missing as well.
+///
+/// Fetches block on the source, which is queried through the tokio runtime the store is used from.
+/// That runtime must be multi-threaded, so that the fetch can make progress while the thread
+/// reading from the store is blocked.
+pub struct ForkingStore {
+    source: Box<dyn DataFetcher + Send + Sync>,
+    /// The checkpoint of the source network the store was forked at.
+    fork_checkpoint: CheckpointSequenceNumber,
+
+    // Checkpoint data
+    checkpoints: BTreeMap<CheckpointSequenceNumber, VerifiedCheckpoint>,
+    checkpoint_digest_to_sequence_number: HashMap<CheckpointDigest, CheckpointSequenceNumber>,
+    checkpoint_contents: HashMap<CheckpointContentsDigest, CheckpointContents>,
+
+    // Transaction data
+    transactions: HashMap<TransactionDigest, VerifiedTransaction>,
+    effects: HashMap<TransactionDigest, TransactionEffects>,
+    events: HashMap<TransactionDigest, TransactionEvents>,
+
+    // Committee data. A fork starts in the middle of the history of the source network, so there
+    // are no committees for the epochs before it.
+    epoch_to_committee: BTreeMap<EpochId, Committee>,
+
+    // Object data, which is filled in by reads as well as writes.
+    objects: RwLock<ObjectCache>,
+}
+
+#[derive(Default)]
+struct ObjectCache {
+    live_objects: HashMap<ObjectID, SequenceNumber>,
+    objects: HashMap<ObjectID, BTreeMap<SequenceNumber, Object>>,
+    /// Objects that were deleted or wrapped locally, and must not be fetched from the source again.
+    removed: HashSet<ObjectID>,
+    /// Objects the source doesn't know about, so that they are only looked up once.
+    missing: HashSet<ObjectID>,
+}
+
+impl ObjectCache {
+    fn get_object(&self, id: &ObjectID) -> Option<&Object> {
+        let version = self.live_objects.get(id)?;
+        self.get_object_at_version(id, *version)
+    }
+
+    fn get_object_at_version(&self, id: &ObjectID, version: SequenceNumber) -> Option<&Object> {
+        self.objects
+            .get(id)
+            .and_then(|versions| versions.get(&version))
+    }
+
+    /// Whether `id` can't be found locally or on the source.
+    fn is_unavailable(&self, id: &ObjectID) -> bool {
+        self.removed.contains(id) || self.missing.contains(id)
+    }
+
+    /// Caches an object fetched from the source. The object becomes the live version if `live` is
+    /// set and the object hasn't been written locally.
+    fn insert_fetched(&mut self, object: Object, live: bool) {
+        let id = object.id();
+        let version = object.version();
+        if live && !self.removed.contains(&id) {
+            self.live_objects.entry(id).or_insert(version);
+        }
+        self.objects
+            .entry(id)
+            .or_default()
+            .entry(version)
+            .or_insert(object);
+    }
+
+    /// Records that the source doesn't know about `id`, unless the object was loaded in the
+    /// meantime.
+    fn insert_missing(&mut self, id: ObjectID) {
+        if !self.live_objects.contains_key(&id) {
+            self.missing.insert(id);
+        }
+    }
+}
+
+impl ForkingStore {
+    /// Create a store that forks `source` at `fork_checkpoint`.
+    pub fn new(
+        source: impl DataFetcher + Send + Sync + 'static,
+        fork_checkpoint: CheckpointSequenceNumber,
+    ) -> Self {
+        Self {
+            source: Box::new(source),
+            fork_checkpoint,
+            checkpoints: BTreeMap::new(),
+            checkpoint_digest_to_sequence_number: HashMap::new(),
+            checkpoint_contents: HashMap::new(),
+            transactions: HashMap::new(),
+            effects: HashMap::new(),
+            events: HashMap::new(),
+            epoch_to_committee: BTreeMap::new(),
+            objects: RwLock::new(ObjectCache::default()),
+        }
+    }
+
+    /// The network this store is forked from.
+    pub fn source(&self) -> &(dyn DataFetcher + Send + Sync) {
+        self.source.as_ref()
+    }
+
+    /// The checkpoint of the source network this store was forked at.
+    pub fn fork_checkpoint(&self) -> CheckpointSequenceNumber {
+        self.fork_checkpoint
+    }
+
+    pub fn get_checkpoint_by_sequence_number(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Option<&VerifiedCheckpoint> {
+        self.checkpoints.get(&sequence_number)
+    }
+
+    pub fn get_checkpoint_by_digest(
+        &self,
+        digest: &CheckpointDigest,
+    ) -> Option<&VerifiedCheckpoint> {
+        self.checkpoint_digest_to_sequence_number
+            .get(digest)
+            .and_then(|sequence_number| self.get_checkpoint_by_sequence_number(*sequence_number))
+    }
+
+    pub fn get_highest_checkpint(&self) -> Option<&VerifiedCheckpoint> {
+        self.checkpoints
+            .last_key_value()
+            .map(|(_, checkpoint)| checkpoint)
+    }
+
+    pub fn get_checkpoint_contents(
+        &self,
+        digest: &CheckpointContentsDigest,
+    ) -> Option<&CheckpointContents> {
+        self.checkpoint_contents.get(digest)
+    }
+
+    pub fn get_committee_by_epoch(&self, epoch: EpochId) -> Option<&Committee> {
+        self.epoch_to_committee.get(&epoch)
+    }
+
+    pub fn get_transaction(&self, digest: &TransactionDigest) -> Option<&VerifiedTransaction> {
+        self.transactions.get(digest)
+    }
+
+    pub fn get_transaction_effects(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Option<&TransactionEffects> {
+        self.effects.get(digest)
+    }
+
+    pub fn get_transaction_events(&self, digest: &TransactionDigest) -> Option<&TransactionEvents> {
+        self.events.get(digest)
+    }
+
+    /// Returns the live version of the object, fetching the version it had at the fork checkpoint
+    /// from the source if the object hasn't been seen locally.
+    pub fn get_object(&self, id: &ObjectID) -> Option<Object> {
+        {
+            let cache = self.objects.read().unwrap();
+            if let Some(object) = cache.get_object(id) {
+                return Some(object.clone());
+            }
+            if cache.is_unavailable(id) {
+                return None;
+            }
+        }
+
+        // The cache is not locked while the source is queried, so that reads of other objects
+        // are not held up by the fetch.
+        let fetched = match self.fetch_at_fork_checkpoint(id) {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                // Failing to reach the source is not cached as a miss, so that the object is
+                // fetched again the next time it is read.
+                error!(
+                    "Failed to load object {id} from the fork source, treating it as missing: {e}"
+                );
+                return None;
+            }
+        };
+
+        let mut cache = self.objects.write().unwrap();
+        match fetched {
+            Some(object) => {
+                cache.insert_fetched(object, /* live */ true);
+                cache.get_object(id).cloned()
+            }
+            None => {
+                cache.insert_missing(*id);
+                None
+            }
+        }
+    }
+
+    /// Fetches the version of the object that was live at the fork checkpoint, or `None` if the
+    /// object did not exist (or no longer exists) on the source.
+    // Objects are read by synchronous execution code, so fetches have to block on the source.
+    #[allow(clippy::disallowed_methods, clippy::result_large_err)]
+    fn fetch_at_fork_checkpoint(&self, id: &ObjectID) -> Result<Option<Object>, ReplayEngineError> {
+        let Some(mut object) = self.fetch(id, block_on(self.source.multi_get_latest(&[*id])))?
+        else {
+            return Ok(None);
+        };
+
+        loop {
+            let response = match block_on(self.source.get_transaction(&object.previous_transaction))
+            {
+                Ok(response) => response,
+                // Archives only contain the transactions that were executed after the snapshot
+                // they start from, and they end at the fork checkpoint, so the transaction
+                // happened before the fork.
+                Err(ReplayEngineError::TransactionNotInArchive { .. }) => return Ok(Some(object)),
+                Err(e) => {
+                    warn!("Failed to fetch the transaction that wrote object {id}: {e}");
+                    return Err(e);
+                }
+            };
+
+            // Transactions that are not in a checkpoint yet happened after the fork too.
+            if response
+                .checkpoint
+                .is_some_and(|checkpoint| checkpoint <= self.fork_checkpoint)
+            {
+                return Ok(Some(object));
+            }
+
+            // The version the transaction modified is the one the object had before it. If the
+            // transaction didn't modify the object, it created (or unwrapped) it after the fork.
+            let Some((_, version)) = response.effects.as_ref().and_then(|effects| {
+                effects
+                    .modified_at_versions()
+                    .into_iter()
+                    .find(|(modified, _)| modified == id)
+            }) else {
+                return Ok(None);
+            };
+
+            let response = block_on(self.source.multi_get_versioned(&[(*id, version)]));
+            let Some(previous) = self.fetch(id, response)? else {
+                return Ok(None);
+            };
+            object = previous;
+        }
+    }
+
+    #[allow(clippy::disallowed_methods)]
+    pub fn get_object_at_version(&self, id: &ObjectID, version: SequenceNumber) -> Option<Object> {
+        if let Some(object) = self
+            .objects
+            .read()
+            .unwrap()
+            .get_object_at_version(id, version)
+        {
+            return Some(object.clone());
+        }
+
+        let object = self
+            .fetch(
+                id,
+                block_on(self.source.multi_get_versioned(&[(*id, version)])),
+            )
+            .ok()??;
+        self.objects
+            .write()
+            .unwrap()
+            .insert_fetched(object.clone(), /* live */ false);
+        Some(object)
+    }
+
+    /// Returns the live version of a child object, fetching the latest version no greater than
+    /// `version_upper_bound` from the source if the object hasn't been seen locally.
+    #[allow(clippy::disallowed_methods)]
+    fn get_child_object(
+        &self,
+        id: &ObjectID,
+        version_upper_bound: SequenceNumber,
+    ) -> Option<Object> {
+        {
+            let cache = self.objects.read().unwrap();
+            if let Some(object) = cache.get_object(id) {
+                return Some(object.clone());
+            }
+            if cache.is_unavailable(id) {
+                return None;
+            }
+        }
+
+        // The bound is the version of the child's root object, which was itself loaded as of the
+        // fork checkpoint, so the child is as well.
+        let response = block_on(self.source.get_child_object(id, version_upper_bound));
+        let object = self.fetch(id, response.map(|object| vec![object])).ok()??;
+        let mut cache = self.objects.write().unwrap();
+        cache.insert_fetched(object, /* live */ true);
+        cache.get_object(id).cloned()
+    }
+
+    /// Unpacks the response to a request for a single object from the source, which is `None` if
+    /// the object doesn't exist on the source. Failures to fetch the object are logged.
+    #[allow(clippy::result_large_err)]
+    fn fetch(
+        &self,
+        id: &ObjectID,
+        response: Result<Vec<Object>, ReplayEngineError>,
+    ) -> Result<Option<Object>, ReplayEngineError> {
+        match response {
+            Ok(mut objects) => Ok(objects.pop()),
+            Err(ReplayEngineError::ObjectNotExist { .. })
+            | Err(ReplayEngineError::ObjectDeleted { .. }) => Ok(None),
+            Err(e) => {
+                warn!("Failed to fetch object {id} from the fork source: {e}");
+                Err(e)
+            }
+        }
+    }
+
+    pub fn get_system_state(&self) -> sui_types::sui_system_state::SuiSystemState {
+        sui_types::sui_system_state::get_sui_system_state(self).expect("system state must exist")
+    }
+
+    pub fn get_clock(&self) -> sui_types::clock::Clock {
+        self.get_object(&sui_types::SUI_CLOCK_OBJECT_ID)
+            .expect("clock should exist")
+            .to_rust()
+            .expect("clock object should deserialize")
+    }
+
+    /// The live objects owned by `owner`, out of the objects that have been loaded so far. Objects
+    /// the owner has on the source that haven't been read yet are not included.
+    pub fn owned_objects(&self, owner: SuiAddress) -> impl Iterator<Item = Object> {
+        let cache = self.objects.read().unwrap();
+        let objects: Vec<_> = cache
+            .live_objects
+            .iter()
+            .flat_map(|(id, version)| cache.get_object_at_version(id, *version))
+            .filter(|object| matches!(object.owner, Owner::AddressOwner(addr) if addr == owner))
+            .cloned()
+            .collect();
+        objects.into_iter()
+    }
+}
+
+impl ForkingStore {
+    pub fn insert_checkpoint(&mut self, checkpoint: VerifiedCheckpoint) {
+        if let Some(end_of_epoch_data) = &checkpoint.data().end_of_epoch_data {
+            let next_committee = end_of_epoch_data
+                .next_epoch_committee
+                .iter()
+                .cloned()
+                .collect();
+            let committee =
+                Committee::new(checkpoint.epoch().checked_add(1).unwrap(), next_committee);
+            self.insert_committee(committee);
+        }
+
+        self.checkpoint_digest_to_sequence_number
+            .insert(*checkpoint.digest(), *checkpoint.sequence_number());
+        self.checkpoints
+            .insert(*checkpoint.sequence_number(), checkpoint);
+    }
+
+    pub fn insert_checkpoint_contents(&mut self, contents: CheckpointContents) {
+        self.checkpoint_contents
+            .insert(*contents.digest(), contents);
+    }
+
+    pub fn insert_committee(&mut self, committee: Committee) {
+        self.epoch_to_committee
+            .entry(committee.epoch)
+            .or_insert(committee);
+    }
+
+    pub fn insert_executed_transaction(
+        &mut self,
+        transaction: VerifiedTransaction,
+        effects: TransactionEffects,
+        events: TransactionEvents,
+        written_objects: BTreeMap<ObjectID, Object>,
+    ) {
+        // Wrapped objects are removed as well as deleted ones, otherwise their previous version
+        // would be fetched from the source again.
+        let mut removed_objects = effects.deleted();
+        removed_objects.extend(effects.wrapped());
+        let tx_digest = *effects.transaction_digest();
+        self.insert_transaction(transaction);
+        self.insert_transaction_effects(effects);
+        self.insert_events(&tx_digest, events);
+        self.update_objects(written_objects, removed_objects);
+    }
+
+    pub fn insert_transaction(&mut self, transaction: VerifiedTransaction) {
+        self.transactions.insert(*transaction.digest(), transaction);
+    }
+
+    pub fn insert_transaction_effects(&mut self, effects: TransactionEffects) {
+        self.effects.insert(*effects.transaction_digest(), effects);
+    }
+
+    pub fn insert_events(&mut self, tx_digest: &TransactionDigest, events: TransactionEvents) {
+        self.events.insert(*tx_digest, events);
+    }
+
+    pub fn update_objects(
+        &mut self,
+        written_objects: BTreeMap<ObjectID, Object>,
+        deleted_objects: Vec<(ObjectID, SequenceNumber, ObjectDigest)>,
+    ) {
+        let cache = self.objects.get_mut().unwrap();
+        for (object_id, _, _) in deleted_objects {
+            cache.live_objects.remove(&object_id);
+            cache.removed.insert(object_id);
+        }
+
+        for (object_id, object) in written_objects {
+            let version = object.version();
+            cache.removed.remove(&object_id);
+            cache.missing.remove(&object_id);
+            cache.live_objects.insert(object_id, version);
+            cache
+                .objects
+                .entry(object_id)
+                .or_default()
+                .insert(version, object);
+        }
+    }
+}
+
+impl BackingPackageStore for ForkingStore {
+    fn get_package_object(
+        &self,
+        package_id: &ObjectID,
+    ) -> sui_types::error::SuiResult<Option<PackageObject>> {
+        load_package_object_from_object_store(self, package_id)
+    }
+}
+
+impl ChildObjectResolver for ForkingStore {
+    fn read_child_object(
+        &self,
+        parent: &ObjectID,
+        child: &ObjectID,
+        child_version_upper_bound: SequenceNumber,
+    ) -> sui_types::error::SuiResult<Option<Object>> {
+        let child_object = match self.get_child_object(child, child_version_upper_bound) {
+            None => return Ok(None),
+            Some(obj) => obj,
+        };
+
+        let parent = *parent;
+        if child_object.owner != Owner::ObjectOwner(parent.into()) {
+            return Err(SuiError::InvalidChildObjectAccess {
+                object: *child,
+                given_parent: parent,
+                actual_owner: child_object.owner.clone(),
+            });
+        }
+
+        if child_object.version() > child_version_upper_bound {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: "TODO ForkingStore::read_child_object does not yet support bounded reads"
+                    .to_owned(),
+            });
+        }
+
+        Ok(Some(child_object))
+    }
+
+    fn get_object_received_at_version(
+        &self,
+        owner: &ObjectID,
+        receiving_object_id: &ObjectID,
+        receive_object_at_version: SequenceNumber,
+        _epoch_id: EpochId,
+    ) -> sui_types::error::SuiResult<Option<Object>> {
+        let recv_object = match self.get_object(receiving_object_id) {
+            None => return Ok(None),
+            Some(obj) => obj,
+        };
+        if recv_object.owner != Owner::AddressOwner((*owner).into()) {
+            return Ok(None);
+        }
+
+        if recv_object.version() != receive_object_at_version {
+            return Ok(None);
+        }
+        Ok(Some(recv_object))
+    }
+}
+
+impl ObjectStore for ForkingStore {
+    fn get_object(&self, object_id: &ObjectID) -> Option<Object> {
+        self.get_object(object_id)
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: sui_types::base_types::VersionNumber,
+    ) -> Option<Object> {
+        self.get_object_at_version(object_id, version)
+    }
+}
+
+impl ParentSync for ForkingStore {
+    fn get_latest_parent_entry_ref_deprecated(
+        &self,
+        _object_id: ObjectID,
+    ) -> Option<sui_types::base_types::ObjectRef> {
+        panic!("Never called in newer protocol versions")
+    }
+}
+
+impl SimulatorStore for ForkingStore {
+    fn get_checkpoint_by_sequence_number(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Option<VerifiedCheckpoint> {
+        self.get_checkpoint_by_sequence_number(sequence_number)
+            .cloned()
+    }
+
+    fn get_checkpoint_by_digest(&self, digest: &CheckpointDigest) -> Option<VerifiedCheckpoint> {
+        self.get_checkpoint_by_digest(digest).cloned()
+    }
+
+    fn get_highest_checkpint(&self) -> Option<VerifiedCheckpoint> {
+        self.get_highest_checkpint().cloned()
+    }
+
+    fn get_checkpoint_contents(
+        &self,
+        digest: &CheckpointContentsDigest,
+    ) -> Option<CheckpointContents> {
+        self.get_checkpoint_contents(digest).cloned()
+    }
+
+    fn get_committee_by_epoch(&self, epoch: EpochId) -> Option<Committee> {
+        self.get_committee_by_epoch(epoch).cloned()
+    }
+
+    fn get_transaction(&self, digest: &TransactionDigest) -> Option<VerifiedTransaction> {
+        self.get_transaction(digest).cloned()
+    }
+
+    fn get_transaction_effects(&self, digest: &TransactionDigest) -> Option<TransactionEffects> {
+        self.get_transaction_effects(digest).cloned()
+    }
+
+    fn get_transaction_events(&self, digest: &TransactionDigest) -> Option<TransactionEvents> {
+        self.get_transaction_events(digest).cloned()
+    }
+
+    fn get_object(&self, id: &ObjectID) -> Option<Object> {
+        self.get_object(id)
+    }
+
+    fn get_object_at_version(&self, id: &ObjectID, version: SequenceNumber) -> Option<Object> {
+        self.get_object_at_version(id, version)
+    }
+
+    fn get_system_state(&self) -> sui_types::sui_system_state::SuiSystemState {
+        self.get_system_state()
+    }
+
+    fn get_clock(&self) -> sui_types::clock::Clock {
+        self.get_clock()
+    }
+
+    fn owned_objects(&self, owner: SuiAddress) -> Box<dyn Iterator<Item = Object> + '_> {
+        Box::new(self.owned_objects(owner))
+    }
+
+    fn insert_checkpoint(&mut self, checkpoint: VerifiedCheckpoint) {
+        self.insert_checkpoint(checkpoint)
+    }
+
+    fn insert_checkpoint_contents(&mut self, contents: CheckpointContents) {
+        self.insert_checkpoint_contents(contents)
+    }
+
+    fn insert_committee(&mut self, committee: Committee) {
+        self.insert_committee(committee)
+    }
+
+    fn insert_executed_transaction(
+        &mut self,
+        transaction: VerifiedTransaction,
+        effects: TransactionEffects,
+        events: TransactionEvents,
+        written_objects: BTreeMap<ObjectID, Object>,
+    ) {
+        self.insert_executed_transaction(transaction, effects, events, written_objects)
+    }
+
+    fn insert_transaction(&mut self, transaction: VerifiedTransaction) {
+        self.insert_transaction(transaction)
+    }
+
+    fn insert_transaction_effects(&mut self, effects: TransactionEffects) {
+        self.insert_transaction_effects(effects)
+    }
+
+    fn insert_events(&mut self, tx_digest: &TransactionDigest, events: TransactionEvents) {
+        self.insert_events(tx_digest, events)
+    }
+
+    fn update_objects(
+        &mut self,
+        written_objects: BTreeMap<ObjectID, Object>,
+        deleted_objects: Vec<(ObjectID, SequenceNumber, ObjectDigest)>,
+    ) {
+        self.update_objects(written_objects, deleted_objects)
+    }
+
+    fn backing_store(&self) -> &dyn sui_types::storage::BackingStore {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use sui_json_rpc_types::{
+        Checkpoint, SuiEvent, SuiTransactionBlockEffects, SuiTransactionBlockResponse,
+    };
+    use sui_types::base_types::{ObjectRef, VersionNumber};
+    use sui_types::effects::TestEffectsBuilder;
+    use sui_types::object::MoveObject;
+    use sui_types::transaction::{SenderSignedData, TransactionData};
+
+    const FORK_CHECKPOINT: CheckpointSequenceNumber = 10;
+
+    /// A source network with a fixed set of objects and transactions, which counts the requests
+    /// for objects made to it.
+    #[derive(Default)]
+    struct MockSource {
+        objects: BTreeMap<(ObjectID, SequenceNumber), Object>,
+        transactions: HashMap<TransactionDigest, SuiTransactionBlockResponse>,
+        object_fetches: Arc<AtomicUsize>,
+    }
+
+    impl MockSource {
+        fn add_object(&mut self, object: &Object) {
+            self.objects
+                .insert((object.id(), object.version()), object.clone());
+        }
+
+        /// Adds a transaction that was included in `checkpoint`, and that used `gas` as its gas
+        /// coin. Returns the transaction's digest.
+        fn add_transaction(
+            &mut self,
+            checkpoint: Option<CheckpointSequenceNumber>,
+            gas: ObjectRef,
+        ) -> TransactionDigest {
+            let data = TransactionData::new_transfer_sui(
+                SuiAddress::ZERO,
+                SuiAddress::ZERO,
+                None,
+                gas,
+                1_000_000,
+                1_000,
+            );
+            let effects = TestEffectsBuilder::new(&SenderSignedData::new(data, vec![])).build();
+            let digest = *effects.transaction_digest();
+
+            let mut response = SuiTransactionBlockResponse::new(digest);
+            response.effects = Some(SuiTransactionBlockEffects::try_from(effects).unwrap());
+            response.checkpoint = checkpoint;
+            self.transactions.insert(digest, response);
+            digest
+        }
+    }
+
+    #[async_trait]
+    impl DataFetcher for MockSource {
+        async fn multi_get_versioned(
+            &self,
+            objects: &[(ObjectID, SequenceNumber)],
+        ) -> Result<Vec<Object>, ReplayEngineError> {
+            self.object_fetches.fetch_add(1, Ordering::Relaxed);
+            objects
+                .iter()
+                .map(|(id, version)| {
+                    self.objects.get(&(*id, *version)).cloned().ok_or(
+                        ReplayEngineError::ObjectVersionNotFound {
+                            id: *id,
+                            version: *version,
+                        },
+                    )
+                })
+                .collect()
+        }
+
+        async fn multi_get_latest(
+            &self,
+            objects: &[ObjectID],
+        ) -> Result<Vec<Object>, ReplayEngineError> {
+            self.object_fetches.fetch_add(1, Ordering::Relaxed);
+            objects
+                .iter()
+                .map(|id| {
+                    self.objects
+                        .range((*id, SequenceNumber::MIN)..=(*id, SequenceNumber::MAX))
+                        .next_back()
+                        .map(|(_, object)| object.clone())
+                        .ok_or(ReplayEngineError::ObjectNotExist { id: *id })
+                })
+                .collect()
+        }
+
+        async fn get_checkpoint_txs(
+            &self,
+            _id: u64,
+        ) -> Result<Vec<TransactionDigest>, ReplayEngineError> {
+            unimplemented!()
+        }
+
+        async fn get_checkpoint(&self, _id: u64) -> Result<Checkpoint, ReplayEngineError> {
+            unimplemented!()
+        }
+
+        async fn get_transaction(
+            &self,
+            tx_digest: &TransactionDigest,
+        ) -> Result<SuiTransactionBlockResponse, ReplayEngineError> {
+            self.transactions.get(tx_digest).cloned().ok_or_else(|| {
+                ReplayEngineError::GeneralError {
+                    err: format!("no transaction {tx_digest}"),
+                }
+            })
+        }
+
+        async fn get_loaded_child_objects(
+            &self,
+            _tx_digest: &TransactionDigest,
+        ) -> Result<Vec<(ObjectID, SequenceNumber)>, ReplayEngineError> {
+            unimplemented!()
+        }
+
+        async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64, ReplayEngineError> {
+            unimplemented!()
+        }
+
+        async fn fetch_random_transaction(
+            &self,
+            _checkpoint_id_start: Option<u64>,
+            _checkpoint_id_end: Option<u64>,
+        ) -> Result<TransactionDigest, ReplayEngineError> {
+            unimplemented!()
+        }
+
+        async fn get_epoch_start_timestamp_and_rgp(
+            &self,
+            _epoch_id: u64,
+        ) -> Result<(u64, u64), ReplayEngineError> {
+            unimplemented!()
+        }
+
+        async fn get_epoch_change_events(
+            &self,
+            _reverse: bool,
+        ) -> Result<Vec<SuiEvent>, ReplayEngineError> {
+            unimplemented!()
+        }
+
+        async fn get_chain_id(&self) -> Result<String, ReplayEngineError> {
+            unimplemented!()
+        }
+
+        async fn get_child_object(
+            &self,
+            _object_id: &ObjectID,
+            _version_upper_bound: VersionNumber,
+        ) -> Result<Object, ReplayEngineError> {
+            unimplemented!()
+        }
+    }
+
+    fn gas_coin(id: ObjectID, version: u64, previous_transaction: TransactionDigest) -> Object {
+        Object::new_move(
+            MoveObject::new_gas_coin(SequenceNumber::from_u64(version), id, 1_000),
+            Owner::AddressOwner(SuiAddress::ZERO),
+            previous_transaction,
+        )
+    }
+
+    /// Adds an object to `source` that was last written before the fork checkpoint.
+    fn add_object_before_fork(source: &mut MockSource, id: ObjectID) -> Object {
+        let funding = gas_coin(ObjectID::random(), 1, TransactionDigest::genesis_marker());
+        let tx = source.add_transaction(
+            Some(FORK_CHECKPOINT - 1),
+            funding.compute_object_reference(),
+        );
+        let object = gas_coin(id, 2, tx);
+        source.add_object(&object);
+        object
+    }
+
+    #[test]
+    fn test_get_object_is_cached() {
+        let mut source = MockSource::default();
+        let id = ObjectID::random();
+        let object = add_object_before_fork(&mut source, id);
+        let fetches = source.object_fetches.clone();
+        let store = ForkingStore::new(source, FORK_CHECKPOINT);
+
+        assert_eq!(store.get_object(&id), Some(object.clone()));
+        assert_eq!(store.get_object(&id), Some(object.clone()));
+        assert_eq!(
+            store.get_object_at_version(&id, object.version()),
+            Some(object)
+        );
+        assert_eq!(fetches.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_get_object_at_fork_checkpoint() {
+        let mut source = MockSource::default();
+        let id = ObjectID::random();
+        let at_fork = add_object_before_fork(&mut source, id);
+
+        // The object is modified twice after the fork checkpoint, once in a checkpoint and once
+        // in a transaction that isn't checkpointed yet.
+        let tx = source.add_transaction(
+            Some(FORK_CHECKPOINT + 1),
+            at_fork.compute_object_reference(),
+        );
+        let after_fork = gas_coin(id, 3, tx);
+        source.add_object(&after_fork);
+        let tx = source.add_transaction(None, after_fork.compute_object_reference());
+        source.add_object(&gas_coin(id, 4, tx));
+
+        // An object created after the fork checkpoint.
+        let created_id = ObjectID::random();
+        let funding = gas_coin(ObjectID::random(), 1, TransactionDigest::genesis_marker());
+        let tx = source.add_transaction(
+            Some(FORK_CHECKPOINT + 1),
+            funding.compute_object_reference(),
+        );
+        source.add_object(&gas_coin(created_id, 2, tx));
+
+        let fetches = source.object_fetches.clone();
+        let store = ForkingStore::new(source, FORK_CHECKPOINT);
+        assert_eq!(store.get_object(&id), Some(at_fork));
+        assert_eq!(store.get_object(&created_id), None);
+
+        // Neither object is fetched again.
+        let fetched = fetches.load(Ordering::Relaxed);
+        store.get_object(&id);
+        store.get_object(&created_id);
+        assert_eq!(fetches.load(Ordering::Relaxed), fetched);
+    }
+
+    #[test]
+    fn test_get_missing_object() {
+        let source = MockSource::default();
+        let fetches = source.object_fetches.clone();
+        let mut store = ForkingStore::new(source, FORK_CHECKPOINT);
+
+        let id = ObjectID::random();
+        assert_eq!(store.get_object(&id), None);
+        assert_eq!(store.get_object(&id), None);
+        assert_eq!(fetches.load(Ordering::Relaxed), 1);
+
+        // Objects created locally are found, even if the source doesn't know about them.
+        let object = gas_coin(id, 1, TransactionDigest::genesis_marker());
+        store.update_objects(BTreeMap::from([(id, object.clone())]), vec![]);
+        assert_eq!(store.get_object(&id), Some(object));
+        assert_eq!(fetches.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_get_removed_object() {
+        let mut source = MockSource::default();
+        let id = ObjectID::random();
+        let object = add_object_before_fork(&mut source, id);
+        let fetches = source.object_fetches.clone();
+        let mut store = ForkingStore::new(source, FORK_CHECKPOINT);
+
+        assert_eq!(store.get_object(&id), Some(object.clone()));
+        store.update_objects(BTreeMap::new(), vec![object.compute_object_reference()]);
+
+        // The object is not fetched from the source again once it has been removed locally, but
+        // its historical versions are still available.
+        assert_eq!(store.get_object(&id), None);
+        assert_eq!(
+            store.get_object_at_version(&id, object.version()),
+            Some(object.clone())
+        );
+        assert_eq!(fetches.load(Ordering::Relaxed), 1);
+
+        // Until it is written locally again.
+        let unwrapped = gas_coin(id, 5, TransactionDigest::genesis_marker());
+        store.update_objects(BTreeMap::from([(id, unwrapped.clone())]), vec![]);
+        assert_eq!(store.get_object(&id), Some(unwrapped));
+    }
+}
@@ -1,10 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::{anyhow, Result};
+use fastcrypto::traits::EncodeDecodeBase64;
+use im::{HashMap as ImHashMap, OrdMap, Vector};
 use move_binary_format::CompiledModule;
 use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::{language_storage::ModuleId, resolver::ModuleResolver};
-use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use sui_config::genesis;
 use sui_types::storage::{get_module, load_package_object_from_object_store, PackageObject};
 use sui_types::{
@@ -15,34 +19,52 @@ use sui_types::{
     effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
     error::SuiError,
     messages_checkpoint::{
-        CheckpointContents, CheckpointContentsDigest, CheckpointDigest, CheckpointSequenceNumber,
-        VerifiedCheckpoint,
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointContentsDigest, CheckpointDigest,
+        CheckpointSequenceNumber, VerifiedCheckpoint,
     },
     object::{Object, Owner},
     storage::{BackingPackageStore, ChildObjectResolver, ObjectStore, ParentSync},
-    transaction::VerifiedTransaction,
+    transaction::{Transaction, VerifiedTransaction},
 };
 
 use super::SimulatorStore;
 
-#[derive(Debug, Default)]
+/// The store's contents are kept in persistent data structures, so cloning the store (e.g. to
+/// branch a Simulacrum) is cheap: the clone shares its contents with the original until either of
+/// them is modified, and then only copies the parts that changed.
+#[derive(Clone, Debug, Default)]
 pub struct InMemoryStore {
     // Checkpoint data
-    checkpoints: BTreeMap<CheckpointSequenceNumber, VerifiedCheckpoint>,
-    checkpoint_digest_to_sequence_number: HashMap<CheckpointDigest, CheckpointSequenceNumber>,
-    checkpoint_contents: HashMap<CheckpointContentsDigest, CheckpointContents>,
+    checkpoints: OrdMap<CheckpointSequenceNumber, VerifiedCheckpoint>,
+    checkpoint_digest_to_sequence_number: ImHashMap<CheckpointDigest, CheckpointSequenceNumber>,
+    checkpoint_contents: ImHashMap<CheckpointContentsDigest, CheckpointContents>,
 
     // Transaction data
-    transactions: HashMap<TransactionDigest, VerifiedTransaction>,
-    effects: HashMap<TransactionDigest, TransactionEffects>,
-    events: HashMap<TransactionDigest, TransactionEvents>,
+    transactions: ImHashMap<TransactionDigest, VerifiedTransaction>,
+    effects: ImHashMap<TransactionDigest, TransactionEffects>,
+    events: ImHashMap<TransactionDigest, TransactionEvents>,
 
     // Committee data
-    epoch_to_committee: Vec<Committee>,
+    epoch_to_committee: Vector<Committee>,
 
     // Object data
-    live_objects: HashMap<ObjectID, SequenceNumber>,
-    objects: HashMap<ObjectID, BTreeMap<SequenceNumber, Object>>,
+    live_objects: ImHashMap<ObjectID, SequenceNumber>,
+    objects: ImHashMap<ObjectID, OrdMap<SequenceNumber, Object>>,
+}
+
+/// The contents of an [`InMemoryStore`], in a form that can be serialized. Checkpoints and
+/// transactions lose their verified status when they are serialized, and regain it when the
+/// snapshot is restored, because they were verified when they were first added to the store.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct InMemoryStoreSnapshot {
+    checkpoints: Vec<CertifiedCheckpointSummary>,
+    checkpoint_contents: Vec<CheckpointContents>,
+    transactions: Vec<Transaction>,
+    effects: Vec<TransactionEffects>,
+    events: Vec<(TransactionDigest, TransactionEvents)>,
+    epoch_to_committee: Vec<Committee>,
+    live_objects: BTreeMap<ObjectID, SequenceNumber>,
+    objects: Vec<Object>,
 }
 
 impl InMemoryStore {
@@ -69,9 +91,7 @@ impl InMemoryStore {
     }
 
     pub fn get_highest_checkpint(&self) -> Option<&VerifiedCheckpoint> {
-        self.checkpoints
-            .last_key_value()
-            .map(|(_, checkpoint)| checkpoint)
+        self.checkpoints.get_max().map(|(_, checkpoint)| checkpoint)
     }
 
     pub fn get_checkpoint_contents(
@@ -163,7 +183,7 @@ impl InMemoryStore {
         }
 
         if self.epoch_to_committee.len() == epoch {
-            self.epoch_to_committee.push(committee);
+            self.epoch_to_committee.push_back(committee);
         } else {
             panic!("committee was inserted into EpochCommitteeMap out of order");
         }
@@ -216,6 +236,91 @@ impl InMemoryStore {
     }
 }
 
+impl InMemoryStore {
+    pub(crate) fn snapshot(&self) -> InMemoryStoreSnapshot {
+        InMemoryStoreSnapshot {
+            checkpoints: self
+                .checkpoints
+                .values()
+                .map(|checkpoint| checkpoint.clone().into_inner())
+                .collect(),
+            checkpoint_contents: self.checkpoint_contents.values().cloned().collect(),
+            transactions: self
+                .transactions
+                .values()
+                .map(|transaction| transaction.clone().into_inner())
+                .collect(),
+            effects: self.effects.values().cloned().collect(),
+            events: self
+                .events
+                .iter()
+                .map(|(digest, events)| (*digest, events.clone()))
+                .collect(),
+            epoch_to_committee: self.epoch_to_committee.iter().cloned().collect(),
+            live_objects: self.live_objects.clone().into_iter().collect(),
+            objects: self
+                .objects
+                .values()
+                .flat_map(|versions| versions.values().cloned())
+                .collect(),
+        }
+    }
+
+    pub(crate) fn from_snapshot(snapshot: InMemoryStoreSnapshot) -> Self {
+        let InMemoryStoreSnapshot {
+            checkpoints,
+            checkpoint_contents,
+            transactions,
+            effects,
+            events,
+            epoch_to_committee,
+            live_objects,
+            objects,
+        } = snapshot;
+
+        let mut store = Self {
+            epoch_to_committee: epoch_to_committee.into_iter().collect(),
+            live_objects: live_objects.into_iter().collect(),
+            events: events.into_iter().collect(),
+            ..Default::default()
+        };
+
+        // Committees are restored as they were, rather than re-derived from end of epoch
+        // checkpoints, so the checkpoints are inserted directly.
+        for checkpoint in checkpoints {
+            let checkpoint = VerifiedCheckpoint::new_unchecked(checkpoint);
+            store
+                .checkpoint_digest_to_sequence_number
+                .insert(*checkpoint.digest(), *checkpoint.sequence_number());
+            store
+                .checkpoints
+                .insert(*checkpoint.sequence_number(), checkpoint);
+        }
+
+        for contents in checkpoint_contents {
+            store.insert_checkpoint_contents(contents);
+        }
+
+        for transaction in transactions {
+            store.insert_transaction(VerifiedTransaction::new_unchecked(transaction));
+        }
+
+        for effects in effects {
+            store.insert_transaction_effects(effects);
+        }
+
+        for object in objects {
+            store
+                .objects
+                .entry(object.id())
+                .or_default()
+                .insert(object.version(), object);
+        }
+
+        store
+    }
+}
+
 impl BackingPackageStore for InMemoryStore {
     fn get_package_object(
         &self,
@@ -328,6 +433,13 @@ pub struct KeyStore {
     account_keys: BTreeMap<SuiAddress, AccountKeyPair>,
 }
 
+/// The keys in a [`KeyStore`], as Base64 encoded private keys.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct KeyStoreSnapshot {
+    validator_keys: Vec<String>,
+    account_keys: Vec<String>,
+}
+
 impl KeyStore {
     pub fn from_network_config(
         network_config: &sui_swarm_config::network_config::NetworkConfig,
@@ -363,6 +475,69 @@ impl KeyStore {
     pub fn accounts(&self) -> impl Iterator<Item = (&SuiAddress, &AccountKeyPair)> {
         self.account_keys.iter()
     }
+
+    pub(crate) fn snapshot(&self) -> KeyStoreSnapshot {
+        KeyStoreSnapshot {
+            validator_keys: self
+                .validator_keys
+                .values()
+                .map(|key| key.encode_base64())
+                .collect(),
+            account_keys: self
+                .account_keys
+                .values()
+                .map(|key| key.encode_base64())
+                .collect(),
+        }
+    }
+
+    pub(crate) fn from_snapshot(snapshot: KeyStoreSnapshot) -> Result<Self> {
+        use fastcrypto::traits::KeyPair;
+
+        let validator_keys = snapshot
+            .validator_keys
+            .iter()
+            .map(|key| {
+                let key = AuthorityKeyPair::decode_base64(key)
+                    .map_err(|e| anyhow!("invalid validator key: {e}"))?;
+                Ok((key.public().into(), key))
+            })
+            .collect::<Result<_>>()?;
+
+        let account_keys = snapshot
+            .account_keys
+            .iter()
+            .map(|key| {
+                let key = AccountKeyPair::decode_base64(key)
+                    .map_err(|e| anyhow!("invalid account key: {e}"))?;
+                Ok((key.public().into(), key))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            validator_keys,
+            account_keys,
+        })
+    }
+}
+
+impl Clone for KeyStore {
+    fn clone(&self) -> Self {
+        use fastcrypto::traits::KeyPair;
+
+        Self {
+            validator_keys: self
+                .validator_keys
+                .iter()
+                .map(|(name, key)| (*name, key.copy()))
+                .collect(),
+            account_keys: self
+                .account_keys
+                .iter()
+                .map(|(address, key)| (*address, key.copy()))
+                .collect(),
+        }
+    }
 }
 
 impl SimulatorStore for InMemoryStore {
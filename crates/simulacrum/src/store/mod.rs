@@ -23,6 +23,7 @@ use sui_types::{
     storage::{BackingStore, ChildObjectResolver, ParentSync},
     transaction::{InputObjectKind, VerifiedTransaction},
 };
+pub mod fork_store;
 pub mod in_mem_store;
 
 pub trait SimulatorStore:
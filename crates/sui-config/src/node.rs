@@ -195,6 +195,11 @@ pub struct NodeConfig {
     #[serde(default = "bool_true")]
     pub enable_validator_tx_finalizer: bool,
 
+    /// Record when each transaction reaches each stage of processing on this node, to be queried
+    /// through the admin interface. Disabled by default, as it adds work to the transaction path.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub enable_transaction_lifecycle_tracking: bool,
+
     #[serde(default)]
     pub verifier_signing_config: VerifierSigningConfig,
 
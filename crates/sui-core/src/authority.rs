@@ -8,6 +8,7 @@ use crate::execution_cache::ExecutionCacheTraitPointers;
 use crate::execution_cache::TransactionCacheRead;
 use crate::jsonrpc_index::CoinIndexKey2;
 use crate::rpc_index::RpcIndexStore;
+use crate::transaction_lifecycle::{LifecycleStage, TransactionLifecycleTracker};
 use crate::transaction_outputs::TransactionOutputs;
 use crate::verify_indexes::{fix_indexes, verify_indexes};
 use anyhow::anyhow;
@@ -53,7 +54,6 @@ use sui_config::node::{AuthorityOverloadConfig, StateDebugDumpConfig};
 use sui_config::NodeConfig;
 use sui_protocol_config::PerObjectCongestionControlMode;
 use sui_types::crypto::RandomnessRound;
-use sui_types::dynamic_field::visitor as DFV;
 use sui_types::execution::ExecutionTimeObservationKey;
 use sui_types::execution::ExecutionTiming;
 use sui_types::execution_status::ExecutionStatus;
@@ -61,7 +61,6 @@ use sui_types::inner_temporary_store::PackageStoreWithFallback;
 use sui_types::layout_resolver::into_struct_layout;
 use sui_types::layout_resolver::LayoutResolver;
 use sui_types::messages_consensus::{AuthorityCapabilitiesV1, AuthorityCapabilitiesV2};
-use sui_types::object::bounded_visitor::BoundedVisitor;
 use sui_types::transaction_executor::SimulateTransactionResult;
 use tap::TapFallible;
 use tokio::sync::mpsc::unbounded_channel;
@@ -85,9 +84,9 @@ use sui_config::genesis::Genesis;
 use sui_config::node::{DBCheckpointConfig, ExpensiveSafetyCheckConfig};
 use sui_framework::{BuiltInFramework, SystemPackage};
 use sui_json_rpc_types::{
-    DevInspectResults, DryRunTransactionBlockResponse, EventFilter, SuiEvent, SuiMoveValue,
-    SuiObjectDataFilter, SuiTransactionBlockData, SuiTransactionBlockEffects,
-    SuiTransactionBlockEvents, TransactionFilter,
+    DevInspectResults, DryRunTransactionBlockResponse, EventFilter, SuiEvent, SuiObjectDataFilter,
+    SuiTransactionBlockData, SuiTransactionBlockEffects, SuiTransactionBlockEvents,
+    TransactionFilter,
 };
 use sui_macros::{fail_point, fail_point_async, fail_point_if};
 use sui_storage::key_value_store::{TransactionKeyValueStore, TransactionKeyValueStoreTrait};
@@ -97,7 +96,7 @@ use sui_types::committee::{EpochId, ProtocolVersion};
 use sui_types::crypto::{default_hash, AuthoritySignInfo, Signer};
 use sui_types::deny_list_v1::check_coin_deny_list_v1;
 use sui_types::digests::ChainIdentifier;
-use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName};
+use sui_types::dynamic_field::DynamicFieldInfo;
 use sui_types::effects::{
     InputSharedObject, SignedTransactionEffects, TransactionEffects, TransactionEffectsAPI,
     TransactionEvents, VerifiedSignedTransactionEffects,
@@ -857,6 +856,9 @@ pub struct AuthorityState {
     chain_identifier: ChainIdentifier,
 
     pub(crate) congestion_tracker: Arc<CongestionTracker>,
+
+    /// Timelines of recently seen transactions, for operators to inspect.
+    pub transaction_lifecycle: Arc<TransactionLifecycleTracker>,
 }
 
 /// The authority state encapsulates all state, drives execution, and ensures safety.
@@ -1300,6 +1302,11 @@ impl AuthorityState {
             execution_start_time.elapsed(),
         );
 
+        self.transaction_lifecycle
+            .record_with_detail(*tx_digest, LifecycleStage::Executed, || {
+                format!("{:?}", effects.status())
+            });
+
         Ok((effects, execution_error_opt))
     }
 
@@ -2537,97 +2544,23 @@ impl AuthorityState {
         written: &WrittenObjects,
         resolver: &mut dyn LayoutResolver,
     ) -> SuiResult<Option<DynamicFieldInfo>> {
-        // Skip if not a move object
-        let Some(move_object) = o.data.try_as_move().cloned() else {
-            return Ok(None);
-        };
-
-        // We only index dynamic field objects
-        if !move_object.type_().is_dynamic_field() {
-            return Ok(None);
-        }
-
-        let layout = resolver
-            .get_annotated_layout(&move_object.type_().clone().into())?
-            .into_layout();
-
-        let field =
-            DFV::FieldVisitor::deserialize(move_object.contents(), &layout).map_err(|e| {
-                SuiError::ObjectDeserializationError {
-                    error: e.to_string(),
-                }
-            })?;
-
-        let type_ = field.kind;
-        let name_type: TypeTag = field.name_layout.into();
-        let bcs_name = field.name_bytes.to_owned();
-
-        let name_value = BoundedVisitor::deserialize_value(field.name_bytes, field.name_layout)
-            .map_err(|e| {
-                warn!("{e}");
-                SuiError::ObjectDeserializationError {
-                    error: e.to_string(),
-                }
-            })?;
-
-        let name = DynamicFieldName {
-            type_: name_type,
-            value: SuiMoveValue::from(name_value).to_json_value(),
-        };
+        crate::jsonrpc_index::try_create_dynamic_field_info(o, resolver, |object_id| {
+            // Find the actual object from storage using the object id obtained from the wrapper.
 
-        let value_metadata = field.value_metadata().map_err(|e| {
-            warn!("{e}");
-            SuiError::ObjectDeserializationError {
-                error: e.to_string(),
+            // Try to find the object in the written objects first.
+            if let Some(object) = written.get(&object_id) {
+                return Ok(object.clone());
             }
-        })?;
-
-        Ok(Some(match value_metadata {
-            DFV::ValueMetadata::DynamicField(object_type) => DynamicFieldInfo {
-                name,
-                bcs_name,
-                type_,
-                object_type: object_type.to_canonical_string(/* with_prefix */ true),
-                object_id: o.id(),
-                version: o.version(),
-                digest: o.digest(),
-            },
 
-            DFV::ValueMetadata::DynamicObjectField(object_id) => {
-                // Find the actual object from storage using the object id obtained from the wrapper.
-
-                // Try to find the object in the written objects first.
-                let (version, digest, object_type) = if let Some(object) = written.get(&object_id) {
-                    let version = object.version();
-                    let digest = object.digest();
-                    let object_type = object.data.type_().unwrap().clone();
-                    (version, digest, object_type)
-                } else {
-                    // If not found, try to find it in the database.
-                    let object = self
-                        .get_object_store()
-                        .get_object_by_key(&object_id, o.version())
-                        .ok_or_else(|| UserInputError::ObjectNotFound {
-                            object_id,
-                            version: Some(o.version()),
-                        })?;
-                    let version = object.version();
-                    let digest = object.digest();
-                    let object_type = object.data.type_().unwrap().clone();
-                    (version, digest, object_type)
-                };
-
-                DynamicFieldInfo {
-                    name,
-                    bcs_name,
-                    type_,
-                    object_type: object_type.to_string(),
+            // If not found, try to find it in the database.
+            Ok(self
+                .get_object_store()
+                .get_object_by_key(&object_id, o.version())
+                .ok_or_else(|| UserInputError::ObjectNotFound {
                     object_id,
-                    version,
-                    digest,
-                }
-            }
-        }))
+                    version: Some(o.version()),
+                })?)
+        })
     }
 
     #[instrument(level = "trace", skip_all, err)]
@@ -2921,6 +2854,9 @@ impl AuthorityState {
         Self::check_protocol_version(supported_protocol_versions, epoch_store.protocol_version());
 
         let metrics = Arc::new(AuthorityMetrics::new(prometheus_registry));
+        let transaction_lifecycle = Arc::new(TransactionLifecycleTracker::new(
+            config.enable_transaction_lifecycle_tracking,
+        ));
         let (tx_ready_certificates, rx_ready_certificates) = unbounded_channel();
         let transaction_manager = Arc::new(TransactionManager::new(
             execution_cache_trait_pointers.object_cache_reader.clone(),
@@ -2930,6 +2866,7 @@ impl AuthorityState {
             &epoch_store,
             tx_ready_certificates,
             metrics.clone(),
+            transaction_lifecycle.clone(),
         ));
         let (tx_execution_shutdown, rx_execution_shutdown) = oneshot::channel();
 
@@ -2975,6 +2912,7 @@ impl AuthorityState {
             validator_tx_finalizer,
             chain_identifier,
             congestion_tracker: Arc::new(CongestionTracker::new()),
+            transaction_lifecycle,
         });
 
         let state_clone = Arc::downgrade(&state);
@@ -121,6 +121,7 @@ use crate::module_cache_metrics::ResolverMetrics;
 use crate::post_consensus_tx_reorder::PostConsensusTxReorder;
 use crate::signature_verifier::*;
 use crate::stake_aggregator::{GenericMultiStakeAggregator, StakeAggregator};
+use crate::transaction_lifecycle::{LifecycleStage, TransactionLifecycleTracker};
 
 /// The key where the latest consensus index is stored in the database.
 // TODO: Make a single table (e.g., called `variables`) storing all our lonely variables in one place.
@@ -163,6 +164,7 @@ impl CertLockGuard {
 
 type JwkAggregator = GenericMultiStakeAggregator<(JwkId, JWK), true>;
 
+#[derive(Debug)]
 pub enum CancelConsensusCertificateReason {
     CongestionOnObjects(Vec<ObjectID>),
     DkgFailed,
@@ -2843,6 +2845,7 @@ impl AuthorityPerEpochStore {
         tx_reader: &dyn TransactionCacheRead,
         consensus_commit_info: &ConsensusCommitInfo,
         authority_metrics: &Arc<AuthorityMetrics>,
+        transaction_lifecycle: &TransactionLifecycleTracker,
     ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
         // Split transactions into different types for processing.
         let verified_transactions: Vec<_> = transactions
@@ -3089,6 +3092,7 @@ impl AuthorityPerEpochStore {
                 randomness_round,
                 execution_time_estimator.as_ref(),
                 authority_metrics,
+                transaction_lifecycle,
             )
             .await?;
         self.process_user_signatures(
@@ -3405,6 +3409,7 @@ impl AuthorityPerEpochStore {
                 skip_consensus_commit_prologue_in_test,
             ),
             authority_metrics,
+            &TransactionLifecycleTracker::new(false),
         )
         .await
     }
@@ -3469,6 +3474,7 @@ impl AuthorityPerEpochStore {
         randomness_round: Option<RandomnessRound>,
         execution_time_estimator: Option<&ExecutionTimeEstimator>,
         authority_metrics: &Arc<AuthorityMetrics>,
+        transaction_lifecycle: &TransactionLifecycleTracker,
     ) -> SuiResult<(
         Vec<VerifiedExecutableTransaction>, // non-randomness transactions to schedule
         Vec<VerifiedExecutableTransaction>, // randomness transactions to schedule
@@ -3549,6 +3555,13 @@ impl AuthorityPerEpochStore {
                     verified_certificates.push_back(cert);
                 }
                 ConsensusCertificateResult::Deferred(deferral_key) => {
+                    if let Some(digest) = tx.0.transaction.executable_transaction_digest() {
+                        transaction_lifecycle.record_with_detail(
+                            digest,
+                            LifecycleStage::Deferred,
+                            || format!("{deferral_key:?}"),
+                        );
+                    }
                     // Note: record_consensus_message_processed() must be called for this
                     // cert even though we are not processing it now!
                     deferred_txns
@@ -3562,6 +3575,11 @@ impl AuthorityPerEpochStore {
                     }
                 }
                 ConsensusCertificateResult::Cancelled((cert, reason)) => {
+                    transaction_lifecycle.record_with_detail(
+                        *cert.digest(),
+                        LifecycleStage::Cancelled,
+                        || format!("{reason:?}"),
+                    );
                     notifications.push(key.clone());
                     assert!(cancelled_txns.insert(*cert.digest(), reason).is_none());
                     verified_certificates.push_back(cert);
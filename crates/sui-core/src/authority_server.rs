@@ -15,7 +15,7 @@ use std::{
     io,
     net::{IpAddr, SocketAddr},
     sync::Arc,
-    time::SystemTime,
+    time::{Duration, SystemTime},
 };
 use sui_network::{
     api::{Validator, ValidatorServer},
@@ -33,7 +33,7 @@ use sui_types::messages_grpc::{
 };
 use sui_types::multiaddr::Multiaddr;
 use sui_types::sui_system_state::SuiSystemState;
-use sui_types::traffic_control::{ClientIdSource, PolicyConfig, RemoteFirewallConfig, Weight};
+use sui_types::traffic_control::{ClientIdSource, Weight};
 use sui_types::{effects::TransactionEffectsAPI, messages_grpc::SubmitTxResponse};
 use sui_types::{error::*, transaction::*};
 use sui_types::{
@@ -50,6 +50,7 @@ use tap::TapFallible;
 use tonic::metadata::{Ascii, MetadataValue};
 use tracing::{error, error_span, info, Instrument};
 
+use crate::consensus_adapter::ConnectionMonitorStatusForTests;
 use crate::{
     authority::authority_per_epoch_store::AuthorityPerEpochStore, checkpoints::CheckpointStore,
     mysticeti_adapter::LazyMysticetiClient,
@@ -58,12 +59,9 @@ use crate::{
     authority::AuthorityState,
     consensus_adapter::{ConsensusAdapter, ConsensusAdapterMetrics},
     traffic_controller::parse_ip,
-    traffic_controller::policies::TrafficTally,
+    traffic_controller::policies::{RequestCost, TrafficTally},
     traffic_controller::TrafficController,
-};
-use crate::{
-    consensus_adapter::ConnectionMonitorStatusForTests,
-    traffic_controller::metrics::TrafficControllerMetrics,
+    transaction_lifecycle::LifecycleStage,
 };
 use nonempty::{nonempty, NonEmpty};
 use sui_config::local_ip_utils::new_local_tcp_address_for_testing;
@@ -370,22 +368,15 @@ impl ValidatorService {
         state: Arc<AuthorityState>,
         consensus_adapter: Arc<ConsensusAdapter>,
         validator_metrics: Arc<ValidatorServiceMetrics>,
-        traffic_controller_metrics: TrafficControllerMetrics,
-        policy_config: Option<PolicyConfig>,
-        firewall_config: Option<RemoteFirewallConfig>,
+        traffic_controller: Option<Arc<TrafficController>>,
+        client_id_source: Option<ClientIdSource>,
     ) -> Self {
         Self {
             state,
             consensus_adapter,
             metrics: validator_metrics,
-            traffic_controller: policy_config.clone().map(|policy| {
-                Arc::new(TrafficController::init(
-                    policy,
-                    traffic_controller_metrics,
-                    firewall_config,
-                ))
-            }),
-            client_id_source: policy_config.map(|policy| policy.client_id_source),
+            traffic_controller,
+            client_id_source,
         }
     }
 
@@ -477,6 +468,9 @@ impl ValidatorService {
         drop(tx_verif_metrics_guard);
 
         let tx_digest = transaction.digest();
+        state
+            .transaction_lifecycle
+            .record(*tx_digest, LifecycleStage::Received);
 
         // Enable Trace Propagation across spans/processes using tx_digest
         let span = error_span!("validator_state_process_tx", ?tx_digest);
@@ -490,6 +484,9 @@ impl ValidatorService {
                     metrics.num_rejected_tx_in_epoch_boundary.inc();
                 }
             })?;
+        state
+            .transaction_lifecycle
+            .record(*tx_digest, LifecycleStage::Signed);
 
         if let Some(error) = validator_pushback_error {
             // TODO: right now, we still sign the txn, but just don't return it. We can also skip signing
@@ -556,6 +553,9 @@ impl ValidatorService {
         // Enable Trace Propagation across spans/processes using tx_digest
         let tx_digest = transaction.digest();
         let _span = error_span!("validator_state_submit_transaction", ?tx_digest);
+        state
+            .transaction_lifecycle
+            .record(*tx_digest, LifecycleStage::Received);
 
         let tx_output = state
             .handle_vote_transaction(&epoch_store, transaction.clone())
@@ -732,6 +732,13 @@ impl ValidatorService {
                 .into_iter()
                 .collect::<Result<Vec<_>, _>>()?
         };
+        for certificate in &verified_certificates {
+            self.state.transaction_lifecycle.record_with_detail(
+                *certificate.digest(),
+                LifecycleStage::Received,
+                || "certificate".to_string(),
+            );
+        }
         let consensus_transactions =
             NonEmpty::collect(verified_certificates.iter().map(|certificate| {
                 ConsensusTransaction::new_certificate_message(
@@ -1344,6 +1351,7 @@ impl ValidatorService {
         &self,
         client: Option<IpAddr>,
         wrapped_response: WrappedServiceResponse<T>,
+        execution_time: Duration,
     ) -> Result<tonic::Response<T>, tonic::Status> {
         let (error, spam_weight, unwrapped_response) = match wrapped_response {
             Ok((result, spam_weight)) => (None, spam_weight.clone(), Ok(result)),
@@ -1364,6 +1372,10 @@ impl ValidatorService {
                     (error_weight, error_type)
                 }),
                 spam_weight,
+                cost: RequestCost {
+                    execution_time,
+                    ..Default::default()
+                },
                 timestamp: SystemTime::now(),
             })
         }
@@ -1415,8 +1427,9 @@ macro_rules! handle_with_decoration {
         $self.handle_traffic_req(client.clone()).await?;
 
         // handle traffic tallying
+        let start = std::time::Instant::now();
         let wrapped_response = $self.$func_name($request).await;
-        $self.handle_traffic_resp(client, wrapped_response)
+        $self.handle_traffic_resp(client, wrapped_response, start.elapsed())
     }};
 }
 
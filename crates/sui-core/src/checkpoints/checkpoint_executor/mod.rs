@@ -46,6 +46,7 @@ use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use crate::authority::backpressure::BackpressureManager;
 use crate::authority::AuthorityState;
 use crate::state_accumulator::StateAccumulator;
+use crate::transaction_lifecycle::LifecycleStage;
 use crate::transaction_manager::TransactionManager;
 use crate::{
     checkpoints::CheckpointStore,
@@ -576,6 +577,14 @@ impl CheckpointExecutor {
             .insert_finalized_transactions(tx_digests, sequence_number)
             .expect("failed to insert finalized transactions");
 
+        for digest in tx_digests {
+            self.state.transaction_lifecycle.record_with_detail(
+                *digest,
+                LifecycleStage::Checkpointed,
+                || format!("checkpoint {sequence_number}"),
+            );
+        }
+
         if self.state.is_fullnode(&self.epoch_store) {
             // TODO remove once we no longer need to support this table for read RPC
             self.state
@@ -53,6 +53,7 @@ use crate::consensus_handler::{classify, SequencedConsensusTransactionKey};
 use crate::consensus_throughput_calculator::{ConsensusThroughputProfiler, Level};
 use crate::epoch::reconfiguration::{ReconfigState, ReconfigurationInitiator};
 use crate::metrics::LatencyObserver;
+use crate::transaction_lifecycle::{LifecycleStage, TransactionLifecycleTracker};
 
 #[cfg(test)]
 #[path = "unit_tests/consensus_tests.rs"]
@@ -260,6 +261,8 @@ pub struct ConsensusAdapter {
     low_scoring_authorities: ArcSwap<Arc<ArcSwap<HashMap<AuthorityName, u64>>>>,
     /// The throughput profiler to be used when making decisions to submit to consensus
     consensus_throughput_profiler: ArcSwapOption<ConsensusThroughputProfiler>,
+    /// Where to record when transactions are submitted to consensus
+    transaction_lifecycle: ArcSwapOption<TransactionLifecycleTracker>,
     /// A structure to register metrics
     metrics: ConsensusAdapterMetrics,
     /// Semaphore limiting parallel submissions to consensus
@@ -317,6 +320,7 @@ impl ConsensusAdapter {
             submit_semaphore: Arc::new(Semaphore::new(max_pending_local_submissions)),
             latency_observer: LatencyObserver::new(),
             consensus_throughput_profiler: ArcSwapOption::empty(),
+            transaction_lifecycle: ArcSwapOption::empty(),
             protocol_config,
         }
     }
@@ -332,6 +336,14 @@ impl ConsensusAdapter {
         self.consensus_throughput_profiler.store(Some(profiler))
     }
 
+    pub fn swap_transaction_lifecycle(
+        &self,
+        transaction_lifecycle: Arc<TransactionLifecycleTracker>,
+    ) {
+        self.transaction_lifecycle
+            .store(Some(transaction_lifecycle))
+    }
+
     pub fn submit_recovered(self: &Arc<Self>, epoch_store: &Arc<AuthorityPerEpochStore>) {
         // Currently narwhal worker might lose transactions on restart, so we need to resend them
         // todo - get_all_pending_consensus_transactions is called twice when
@@ -961,6 +973,22 @@ impl ConsensusAdapter {
             }
         };
 
+        let lifecycle = self.transaction_lifecycle.load();
+        if let Some(lifecycle) = lifecycle.as_ref() {
+            for transaction in transactions {
+                let digest = match &transaction.kind {
+                    ConsensusTransactionKind::CertifiedTransaction(certificate) => {
+                        *certificate.digest()
+                    }
+                    ConsensusTransactionKind::UserTransaction(tx) => *tx.digest(),
+                    _ => continue,
+                };
+                lifecycle.record_with_detail(digest, LifecycleStage::SubmittedToConsensus, || {
+                    format!("{retries} retries")
+                });
+            }
+        }
+
         // we want to record the num of retries when reporting latency but to avoid label
         // cardinality we do some simple bucketing to give us a good enough idea of how
         // many retries happened associated with the latency.
@@ -54,6 +54,7 @@ use crate::{
     consensus_types::consensus_output_api::{parse_block_transactions, ConsensusCommitAPI},
     execution_cache::{ObjectCacheRead, TransactionCacheRead},
     scoring_decision::update_low_scoring_authorities,
+    transaction_lifecycle::{LifecycleStage, TransactionLifecycleTracker},
     transaction_manager::TransactionManager,
 };
 
@@ -117,6 +118,7 @@ impl ConsensusHandlerInitializer {
             self.low_scoring_authorities.clone(),
             consensus_committee,
             self.state.metrics.clone(),
+            self.state.transaction_lifecycle.clone(),
             self.throughput_calculator.clone(),
             self.backpressure_manager.subscribe(),
         )
@@ -478,6 +480,8 @@ pub struct ConsensusHandler<C> {
     // TODO: ConsensusHandler doesn't really share metrics with AuthorityState. We could define
     // a new metrics type here if we want to.
     metrics: Arc<AuthorityMetrics>,
+    /// Records when user transactions are sequenced, deferred or cancelled.
+    transaction_lifecycle: Arc<TransactionLifecycleTracker>,
     /// Lru cache to quickly discard transactions processed by consensus
     processed_cache: LruCache<SequencedConsensusTransactionKey, ()>,
     /// Enqueues transactions to the transaction manager via a separate task.
@@ -502,6 +506,7 @@ impl<C> ConsensusHandler<C> {
         low_scoring_authorities: Arc<ArcSwap<HashMap<AuthorityName, u64>>>,
         committee: ConsensusCommittee,
         metrics: Arc<AuthorityMetrics>,
+        transaction_lifecycle: Arc<TransactionLifecycleTracker>,
         throughput_calculator: Arc<ConsensusThroughputCalculator>,
         backpressure_subscriber: BackpressureSubscriber,
     ) -> Self {
@@ -527,6 +532,7 @@ impl<C> ConsensusHandler<C> {
             low_scoring_authorities,
             committee,
             metrics,
+            transaction_lifecycle,
             processed_cache: LruCache::new(
                 NonZeroUsize::new(randomize_cache_capacity_in_tests(PROCESSED_CACHE_CAP)).unwrap(),
             ),
@@ -810,6 +816,17 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
                     continue;
                 }
 
+                if let Some(digest) = sequenced_transaction
+                    .transaction
+                    .executable_transaction_digest()
+                {
+                    self.transaction_lifecycle.record_with_detail(
+                        digest,
+                        LifecycleStage::Sequenced,
+                        || format!("round {}", commit_info.round),
+                    );
+                }
+
                 all_transactions.push(sequenced_transaction);
             }
         }
@@ -824,6 +841,7 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
                 self.tx_reader.as_ref(),
                 &commit_info,
                 &self.metrics,
+                &self.transaction_lifecycle,
             )
             .await
             .expect("Unrecoverable error in consensus handler");
@@ -1433,6 +1451,7 @@ mod tests {
             Arc::new(ArcSwap::default()),
             consensus_committee.clone(),
             metrics,
+            state.transaction_lifecycle.clone(),
             Arc::new(throughput_calculator),
             backpressure_manager.subscribe(),
         );
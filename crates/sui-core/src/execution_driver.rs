@@ -12,6 +12,7 @@ use tokio::sync::{mpsc::UnboundedReceiver, oneshot, Semaphore};
 use tracing::{error_span, info, trace, warn, Instrument};
 
 use crate::authority::AuthorityState;
+use crate::transaction_lifecycle::LifecycleStage;
 use crate::transaction_manager::PendingCertificate;
 
 #[cfg(test)]
@@ -103,6 +104,11 @@ pub async fn execution_process(
         }
 
         authority.metrics.execution_rate_tracker.lock().record();
+        authority.transaction_lifecycle.record_with_detail(
+            digest,
+            LifecycleStage::Dequeued,
+            || format!("queued for {:?}", txn_ready_time.elapsed()),
+        );
 
         // Certificate execution can take significant time, so run it in a separate task.
         let epoch_store_clone = epoch_store.clone();
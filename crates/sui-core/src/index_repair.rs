@@ -0,0 +1,193 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for rebuilding the fullnode indexes of an existing store in resumable steps.
+//!
+//! Building an index from the live object set and the checkpoint history of a store can take
+//! hours, so instead of starting over after an interruption, a rebuild records the steps it has
+//! completed through an [`IndexingProgress`] and skips them when it is run again.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::storage::error::Error as StorageError;
+
+/// Tracks the completed steps of an index (re)build.
+///
+/// Each step must be idempotent, as a step that was interrupted after writing some of its entries
+/// but before being recorded as complete is run again from the start.
+pub trait IndexingProgress: Sync {
+    /// Whether the live object set indexing task with the given id has completed.
+    fn is_live_object_task_complete(&self, task_id: u8) -> bool;
+
+    /// Records that the live object set indexing task with the given id has completed.
+    fn live_object_task_complete(&self, task_id: u8) -> Result<(), StorageError>;
+
+    /// The highest checkpoint such that all checkpoints up to and including it have been indexed.
+    fn indexed_checkpoints(&self) -> Option<CheckpointSequenceNumber>;
+
+    /// Records that all checkpoints up to and including `checkpoint` have been indexed.
+    fn checkpoints_indexed(&self, checkpoint: CheckpointSequenceNumber)
+        -> Result<(), StorageError>;
+}
+
+/// No progress tracking, for indexes that are built in one go.
+impl IndexingProgress for () {
+    fn is_live_object_task_complete(&self, _task_id: u8) -> bool {
+        false
+    }
+
+    fn live_object_task_complete(&self, _task_id: u8) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn indexed_checkpoints(&self) -> Option<CheckpointSequenceNumber> {
+        None
+    }
+
+    fn checkpoints_indexed(
+        &self,
+        _checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), StorageError> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct RepairState {
+    /// Whether the tables being rebuilt have been cleared. Until they have, the rebuild has not
+    /// started and there is no progress to resume from.
+    tables_cleared: bool,
+    live_object_tasks_complete: BTreeSet<u8>,
+    indexed_checkpoints: Option<CheckpointSequenceNumber>,
+}
+
+/// Progress of an index repair, persisted to a JSON file after every completed step.
+///
+/// The file is written atomically (to a temporary file which is then renamed over it), so it
+/// always reflects a consistent set of completed steps.
+pub struct RepairProgress {
+    path: PathBuf,
+    state: Mutex<RepairState>,
+}
+
+impl RepairProgress {
+    /// Loads the progress recorded at `path`, or starts with no progress if the file doesn't
+    /// exist.
+    pub fn load_or_default(path: impl Into<PathBuf>) -> Result<Self, StorageError> {
+        let path = path.into();
+        let state = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                StorageError::custom(format!(
+                    "malformed repair progress file {}: {e}",
+                    path.display()
+                ))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => RepairState::default(),
+            Err(e) => return Err(StorageError::custom(e)),
+        };
+
+        Ok(Self {
+            path,
+            state: Mutex::new(state),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether a previous run already started the repair, in which case it is resumed.
+    pub fn is_resumed(&self) -> bool {
+        self.state.lock().unwrap().tables_cleared
+    }
+
+    /// Records that the tables being rebuilt have been cleared, which starts the repair.
+    pub fn tables_cleared(&self) -> Result<(), StorageError> {
+        self.update(|state| state.tables_cleared = true)
+    }
+
+    pub fn num_live_object_tasks_complete(&self) -> usize {
+        self.state.lock().unwrap().live_object_tasks_complete.len()
+    }
+
+    /// Removes the progress file once the repair has completed.
+    pub fn finish(self) -> Result<(), StorageError> {
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(StorageError::custom(e)),
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut RepairState)) -> Result<(), StorageError> {
+        let mut state = self.state.lock().unwrap();
+        f(&mut state);
+
+        let bytes = serde_json::to_vec_pretty(&*state).map_err(StorageError::custom)?;
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, bytes).map_err(StorageError::custom)?;
+        std::fs::rename(&tmp_path, &self.path).map_err(StorageError::custom)?;
+        Ok(())
+    }
+}
+
+impl IndexingProgress for RepairProgress {
+    fn is_live_object_task_complete(&self, task_id: u8) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .live_object_tasks_complete
+            .contains(&task_id)
+    }
+
+    fn live_object_task_complete(&self, task_id: u8) -> Result<(), StorageError> {
+        self.update(|state| {
+            state.live_object_tasks_complete.insert(task_id);
+        })
+    }
+
+    fn indexed_checkpoints(&self) -> Option<CheckpointSequenceNumber> {
+        self.state.lock().unwrap().indexed_checkpoints
+    }
+
+    fn checkpoints_indexed(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), StorageError> {
+        self.update(|state| state.indexed_checkpoints = Some(checkpoint))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repair_progress_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("progress.json");
+
+        let progress = RepairProgress::load_or_default(&path).unwrap();
+        assert!(!progress.is_resumed());
+        assert!(!path.exists());
+
+        progress.tables_cleared().unwrap();
+        progress.live_object_task_complete(3).unwrap();
+        progress.checkpoints_indexed(41).unwrap();
+        drop(progress);
+
+        let progress = RepairProgress::load_or_default(&path).unwrap();
+        assert!(progress.is_resumed());
+        assert!(progress.is_live_object_task_complete(3));
+        assert!(!progress.is_live_object_task_complete(4));
+        assert_eq!(progress.num_live_object_tasks_complete(), 1);
+        assert_eq!(progress.indexed_checkpoints(), Some(41));
+
+        progress.finish().unwrap();
+        assert!(!path.exists());
+        assert!(!RepairProgress::load_or_default(&path).unwrap().is_resumed());
+    }
+}
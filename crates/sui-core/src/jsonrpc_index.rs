@@ -22,7 +22,8 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use typed_store::rocksdb::compaction_filter::Decision;
 use typed_store::TypedStoreError;
 
-use sui_json_rpc_types::{SuiObjectDataFilter, TransactionFilter};
+use sui_execution::Executor;
+use sui_json_rpc_types::{SuiMoveValue, SuiObjectDataFilter, TransactionFilter};
 use sui_storage::mutex_table::MutexTable;
 use sui_storage::sharded_lru::ShardedLruCache;
 use sui_types::base_types::{
@@ -30,14 +31,18 @@ use sui_types::base_types::{
 };
 use sui_types::base_types::{ObjectInfo, ObjectRef};
 use sui_types::digests::TransactionEventsDigest;
-use sui_types::dynamic_field::{self, DynamicFieldInfo};
+use sui_types::dynamic_field::visitor as DFV;
+use sui_types::dynamic_field::{self, DynamicFieldInfo, DynamicFieldName};
 use sui_types::effects::TransactionEvents;
 use sui_types::error::{SuiError, SuiResult, UserInputError};
 use sui_types::inner_temporary_store::TxCoins;
+use sui_types::layout_resolver::LayoutResolver;
+use sui_types::object::bounded_visitor::BoundedVisitor;
 use sui_types::object::{Object, Owner};
 use sui_types::parse_sui_struct_tag;
 use sui_types::storage::error::Error as StorageError;
-use tracing::{debug, info, instrument, trace};
+use sui_types::storage::{BackingPackageStore, ObjectStore};
+use tracing::{debug, info, instrument, trace, warn};
 use typed_store::rocks::{
     default_db_options, read_size_from_env, DBBatch, DBMap, DBMapTableConfigMap, DBOptions,
     MetricConf,
@@ -46,6 +51,7 @@ use typed_store::traits::Map;
 use typed_store::DBMapUtils;
 
 use crate::authority::AuthorityStore;
+use crate::index_repair::RepairProgress;
 use crate::par_index_live_object_set::{LiveObjectIndexer, ParMakeLiveObjectIndexer};
 
 type OwnedMutexGuard<T> = ArcMutexGuard<parking_lot::RawMutex, T>;
@@ -589,6 +595,62 @@ impl IndexStore {
         store
     }
 
+    /// Rebuilds the owner, dynamic field and coin indexes from the live object set of
+    /// `authority_store`, discarding their current contents. Meant to be run offline, on a store
+    /// that isn't being written to.
+    ///
+    /// The rebuild is resumable: the parts of the live object set that have been indexed are
+    /// recorded in `progress`, and are skipped if the rebuild is interrupted and run again with
+    /// the same `progress`.
+    pub fn repair_live_object_indexes(
+        &self,
+        authority_store: &AuthorityStore,
+        executor: &Arc<dyn Executor + Send + Sync>,
+        package_store: &Arc<dyn BackingPackageStore + Send + Sync>,
+        progress: &RepairProgress,
+    ) -> Result<(), StorageError> {
+        if progress.is_resumed() {
+            info!(
+                "Resuming JSON-RPC live object index repair, {} tasks already complete",
+                progress.num_live_object_tasks_complete()
+            );
+        } else {
+            info!("Clearing JSON-RPC live object indexes");
+            self.tables.owner_index.unsafe_clear()?;
+            self.tables.dynamic_field_index.unsafe_clear()?;
+            self.tables.coin_index_2.unsafe_clear()?;
+            progress.tables_cleared()?;
+        }
+
+        let make_live_object_indexer = RepairParLiveObjectSetIndexer {
+            tables: &self.tables,
+            executor,
+            package_store,
+            object_store: authority_store as _,
+        };
+        crate::par_index_live_object_set::par_index_live_object_set_with_progress(
+            authority_store,
+            &make_live_object_indexer,
+            progress,
+        )?;
+
+        // The coin index has been fully populated, so make sure it isn't rebuilt on startup.
+        let mut metadata = self.tables.meta.get(&())?.unwrap_or(MetadataInfo {
+            version: CURRENT_DB_VERSION,
+            column_families: BTreeMap::new(),
+        });
+        metadata.column_families.insert(
+            self.tables.coin_index_2.cf_name().to_owned(),
+            ColumnFamilyInfo {
+                version: CURRENT_COIN_INDEX_VERSION,
+            },
+        );
+        self.tables.meta.insert(&(), &metadata)?;
+
+        info!("Finished repairing JSON-RPC live object indexes");
+        Ok(())
+    }
+
     pub fn tables(&self) -> &IndexStoreTables {
         &self.tables
     }
@@ -1900,6 +1962,194 @@ impl IndexStore {
     }
 }
 
+/// Creates the `DynamicFieldInfo` indexed for `o`, or returns `None` if `o` isn't a dynamic field.
+/// For dynamic object fields, `get_field_object` is called with the id of the object the field
+/// points to, and must return that object.
+pub(crate) fn try_create_dynamic_field_info(
+    o: &Object,
+    resolver: &mut dyn LayoutResolver,
+    get_field_object: impl FnOnce(ObjectID) -> SuiResult<Object>,
+) -> SuiResult<Option<DynamicFieldInfo>> {
+    // Skip if not a move object
+    let Some(move_object) = o.data.try_as_move().cloned() else {
+        return Ok(None);
+    };
+
+    // We only index dynamic field objects
+    if !move_object.type_().is_dynamic_field() {
+        return Ok(None);
+    }
+
+    let layout = resolver
+        .get_annotated_layout(&move_object.type_().clone().into())?
+        .into_layout();
+
+    let field = DFV::FieldVisitor::deserialize(move_object.contents(), &layout).map_err(|e| {
+        SuiError::ObjectDeserializationError {
+            error: e.to_string(),
+        }
+    })?;
+
+    let type_ = field.kind;
+    let name_type: TypeTag = field.name_layout.into();
+    let bcs_name = field.name_bytes.to_owned();
+
+    let name_value = BoundedVisitor::deserialize_value(field.name_bytes, field.name_layout)
+        .map_err(|e| {
+            warn!("{e}");
+            SuiError::ObjectDeserializationError {
+                error: e.to_string(),
+            }
+        })?;
+
+    let name = DynamicFieldName {
+        type_: name_type,
+        value: SuiMoveValue::from(name_value).to_json_value(),
+    };
+
+    let value_metadata = field.value_metadata().map_err(|e| {
+        warn!("{e}");
+        SuiError::ObjectDeserializationError {
+            error: e.to_string(),
+        }
+    })?;
+
+    Ok(Some(match value_metadata {
+        DFV::ValueMetadata::DynamicField(object_type) => DynamicFieldInfo {
+            name,
+            bcs_name,
+            type_,
+            object_type: object_type.to_canonical_string(/* with_prefix */ true),
+            object_id: o.id(),
+            version: o.version(),
+            digest: o.digest(),
+        },
+
+        DFV::ValueMetadata::DynamicObjectField(object_id) => {
+            let object = get_field_object(object_id)?;
+            DynamicFieldInfo {
+                name,
+                bcs_name,
+                type_,
+                object_type: object.data.type_().unwrap().to_string(),
+                object_id,
+                version: object.version(),
+                digest: object.digest(),
+            }
+        }
+    }))
+}
+
+/// The `coin_index_2` entry for `object`, if it is an address-owned coin.
+fn coin_index_entry(object: &Object) -> Option<(CoinIndexKey2, CoinInfo)> {
+    let Owner::AddressOwner(owner) = object.owner() else {
+        return None;
+    };
+
+    // only process coin types
+    let (coin_type, coin) = object
+        .coin_type_maybe()
+        .and_then(|coin_type| object.as_coin_maybe().map(|coin| (coin_type, coin)))?;
+
+    let key = CoinIndexKey2::new(
+        *owner,
+        coin_type.to_string(),
+        coin.balance.value(),
+        object.id(),
+    );
+    let value = CoinInfo {
+        version: object.version(),
+        digest: object.digest(),
+        balance: coin.balance.value(),
+        previous_transaction: object.previous_transaction,
+    };
+    Some((key, value))
+}
+
+/// Rebuilds all the indexes derived from the live object set: the owner, dynamic field and coin
+/// indexes.
+struct RepairParLiveObjectSetIndexer<'a> {
+    tables: &'a IndexStoreTables,
+    executor: &'a Arc<dyn Executor + Send + Sync>,
+    package_store: &'a Arc<dyn BackingPackageStore + Send + Sync>,
+    object_store: &'a (dyn ObjectStore + Sync),
+}
+
+struct RepairLiveObjectIndexer<'a> {
+    tables: &'a IndexStoreTables,
+    batch: typed_store::rocks::DBBatch,
+    resolver: Box<dyn LayoutResolver + 'a>,
+    object_store: &'a (dyn ObjectStore + Sync),
+}
+
+impl<'a> ParMakeLiveObjectIndexer for RepairParLiveObjectSetIndexer<'a> {
+    type ObjectIndexer = RepairLiveObjectIndexer<'a>;
+
+    fn make_live_object_indexer(&self) -> Self::ObjectIndexer {
+        RepairLiveObjectIndexer {
+            tables: self.tables,
+            batch: self.tables.owner_index.batch(),
+            resolver: self
+                .executor
+                .type_layout_resolver(Box::new(self.package_store)),
+            object_store: self.object_store,
+        }
+    }
+}
+
+impl LiveObjectIndexer for RepairLiveObjectIndexer<'_> {
+    fn index_object(&mut self, object: Object) -> Result<(), StorageError> {
+        match object.owner() {
+            Owner::AddressOwner(owner) => {
+                self.batch.insert_batch(
+                    &self.tables.owner_index,
+                    [((*owner, object.id()), ObjectInfo::from_object(&object))],
+                )?;
+            }
+            Owner::ObjectOwner(parent) => {
+                let field_info =
+                    try_create_dynamic_field_info(&object, self.resolver.as_mut(), |object_id| {
+                        // The live object set only has the latest version of each object, so
+                        // look up the latest version of the object the field points to.
+                        Ok(self.object_store.get_object(&object_id).ok_or(
+                            UserInputError::ObjectNotFound {
+                                object_id,
+                                version: None,
+                            },
+                        )?)
+                    })
+                    .map_err(StorageError::custom)?;
+
+                if let Some(field_info) = field_info {
+                    self.batch.insert_batch(
+                        &self.tables.dynamic_field_index,
+                        [((ObjectID::from(*parent), object.id()), field_info)],
+                    )?;
+                }
+            }
+            _ => {}
+        }
+
+        if let Some((key, value)) = coin_index_entry(&object) {
+            self.batch
+                .insert_batch(&self.tables.coin_index_2, [(key, value)])?;
+        }
+
+        // If the batch size grows to greater that 128MB then write out to the DB so that the
+        // data we need to hold in memory doesn't grown unbounded.
+        if self.batch.size_in_bytes() >= 1 << 27 {
+            std::mem::replace(&mut self.batch, self.tables.owner_index.batch()).write()?;
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), StorageError> {
+        self.batch.write()?;
+        Ok(())
+    }
+}
+
 struct CoinParLiveObjectSetIndexer<'a> {
     tables: &'a IndexStoreTables,
 }
@@ -1922,31 +2172,10 @@ impl<'a> ParMakeLiveObjectIndexer for CoinParLiveObjectSetIndexer<'a> {
 
 impl LiveObjectIndexer for CoinLiveObjectIndexer<'_> {
     fn index_object(&mut self, object: Object) -> Result<(), StorageError> {
-        let Owner::AddressOwner(owner) = object.owner() else {
+        let Some((key, value)) = coin_index_entry(&object) else {
             return Ok(());
         };
 
-        // only process coin types
-        let Some((coin_type, coin)) = object
-            .coin_type_maybe()
-            .and_then(|coin_type| object.as_coin_maybe().map(|coin| (coin_type, coin)))
-        else {
-            return Ok(());
-        };
-
-        let key = CoinIndexKey2::new(
-            *owner,
-            coin_type.to_string(),
-            coin.balance.value(),
-            object.id(),
-        );
-        let value = CoinInfo {
-            version: object.version(),
-            digest: object.digest(),
-            balance: coin.balance.value(),
-            previous_transaction: object.previous_transaction,
-        };
-
         self.batch
             .insert_batch(&self.tables.coin_index_2, [(key, value)])?;
 
@@ -21,6 +21,7 @@ pub mod epoch;
 pub mod execution_cache;
 mod execution_driver;
 mod fallback_fetch;
+pub mod index_repair;
 pub mod jsonrpc_index;
 pub mod metrics;
 pub mod mock_consensus;
@@ -42,6 +43,7 @@ pub mod test_utils;
 pub mod traffic_controller;
 pub mod transaction_driver;
 mod transaction_input_loader;
+pub mod transaction_lifecycle;
 mod transaction_manager;
 pub mod transaction_orchestrator;
 mod transaction_outputs;
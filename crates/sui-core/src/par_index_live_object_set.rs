@@ -3,6 +3,8 @@
 
 use crate::authority::authority_store_tables::LiveObject;
 use crate::authority::AuthorityStore;
+use crate::index_repair::IndexingProgress;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
 use sui_types::base_types::ObjectID;
 use sui_types::object::Object;
@@ -33,20 +35,44 @@ pub trait LiveObjectIndexer {
 /// operating on each set in parallel in a separate thread. User's will need to implement the
 /// `ParMakeLiveObjectIndexer` trait which will be used to make N `LiveObjectIndexer`s which will
 /// then process one of the disjoint parts of the live object set.
-#[tracing::instrument(skip_all)]
 pub fn par_index_live_object_set<T: ParMakeLiveObjectIndexer>(
     authority_store: &AuthorityStore,
     make_indexer: &T,
+) -> Result<(), StorageError> {
+    par_index_live_object_set_with_progress(authority_store, make_indexer, &())
+}
+
+/// Like `par_index_live_object_set`, but skips the parts of the live object set that `progress`
+/// reports as already indexed, and records each part in `progress` once it has been indexed.
+#[tracing::instrument(skip_all)]
+pub fn par_index_live_object_set_with_progress<T: ParMakeLiveObjectIndexer>(
+    authority_store: &AuthorityStore,
+    make_indexer: &T,
+    progress: &dyn IndexingProgress,
 ) -> Result<(), StorageError> {
     info!("Indexing Live Object Set");
     let start_time = Instant::now();
     std::thread::scope(|s| -> Result<(), StorageError> {
         let mut threads = Vec::new();
         const BITS: u8 = 5;
+        const NUM_TASKS: usize = 1 << BITS;
+        let tasks_complete = AtomicUsize::new(0);
+        let tasks_complete = &tasks_complete;
         for index in 0u8..(1 << BITS) {
+            if progress.is_live_object_task_complete(index) {
+                tasks_complete.fetch_add(1, Ordering::Relaxed);
+                info!("[Index] Task {index} already complete, skipping");
+                continue;
+            }
+
             threads.push(s.spawn(move || {
                 let object_indexer = make_indexer.make_live_object_indexer();
-                live_object_set_index_task(index, BITS, authority_store, object_indexer)
+                live_object_set_index_task(index, BITS, authority_store, object_indexer)?;
+                progress.live_object_task_complete(index)?;
+
+                let complete = tasks_complete.fetch_add(1, Ordering::Relaxed) + 1;
+                info!("[Index] Task {index} complete ({complete}/{NUM_TASKS} tasks)");
+                Ok::<_, StorageError>(())
             }));
         }
 
@@ -31,6 +31,7 @@ use crate::authority_aggregator::{
     ProcessTransactionResult,
 };
 use crate::authority_client::AuthorityAPI;
+use crate::transaction_lifecycle::{LifecycleStage, TransactionLifecycleTracker};
 use mysten_common::sync::notify_read::{NotifyRead, Registration};
 use mysten_metrics::{
     spawn_monitored_task, GaugeGuard, TX_TYPE_SHARED_OBJ_TX, TX_TYPE_SINGLE_WRITER_TX,
@@ -83,6 +84,7 @@ pub struct QuorumDriver<A: Clone> {
     notifier: Arc<NotifyRead<TransactionDigest, QuorumDriverResult>>,
     metrics: Arc<QuorumDriverMetrics>,
     max_retry_times: u32,
+    transaction_lifecycle: Arc<TransactionLifecycleTracker>,
 }
 
 impl<A: Clone> QuorumDriver<A> {
@@ -93,6 +95,7 @@ impl<A: Clone> QuorumDriver<A> {
         notifier: Arc<NotifyRead<TransactionDigest, QuorumDriverResult>>,
         metrics: Arc<QuorumDriverMetrics>,
         max_retry_times: u32,
+        transaction_lifecycle: Arc<TransactionLifecycleTracker>,
     ) -> Self {
         Self {
             validators,
@@ -101,6 +104,7 @@ impl<A: Clone> QuorumDriver<A> {
             notifier,
             metrics,
             max_retry_times,
+            transaction_lifecycle,
         }
     }
 
@@ -176,9 +180,14 @@ impl<A: Clone> QuorumDriver<A> {
         client_addr: Option<SocketAddr>,
         min_backoff_duration: Option<Duration>,
     ) -> SuiResult<()> {
-        let next_retry_after = Instant::now()
-            + Duration::from_millis(200 * u64::pow(2, old_retry_times))
-                .max(min_backoff_duration.unwrap_or(Duration::from_secs(0)));
+        let backoff = Duration::from_millis(200 * u64::pow(2, old_retry_times))
+            .max(min_backoff_duration.unwrap_or(Duration::from_secs(0)));
+        let next_retry_after = Instant::now() + backoff;
+        self.transaction_lifecycle.record_with_detail(
+            *request.transaction.digest(),
+            LifecycleStage::Retried,
+            || format!("retrying in {backoff:?}"),
+        );
         sleep_until(next_retry_after).await;
 
         fail_point!("count_retry_times");
@@ -458,6 +467,7 @@ where
         reconfig_observer: Arc<dyn ReconfigObserver<A> + Sync + Send>,
         metrics: Arc<QuorumDriverMetrics>,
         max_retry_times: u32,
+        transaction_lifecycle: Arc<TransactionLifecycleTracker>,
     ) -> Self {
         let (task_tx, task_rx) = mpsc::channel::<QuorumDriverTask>(TASK_QUEUE_SIZE);
         let (subscriber_tx, subscriber_rx) =
@@ -469,6 +479,7 @@ where
             notifier,
             metrics.clone(),
             max_retry_times,
+            transaction_lifecycle,
         ));
         let metrics_clone = metrics.clone();
         let processor_handle = {
@@ -533,6 +544,7 @@ where
             notifier: Arc::new(NotifyRead::new()),
             metrics: self.quorum_driver_metrics.clone(),
             max_retry_times: self.quorum_driver.max_retry_times,
+            transaction_lifecycle: self.quorum_driver.transaction_lifecycle.clone(),
         });
         let metrics = self.quorum_driver_metrics.clone();
         let processor_handle = {
@@ -597,6 +609,11 @@ where
         let transaction = &request.transaction;
         let tx_digest = *transaction.digest();
         let is_single_writer_tx = !transaction.contains_shared_object();
+        quorum_driver.transaction_lifecycle.record_with_detail(
+            tx_digest,
+            LifecycleStage::SubmittedToValidators,
+            || format!("attempt {}", old_retry_times + 1),
+        );
 
         let timer = Instant::now();
         let (tx_cert, newly_formed) = match tx_cert {
@@ -788,6 +805,7 @@ pub struct QuorumDriverHandlerBuilder<A: Clone> {
     notifier: Option<Arc<NotifyRead<TransactionDigest, QuorumDriverResult>>>,
     reconfig_observer: Option<Arc<dyn ReconfigObserver<A> + Sync + Send>>,
     max_retry_times: u32,
+    transaction_lifecycle: Option<Arc<TransactionLifecycleTracker>>,
 }
 
 impl<A> QuorumDriverHandlerBuilder<A>
@@ -801,6 +819,7 @@ where
             notifier: None,
             reconfig_observer: None,
             max_retry_times: TX_MAX_RETRY_TIMES,
+            transaction_lifecycle: None,
         }
     }
 
@@ -820,6 +839,15 @@ where
         self
     }
 
+    /// Record submissions and retries of each transaction in `transaction_lifecycle`.
+    pub fn with_transaction_lifecycle(
+        mut self,
+        transaction_lifecycle: Arc<TransactionLifecycleTracker>,
+    ) -> Self {
+        self.transaction_lifecycle = Some(transaction_lifecycle);
+        self
+    }
+
     /// Used in tests when smaller number of retries is desired
     pub fn with_max_retry_times(mut self, max_retry_times: u32) -> Self {
         self.max_retry_times = max_retry_times;
@@ -836,6 +864,8 @@ where
                 .expect("Reconfig observer is missing"),
             self.metrics,
             self.max_retry_times,
+            self.transaction_lifecycle
+                .unwrap_or_else(|| Arc::new(TransactionLifecycleTracker::new(false))),
         )
     }
 }
@@ -4,6 +4,8 @@
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use crate::authority::AuthorityStore;
 use crate::checkpoints::CheckpointStore;
+use crate::index_repair::IndexingProgress;
+use crate::index_repair::RepairProgress;
 use crate::par_index_live_object_set::LiveObjectIndexer;
 use crate::par_index_live_object_set::ParMakeLiveObjectIndexer;
 use move_core_types::language_storage::StructTag;
@@ -12,6 +14,7 @@ use rayon::iter::ParallelIterator;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
@@ -19,6 +22,7 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::Duration;
 use std::time::Instant;
+use sui_execution::Executor;
 use sui_types::base_types::MoveObjectType;
 use sui_types::base_types::ObjectID;
 use sui_types::base_types::SequenceNumber;
@@ -27,7 +31,9 @@ use sui_types::committee::EpochId;
 use sui_types::digests::ObjectDigest;
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::visitor as DFV;
+use sui_types::effects::TransactionEffectsAPI;
 use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::full_checkpoint_content::CheckpointTransaction;
 use sui_types::layout_resolver::LayoutResolver;
 use sui_types::messages_checkpoint::CheckpointContents;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
@@ -38,7 +44,9 @@ use sui_types::storage::BackingPackageStore;
 use sui_types::storage::DynamicFieldIndexInfo;
 use sui_types::storage::DynamicFieldKey;
 use sui_types::storage::EpochInfo;
+use sui_types::storage::EventIndexFilter;
 use sui_types::storage::ObjectStore;
+use sui_types::storage::TransactionIndexFilter;
 use sui_types::storage::TransactionInfo;
 use sui_types::sui_system_state::SuiSystemStateTrait;
 use sui_types::transaction::TransactionDataAPI;
@@ -62,6 +70,10 @@ struct MetadataInfo {
 pub enum Watermark {
     Indexed,
     Pruned,
+    /// Set once the `transactions_by_filter` and `events_by_filter` tables cover every transaction
+    /// in the index. These tables were added without a version bump (which would force a full
+    /// re-index), so indexes created before them are backfilled in place on startup.
+    FiltersIndexed,
 }
 
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -137,6 +149,71 @@ impl CoinIndexInfo {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct TransactionIndexKey {
+    pub filter: TransactionIndexFilter,
+    pub checkpoint: CheckpointSequenceNumber,
+    pub transaction_index: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct EventIndexKey {
+    pub filter: EventIndexFilter,
+    pub checkpoint: CheckpointSequenceNumber,
+    pub transaction_index: u64,
+    pub event_index: u64,
+}
+
+/// The entries written to the `transactions_by_filter` and `events_by_filter` tables for a single
+/// transaction.
+///
+/// These are recorded so that the entries can be located and removed when the transaction is
+/// pruned.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TransactionIndexEntries {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub transaction_index: u64,
+    pub transaction_filters: Vec<TransactionIndexFilter>,
+    // (event_index, filter)
+    pub event_filters: Vec<(u64, EventIndexFilter)>,
+}
+
+impl TransactionIndexEntries {
+    fn new(
+        checkpoint: CheckpointSequenceNumber,
+        transaction_index: u64,
+        tx: &CheckpointTransaction,
+    ) -> Self {
+        Self {
+            checkpoint,
+            transaction_index,
+            transaction_filters: transaction_index_filters(tx).into_iter().collect(),
+            event_filters: event_index_filters(tx),
+        }
+    }
+
+    fn transaction_keys(&self) -> impl Iterator<Item = TransactionIndexKey> + '_ {
+        self.transaction_filters
+            .iter()
+            .map(|filter| TransactionIndexKey {
+                filter: filter.clone(),
+                checkpoint: self.checkpoint,
+                transaction_index: self.transaction_index,
+            })
+    }
+
+    fn event_keys(&self) -> impl Iterator<Item = EventIndexKey> + '_ {
+        self.event_filters
+            .iter()
+            .map(|(event_index, filter)| EventIndexKey {
+                filter: filter.clone(),
+                checkpoint: self.checkpoint,
+                transaction_index: self.transaction_index,
+                event_index: *event_index,
+            })
+    }
+}
+
 /// RocksDB tables for the RpcIndexStore
 ///
 /// Anytime a new table is added, or and existing one has it's schema changed, make sure to also
@@ -190,6 +267,26 @@ struct IndexStoreTables {
     /// Allows looking up information related to published Coins, like the ObjectID of its
     /// coorisponding CoinMetadata.
     coin: DBMap<CoinIndexKey, CoinIndexInfo>,
+
+    /// An index of Transactions by the various dimensions in `TransactionIndexFilter`.
+    ///
+    /// Allows an efficient iterator to list, in execution order, all transactions matching a
+    /// particular filter. Only contains entries for transactions which have yet to be pruned from
+    /// the main database.
+    transactions_by_filter: DBMap<TransactionIndexKey, TransactionDigest>,
+
+    /// An index of Events by the various dimensions in `EventIndexFilter`.
+    ///
+    /// Allows an efficient iterator to list, in emission order, all events matching a particular
+    /// filter. Only contains entries for transactions which have yet to be pruned from the main
+    /// database.
+    events_by_filter: DBMap<EventIndexKey, TransactionDigest>,
+
+    /// The entries written to `transactions_by_filter` and `events_by_filter` for each
+    /// transaction, used to clean up those tables when a transaction is pruned.
+    ///
+    /// Only contains entries for transactions which have yet to be pruned from the main database.
+    transaction_index_entries: DBMap<TransactionDigest, TransactionIndexEntries>,
     // NOTE: Authors and Reviewers before adding any new tables ensure that they are either:
     // - bounded in size by the live object set
     // - are prune-able and have corresponding logic in the `prune` function
@@ -228,8 +325,9 @@ impl IndexStoreTables {
         &mut self,
         authority_store: &AuthorityStore,
         checkpoint_store: &CheckpointStore,
-        epoch_store: &AuthorityPerEpochStore,
+        executor: &Arc<dyn Executor + Send + Sync>,
         package_store: &Arc<dyn BackingPackageStore + Send + Sync>,
+        progress: &dyn IndexingProgress,
     ) -> Result<(), StorageError> {
         info!("Initializing RPC indexes");
 
@@ -249,35 +347,50 @@ impl IndexStoreTables {
         let lowest_available_checkpoint =
             lowest_available_checkpoint.max(lowest_available_checkpoint_objects);
 
+        // Skip over any checkpoints indexed by a previous, interrupted, run
+        let lowest_available_checkpoint = progress
+            .indexed_checkpoints()
+            .map(|c| c.saturating_add(1))
+            .unwrap_or(0)
+            .max(lowest_available_checkpoint);
+
         let checkpoint_range = highest_executed_checkpint.map(|highest_executed_checkpint| {
             lowest_available_checkpoint..=highest_executed_checkpint
         });
 
         if let Some(checkpoint_range) = checkpoint_range {
-            self.index_existing_transactions(authority_store, checkpoint_store, checkpoint_range)?;
+            self.index_existing_transactions(
+                authority_store,
+                checkpoint_store,
+                checkpoint_range,
+                progress,
+            )?;
         }
 
-        let coin_index = Mutex::new(HashMap::new());
+        let coin_index_lock = Mutex::new(());
 
         let make_live_object_indexer = RpcParLiveObjectSetIndexer {
             tables: self,
-            coin_index: &coin_index,
-            epoch_store,
+            coin_index_lock: &coin_index_lock,
+            executor,
             package_store,
             object_store: authority_store as _,
         };
 
-        crate::par_index_live_object_set::par_index_live_object_set(
+        crate::par_index_live_object_set::par_index_live_object_set_with_progress(
             authority_store,
             &make_live_object_indexer,
+            progress,
         )?;
 
-        self.coin.multi_insert(coin_index.into_inner().unwrap())?;
-
         self.watermark.insert(
             &Watermark::Indexed,
             &highest_executed_checkpint.unwrap_or(0),
         )?;
+        self.watermark.insert(
+            &Watermark::FiltersIndexed,
+            &highest_executed_checkpint.unwrap_or(0),
+        )?;
 
         self.meta.insert(
             &(),
@@ -291,30 +404,60 @@ impl IndexStoreTables {
         Ok(())
     }
 
-    #[tracing::instrument(skip(self, authority_store, checkpoint_store))]
+    #[tracing::instrument(skip(self, authority_store, checkpoint_store, progress))]
     fn index_existing_transactions(
         &mut self,
         authority_store: &AuthorityStore,
         checkpoint_store: &CheckpointStore,
         checkpoint_range: std::ops::RangeInclusive<u64>,
+        progress: &dyn IndexingProgress,
     ) -> Result<(), StorageError> {
+        // Number of checkpoints indexed in parallel before recording progress
+        const CHECKPOINTS_PER_CHUNK: u64 = 10_000;
+
         info!(
             "Indexing {} checkpoints in range {checkpoint_range:?}",
             checkpoint_range.size_hint().0
         );
         let start_time = Instant::now();
 
-        checkpoint_range.into_par_iter().try_for_each(|seq| {
-            let checkpoint_data =
-                sparse_checkpoint_data_for_backfill(authority_store, checkpoint_store, seq)?;
+        let (start, end) = checkpoint_range.into_inner();
+        let mut chunk_start = start;
+        while chunk_start <= end {
+            let chunk_end = chunk_start
+                .saturating_add(CHECKPOINTS_PER_CHUNK - 1)
+                .min(end);
+
+            (chunk_start..=chunk_end)
+                .into_par_iter()
+                .try_for_each(|seq| {
+                    let checkpoint_data = sparse_checkpoint_data_for_backfill(
+                        authority_store,
+                        checkpoint_store,
+                        seq,
+                        /* with_objects */ true,
+                    )?;
 
-            let mut batch = self.transactions.batch();
+                    let mut batch = self.transactions.batch();
 
-            self.index_epoch(&checkpoint_data, &mut batch)?;
-            self.index_transactions(&checkpoint_data, &mut batch)?;
+                    self.index_epoch(&checkpoint_data, &mut batch)?;
+                    self.index_transactions(&checkpoint_data, &mut batch)?;
 
-            batch.write().map_err(StorageError::from)
-        })?;
+                    batch.write().map_err(StorageError::from)
+                })?;
+
+            progress.checkpoints_indexed(chunk_end)?;
+            info!(
+                "Indexed checkpoints up to {chunk_end} ({} of {} checkpoints)",
+                chunk_end - start + 1,
+                end.saturating_sub(start).saturating_add(1),
+            );
+
+            let Some(next) = chunk_end.checked_add(1) else {
+                break;
+            };
+            chunk_start = next;
+        }
 
         info!(
             "Indexing checkpoints took {} seconds",
@@ -323,6 +466,86 @@ impl IndexStoreTables {
         Ok(())
     }
 
+    fn needs_filter_index_backfill(&self) -> bool {
+        !matches!(self.watermark.get(&Watermark::FiltersIndexed), Ok(Some(_)))
+    }
+
+    /// Populate the `transactions_by_filter` and `events_by_filter` tables for transactions that
+    /// were indexed before those tables existed, i.e. every transaction that hasn't been pruned,
+    /// up to the indexed watermark. Only transaction data, effects and events are needed to do
+    /// this, so it is much cheaper than re-indexing from scratch.
+    #[tracing::instrument(skip_all)]
+    fn backfill_filter_indexes(
+        &self,
+        authority_store: &AuthorityStore,
+        checkpoint_store: &CheckpointStore,
+    ) -> Result<(), StorageError> {
+        // Number of checkpoints indexed in parallel before logging progress
+        const CHECKPOINTS_PER_CHUNK: u64 = 10_000;
+
+        let lowest_available_checkpoint = checkpoint_store
+            .get_highest_pruned_checkpoint_seq_number()?
+            .map(|c| c.saturating_add(1))
+            .unwrap_or(0);
+        let indexed_checkpoint = self.watermark.get(&Watermark::Indexed)?;
+
+        info!(
+            "Backfilling RPC transaction and event indexes for checkpoints \
+            {lowest_available_checkpoint} to {indexed_checkpoint:?}"
+        );
+        let start_time = Instant::now();
+
+        if let Some(end) = indexed_checkpoint {
+            let mut chunk_start = lowest_available_checkpoint;
+            while chunk_start <= end {
+                let chunk_end = chunk_start
+                    .saturating_add(CHECKPOINTS_PER_CHUNK - 1)
+                    .min(end);
+
+                (chunk_start..=chunk_end)
+                    .into_par_iter()
+                    .try_for_each(|seq| {
+                        let checkpoint_data = sparse_checkpoint_data_for_backfill(
+                            authority_store,
+                            checkpoint_store,
+                            seq,
+                            /* with_objects */ false,
+                        )?;
+
+                        let mut batch = self.transactions.batch();
+                        for (transaction_index, tx) in
+                            checkpoint_data.transactions.iter().enumerate()
+                        {
+                            self.index_transaction_filters(
+                                seq,
+                                transaction_index as u64,
+                                tx,
+                                &mut batch,
+                            )?;
+                        }
+
+                        batch.write().map_err(StorageError::from)
+                    })?;
+
+                info!("Backfilled transaction and event indexes up to checkpoint {chunk_end}");
+
+                let Some(next) = chunk_end.checked_add(1) else {
+                    break;
+                };
+                chunk_start = next;
+            }
+        }
+
+        self.watermark
+            .insert(&Watermark::FiltersIndexed, &indexed_checkpoint.unwrap_or(0))?;
+
+        info!(
+            "Backfilling transaction and event indexes took {} seconds",
+            start_time.elapsed().as_secs()
+        );
+        Ok(())
+    }
+
     /// Prune data from this Index
     fn prune(
         &self,
@@ -333,8 +556,20 @@ impl IndexStoreTables {
 
         let transactions_to_prune = checkpoint_contents_to_prune
             .iter()
-            .flat_map(|contents| contents.iter().map(|digests| digests.transaction));
+            .flat_map(|contents| contents.iter().map(|digests| digests.transaction))
+            .collect::<Vec<_>>();
+
+        for entries in self
+            .transaction_index_entries
+            .multi_get(&transactions_to_prune)?
+            .into_iter()
+            .flatten()
+        {
+            batch.delete_batch(&self.transactions_by_filter, entries.transaction_keys())?;
+            batch.delete_batch(&self.events_by_filter, entries.event_keys())?;
+        }
 
+        batch.delete_batch(&self.transaction_index_entries, &transactions_to_prune)?;
         batch.delete_batch(&self.transactions, transactions_to_prune)?;
         batch.insert_batch(
             &self.watermark,
@@ -442,22 +677,48 @@ impl IndexStoreTables {
         checkpoint: &CheckpointData,
         batch: &mut typed_store::rocks::DBBatch,
     ) -> Result<(), StorageError> {
-        for tx in &checkpoint.transactions {
+        let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+
+        for (transaction_index, tx) in checkpoint.transactions.iter().enumerate() {
             let info = TransactionInfo::new(
                 tx.transaction.transaction_data(),
                 &tx.effects,
                 &tx.input_objects,
                 &tx.output_objects,
-                checkpoint.checkpoint_summary.sequence_number,
+                sequence_number,
             );
 
             let digest = tx.transaction.digest();
             batch.insert_batch(&self.transactions, [(digest, info)])?;
+
+            self.index_transaction_filters(sequence_number, transaction_index as u64, tx, batch)?;
         }
 
         Ok(())
     }
 
+    fn index_transaction_filters(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        transaction_index: u64,
+        tx: &CheckpointTransaction,
+        batch: &mut typed_store::rocks::DBBatch,
+    ) -> Result<(), StorageError> {
+        let digest = tx.transaction.digest();
+        let entries = TransactionIndexEntries::new(checkpoint, transaction_index, tx);
+        batch.insert_batch(
+            &self.transactions_by_filter,
+            entries.transaction_keys().map(|key| (key, *digest)),
+        )?;
+        batch.insert_batch(
+            &self.events_by_filter,
+            entries.event_keys().map(|key| (key, *digest)),
+        )?;
+        batch.insert_batch(&self.transaction_index_entries, [(digest, entries)])?;
+
+        Ok(())
+    }
+
     fn index_objects(
         &self,
         checkpoint: &CheckpointData,
@@ -630,6 +891,59 @@ impl IndexStoreTables {
         };
         self.coin.get(&key)
     }
+
+    fn transactions_iter(
+        &self,
+        filter: TransactionIndexFilter,
+        cursor: Option<(CheckpointSequenceNumber, u64)>,
+        descending: bool,
+    ) -> Result<
+        impl Iterator<Item = Result<(TransactionIndexKey, TransactionDigest), TypedStoreError>> + '_,
+        TypedStoreError,
+    > {
+        let key = |(checkpoint, transaction_index)| TransactionIndexKey {
+            filter: filter.clone(),
+            checkpoint,
+            transaction_index,
+        };
+
+        Ok(if descending {
+            let upper_bound = key(cursor.unwrap_or((u64::MAX, u64::MAX)));
+            self.transactions_by_filter
+                .reversed_safe_iter_with_bounds(Some(key((0, 0))), Some(upper_bound))?
+        } else {
+            let lower_bound = key(cursor.unwrap_or((0, 0)));
+            self.transactions_by_filter
+                .safe_iter_with_bounds(Some(lower_bound), Some(key((u64::MAX, u64::MAX))))
+        })
+    }
+
+    fn events_iter(
+        &self,
+        filter: EventIndexFilter,
+        cursor: Option<(CheckpointSequenceNumber, u64, u64)>,
+        descending: bool,
+    ) -> Result<
+        impl Iterator<Item = Result<(EventIndexKey, TransactionDigest), TypedStoreError>> + '_,
+        TypedStoreError,
+    > {
+        let key = |(checkpoint, transaction_index, event_index)| EventIndexKey {
+            filter: filter.clone(),
+            checkpoint,
+            transaction_index,
+            event_index,
+        };
+
+        Ok(if descending {
+            let upper_bound = key(cursor.unwrap_or((u64::MAX, u64::MAX, u64::MAX)));
+            self.events_by_filter
+                .reversed_safe_iter_with_bounds(Some(key((0, 0, 0))), Some(upper_bound))?
+        } else {
+            let lower_bound = key(cursor.unwrap_or((0, 0, 0)));
+            self.events_by_filter
+                .safe_iter_with_bounds(Some(lower_bound), Some(key((u64::MAX, u64::MAX, u64::MAX))))
+        })
+    }
 }
 
 pub struct RpcIndexStore {
@@ -670,12 +984,18 @@ impl RpcIndexStore {
                     .init(
                         authority_store,
                         checkpoint_store,
-                        epoch_store,
+                        epoch_store.executor(),
                         package_store,
+                        &(),
                     )
                     .expect("unable to initialize rpc index from live object set");
                 tables
             } else {
+                if tables.needs_filter_index_backfill() {
+                    tables
+                        .backfill_filter_indexes(authority_store, checkpoint_store)
+                        .expect("unable to backfill rpc transaction and event indexes");
+                }
                 tables
             }
         };
@@ -686,6 +1006,52 @@ impl RpcIndexStore {
         }
     }
 
+    /// Rebuilds the index under `dir` from the live object set and checkpoint history of
+    /// `authority_store` and `checkpoint_store`, discarding its current contents. Meant to be run
+    /// offline, on a store that isn't being written to.
+    ///
+    /// The rebuild is resumable: the checkpoints and parts of the live object set that have been
+    /// indexed are recorded in `progress`, and are skipped if the rebuild is interrupted and run
+    /// again with the same `progress`.
+    pub async fn repair(
+        dir: &Path,
+        authority_store: &AuthorityStore,
+        checkpoint_store: &CheckpointStore,
+        executor: &Arc<dyn Executor + Send + Sync>,
+        package_store: &Arc<dyn BackingPackageStore + Send + Sync>,
+        progress: &RepairProgress,
+    ) -> Result<Self, StorageError> {
+        let path = Self::db_path(dir);
+
+        if progress.is_resumed() {
+            info!(
+                "Resuming RPC index repair, checkpoints indexed up to {:?}, {} live object tasks complete",
+                progress.indexed_checkpoints(),
+                progress.num_live_object_tasks_complete(),
+            );
+        } else {
+            info!("Dropping RPC index db at {}", path.display());
+            typed_store::rocks::safe_drop_db(path.clone(), Duration::from_secs(30))
+                .await
+                .map_err(StorageError::custom)?;
+            progress.tables_cleared()?;
+        }
+
+        let mut tables = IndexStoreTables::open(path);
+        tables.init(
+            authority_store,
+            checkpoint_store,
+            executor,
+            package_store,
+            progress,
+        )?;
+
+        Ok(Self {
+            tables,
+            pending_updates: Default::default(),
+        })
+    }
+
     pub fn new_without_init(dir: &Path) -> Self {
         let path = Self::db_path(dir);
         let tables = IndexStoreTables::open(path);
@@ -787,6 +1153,30 @@ impl RpcIndexStore {
     ) -> Result<Option<CoinIndexInfo>, TypedStoreError> {
         self.tables.get_coin_info(coin_type)
     }
+
+    pub fn transactions_iter(
+        &self,
+        filter: TransactionIndexFilter,
+        cursor: Option<(CheckpointSequenceNumber, u64)>,
+        descending: bool,
+    ) -> Result<
+        impl Iterator<Item = Result<(TransactionIndexKey, TransactionDigest), TypedStoreError>> + '_,
+        TypedStoreError,
+    > {
+        self.tables.transactions_iter(filter, cursor, descending)
+    }
+
+    pub fn events_iter(
+        &self,
+        filter: EventIndexFilter,
+        cursor: Option<(CheckpointSequenceNumber, u64, u64)>,
+        descending: bool,
+    ) -> Result<
+        impl Iterator<Item = Result<(EventIndexKey, TransactionDigest), TypedStoreError>> + '_,
+        TypedStoreError,
+    > {
+        self.tables.events_iter(filter, cursor, descending)
+    }
 }
 
 fn try_create_dynamic_field_info(
@@ -843,6 +1233,92 @@ fn try_create_dynamic_field_info(
     }))
 }
 
+/// Determine the set of `TransactionIndexFilter`s which a transaction should be indexed under.
+fn transaction_index_filters(tx: &CheckpointTransaction) -> BTreeSet<TransactionIndexFilter> {
+    let data = tx.transaction.transaction_data();
+    let sender = data.sender();
+
+    let mut filters = BTreeSet::new();
+    filters.insert(TransactionIndexFilter::Sender(sender));
+    filters.insert(TransactionIndexFilter::Kind(data.kind().name().to_owned()));
+
+    for (package, module, function) in data.move_calls() {
+        filters.insert(TransactionIndexFilter::MovePackage(*package));
+        filters.insert(TransactionIndexFilter::MoveModule(
+            *package,
+            module.to_owned(),
+        ));
+        filters.insert(TransactionIndexFilter::MoveFunction(
+            *package,
+            module.to_owned(),
+            function.to_owned(),
+        ));
+    }
+
+    for input in data.input_objects().unwrap_or_default() {
+        filters.insert(TransactionIndexFilter::InputObject(input.object_id()));
+    }
+
+    let changed_objects = tx
+        .effects
+        .created()
+        .into_iter()
+        .chain(tx.effects.mutated())
+        .chain(tx.effects.unwrapped());
+    for ((object_id, _, _), owner) in changed_objects {
+        filters.insert(TransactionIndexFilter::ChangedObject(object_id));
+
+        if let Owner::AddressOwner(recipient) = owner {
+            filters.insert(TransactionIndexFilter::Recipient(recipient));
+            filters.insert(TransactionIndexFilter::SenderAndRecipient(
+                sender, recipient,
+            ));
+        }
+    }
+
+    let affected_objects = tx
+        .effects
+        .object_changes()
+        .into_iter()
+        .map(|change| change.id)
+        .chain(
+            tx.effects
+                .input_shared_objects()
+                .into_iter()
+                .map(|shared| shared.id_and_version().0),
+        );
+    for object_id in affected_objects {
+        filters.insert(TransactionIndexFilter::AffectedObject(object_id));
+    }
+
+    filters
+}
+
+/// Determine the set of `EventIndexFilter`s which each event emitted by a transaction should be
+/// indexed under.
+fn event_index_filters(tx: &CheckpointTransaction) -> Vec<(u64, EventIndexFilter)> {
+    tx.events
+        .iter()
+        .flat_map(|events| events.data.iter().enumerate())
+        .flat_map(|(event_index, event)| {
+            let event_index = event_index as u64;
+            [
+                EventIndexFilter::Sender(event.sender),
+                EventIndexFilter::EmittingModule(
+                    event.package_id,
+                    event.transaction_module.to_string(),
+                ),
+                EventIndexFilter::EventModule(
+                    ObjectID::from(event.type_.address),
+                    event.type_.module.to_string(),
+                ),
+                EventIndexFilter::EventType(event.type_.clone()),
+            ]
+            .map(|filter| (event_index, filter))
+        })
+        .collect()
+}
+
 fn try_create_coin_index_info(object: &Object) -> Option<(CoinIndexKey, CoinIndexInfo)> {
     use sui_types::coin::CoinMetadata;
     use sui_types::coin::RegulatedCoinMetadata;
@@ -890,8 +1366,9 @@ fn try_create_coin_index_info(object: &Object) -> Option<(CoinIndexKey, CoinInde
 
 struct RpcParLiveObjectSetIndexer<'a> {
     tables: &'a IndexStoreTables,
-    coin_index: &'a Mutex<HashMap<CoinIndexKey, CoinIndexInfo>>,
-    epoch_store: &'a AuthorityPerEpochStore,
+    /// Serializes the read-merge-write of entries into the coin table by concurrent tasks
+    coin_index_lock: &'a Mutex<()>,
+    executor: &'a Arc<dyn Executor + Send + Sync>,
     package_store: &'a Arc<dyn BackingPackageStore + Send + Sync>,
     object_store: &'a (dyn ObjectStore + Sync),
 }
@@ -899,7 +1376,8 @@ struct RpcParLiveObjectSetIndexer<'a> {
 struct RpcLiveObjectIndexer<'a> {
     tables: &'a IndexStoreTables,
     batch: typed_store::rocks::DBBatch,
-    coin_index: &'a Mutex<HashMap<CoinIndexKey, CoinIndexInfo>>,
+    coin_index: HashMap<CoinIndexKey, CoinIndexInfo>,
+    coin_index_lock: &'a Mutex<()>,
     resolver: Box<dyn LayoutResolver + 'a>,
     object_store: &'a (dyn ObjectStore + Sync),
 }
@@ -911,10 +1389,10 @@ impl<'a> ParMakeLiveObjectIndexer for RpcParLiveObjectSetIndexer<'a> {
         RpcLiveObjectIndexer {
             tables: self.tables,
             batch: self.tables.owner.batch(),
-            coin_index: self.coin_index,
+            coin_index: HashMap::new(),
+            coin_index_lock: self.coin_index_lock,
             resolver: self
-                .epoch_store
-                .executor()
+                .executor
                 .type_layout_resolver(Box::new(self.package_store)),
             object_store: self.object_store,
         }
@@ -955,7 +1433,7 @@ impl LiveObjectIndexer for RpcLiveObjectIndexer<'_> {
         if let Some((key, value)) = try_create_coin_index_info(&object) {
             use std::collections::hash_map::Entry;
 
-            match self.coin_index.lock().unwrap().entry(key) {
+            match self.coin_index.entry(key) {
                 Entry::Occupied(mut o) => {
                     o.get_mut().merge(value);
                 }
@@ -974,7 +1452,20 @@ impl LiveObjectIndexer for RpcLiveObjectIndexer<'_> {
         Ok(())
     }
 
-    fn finish(self) -> Result<(), StorageError> {
+    fn finish(mut self) -> Result<(), StorageError> {
+        // Other tasks may have found the CoinMetadata<T> or TreasuryCap<T> of the same coin types,
+        // so merge with what they have already written.
+        let _guard = self.coin_index_lock.lock().unwrap();
+        let keys = self.coin_index.keys().cloned().collect::<Vec<_>>();
+        let existing = self.tables.coin.multi_get(&keys)?;
+        for (key, existing) in keys.into_iter().zip(existing) {
+            let mut info = self.coin_index.remove(&key).unwrap();
+            if let Some(existing) = existing {
+                info.merge(existing);
+            }
+            self.batch.insert_batch(&self.tables.coin, [(key, info)])?;
+        }
+
         self.batch.write()?;
         Ok(())
     }
@@ -983,14 +1474,14 @@ impl LiveObjectIndexer for RpcLiveObjectIndexer<'_> {
 // TODO figure out a way to dedup this logic. Today we'd need to do quite a bit of refactoring to
 // make it possible.
 //
-// Load a CheckpointData struct without event data
+// Load a CheckpointData struct without the full set of input and output objects (or without any
+// objects at all, if `with_objects` is false)
 fn sparse_checkpoint_data_for_backfill(
     authority_store: &AuthorityStore,
     checkpoint_store: &CheckpointStore,
     checkpoint: u64,
+    with_objects: bool,
 ) -> Result<CheckpointData, StorageError> {
-    use sui_types::full_checkpoint_content::CheckpointTransaction;
-
     let summary = checkpoint_store
         .get_checkpoint_by_sequence_number(checkpoint)?
         .ok_or_else(|| StorageError::missing(format!("missing checkpoint {checkpoint}")))?;
@@ -1018,15 +1509,24 @@ fn sparse_checkpoint_data_for_backfill(
 
     let mut full_transactions = Vec::with_capacity(transactions.len());
     for (tx, fx) in transactions.into_iter().zip(effects) {
-        let input_objects =
-            sui_types::storage::get_transaction_input_objects(authority_store, &fx)?;
-        let output_objects =
-            sui_types::storage::get_transaction_output_objects(authority_store, &fx)?;
+        let (input_objects, output_objects) = if with_objects {
+            (
+                sui_types::storage::get_transaction_input_objects(authority_store, &fx)?,
+                sui_types::storage::get_transaction_output_objects(authority_store, &fx)?,
+            )
+        } else {
+            (vec![], vec![])
+        };
+        let events = if fx.events_digest().is_some() {
+            authority_store.get_events(fx.transaction_digest())?
+        } else {
+            None
+        };
 
         let full_transaction = CheckpointTransaction {
             transaction: tx.into(),
             effects: fx,
-            events: None,
+            events,
             input_objects,
             output_objects,
         };
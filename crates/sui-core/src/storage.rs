@@ -6,9 +6,11 @@ use crate::checkpoints::CheckpointStore;
 use crate::epoch::committee_store::CommitteeStore;
 use crate::execution_cache::ExecutionCacheTraitPointers;
 use crate::rpc_index::CoinIndexInfo;
+use crate::rpc_index::EventIndexKey;
 use crate::rpc_index::OwnerIndexInfo;
 use crate::rpc_index::OwnerIndexKey;
 use crate::rpc_index::RpcIndexStore;
+use crate::rpc_index::TransactionIndexKey;
 use move_core_types::language_storage::StructTag;
 use parking_lot::Mutex;
 use std::sync::Arc;
@@ -31,11 +33,17 @@ use sui_types::storage::error::Result;
 use sui_types::storage::CoinInfo;
 use sui_types::storage::DynamicFieldIndexInfo;
 use sui_types::storage::DynamicFieldKey;
+use sui_types::storage::EventCursor;
+use sui_types::storage::EventIndexFilter;
+use sui_types::storage::EventIteratorItem;
 use sui_types::storage::ObjectStore;
 use sui_types::storage::OwnedObjectInfo;
 use sui_types::storage::RpcIndexes;
 use sui_types::storage::RpcStateReader;
+use sui_types::storage::TransactionCursor;
+use sui_types::storage::TransactionIndexFilter;
 use sui_types::storage::TransactionInfo;
+use sui_types::storage::TransactionIteratorItem;
 use sui_types::storage::WriteStore;
 use sui_types::storage::{ObjectKey, ReadStore};
 use sui_types::transaction::VerifiedTransaction;
@@ -550,4 +558,78 @@ impl RpcIndexes for RpcIndexStore {
             )
             .pipe(Ok)
     }
+
+    fn transactions_iter(
+        &self,
+        filter: TransactionIndexFilter,
+        cursor: Option<TransactionCursor>,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = TransactionIteratorItem> + '_>> {
+        let cursor = cursor.map(|cursor| (cursor.checkpoint, cursor.transaction_index));
+
+        let iter = self
+            .transactions_iter(filter, cursor, descending)?
+            .map(|result| {
+                result.map(
+                    |(
+                        TransactionIndexKey {
+                            checkpoint,
+                            transaction_index,
+                            ..
+                        },
+                        digest,
+                    )| {
+                        (
+                            TransactionCursor {
+                                checkpoint,
+                                transaction_index,
+                            },
+                            digest,
+                        )
+                    },
+                )
+            });
+
+        Ok(Box::new(iter) as _)
+    }
+
+    fn events_iter(
+        &self,
+        filter: EventIndexFilter,
+        cursor: Option<EventCursor>,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = EventIteratorItem> + '_>> {
+        let cursor = cursor.map(|cursor| {
+            (
+                cursor.checkpoint,
+                cursor.transaction_index,
+                cursor.event_index,
+            )
+        });
+
+        let iter = self.events_iter(filter, cursor, descending)?.map(|result| {
+            result.map(
+                |(
+                    EventIndexKey {
+                        checkpoint,
+                        transaction_index,
+                        event_index,
+                        ..
+                    },
+                    digest,
+                )| {
+                    (
+                        EventCursor {
+                            checkpoint,
+                            transaction_index,
+                            event_index,
+                        },
+                        digest,
+                    )
+                },
+            )
+        });
+
+        Ok(Box::new(iter) as _)
+    }
 }
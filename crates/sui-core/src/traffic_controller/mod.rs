@@ -6,37 +6,131 @@ pub mod nodefw_client;
 pub mod nodefw_test_server;
 pub mod policies;
 
+use anyhow::{bail, ensure};
+use arc_swap::ArcSwap;
+use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
 use fs::File;
+use parking_lot::RwLock;
 use prometheus::IntGauge;
+use serde::Deserialize;
 use std::fs;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::ops::Add;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use self::metrics::TrafficControllerMetrics;
 use crate::traffic_controller::nodefw_client::{BlockAddress, BlockAddresses, NodeFWClient};
 use crate::traffic_controller::policies::{
-    Policy, PolicyResponse, TrafficControlPolicy, TrafficTally,
+    IpSubnet, Policy, PolicyResponse, TrafficControlPolicy, TrafficTally,
 };
 use mysten_metrics::spawn_monitored_task;
 use rand::Rng;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 use std::time::{Duration, Instant, SystemTime};
 use sui_types::traffic_control::{PolicyConfig, PolicyType, RemoteFirewallConfig, Weight};
-use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, watch};
 use tracing::{debug, error, info, trace, warn};
 
 pub const METRICS_INTERVAL_SECS: u64 = 2;
 pub const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 300;
 
-type Blocklist = Arc<DashMap<IpAddr, SystemTime>>;
+/// Why a client was added to a blocklist.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockReason {
+    /// Blocked by the spam policy.
+    Spam,
+    /// Blocked by the error policy.
+    Error,
+    /// Blocked by an operator, through [TrafficController::block].
+    Manual,
+}
+
+impl fmt::Display for BlockReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockReason::Spam => write!(f, "spam"),
+            BlockReason::Error => write!(f, "error"),
+            BlockReason::Manual => write!(f, "manual"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct BlocklistEntry {
+    expiration: SystemTime,
+    reason: BlockReason,
+}
+
+/// Blocked clients, and subnets of clients, mapped to when they will be unblocked.
+type Blocklist = Arc<DashMap<IpSubnet, BlocklistEntry>>;
+
+/// Whether a blocklist applies to the clients connecting to the node directly, or to clients
+/// whose requests are proxied to the node (e.g. by a fullnode).
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BlocklistType {
+    Client,
+    ProxiedClient,
+}
+
+impl fmt::Display for BlocklistType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlocklistType::Client => write!(f, "client"),
+            BlocklistType::ProxiedClient => write!(f, "proxied-client"),
+        }
+    }
+}
+
+/// A client, or subnet of clients, in one of the blocklists.
+#[derive(Clone, Debug)]
+pub struct BlockedClient {
+    pub blocklist: BlocklistType,
+    pub subnet: IpSubnet,
+    pub expiration: SystemTime,
+    pub reason: BlockReason,
+}
+
+/// The highest recent rates seen by the spam and error policies, highest first. These are
+/// refreshed along with the rate metrics, every [METRICS_INTERVAL_SECS].
+#[derive(Clone, Debug, Default)]
+pub struct TopRates {
+    pub spam_direct: Vec<(u64, IpAddr)>,
+    pub spam_proxied: Vec<(u64, IpAddr)>,
+    pub error_direct: Vec<(u64, IpAddr)>,
+    pub error_proxied: Vec<(u64, IpAddr)>,
+}
 
 #[derive(Clone)]
 struct Blocklists {
     clients: Blocklist,
     proxied_clients: Blocklist,
+    /// IPv4 and IPv6 prefix lengths of the subnets that may be blocked, besides individual
+    /// addresses, so that checks know which subnets of a client to look for.
+    subnet_prefix_lens: Arc<ArcSwap<Vec<(u8, u8)>>>,
+}
+
+impl Blocklists {
+    fn get(&self, blocklist: BlocklistType) -> &Blocklist {
+        match blocklist {
+            BlocklistType::Client => &self.clients,
+            BlocklistType::ProxiedClient => &self.proxied_clients,
+        }
+    }
+
+    /// Make sure that checks look for clients' subnets with the given prefix lengths.
+    fn add_subnet_prefix_lens(&self, prefix_lens: (u8, u8)) {
+        self.subnet_prefix_lens.rcu(|current| {
+            let mut updated = Vec::clone(current);
+            if !updated.contains(&prefix_lens) {
+                updated.push(prefix_lens);
+            }
+            updated
+        });
+    }
 }
 
 #[derive(Clone)]
@@ -54,7 +148,10 @@ pub struct TrafficController {
     tally_channel: Option<mpsc::Sender<TrafficTally>>,
     acl: Acl,
     metrics: Arc<TrafficControllerMetrics>,
-    dry_run_mode: bool,
+    dry_run_mode: Arc<AtomicBool>,
+    /// The policy config in effect. The tally loop watches for updates to it.
+    policy_config: Arc<watch::Sender<PolicyConfig>>,
+    top_rates: Arc<RwLock<TopRates>>,
 }
 
 impl Debug for TrafficController {
@@ -83,7 +180,7 @@ impl TrafficController {
         metrics: TrafficControllerMetrics,
         fw_config: Option<RemoteFirewallConfig>,
     ) -> Self {
-        match policy_config.allow_list {
+        match policy_config.allow_list.clone() {
             Some(allow_list) => {
                 let allowlist = allow_list
                     .into_iter()
@@ -97,7 +194,9 @@ impl TrafficController {
                     tally_channel: None,
                     acl: Acl::Allowlist(allowlist),
                     metrics: Arc::new(metrics),
-                    dry_run_mode: policy_config.dry_run,
+                    dry_run_mode: Arc::new(AtomicBool::new(policy_config.dry_run)),
+                    policy_config: Arc::new(watch::channel(policy_config).0),
+                    top_rates: Arc::new(RwLock::new(TopRates::default())),
                 }
             }
             None => Self::spawn(policy_config, metrics, fw_config),
@@ -126,18 +225,24 @@ impl TrafficController {
         let blocklists = Blocklists {
             clients: Arc::new(DashMap::new()),
             proxied_clients: Arc::new(DashMap::new()),
+            subnet_prefix_lens: Arc::new(ArcSwap::from_pointee(Self::subnet_prefix_lens(
+                &policy_config,
+            ))),
         };
         let tally_loop_blocklists = blocklists.clone();
         let clear_loop_blocklists = blocklists.clone();
         let tally_loop_metrics = metrics.clone();
         let clear_loop_metrics = metrics.clone();
-        let dry_run_mode = policy_config.dry_run;
+        let dry_run_mode = Arc::new(AtomicBool::new(policy_config.dry_run));
+        let (policy_tx, policy_rx) = watch::channel(policy_config);
+        let top_rates = Arc::new(RwLock::new(TopRates::default()));
         spawn_monitored_task!(run_tally_loop(
             rx,
-            policy_config,
+            policy_rx,
             fw_config,
             tally_loop_blocklists,
             tally_loop_metrics,
+            top_rates.clone(),
             mem_drainfile_present,
         ));
         spawn_monitored_task!(run_clear_blocklists_loop(
@@ -149,13 +254,38 @@ impl TrafficController {
             acl: Acl::Blocklists(blocklists),
             metrics: metrics.clone(),
             dry_run_mode,
+            policy_config: Arc::new(policy_tx),
+            top_rates,
+        }
+    }
+
+    /// Prefix lengths of the subnets that the configured policies may block.
+    fn subnet_prefix_lens(policy_config: &PolicyConfig) -> Vec<(u8, u8)> {
+        let mut prefix_lens = vec![];
+        for policy_type in [
+            &policy_config.spam_policy_type,
+            &policy_config.error_policy_type,
+        ] {
+            if let PolicyType::CostThreshold(config) = policy_type {
+                let lens = (config.ipv4_prefix_len, config.ipv6_prefix_len);
+                if !prefix_lens.contains(&lens) {
+                    prefix_lens.push(lens);
+                }
+            }
         }
+        prefix_lens
     }
 
     fn set_policy_config_metrics(
         policy_config: &PolicyConfig,
         metrics: Arc<TrafficControllerMetrics>,
     ) {
+        // Thresholds of policies without one are cleared, in case they replace a policy that had
+        // one.
+        metrics.spam_client_threshold.set(0);
+        metrics.spam_proxied_client_threshold.set(0);
+        metrics.error_client_threshold.set(0);
+        metrics.error_proxied_client_threshold.set(0);
         if let PolicyType::FreqThreshold(config) = &policy_config.spam_policy_type {
             metrics
                 .spam_client_threshold
@@ -164,6 +294,14 @@ impl TrafficController {
                 .spam_proxied_client_threshold
                 .set(config.proxied_client_threshold as i64);
         }
+        if let PolicyType::CostThreshold(config) = &policy_config.spam_policy_type {
+            metrics
+                .spam_client_threshold
+                .set(config.client_threshold as i64);
+            metrics
+                .spam_proxied_client_threshold
+                .set(config.proxied_client_threshold as i64);
+        }
         if let PolicyType::FreqThreshold(config) = &policy_config.error_policy_type {
             metrics
                 .error_client_threshold
@@ -172,6 +310,14 @@ impl TrafficController {
                 .error_proxied_client_threshold
                 .set(config.proxied_client_threshold as i64);
         }
+        if let PolicyType::CostThreshold(config) = &policy_config.error_policy_type {
+            metrics
+                .error_client_threshold
+                .set(config.client_threshold as i64);
+            metrics
+                .error_proxied_client_threshold
+                .set(config.proxied_client_threshold as i64);
+        }
     }
 
     pub fn init_for_test(
@@ -243,14 +389,17 @@ impl TrafficController {
         client: &Option<IpAddr>,
         proxied_client: &Option<IpAddr>,
     ) -> bool {
+        let subnet_prefix_lens = blocklists.subnet_prefix_lens.load();
         let client_check = self.check_and_clear_blocklist(
             client,
             blocklists.clients.clone(),
+            &subnet_prefix_lens,
             &self.metrics.connection_ip_blocklist_len,
         );
         let proxied_client_check = self.check_and_clear_blocklist(
             proxied_client,
             blocklists.proxied_clients.clone(),
+            &subnet_prefix_lens,
             &self.metrics.proxy_ip_blocklist_len,
         );
         let (client_check, proxied_client_check) =
@@ -259,32 +408,190 @@ impl TrafficController {
     }
 
     pub fn dry_run_mode(&self) -> bool {
+        self.dry_run_mode.load(Ordering::Relaxed)
+    }
+
+    /// The policy config in effect.
+    pub fn policy_config(&self) -> PolicyConfig {
+        self.policy_config.borrow().clone()
+    }
+
+    /// Whether any policy in effect weighs requests by the gas they were charged, so that callers
+    /// only need to work it out if it will be used.
+    pub fn weighs_gas(&self) -> bool {
+        let policy_config = self.policy_config.borrow();
+        [
+            &policy_config.spam_policy_type,
+            &policy_config.error_policy_type,
+        ]
+        .into_iter()
+        .any(|policy_type| {
+            matches!(
+                policy_type,
+                PolicyType::CostThreshold(config) if config.cost_weights.gas != 0.0
+            )
+        })
+    }
+
+    /// Replace the policy config in effect. The spam and error policies are recreated from the
+    /// new config, so they start tallying from scratch, while clients that are already blocked
+    /// stay blocked until they expire.
+    ///
+    /// The client ID source, channel capacity, and allowlist are fixed when the controller is
+    /// created, and can only be changed by restarting.
+    pub fn update_policy_config(&self, policy_config: PolicyConfig) -> anyhow::Result<()> {
+        let Acl::Blocklists(blocklists) = &self.acl else {
+            bail!("The policy config of a traffic controller in allowlist mode cannot be changed");
+        };
+        ensure!(
+            policy_config.allow_list.is_none(),
+            "Cannot switch a running traffic controller to allowlist mode"
+        );
+        {
+            let current = self.policy_config.borrow();
+            ensure!(
+                policy_config.client_id_source == current.client_id_source,
+                "client-id-source cannot be changed without restarting"
+            );
+            ensure!(
+                policy_config.channel_capacity == current.channel_capacity,
+                "channel-capacity cannot be changed without restarting"
+            );
+        }
+        TrafficControlPolicy::validate(&policy_config.spam_policy_type)?;
+        TrafficControlPolicy::validate(&policy_config.error_policy_type)?;
+
+        for prefix_lens in Self::subnet_prefix_lens(&policy_config) {
+            blocklists.add_subnet_prefix_lens(prefix_lens);
+        }
+        Self::set_policy_config_metrics(&policy_config, self.metrics.clone());
         self.dry_run_mode
+            .store(policy_config.dry_run, Ordering::Relaxed);
+        info!(
+            "Updating traffic control policy config: {:?}",
+            policy_config
+        );
+        self.policy_config.send_replace(policy_config);
+        Ok(())
+    }
+
+    /// The highest recent rates seen by the policies.
+    pub fn top_rates(&self) -> TopRates {
+        self.top_rates.read().clone()
+    }
+
+    /// All the clients, and subnets of clients, that are currently blocked. Blocks delegated to
+    /// a remote firewall are not included.
+    pub fn blocked_clients(&self) -> anyhow::Result<Vec<BlockedClient>> {
+        let Acl::Blocklists(blocklists) = &self.acl else {
+            bail!("A traffic controller in allowlist mode has no blocklists");
+        };
+        let now = SystemTime::now();
+        let mut blocked = vec![];
+        for blocklist in [BlocklistType::Client, BlocklistType::ProxiedClient] {
+            for entry in blocklists.get(blocklist).iter() {
+                if now < entry.expiration {
+                    blocked.push(BlockedClient {
+                        blocklist,
+                        subnet: *entry.key(),
+                        expiration: entry.expiration,
+                        reason: entry.reason,
+                    });
+                }
+            }
+        }
+        Ok(blocked)
+    }
+
+    /// Block `subnet` (which may be a single address) for `ttl`. Manual blocks replace any
+    /// block already in place for the same subnet, and are not overridden by the policies.
+    pub fn block(
+        &self,
+        blocklist: BlocklistType,
+        subnet: IpSubnet,
+        ttl: Duration,
+    ) -> anyhow::Result<()> {
+        let Acl::Blocklists(blocklists) = &self.acl else {
+            bail!("A traffic controller in allowlist mode has no blocklists");
+        };
+        // Add the subnet's prefix length before blocking it, so that checks never miss it.
+        if subnet != IpSubnet::host(subnet.network()) {
+            let prefix_lens = if subnet.network().is_ipv4() {
+                (subnet.prefix_len(), 128)
+            } else {
+                (32, subnet.prefix_len())
+            };
+            blocklists.add_subnet_prefix_lens(prefix_lens);
+        }
+
+        let entry = BlocklistEntry {
+            expiration: SystemTime::now() + ttl,
+            reason: BlockReason::Manual,
+        };
+        info!("Manually blocking {blocklist} {subnet} for {ttl:?}");
+        if blocklists.get(blocklist).insert(subnet, entry).is_none() {
+            self.blocklist_len_gauge(blocklist).inc();
+        }
+        Ok(())
+    }
+
+    /// Unblock `subnet`, however it was blocked. Only a block of exactly this subnet is removed,
+    /// not blocks of addresses in it, or of subnets containing it. Returns whether it was
+    /// blocked.
+    pub fn unblock(&self, blocklist: BlocklistType, subnet: IpSubnet) -> anyhow::Result<bool> {
+        let Acl::Blocklists(blocklists) = &self.acl else {
+            bail!("A traffic controller in allowlist mode has no blocklists");
+        };
+        info!("Manually unblocking {blocklist} {subnet}");
+        let unblocked = blocklists.get(blocklist).remove(&subnet).is_some();
+        if unblocked {
+            self.blocklist_len_gauge(blocklist).dec();
+        }
+        Ok(unblocked)
+    }
+
+    fn blocklist_len_gauge(&self, blocklist: BlocklistType) -> &IntGauge {
+        match blocklist {
+            BlocklistType::Client => &self.metrics.connection_ip_blocklist_len,
+            BlocklistType::ProxiedClient => &self.metrics.proxy_ip_blocklist_len,
+        }
     }
 
     async fn check_and_clear_blocklist(
         &self,
         client: &Option<IpAddr>,
         blocklist: Blocklist,
+        subnet_prefix_lens: &[(u8, u8)],
         blocklist_len_gauge: &IntGauge,
     ) -> bool {
         let client = match client {
-            Some(client) => client,
+            Some(client) => *client,
             None => return true,
         };
         let now = SystemTime::now();
-        // the below two blocks cannot be nested, otherwise we will deadlock
-        // due to aquiring the lock on get, then holding across the remove
-        let (should_block, should_remove) = {
-            match blocklist.get(client) {
-                Some(expiration) if now >= *expiration => (false, true),
-                None => (false, false),
-                _ => (true, false),
+        // The client is blocked if either its own address, or any subnet it is in that a policy
+        // may have blocked, is in the blocklist.
+        let subnets = std::iter::once(IpSubnet::host(client)).chain(
+            subnet_prefix_lens
+                .iter()
+                .map(|(ipv4_len, ipv6_len)| IpSubnet::containing(client, *ipv4_len, *ipv6_len)),
+        );
+        let mut should_block = false;
+        for subnet in subnets {
+            // the below two blocks cannot be nested, otherwise we will deadlock
+            // due to aquiring the lock on get, then holding across the remove
+            let (blocked, should_remove) = {
+                match blocklist.get(&subnet) {
+                    Some(entry) if now >= entry.expiration => (false, true),
+                    None => (false, false),
+                    _ => (true, false),
+                }
+            };
+            if should_remove {
+                blocklist_len_gauge.dec();
+                blocklist.remove(&subnet);
             }
-        };
-        if should_remove {
-            blocklist_len_gauge.dec();
-            blocklist.remove(client);
+            should_block |= blocked;
         }
         !should_block
     }
@@ -300,10 +607,10 @@ async fn run_clear_blocklists_loop(blocklists: Blocklists, metrics: Arc<TrafficC
     loop {
         tokio::time::sleep(Duration::from_secs(3)).await;
         let now = SystemTime::now();
-        blocklists.clients.retain(|_, expiration| now < *expiration);
+        blocklists.clients.retain(|_, entry| now < entry.expiration);
         blocklists
             .proxied_clients
-            .retain(|_, expiration| now < *expiration);
+            .retain(|_, entry| now < entry.expiration);
         metrics
             .connection_ip_blocklist_len
             .set(blocklists.clients.len() as i64);
@@ -315,12 +622,14 @@ async fn run_clear_blocklists_loop(blocklists: Blocklists, metrics: Arc<TrafficC
 
 async fn run_tally_loop(
     mut receiver: mpsc::Receiver<TrafficTally>,
-    policy_config: PolicyConfig,
+    mut policy_updates: watch::Receiver<PolicyConfig>,
     fw_config: Option<RemoteFirewallConfig>,
     blocklists: Blocklists,
     metrics: Arc<TrafficControllerMetrics>,
+    top_rates: Arc<RwLock<TopRates>>,
     mut mem_drainfile_present: bool,
 ) {
+    let mut policy_config = policy_updates.borrow_and_update().clone();
     let mut spam_policy = TrafficControlPolicy::from_spam_config(policy_config.clone()).await;
     let mut error_policy = TrafficControlPolicy::from_error_config(policy_config.clone()).await;
     let spam_blocklists = Arc::new(blocklists.clone());
@@ -375,6 +684,16 @@ async fn run_tally_loop(
                     }
                 }
             }
+            updated = policy_updates.changed() => {
+                if updated.is_err() {
+                    info!("TrafficController policy config channel closed");
+                    return;
+                }
+                policy_config = policy_updates.borrow_and_update().clone();
+                spam_policy = TrafficControlPolicy::from_spam_config(policy_config.clone()).await;
+                error_policy = TrafficControlPolicy::from_error_config(policy_config.clone()).await;
+                info!("TrafficController policies updated");
+            }
             // Dead man's switch - if we suspect something is sinking all traffic to node, disable nodefw
             _ = tokio::time::sleep(tokio::time::Duration::from_secs(timeout)) => {
                 if let Some(fw_config) = &fw_config {
@@ -396,43 +715,45 @@ async fn run_tally_loop(
         // every N seconds, we update metrics and logging that would be too
         // spammy to be handled while processing each tally
         if metric_timer.elapsed() > Duration::from_secs(METRICS_INTERVAL_SECS) {
-            if let TrafficControlPolicy::FreqThreshold(spam_policy) = &spam_policy {
-                if let Some(highest_direct_rate) = spam_policy.highest_direct_rate() {
-                    metrics
-                        .highest_direct_spam_rate
-                        .set(highest_direct_rate.0 as i64);
-                    debug!("Recent highest direct spam rate: {:?}", highest_direct_rate);
-                }
-                if let Some(highest_proxied_rate) = spam_policy.highest_proxied_rate() {
-                    metrics
-                        .highest_proxied_spam_rate
-                        .set(highest_proxied_rate.0 as i64);
-                    debug!(
-                        "Recent highest proxied spam rate: {:?}",
-                        highest_proxied_rate
-                    );
-                }
+            if let Some(highest_direct_rate) = spam_policy.highest_direct_rate() {
+                metrics
+                    .highest_direct_spam_rate
+                    .set(highest_direct_rate.0 as i64);
+                debug!("Recent highest direct spam rate: {:?}", highest_direct_rate);
             }
-            if let TrafficControlPolicy::FreqThreshold(error_policy) = &error_policy {
-                if let Some(highest_direct_rate) = error_policy.highest_direct_rate() {
-                    metrics
-                        .highest_direct_error_rate
-                        .set(highest_direct_rate.0 as i64);
-                    debug!(
-                        "Recent highest direct error rate: {:?}",
-                        highest_direct_rate
-                    );
-                }
-                if let Some(highest_proxied_rate) = error_policy.highest_proxied_rate() {
-                    metrics
-                        .highest_proxied_error_rate
-                        .set(highest_proxied_rate.0 as i64);
-                    debug!(
-                        "Recent highest proxied error rate: {:?}",
-                        highest_proxied_rate
-                    );
-                }
+            if let Some(highest_proxied_rate) = spam_policy.highest_proxied_rate() {
+                metrics
+                    .highest_proxied_spam_rate
+                    .set(highest_proxied_rate.0 as i64);
+                debug!(
+                    "Recent highest proxied spam rate: {:?}",
+                    highest_proxied_rate
+                );
+            }
+            if let Some(highest_direct_rate) = error_policy.highest_direct_rate() {
+                metrics
+                    .highest_direct_error_rate
+                    .set(highest_direct_rate.0 as i64);
+                debug!(
+                    "Recent highest direct error rate: {:?}",
+                    highest_direct_rate
+                );
+            }
+            if let Some(highest_proxied_rate) = error_policy.highest_proxied_rate() {
+                metrics
+                    .highest_proxied_error_rate
+                    .set(highest_proxied_rate.0 as i64);
+                debug!(
+                    "Recent highest proxied error rate: {:?}",
+                    highest_proxied_rate
+                );
             }
+            *top_rates.write() = TopRates {
+                spam_direct: spam_policy.highest_direct_rates(),
+                spam_proxied: spam_policy.highest_proxied_rates(),
+                error_direct: error_policy.highest_direct_rates(),
+                error_proxied: error_policy.highest_proxied_rates(),
+            };
             metric_timer = Instant::now();
         }
     }
@@ -480,7 +801,7 @@ async fn handle_error_tally(
             .await;
         }
     }
-    handle_policy_response(resp, policy_config, blocklists, metrics).await;
+    handle_policy_response(resp, policy_config, blocklists, metrics, BlockReason::Error).await;
     Ok(())
 }
 
@@ -514,7 +835,7 @@ async fn handle_spam_tally(
             .await;
         }
     }
-    handle_policy_response(resp, policy_config, blocklists, metrics).await;
+    handle_policy_response(resp, policy_config, blocklists, metrics, BlockReason::Spam).await;
     Ok(())
 }
 
@@ -523,25 +844,32 @@ async fn handle_policy_response(
     policy_config: &PolicyConfig,
     blocklists: Arc<Blocklists>,
     metrics: Arc<TrafficControllerMetrics>,
+    reason: BlockReason,
 ) {
     let PolicyResponse {
         block_client,
         block_proxied_client,
+        block_client_subnet,
+        block_proxied_client_subnet,
     } = response;
     let PolicyConfig {
         connection_blocklist_ttl_sec,
         proxy_blocklist_ttl_sec,
         ..
     } = policy_config;
+    let block_client = block_client.map(IpSubnet::host).or(block_client_subnet);
+    let block_proxied_client = block_proxied_client
+        .map(IpSubnet::host)
+        .or(block_proxied_client_subnet);
     if let Some(client) = block_client {
-        if blocklists
-            .clients
-            .insert(
-                client,
-                SystemTime::now() + Duration::from_secs(*connection_blocklist_ttl_sec),
-            )
-            .is_none()
-        {
+        if insert_policy_block(
+            &blocklists.clients,
+            client,
+            BlocklistEntry {
+                expiration: SystemTime::now() + Duration::from_secs(*connection_blocklist_ttl_sec),
+                reason,
+            },
+        ) {
             // Only increment the metric if the client was not already blocked
             debug!("Blocking client: {:?}", client);
             metrics.requests_blocked_at_protocol.inc();
@@ -549,14 +877,14 @@ async fn handle_policy_response(
         }
     }
     if let Some(client) = block_proxied_client {
-        if blocklists
-            .proxied_clients
-            .insert(
-                client,
-                SystemTime::now() + Duration::from_secs(*proxy_blocklist_ttl_sec),
-            )
-            .is_none()
-        {
+        if insert_policy_block(
+            &blocklists.proxied_clients,
+            client,
+            BlocklistEntry {
+                expiration: SystemTime::now() + Duration::from_secs(*proxy_blocklist_ttl_sec),
+                reason,
+            },
+        ) {
             // Only increment the metric if the client was not already blocked
             debug!("Blocking proxied client: {:?}", client);
             metrics.requests_blocked_at_protocol.inc();
@@ -565,6 +893,24 @@ async fn handle_policy_response(
     }
 }
 
+/// Add a block from a policy to `blocklist`, unless the client is blocked manually. Returns
+/// whether the client was not already blocked.
+fn insert_policy_block(blocklist: &Blocklist, client: IpSubnet, entry: BlocklistEntry) -> bool {
+    match blocklist.entry(client) {
+        Entry::Occupied(mut existing) => {
+            let current = existing.get();
+            if current.reason != BlockReason::Manual || current.expiration <= SystemTime::now() {
+                existing.insert(entry);
+            }
+            false
+        }
+        Entry::Vacant(vacant) => {
+            vacant.insert(entry);
+            true
+        }
+    }
+}
+
 async fn delegate_policy_response(
     response: PolicyResponse,
     policy_config: &PolicyConfig,
@@ -575,6 +921,8 @@ async fn delegate_policy_response(
     let PolicyResponse {
         block_client,
         block_proxied_client,
+        block_client_subnet,
+        block_proxied_client_subnet,
     } = response;
     let PolicyConfig {
         connection_blocklist_ttl_sec,
@@ -590,6 +938,23 @@ async fn delegate_policy_response(
             ttl: *connection_blocklist_ttl_sec,
         });
     }
+    // Subnets are delegated in CIDR notation
+    if let Some(subnet) = block_client_subnet {
+        debug!("Delegating client subnet blocking to firewall");
+        addresses.push(BlockAddress {
+            source_address: subnet.to_string(),
+            destination_port,
+            ttl: *connection_blocklist_ttl_sec,
+        });
+    }
+    if let Some(subnet) = block_proxied_client_subnet {
+        debug!("Delegating proxied client subnet blocking to firewall");
+        addresses.push(BlockAddress {
+            source_address: subnet.to_string(),
+            destination_port,
+            ttl: *proxy_blocklist_ttl_sec,
+        });
+    }
     if let Some(ip) = block_proxied_client {
         debug!("Delegating proxied client blocking to firewall");
         addresses.push(BlockAddress {
@@ -2,7 +2,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashMap, net::IpAddr, sync::Arc};
+use std::{
+    collections::HashMap,
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+    sync::Arc,
+};
+
+use anyhow::{anyhow, ensure};
 
 use count_min_sketch::CountMinSketch32;
 use mysten_metrics::spawn_monitored_task;
@@ -13,11 +21,16 @@ use std::fmt::Debug;
 use std::hash::Hash;
 use std::time::Duration;
 use std::time::{Instant, SystemTime};
-use sui_types::traffic_control::{FreqThresholdConfig, PolicyConfig, PolicyType, Weight};
+use sui_types::traffic_control::{
+    CostThresholdConfig, CostWeights, FreqThresholdConfig, PolicyConfig, PolicyType, Weight,
+};
 use tracing::{info, trace};
 
 const HIGHEST_RATES_CAPACITY: usize = 20;
 
+/// Costs are tallied in thousandths, so that requests cheaper than one unit of cost still count.
+const COST_SCALE: f64 = 1000.0;
+
 /// The type of request client.
 #[derive(Hash, Eq, PartialEq, Debug)]
 enum ClientType {
@@ -62,6 +75,9 @@ pub struct TrafficSketch {
     /// However, this should only lead to inaccuracy edge cases
     /// with very low traffic.
     highest_rates: HighestRates,
+    /// Number of counts in the sketch that make up one tally, for sketches
+    /// tallying fractional amounts.
+    count_scale: f64,
 }
 
 impl TrafficSketch {
@@ -73,6 +89,16 @@ impl TrafficSketch {
         sketch_tolerance: f64,
         highest_rates_capacity: usize,
     ) -> Self {
+        if let Err(err) = Self::validate(
+            window_size,
+            update_interval,
+            sketch_capacity,
+            sketch_probability,
+            sketch_tolerance,
+        ) {
+            panic!("{err}");
+        }
+
         // intentionally round down via integer division. We can't have a partial sketch
         let num_sketches = window_size.as_secs() / update_interval.as_secs();
         let new_window_size = Duration::from_secs(num_sketches * update_interval.as_secs());
@@ -85,28 +111,6 @@ impl TrafficSketch {
         }
         let window_size = new_window_size;
 
-        assert!(
-            window_size < Duration::from_secs(600),
-            "window_size too large. Max 600 seconds"
-        );
-        assert!(
-            update_interval < window_size,
-            "Update interval may not be larger than window size"
-        );
-        assert!(
-            update_interval >= Duration::from_secs(1),
-            "Update interval too short, must be at least 1 second"
-        );
-        assert!(num_sketches <= 10, "Given parameters require too many sketches to be stored. Reduce window size or increase update interval.");
-        let mem_estimate = (num_sketches as usize)
-            * CountMinSketch32::<IpAddr>::estimate_memory(
-                sketch_capacity,
-                sketch_probability,
-                sketch_tolerance,
-            )
-            .expect("Failed to estimate memory for CountMinSketch32");
-        assert!(mem_estimate < 128_000_000, "Memory estimate for traffic sketch exceeds 128MB. Reduce window size or increase update interval.");
-
         let mut sketches = VecDeque::with_capacity(num_sketches as usize);
         for _ in 0..num_sketches {
             sketches.push_back(
@@ -129,10 +133,57 @@ impl TrafficSketch {
                 proxied: BinaryHeap::with_capacity(highest_rates_capacity),
                 capacity: highest_rates_capacity,
             },
+            count_scale: 1.0,
         }
     }
 
+    /// Check that a sketch can be created with the given parameters, without creating it.
+    pub fn validate(
+        window_size: Duration,
+        update_interval: Duration,
+        sketch_capacity: usize,
+        sketch_probability: f64,
+        sketch_tolerance: f64,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            update_interval >= Duration::from_secs(1),
+            "Update interval too short, must be at least 1 second"
+        );
+        // The window is rounded down to an integer multiple of the update interval.
+        let num_sketches = window_size.as_secs() / update_interval.as_secs();
+        let window_size = Duration::from_secs(num_sketches * update_interval.as_secs());
+
+        ensure!(
+            window_size < Duration::from_secs(600),
+            "window_size too large. Max 600 seconds"
+        );
+        ensure!(
+            update_interval < window_size,
+            "Update interval may not be larger than window size"
+        );
+        ensure!(num_sketches <= 10, "Given parameters require too many sketches to be stored. Reduce window size or increase update interval.");
+        let mem_estimate = (num_sketches as usize)
+            * CountMinSketch32::<IpAddr>::estimate_memory(
+                sketch_capacity,
+                sketch_probability,
+                sketch_tolerance,
+            )
+            .map_err(|_| anyhow!("Failed to estimate memory for CountMinSketch32"))?;
+        ensure!(mem_estimate < 128_000_000, "Memory estimate for traffic sketch exceeds 128MB. Reduce window size or increase update interval.");
+        Ok(())
+    }
+
+    /// Count `count_scale` increments in the sketch as a single tally.
+    pub fn with_count_scale(mut self, count_scale: f64) -> Self {
+        self.count_scale = count_scale;
+        self
+    }
+
     fn increment_count(&mut self, key: &SketchKey) {
+        self.add_count(key, 1);
+    }
+
+    fn add_count(&mut self, key: &SketchKey, count: u32) {
         // reset all expired intervals
         let current_time = Instant::now();
         let mut elapsed = current_time.duration_since(self.last_reset_time);
@@ -141,7 +192,7 @@ impl TrafficSketch {
             elapsed -= self.update_interval;
         }
         // Increment in the current active sketch
-        self.sketches[self.current_sketch_index].increment(key);
+        self.sketches[self.current_sketch_index].add(key, count);
     }
 
     fn get_request_rate(&mut self, key: &SketchKey) -> f64 {
@@ -150,7 +201,7 @@ impl TrafficSketch {
             .iter()
             .map(|sketch| sketch.estimate(key))
             .sum();
-        let rate = count as f64 / self.window_size.as_secs() as f64;
+        let rate = count as f64 / self.count_scale / self.window_size.as_secs() as f64;
         self.update_highest_rates(key, rate);
         rate
     }
@@ -215,6 +266,22 @@ impl TrafficSketch {
             .copied()
     }
 
+    /// All of the highest recent direct client rates being tracked, highest first.
+    pub fn highest_direct_rates(&self) -> Vec<(u64, IpAddr)> {
+        Self::sorted_rates(&self.highest_rates.direct)
+    }
+
+    /// All of the highest recent proxied client rates being tracked, highest first.
+    pub fn highest_proxied_rates(&self) -> Vec<(u64, IpAddr)> {
+        Self::sorted_rates(&self.highest_rates.proxied)
+    }
+
+    fn sorted_rates(rate_heap: &BinaryHeap<Reverse<(u64, IpAddr)>>) -> Vec<(u64, IpAddr)> {
+        let mut rates: Vec<_> = rate_heap.iter().map(|Reverse(v)| *v).collect();
+        rates.sort_by(|a, b| b.cmp(a));
+        rates
+    }
+
     fn rotate_window(&mut self) {
         self.current_sketch_index = (self.current_sketch_index + 1) % self.sketches.len();
         self.sketches[self.current_sketch_index].clear();
@@ -222,12 +289,115 @@ impl TrafficSketch {
     }
 }
 
+/// A block of IP addresses sharing a prefix, identified by its first address and the length of
+/// the prefix. A single address is the subnet whose prefix is the whole address.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub struct IpSubnet {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpSubnet {
+    /// The subnet containing only `ip`.
+    pub fn host(ip: IpAddr) -> Self {
+        match ip {
+            IpAddr::V4(_) => Self::containing(ip, 32, 0),
+            IpAddr::V6(_) => Self::containing(ip, 0, 128),
+        }
+    }
+
+    /// The subnet containing `ip`, with a prefix of `ipv4_prefix_len` or `ipv6_prefix_len`
+    /// depending on the kind of address. Prefix lengths longer than the address are treated as
+    /// the whole address.
+    pub fn containing(ip: IpAddr, ipv4_prefix_len: u8, ipv6_prefix_len: u8) -> Self {
+        match ip {
+            IpAddr::V4(addr) => {
+                let prefix_len = ipv4_prefix_len.min(32);
+                let mask = u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or(0);
+                Self {
+                    network: IpAddr::V4(Ipv4Addr::from(u32::from(addr) & mask)),
+                    prefix_len,
+                }
+            }
+            IpAddr::V6(addr) => {
+                let prefix_len = ipv6_prefix_len.min(128);
+                let mask = u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or(0);
+                Self {
+                    network: IpAddr::V6(Ipv6Addr::from(u128::from(addr) & mask)),
+                    prefix_len,
+                }
+            }
+        }
+    }
+
+    pub fn network(&self) -> IpAddr {
+        self.network
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+}
+
+impl fmt::Display for IpSubnet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+/// Parses a single address, or a subnet in CIDR notation. Bits of the address beyond the prefix
+/// are ignored, so `10.0.0.7/8` is the same subnet as `10.0.0.0/8`.
+impl FromStr for IpSubnet {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((addr, prefix_len)) = s.split_once('/') else {
+            let ip: IpAddr = s.parse().map_err(|_| anyhow!("Invalid IP address: {s}"))?;
+            return Ok(Self::host(ip));
+        };
+
+        let ip: IpAddr = addr
+            .parse()
+            .map_err(|_| anyhow!("Invalid IP address: {addr}"))?;
+        let prefix_len: u8 = prefix_len
+            .parse()
+            .map_err(|_| anyhow!("Invalid prefix length: {prefix_len}"))?;
+        let max_prefix_len = if ip.is_ipv4() { 32 } else { 128 };
+        ensure!(
+            prefix_len <= max_prefix_len,
+            "Prefix length {prefix_len} is too long for {ip}"
+        );
+        Ok(Self::containing(ip, prefix_len, prefix_len))
+    }
+}
+
+/// What it took to serve a request, for policies that weight tallies by cost. Dimensions that
+/// were not measured are left at zero.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestCost {
+    pub execution_time: Duration,
+    /// Computation cost, in MIST, of a transaction executed (or dry-run) by the request.
+    pub gas: u64,
+    pub response_bytes: u64,
+}
+
+impl RequestCost {
+    /// The cost of the request, in the units of `weights`.
+    pub fn weigh(&self, weights: &CostWeights) -> f64 {
+        weights.request
+            + weights.execution_time_ms * self.execution_time.as_secs_f64() * 1000.0
+            + weights.gas * self.gas as f64
+            + weights.response_kib * self.response_bytes as f64 / 1024.0
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TrafficTally {
     pub direct: Option<IpAddr>,
     pub through_fullnode: Option<IpAddr>,
     pub error_info: Option<(Weight, String)>,
     pub spam_weight: Weight,
+    pub cost: RequestCost,
     pub timestamp: SystemTime,
 }
 
@@ -243,15 +413,24 @@ impl TrafficTally {
             through_fullnode,
             error_info,
             spam_weight,
+            cost: RequestCost::default(),
             timestamp: SystemTime::now(),
         }
     }
+
+    pub fn with_cost(mut self, cost: RequestCost) -> Self {
+        self.cost = cost;
+        self
+    }
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct PolicyResponse {
     pub block_client: Option<IpAddr>,
     pub block_proxied_client: Option<IpAddr>,
+    /// Set by policies that aggregate clients by subnet, to block the whole subnet.
+    pub block_client_subnet: Option<IpSubnet>,
+    pub block_proxied_client_subnet: Option<IpSubnet>,
 }
 
 pub trait Policy {
@@ -265,6 +444,7 @@ pub trait Policy {
 // not object safe, so we can't use a trait object instead
 pub enum TrafficControlPolicy {
     FreqThreshold(FreqThresholdPolicy),
+    CostThreshold(CostThresholdPolicy),
     NoOp(NoOpPolicy),
     // Test policies below this point
     TestNConnIP(TestNConnIPPolicy),
@@ -276,6 +456,7 @@ impl Policy for TrafficControlPolicy {
         match self {
             TrafficControlPolicy::NoOp(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::FreqThreshold(policy) => policy.handle_tally(tally),
+            TrafficControlPolicy::CostThreshold(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::TestNConnIP(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::TestPanicOnInvocation(policy) => policy.handle_tally(tally),
         }
@@ -285,6 +466,7 @@ impl Policy for TrafficControlPolicy {
         match self {
             TrafficControlPolicy::NoOp(policy) => policy.policy_config(),
             TrafficControlPolicy::FreqThreshold(policy) => policy.policy_config(),
+            TrafficControlPolicy::CostThreshold(policy) => policy.policy_config(),
             TrafficControlPolicy::TestNConnIP(policy) => policy.policy_config(),
             TrafficControlPolicy::TestPanicOnInvocation(policy) => policy.policy_config(),
         }
@@ -304,6 +486,9 @@ impl TrafficControlPolicy {
             PolicyType::FreqThreshold(freq_threshold_config) => Self::FreqThreshold(
                 FreqThresholdPolicy::new(policy_config, freq_threshold_config),
             ),
+            PolicyType::CostThreshold(cost_threshold_config) => Self::CostThreshold(
+                CostThresholdPolicy::new(policy_config, cost_threshold_config),
+            ),
             PolicyType::TestNConnIP(n) => {
                 Self::TestNConnIP(TestNConnIPPolicy::new(policy_config, n).await)
             }
@@ -312,6 +497,87 @@ impl TrafficControlPolicy {
             }
         }
     }
+
+    /// Check that a policy can be created from `policy_type`, without creating it.
+    pub fn validate(policy_type: &PolicyType) -> anyhow::Result<()> {
+        match policy_type {
+            PolicyType::FreqThreshold(FreqThresholdConfig {
+                window_size_secs,
+                update_interval_secs,
+                sketch_capacity,
+                sketch_probability,
+                sketch_tolerance,
+                ..
+            }) => TrafficSketch::validate(
+                Duration::from_secs(*window_size_secs),
+                Duration::from_secs(*update_interval_secs),
+                *sketch_capacity,
+                *sketch_probability,
+                *sketch_tolerance,
+            ),
+            PolicyType::CostThreshold(CostThresholdConfig {
+                ipv4_prefix_len,
+                ipv6_prefix_len,
+                window_size_secs,
+                update_interval_secs,
+                sketch_capacity,
+                sketch_probability,
+                sketch_tolerance,
+                ..
+            }) => {
+                ensure!(
+                    *ipv4_prefix_len <= 32 && *ipv6_prefix_len <= 128,
+                    "Prefix lengths may not be longer than the addresses they apply to"
+                );
+                TrafficSketch::validate(
+                    Duration::from_secs(*window_size_secs),
+                    Duration::from_secs(*update_interval_secs),
+                    *sketch_capacity,
+                    *sketch_probability,
+                    *sketch_tolerance,
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The highest recent direct client rate seen by the policy, if it keeps track.
+    pub fn highest_direct_rate(&self) -> Option<(u64, IpAddr)> {
+        match self {
+            TrafficControlPolicy::FreqThreshold(policy) => policy.highest_direct_rate(),
+            TrafficControlPolicy::CostThreshold(policy) => policy.highest_direct_rate(),
+            _ => None,
+        }
+    }
+
+    /// The highest recent proxied client rate seen by the policy, if it keeps track.
+    pub fn highest_proxied_rate(&self) -> Option<(u64, IpAddr)> {
+        match self {
+            TrafficControlPolicy::FreqThreshold(policy) => policy.highest_proxied_rate(),
+            TrafficControlPolicy::CostThreshold(policy) => policy.highest_proxied_rate(),
+            _ => None,
+        }
+    }
+
+    /// The highest recent direct client rates seen by the policy, highest first, or nothing if
+    /// it does not keep track.
+    pub fn highest_direct_rates(&self) -> Vec<(u64, IpAddr)> {
+        match self {
+            TrafficControlPolicy::FreqThreshold(policy) => policy.sketch.highest_direct_rates(),
+            TrafficControlPolicy::CostThreshold(policy) => policy.sketch.highest_direct_rates(),
+            _ => vec![],
+        }
+    }
+
+    /// The highest recent proxied client rates seen by the policy, highest first, or nothing if
+    /// it does not keep track.
+    pub fn highest_proxied_rates(&self) -> Vec<(u64, IpAddr)> {
+        match self {
+            TrafficControlPolicy::FreqThreshold(policy) => policy.sketch.highest_proxied_rates(),
+            TrafficControlPolicy::CostThreshold(policy) => policy.sketch.highest_proxied_rates(),
+            _ => vec![],
+        }
+    }
 }
 
 ////////////// *** Policy definitions *** //////////////
@@ -408,6 +674,7 @@ impl FreqThresholdPolicy {
         PolicyResponse {
             block_client,
             block_proxied_client,
+            ..Default::default()
         }
     }
 
@@ -416,6 +683,124 @@ impl FreqThresholdPolicy {
     }
 }
 
+/// Like [FreqThresholdPolicy], but each request is tallied by what it cost to serve (according to
+/// the configured weights) rather than counting one, and clients are grouped by subnet, so that an
+/// attacker cannot avoid being blocked by spreading cheap-to-send but expensive-to-serve requests
+/// over many addresses in a subnet they control.
+pub struct CostThresholdPolicy {
+    config: PolicyConfig,
+    sketch: TrafficSketch,
+    client_threshold: u64,
+    proxied_client_threshold: u64,
+    cost_weights: CostWeights,
+    ipv4_prefix_len: u8,
+    ipv6_prefix_len: u8,
+    /// See [FreqThresholdPolicy::salt].
+    salt: u64,
+}
+
+impl CostThresholdPolicy {
+    pub fn new(
+        config: PolicyConfig,
+        CostThresholdConfig {
+            client_threshold,
+            proxied_client_threshold,
+            cost_weights,
+            ipv4_prefix_len,
+            ipv6_prefix_len,
+            window_size_secs,
+            update_interval_secs,
+            sketch_capacity,
+            sketch_probability,
+            sketch_tolerance,
+        }: CostThresholdConfig,
+    ) -> Self {
+        assert!(
+            ipv4_prefix_len <= 32 && ipv6_prefix_len <= 128,
+            "Prefix lengths may not be longer than the addresses they apply to"
+        );
+        let sketch = TrafficSketch::new(
+            Duration::from_secs(window_size_secs),
+            Duration::from_secs(update_interval_secs),
+            sketch_capacity,
+            sketch_probability,
+            sketch_tolerance,
+            HIGHEST_RATES_CAPACITY,
+        )
+        .with_count_scale(COST_SCALE);
+        Self {
+            config,
+            sketch,
+            client_threshold,
+            proxied_client_threshold,
+            cost_weights,
+            ipv4_prefix_len,
+            ipv6_prefix_len,
+            salt: rand::random(),
+        }
+    }
+
+    pub fn highest_direct_rate(&self) -> Option<(u64, IpAddr)> {
+        self.sketch.highest_direct_rate()
+    }
+
+    pub fn highest_proxied_rate(&self) -> Option<(u64, IpAddr)> {
+        self.sketch.highest_proxied_rate()
+    }
+
+    pub fn handle_tally(&mut self, tally: TrafficTally) -> PolicyResponse {
+        // `as` saturates, so outrageously expensive requests count as the most the sketch can
+        // tally at once.
+        let count = (tally.cost.weigh(&self.cost_weights) * COST_SCALE).round() as u32;
+        let block_client_subnet = tally.direct.and_then(|source| {
+            self.tally_subnet(source, ClientType::Direct, count, self.client_threshold)
+        });
+        let block_proxied_client_subnet = tally.through_fullnode.and_then(|source| {
+            self.tally_subnet(
+                source,
+                ClientType::ThroughFullnode,
+                count,
+                self.proxied_client_threshold,
+            )
+        });
+        PolicyResponse {
+            block_client_subnet,
+            block_proxied_client_subnet,
+            ..Default::default()
+        }
+    }
+
+    /// Add `count` to the tally of the subnet containing `source`, and return the subnet if its
+    /// rate is now over `threshold`.
+    fn tally_subnet(
+        &mut self,
+        source: IpAddr,
+        client_type: ClientType,
+        count: u32,
+        threshold: u64,
+    ) -> Option<IpSubnet> {
+        let subnet = IpSubnet::containing(source, self.ipv4_prefix_len, self.ipv6_prefix_len);
+        let key = SketchKey {
+            salt: self.salt,
+            ip_addr: subnet.network(),
+            client_type,
+        };
+        self.sketch.add_count(&key, count);
+        let cost_rate = self.sketch.get_request_rate(&key);
+        trace!(
+            "CostThresholdPolicy handling tally -- cost_rate: {:?}, threshold: {:?}, subnet: {}",
+            cost_rate,
+            threshold,
+            subnet,
+        );
+        (cost_rate >= threshold as f64).then_some(subnet)
+    }
+
+    fn policy_config(&self) -> &PolicyConfig {
+        &self.config
+    }
+}
+
 ////////////// *** Test policies below this point *** //////////////
 
 #[derive(Clone)]
@@ -477,6 +862,7 @@ impl TestNConnIPPolicy {
                 None
             },
             block_proxied_client: None,
+            ..Default::default()
         }
     }
 
@@ -543,6 +929,7 @@ mod tests {
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4))),
             error_info: None,
             spam_weight: Weight::one(),
+            cost: RequestCost::default(),
             timestamp: SystemTime::now(),
         };
         let bob = TrafficTally {
@@ -550,6 +937,7 @@ mod tests {
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(4, 3, 2, 1))),
             error_info: None,
             spam_weight: Weight::one(),
+            cost: RequestCost::default(),
             timestamp: SystemTime::now(),
         };
         let charlie = TrafficTally {
@@ -557,6 +945,7 @@ mod tests {
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(5, 6, 7, 8))),
             error_info: None,
             spam_weight: Weight::one(),
+            cost: RequestCost::default(),
             timestamp: SystemTime::now(),
         };
 
@@ -674,6 +1063,107 @@ mod tests {
         assert_eq!(proxied_rate, 1);
     }
 
+    #[sim_test]
+    async fn test_cost_threshold_policy() {
+        // Block subnets (of /24 for IPv4) once they average 10ms of execution time per second,
+        // over a 5 second window, regardless of how many requests that takes.
+        let mut policy = CostThresholdPolicy::new(
+            PolicyConfig::default(),
+            CostThresholdConfig {
+                client_threshold: 10,
+                proxied_client_threshold: 10,
+                cost_weights: CostWeights {
+                    request: 0.0,
+                    execution_time_ms: 1.0,
+                    ..Default::default()
+                },
+                ipv4_prefix_len: 24,
+                window_size_secs: 5,
+                update_interval_secs: 1,
+                ..Default::default()
+            },
+        );
+        let tally = |ip: [u8; 4], execution_time_ms: u64| {
+            TrafficTally::new(Some(IpAddr::from(ip)), None, None, Weight::one()).with_cost(
+                RequestCost {
+                    execution_time: Duration::from_millis(execution_time_ms),
+                    ..Default::default()
+                },
+            )
+        };
+
+        // Plenty of cheap requests are fine.
+        for _ in 0..100 {
+            let response = policy.handle_tally(tally([1, 2, 4, 1], 0));
+            assert_eq!(response.block_client_subnet, None);
+        }
+
+        // Expensive requests from different addresses in the same subnet add up: 40ms over the
+        // window is 8ms per second.
+        for i in 0..4 {
+            let response = policy.handle_tally(tally([1, 2, 3, i], 10));
+            assert_eq!(response.block_client_subnet, None, "Blocked at i = {}", i);
+        }
+
+        // ...until they reach the threshold, and the whole subnet is blocked.
+        let response = policy.handle_tally(tally([1, 2, 3, 200], 10));
+        assert_eq!(response.block_client, None);
+        assert_eq!(
+            response.block_client_subnet,
+            Some(IpSubnet::containing(
+                IpAddr::V4(Ipv4Addr::new(1, 2, 3, 0)),
+                24,
+                128
+            ))
+        );
+        assert_eq!(
+            policy.highest_direct_rate(),
+            Some((10, IpAddr::V4(Ipv4Addr::new(1, 2, 3, 0))))
+        );
+
+        // Other subnets are unaffected.
+        let response = policy.handle_tally(tally([1, 2, 4, 1], 10));
+        assert_eq!(response.block_client_subnet, None);
+    }
+
+    #[test]
+    fn test_ip_subnet() {
+        let v4 = IpAddr::V4(Ipv4Addr::new(192, 168, 37, 201));
+        assert_eq!(
+            IpSubnet::containing(v4, 24, 64).to_string(),
+            "192.168.37.0/24"
+        );
+        assert_eq!(IpSubnet::containing(v4, 0, 64).to_string(), "0.0.0.0/0");
+        assert_eq!(IpSubnet::host(v4).to_string(), "192.168.37.201/32");
+        assert_eq!(IpSubnet::containing(v4, 40, 64), IpSubnet::host(v4));
+
+        let v6: IpAddr = "2001:db8:abcd:12:1:2:3:4".parse().unwrap();
+        assert_eq!(
+            IpSubnet::containing(v6, 24, 64).to_string(),
+            "2001:db8:abcd:12::/64"
+        );
+        assert_eq!(
+            IpSubnet::host(v6).to_string(),
+            "2001:db8:abcd:12:1:2:3:4/128"
+        );
+
+        assert_eq!(
+            "192.168.37.201".parse::<IpSubnet>().unwrap(),
+            IpSubnet::host(v4)
+        );
+        assert_eq!(
+            "192.168.37.201/24".parse::<IpSubnet>().unwrap(),
+            IpSubnet::containing(v4, 24, 128)
+        );
+        assert_eq!(
+            "2001:db8:abcd:12::/64".parse::<IpSubnet>().unwrap(),
+            IpSubnet::containing(v6, 32, 64)
+        );
+        assert!("192.168.37.201/33".parse::<IpSubnet>().is_err());
+        assert!("192.168.37/24".parse::<IpSubnet>().is_err());
+        assert!("192.168.37.201/x".parse::<IpSubnet>().is_err());
+    }
+
     #[sim_test]
     async fn test_traffic_sketch_mem_estimate() {
         // Test for getting a rough estimate of memory usage for the traffic sketch
@@ -33,6 +33,7 @@ use crate::{
     authority_aggregator::AuthorityAggregator,
     authority_client::AuthorityAPI,
     quorum_driver::{reconfig_observer::ReconfigObserver, AuthorityAggregatorUpdatable},
+    transaction_lifecycle::{LifecycleStage, TransactionLifecycleTracker},
 };
 
 /// Options for submitting a transaction.
@@ -47,21 +48,26 @@ pub struct TransactionDriver<A: Clone> {
     authority_aggregator: ArcSwap<AuthorityAggregator<A>>,
     state: Mutex<State>,
     metrics: Arc<TransactionDriverMetrics>,
+    transaction_lifecycle: Option<Arc<TransactionLifecycleTracker>>,
 }
 
 impl<A> TransactionDriver<A>
 where
     A: AuthorityAPI + Send + Sync + 'static + Clone,
 {
+    /// Submissions and retries of each transaction are recorded in `transaction_lifecycle`, if it
+    /// is provided.
     pub fn new(
         authority_aggregator: Arc<AuthorityAggregator<A>>,
         reconfig_observer: Arc<dyn ReconfigObserver<A> + Sync + Send>,
         metrics: Arc<TransactionDriverMetrics>,
+        transaction_lifecycle: Option<Arc<TransactionLifecycleTracker>>,
     ) -> Arc<Self> {
         let driver = Arc::new(Self {
             authority_aggregator: ArcSwap::new(authority_aggregator),
             state: Mutex::new(State::new()),
             metrics,
+            transaction_lifecycle,
         });
         driver.enable_reconfig(reconfig_observer);
         driver
@@ -76,7 +82,16 @@ where
         let tx_digest = request.transaction.digest();
         let is_single_writer_tx = !request.transaction.contains_shared_object();
         let timer = Instant::now();
+        let mut attempts = 0;
         loop {
+            attempts += 1;
+            if let Some(lifecycle) = &self.transaction_lifecycle {
+                lifecycle.record_with_detail(
+                    *tx_digest,
+                    LifecycleStage::SubmittedToValidators,
+                    || format!("attempt {attempts}"),
+                );
+            }
             match self.submit_transaction_once(&request, &options).await {
                 Ok(resp) => {
                     let settlement_finality_latency = timer.elapsed().as_secs_f64();
@@ -92,6 +107,11 @@ where
                 }
                 Err(e) => {
                     tracing::warn!("Failed to submit transaction {tx_digest}: {}", e);
+                    if let Some(lifecycle) = &self.transaction_lifecycle {
+                        lifecycle.record_with_detail(*tx_digest, LifecycleStage::Retried, || {
+                            e.to_string()
+                        });
+                    }
                     sleep(Duration::from_secs(1)).await;
                 }
             }
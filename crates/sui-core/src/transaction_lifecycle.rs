@@ -0,0 +1,180 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use moka::sync::Cache;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::SystemTime;
+use sui_types::base_types::TransactionDigest;
+
+/// Number of transactions whose timelines are kept. Older timelines are evicted as new
+/// transactions are recorded.
+const MAX_TRACKED_TRANSACTIONS: u64 = 100_000;
+
+/// Number of events kept per transaction. A transaction that keeps being deferred could otherwise
+/// grow its timeline without bound, so once it is full, only the first events are kept (the most
+/// recent one replaces the last).
+const MAX_EVENTS_PER_TRANSACTION: usize = 64;
+
+/// A point in the path of a transaction through this node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LifecycleStage {
+    /// The transaction (or its certificate) was received from a client, and passed verification.
+    Received,
+    /// This validator signed the transaction.
+    Signed,
+    /// The transaction was submitted to validators by this fullnode, on behalf of a client.
+    SubmittedToValidators,
+    /// Submitting the transaction to validators failed, and will be retried after a backoff.
+    Retried,
+    /// The transaction was submitted to consensus by this node.
+    SubmittedToConsensus,
+    /// The transaction was sequenced in a consensus commit.
+    Sequenced,
+    /// The transaction was deferred to a later consensus commit.
+    Deferred,
+    /// The transaction was cancelled, e.g. due to shared object congestion.
+    Cancelled,
+    /// The transaction was enqueued for execution, but some of its inputs are not available yet.
+    WaitingOnInputs,
+    /// The transaction was enqueued for execution, with all of its inputs available.
+    Enqueued,
+    /// The execution driver picked up the transaction, and is about to execute it.
+    Dequeued,
+    /// The transaction was executed by this node.
+    Executed,
+    /// The transaction was included in a checkpoint executed by this node.
+    Checkpointed,
+    /// The transaction was finalized by the quorum driver, on behalf of a client of this node.
+    Finalized,
+    /// The quorum driver failed to finalize the transaction.
+    Failed,
+}
+
+#[derive(Clone, Debug)]
+pub struct LifecycleEvent {
+    pub stage: LifecycleStage,
+    pub timestamp: SystemTime,
+    pub detail: Option<String>,
+}
+
+/// Records when each transaction reached each [LifecycleStage] on this node, so that operators
+/// can see where a transaction is stuck, without having to correlate logs from every component it
+/// passes through. Timelines are only kept in memory, for a bounded number of transactions.
+///
+/// Unless tracking is enabled in the node config, recording is a no-op.
+pub struct TransactionLifecycleTracker {
+    timelines: Option<Cache<TransactionDigest, Arc<Mutex<Vec<LifecycleEvent>>>>>,
+}
+
+impl TransactionLifecycleTracker {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            timelines: enabled.then(|| Cache::new(MAX_TRACKED_TRANSACTIONS)),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.timelines.is_some()
+    }
+
+    pub fn record(&self, digest: TransactionDigest, stage: LifecycleStage) {
+        self.record_event(digest, stage, None);
+    }
+
+    /// Like [Self::record], with a description of the event. `detail` is only called if tracking
+    /// is enabled.
+    pub fn record_with_detail(
+        &self,
+        digest: TransactionDigest,
+        stage: LifecycleStage,
+        detail: impl FnOnce() -> String,
+    ) {
+        if self.is_enabled() {
+            self.record_event(digest, stage, Some(detail()));
+        }
+    }
+
+    fn record_event(
+        &self,
+        digest: TransactionDigest,
+        stage: LifecycleStage,
+        detail: Option<String>,
+    ) {
+        let Some(timelines) = &self.timelines else {
+            return;
+        };
+
+        let timeline = timelines.get_with(digest, || Arc::new(Mutex::new(Vec::new())));
+
+        let event = LifecycleEvent {
+            stage,
+            timestamp: SystemTime::now(),
+            detail,
+        };
+
+        let mut timeline = timeline.lock();
+        if timeline.len() < MAX_EVENTS_PER_TRANSACTION {
+            timeline.push(event);
+        } else {
+            *timeline.last_mut().unwrap() = event;
+        }
+    }
+
+    /// The events recorded for `digest`, in the order they happened, or `None` if the transaction
+    /// has not been seen (or its timeline has been evicted, or tracking is disabled).
+    pub fn get(&self, digest: &TransactionDigest) -> Option<Vec<LifecycleEvent>> {
+        self.timelines
+            .as_ref()?
+            .get(digest)
+            .map(|timeline| timeline.lock().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_timeline() {
+        let tracker = TransactionLifecycleTracker::new(true);
+        let digest = TransactionDigest::random();
+        assert!(tracker.get(&digest).is_none());
+
+        tracker.record(digest, LifecycleStage::Received);
+        tracker.record_with_detail(digest, LifecycleStage::Sequenced, || "round 3".to_string());
+        tracker.record(TransactionDigest::random(), LifecycleStage::Executed);
+
+        let timeline = tracker.get(&digest).unwrap();
+        let stages: Vec<_> = timeline.iter().map(|e| e.stage).collect();
+        assert_eq!(
+            stages,
+            vec![LifecycleStage::Received, LifecycleStage::Sequenced]
+        );
+        assert_eq!(timeline[1].detail.as_deref(), Some("round 3"));
+        assert!(timeline[0].timestamp <= timeline[1].timestamp);
+
+        // Repeated deferrals don't grow the timeline without bound.
+        for _ in 0..2 * MAX_EVENTS_PER_TRANSACTION {
+            tracker.record(digest, LifecycleStage::Deferred);
+        }
+        tracker.record(digest, LifecycleStage::Executed);
+        let timeline = tracker.get(&digest).unwrap();
+        assert_eq!(timeline.len(), MAX_EVENTS_PER_TRANSACTION);
+        assert_eq!(timeline[0].stage, LifecycleStage::Received);
+        assert_eq!(timeline.last().unwrap().stage, LifecycleStage::Executed);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let tracker = TransactionLifecycleTracker::new(false);
+        let digest = TransactionDigest::random();
+
+        tracker.record(digest, LifecycleStage::Received);
+        tracker.record_with_detail(digest, LifecycleStage::Executed, || {
+            panic!("detail should not be computed when tracking is disabled")
+        });
+        assert!(!tracker.is_enabled());
+        assert!(tracker.get(&digest).is_none());
+    }
+}
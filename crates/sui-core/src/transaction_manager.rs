@@ -27,6 +27,7 @@ use tokio::sync::mpsc::UnboundedSender;
 use tokio::time::Instant;
 use tracing::{error, info, instrument, trace, warn};
 
+use crate::transaction_lifecycle::{LifecycleStage, TransactionLifecycleTracker};
 use crate::{
     authority::authority_per_epoch_store::AuthorityPerEpochStore, execution_cache::ObjectCacheRead,
 };
@@ -54,6 +55,7 @@ pub struct TransactionManager {
     transaction_cache_read: Arc<dyn TransactionCacheRead>,
     tx_ready_certificates: UnboundedSender<PendingCertificate>,
     metrics: Arc<AuthorityMetrics>,
+    transaction_lifecycle: Arc<TransactionLifecycleTracker>,
     // inner is a doubly nested lock so that we can enforce that an outer lock (for read) is held
     // before the inner lock (for read or write) can be acquired. During reconfiguration, we acquire
     // the outer lock for write, to ensure that no other threads can be running while we reconfigure.
@@ -342,11 +344,13 @@ impl TransactionManager {
         epoch_store: &AuthorityPerEpochStore,
         tx_ready_certificates: UnboundedSender<PendingCertificate>,
         metrics: Arc<AuthorityMetrics>,
+        transaction_lifecycle: Arc<TransactionLifecycleTracker>,
     ) -> TransactionManager {
         let transaction_manager = TransactionManager {
             object_cache_read,
             transaction_cache_read,
             metrics: metrics.clone(),
+            transaction_lifecycle,
             inner: RwLock::new(RwLock::new(Inner::new(epoch_store.epoch(), metrics))),
             tx_ready_certificates,
         };
@@ -635,12 +639,20 @@ impl TransactionManager {
                     .transaction_manager_num_enqueued_certificates
                     .with_label_values(&["ready"])
                     .inc();
+                self.transaction_lifecycle
+                    .record(digest, LifecycleStage::Enqueued);
                 pending_cert.stats.ready_time = Some(Instant::now());
                 // Send to execution driver for execution.
                 self.certificate_ready(&mut inner, pending_cert);
                 continue;
             }
 
+            self.transaction_lifecycle.record_with_detail(
+                digest,
+                LifecycleStage::WaitingOnInputs,
+                || format!("{:?}", pending_cert.waiting_input_objects),
+            );
+
             assert!(
                 inner
                     .pending_certificates
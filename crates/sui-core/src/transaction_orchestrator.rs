@@ -13,6 +13,7 @@ use crate::authority_aggregator::AuthorityAggregator;
 use crate::authority_client::{AuthorityAPI, NetworkAuthorityClient};
 use crate::quorum_driver::reconfig_observer::{OnsiteReconfigObserver, ReconfigObserver};
 use crate::quorum_driver::{QuorumDriverHandler, QuorumDriverHandlerBuilder, QuorumDriverMetrics};
+use crate::transaction_lifecycle::LifecycleStage;
 use futures::future::{select, Either, Future};
 use futures::FutureExt;
 use mysten_common::sync::notify_read::NotifyRead;
@@ -105,6 +106,7 @@ where
             QuorumDriverHandlerBuilder::new(validators.clone(), metrics.clone())
                 .with_notifier(notifier.clone())
                 .with_reconfig_observer(reconfig_observer.clone())
+                .with_transaction_lifecycle(validator_state.transaction_lifecycle.clone())
                 .start(),
         );
 
@@ -235,6 +237,8 @@ where
         let (_in_flight_metrics_guards, good_response_metrics) = self.update_metrics(&transaction);
         let tx_digest = *transaction.digest();
         debug!(?tx_digest, "TO Received transaction execution request.");
+        let lifecycle = &self.validator_state.transaction_lifecycle;
+        lifecycle.record(tx_digest, LifecycleStage::Received);
 
         let (_e2e_latency_timer, _txn_finality_timer) = if transaction.contains_shared_object() {
             (
@@ -264,12 +268,16 @@ where
             .await
             .map_err(|e| {
                 warn!(?tx_digest, "QuorumDriverInternalError: {e:?}");
+                lifecycle.record_with_detail(tx_digest, LifecycleStage::Failed, || e.to_string());
                 QuorumDriverError::QuorumDriverInternalError(e)
             })?;
 
         let Ok(result) = timeout(WAIT_FOR_FINALITY_TIMEOUT, ticket).await else {
             debug!(?tx_digest, "Timeout waiting for transaction finality.");
             self.metrics.wait_for_finality_timeout.inc();
+            lifecycle.record_with_detail(tx_digest, LifecycleStage::Failed, || {
+                "timed out waiting for finality".to_string()
+            });
             return Err(QuorumDriverError::TimeoutBeforeFinality);
         };
         add_server_timing("wait_for_finality");
@@ -281,11 +289,16 @@ where
         match result {
             Err(err) => {
                 warn!(?tx_digest, "QuorumDriverInternalError: {err:?}");
+                lifecycle.record_with_detail(tx_digest, LifecycleStage::Failed, || err.to_string());
                 Err(QuorumDriverError::QuorumDriverInternalError(err))
             }
-            Ok(Err(err)) => Err(err),
+            Ok(Err(err)) => {
+                lifecycle.record_with_detail(tx_digest, LifecycleStage::Failed, || err.to_string());
+                Err(err)
+            }
             Ok(Ok(response)) => {
                 good_response_metrics.inc();
+                lifecycle.record(tx_digest, LifecycleStage::Finalized);
                 Ok((transaction, response))
             }
         }
@@ -0,0 +1,270 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::stake_with_validator;
+use sui_macros::sim_test;
+use sui_rpc_api::proto::rpc::v2beta::event_filter;
+use sui_rpc_api::proto::rpc::v2beta::ledger_service_client::LedgerServiceClient;
+use sui_rpc_api::proto::rpc::v2beta::EventFilter;
+use sui_rpc_api::proto::rpc::v2beta::IndexedEvent;
+use sui_rpc_api::proto::rpc::v2beta::ListEventsRequest;
+use sui_rpc_api::proto::rpc::v2beta::MoveModuleFilter;
+use sui_rpc_api::proto::rpc::v2beta::TimeRangeFilter;
+use sui_rpc_api::proto::types::proto_to_timestamp_ms;
+use sui_rpc_api::proto::types::timestamp_ms_to_proto;
+use test_cluster::TestClusterBuilder;
+use tonic::transport::Channel;
+
+/// Identifies an event by its transaction digest and index within the transaction.
+fn event_id(event: &IndexedEvent) -> (String, u64) {
+    (
+        event.transaction_digest.clone().unwrap(),
+        event.event_index.unwrap(),
+    )
+}
+
+/// Fetch every event matching `request`, one page of `page_size` events at a time.
+async fn list_all_events(
+    grpc_client: &mut LedgerServiceClient<Channel>,
+    request: ListEventsRequest,
+    page_size: u32,
+) -> Vec<IndexedEvent> {
+    let mut events = vec![];
+    let mut page_token = None;
+    loop {
+        let response = grpc_client
+            .list_events(ListEventsRequest {
+                page_size: Some(page_size),
+                page_token,
+                ..request.clone()
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(response.events.len() <= page_size as usize);
+        events.extend(response.events);
+
+        page_token = response.next_page_token;
+        if page_token.is_none() {
+            break events;
+        }
+    }
+}
+
+#[sim_test]
+async fn list_events() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let transaction_digest = stake_with_validator(&test_cluster).await;
+    let sender = test_cluster.get_address_0();
+
+    let mut grpc_client = LedgerServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let filter = EventFilter {
+        filter: Some(event_filter::Filter::Transaction(
+            transaction_digest.to_string(),
+        )),
+    };
+
+    // The indexes are only updated once the transaction has been included in a checkpoint
+    let events = tokio::time::timeout(std::time::Duration::from_secs(30), async {
+        loop {
+            if let Ok(response) = grpc_client
+                .list_events(ListEventsRequest {
+                    filter: Some(filter.clone()),
+                    ..Default::default()
+                })
+                .await
+            {
+                break response.into_inner().events;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    assert!(!events.is_empty());
+    for (idx, event) in events.iter().enumerate() {
+        assert_eq!(
+            event.transaction_digest,
+            Some(transaction_digest.to_string())
+        );
+        assert_eq!(event.event_index, Some(idx as u64));
+        assert!(event.checkpoint.is_some());
+        assert!(event.timestamp.is_some());
+        assert!(event.event.is_some());
+    }
+    let ids = events.iter().map(event_id).collect::<Vec<_>>();
+    let timestamp_ms = proto_to_timestamp_ms(events[0].timestamp.unwrap()).unwrap();
+
+    // Paging through the transaction's events, in either direction, yields the same events
+    let request = ListEventsRequest {
+        filter: Some(filter.clone()),
+        ..Default::default()
+    };
+    let paged = list_all_events(&mut grpc_client, request.clone(), 1).await;
+    assert_eq!(paged.iter().map(event_id).collect::<Vec<_>>(), ids);
+
+    let request = ListEventsRequest {
+        descending: Some(true),
+        ..request
+    };
+    let mut paged = list_all_events(&mut grpc_client, request, 1).await;
+    paged.reverse();
+    assert_eq!(paged.iter().map(event_id).collect::<Vec<_>>(), ids);
+
+    // Staking emits a `0x3::validator::StakingRequestEvent`
+    let response = grpc_client
+        .list_events(ListEventsRequest {
+            filter: Some(EventFilter {
+                filter: Some(event_filter::Filter::MoveEventModule(MoveModuleFilter {
+                    package: Some("0x3".to_owned()),
+                    module: Some("validator".to_owned()),
+                })),
+            }),
+            descending: Some(true),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.transaction_digest == Some(transaction_digest.to_string())));
+
+    // Paging through an indexed filter yields the same events as a single page
+    let request = ListEventsRequest {
+        filter: Some(EventFilter {
+            filter: Some(event_filter::Filter::MoveEventType(
+                "0x3::validator::StakingRequestEvent".to_owned(),
+            )),
+        }),
+        ..Default::default()
+    };
+    let events = list_all_events(&mut grpc_client, request.clone(), 1000).await;
+    assert!(events.iter().any(|event| event_id(event) == ids[0]));
+    for event in &events {
+        let event_type = event.event.as_ref().unwrap().event_type.as_ref().unwrap();
+        assert!(
+            event_type.ends_with("::validator::StakingRequestEvent"),
+            "{event_type}"
+        );
+    }
+    let paged = list_all_events(&mut grpc_client, request, 1).await;
+    assert_eq!(
+        paged.iter().map(event_id).collect::<Vec<_>>(),
+        events.iter().map(event_id).collect::<Vec<_>>()
+    );
+
+    // Filter by the sender of the transaction
+    let response = grpc_client
+        .list_events(ListEventsRequest {
+            filter: Some(EventFilter {
+                filter: Some(event_filter::Filter::Sender(sender.to_string())),
+            }),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event_id(event) == ids[0]));
+
+    // Filter by a time range covering only the checkpoint of the transaction
+    let request = ListEventsRequest {
+        filter: Some(EventFilter {
+            filter: Some(event_filter::Filter::TimeRange(TimeRangeFilter {
+                start_time: Some(timestamp_ms_to_proto(timestamp_ms)),
+                end_time: Some(timestamp_ms_to_proto(timestamp_ms + 1)),
+            })),
+        }),
+        ..Default::default()
+    };
+    let events = list_all_events(&mut grpc_client, request.clone(), 1000).await;
+    assert!(events.iter().any(|event| event_id(event) == ids[0]));
+    for event in &events {
+        assert_eq!(
+            proto_to_timestamp_ms(event.timestamp.unwrap()).unwrap(),
+            timestamp_ms
+        );
+    }
+    let mut paged = list_all_events(
+        &mut grpc_client,
+        ListEventsRequest {
+            descending: Some(true),
+            ..request
+        },
+        1,
+    )
+    .await;
+    paged.reverse();
+    assert_eq!(
+        paged.iter().map(event_id).collect::<Vec<_>>(),
+        events.iter().map(event_id).collect::<Vec<_>>()
+    );
+
+    // Paging through all events from the start of the chain yields the same events as a single
+    // page. New events are only ever appended, so the pages can be compared to a prefix.
+    let events = grpc_client
+        .list_events(ListEventsRequest {
+            page_size: Some(1000),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .events;
+    assert!(events.iter().any(|event| event_id(event) == ids[0]));
+    let mut paged = vec![];
+    let mut page_token = None;
+    while paged.len() < events.len() {
+        let response = grpc_client
+            .list_events(ListEventsRequest {
+                page_size: Some(2),
+                page_token,
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(response.events.len() <= 2);
+        paged.extend(response.events);
+        page_token = Some(response.next_page_token.unwrap());
+    }
+    paged.truncate(events.len());
+    assert_eq!(
+        paged.iter().map(event_id).collect::<Vec<_>>(),
+        events.iter().map(event_id).collect::<Vec<_>>()
+    );
+
+    // A filter without a variant is invalid
+    let error = grpc_client
+        .list_events(ListEventsRequest {
+            filter: Some(EventFilter { filter: None }),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), tonic::Code::InvalidArgument);
+
+    // A module filter without a module is invalid
+    let error = grpc_client
+        .list_events(ListEventsRequest {
+            filter: Some(EventFilter {
+                filter: Some(event_filter::Filter::MoveEventModule(MoveModuleFilter {
+                    package: Some("0x3".to_owned()),
+                    module: None,
+                })),
+            }),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), tonic::Code::InvalidArgument);
+}
@@ -0,0 +1,115 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::stake_with_validator;
+use sui_macros::sim_test;
+use sui_rpc_api::proto::rpc::v2beta::ledger_service_client::LedgerServiceClient;
+use sui_rpc_api::proto::rpc::v2beta::transaction_filter;
+use sui_rpc_api::proto::rpc::v2beta::ListTransactionsRequest;
+use sui_rpc_api::proto::rpc::v2beta::MoveFunctionFilter;
+use sui_rpc_api::proto::rpc::v2beta::TransactionFilter;
+use test_cluster::TestClusterBuilder;
+
+#[sim_test]
+async fn list_transactions() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let transaction_digest = stake_with_validator(&test_cluster).await;
+    let sender = test_cluster.get_address_0();
+
+    let mut grpc_client = LedgerServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let filter = TransactionFilter {
+        filter: Some(transaction_filter::Filter::Sender(sender.to_string())),
+    };
+
+    // The indexes are only updated once the transaction has been included in a checkpoint
+    let transactions = tokio::time::timeout(std::time::Duration::from_secs(30), async {
+        loop {
+            let response = grpc_client
+                .list_transactions(ListTransactionsRequest {
+                    filter: Some(filter.clone()),
+                    ..Default::default()
+                })
+                .await
+                .unwrap()
+                .into_inner();
+            if !response.transactions.is_empty() {
+                break response.transactions;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0].digest, Some(transaction_digest.to_string()));
+
+    // Filter by the function called
+    let response = grpc_client
+        .list_transactions(ListTransactionsRequest {
+            filter: Some(TransactionFilter {
+                filter: Some(transaction_filter::Filter::MoveFunction(
+                    MoveFunctionFilter {
+                        package: Some("0x3".to_owned()),
+                        module: Some("sui_system".to_owned()),
+                        function: Some("request_add_stake".to_owned()),
+                    },
+                )),
+            }),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.transactions.len(), 1);
+    assert_eq!(
+        response.transactions[0].digest,
+        Some(transaction_digest.to_string())
+    );
+
+    // A function without a module is invalid
+    grpc_client
+        .list_transactions(ListTransactionsRequest {
+            filter: Some(TransactionFilter {
+                filter: Some(transaction_filter::Filter::MoveFunction(
+                    MoveFunctionFilter {
+                        package: Some("0x3".to_owned()),
+                        module: None,
+                        function: Some("request_add_stake".to_owned()),
+                    },
+                )),
+            }),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+
+    // Paginate through the first few transactions of the chain
+    let first_page = grpc_client
+        .list_transactions(ListTransactionsRequest {
+            page_size: Some(2),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(first_page.transactions.len(), 2);
+    let second_page = grpc_client
+        .list_transactions(ListTransactionsRequest {
+            page_size: Some(2),
+            page_token: first_page.next_page_token,
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(second_page.transactions.len(), 2);
+    assert_ne!(
+        first_page.transactions[1].digest,
+        second_page.transactions[0].digest
+    );
+}
@@ -2,3 +2,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod get_service_info;
+mod list_events;
+mod list_transactions;
@@ -6,6 +6,7 @@ use sui_macros::sim_test;
 use sui_rpc_api::field_mask::FieldMask;
 use sui_rpc_api::field_mask::FieldMaskUtil;
 use sui_rpc_api::proto::rpc::v2alpha::subscription_service_client::SubscriptionServiceClient;
+use sui_rpc_api::proto::rpc::v2alpha::CheckpointFilter;
 use sui_rpc_api::proto::rpc::v2alpha::SubscribeCheckpointsRequest;
 use test_cluster::TestClusterBuilder;
 use tokio_stream::StreamExt;
@@ -22,6 +23,7 @@ async fn subscribe_checkpoint() {
 
     let request = SubscribeCheckpointsRequest {
         read_mask: Some(FieldMask::from_str("sequence_number")),
+        ..Default::default()
     };
 
     let mut stream = client
@@ -55,3 +57,102 @@ async fn subscribe_checkpoint() {
 
     assert!(count >= 50);
 }
+
+#[sim_test]
+async fn subscribe_checkpoint_from_start() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut client = SubscriptionServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let request = SubscribeCheckpointsRequest {
+        read_mask: Some(FieldMask::from_str("sequence_number")),
+        start_checkpoint: Some(0),
+        ..Default::default()
+    };
+
+    let mut stream = client
+        .subscribe_checkpoints(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    // The stream should start at genesis and continue, without gaps, through the backfill and
+    // into the live stream of checkpoints
+    let mut expected = 0;
+    while let Some(item) = stream.next().await {
+        let cursor = item.unwrap().cursor.unwrap();
+        assert_eq!(cursor, expected);
+        expected += 1;
+
+        if expected > 50 {
+            break;
+        }
+    }
+
+    assert!(expected > 50);
+}
+
+#[sim_test]
+async fn subscribe_checkpoint_with_filter() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let sender = test_cluster
+        .wallet
+        .get_all_accounts_and_gas_objects()
+        .await
+        .unwrap()[0]
+        .0;
+    let transaction_digest = transfer_coin(&test_cluster.wallet).await;
+
+    let mut client = SubscriptionServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let request = SubscribeCheckpointsRequest {
+        read_mask: Some(FieldMask::from_paths([
+            "sequence_number",
+            "transactions.digest",
+            "transactions.transaction.sender",
+        ])),
+        start_checkpoint: Some(0),
+        filter: Some(CheckpointFilter {
+            senders: vec![sender.to_string()],
+            ..Default::default()
+        }),
+    };
+
+    let mut stream = client
+        .subscribe_checkpoints(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    let mut found = false;
+    let mut count = 0;
+    while let Some(item) = stream.next().await {
+        let checkpoint = item.unwrap().checkpoint.unwrap();
+
+        for transaction in checkpoint.transactions {
+            let transaction_sender = transaction.transaction.unwrap().sender.unwrap();
+            assert_eq!(
+                transaction_sender
+                    .parse::<sui_sdk_types::Address>()
+                    .unwrap(),
+                sui_sdk_types::Address::from(sender)
+            );
+
+            if transaction.digest.unwrap() == transaction_digest.to_string() {
+                found = true;
+            }
+        }
+
+        count += 1;
+        if found || count > 50 {
+            break;
+        }
+    }
+
+    assert!(found);
+}
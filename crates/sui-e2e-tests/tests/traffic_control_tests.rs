@@ -8,12 +8,15 @@ use core::panic;
 use fastcrypto::encoding::Base64;
 use jsonrpsee::{core::client::ClientT, rpc_params};
 use std::fs::File;
+use std::net::IpAddr;
 use std::num::NonZeroUsize;
 use std::time::Duration;
 use sui_core::authority_client::make_network_authority_clients_with_network_config;
 use sui_core::authority_client::AuthorityAPI;
 use sui_core::traffic_controller::{
-    nodefw_test_server::NodeFwTestServer, TrafficController, TrafficSim,
+    nodefw_test_server::NodeFwTestServer,
+    policies::{IpSubnet, TrafficTally},
+    BlockReason, BlocklistType, TrafficController, TrafficSim,
 };
 use sui_json_rpc_types::{
     SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
@@ -27,7 +30,8 @@ use sui_types::{
     quorum_driver_types::ExecuteTransactionRequestType,
     signature::GenericSignature,
     traffic_control::{
-        FreqThresholdConfig, PolicyConfig, PolicyType, RemoteFirewallConfig, Weight,
+        CostThresholdConfig, FreqThresholdConfig, PolicyConfig, PolicyType, RemoteFirewallConfig,
+        Weight,
     },
 };
 use test_cluster::{TestCluster, TestClusterBuilder};
@@ -595,6 +599,110 @@ async fn test_traffic_control_manual_set_dead_mans_switch() -> Result<(), anyhow
     Ok(())
 }
 
+#[tokio::test]
+async fn test_traffic_control_manual_block() -> Result<(), anyhow::Error> {
+    let policy_config = PolicyConfig {
+        dry_run: false,
+        ..Default::default()
+    };
+    let tc = TrafficController::init_for_test(policy_config, None);
+    let client: IpAddr = "10.1.2.3".parse().unwrap();
+    let neighbour: IpAddr = "10.1.2.4".parse().unwrap();
+    assert!(tc.check(&Some(client), &None).await);
+
+    // Blocking a subnet blocks every address in it.
+    let subnet: IpSubnet = "10.1.2.0/24".parse().unwrap();
+    tc.block(BlocklistType::Client, subnet, Duration::from_secs(60))?;
+    assert!(!tc.check(&Some(client), &None).await);
+    assert!(!tc.check(&Some(neighbour), &None).await);
+    assert!(tc.check(&None, &Some(client)).await);
+
+    let blocked = tc.blocked_clients()?;
+    assert_eq!(blocked.len(), 1);
+    assert_eq!(blocked[0].blocklist, BlocklistType::Client);
+    assert_eq!(blocked[0].subnet, subnet);
+    assert_eq!(blocked[0].reason, BlockReason::Manual);
+
+    // Unblocking an address does not unblock the subnet containing it.
+    assert!(!tc.unblock(BlocklistType::Client, IpSubnet::host(client))?);
+    assert!(!tc.check(&Some(client), &None).await);
+    assert!(tc.unblock(BlocklistType::Client, subnet)?);
+    assert!(tc.check(&Some(client), &None).await);
+
+    // Blocks expire after their TTL.
+    tc.block(
+        BlocklistType::ProxiedClient,
+        IpSubnet::host(client),
+        Duration::from_secs(1),
+    )?;
+    assert!(!tc.check(&None, &Some(client)).await);
+    assert!(tc.check(&None, &Some(neighbour)).await);
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    assert!(tc.check(&None, &Some(client)).await);
+    assert!(tc.blocked_clients()?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_traffic_control_update_policy() -> Result<(), anyhow::Error> {
+    let policy_config = PolicyConfig {
+        connection_blocklist_ttl_sec: 60,
+        spam_sample_rate: Weight::one(),
+        dry_run: false,
+        ..Default::default()
+    };
+    let tc = TrafficController::init_for_test(policy_config.clone(), None);
+    let client: IpAddr = "10.1.2.3".parse().unwrap();
+
+    // Nothing is blocked under the initial no-op policy.
+    for _ in 0..5 {
+        tc.tally(TrafficTally::new(Some(client), None, None, Weight::one()));
+    }
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(tc.check(&Some(client), &None).await);
+
+    tc.update_policy_config(PolicyConfig {
+        spam_policy_type: PolicyType::TestNConnIP(3),
+        ..policy_config.clone()
+    })?;
+    assert!(matches!(
+        tc.policy_config().spam_policy_type,
+        PolicyType::TestNConnIP(3)
+    ));
+    // Give the tally loop a chance to pick up the new policy.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    for _ in 0..5 {
+        tc.tally(TrafficTally::new(Some(client), None, None, Weight::one()));
+    }
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(!tc.check(&Some(client), &None).await);
+    assert_eq!(tc.blocked_clients()?[0].reason, BlockReason::Spam);
+
+    // Invalid configs, and changes that need a restart, are rejected without changing the
+    // policy in effect.
+    assert!(tc
+        .update_policy_config(PolicyConfig {
+            spam_policy_type: PolicyType::FreqThreshold(FreqThresholdConfig {
+                update_interval_secs: 0,
+                ..Default::default()
+            }),
+            ..policy_config.clone()
+        })
+        .is_err());
+    assert!(tc
+        .update_policy_config(PolicyConfig {
+            allow_list: Some(vec![client.to_string()]),
+            ..policy_config.clone()
+        })
+        .is_err());
+    assert!(matches!(
+        tc.policy_config().spam_policy_type,
+        PolicyType::TestNConnIP(3)
+    ));
+    Ok(())
+}
+
 #[sim_test]
 async fn test_traffic_sketch_no_blocks() {
     let sketch_config = FreqThresholdConfig {
@@ -711,6 +819,43 @@ async fn test_traffic_sketch_with_sampled_spam() {
     assert!(metrics.num_blocked > (expected_requests / 5) - 1000);
 }
 
+#[sim_test]
+async fn test_traffic_sketch_subnet_blocks() {
+    // Each client alone stays well below the threshold, but all clients of the simulation are
+    // in 127.0.0.0/24, which together exceeds it.
+    let sketch_config = CostThresholdConfig {
+        client_threshold: 2_500,
+        proxied_client_threshold: 2_500,
+        ipv4_prefix_len: 24,
+        window_size_secs: 4,
+        update_interval_secs: 1,
+        ..Default::default()
+    };
+    let policy = PolicyConfig {
+        connection_blocklist_ttl_sec: 1,
+        proxy_blocklist_ttl_sec: 1,
+        spam_policy_type: PolicyType::CostThreshold(sketch_config),
+        spam_sample_rate: Weight::one(),
+        dry_run: false,
+        ..Default::default()
+    };
+    let metrics = TrafficSim::run(
+        policy,
+        4,     // num_clients
+        1_000, // per_client_tps
+        Duration::from_secs(20),
+        true, // report
+    )
+    .await;
+
+    let expected_requests = 1_000 * 4 * 20;
+    assert!(metrics.num_requests > expected_requests - 1_000);
+    assert!(metrics.num_requests < expected_requests + 200);
+    // every client is blocked along with the rest of the subnet
+    assert!(metrics.num_blocklist_adds >= 4);
+    assert!(metrics.num_blocked > expected_requests / 10);
+}
+
 #[sim_test]
 async fn test_traffic_sketch_allowlist_mode() {
     let policy_config = PolicyConfig {
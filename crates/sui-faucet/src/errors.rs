@@ -51,6 +51,9 @@ pub enum FaucetError {
 
     #[error("Invalid user agent: {0}")]
     InvalidUserAgent(String),
+
+    #[error("Invalid API key")]
+    InvalidApiKey,
 }
 
 impl FaucetError {
@@ -1,6 +1,6 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::FaucetError;
+use crate::{FaucetError, RateLimitBackendConfig, RouteQuota};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
@@ -151,6 +151,30 @@ pub struct FaucetConfig {
     /// used for authenticated mode.
     #[clap(long, default_value_t = 60)]
     pub rate_limiter_cleanup_interval_secs: u64,
+
+    /// Where to keep rate limit counters: `memory` keeps them in-process, `sqlite:<path>` persists
+    /// them to a SQLite database so they survive restarts, and `redis[s]://<host>:<port>` shares
+    /// them between faucet replicas through a Redis-compatible server (over TLS for `rediss`).
+    #[clap(long, default_value = "memory")]
+    pub rate_limit_backend: RateLimitBackendConfig,
+
+    /// Limit requests to a route, given as `<route>=<quota>`, to `quota` per IP address and per
+    /// recipient address in each reset interval. Can be repeated. In authenticated mode, the web
+    /// app route is limited to `max_requests_per_ip` unless it is given a quota here.
+    #[clap(long = "route-quota")]
+    pub route_quotas: Vec<RouteQuota>,
+
+    /// Header that the trusted proxies in front of the faucet record the addresses they forward
+    /// requests for in, like `X-Forwarded-For`. If set, clients are identified by the address in
+    /// this header rather than the address of the connection, for rate limiting and turnstile
+    /// validation.
+    #[clap(long)]
+    pub client_ip_header: Option<String>,
+
+    /// Number of trusted proxies in front of the faucet that append to `client_ip_header`. The
+    /// client's address is the entry this many from the right of the header.
+    #[clap(long, default_value_t = 1)]
+    pub trusted_proxy_hops: usize,
 }
 
 impl Default for FaucetConfig {
@@ -174,6 +198,10 @@ impl Default for FaucetConfig {
             replenish_quota_interval_ms: 10,
             reset_time_interval_secs: 3600 * 12,
             rate_limiter_cleanup_interval_secs: 60,
+            rate_limit_backend: RateLimitBackendConfig::InMemory,
+            route_quotas: vec![],
+            client_ip_header: None,
+            trusted_proxy_hops: 1,
         }
     }
 }
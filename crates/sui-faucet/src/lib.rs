@@ -4,6 +4,7 @@
 mod errors;
 mod faucet;
 mod metrics;
+mod rate_limit;
 mod requests;
 mod responses;
 mod server;
@@ -13,6 +14,7 @@ pub use metrics_layer::*;
 
 pub use errors::FaucetError;
 pub use faucet::*;
+pub use rate_limit::{RateLimitBackendConfig, RouteQuota, API_KEY_HEADER, FAUCET_API_KEYS_ENV};
 pub use requests::*;
 pub use responses::*;
 pub use server::{create_wallet_context, start_faucet};
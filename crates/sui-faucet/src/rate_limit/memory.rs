@@ -0,0 +1,71 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Quota, RateLimitBackend};
+use crate::FaucetError;
+use async_trait::async_trait;
+use dashmap::{mapref::entry::Entry, DashMap};
+use std::time::{Duration, Instant};
+
+/// Keeps rate limit counters in the faucet's memory.
+#[derive(Debug, Default)]
+pub(crate) struct InMemoryBackend {
+    pub(crate) data: DashMap<String, RequestInfo>,
+}
+
+/// Request's metadata
+#[derive(Debug, Clone)]
+pub(crate) struct RequestInfo {
+    /// When the first request for this key was made. In case of resetting the key's metadata, this
+    /// field will be updated with the new current time.
+    pub(crate) timestamp: Instant,
+    pub(crate) requests_used: u64,
+}
+
+impl InMemoryBackend {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RateLimitBackend for InMemoryBackend {
+    async fn acquire(&self, key: &str, quota: u64, window: Duration) -> Result<Quota, FaucetError> {
+        // Starting a window counts a request, so a key without any quota must not start one.
+        if quota == 0 {
+            return Ok(Quota::Exhausted(window));
+        }
+
+        match self.data.entry(key.to_string()) {
+            Entry::Vacant(entry) => {
+                entry.insert(RequestInfo {
+                    timestamp: Instant::now(),
+                    requests_used: 1,
+                });
+            }
+
+            Entry::Occupied(mut entry) => {
+                let info = entry.get_mut();
+                let elapsed = info.timestamp.elapsed();
+
+                if elapsed >= window {
+                    info.timestamp = Instant::now();
+                    info.requests_used = 1;
+                } else if info.requests_used >= quota {
+                    return Ok(Quota::Exhausted(window - elapsed));
+                } else {
+                    info.requests_used += 1;
+                }
+            }
+        }
+
+        Ok(Quota::Available)
+    }
+
+    async fn cleanup(&self, window: Duration) -> Result<(), FaucetError> {
+        // keep only those keys that are still under time limit.
+        self.data
+            .retain(|_, info| info.timestamp.elapsed() < window);
+        Ok(())
+    }
+}
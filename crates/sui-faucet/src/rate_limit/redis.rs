@@ -0,0 +1,94 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Quota, RateLimitBackend};
+use crate::FaucetError;
+use async_trait::async_trait;
+use redis::{
+    aio::{ConnectionManager, ConnectionManagerConfig},
+    Client, Script,
+};
+use std::time::Duration;
+
+/// Prefix for every key the faucet writes, so that it can share a server with other services.
+const KEY_PREFIX: &str = "sui-faucet:rate-limit:";
+
+/// How long to wait to connect to the server, and for it to respond to a request, before giving
+/// up.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Counts a request against `KEYS[1]` (with window `ARGV[1]` in milliseconds and quota
+/// `ARGV[2]`), atomically on the server. Returns `{1, 0}` if the request is within quota, or
+/// `{0, <milliseconds until the window resets>}` if not.
+const ACQUIRE_SCRIPT: &str = r#"
+local used = tonumber(redis.call('GET', KEYS[1]) or '0')
+if used >= tonumber(ARGV[2]) then
+    return {0, redis.call('PTTL', KEYS[1])}
+end
+if redis.call('INCR', KEYS[1]) == 1 then
+    redis.call('PEXPIRE', KEYS[1], ARGV[1])
+end
+return {1, 0}
+"#;
+
+/// Keeps rate limit counters in a Redis-compatible server, so that they are shared between faucet
+/// replicas. Counters expire on the server when their window ends, so there is nothing to clean up.
+///
+/// Requests are multiplexed over a single connection, which is re-established in the background if
+/// it fails.
+pub(crate) struct RedisBackend {
+    conn: ConnectionManager,
+    acquire: Script,
+}
+
+impl RedisBackend {
+    /// Connect to the server at `url` (`redis[s]://[[<username>]:<password>@]<host>[:<port>]`).
+    pub(crate) async fn connect(url: &str) -> anyhow::Result<Self> {
+        let client = Client::open(url)?;
+        let config = ConnectionManagerConfig::new()
+            .set_connection_timeout(REQUEST_TIMEOUT)
+            .set_response_timeout(REQUEST_TIMEOUT);
+
+        Ok(Self {
+            conn: ConnectionManager::new_with_config(client, config).await?,
+            acquire: Script::new(ACQUIRE_SCRIPT),
+        })
+    }
+}
+
+#[async_trait]
+impl RateLimitBackend for RedisBackend {
+    async fn acquire(&self, key: &str, quota: u64, window: Duration) -> Result<Quota, FaucetError> {
+        let window_ms = window.as_millis().max(1) as u64;
+        let (available, ttl_ms): (i64, i64) = self
+            .acquire
+            .key(format!("{KEY_PREFIX}{key}"))
+            .arg(window_ms)
+            .arg(quota)
+            .invoke_async(&mut self.conn.clone())
+            .await
+            .map_err(FaucetError::internal)?;
+
+        Ok(if available == 1 {
+            Quota::Available
+        } else {
+            Quota::Exhausted(Duration::from_millis(ttl_ms.max(0) as u64))
+        })
+    }
+
+    async fn cleanup(&self, _window: Duration) -> Result<(), FaucetError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_invalid_url() {
+        assert!(RedisBackend::connect("http://127.0.0.1:7000")
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Quota, RateLimitBackend};
+use crate::FaucetError;
+use anyhow::Context;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rusqlite::{params, OptionalExtension, TransactionBehavior};
+use std::{
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const RATE_LIMITS_SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS rate_limits
+(
+    key                 TEXT          PRIMARY KEY,
+    window_start_ms     BIGINT        NOT NULL,
+    requests_used       BIGINT        NOT NULL
+);
+"#;
+
+/// How long a connection will wait for a lock held by another connection (e.g. from another
+/// faucet process sharing the file), before giving up.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Keeps rate limit counters in a SQLite database file, so that they survive restarts. Windows are
+/// measured in wall-clock time, because they need to be comparable across processes.
+pub(crate) struct SqliteBackend {
+    conn: Arc<Mutex<rusqlite::Connection>>,
+}
+
+impl SqliteBackend {
+    /// Open (creating if necessary) the database at `path`, and make sure its table exists.
+    pub(crate) async fn open(path: &Path) -> anyhow::Result<Self> {
+        let path = path.to_owned();
+        let conn = tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
+            let conn = rusqlite::Connection::open(&path).with_context(|| {
+                format!("Failed to open rate limit database at {}", path.display())
+            })?;
+            conn.busy_timeout(BUSY_TIMEOUT)?;
+            conn.pragma_update(None, "journal_mode", "WAL")
+                .context("Failed to enable WAL mode")?;
+            conn.execute_batch(RATE_LIMITS_SCHEMA)
+                .context("Failed to create rate_limits table")?;
+            Ok(conn)
+        })
+        .await??;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Run `f` against the connection on a blocking thread.
+    async fn with_conn<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut rusqlite::Connection) -> rusqlite::Result<T> + Send + 'static,
+    ) -> Result<T, FaucetError> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || f(&mut conn.lock()))
+            .await
+            .map_err(FaucetError::internal)?
+            .map_err(FaucetError::internal)
+    }
+}
+
+#[async_trait]
+impl RateLimitBackend for SqliteBackend {
+    async fn acquire(&self, key: &str, quota: u64, window: Duration) -> Result<Quota, FaucetError> {
+        // Starting a window counts a request, so a key without any quota must not start one.
+        if quota == 0 {
+            return Ok(Quota::Exhausted(window));
+        }
+
+        let key = key.to_string();
+        let window_ms = window.as_millis() as i64;
+        let now_ms = now_ms();
+
+        self.with_conn(move |conn| {
+            // Take the write lock up-front, so that concurrent processes can't both read the
+            // same count and each allow a request.
+            let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+            let row: Option<(i64, i64)> = tx
+                .query_row(
+                    "SELECT window_start_ms, requests_used FROM rate_limits WHERE key = ?1",
+                    params![key],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+
+            let outcome = match row {
+                Some((start_ms, used)) if now_ms - start_ms < window_ms => {
+                    if used as u64 >= quota {
+                        Quota::Exhausted(Duration::from_millis(
+                            (start_ms + window_ms - now_ms) as u64,
+                        ))
+                    } else {
+                        tx.execute(
+                            "UPDATE rate_limits SET requests_used = requests_used + 1 WHERE key = ?1",
+                            params![key],
+                        )?;
+                        Quota::Available
+                    }
+                }
+
+                _ => {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO rate_limits (key, window_start_ms, requests_used)
+                         VALUES (?1, ?2, 1)",
+                        params![key, now_ms],
+                    )?;
+                    Quota::Available
+                }
+            };
+
+            tx.commit()?;
+            Ok(outcome)
+        })
+        .await
+    }
+
+    async fn cleanup(&self, window: Duration) -> Result<(), FaucetError> {
+        let cutoff_ms = now_ms() - window.as_millis() as i64;
+        self.with_conn(move |conn| {
+            conn.execute(
+                "DELETE FROM rate_limits WHERE window_start_ms <= ?1",
+                params![cutoff_ms],
+            )
+        })
+        .await?;
+        Ok(())
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    #[tokio::test]
+    async fn test_counters_survive_reopening() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("limits.db");
+
+        let backend = SqliteBackend::open(&path).await.unwrap();
+        for _ in 0..2 {
+            let quota = backend.acquire("ip:127.0.0.1", 2, WINDOW).await.unwrap();
+            assert_eq!(quota, Quota::Available);
+        }
+        drop(backend);
+
+        let backend = SqliteBackend::open(&path).await.unwrap();
+        let quota = backend.acquire("ip:127.0.0.1", 2, WINDOW).await.unwrap();
+        assert!(matches!(quota, Quota::Exhausted(d) if d <= WINDOW));
+
+        // Other keys have their own quota.
+        let quota = backend.acquire("ip:127.0.0.2", 2, WINDOW).await.unwrap();
+        assert_eq!(quota, Quota::Available);
+    }
+
+    #[tokio::test]
+    async fn test_window_reset_and_cleanup() {
+        let tmp = tempfile::tempdir().unwrap();
+        let backend = SqliteBackend::open(&tmp.path().join("limits.db"))
+            .await
+            .unwrap();
+        let window = Duration::from_millis(100);
+
+        assert_eq!(
+            backend.acquire("key", 1, window).await.unwrap(),
+            Quota::Available
+        );
+        assert!(matches!(
+            backend.acquire("key", 1, window).await.unwrap(),
+            Quota::Exhausted(_)
+        ));
+
+        tokio::time::sleep(window).await;
+        backend.cleanup(window).await.unwrap();
+        let rows: i64 = backend
+            .with_conn(|conn| conn.query_row("SELECT COUNT(*) FROM rate_limits", [], |r| r.get(0)))
+            .await
+            .unwrap();
+        assert_eq!(rows, 0);
+
+        assert_eq!(
+            backend.acquire("key", 1, window).await.unwrap(),
+            Quota::Available
+        );
+    }
+}
@@ -2,13 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    rate_limit::{RateLimiter, API_KEY_HEADER, FAUCET_WEB_ROUTE},
     AppState, BatchFaucetResponse, BatchStatusFaucetResponse, FaucetConfig, FaucetError,
     FaucetRequest, FaucetResponse, FixedAmountRequest, RequestMetricsLayer,
 };
 use axum::{
     error_handling::HandleErrorLayer,
     extract::{ConnectInfo, Path},
-    http::{header::HeaderMap, StatusCode},
+    http::{
+        header::{HeaderMap, HeaderName},
+        StatusCode,
+    },
     response::{IntoResponse, Redirect, Response},
     routing::{get, post},
     BoxError, Extension, Json, Router,
@@ -21,11 +25,13 @@ use std::{
     borrow::Cow,
     net::{IpAddr, SocketAddr},
     path::PathBuf,
+    str::FromStr,
     sync::Arc,
-    time::{Duration, Instant},
+    time::Duration,
 };
 use sui_config::SUI_CLIENT_CONFIG;
 use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::SuiAddress;
 use tower::ServiceBuilder;
 use tower_governor::{
     governor::GovernorConfigBuilder, key_extractor::GlobalKeyExtractor, GovernorLayer,
@@ -35,7 +41,6 @@ use tracing::{error, info, warn};
 use uuid::Uuid;
 
 use crate::faucet::Faucet;
-use dashmap::{mapref::entry::Entry, DashMap};
 use serde::Deserialize;
 
 use anyhow::ensure;
@@ -58,23 +63,14 @@ static TURNSTILE_SECRET_KEY: Lazy<Option<String>> =
 static DISCORD_BOT_PWD: Lazy<String> =
     Lazy::new(|| std::env::var("DISCORD_BOT_PWD").unwrap_or_else(|_| "".to_string()));
 
-/// Keep track of every IP address' requests.
-#[derive(Debug)]
+/// Validates requests against Cloudflare Turnstile and the faucet's rate limits.
 struct RequestsManager {
-    data: Arc<DashMap<IpAddr, RequestInfo>>,
-    reset_time_interval: Duration,
-    max_requests_per_ip: u64,
+    rate_limiter: RateLimiter,
     cloudflare_turnstile_url: String,
     turnstile_secret_key: String,
-}
-
-/// Request's metadata
-#[derive(Debug, Clone)]
-struct RequestInfo {
-    /// When the first request from this IP address was made. In case of resetting the IP addresses
-    /// metadata, this field will be updated with the new current time.
-    timestamp: Instant,
-    requests_used: u64,
+    /// Header listing the addresses a request was forwarded for by trusted proxies, and how many
+    /// of them there are.
+    client_ip_header: Option<(HeaderName, usize)>,
 }
 
 /// Struct to deserialize token verification response from Cloudflare
@@ -88,29 +84,62 @@ struct TurnstileValidationResponse {
 impl RequestsManager {
     /// Initialize a new RequestsManager
     fn new(
-        max_requests_per_ip: u64,
-        reset_time_interval_secs: Duration,
+        rate_limiter: RateLimiter,
         cloudflare_turnstile_url: String,
         turnstile_secret_key: String,
     ) -> Self {
         Self {
-            data: Arc::new(DashMap::new()),
-            reset_time_interval: reset_time_interval_secs,
-            max_requests_per_ip,
+            rate_limiter,
             cloudflare_turnstile_url,
             turnstile_secret_key,
+            client_ip_header: None,
         }
     }
 
-    /// Validates a turnstile token
-    /// - against Cloudflare turnstile's server to ensure token was issued by turnstile
-    /// - against the IP address' request count
+    /// Identify clients by the address in `header`, which is appended to by each of the
+    /// `trusted_proxy_hops` proxies in front of the faucet, rather than by the address of the
+    /// connection.
+    fn with_client_ip_header(mut self, header: HeaderName, trusted_proxy_hops: usize) -> Self {
+        self.client_ip_header = Some((header, trusted_proxy_hops));
+        self
+    }
+
+    /// The address of the client that sent a request over the connection from `addr`.
+    ///
+    /// If a client IP header is configured, each trusted proxy appends the address it received
+    /// the request from to it, so the client's address is the entry that many from the right.
+    /// Entries further left are supplied by the client, and can't be trusted. Falls back to the
+    /// address of the connection if the header is missing, or doesn't have enough valid entries.
+    fn client_ip(&self, addr: SocketAddr, headers: &HeaderMap) -> IpAddr {
+        let Some((header, hops)) = &self.client_ip_header else {
+            return addr.ip();
+        };
+
+        // A repeated header's values form a single list, in order.
+        let Ok(values) = headers
+            .get_all(header)
+            .iter()
+            .map(|v| v.to_str())
+            .collect::<Result<Vec<_>, _>>()
+        else {
+            return addr.ip();
+        };
+
+        let entries: Vec<_> = values.iter().flat_map(|v| v.split(',')).collect();
+        entries
+            .len()
+            .checked_sub(*hops)
+            .and_then(|i| parse_forwarded_ip(entries[i].trim()))
+            .unwrap_or_else(|| addr.ip())
+    }
+
+    /// Validates a turnstile token against Cloudflare turnstile's server to ensure token was issued
+    /// by turnstile
     async fn validate_turnstile_token(
         &self,
-        addr: SocketAddr,
+        ip: IpAddr,
         token: &str,
     ) -> Result<(), (StatusCode, FaucetError)> {
-        let ip = addr.ip();
         let req = reqwest::Client::new();
         let params = [
             ("secret", self.turnstile_secret_key.as_str()),
@@ -161,44 +190,33 @@ impl RequestsManager {
             ));
         }
 
-        match self.data.entry(ip) {
-            Entry::Vacant(entry) => {
-                entry.insert(RequestInfo {
-                    timestamp: Instant::now(),
-                    requests_used: 1,
-                });
-            }
-
-            Entry::Occupied(mut entry) => {
-                let token = entry.get_mut();
-                let elapsed = token.timestamp.elapsed();
-
-                if elapsed >= self.reset_time_interval {
-                    token.timestamp = Instant::now();
-                    token.requests_used = 1;
-                } else if token.requests_used >= self.max_requests_per_ip {
-                    return Err((
-                        StatusCode::TOO_MANY_REQUESTS,
-                        FaucetError::TooManyRequests(format!(
-                            "You can request a new token in {}",
-                            secs_to_human_readable((self.reset_time_interval - elapsed).as_secs())
-                        )),
-                    ));
-                } else {
-                    token.requests_used += 1;
-                }
-            }
-        }
-
         Ok(())
     }
 
-    /// This function iterates through the stored IPs and removes those IP addresses which are now
-    /// eligible to make new requests.
-    fn cleanup_expired_tokens(&self) {
-        // keep only those IP addresses that are still under time limit.
-        self.data
-            .retain(|_, info| info.timestamp.elapsed() < self.reset_time_interval);
+    /// Counts a request on `route` against the quotas of the client it came from and its
+    /// recipient, taking into account the API key in its headers, if any.
+    async fn check_rate_limit(
+        &self,
+        route: &str,
+        addr: SocketAddr,
+        recipient: SuiAddress,
+        headers: &HeaderMap,
+    ) -> Result<(), (StatusCode, FaucetError)> {
+        let api_key = headers.get(API_KEY_HEADER).and_then(|v| v.to_str().ok());
+        self.rate_limiter
+            .check(
+                route,
+                self.client_ip(addr, headers),
+                Some(recipient),
+                api_key,
+            )
+            .await
+    }
+
+    /// Forgets the request counts of IP addresses and recipients which are now eligible to make
+    /// new requests.
+    async fn cleanup_expired_tokens(&self) {
+        self.rate_limiter.cleanup().await;
     }
 }
 
@@ -232,18 +250,28 @@ pub async fn start_faucet(
         max_request_per_second,
         wal_retry_interval,
         replenish_quota_interval_ms,
-        reset_time_interval_secs,
         rate_limiter_cleanup_interval_secs,
-        max_requests_per_ip,
+        trusted_proxy_hops,
         ..
     } = app_state.config;
 
-    let token_manager = Arc::new(RequestsManager::new(
-        max_requests_per_ip,
-        Duration::from_secs(reset_time_interval_secs),
+    let mut token_manager = RequestsManager::new(
+        RateLimiter::from_config(&app_state.config).await?,
         cloudflare_turnstile_url,
         turnstile_secret_key,
-    ));
+    );
+
+    if let Some(header) = &app_state.config.client_ip_header {
+        ensure!(
+            trusted_proxy_hops > 0,
+            "--trusted-proxy-hops must be at least 1 when --client-ip-header is set"
+        );
+        info!("Identifying clients by the {header} header, behind {trusted_proxy_hops} proxies");
+        token_manager =
+            token_manager.with_client_ip_header(HeaderName::from_str(header)?, trusted_proxy_hops);
+    }
+
+    let token_manager = Arc::new(token_manager);
 
     let governor_cfg = Arc::new(
         GovernorConfigBuilder::default()
@@ -255,7 +283,8 @@ pub async fn start_faucet(
     );
 
     // these routes have a more aggressive rate limit to reduce the number of reqs per second as
-    // per the governor config above.
+    // per the governor config above. Like all routes that send coins, they are additionally
+    // subject to any per-IP and per-recipient quota configured for them.
     let global_limited_routes = Router::new()
         .route("/gas", post(request_gas))
         .route("/v1/gas", post(batch_request_gas))
@@ -264,8 +293,8 @@ pub async fn start_faucet(
         });
 
     // This has its own rate limiter via the RequestManager
-    let faucet_web_routes = Router::new().route("/v1/faucet_web_gas", post(batch_faucet_web_gas));
-    // Routes with no rate limit
+    let faucet_web_routes = Router::new().route(FAUCET_WEB_ROUTE, post(batch_faucet_web_gas));
+    // Routes with no rate limit (apart from any configured quota for the discord route)
     let unrestricted_routes = Router::new()
         .route("/", get(redirect))
         .route("/health", get(health))
@@ -303,7 +332,7 @@ pub async fn start_faucet(
         info!("Starting task to clear banned ip addresses.");
         loop {
             tokio::time::sleep(Duration::from_secs(rate_limiter_cleanup_interval_secs)).await;
-            token_manager.cleanup_expired_tokens();
+            token_manager.cleanup_expired_tokens().await;
         }
     });
 
@@ -341,6 +370,8 @@ async fn redirect(Host(host): Host) -> Response {
 /// A route for requests coming from the discord bot.
 async fn batch_faucet_discord(
     headers: HeaderMap,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Extension(token_manager): Extension<Arc<RequestsManager>>,
     Extension(state): Extension<Arc<AppState>>,
     Json(payload): Json<FaucetRequest>,
 ) -> impl IntoResponse {
@@ -376,6 +407,13 @@ async fn batch_faucet_discord(
         );
     };
 
+    if let Err((status_code, faucet_error)) = token_manager
+        .check_rate_limit("/v1/faucet_discord", addr, request.recipient, &headers)
+        .await
+    {
+        return (status_code, Json(BatchFaucetResponse::from(faucet_error)));
+    }
+
     batch_request_spawn_task(request, state).await
 }
 
@@ -400,7 +438,9 @@ async fn batch_faucet_web_gas(
             );
         };
 
-        let validation = token_manager.validate_turnstile_token(addr, token).await;
+        let validation = token_manager
+            .validate_turnstile_token(token_manager.client_ip(addr, &headers), token)
+            .await;
 
         if let Err((status_code, faucet_error)) = validation {
             return (status_code, Json(BatchFaucetResponse::from(faucet_error)));
@@ -416,6 +456,13 @@ async fn batch_faucet_web_gas(
         );
     };
 
+    if let Err((status_code, faucet_error)) = token_manager
+        .check_rate_limit(FAUCET_WEB_ROUTE, addr, request.recipient, &headers)
+        .await
+    {
+        return (status_code, Json(BatchFaucetResponse::from(faucet_error)));
+    }
+
     batch_request_spawn_task(request, state).await
 }
 
@@ -447,6 +494,9 @@ async fn batch_request_spawn_task(
 
 /// handler for batch_request_gas requests
 async fn batch_request_gas(
+    headers: HeaderMap,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Extension(token_manager): Extension<Arc<RequestsManager>>,
     Extension(state): Extension<Arc<AppState>>,
     Json(payload): Json<FaucetRequest>,
 ) -> impl IntoResponse {
@@ -463,6 +513,13 @@ async fn batch_request_gas(
         );
     };
 
+    if let Err((status_code, faucet_error)) = token_manager
+        .check_rate_limit("/v1/gas", addr, request.recipient, &headers)
+        .await
+    {
+        return (status_code, Json(BatchFaucetResponse::from(faucet_error)));
+    }
+
     if state.config.batch_enabled {
         batch_request_spawn_task(request, state).await
     } else {
@@ -527,6 +584,9 @@ async fn request_status(
 
 /// handler for all the request_gas requests
 async fn request_gas(
+    headers: HeaderMap,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Extension(token_manager): Extension<Arc<RequestsManager>>,
     Extension(state): Extension<Arc<AppState>>,
     Json(payload): Json<FaucetRequest>,
 ) -> impl IntoResponse {
@@ -536,6 +596,13 @@ async fn request_gas(
 
     let result = match payload {
         FaucetRequest::FixedAmountRequest(requests) => {
+            if let Err((status_code, faucet_error)) = token_manager
+                .check_rate_limit("/gas", addr, requests.recipient, &headers)
+                .await
+            {
+                return (status_code, Json(FaucetResponse::from(faucet_error)));
+            }
+
             // We spawn a tokio task for this such that connection drop will not interrupt
             // it and impact the recycling of coins
             spawn_monitored_task!(async move {
@@ -602,24 +669,18 @@ async fn handle_error(error: BoxError) -> impl IntoResponse {
     )
 }
 
-/// Format seconds to human readable format.
-fn secs_to_human_readable(seconds: u64) -> String {
-    let hours = seconds / 3600;
-    let minutes = (seconds % 3600) / 60;
-    let seconds = seconds % 60;
-
-    if hours > 0 {
-        format!("{}h {}m {}s", hours, minutes, seconds)
-    } else if minutes > 0 {
-        format!("{}m {}s", minutes, seconds)
-    } else {
-        format!("{}s", seconds)
-    }
+/// Parse an entry of a forwarding header, which may include a port.
+fn parse_forwarded_ip(entry: &str) -> Option<IpAddr> {
+    entry
+        .parse()
+        .or_else(|_| entry.parse::<SocketAddr>().map(|addr| addr.ip()))
+        .ok()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rate_limit::InMemoryBackend;
     use serde_json::json;
     use std::net::{IpAddr, Ipv4Addr};
     use std::time::Duration;
@@ -643,65 +704,114 @@ mod tests {
         mock_server
     }
 
+    /// A manager limiting the web app route to `MAX_REQUESTS_PER_IP` requests per IP address,
+    /// along with the backend keeping its counters.
+    fn setup_manager(turnstile_url: String) -> (RequestsManager, Arc<InMemoryBackend>) {
+        let backend = Arc::new(InMemoryBackend::new());
+        let rate_limiter = RateLimiter::new(backend.clone(), RESET_TIME_INTERVAL)
+            .with_quota(FAUCET_WEB_ROUTE, MAX_REQUESTS_PER_IP);
+        let manager = RequestsManager::new(rate_limiter, turnstile_url, "test_secret".to_string());
+        (manager, backend)
+    }
+
+    /// Validate a request to the web app route, as its handler does.
+    async fn validate_web_request(
+        manager: &RequestsManager,
+        addr: SocketAddr,
+        token: &str,
+    ) -> Result<(), (StatusCode, FaucetError)> {
+        manager.validate_turnstile_token(addr.ip(), token).await?;
+        manager
+            .rate_limiter
+            .check(FAUCET_WEB_ROUTE, addr.ip(), None, None)
+            .await
+    }
+
+    #[test]
+    fn test_client_ip() {
+        let (manager, _) = setup_manager(String::new());
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 8080);
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        let headers = |values: &[&str]| {
+            let mut headers = HeaderMap::new();
+            for value in values {
+                headers.append("x-forwarded-for", value.parse().unwrap());
+            }
+            headers
+        };
+
+        // Without a configured header, forwarding headers are ignored.
+        let spoofed = headers(&["1.1.1.1"]);
+        assert_eq!(manager.client_ip(addr, &spoofed), addr.ip());
+
+        let manager = manager.with_client_ip_header(HeaderName::from_static("x-forwarded-for"), 2);
+
+        // Entries are counted from the right, across repeated headers, ignoring any supplied by
+        // the client.
+        let forwarded = headers(&["1.1.1.1, 2.2.2.2", "3.3.3.3:443, 10.0.0.2"]);
+        assert_eq!(manager.client_ip(addr, &forwarded), ip("3.3.3.3"));
+
+        let forwarded = headers(&["[2001:db8::1]:443", "10.0.0.2"]);
+        assert_eq!(manager.client_ip(addr, &forwarded), ip("2001:db8::1"));
+
+        // Falls back to the connection's address if the header is missing, too short, or invalid.
+        assert_eq!(manager.client_ip(addr, &HeaderMap::new()), addr.ip());
+        assert_eq!(manager.client_ip(addr, &headers(&["10.0.0.2"])), addr.ip());
+        assert_eq!(
+            manager.client_ip(addr, &headers(&["unknown, 10.0.0.2"])),
+            addr.ip()
+        );
+    }
+
     #[tokio::test]
     async fn test_token_validation_and_limits() {
         // Start mock server
         let mock_server = setup_mock_cloudflare().await;
-        let manager = RequestsManager::new(
-            MAX_REQUESTS_PER_IP,
-            RESET_TIME_INTERVAL,
-            mock_server.uri(),
-            "test_secret".to_string(),
-        );
+        let (manager, _) = setup_manager(mock_server.uri());
         let ip = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
         let token = "test_token";
 
         // First request should succeed
-        let result = manager.validate_turnstile_token(ip, token).await;
+        let result = validate_web_request(&manager, ip, token).await;
         assert!(result.is_ok());
 
         // Use up remaining requests
-        for _ in 1..manager.max_requests_per_ip {
-            let result = manager.validate_turnstile_token(ip, token).await;
+        for _ in 1..MAX_REQUESTS_PER_IP {
+            let result = validate_web_request(&manager, ip, token).await;
             assert!(result.is_ok());
         }
 
         // Next request should fail due to limit
-        let result = manager.validate_turnstile_token(ip, token).await;
+        let result = validate_web_request(&manager, ip, token).await;
         assert!(result.is_err());
     }
 
     #[tokio::test]
     async fn test_token_reset_after_interval() {
         let mock_server = setup_mock_cloudflare().await;
-        let manager = RequestsManager::new(
-            MAX_REQUESTS_PER_IP,
-            RESET_TIME_INTERVAL,
-            mock_server.uri(),
-            "test_secret".to_string(),
-        );
+        let (manager, backend) = setup_manager(mock_server.uri());
 
         let ip = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
         let token = "test_token";
 
         // Use up all requests
-        for _ in 0..manager.max_requests_per_ip {
-            let result = manager.validate_turnstile_token(ip, token).await;
+        for _ in 0..MAX_REQUESTS_PER_IP {
+            let result = validate_web_request(&manager, ip, token).await;
             assert!(result.is_ok());
         }
 
         // Try one more, it should fail
-        let result = manager.validate_turnstile_token(ip, token).await;
+        let result = validate_web_request(&manager, ip, token).await;
         assert!(result.is_err());
         assert!(result.unwrap_err().0 == StatusCode::TOO_MANY_REQUESTS);
-        assert!(!manager.data.is_empty());
+        assert!(!backend.data.is_empty());
 
         tokio::time::sleep(RESET_TIME_INTERVAL + Duration::from_secs(3)).await;
         // Trigger cleanup
-        manager.cleanup_expired_tokens();
+        manager.cleanup_expired_tokens().await;
 
         // Should be able to make new requests
-        let result = manager.validate_turnstile_token(ip, token).await;
+        let result = validate_web_request(&manager, ip, token).await;
         assert!(result.is_ok());
     }
 
@@ -719,28 +829,19 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let manager = RequestsManager::new(
-            MAX_REQUESTS_PER_IP,
-            RESET_TIME_INTERVAL,
-            mock_server.uri(),
-            "test_secret".to_string(),
-        );
+        let (manager, _) = setup_manager(mock_server.uri());
         let ip = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
         let token = "invalid_token";
 
-        let result = manager.validate_turnstile_token(ip, token).await;
+        let result = validate_web_request(&manager, ip, token).await;
         assert!(result.is_err());
     }
 
     #[tokio::test]
     async fn test_concurrent_ip_requests() {
         let mock_server = setup_mock_cloudflare().await;
-        let manager = Arc::new(RequestsManager::new(
-            MAX_REQUESTS_PER_IP,
-            RESET_TIME_INTERVAL,
-            mock_server.uri(),
-            "test_secret".to_string(),
-        ));
+        let (manager, backend) = setup_manager(mock_server.uri());
+        let manager = Arc::new(manager);
 
         // Create 10 different IP addresses
         let ips: Vec<SocketAddr> = (0..10)
@@ -761,7 +862,7 @@ mod tests {
                 let mut results = vec![];
                 // Each IP tries to make MAX_REQUESTS_PER_IP + 1 requests
                 for _ in 0..=MAX_REQUESTS_PER_IP {
-                    let result = manager.validate_turnstile_token(ip, token).await;
+                    let result = validate_web_request(&manager, ip, token).await;
                     results.push(result);
                 }
                 (ip, results)
@@ -799,32 +900,13 @@ mod tests {
         }
 
         // Verify the data in the DashMap
-        assert_eq!(manager.data.len(), 10, "Should have 10 IPs in the map");
+        assert_eq!(backend.data.len(), 10, "Should have 10 IPs in the map");
 
-        for info in manager.data.iter() {
+        for info in backend.data.iter() {
             assert_eq!(
                 info.requests_used, MAX_REQUESTS_PER_IP,
                 "Each IP should have used exactly MAX_REQUESTS_PER_IP requests"
             );
         }
     }
-
-    #[test]
-    fn test_secs_to_human_readable() {
-        // Test seconds only
-        assert_eq!(secs_to_human_readable(45), "45s");
-        assert_eq!(secs_to_human_readable(1), "1s");
-
-        // Test minutes and seconds
-        assert_eq!(secs_to_human_readable(65), "1m 5s");
-        assert_eq!(secs_to_human_readable(3599), "59m 59s");
-
-        // Test hours, minutes, and seconds
-        assert_eq!(secs_to_human_readable(3600), "1h 0m 0s");
-        assert_eq!(secs_to_human_readable(3661), "1h 1m 1s");
-        assert_eq!(secs_to_human_readable(7384), "2h 3m 4s");
-
-        // Test edge case
-        assert_eq!(secs_to_human_readable(0), "0s");
-    }
 }
@@ -126,6 +126,10 @@ pub struct Limits {
     pub max_multi_get_objects_keys: u32,
     /// Maximum number of candidates to scan when gathering a page of results.
     pub max_scan_limit: u32,
+    /// Maximum number of subscriptions that can be open at once on a single connection.
+    pub max_subscriptions_per_connection: u32,
+    /// Maximum number of subscriptions that can be open at once across all connections.
+    pub max_subscriptions: u32,
 }
 
 #[DefaultConfig]
@@ -519,6 +523,9 @@ impl Default for Limits {
             max_transaction_ids: 1000,
             max_multi_get_objects_keys: 500,
             max_scan_limit: 100_000_000,
+            // Each open subscription queries the database every time a checkpoint is indexed.
+            max_subscriptions_per_connection: 10,
+            max_subscriptions: 1_000,
             // This value is set to be the size of the max transaction bytes allowed + base64
             // overhead (roughly 1/3 of the original string). This is rounded up.
             //
@@ -604,6 +611,8 @@ mod tests {
                 max-transaction-ids = 11
                 max-multi-get-objects-keys = 11
                 max-scan-limit = 50
+                max-subscriptions-per-connection = 5
+                max-subscriptions = 100
             "#,
         )
         .unwrap();
@@ -627,6 +636,8 @@ mod tests {
                 max_transaction_ids: 11,
                 max_multi_get_objects_keys: 11,
                 max_scan_limit: 50,
+                max_subscriptions_per_connection: 5,
+                max_subscriptions: 100,
             },
             ..Default::default()
         };
@@ -694,6 +705,8 @@ mod tests {
                 max-transaction-ids = 42
                 max-multi-get-objects-keys = 42
                 max-scan-limit = 420
+                max-subscriptions-per-connection = 42
+                max-subscriptions = 4200
 
                 [experiments]
                 test-flag = true
@@ -720,6 +733,8 @@ mod tests {
                 max_transaction_ids: 42,
                 max_multi_get_objects_keys: 42,
                 max_scan_limit: 420,
+                max_subscriptions_per_connection: 42,
+                max_subscriptions: 4200,
             },
             disabled_features: BTreeSet::from([FunctionalGroup::Analytics]),
             experiments: Experiments { test_flag: true },
@@ -746,7 +746,14 @@ impl Extension for QueryLimitsCheckerExt {
         next: NextParseQuery<'_>,
     ) -> ServerResult<ExecutableDocument> {
         let metrics: &Metrics = ctx.data_unchecked();
-        let payload_size: &PayloadSize = ctx.data_unchecked();
+        // Requests over a WebSocket don't come with a `Content-Length`, so their payload is
+        // measured from the query and its variables instead.
+        let payload_size = match ctx.data_opt::<PayloadSize>() {
+            Some(payload_size) => *payload_size,
+            None => PayloadSize(
+                (query.len() + serde_json::to_vec(variables).map_or(0, |v| v.len())) as u64,
+            ),
+        };
         let reporter = Reporter::new(ctx);
 
         let instant = Instant::now();
@@ -777,7 +784,7 @@ impl Extension for QueryLimitsCheckerExt {
         }
 
         let mut traversal =
-            LimitsTraversal::new(*payload_size, &reporter, &doc.fragments, variables);
+            LimitsTraversal::new(payload_size, &reporter, &doc.fragments, variables);
 
         let res = traversal.check_document(&doc);
         let usage = traversal.finish(query.len() as u32);
@@ -3,7 +3,9 @@
 
 use super::exchange_rates_task::TriggerExchangeRatesTask;
 use super::system_package_task::SystemPackageTask;
-use super::watermark_task::{ChainIdentifierLock, Watermark, WatermarkLock, WatermarkTask};
+use super::watermark_task::{
+    ChainIdentifierLock, CheckpointWatch, Watermark, WatermarkLock, WatermarkTask,
+};
 use crate::config::{
     ConnectionConfig, ServiceConfig, Version, MAX_CONCURRENT_REQUESTS,
     RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
@@ -14,6 +16,7 @@ use crate::data::{DataLoader, Db};
 use crate::extensions::directive_checker::DirectiveChecker;
 use crate::metrics::Metrics;
 use crate::mutation::Mutation;
+use crate::subscription::{Subscription, SubscriptionSlots};
 use crate::types::datatype::IMoveDatatype;
 use crate::types::move_object::IMoveObject;
 use crate::types::object::IObject;
@@ -33,10 +36,11 @@ use crate::{
 };
 use async_graphql::extensions::ApolloTracing;
 use async_graphql::extensions::Tracing;
-use async_graphql::EmptySubscription;
+use async_graphql::http::ALL_WEBSOCKET_PROTOCOLS;
 use async_graphql::{extensions::ExtensionFactory, Schema, SchemaBuilder};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
 use axum::body::Body;
+use axum::extract::ws::WebSocketUpgrade;
 use axum::extract::FromRef;
 use axum::extract::{ConnectInfo, Query as AxumQuery, State};
 use axum::http::{HeaderMap, StatusCode};
@@ -145,7 +149,7 @@ impl Server {
 
 pub(crate) struct ServerBuilder {
     state: AppState,
-    schema: SchemaBuilder<Query, Mutation, EmptySubscription>,
+    schema: SchemaBuilder<Query, Mutation, Subscription>,
     router: Option<Router>,
     db_reader: Option<Db>,
     resolver: Option<PackageResolver>,
@@ -219,7 +223,7 @@ impl ServerBuilder {
     }
 
     #[cfg(test)]
-    fn build_schema(self) -> Schema<Query, Mutation, EmptySubscription> {
+    fn build_schema(self) -> Schema<Query, Mutation, Subscription> {
         self.schema.finish()
     }
 
@@ -229,7 +233,7 @@ impl ServerBuilder {
         self,
     ) -> (
         String,
-        Schema<Query, Mutation, EmptySubscription>,
+        Schema<Query, Mutation, Subscription>,
         Db,
         PackageResolver,
         Router,
@@ -256,6 +260,8 @@ impl ServerBuilder {
             let router: Router = Router::new()
                 .route("/", post(graphql_handler))
                 .route("/graphql", post(graphql_handler))
+                .route("/subscriptions", get(subscription_handler))
+                .route("/graphql/subscriptions", get(subscription_handler))
                 .route("/health", get(health_check))
                 .route("/graphql/health", get(health_check))
                 .with_state(self.state.clone())
@@ -345,6 +351,10 @@ impl ServerBuilder {
             .layer(axum::extract::Extension(schema))
             .layer(axum::extract::Extension(watermark_task.lock()))
             .layer(axum::extract::Extension(watermark_task.chain_id_lock()))
+            .layer(axum::extract::Extension(watermark_task.checkpoint_watch()))
+            .layer(axum::extract::Extension(SubscriptionSlots::new(
+                &state.service.limits,
+            )))
             .layer(Self::cors()?);
 
         Ok(Server {
@@ -509,8 +519,8 @@ impl ServerBuilder {
     }
 }
 
-fn schema_builder() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
-    async_graphql::Schema::build(Query, Mutation, EmptySubscription)
+fn schema_builder() -> SchemaBuilder<Query, Mutation, Subscription> {
+    async_graphql::Schema::build(Query, Mutation, Subscription)
         .register_output_type::<IMoveObject>()
         .register_output_type::<IObject>()
         .register_output_type::<IOwner>()
@@ -558,6 +568,42 @@ async fn graphql_handler(
     (extensions, result.into())
 }
 
+/// Entry point for graphql subscriptions, served over a WebSocket. Requests on the connection are
+/// stamped like those to `graphql_handler`, except that they share the connection's ID, and its
+/// watermark as of when it was opened. Subscriptions follow the watermark as it advances, and
+/// share the connection's slots, which are limited per connection and across the service.
+async fn subscription_handler(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Extension(schema): Extension<SuiGraphQLSchema>,
+    Extension(watermark_lock): Extension<WatermarkLock>,
+    Extension(chain_identifier_lock): Extension<ChainIdentifierLock>,
+    Extension(checkpoint_watch): Extension<CheckpointWatch>,
+    Extension(subscription_slots): Extension<SubscriptionSlots>,
+    headers: HeaderMap,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let mut data = async_graphql::Data::default();
+    data.insert(Uuid::new_v4());
+    if headers.contains_key(ShowUsage::name()) {
+        data.insert(ShowUsage)
+    }
+
+    data.insert(addr);
+    data.insert(Watermark::new(watermark_lock).await);
+    data.insert(chain_identifier_lock.read().await);
+    data.insert(checkpoint_watch);
+    data.insert(subscription_slots.connection());
+
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .with_data(data)
+                .serve()
+        })
+}
+
 #[derive(Clone)]
 struct MetricsMakeCallbackHandler {
     metrics: Metrics,
@@ -692,6 +738,7 @@ pub mod tests {
         extensions::{Extension, ExtensionContext, NextExecute},
         Request, Response, Variables,
     };
+    use futures::StreamExt;
     use serde_json::json;
     use std::sync::Arc;
     use std::time::Duration;
@@ -904,6 +951,102 @@ pub mod tests {
         assert_eq!(errs, vec![exp]);
     }
 
+    #[tokio::test]
+    async fn test_subscriptions() {
+        telemetry_subscribers::init_for_testing();
+        let cluster = start_cluster(ServiceConfig::test_defaults()).await;
+        cluster
+            .wait_for_checkpoint_catchup(1, Duration::from_secs(30))
+            .await;
+        let wallet = &cluster.network.validator_fullnode_handle.wallet;
+        let db_url = cluster.network.graphql_connection_config.db_url.clone();
+
+        let mut cfg = ServiceConfig::default();
+        cfg.limits.max_subscriptions_per_connection = 1;
+
+        // Subscriptions follow the watermark as the indexer advances it.
+        let reader =
+            PgManager::reader_with_config(db_url.clone(), 5, cfg.limits.request_timeout_ms.into())
+                .await
+                .expect("Failed to create pg connection pool");
+        let metrics = metrics();
+        let cancel = CancellationToken::new();
+        let watermark_task = WatermarkTask::new(
+            Db::new(reader, cfg.limits.clone(), metrics.clone()),
+            metrics,
+            Duration::from_millis(100),
+            cancel.clone(),
+        );
+        let checkpoint_watch = watermark_task.checkpoint_watch();
+        let connection = SubscriptionSlots::new(&cfg.limits).connection();
+        tokio::spawn(async move { watermark_task.run().await });
+
+        let schema = prep_schema(db_url, Some(cfg))
+            .await
+            .context_data(checkpoint_watch)
+            .build_schema();
+
+        let addresses = wallet.get_addresses();
+        let query = format!(
+            r#"subscription {{ transactions(filter: {{ sentAddress: "{}" }}) {{ digest }} }}"#,
+            addresses[0],
+        );
+
+        // Start the subscription, which waits for the first watermark, and then for new
+        // transactions.
+        let mut transactions =
+            schema.execute_stream(Request::new(query.clone()).data(connection.clone()));
+        assert!(
+            tokio::time::timeout(Duration::from_secs(5), transactions.next())
+                .await
+                .is_err(),
+            "No transactions should have been sent yet",
+        );
+
+        // The connection is only allowed one subscription at a time.
+        let errs: Vec<_> = schema
+            .execute_stream(Request::new(query).data(connection))
+            .next()
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            errs,
+            vec!["Too many subscriptions on this connection. Limit: 1".to_string()]
+        );
+
+        let gas = wallet
+            .get_one_gas_object_owned_by_address(addresses[0])
+            .await
+            .unwrap();
+        let tx_data = TransactionData::new_transfer_sui(
+            addresses[1],
+            addresses[0],
+            Some(1000),
+            gas.unwrap(),
+            1_000_000,
+            wallet.get_reference_gas_price().await.unwrap(),
+        );
+        let response = wallet
+            .execute_transaction_must_succeed(wallet.sign_transaction(&tx_data))
+            .await;
+
+        let next = tokio::time::timeout(Duration::from_secs(30), transactions.next())
+            .await
+            .expect("Timed out waiting for the transaction")
+            .expect("Subscription ended early");
+        assert_eq!(
+            next.into_result().unwrap().data.into_json().unwrap(),
+            json!({ "transactions": { "digest": response.digest.to_string() } }),
+        );
+
+        cancel.cancel();
+    }
+
     #[tokio::test]
     async fn test_query_depth_limit() {
         let cluster = prep_executor_cluster().await;
@@ -32,6 +32,7 @@ pub(crate) struct WatermarkTask {
     cancel: CancellationToken,
     sender: watch::Sender<u64>,
     receiver: watch::Receiver<u64>,
+    checkpoint_sender: watch::Sender<Option<Watermark>>,
 }
 
 #[derive(Clone, Default)]
@@ -39,6 +40,12 @@ pub(crate) struct ChainIdentifierLock(pub(crate) Arc<RwLock<ChainIdentifier>>);
 
 pub(crate) type WatermarkLock = Arc<RwLock<Watermark>>;
 
+/// Receiver for the latest watermark, which notifies every time the indexer commits new
+/// checkpoints. It holds `None` until the watermark has been read from the database for the first
+/// time.
+#[derive(Clone)]
+pub(crate) struct CheckpointWatch(pub(crate) watch::Receiver<Option<Watermark>>);
+
 /// Watermark used by GraphQL queries to ensure cross-query consistency and flag epoch-boundary
 /// changes.
 #[derive(Clone, Copy, Default)]
@@ -63,6 +70,7 @@ impl WatermarkTask {
         cancel: CancellationToken,
     ) -> Self {
         let (sender, receiver) = watch::channel(0);
+        let (checkpoint_sender, _) = watch::channel(None);
 
         Self {
             watermark: Default::default(),
@@ -73,6 +81,7 @@ impl WatermarkTask {
             cancel,
             sender,
             receiver,
+            checkpoint_sender,
         }
     }
 
@@ -89,7 +98,7 @@ impl WatermarkTask {
                     return;
                 },
                 _ = interval.tick() => {
-                    let Watermark { lo_cp, lo_tx, hi_cp, hi_cp_timestamp_ms, epoch } = match Watermark::query(&self.db).await {
+                    let watermark = match Watermark::query(&self.db).await {
                         Ok(Some(watermark)) => watermark,
                         Ok(None) => continue,
                         Err(e) => {
@@ -98,6 +107,7 @@ impl WatermarkTask {
                             continue;
                         }
                     };
+                    let Watermark { lo_cp, lo_tx, hi_cp, hi_cp_timestamp_ms, epoch } = watermark;
 
                     // Write the watermark as follows to limit how long we hold the lock
                     let prev_epoch = {
@@ -113,6 +123,14 @@ impl WatermarkTask {
                     if epoch > prev_epoch {
                         self.sender.send(epoch).unwrap();
                     }
+
+                    // Keep the latest watermark for subscribers, but only notify them of new
+                    // checkpoints.
+                    self.checkpoint_sender.send_if_modified(|w| {
+                        let advanced = w.is_none_or(|w| hi_cp > w.hi_cp);
+                        *w = Some(watermark);
+                        advanced
+                    });
                 }
             }
         }
@@ -131,6 +149,11 @@ impl WatermarkTask {
         self.receiver.clone()
     }
 
+    /// Receiver for subscribing to new checkpoints.
+    pub(crate) fn checkpoint_watch(&self) -> CheckpointWatch {
+        CheckpointWatch(self.checkpoint_sender.subscribe())
+    }
+
     // Fetch the chain identifier (once) from the database and cache it.
     async fn get_and_cache_chain_identifier(&self, interval: &mut Interval) {
         loop {
@@ -0,0 +1,347 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_graphql::connection::CursorType;
+use async_graphql::*;
+use async_stream::stream;
+use futures::Stream;
+use tokio::sync::{watch, OwnedSemaphorePermit, Semaphore};
+
+use crate::config::{Limits, ServiceConfig};
+use crate::data::Db;
+use crate::error::{code, Error};
+use crate::server::watermark_task::{CheckpointWatch, Watermark};
+use crate::types::checkpoint::{self, Checkpoint};
+use crate::types::cursor::Page;
+use crate::types::event::{self, Event, EventFilter};
+use crate::types::transaction_block::{self, TransactionBlock, TransactionBlockFilter};
+use crate::types::uint53::UInt53;
+
+pub struct Subscription;
+
+/// Bounds the number of subscriptions that can be open at once across the service. Each connection
+/// gets its own [`ConnectionSubscriptions`], which shares these slots, and further bounds the
+/// subscriptions open on that connection.
+#[derive(Clone)]
+pub(crate) struct SubscriptionSlots {
+    server: Arc<Semaphore>,
+    max_subscriptions: usize,
+    max_per_connection: usize,
+}
+
+/// Slots for subscriptions on a single connection.
+#[derive(Clone)]
+pub(crate) struct ConnectionSubscriptions {
+    server: Arc<Semaphore>,
+    connection: Arc<Semaphore>,
+    max_subscriptions: usize,
+    max_per_connection: usize,
+}
+
+/// Held by a subscription's stream for as long as it is open, to reserve its slots.
+struct SubscriptionPermit {
+    _server: OwnedSemaphorePermit,
+    _connection: OwnedSemaphorePermit,
+}
+
+/// Subscriptions push data to the client as it is indexed, over a WebSocket connection. Each
+/// subscription starts from the latest checkpoint known to the service when it is made, and
+/// delivers data in the order it was committed to the chain.
+#[Subscription]
+impl Subscription {
+    /// Checkpoints, as they are indexed.
+    async fn checkpoints<'ctx>(
+        &self,
+        ctx: &'ctx Context<'_>,
+    ) -> Result<impl Stream<Item = Result<Checkpoint>> + 'ctx> {
+        let permit = ctx.data::<ConnectionSubscriptions>()?.acquire()?;
+        let CheckpointWatch(mut watermark) = ctx.data::<CheckpointWatch>()?.clone();
+        let config: &ServiceConfig = ctx.data_unchecked();
+        let db: &Db = ctx.data_unchecked();
+        let page_size = config.limits.max_page_size as u64;
+
+        // The latest checkpoint at the time of subscribing has already been seen.
+        let Watermark { hi_cp, .. } = first_watermark(&mut watermark).await?;
+        let start = Some(checkpoint::Cursor::new(checkpoint::CheckpointCursor {
+            checkpoint_viewed_at: hi_cp,
+            sequence_number: hi_cp,
+        }));
+
+        let next_page = move |w: Watermark, after: Option<checkpoint::Cursor>| async move {
+            let hi_cp = w.hi_cp;
+            let after = after.map(|c| c.viewed_at(hi_cp));
+            let page = Page::from_params(config, Some(page_size), after, None, None)?;
+            let connection = Checkpoint::paginate(db, page, /* epoch */ None, hi_cp)
+                .await
+                .extend()?;
+
+            let end = connection.edges.last().map(|e| e.cursor.clone());
+            NextPage::new(
+                connection.edges.into_iter().map(|e| e.node).collect(),
+                end,
+                connection.has_next_page,
+            )
+        };
+
+        Ok(follow(config, permit, watermark, start, next_page))
+    }
+
+    /// Transaction blocks matching `filter`, as they are indexed.
+    ///
+    /// `scanLimit` restricts the number of candidate transactions scanned when gathering each
+    /// batch of results, as it does for `Query.transactionBlocks`. It is required for filters
+    /// that apply more than two complex filters (on function, kind, sender, recipient, input
+    /// object, changed object, or ids), and can be at most `serviceConfig.maxScanLimit`.
+    async fn transactions<'ctx>(
+        &self,
+        ctx: &'ctx Context<'_>,
+        filter: Option<TransactionBlockFilter>,
+        scan_limit: Option<u64>,
+    ) -> Result<impl Stream<Item = Result<TransactionBlock>> + 'ctx> {
+        let permit = ctx.data::<ConnectionSubscriptions>()?.acquire()?;
+        let CheckpointWatch(mut watermark) = ctx.data::<CheckpointWatch>()?.clone();
+        let config: &ServiceConfig = ctx.data_unchecked();
+        let page_size = config.limits.max_page_size as u64;
+
+        // Only look for transactions from checkpoints after the latest one at the time of
+        // subscribing.
+        let Watermark { hi_cp, .. } = first_watermark(&mut watermark).await?;
+        let Some(filter) = filter
+            .unwrap_or_default()
+            .intersect(TransactionBlockFilter {
+                after_checkpoint: Some(UInt53::from(hi_cp)),
+                ..Default::default()
+            })
+        else {
+            return Err(Error::Client(
+                "Filter cannot match transactions from future checkpoints".to_string(),
+            )
+            .extend());
+        };
+
+        Ok(follow(config, permit, watermark, None, move |w, after| {
+            let filter = filter.clone();
+            async move {
+                let after = after.map(|c: transaction_block::Cursor| c.viewed_at(w.hi_cp));
+                let page = Page::from_params(config, Some(page_size), after, None, None)?;
+                let connection = TransactionBlock::paginate_above(
+                    ctx, page, filter, w.lo_cp, w.hi_cp, scan_limit,
+                )
+                .await
+                .extend()?;
+
+                // When the scan limit is reached, the end cursor points at the last transaction
+                // scanned, rather than the last one in the page.
+                let end = connection
+                    .end_cursor
+                    .clone()
+                    .or_else(|| connection.edges.last().map(|e| e.cursor.clone()));
+                NextPage::new(
+                    connection.edges.into_iter().map(|e| e.node).collect(),
+                    end,
+                    connection.has_next_page,
+                )
+            }
+        }))
+    }
+
+    /// Events matching `filter`, as they are indexed.
+    async fn events<'ctx>(
+        &self,
+        ctx: &'ctx Context<'_>,
+        filter: Option<EventFilter>,
+    ) -> Result<impl Stream<Item = Result<Event>> + 'ctx> {
+        let permit = ctx.data::<ConnectionSubscriptions>()?.acquire()?;
+        let CheckpointWatch(mut watermark) = ctx.data::<CheckpointWatch>()?.clone();
+        let config: &ServiceConfig = ctx.data_unchecked();
+        let db: &Db = ctx.data_unchecked();
+        let filter = filter.unwrap_or_default();
+        let page_size = config.limits.max_page_size as u64;
+
+        // Resume after the last matching event at the time of subscribing, if there is one.
+        let Watermark { hi_cp, .. } = first_watermark(&mut watermark).await?;
+        let page = Page::from_params(config, None, None, Some(1), None)?;
+        let latest = Event::paginate(db, page, filter.clone(), hi_cp)
+            .await
+            .extend()?;
+        let start = latest.edges.last().map(|e| e.cursor.clone());
+        let start = start.map(|c| decode_cursor(&c)).transpose()?;
+
+        Ok(follow(config, permit, watermark, start, move |w, after| {
+            let filter = filter.clone();
+            async move {
+                let after = after.map(|c: event::Cursor| c.viewed_at(w.hi_cp));
+                let page = Page::from_params(config, Some(page_size), after, None, None)?;
+                let connection = Event::paginate(db, page, filter, w.hi_cp).await.extend()?;
+
+                let end = connection.edges.last().map(|e| e.cursor.clone());
+                NextPage::new(
+                    connection.edges.into_iter().map(|e| e.node).collect(),
+                    end,
+                    connection.has_next_page,
+                )
+            }
+        }))
+    }
+}
+
+impl SubscriptionSlots {
+    pub(crate) fn new(limits: &Limits) -> Self {
+        let max_subscriptions = limits.max_subscriptions as usize;
+        Self {
+            server: Arc::new(Semaphore::new(max_subscriptions)),
+            max_subscriptions,
+            max_per_connection: limits.max_subscriptions_per_connection as usize,
+        }
+    }
+
+    /// Slots for a newly opened connection.
+    pub(crate) fn connection(&self) -> ConnectionSubscriptions {
+        ConnectionSubscriptions {
+            server: self.server.clone(),
+            connection: Arc::new(Semaphore::new(self.max_per_connection)),
+            max_subscriptions: self.max_subscriptions,
+            max_per_connection: self.max_per_connection,
+        }
+    }
+}
+
+impl ConnectionSubscriptions {
+    /// Reserve a slot for a new subscription on this connection, failing if the connection or the
+    /// service already has as many subscriptions open as it allows.
+    fn acquire(&self) -> Result<SubscriptionPermit> {
+        let Ok(connection) = self.connection.clone().try_acquire_owned() else {
+            return Err(Error::Client(format!(
+                "Too many subscriptions on this connection. Limit: {}",
+                self.max_per_connection,
+            ))
+            .extend());
+        };
+
+        let Ok(server) = self.server.clone().try_acquire_owned() else {
+            return Err(Error::Client(format!(
+                "Too many subscriptions open on this service. Limit: {}",
+                self.max_subscriptions,
+            ))
+            .extend());
+        };
+
+        Ok(SubscriptionPermit {
+            _server: server,
+            _connection: connection,
+        })
+    }
+}
+
+/// A page of results fetched by a subscription, along with the cursor to resume from.
+struct NextPage<T, C> {
+    nodes: Vec<T>,
+    end: Option<C>,
+    has_next_page: bool,
+}
+
+impl<T, C: CursorType> NextPage<T, C> {
+    fn new(nodes: Vec<T>, end: Option<String>, has_next_page: bool) -> Result<Self> {
+        Ok(Self {
+            nodes,
+            end: end.map(|c| decode_cursor(&c)).transpose()?,
+            has_next_page,
+        })
+    }
+}
+
+/// Wait for the service to read its first watermark from the database, and return it.
+async fn first_watermark(watermark: &mut watch::Receiver<Option<Watermark>>) -> Result<Watermark> {
+    // The sender is dropped when the service shuts down.
+    match watermark.wait_for(Option::is_some).await {
+        Ok(w) => Ok(w.expect("watermark is set")),
+        Err(_) => Err(Error::Internal("Service is shutting down".to_string()).extend()),
+    }
+}
+
+fn decode_cursor<C: CursorType>(cursor: &str) -> Result<C> {
+    C::decode_cursor(cursor)
+        .map_err(|_| Error::Internal(format!("Failed to decode cursor: {cursor}")).extend())
+}
+
+/// Follow the chain as the watermark advances: every time new checkpoints are indexed, fetch
+/// pages of results with `next_page`, starting after the cursor where the previous page ended
+/// (or `start` for the first page), until there are no more results as of the latest checkpoint.
+///
+/// Each page is fetched as of the latest watermark, and is subject to the service's request
+/// timeout. The stream holds onto `permit` for as long as it is open, and ends with the first
+/// error, or when the service shuts down.
+fn follow<'a, T, C, F, Fut>(
+    config: &ServiceConfig,
+    permit: SubscriptionPermit,
+    mut watermark: watch::Receiver<Option<Watermark>>,
+    start: Option<C>,
+    next_page: F,
+) -> impl Stream<Item = Result<T>> + 'a
+where
+    T: 'a,
+    C: Clone + 'a,
+    F: Fn(Watermark, Option<C>) -> Fut + 'a,
+    Fut: Future<Output = Result<NextPage<T, C>>> + 'a,
+{
+    let request_timeout = Duration::from_millis(config.limits.request_timeout_ms.into());
+    stream! {
+        let _permit = permit;
+        let mut after = start;
+        let mut current = Watermark::default();
+        loop {
+            loop {
+                if let Some(w) = *watermark.borrow_and_update() {
+                    current = w;
+                }
+
+                let page = match tokio::time::timeout(
+                    request_timeout,
+                    next_page(current, after.clone()),
+                )
+                .await
+                {
+                    Ok(Ok(page)) => page,
+                    Ok(Err(e)) => {
+                        yield Err(e);
+                        return;
+                    }
+                    Err(_) => {
+                        yield Err(async_graphql::Error::new(format!(
+                            "Subscription request timed out. Limit: {}s",
+                            request_timeout.as_secs_f32(),
+                        ))
+                        .extend_with(|_, e| e.set("code", code::REQUEST_TIMEOUT)));
+                        return;
+                    }
+                };
+
+                if page.end.is_some() {
+                    after = page.end;
+                }
+
+                for node in page.nodes {
+                    yield Ok(node);
+                }
+
+                if !page.has_next_page {
+                    break;
+                }
+            }
+
+            // The sender is dropped when the service shuts down.
+            let hi_cp = current.hi_cp;
+            if watermark
+                .wait_for(|w| w.is_some_and(|w| w.hi_cp > hi_cp))
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
@@ -126,8 +126,10 @@ impl Epoch {
         let last = match self.stored.last_checkpoint_id {
             Some(last) => last as u64,
             None => {
+                // Subscriptions view epochs at checkpoints past the watermark their connection
+                // was opened with.
                 let Watermark { hi_cp, .. } = *ctx.data_unchecked();
-                hi_cp
+                hi_cp.max(self.checkpoint_viewed_at)
             }
         };
 
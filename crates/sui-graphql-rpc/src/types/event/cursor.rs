@@ -122,6 +122,17 @@ impl Checkpointed for Cursor {
     }
 }
 
+impl Cursor {
+    /// The same position, viewed at a later checkpoint, to continue paginating over data that has
+    /// been indexed since this cursor was created.
+    pub(crate) fn viewed_at(&self, checkpoint_viewed_at: u64) -> Self {
+        Cursor::new(EventKey {
+            checkpoint_viewed_at,
+            ..(**self).clone()
+        })
+    }
+}
+
 impl ScanLimited for Cursor {}
 
 impl Target<Cursor> for EvLookup {
@@ -352,11 +352,25 @@ impl TransactionBlock {
         checkpoint_viewed_at: u64,
         scan_limit: Option<u64>,
     ) -> Result<ScanConnection<String, TransactionBlock>, Error> {
-        let limits = &ctx.data_unchecked::<ServiceConfig>().limits;
-        let db: &Db = ctx.data_unchecked();
         // If we've entered this function, we already fetched `checkpoint_viewed_at` from the
         // `Watermark`, and so we must be able to retrieve `lo_cp` as well.
         let Watermark { lo_cp, .. } = *ctx.data_unchecked();
+        Self::paginate_above(ctx, page, filter, lo_cp, checkpoint_viewed_at, scan_limit).await
+    }
+
+    /// Like [`Self::paginate`], but with the lowest unpruned checkpoint, `lo_cp`, provided by the
+    /// caller rather than read from the request's `Watermark`. Subscriptions outlive the watermark
+    /// they were opened with, and provide their own as it advances.
+    pub(crate) async fn paginate_above(
+        ctx: &Context<'_>,
+        page: Page<Cursor>,
+        filter: TransactionBlockFilter,
+        lo_cp: u64,
+        checkpoint_viewed_at: u64,
+        scan_limit: Option<u64>,
+    ) -> Result<ScanConnection<String, TransactionBlock>, Error> {
+        let limits = &ctx.data_unchecked::<ServiceConfig>().limits;
+        let db: &Db = ctx.data_unchecked();
 
         // If the caller has provided some arbitrary combination of `function`, `kind`,
         // `recvAddress`, `inputObject`, or `changedObject`, we require setting a `scanLimit`.
@@ -4,6 +4,7 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
 use anyhow::Context;
+use fastcrypto::encoding::{Base64, Encoding};
 use prometheus::Registry;
 use reqwest::Client;
 use serde_json::{json, Value};
@@ -14,6 +15,7 @@ use sui_indexer_alt_jsonrpc::{
 use sui_macros::sim_test;
 use sui_pg_db::{temp::get_available_port, DbArgs};
 use sui_swarm_config::genesis_config::AccountConfig;
+use sui_types::transaction::TransactionDataAPI;
 use test_cluster::{TestCluster, TestClusterBuilder};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
@@ -106,6 +108,24 @@ impl FnDelegationTestCluster {
         Ok((tx_digest, tx_bytes, sigs))
     }
 
+    /// Builds a simple transaction and returns its sender, and its transaction kind bytes (without
+    /// gas information), to be used for dev-inspect.
+    async fn transfer_transaction_kind(&self) -> anyhow::Result<(String, String)> {
+        let addresses = self.onchain_cluster.wallet.get_addresses();
+
+        let recipient = addresses[1];
+        let tx = self
+            .onchain_cluster
+            .test_transaction_builder()
+            .await
+            .transfer_sui(Some(1_000), recipient)
+            .build();
+        let sender = tx.sender().to_string();
+        let kind_bytes = Base64::encode(bcs::to_bytes(tx.kind())?);
+
+        Ok((sender, kind_bytes))
+    }
+
     /// Builds a transaction that would abort if called by a normal user.
     async fn privileged_transaction(&self) -> anyhow::Result<(String, String, Vec<String>)> {
         let tx: sui_types::transaction::TransactionData = self
@@ -378,6 +398,54 @@ async fn test_dry_run_with_invalid_tx() {
     test_cluster.stopped().await;
 }
 
+#[sim_test]
+async fn test_dev_inspect() {
+    let test_cluster = FnDelegationTestCluster::new()
+        .await
+        .expect("Failed to create test cluster");
+
+    let (sender, tx_bytes) = test_cluster.transfer_transaction_kind().await.unwrap();
+
+    let response = test_cluster
+        .execute_jsonrpc(
+            "sui_devInspectTransactionBlock".to_string(),
+            json!({
+                "sender_address": sender,
+                "tx_bytes": tx_bytes,
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response["result"]["effects"]["status"]["status"], "success");
+
+    test_cluster.stopped().await;
+}
+
+#[sim_test]
+async fn test_dev_inspect_with_invalid_tx() {
+    let test_cluster = FnDelegationTestCluster::new()
+        .await
+        .expect("Failed to create test cluster");
+
+    let (sender, _) = test_cluster.transfer_transaction_kind().await.unwrap();
+
+    let response = test_cluster
+        .execute_jsonrpc(
+            "sui_devInspectTransactionBlock".to_string(),
+            json!({
+                "sender_address": sender,
+                "tx_bytes": "invalid_tx_bytes",
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["message"], "Invalid params");
+    test_cluster.stopped().await;
+}
+
 #[sim_test]
 async fn test_get_all_balances() {
     let test_cluster = FnDelegationTestCluster::new()
@@ -0,0 +1,83 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A --simulator
+
+// 1. Default behavior of getCheckpoints (no cursor, limit, or order)
+// 2. Setting a limit
+// 3. Setting a limit and cursor
+// 4. Changing the order
+// 5. Setting the order, cursor and limit
+// 6. Cursor past the end of the available checkpoints
+// 7. Providing a bad cursor
+// 8. Page size too large
+
+//# programmable --inputs 12 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# programmable --inputs 34 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# programmable --inputs 56 @A
+//> SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# create-checkpoint
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": []
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": [null, 2]
+}
+
+//# run-jsonrpc --cursors 1
+{
+  "method": "sui_getCheckpoints",
+  "params": ["@{cursor_0}", 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": [null, null, true]
+}
+
+//# run-jsonrpc --cursors 3
+{
+  "method": "sui_getCheckpoints",
+  "params": ["@{cursor_0}", 2, true]
+}
+
+//# run-jsonrpc --cursors 5
+{
+  "method": "sui_getCheckpoints",
+  "params": ["@{cursor_0}", 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": ["i_am_not_a_cursor"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getCheckpoints",
+  "params": [null, 10000]
+}
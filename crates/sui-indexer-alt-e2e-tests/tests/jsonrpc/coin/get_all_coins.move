@@ -0,0 +1,97 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --addresses Test=0x0 --accounts A B --simulator --objects-snapshot-min-checkpoint-lag 2
+
+// 1. All of A's coins, of every type, ordered by balance bucket (descending)
+// 2. Setting a limit
+// 3. Setting a cursor and a limit, in the middle of a bucket shared by coins of different types
+// 4. Setting a cursor that points into the last page
+// 5. An address that owns no coins
+// 6. Providing a bad cursor
+// 7. Page size too large
+
+//# publish --sender A
+module Test::fake {
+    use sui::coin;
+
+    public struct FAKE has drop {}
+
+    fun init(witness: FAKE, ctx: &mut TxContext){
+        let (mut treasury_cap, metadata) = coin::create_currency(
+            witness,
+            2,
+            b"FAKE",
+            b"",
+            b"",
+            option::none(),
+            ctx,
+        );
+
+        let c1 = coin::mint(&mut treasury_cap, 100, ctx);
+        let c2 = coin::mint(&mut treasury_cap, 2, ctx);
+        let c3 = coin::mint(&mut treasury_cap, 3000, ctx);
+        let c4 = coin::mint(&mut treasury_cap, 4000, ctx);
+        let c5 = coin::mint(&mut treasury_cap, 5000, ctx);
+
+        transfer::public_freeze_object(metadata);
+        transfer::public_transfer(treasury_cap, tx_context::sender(ctx));
+        transfer::public_transfer(c1, tx_context::sender(ctx));
+        transfer::public_transfer(c2, tx_context::sender(ctx));
+        transfer::public_transfer(c3, tx_context::sender(ctx));
+        transfer::public_transfer(c4, tx_context::sender(ctx));
+        transfer::public_transfer(c5, tx_context::sender(ctx));
+    }
+}
+
+//# programmable --sender A --inputs 1000 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 20 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllCoins",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllCoins",
+  "params": ["@{A}", null, 3]
+}
+
+//# run-jsonrpc --cursors bcs(@{obj_2_0},1,3)
+{
+  "method": "suix_getAllCoins",
+  "params": ["@{A}", "@{cursor_0}", 2]
+}
+
+//# run-jsonrpc --cursors bcs(@{obj_3_0},1,1)
+{
+  "method": "suix_getAllCoins",
+  "params": ["@{A}", "@{cursor_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllCoins",
+  "params": ["@{B}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllCoins",
+  "params": ["@{A}", "i_am_not_a_cursor"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllCoins",
+  "params": ["@{A}", null, 10000]
+}
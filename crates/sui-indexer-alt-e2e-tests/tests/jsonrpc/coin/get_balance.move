@@ -0,0 +1,88 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --addresses Test=0x0 --accounts A B --simulator --objects-snapshot-min-checkpoint-lag 2
+
+// 1. SUI balance, when no coin type is given
+// 2. SUI balance, with an explicit coin type
+// 3. Balance of a custom coin, summed across coins in different balance buckets
+// 4. Balance of a coin type the address owns none of
+// 5. SUI balance of an address after it receives a coin
+// 6. Providing a bad coin type
+
+//# publish --sender A
+module Test::fake {
+    use sui::coin;
+
+    public struct FAKE has drop {}
+
+    fun init(witness: FAKE, ctx: &mut TxContext){
+        let (mut treasury_cap, metadata) = coin::create_currency(
+            witness,
+            2,
+            b"FAKE",
+            b"",
+            b"",
+            option::none(),
+            ctx,
+        );
+
+        let c1 = coin::mint(&mut treasury_cap, 100, ctx);
+        let c2 = coin::mint(&mut treasury_cap, 2, ctx);
+        let c3 = coin::mint(&mut treasury_cap, 3000, ctx);
+        let c4 = coin::mint(&mut treasury_cap, 4000, ctx);
+        let c5 = coin::mint(&mut treasury_cap, 5000, ctx);
+
+        transfer::public_freeze_object(metadata);
+        transfer::public_transfer(treasury_cap, tx_context::sender(ctx));
+        transfer::public_transfer(c1, tx_context::sender(ctx));
+        transfer::public_transfer(c2, tx_context::sender(ctx));
+        transfer::public_transfer(c3, tx_context::sender(ctx));
+        transfer::public_transfer(c4, tx_context::sender(ctx));
+        transfer::public_transfer(c5, tx_context::sender(ctx));
+    }
+}
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{A}", "0x2::sui::SUI"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{A}", "@{Test}::fake::FAKE"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{B}", "@{Test}::fake::FAKE"]
+}
+
+//# programmable --sender A --inputs 1000 @B
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{B}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{A}", "not_a_type"]
+}
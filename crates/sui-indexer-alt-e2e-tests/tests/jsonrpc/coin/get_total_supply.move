@@ -0,0 +1,68 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --addresses Test=0x0 --accounts A --simulator
+
+// 1. Total supply of a custom coin
+// 2. Total supply of SUI
+// 3. Total supply of a type that is not a coin (no TreasuryCap)
+// 4. Providing a bad coin type
+
+//# publish --sender A
+module Test::fake {
+    use sui::coin;
+
+    public struct FAKE has drop {}
+
+    fun init(witness: FAKE, ctx: &mut TxContext){
+        let (mut treasury_cap, metadata) = coin::create_currency(
+            witness,
+            2,
+            b"FAKE",
+            b"",
+            b"",
+            option::none(),
+            ctx,
+        );
+
+        let c1 = coin::mint(&mut treasury_cap, 100, ctx);
+        let c2 = coin::mint(&mut treasury_cap, 2, ctx);
+        let c3 = coin::mint(&mut treasury_cap, 3000, ctx);
+        let c4 = coin::mint(&mut treasury_cap, 4000, ctx);
+        let c5 = coin::mint(&mut treasury_cap, 5000, ctx);
+
+        transfer::public_freeze_object(metadata);
+        transfer::public_transfer(treasury_cap, tx_context::sender(ctx));
+        transfer::public_transfer(c1, tx_context::sender(ctx));
+        transfer::public_transfer(c2, tx_context::sender(ctx));
+        transfer::public_transfer(c3, tx_context::sender(ctx));
+        transfer::public_transfer(c4, tx_context::sender(ctx));
+        transfer::public_transfer(c5, tx_context::sender(ctx));
+    }
+}
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getTotalSupply",
+  "params": ["@{Test}::fake::FAKE"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getTotalSupply",
+  "params": ["0x2::sui::SUI"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getTotalSupply",
+  "params": ["@{Test}::fake::NOT_A_COIN"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getTotalSupply",
+  "params": ["not_a_type"]
+}
@@ -0,0 +1,86 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A --simulator
+
+// 1. All the dynamic fields of an object, most recently modified first
+// 2. Setting a limit
+// 3. Setting a cursor and a limit
+// 4. Setting a cursor that points into the last page
+// 5. Removed fields are not returned
+// 6. An object with no dynamic fields
+// 7. Providing a bad cursor
+// 8. Page size too large
+
+//# programmable --sender A --inputs @A
+//> 0: sui::bag::new();
+//> 1: TransferObjects([Result(0)], Input(0))
+
+//# programmable --sender A --inputs object(1,0) 1 10
+//> 0: sui::bag::add<u64, u64>(Input(0), Input(1), Input(2))
+
+//# programmable --sender A --inputs object(1,0) 2 20
+//> 0: sui::bag::add<u64, u64>(Input(0), Input(1), Input(2))
+
+//# create-checkpoint
+
+//# programmable --sender A --inputs object(1,0) 3 30
+//> 0: sui::bag::add<u64, u64>(Input(0), Input(1), Input(2))
+
+//# programmable --sender A --inputs object(1,0) 4 40
+//> 0: sui::bag::add<u64, u64>(Input(0), Input(1), Input(2))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_1_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_1_0}", null, 2]
+}
+
+//# run-jsonrpc --cursors bcs(@{obj_5_0},2)
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_1_0}", "@{cursor_0}", 2]
+}
+
+//# run-jsonrpc --cursors bcs(@{obj_2_0},1)
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_1_0}", "@{cursor_0}"]
+}
+
+//# programmable --sender A --inputs object(1,0) 1
+//> 0: sui::bag::remove<u64, u64>(Input(0), Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_1_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_0_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_1_0}", "i_am_not_a_cursor"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_1_0}", null, 10000]
+}
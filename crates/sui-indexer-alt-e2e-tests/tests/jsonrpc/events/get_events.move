@@ -0,0 +1,77 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A --addresses P=0x0 --simulator
+
+// 1. Events from a transaction that emitted multiple events, in emission order
+// 2. Events from a transaction that emitted events of different types
+// 3. A transaction that emitted no events
+// 4. A transaction that doesn't exist
+
+//# publish
+module P::M {
+  use sui::event;
+
+  public struct E1 has copy, drop {
+    x: u64,
+  }
+
+  public struct E2<T: copy + drop> has copy, drop {
+    t: T,
+  }
+
+  public fun emit_e1(n: u64) {
+    let mut i = 0;
+    while (i < n) {
+      event::emit(E1 { x: i });
+      i = i + 1;
+    }
+  }
+
+  public fun emit_e2<T: copy + drop>(t: T) {
+    event::emit(E2 { t })
+  }
+}
+
+module P::N {
+  public fun emit_via_m(n: u64) {
+    P::M::emit_e1(n)
+  }
+}
+
+//# programmable --sender A --inputs 3
+//> 0: P::M::emit_e1(Input(0))
+
+//# programmable --sender A --inputs 42 true
+//> 0: P::M::emit_e2<u64>(Input(0));
+//> 1: P::M::emit_e2<bool>(Input(1))
+
+//# programmable --sender A --inputs 42 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getEvents",
+  "params": ["@{digest_2}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getEvents",
+  "params": ["@{digest_3}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getEvents",
+  "params": ["@{digest_4}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getEvents",
+  "params": ["11111111111111111111111111111111"]
+}
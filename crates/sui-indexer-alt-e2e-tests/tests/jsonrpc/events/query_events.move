@@ -0,0 +1,184 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses P=0x0 --simulator
+
+// Transactions are numbered from 0 (genesis), so the transactions below that emit events are:
+//
+//   tx 2: A emits three `M::E1`s, from `M`
+//   tx 3: B emits an `M::E2<u64>` and an `M::E2<bool>`, from `M`
+//   tx 4: A emits two `M::E1`s, from `N`
+//   tx 6: A emits one `M::E1`, from `M`
+//
+// Event cursors are of the form {"tx":T,"ev":E}, and are exclusive: pagination continues from the
+// event after the one they point to, possibly in the same transaction.
+//
+//  1. All events, in ascending order
+//  2. Setting a limit
+//  3. Setting a cursor and a limit, where the cursor points into the middle of a transaction
+//  4. All events, in descending order
+//  5. Setting the order, cursor and limit
+//  6. Cursor pointing at the last event
+//  7. Filtering by sender
+//  8. Filtering by sender, with a cursor
+//  9. Filtering by emitting module
+// 10. Filtering by emitting module, in descending order
+// 11. Filtering by the module that defines the event type
+// 12. Filtering by event type
+// 13. Filtering by event type, including type parameters
+// 14. Filtering by transaction
+// 15. Filtering by a transaction that doesn't exist
+// 16. Providing a bad cursor
+// 17. Page size too large
+
+//# publish
+module P::M {
+  use sui::event;
+
+  public struct E1 has copy, drop {
+    x: u64,
+  }
+
+  public struct E2<T: copy + drop> has copy, drop {
+    t: T,
+  }
+
+  public fun emit_e1(n: u64) {
+    let mut i = 0;
+    while (i < n) {
+      event::emit(E1 { x: i });
+      i = i + 1;
+    }
+  }
+
+  public fun emit_e2<T: copy + drop>(t: T) {
+    event::emit(E2 { t })
+  }
+}
+
+module P::N {
+  public fun emit_via_m(n: u64) {
+    P::M::emit_e1(n)
+  }
+}
+
+//# programmable --sender A --inputs 3
+//> 0: P::M::emit_e1(Input(0))
+
+//# programmable --sender B --inputs 42 true
+//> 0: P::M::emit_e2<u64>(Input(0));
+//> 1: P::M::emit_e2<bool>(Input(1))
+
+//# programmable --sender A --inputs 2
+//> 0: P::N::emit_via_m(Input(0))
+
+//# programmable --sender A --inputs 42 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 1
+//> 0: P::M::emit_e1(Input(0))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, null, 2]
+}
+
+//# run-jsonrpc --cursors {"tx":2,"ev":1}
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, "@{cursor_0}", 3]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, null, null, true]
+}
+
+//# run-jsonrpc --cursors {"tx":4,"ev":1}
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, "@{cursor_0}", 2, true]
+}
+
+//# run-jsonrpc --cursors {"tx":6,"ev":0}
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, "@{cursor_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{B}" }]
+}
+
+//# run-jsonrpc --cursors {"tx":2,"ev":2}
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Sender": "@{A}" }, "@{cursor_0}", 3]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveModule": { "package": "@{P}", "module": "N" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveModule": { "package": "@{P}", "module": "M" } }, null, 4, true]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventModule": { "package": "@{P}", "module": "M" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventType": "@{P}::M::E1" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveEventType": "@{P}::M::E2<bool>" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Transaction": "@{digest_3}" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "Transaction": "11111111111111111111111111111111" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, "i_am_not_a_cursor"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "All": [] }, null, 10000]
+}
@@ -0,0 +1,87 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --simulator --accounts A B
+
+// 1. An address with no stakes
+// 2. A stake that is pending (not yet active)
+// 3. Stakes that are active, grouped by pool, with estimated rewards
+// 4. Fetching a stake by ID
+// 5. A stake that has been withdrawn shows up as unstaked when fetched by ID, but is no longer
+//    returned for its owner
+// 6. Fetching an ID that is not a stake
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakes",
+  "params": ["@{A}"]
+}
+
+//# programmable --sender A --inputs 1000000000 object(0x5) @validator_0
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: sui_system::sui_system::request_add_stake(Input(1), Result(0), Input(2))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakes",
+  "params": ["@{A}"]
+}
+
+//# programmable --sender A --inputs 2000000000 object(0x5) @validator_0
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: sui_system::sui_system::request_add_stake(Input(1), Result(0), Input(2))
+
+//# programmable --sender B --inputs 3000000000 object(0x5) @validator_0
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: sui_system::sui_system::request_add_stake(Input(1), Result(0), Input(2))
+
+//# create-checkpoint
+
+//# advance-clock --duration-ns 1000000
+
+//# advance-epoch
+
+//# advance-epoch
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakes",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakes",
+  "params": ["@{B}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakesByIds",
+  "params": [["@{obj_2_1}"]]
+}
+
+//# programmable --sender A --inputs object(0x5) object(2,1)
+//> 0: sui_system::sui_system::request_withdraw_stake(Input(0), Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakes",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakesByIds",
+  "params": [["@{obj_2_1}"]]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getStakesByIds",
+  "params": [["@{obj_0_0}"]]
+}
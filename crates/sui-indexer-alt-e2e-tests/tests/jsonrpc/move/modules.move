@@ -0,0 +1,134 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A --addresses P=0x0 --simulator
+
+//  1. All the modules in a package
+//  2. A single module
+//  3. A struct with type parameters
+//  4. A struct from another module, that refers to a type from the first module
+//  5. The argument types of a function, including objects passed by value, by reference, and by
+//     mutable reference
+//  6. The argument types of an entry function that only takes pure values
+//  7. A system package
+//  8. Attempting to get modules from an object that doesn't exist
+//  9. Attempting to get modules from a move object (not a package)
+// 10. Attempting to get a module that doesn't exist
+// 11. Attempting to get a struct that doesn't exist
+// 12. Attempting to get the argument types of a function that doesn't exist
+// 13. Attempting to get a struct with an invalid identifier
+
+//# publish --sender A
+module P::M {
+  public struct O has key, store {
+    id: UID,
+  }
+
+  public struct S<T: store> has store {
+    x: u64,
+    t: T,
+  }
+
+  public fun foo(_: u64, o: O, p: &mut O, q: &O, _: &mut TxContext): (u32, O) {
+    let _ = p;
+    let _ = q;
+    (0, o)
+  }
+
+  entry fun bar(_: u16, _: vector<u8>) {}
+}
+
+module P::N {
+  use P::M::O;
+
+  public struct W has key {
+    id: UID,
+    o: O,
+  }
+
+  public fun unwrap(w: W): O {
+    let W { id, o } = w;
+    id.delete();
+    o
+  }
+}
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveModulesByPackage",
+  "params": ["@{P}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveModule",
+  "params": ["@{P}", "M"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveStruct",
+  "params": ["@{P}", "M", "S"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveStruct",
+  "params": ["@{P}", "N", "W"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getMoveFunctionArgTypes",
+  "params": ["@{P}", "M", "foo"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getMoveFunctionArgTypes",
+  "params": ["@{P}", "M", "bar"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveModule",
+  "params": ["0x1", "option"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveModulesByPackage",
+  "params": ["0x0"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveModulesByPackage",
+  "params": ["@{obj_0_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveModule",
+  "params": ["@{P}", "O"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveStruct",
+  "params": ["@{P}", "M", "T"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getMoveFunctionArgTypes",
+  "params": ["@{P}", "M", "baz"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getNormalizedMoveStruct",
+  "params": ["@{P}", "M", "not a struct"]
+}
@@ -0,0 +1,120 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --simulator
+
+// 1. Fetching multiple transactions, which are returned in the order they were requested in
+// 2. Fetching the same transaction more than once
+// 3. Supplying response options
+// 4. Fetching no transactions
+// 5. One of the transactions doesn't exist
+// 6. Too many transactions requested
+
+//# programmable --sender A --inputs 12 @B
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 34 @B
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# programmable --sender B --inputs 56 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_multiGetTransactionBlocks",
+  "params": [["@{digest_4}", "@{digest_1}", "@{digest_2}"]]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_multiGetTransactionBlocks",
+  "params": [["@{digest_1}", "@{digest_1}"]]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_multiGetTransactionBlocks",
+  "params": [
+    ["@{digest_1}", "@{digest_4}"],
+    { "showInput": true, "showEffects": true, "showBalanceChanges": true }
+  ]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_multiGetTransactionBlocks",
+  "params": [[]]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_multiGetTransactionBlocks",
+  "params": [["@{digest_1}", "11111111111111111111111111111111"]]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_multiGetTransactionBlocks",
+  "params": [
+    [
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}",
+      "@{digest_1}"
+    ]
+  ]
+}
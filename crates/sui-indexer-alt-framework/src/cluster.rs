@@ -16,8 +16,9 @@ use tracing::info;
 use url::Url;
 
 use crate::{
-    db::DbArgs,
+    db::{Db, DbArgs},
     ingestion::{ClientArgs, IngestionConfig},
+    store::Store,
     Indexer, IndexerArgs, IndexerMetrics, Result,
 };
 
@@ -41,16 +42,17 @@ pub struct Args {
 
 /// An [IndexerCluster] combines an [Indexer] with a [MetricsService] and a tracing subscriber
 /// (outputting to stderr) to provide observability. It is a useful starting point for an indexer
-/// binary.
-pub struct IndexerCluster {
-    indexer: Indexer,
+/// binary. Like [Indexer], it writes to Postgres by default, but can be constructed over any
+/// [Store] using [IndexerCluster::new_with_store].
+pub struct IndexerCluster<S: Store = Db> {
+    indexer: Indexer<S>,
     metrics: MetricsService,
 
     /// Cancelling this token signals cancellation to both the indexer and metrics service.
     cancel: CancellationToken,
 }
 
-impl IndexerCluster {
+impl IndexerCluster<Db> {
     /// Create a new cluster with most of the configuration set to its default value. Use
     /// [Self::new_with_configs] to construct a cluster with full customization.
     pub async fn new(
@@ -86,22 +88,51 @@ impl IndexerCluster {
         migrations: Option<&'static EmbeddedMigrations>,
         metric_label: Option<String>,
     ) -> Result<Self> {
-        tracing_subscriber::fmt::init();
+        let (metrics, cancel) = init(args.metrics_args, metric_label)?;
 
-        let cancel = CancellationToken::new();
+        let indexer = Indexer::new_from_pg(
+            database_url,
+            db_args,
+            args.indexer_args,
+            args.client_args,
+            ingestion_config,
+            migrations,
+            metrics.registry(),
+            cancel.child_token(),
+        )
+        .await?;
 
-        let registry = Registry::new_custom(metric_label, None)
-            .context("Failed to create Prometheus registry.")?;
+        Ok(Self {
+            indexer,
+            metrics,
+            cancel,
+        })
+    }
+}
 
-        let metrics = MetricsService::new(args.metrics_args, registry, cancel.child_token());
+impl<S: Store> IndexerCluster<S> {
+    /// Create a new cluster whose indexer writes to `store`.
+    ///
+    /// - `args` configures where checkpoints are come from, what is indexed and metrics.
+    /// - `ingestion_config` controls how the ingestion service is set-up (its concurrency, polling
+    ///    intervals, etc).
+    /// - `metric_label` is an optional custom label to add to metrics reported by this service.
+    ///
+    /// The store is expected to be ready to accept writes (e.g. its schema should be up-to-date)
+    /// by the time it is passed in.
+    pub async fn new_with_store(
+        store: S,
+        args: Args,
+        ingestion_config: IngestionConfig,
+        metric_label: Option<String>,
+    ) -> Result<Self> {
+        let (metrics, cancel) = init(args.metrics_args, metric_label)?;
 
         let indexer = Indexer::new(
-            database_url,
-            db_args,
+            store,
             args.indexer_args,
             args.client_args,
             ingestion_config,
-            migrations,
             metrics.registry(),
             cancel.child_token(),
         )
@@ -155,20 +186,38 @@ impl IndexerCluster {
     }
 }
 
-impl Deref for IndexerCluster {
-    type Target = Indexer;
+impl<S: Store> Deref for IndexerCluster<S> {
+    type Target = Indexer<S>;
 
     fn deref(&self) -> &Self::Target {
         &self.indexer
     }
 }
 
-impl DerefMut for IndexerCluster {
+impl<S: Store> DerefMut for IndexerCluster<S> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.indexer
     }
 }
 
+/// Set up the tracing subscriber and metrics service shared by all clusters, returning the metrics
+/// service, and the cancellation token that controls the cluster.
+fn init(
+    metrics_args: MetricsArgs,
+    metric_label: Option<String>,
+) -> Result<(MetricsService, CancellationToken)> {
+    tracing_subscriber::fmt::init();
+
+    let cancel = CancellationToken::new();
+
+    let registry = Registry::new_custom(metric_label, None)
+        .context("Failed to create Prometheus registry.")?;
+
+    let metrics = MetricsService::new(metrics_args, registry, cancel.child_token());
+
+    Ok((metrics, cancel))
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -15,11 +15,12 @@ use metrics::IndexerMetrics;
 use pipeline::{
     concurrent::{self, ConcurrentConfig},
     sequential::{self, Handler, SequentialConfig},
-    Processor,
+    PackageResolver,
 };
 use prometheus::Registry;
-use store::{CommitterWatermark, Connection};
+use store::{CommitterWatermark, Connection, Store, TransactionalStore};
 use sui_indexer_alt_metrics::db::DbConnectionStatsCollector;
+use sui_package_resolver::{PackageStore, Resolver};
 use sui_pg_db::{temp::TempDb, Db, DbArgs};
 use tempfile::tempdir;
 use tokio::task::JoinHandle;
@@ -40,7 +41,11 @@ pub mod metrics;
 pub mod models;
 pub mod pg_store;
 pub mod pipeline;
+#[cfg(feature = "rocksdb")]
+pub mod rocks_store;
 pub mod schema;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_store;
 pub mod store;
 pub mod task;
 
@@ -70,11 +75,11 @@ pub struct IndexerArgs {
     pub skip_watermark: bool,
 }
 
-// TODO (wlmyng): Parameterize over S: Store
-pub struct Indexer {
-    // TODO (wlmyng): rename to `Store`
-    /// Connection pool to the database.
-    db: Db,
+/// The indexer framework, writing to a store of type `S`. By default this is a Postgres database
+/// ([Db]), but any implementation of [Store] can be used (see [Indexer::new]).
+pub struct Indexer<S: Store = Db> {
+    /// The store that pipelines write to, and watermarks are tracked in.
+    store: S,
 
     /// Prometheus Metrics.
     metrics: Arc<IndexerMetrics>,
@@ -82,6 +87,10 @@ pub struct Indexer {
     /// Service for downloading and disseminating checkpoint data.
     ingestion_service: IngestionService,
 
+    /// Optional resolver for Move types, shared with the processors of all pipelines added after
+    /// it was configured.
+    package_resolver: Option<Arc<PackageResolver>>,
+
     /// Optional override of the checkpoint lowerbound.
     first_checkpoint: Option<u64>,
 
@@ -112,11 +121,10 @@ pub struct Indexer {
     handles: Vec<JoinHandle<()>>,
 }
 
-// TODO (wlmyng): non-pg store impl<S: TransactionalStore> Indexer<S> {
-impl Indexer {
-    /// Create a new instance of the indexer framework. `database_url`, `db_args`, `indexer_args,`,
-    /// `client_args`, and `ingestion_config` contain configurations for the following,
-    /// respectively:
+impl Indexer<Db> {
+    /// Create a new instance of the indexer framework, writing to a Postgres database.
+    /// `database_url`, `db_args`, `indexer_args,`, `client_args`, and `ingestion_config` contain
+    /// configurations for the following, respectively:
     ///
     /// - Connecting to the database,
     /// - What is indexed (which checkpoints, which pipelines, whether to update the watermarks
@@ -130,7 +138,7 @@ impl Indexer {
     ///
     /// After initialization, at least one pipeline must be added using [Self::concurrent_pipeline]
     /// or [Self::sequential_pipeline], before the indexer is started using [Self::run].
-    pub async fn new(
+    pub async fn new_from_pg(
         database_url: Url,
         db_args: DbArgs,
         indexer_args: IndexerArgs,
@@ -140,15 +148,7 @@ impl Indexer {
         registry: &Registry,
         cancel: CancellationToken,
     ) -> Result<Self> {
-        let IndexerArgs {
-            first_checkpoint,
-            last_checkpoint,
-            pipeline,
-            skip_watermark,
-        } = indexer_args;
-
-        // TODO (wlmyng): This will become an arg into `new`
-        let db = Db::for_write(database_url, db_args) // I guess our store needs a constructor fn
+        let db = Db::for_write(database_url, db_args)
             .await
             .context("Failed to connect to database")?;
 
@@ -157,44 +157,25 @@ impl Indexer {
             .await
             .context("Failed to run pending migrations")?;
 
-        let metrics = IndexerMetrics::new(registry);
-
-        // TODO (wlmyng): Users will be responsible for configuring their registry with db metrics,
-        // if desired
         registry.register(Box::new(DbConnectionStatsCollector::new(
             Some("indexer_db"),
             db.clone(),
         )))?;
 
-        let ingestion_service = IngestionService::new(
+        Indexer::new(
+            db,
+            indexer_args,
             client_args,
             ingestion_config,
-            metrics.clone(),
-            cancel.clone(),
-        )?;
-
-        Ok(Self {
-            db,
-            metrics,
-            ingestion_service,
-            first_checkpoint,
-            last_checkpoint,
-            skip_watermark,
-            enabled_pipelines: if pipeline.is_empty() {
-                None
-            } else {
-                Some(pipeline.into_iter().collect())
-            },
-            added_pipelines: BTreeSet::new(),
+            registry,
             cancel,
-            first_checkpoint_from_watermark: u64::MAX,
-            handles: vec![],
-        })
+        )
+        .await
     }
 
     pub async fn new_for_testing(migrations: &'static EmbeddedMigrations) -> (Self, TempDb) {
         let temp_db = TempDb::new().unwrap();
-        let indexer = Indexer::new(
+        let indexer = Indexer::new_from_pg(
             temp_db.database().url().clone(),
             DbArgs::default(),
             IndexerArgs::default(),
@@ -217,7 +198,93 @@ impl Indexer {
 
     /// The database connection pool used by the indexer.
     pub fn db(&self) -> &Db {
-        &self.db
+        &self.store
+    }
+
+    /// Combine the provided `migrations` with the migrations necessary to set up the indexer
+    /// framework. The returned migration source can be passed to [Db::run_migrations] to ensure
+    /// the database's schema is up-to-date for both the indexer framework and the specific
+    /// indexer.
+    pub fn migrations(
+        migrations: Option<&'static EmbeddedMigrations>,
+    ) -> impl MigrationSource<Pg> + Send + Sync + 'static {
+        struct Migrations(Option<&'static EmbeddedMigrations>);
+        impl MigrationSource<Pg> for Migrations {
+            fn migrations(&self) -> migration::Result<Vec<Box<dyn Migration<Pg>>>> {
+                let mut migrations = MIGRATIONS.migrations()?;
+                if let Some(more_migrations) = self.0 {
+                    migrations.extend(more_migrations.migrations()?);
+                }
+                Ok(migrations)
+            }
+        }
+
+        Migrations(migrations)
+    }
+}
+
+impl<S: Store> Indexer<S> {
+    /// Create a new instance of the indexer framework, writing to `store`. `indexer_args`,
+    /// `client_args`, and `ingestion_config` contain configurations for the following,
+    /// respectively:
+    ///
+    /// - What is indexed (which checkpoints, which pipelines, whether to update the watermarks
+    ///   table) and where to serve metrics from,
+    /// - Where to download checkpoints from,
+    /// - Concurrency and buffering parameters for downloading checkpoints.
+    ///
+    /// The store is expected to be ready to accept writes (e.g. its schema should be up-to-date)
+    /// by the time it is passed in.
+    ///
+    /// After initialization, at least one pipeline must be added using [Self::concurrent_pipeline]
+    /// or [Self::sequential_pipeline], before the indexer is started using [Self::run].
+    pub async fn new(
+        store: S,
+        indexer_args: IndexerArgs,
+        client_args: ClientArgs,
+        ingestion_config: IngestionConfig,
+        registry: &Registry,
+        cancel: CancellationToken,
+    ) -> Result<Self> {
+        let IndexerArgs {
+            first_checkpoint,
+            last_checkpoint,
+            pipeline,
+            skip_watermark,
+        } = indexer_args;
+
+        let metrics = IndexerMetrics::new(registry);
+
+        let ingestion_service = IngestionService::new(
+            client_args,
+            ingestion_config,
+            metrics.clone(),
+            cancel.clone(),
+        )?;
+
+        Ok(Self {
+            store,
+            metrics,
+            ingestion_service,
+            package_resolver: None,
+            first_checkpoint,
+            last_checkpoint,
+            skip_watermark,
+            enabled_pipelines: if pipeline.is_empty() {
+                None
+            } else {
+                Some(pipeline.into_iter().collect())
+            },
+            added_pipelines: BTreeSet::new(),
+            cancel,
+            first_checkpoint_from_watermark: u64::MAX,
+            handles: vec![],
+        })
+    }
+
+    /// The store used by the indexer.
+    pub fn store(&self) -> &S {
+        &self.store
     }
 
     /// The ingestion client used by the indexer to fetch checkpoints.
@@ -230,6 +297,14 @@ impl Indexer {
         &self.metrics
     }
 
+    /// Configure the package store that processors use to resolve Move types and layouts (through
+    /// [pipeline::ProcessorContext::package_resolver]). Only pipelines added after this call will
+    /// have access to the resolver.
+    pub fn set_package_store(&mut self, package_store: impl PackageStore) {
+        let package_store: Arc<dyn PackageStore> = Arc::new(package_store);
+        self.package_resolver = Some(Arc::new(Resolver::new(package_store)));
+    }
+
     /// The pipelines that this indexer will run.
     pub fn pipelines(&self) -> impl Iterator<Item = &'static str> + '_ {
         self.added_pipelines.iter().copied().filter(|p| {
@@ -251,8 +326,7 @@ impl Indexer {
         config: ConcurrentConfig,
     ) -> Result<()>
     where
-        // TODO (wlmyng): eventually this will be Handler<Store = S>
-        H: concurrent::Handler<Store = Db> + Send + Sync + 'static,
+        H: concurrent::Handler<Store = S> + Send + Sync + 'static,
     {
         let start_from_pruner_watermark = H::PRUNING_REQUIRES_PROCESSED_VALUES;
         let Some(watermark) = self.add_pipeline::<H>(start_from_pruner_watermark).await? else {
@@ -271,7 +345,8 @@ impl Indexer {
             watermark,
             config,
             self.skip_watermark,
-            self.db.clone(),
+            self.store.clone(),
+            self.package_resolver.clone(),
             self.ingestion_service.subscribe().0,
             self.metrics.clone(),
             self.cancel.clone(),
@@ -296,9 +371,8 @@ impl Indexer {
         config: SequentialConfig,
     ) -> Result<()>
     where
-        // TODO (wlmyng): eventually this will be Handler<Store = S>
-        // And additionally, S: TransactionalStore
-        H: Handler<Store = Db> + Send + Sync + 'static,
+        H: Handler<Store = S> + Send + Sync + 'static,
+        S: TransactionalStore,
     {
         let Some(watermark) = self.add_pipeline::<H>(false).await? else {
             return Ok(());
@@ -321,7 +395,8 @@ impl Indexer {
             handler,
             watermark,
             config,
-            self.db.clone(),
+            self.store.clone(),
+            self.package_resolver.clone(),
             checkpoint_rx,
             watermark_tx,
             self.metrics.clone(),
@@ -335,7 +410,7 @@ impl Indexer {
     /// If the watermark does not exist, the override can be anything. If the watermark exists, the
     /// override must not leave any gap in the data: it can be in the past, or at the tip of the
     /// network, but not in the future.
-    fn check_first_checkpoint_consistency<P: Processor>(
+    fn check_first_checkpoint_consistency<P: pipeline::AsyncProcessor<S>>(
         &self,
         watermark: &Option<CommitterWatermark>,
     ) -> Result<()> {
@@ -395,27 +470,6 @@ impl Indexer {
         }))
     }
 
-    /// Combine the provided `migrations` with the migrations necessary to set up the indexer
-    /// framework. The returned migration source can be passed to [Db::run_migrations] to ensure
-    /// the database's schema is up-to-date for both the indexer framework and the specific
-    /// indexer.
-    pub fn migrations(
-        migrations: Option<&'static EmbeddedMigrations>,
-    ) -> impl MigrationSource<Pg> + Send + Sync + 'static {
-        struct Migrations(Option<&'static EmbeddedMigrations>);
-        impl MigrationSource<Pg> for Migrations {
-            fn migrations(&self) -> migration::Result<Vec<Box<dyn Migration<Pg>>>> {
-                let mut migrations = MIGRATIONS.migrations()?;
-                if let Some(more_migrations) = self.0 {
-                    migrations.extend(more_migrations.migrations()?);
-                }
-                Ok(migrations)
-            }
-        }
-
-        Migrations(migrations)
-    }
-
     /// Update the indexer's first checkpoint based on the watermark for the pipeline by adding for
     /// handler `H` (as long as it's enabled). Returns `Ok(None)` if the pipeline is disabled,
     /// `Ok(Some(None))` if the pipeline is enabled but its watermark is not found, and
@@ -424,7 +478,7 @@ impl Indexer {
     /// If `start_from_pruner_watermark` is true, the indexer will start ingestion from just after
     /// the pruner watermark, so that the pruner have access to the processed values for any
     /// unpruned checkpoints.
-    async fn add_pipeline<P: Processor + 'static>(
+    async fn add_pipeline<P: pipeline::AsyncProcessor<S> + 'static>(
         &mut self,
         start_from_pruner_watermark: bool,
     ) -> Result<Option<Option<CommitterWatermark>>> {
@@ -441,7 +495,11 @@ impl Indexer {
             }
         }
 
-        let mut conn = self.db.connect().await.context("Failed DB connection")?;
+        let mut conn = self
+            .store
+            .connect()
+            .await
+            .context("Failed to connect to store")?;
 
         let watermark = conn
             .committer_watermark(P::NAME)
@@ -475,7 +533,7 @@ impl Indexer {
 mod tests {
     use async_trait::async_trait;
 
-    use crate::types::full_checkpoint_content::CheckpointData;
+    use crate::{pipeline::Processor, types::full_checkpoint_content::CheckpointData};
     use sui_pg_db::Connection as PgConnection;
 
     use super::*;
@@ -11,7 +11,7 @@ use prometheus::{
 };
 use tracing::warn;
 
-use crate::{ingestion::error::Error, pipeline::Processor};
+use crate::{ingestion::error::Error, pipeline::AsyncProcessor, store::Store};
 
 /// Histogram buckets for the distribution of checkpoint fetching latencies.
 const INGESTION_LATENCY_SEC_BUCKETS: &[f64] = &[
@@ -620,7 +620,7 @@ impl CheckpointLagMetricReporter {
         })
     }
 
-    pub fn new_for_pipeline<P: Processor>(
+    pub fn new_for_pipeline<P: AsyncProcessor<S>, S: Store>(
         checkpoint_time_lag_histogram: &HistogramVec,
         latest_checkpoint_time_lag_gauge: &IntGaugeVec,
         latest_checkpoint_sequence_number_gauge: &IntGaugeVec,
@@ -52,8 +52,8 @@ impl<H: Handler> PendingCheckpoint<H> {
     }
 }
 
-impl<H: Handler> From<IndexedCheckpoint<H>> for PendingCheckpoint<H> {
-    fn from(indexed: IndexedCheckpoint<H>) -> Self {
+impl<H: Handler> From<IndexedCheckpoint<H::Value>> for PendingCheckpoint<H> {
+    fn from(indexed: IndexedCheckpoint<H::Value>) -> Self {
         Self {
             watermark: WatermarkPart {
                 watermark: indexed.watermark,
@@ -81,7 +81,7 @@ impl<H: Handler> From<IndexedCheckpoint<H>> for PendingCheckpoint<H> {
 /// closed.
 pub(super) fn collector<H: Handler + 'static>(
     config: CommitterConfig,
-    mut rx: mpsc::Receiver<IndexedCheckpoint<H>>,
+    mut rx: mpsc::Receiver<IndexedCheckpoint<H::Value>>,
     tx: mpsc::Sender<BatchedRows<H>>,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
@@ -92,7 +92,7 @@ pub(super) fn collector<H: Handler + 'static>(
         let mut poll = interval(config.collect_interval());
         poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
-        let checkpoint_lag_reporter = CheckpointLagMetricReporter::new_for_pipeline::<H>(
+        let checkpoint_lag_reporter = CheckpointLagMetricReporter::new_for_pipeline::<H, H::Store>(
             &metrics.collected_checkpoint_timestamp_lag,
             &metrics.latest_collected_checkpoint_timestamp_lag_ms,
             &metrics.latest_collected_checkpoint,
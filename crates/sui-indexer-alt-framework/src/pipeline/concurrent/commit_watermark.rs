@@ -78,7 +78,7 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
         // demonstrate that the pipeline is making progress.
         let mut logger = WatermarkLogger::new("concurrent_committer", &watermark);
 
-        let checkpoint_lag_reporter = CheckpointLagMetricReporter::new_for_pipeline::<H>(
+        let checkpoint_lag_reporter = CheckpointLagMetricReporter::new_for_pipeline::<H, H::Store>(
             &metrics.watermarked_checkpoint_timestamp_lag,
             &metrics.latest_watermarked_checkpoint_timestamp_lag_ms,
             &metrics.watermark_checkpoint_in_db,
@@ -208,7 +208,7 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
                             Ok(true) => {
                                 let elapsed = guard.stop_and_record();
 
-                                logger.log::<H>(&watermark, elapsed);
+                                logger.log::<H, H::Store>(&watermark, elapsed);
 
                                 checkpoint_lag_reporter.report_lag(
                                     watermark.checkpoint_hi_inclusive,
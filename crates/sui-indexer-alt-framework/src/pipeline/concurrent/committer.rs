@@ -45,7 +45,7 @@ pub(super) fn committer<H: Handler + 'static>(
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
         info!(pipeline = H::NAME, "Starting committer");
-        let checkpoint_lag_reporter = CheckpointLagMetricReporter::new_for_pipeline::<H>(
+        let checkpoint_lag_reporter = CheckpointLagMetricReporter::new_for_pipeline::<H, H::Store>(
             &metrics.partially_committed_checkpoint_timestamp_lag,
             &metrics.latest_partially_committed_checkpoint_timestamp_lag_ms,
             &metrics.latest_partially_committed_checkpoint,
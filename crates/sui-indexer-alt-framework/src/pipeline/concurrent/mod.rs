@@ -15,7 +15,10 @@ use crate::{
     FieldCount,
 };
 
-use super::{processor::processor, CommitterConfig, Processor, WatermarkPart, PIPELINE_BUFFER};
+use super::{
+    processor::processor, AsyncProcessor, CommitterConfig, PackageResolver, ProcessorContext,
+    WatermarkPart, PIPELINE_BUFFER,
+};
 
 use self::{
     collector::collector, commit_watermark::commit_watermark, committer::committer, pruner::pruner,
@@ -34,7 +37,8 @@ mod reader_watermark;
 const MAX_WATERMARK_UPDATES: usize = 10_000;
 
 /// Handlers implement the logic for a given indexing pipeline: How to process checkpoint data (by
-/// implementing [Processor]) into rows for their table, and how to write those rows to the database.
+/// implementing [Processor](super::Processor) or [AsyncProcessor]) into rows for their table, and
+/// how to write those rows to the database.
 ///
 /// The handler is also responsible for tuning the various parameters of the pipeline (provided as
 /// associated values). Reasonable defaults have been chosen to balance concurrency with memory
@@ -53,7 +57,7 @@ const MAX_WATERMARK_UPDATES: usize = 10_000;
 /// build up, the collector will stop accepting new checkpoints, which will eventually propagate
 /// back to the ingestion service.
 #[async_trait::async_trait]
-pub trait Handler: Processor<Value: FieldCount> {
+pub trait Handler: AsyncProcessor<<Self as Handler>::Store, Value: FieldCount> {
     type Store: Store;
 
     /// If at least this many rows are pending, the committer will commit them eagerly.
@@ -202,6 +206,7 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
     config: ConcurrentConfig,
     skip_watermark: bool,
     db: H::Store,
+    package_resolver: Option<Arc<PackageResolver>>,
     checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
@@ -230,6 +235,7 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
 
     let processor = processor(
         handler.clone(),
+        ProcessorContext::new(db.clone(), package_resolver, metrics.clone()),
         checkpoint_rx,
         processor_tx,
         metrics.clone(),
@@ -288,3 +294,232 @@ const fn max_chunk_rows<H: Handler>() -> usize {
         i16::MAX as usize / H::Value::FIELD_COUNT
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use sui_types::test_checkpoint_data_builder::TestCheckpointDataBuilder;
+
+    use crate::{
+        metrics::tests::test_metrics,
+        store::{Connection, PrunerWatermark, ReaderWatermark},
+    };
+
+    use super::*;
+
+    /// An in-memory store that records the values committed to it, and the pipeline's committer
+    /// watermark.
+    #[derive(Clone, Default)]
+    struct MockStore(Arc<Mutex<MockState>>);
+
+    #[derive(Default)]
+    struct MockState {
+        values: Vec<Entry>,
+        watermark: Option<CommitterWatermark>,
+    }
+
+    struct MockConnection(Arc<Mutex<MockState>>);
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Entry {
+        checkpoint: u64,
+        transactions: usize,
+        /// The committer watermark that was visible to the processor through its context.
+        seen_watermark: Option<u64>,
+    }
+
+    impl FieldCount for Entry {
+        const FIELD_COUNT: usize = 3;
+    }
+
+    #[async_trait]
+    impl Connection for MockConnection {
+        async fn committer_watermark(
+            &mut self,
+            _pipeline: &'static str,
+        ) -> anyhow::Result<Option<CommitterWatermark>> {
+            Ok(self.0.lock().unwrap().watermark)
+        }
+
+        async fn reader_watermark(
+            &mut self,
+            _pipeline: &'static str,
+        ) -> anyhow::Result<Option<ReaderWatermark>> {
+            Ok(None)
+        }
+
+        async fn set_committer_watermark(
+            &mut self,
+            _pipeline: &'static str,
+            watermark: CommitterWatermark,
+        ) -> anyhow::Result<bool> {
+            let mut state = self.0.lock().unwrap();
+            if state
+                .watermark
+                .is_some_and(|w| w.checkpoint_hi_inclusive >= watermark.checkpoint_hi_inclusive)
+            {
+                return Ok(false);
+            }
+
+            state.watermark = Some(watermark);
+            Ok(true)
+        }
+
+        async fn set_reader_watermark(
+            &mut self,
+            _pipeline: &'static str,
+            _reader_lo: u64,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn pruner_watermark(
+            &mut self,
+            _pipeline: &'static str,
+            _delay: Duration,
+        ) -> anyhow::Result<Option<PrunerWatermark>> {
+            Ok(None)
+        }
+
+        async fn set_pruner_watermark(
+            &mut self,
+            _pipeline: &'static str,
+            _pruner_hi: u64,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+    }
+
+    #[async_trait]
+    impl Store for MockStore {
+        type Connection<'c> = MockConnection;
+
+        async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>> {
+            Ok(MockConnection(self.0.clone()))
+        }
+    }
+
+    /// A handler that only implements asynchronous processing, reading from the store through its
+    /// context.
+    struct AsyncHandler;
+
+    #[async_trait]
+    impl AsyncProcessor<MockStore> for AsyncHandler {
+        const NAME: &'static str = "async_handler";
+        type Value = Entry;
+
+        async fn process(
+            &self,
+            checkpoint: &Arc<CheckpointData>,
+            ctx: &ProcessorContext<MockStore>,
+        ) -> anyhow::Result<Vec<Entry>> {
+            // No package store was configured for this pipeline.
+            assert!(ctx.package_resolver().is_err());
+
+            // The processor counts the checkpoint as received before processing it.
+            assert!(
+                ctx.metrics()
+                    .total_handler_checkpoints_received
+                    .with_label_values(&[Self::NAME])
+                    .get()
+                    > 0
+            );
+
+            let mut conn = ctx.connect().await?;
+            let seen_watermark = conn
+                .committer_watermark(Self::NAME)
+                .await?
+                .map(|w| w.checkpoint_hi_inclusive);
+
+            Ok(vec![Entry {
+                checkpoint: checkpoint.checkpoint_summary.sequence_number,
+                transactions: checkpoint.transactions.len(),
+                seen_watermark,
+            }])
+        }
+    }
+
+    #[async_trait]
+    impl Handler for AsyncHandler {
+        type Store = MockStore;
+
+        async fn commit<'a>(values: &[Entry], conn: &mut MockConnection) -> anyhow::Result<usize> {
+            conn.0.lock().unwrap().values.extend(values.iter().cloned());
+            Ok(values.len())
+        }
+    }
+
+    fn test_config() -> ConcurrentConfig {
+        ConcurrentConfig {
+            committer: CommitterConfig {
+                collect_interval_ms: 10,
+                watermark_interval_ms: 10,
+                ..Default::default()
+            },
+            pruner: None,
+        }
+    }
+
+    fn test_checkpoint(cp: u64) -> Arc<CheckpointData> {
+        Arc::new(
+            TestCheckpointDataBuilder::new(cp)
+                .start_transaction(0)
+                .finish_transaction()
+                .build_checkpoint(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_process_async() {
+        let watermark = CommitterWatermark {
+            checkpoint_hi_inclusive: 9,
+            ..Default::default()
+        };
+
+        let store = MockStore::default();
+        store.0.lock().unwrap().watermark = Some(watermark);
+
+        let (checkpoint_tx, checkpoint_rx) = mpsc::channel(10);
+        let cancel = CancellationToken::new();
+        let handle = pipeline(
+            AsyncHandler,
+            Some(watermark),
+            test_config(),
+            /* skip_watermark */ false,
+            store.clone(),
+            None,
+            checkpoint_rx,
+            test_metrics(),
+            cancel.clone(),
+        );
+
+        for cp in 10..15 {
+            checkpoint_tx.send(test_checkpoint(cp)).await.unwrap();
+        }
+
+        // Closing the input channel winds the pipeline down once everything has been committed.
+        drop(checkpoint_tx);
+        tokio::time::timeout(Duration::from_secs(10), handle)
+            .await
+            .expect("Timed out waiting for pipeline to finish")
+            .unwrap();
+        assert!(!cancel.is_cancelled());
+
+        let state = store.0.lock().unwrap();
+        let mut values = state.values.clone();
+        values.sort_by_key(|e| e.checkpoint);
+        assert_eq!(
+            values.iter().map(|e| e.checkpoint).collect::<Vec<_>>(),
+            (10..15).collect::<Vec<_>>(),
+        );
+
+        for entry in &values {
+            assert_eq!(entry.transactions, 1);
+            assert!(entry.seen_watermark.is_some_and(|w| w >= 9));
+        }
+
+        assert_eq!(state.watermark.unwrap().checkpoint_hi_inclusive, 14);
+    }
+}
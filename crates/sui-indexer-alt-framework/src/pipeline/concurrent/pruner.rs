@@ -276,7 +276,7 @@ pub(super) fn pruner<H: Handler + Send + Sync + 'static>(
                         Ok(true) => {
                             highest_watermarked = highest_pruned;
                             let elapsed = guard.stop_and_record();
-                            logger.log::<H>(
+                            logger.log::<H, H::Store>(
                                 LoggerWatermark::checkpoint(highest_watermarked),
                                 elapsed,
                             );
@@ -3,7 +3,7 @@
 
 use std::time::Duration;
 
-pub use processor::Processor;
+pub use processor::{AsyncProcessor, PackageResolver, Processor, ProcessorContext};
 use serde::{Deserialize, Serialize};
 
 use crate::store::CommitterWatermark;
@@ -40,9 +40,9 @@ pub struct CommitterConfig {
 
 /// Processed values associated with a single checkpoint. This is an internal type used to
 /// communicate between the processor and the collector parts of the pipeline.
-struct IndexedCheckpoint<P: Processor> {
+struct IndexedCheckpoint<V> {
     /// Values to be inserted into the database from this checkpoint
-    values: Vec<P::Value>,
+    values: Vec<V>,
     /// The watermark associated with this checkpoint
     watermark: CommitterWatermark,
 }
@@ -79,13 +79,13 @@ impl CommitterConfig {
     }
 }
 
-impl<P: Processor> IndexedCheckpoint<P> {
+impl<V> IndexedCheckpoint<V> {
     fn new(
         epoch: u64,
         cp_sequence_number: u64,
         tx_hi: u64,
         timestamp_ms: u64,
-        values: Vec<P::Value>,
+        values: Vec<V>,
     ) -> Self {
         Self {
             watermark: CommitterWatermark {
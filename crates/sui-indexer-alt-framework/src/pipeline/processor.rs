@@ -3,6 +3,9 @@
 
 use std::sync::Arc;
 
+use anyhow::Context;
+use async_trait::async_trait;
+use sui_package_resolver::{PackageStore, Resolver};
 use sui_types::full_checkpoint_content::CheckpointData;
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_stream::wrappers::ReceiverStream;
@@ -12,6 +15,7 @@ use tracing::{debug, error, info};
 use crate::{
     metrics::{CheckpointLagMetricReporter, IndexerMetrics},
     pipeline::Break,
+    store::Store,
     task::TrySpawnStreamExt,
 };
 
@@ -34,25 +38,127 @@ pub trait Processor {
     fn process(&self, checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Self::Value>>;
 }
 
+/// Asynchronous variant of [Processor], for processors that need to perform I/O while processing a
+/// checkpoint (e.g. to resolve type layouts, or read from the store), with access to the
+/// pipeline's [ProcessorContext]. Pipelines process checkpoints through this trait, and every
+/// [Processor] implements it, so a handler implements exactly one of the two.
+///
+/// Up to `FANOUT` checkpoints are processed concurrently, as with synchronous processing.
+#[async_trait]
+pub trait AsyncProcessor<S: Store> {
+    /// Used to identify the pipeline in logs and metrics.
+    const NAME: &'static str;
+
+    /// How much concurrency to use when processing checkpoint data.
+    const FANOUT: usize = 10;
+
+    /// The type of value being inserted by the handler.
+    type Value: Send + Sync + 'static;
+
+    /// The processing logic for turning a checkpoint into rows of the table.
+    async fn process(
+        &self,
+        checkpoint: &Arc<CheckpointData>,
+        ctx: &ProcessorContext<S>,
+    ) -> anyhow::Result<Vec<Self::Value>>;
+}
+
+/// Resolves Move types and layouts, using the package store configured on the indexer.
+pub type PackageResolver = Resolver<Arc<dyn PackageStore>>;
+
+/// Handles available to processors that perform I/O while processing a checkpoint. The context is
+/// shared by all the workers of a pipeline's processor, and is cheap to clone.
+pub struct ProcessorContext<S: Store> {
+    store: S,
+    package_resolver: Option<Arc<PackageResolver>>,
+    metrics: Arc<IndexerMetrics>,
+}
+
+impl<S: Store> ProcessorContext<S> {
+    pub(crate) fn new(
+        store: S,
+        package_resolver: Option<Arc<PackageResolver>>,
+        metrics: Arc<IndexerMetrics>,
+    ) -> Self {
+        Self {
+            store,
+            package_resolver,
+            metrics,
+        }
+    }
+
+    /// The store that the pipeline writes to. Processors can use this to read data written by
+    /// other pipelines, but should bear in mind that it may not be consistent with the checkpoint
+    /// being processed.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Get a connection to the store, for reads.
+    pub async fn connect(&self) -> anyhow::Result<S::Connection<'_>> {
+        self.store.connect().await
+    }
+
+    /// Resolver for Move types and layouts. Fails if the indexer was not configured with a
+    /// package store (see [crate::Indexer::set_package_store]).
+    pub fn package_resolver(&self) -> anyhow::Result<&PackageResolver> {
+        self.package_resolver
+            .as_deref()
+            .context("No package store configured for this indexer")
+    }
+
+    /// The indexer's metrics.
+    pub fn metrics(&self) -> &Arc<IndexerMetrics> {
+        &self.metrics
+    }
+}
+
+#[async_trait]
+impl<P: Processor + Send + Sync, S: Store> AsyncProcessor<S> for P {
+    const NAME: &'static str = P::NAME;
+    const FANOUT: usize = P::FANOUT;
+    type Value = P::Value;
+
+    async fn process(
+        &self,
+        checkpoint: &Arc<CheckpointData>,
+        _ctx: &ProcessorContext<S>,
+    ) -> anyhow::Result<Vec<Self::Value>> {
+        Processor::process(self, checkpoint)
+    }
+}
+
+impl<S: Store> Clone for ProcessorContext<S> {
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            package_resolver: self.package_resolver.clone(),
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
 /// The processor task is responsible for taking checkpoint data and breaking it down into rows
 /// ready to commit. It spins up a supervisor that waits on the `rx` channel for checkpoints, and
 /// distributes them among `H::FANOUT` workers.
 ///
-/// Each worker processes a checkpoint into rows and sends them on to the committer using the `tx`
-/// channel.
+/// Each worker processes a checkpoint into rows (passing `ctx` to the processor) and sends them on
+/// to the committer using the `tx` channel. Workers may finish out of order, and it is the
+/// responsibility of downstream tasks to restore ordering where necessary.
 ///
 /// The task will shutdown if the `cancel` token is cancelled, or if any of the workers encounters
 /// an error -- there is no retry logic at this level.
-pub(super) fn processor<P: Processor + Send + Sync + 'static>(
+pub(super) fn processor<P: AsyncProcessor<S> + Send + Sync + 'static, S: Store>(
     processor: Arc<P>,
+    ctx: ProcessorContext<S>,
     rx: mpsc::Receiver<Arc<CheckpointData>>,
-    tx: mpsc::Sender<IndexedCheckpoint<P>>,
+    tx: mpsc::Sender<IndexedCheckpoint<P::Value>>,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
         info!(pipeline = P::NAME, "Starting processor");
-        let checkpoint_lag_reporter = CheckpointLagMetricReporter::new_for_pipeline::<P>(
+        let checkpoint_lag_reporter = CheckpointLagMetricReporter::new_for_pipeline::<P, S>(
             &metrics.processed_checkpoint_timestamp_lag,
             &metrics.latest_processed_checkpoint_timestamp_lag_ms,
             &metrics.latest_processed_checkpoint,
@@ -65,6 +171,7 @@ pub(super) fn processor<P: Processor + Send + Sync + 'static>(
                 let cancel = cancel.clone();
                 let checkpoint_lag_reporter = checkpoint_lag_reporter.clone();
                 let processor = processor.clone();
+                let ctx = ctx.clone();
 
                 async move {
                     if cancel.is_cancelled() {
@@ -81,7 +188,7 @@ pub(super) fn processor<P: Processor + Send + Sync + 'static>(
                         .with_label_values(&[P::NAME])
                         .start_timer();
 
-                    let values = processor.process(&checkpoint)?;
+                    let values = processor.process(&checkpoint, &ctx).await?;
                     let elapsed = guard.stop_and_record();
 
                     let epoch = checkpoint.checkpoint_summary.epoch;
@@ -41,7 +41,7 @@ use super::{Handler, SequentialConfig};
 pub(super) fn committer<H>(
     config: SequentialConfig,
     watermark: Option<CommitterWatermark>,
-    mut rx: mpsc::Receiver<IndexedCheckpoint<H>>,
+    mut rx: mpsc::Receiver<IndexedCheckpoint<H::Value>>,
     tx: mpsc::UnboundedSender<(&'static str, u64)>,
     store: H::Store,
     metrics: Arc<IndexerMetrics>,
@@ -87,7 +87,7 @@ where
 
         // Data for checkpoint that haven't been written yet. Note that `pending_rows` includes
         // rows in `batch`.
-        let mut pending: BTreeMap<u64, IndexedCheckpoint<H>> = BTreeMap::new();
+        let mut pending: BTreeMap<u64, IndexedCheckpoint<H::Value>> = BTreeMap::new();
         let mut pending_rows = 0;
 
         info!(pipeline = H::NAME, ?watermark, "Starting committer");
@@ -262,7 +262,7 @@ where
                         "Wrote batch",
                     );
 
-                    logger.log::<H>(&watermark, elapsed);
+                    logger.log::<H, H::Store>(&watermark, elapsed);
 
                     metrics
                         .total_committer_batches_succeeded
@@ -7,7 +7,10 @@ use serde::{Deserialize, Serialize};
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_util::sync::CancellationToken;
 
-use super::{processor::processor, CommitterConfig, Processor, PIPELINE_BUFFER};
+use super::{
+    processor::processor, AsyncProcessor, CommitterConfig, PackageResolver, ProcessorContext,
+    PIPELINE_BUFFER,
+};
 
 use crate::{
     metrics::IndexerMetrics,
@@ -20,8 +23,9 @@ use self::committer::committer;
 mod committer;
 
 /// Handlers implement the logic for a given indexing pipeline: How to process checkpoint data (by
-/// implementing [Processor]) into rows for their table, how to combine multiple rows into a single
-/// DB operation, and then how to write those rows atomically to the database.
+/// implementing [Processor](super::Processor) or [AsyncProcessor]) into rows for their table, how
+/// to combine multiple rows into a single DB operation, and then how to write those rows atomically
+/// to the database.
 ///
 /// The handler is also responsible for tuning the various parameters of the pipeline (provided as
 /// associated values).
@@ -37,7 +41,7 @@ mod committer;
 /// liveness and limits the amount of memory the pipeline can consume, by bounding the number of
 /// checkpoints that can be received before the next checkpoint.
 #[async_trait::async_trait]
-pub trait Handler: Processor {
+pub trait Handler: AsyncProcessor<<Self as Handler>::Store> {
     type Store: TransactionalStore;
 
     /// If at least this many rows are pending, the committer will commit them eagerly.
@@ -105,6 +109,7 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
     initial_watermark: Option<CommitterWatermark>,
     config: SequentialConfig,
     db: H::Store,
+    package_resolver: Option<Arc<PackageResolver>>,
     checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
     watermark_tx: mpsc::UnboundedSender<(&'static str, u64)>,
     metrics: Arc<IndexerMetrics>,
@@ -114,6 +119,7 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
 
     let processor = processor(
         Arc::new(handler),
+        ProcessorContext::new(db.clone(), package_resolver, metrics.clone()),
         checkpoint_rx,
         processor_tx,
         metrics.clone(),
@@ -0,0 +1,417 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    path::Path,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use scoped_futures::ScopedBoxFuture;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use typed_store::{
+    rocks::{
+        default_db_options, open_cf_opts, DBBatch, DBMap, Database, MetricConf, ReadWriteOptions,
+    },
+    Map,
+};
+
+use crate::store::{
+    CommitterWatermark, Connection, PrunerWatermark, ReaderWatermark, Store, TransactionalStore,
+};
+
+/// Column family holding the framework's watermarks, keyed by pipeline name.
+const WATERMARKS_CF: &str = "watermarks";
+
+/// A [Store] backed by an embedded RocksDB instance (via `typed-store`), for indexers that need
+/// to run without operating a Postgres instance.
+///
+/// The column families that pipelines write to are declared when the store is opened, and
+/// handlers access them as typed [DBMap]s through [RocksStore::table].
+#[derive(Clone)]
+pub struct RocksStore {
+    db: Arc<Database>,
+    watermarks: DBMap<String, StoredWatermark>,
+
+    /// RocksDB does not offer conditional writes, so watermark updates are implemented as a
+    /// read-modify-write, and this lock prevents updates from different tasks (committer, reader
+    /// watermark and pruner) from clobbering each other.
+    watermark_lock: Arc<Mutex<()>>,
+}
+
+/// A connection to a [RocksStore]. Writes are staged in a batch (see [RocksConnection::batch]) and
+/// applied atomically by [RocksConnection::commit], or when the enclosing transaction commits.
+pub struct RocksConnection<'c> {
+    store: &'c RocksStore,
+
+    /// Writes staged on this connection. Batches are `Send` but not `Sync`, which the
+    /// [Connection] trait requires. The mutex is never contended: all access goes through
+    /// `&mut self`.
+    batch: Mutex<Option<DBBatch>>,
+
+    /// Whether this connection was created by [TransactionalStore::transaction], in which case
+    /// staged writes are only applied when the transaction commits.
+    in_transaction: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct StoredWatermark {
+    epoch_hi_inclusive: u64,
+    checkpoint_hi_inclusive: u64,
+    tx_hi: u64,
+    timestamp_ms_hi_inclusive: u64,
+    reader_lo: u64,
+    /// Milliseconds since the Unix epoch, according to the indexer's clock, at which `reader_lo`
+    /// was last updated.
+    pruner_timestamp_ms: u64,
+    pruner_hi: u64,
+}
+
+impl RocksStore {
+    /// Open (creating if necessary) the RocksDB database at `path`, with the framework's
+    /// watermarks column family, and one column family per entry in `column_families` for the
+    /// indexer's pipelines to write to.
+    pub fn open(path: impl AsRef<Path>, column_families: &[&str]) -> anyhow::Result<Self> {
+        let cfs: Vec<_> = column_families
+            .iter()
+            .copied()
+            .chain(std::iter::once(WATERMARKS_CF))
+            .map(|cf| (cf, default_db_options().options))
+            .collect();
+
+        let db = open_cf_opts(path, None, MetricConf::new("indexer"), &cfs)
+            .context("Failed to open RocksDB store")?;
+
+        let watermarks = DBMap::reopen(
+            &db,
+            Some(WATERMARKS_CF),
+            &ReadWriteOptions::default(),
+            false,
+        )
+        .context("Failed to open watermarks column family")?;
+
+        Ok(Self {
+            db,
+            watermarks,
+            watermark_lock: Arc::new(Mutex::new(())),
+        })
+    }
+
+    /// The underlying database.
+    pub fn database(&self) -> &Arc<Database> {
+        &self.db
+    }
+
+    /// A typed view over the column family `cf`, which must have been declared when the store was
+    /// opened.
+    pub fn table<K, V>(&self, cf: &str) -> anyhow::Result<DBMap<K, V>>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        DBMap::reopen(&self.db, Some(cf), &ReadWriteOptions::default(), false)
+            .with_context(|| format!("Failed to open column family {cf}"))
+    }
+
+    /// Read the watermark for `pipeline`, and replace it with the value returned by `update`, if
+    /// there is one. If `batch` is provided, the write is staged in it instead of being applied
+    /// immediately. Returns whether the watermark was updated.
+    fn update_watermark(
+        &self,
+        pipeline: &'static str,
+        batch: Option<&mut DBBatch>,
+        update: impl FnOnce(Option<StoredWatermark>) -> Option<StoredWatermark>,
+    ) -> anyhow::Result<bool> {
+        let _guard = self
+            .watermark_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let key = pipeline.to_string();
+        let current = self.watermarks.get(&key)?;
+        let Some(updated) = update(current) else {
+            return Ok(false);
+        };
+
+        if let Some(batch) = batch {
+            batch.insert_batch(&self.watermarks, [(key, updated)])?;
+        } else {
+            self.watermarks.insert(&key, &updated)?;
+        }
+
+        Ok(true)
+    }
+}
+
+impl RocksConnection<'_> {
+    /// The store this connection belongs to.
+    pub fn store(&self) -> &RocksStore {
+        self.store
+    }
+
+    /// The batch that writes on this connection should be staged in. Staged writes are applied
+    /// atomically by [Self::commit] (or when the enclosing transaction commits).
+    pub fn batch(&mut self) -> &mut DBBatch {
+        self.batch
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_insert_with(|| self.store.watermarks.batch())
+    }
+
+    /// Apply all writes staged on this connection. Within a transaction this is a no-op, as the
+    /// writes are applied when the transaction commits.
+    pub fn commit(&mut self) -> anyhow::Result<()> {
+        if self.in_transaction {
+            return Ok(());
+        }
+
+        self.flush()
+    }
+
+    fn flush(&mut self) -> anyhow::Result<()> {
+        let batch = self
+            .batch
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+
+        if let Some(batch) = batch {
+            batch.write().context("Failed to write batch")?;
+        }
+
+        Ok(())
+    }
+
+    /// Batch that watermark updates should be staged in: within a transaction, they are written
+    /// along with the rest of the transaction's writes, otherwise they are written immediately.
+    fn watermark_batch(&mut self) -> Option<&mut DBBatch> {
+        if self.in_transaction {
+            Some(self.batch())
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl Connection for RocksConnection<'_> {
+    async fn committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<CommitterWatermark>> {
+        let watermark = self.store.watermarks.get(&pipeline.to_string())?;
+        Ok(watermark.map(|w| CommitterWatermark {
+            epoch_hi_inclusive: w.epoch_hi_inclusive,
+            checkpoint_hi_inclusive: w.checkpoint_hi_inclusive,
+            tx_hi: w.tx_hi,
+            timestamp_ms_hi_inclusive: w.timestamp_ms_hi_inclusive,
+        }))
+    }
+
+    async fn reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<ReaderWatermark>> {
+        let watermark = self.store.watermarks.get(&pipeline.to_string())?;
+        Ok(watermark.map(|w| ReaderWatermark {
+            checkpoint_hi_inclusive: w.checkpoint_hi_inclusive,
+            reader_lo: w.reader_lo,
+        }))
+    }
+
+    async fn set_committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+        watermark: CommitterWatermark,
+    ) -> anyhow::Result<bool> {
+        let store = self.store;
+        store.update_watermark(pipeline, self.watermark_batch(), |current| match current {
+            Some(current)
+                if current.checkpoint_hi_inclusive >= watermark.checkpoint_hi_inclusive =>
+            {
+                None
+            }
+
+            // There is an existing entry, so only write the new `hi` values.
+            current => Some(StoredWatermark {
+                epoch_hi_inclusive: watermark.epoch_hi_inclusive,
+                checkpoint_hi_inclusive: watermark.checkpoint_hi_inclusive,
+                tx_hi: watermark.tx_hi,
+                timestamp_ms_hi_inclusive: watermark.timestamp_ms_hi_inclusive,
+                ..current.unwrap_or_default()
+            }),
+        })
+    }
+
+    async fn set_reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+        reader_lo: u64,
+    ) -> anyhow::Result<bool> {
+        let store = self.store;
+        store.update_watermark(pipeline, self.watermark_batch(), |current| {
+            let current = current?;
+            (current.reader_lo < reader_lo).then(|| StoredWatermark {
+                reader_lo,
+                pruner_timestamp_ms: now_ms(),
+                ..current
+            })
+        })
+    }
+
+    async fn pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        delay: Duration,
+    ) -> anyhow::Result<Option<PrunerWatermark>> {
+        let watermark = self.store.watermarks.get(&pipeline.to_string())?;
+        Ok(watermark.map(|w| {
+            //     |---------- + delay ---------------------|
+            //                             |--- wait_for ---|
+            //     |-----------------------|----------------|
+            //     ^                       ^
+            //     pruner_timestamp        now
+            let prune_after = w.pruner_timestamp_ms + delay.as_millis() as u64;
+            PrunerWatermark {
+                wait_for_ms: prune_after.saturating_sub(now_ms()),
+                reader_lo: w.reader_lo,
+                pruner_hi: w.pruner_hi,
+            }
+        }))
+    }
+
+    async fn set_pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        pruner_hi: u64,
+    ) -> anyhow::Result<bool> {
+        let store = self.store;
+        store.update_watermark(pipeline, self.watermark_batch(), |current| {
+            Some(StoredWatermark {
+                pruner_hi,
+                ..current?
+            })
+        })
+    }
+}
+
+#[async_trait]
+impl Store for RocksStore {
+    type Connection<'c> = RocksConnection<'c>;
+
+    async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>> {
+        Ok(RocksConnection {
+            store: self,
+            batch: Mutex::new(None),
+            in_transaction: false,
+        })
+    }
+}
+
+#[async_trait]
+impl TransactionalStore for RocksStore {
+    /// Run `f` with a connection whose writes (including watermark updates) are staged in a
+    /// single batch, which is written atomically if `f` succeeds, and discarded otherwise.
+    ///
+    /// Watermark updates within a transaction are checked against the watermark at the time they
+    /// are staged, so there should only be one writer for a given pipeline's watermark when using
+    /// transactions (which is the case for sequential pipelines).
+    async fn transaction<'a, R, F>(&self, f: F) -> anyhow::Result<R>
+    where
+        R: Send + 'a,
+        F: Send + 'a,
+        F: for<'r> FnOnce(
+            &'r mut Self::Connection<'_>,
+        ) -> ScopedBoxFuture<'a, 'r, anyhow::Result<R>>,
+    {
+        let mut conn = RocksConnection {
+            store: self,
+            batch: Mutex::new(None),
+            in_transaction: true,
+        };
+
+        let r = f(&mut conn).await?;
+        conn.flush()?;
+        Ok(r)
+    }
+}
+
+/// Milliseconds since the Unix epoch, according to the local clock.
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use scoped_futures::ScopedFutureExt;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_committer_watermark_only_raises() {
+        let dir = tempdir().unwrap();
+        let store = RocksStore::open(dir.path(), &[]).unwrap();
+
+        let mut conn = store.connect().await.unwrap();
+        assert!(conn.committer_watermark("p").await.unwrap().is_none());
+
+        let w10 = CommitterWatermark::new_for_testing(10);
+        let w5 = CommitterWatermark::new_for_testing(5);
+        assert!(conn.set_committer_watermark("p", w10).await.unwrap());
+        assert!(!conn.set_committer_watermark("p", w5).await.unwrap());
+
+        let watermark = conn.committer_watermark("p").await.unwrap().unwrap();
+        assert_eq!(watermark.checkpoint_hi_inclusive, 10);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_is_atomic() {
+        let dir = tempdir().unwrap();
+        let store = RocksStore::open(dir.path(), &["values"]).unwrap();
+        let values: DBMap<u64, u64> = store.table("values").unwrap();
+
+        // A failed transaction leaves no trace.
+        let result: anyhow::Result<()> = store
+            .transaction(|conn| {
+                let values = values.clone();
+                async move {
+                    conn.batch().insert_batch(&values, [(1u64, 1u64)])?;
+                    let watermark = CommitterWatermark::new_for_testing(1);
+                    conn.set_committer_watermark("p", watermark).await?;
+                    anyhow::bail!("Abort");
+                }
+                .scope_boxed()
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert!(values.get(&1).unwrap().is_none());
+        let mut conn = store.connect().await.unwrap();
+        assert!(conn.committer_watermark("p").await.unwrap().is_none());
+
+        // A successful transaction writes both data and watermark.
+        store
+            .transaction(|conn| {
+                let values = values.clone();
+                async move {
+                    conn.batch().insert_batch(&values, [(1u64, 1u64)])?;
+                    let watermark = CommitterWatermark::new_for_testing(1);
+                    conn.set_committer_watermark("p", watermark).await?;
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(values.get(&1).unwrap(), Some(1));
+        let watermark = conn.committer_watermark("p").await.unwrap().unwrap();
+        assert_eq!(watermark.checkpoint_hi_inclusive, 1);
+    }
+}
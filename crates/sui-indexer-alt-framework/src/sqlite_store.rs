@@ -0,0 +1,424 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use rusqlite::{params, OptionalExtension};
+use scoped_futures::ScopedBoxFuture;
+
+use crate::store::{
+    CommitterWatermark, Connection, PrunerWatermark, ReaderWatermark, Store, TransactionalStore,
+};
+
+/// The SQLite equivalent of the framework's `watermarks` migration. `pruner_timestamp` is stored
+/// as milliseconds since the Unix epoch, because SQLite has no native timestamp type.
+const WATERMARKS_SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS watermarks
+(
+    pipeline                    TEXT          PRIMARY KEY,
+    epoch_hi_inclusive          BIGINT        NOT NULL,
+    checkpoint_hi_inclusive     BIGINT        NOT NULL,
+    tx_hi                       BIGINT        NOT NULL,
+    timestamp_ms_hi_inclusive   BIGINT        NOT NULL,
+    reader_lo                   BIGINT        NOT NULL,
+    pruner_timestamp            BIGINT        NOT NULL,
+    pruner_hi                   BIGINT        NOT NULL
+);
+"#;
+
+/// SQL expression for the current time in milliseconds since the Unix epoch, from the
+/// perspective of the database.
+const NOW_MS: &str = "CAST((julianday('now') - 2440587.5) * 86400000 AS BIGINT)";
+
+/// How long a connection will wait for a lock held by another connection, before giving up.
+const DEFAULT_BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A [Store] backed by a single SQLite database file, for indexers that need to run without
+/// operating a Postgres instance.
+///
+/// Every call to [Store::connect] opens a fresh connection to the file (SQLite connections are
+/// cheap to open), and the database is put into WAL mode so that readers and the single writer
+/// do not block each other.
+#[derive(Clone)]
+pub struct SqliteStore {
+    path: Arc<PathBuf>,
+    busy_timeout: Duration,
+}
+
+/// A connection to a [SqliteStore]. Handlers can access the underlying [rusqlite::Connection]
+/// through [SqliteConnection::with_conn] to write their own tables.
+pub struct SqliteConnection<'c> {
+    // SQLite connections are `Send` but not `Sync`, which the [Connection] trait requires, and
+    // calls need to be able to move the connection to a blocking thread. The mutex is never
+    // contended: all access goes through `&mut self`.
+    conn: Arc<Mutex<rusqlite::Connection>>,
+    _store: PhantomData<&'c SqliteStore>,
+}
+
+impl SqliteStore {
+    /// Open (creating if necessary) the SQLite database at `path`, and make sure the framework's
+    /// watermarks table exists.
+    pub async fn new(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let store = Self {
+            path: Arc::new(path.as_ref().to_owned()),
+            busy_timeout: DEFAULT_BUSY_TIMEOUT,
+        };
+
+        let mut conn = store.connect().await?;
+        conn.with_conn(|conn| {
+            conn.pragma_update(None, "journal_mode", "WAL")?;
+            conn.execute_batch(WATERMARKS_SCHEMA)
+        })
+        .await
+        .context("Failed to initialize database")?;
+
+        Ok(store)
+    }
+
+    /// Override how long connections wait on locks held by other connections.
+    pub fn with_busy_timeout(mut self, busy_timeout: Duration) -> Self {
+        self.busy_timeout = busy_timeout;
+        self
+    }
+
+    /// Path to the database file backing this store.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Run the SQL statements in `schema` (e.g. `CREATE TABLE IF NOT EXISTS ...`) to prepare the
+    /// database for the tables written by the indexer's pipelines. Statements should be
+    /// idempotent, because they are run every time the indexer starts.
+    pub async fn run_migrations(&self, schema: &str) -> anyhow::Result<()> {
+        let schema = schema.to_owned();
+        let mut conn = self.connect().await?;
+        conn.with_conn(move |conn| conn.execute_batch(&schema))
+            .await
+            .context("Failed to run migrations")?;
+        Ok(())
+    }
+}
+
+impl SqliteConnection<'_> {
+    /// Run `f` against the underlying SQLite connection on a blocking thread, so that database
+    /// I/O does not stall the async runtime.
+    pub async fn with_conn<T: Send + 'static>(
+        &mut self,
+        f: impl FnOnce(&mut rusqlite::Connection) -> rusqlite::Result<T> + Send + 'static,
+    ) -> anyhow::Result<T> {
+        let conn = self.conn.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            f(&mut conn.lock().unwrap_or_else(PoisonError::into_inner))
+        })
+        .await
+        .context("SQLite task failed")?;
+        Ok(result?)
+    }
+}
+
+#[async_trait]
+impl Connection for SqliteConnection<'_> {
+    async fn committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<CommitterWatermark>> {
+        self.with_conn(move |conn| {
+            conn.query_row(
+                "SELECT epoch_hi_inclusive, checkpoint_hi_inclusive, tx_hi, timestamp_ms_hi_inclusive
+                 FROM watermarks WHERE pipeline = ?1",
+                params![pipeline],
+                |row| {
+                    Ok(CommitterWatermark {
+                        epoch_hi_inclusive: row.get::<_, i64>(0)? as u64,
+                        checkpoint_hi_inclusive: row.get::<_, i64>(1)? as u64,
+                        tx_hi: row.get::<_, i64>(2)? as u64,
+                        timestamp_ms_hi_inclusive: row.get::<_, i64>(3)? as u64,
+                    })
+                },
+            )
+            .optional()
+        })
+        .await
+    }
+
+    async fn reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<ReaderWatermark>> {
+        self.with_conn(move |conn| {
+            conn.query_row(
+                "SELECT checkpoint_hi_inclusive, reader_lo FROM watermarks WHERE pipeline = ?1",
+                params![pipeline],
+                |row| {
+                    Ok(ReaderWatermark {
+                        checkpoint_hi_inclusive: row.get::<_, i64>(0)? as u64,
+                        reader_lo: row.get::<_, i64>(1)? as u64,
+                    })
+                },
+            )
+            .optional()
+        })
+        .await
+    }
+
+    async fn set_committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+        watermark: CommitterWatermark,
+    ) -> anyhow::Result<bool> {
+        let updated = self
+            .with_conn(move |conn| {
+                conn.execute(
+                    // There is an existing entry, so only write the new `hi` values, and only
+                    // if they raise the watermark.
+                    "INSERT INTO watermarks (
+                         pipeline, epoch_hi_inclusive, checkpoint_hi_inclusive, tx_hi,
+                         timestamp_ms_hi_inclusive, reader_lo, pruner_timestamp, pruner_hi
+                     ) VALUES (?1, ?2, ?3, ?4, ?5, 0, 0, 0)
+                     ON CONFLICT (pipeline) DO UPDATE SET
+                         epoch_hi_inclusive = excluded.epoch_hi_inclusive,
+                         checkpoint_hi_inclusive = excluded.checkpoint_hi_inclusive,
+                         tx_hi = excluded.tx_hi,
+                         timestamp_ms_hi_inclusive = excluded.timestamp_ms_hi_inclusive
+                     WHERE watermarks.checkpoint_hi_inclusive < excluded.checkpoint_hi_inclusive",
+                    params![
+                        pipeline,
+                        watermark.epoch_hi_inclusive as i64,
+                        watermark.checkpoint_hi_inclusive as i64,
+                        watermark.tx_hi as i64,
+                        watermark.timestamp_ms_hi_inclusive as i64,
+                    ],
+                )
+            })
+            .await?;
+
+        Ok(updated > 0)
+    }
+
+    async fn set_reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+        reader_lo: u64,
+    ) -> anyhow::Result<bool> {
+        let updated = self
+            .with_conn(move |conn| {
+                conn.execute(
+                    &format!(
+                        "UPDATE watermarks SET reader_lo = ?2, pruner_timestamp = {NOW_MS}
+                         WHERE pipeline = ?1 AND reader_lo < ?2"
+                    ),
+                    params![pipeline, reader_lo as i64],
+                )
+            })
+            .await?;
+
+        Ok(updated > 0)
+    }
+
+    async fn pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        delay: Duration,
+    ) -> anyhow::Result<Option<PrunerWatermark>> {
+        //     |---------- + delay ---------------------|
+        //                             |--- wait_for ---|
+        //     |-----------------------|----------------|
+        //     ^                       ^
+        //     pruner_timestamp        NOW()
+        self.with_conn(move |conn| {
+            conn.query_row(
+                &format!(
+                    "SELECT MAX(0, ?2 + pruner_timestamp - {NOW_MS}), pruner_hi, reader_lo
+                     FROM watermarks WHERE pipeline = ?1"
+                ),
+                params![pipeline, delay.as_millis() as i64],
+                |row| {
+                    Ok(PrunerWatermark {
+                        wait_for_ms: row.get::<_, i64>(0)? as u64,
+                        pruner_hi: row.get::<_, i64>(1)? as u64,
+                        reader_lo: row.get::<_, i64>(2)? as u64,
+                    })
+                },
+            )
+            .optional()
+        })
+        .await
+    }
+
+    async fn set_pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        pruner_hi: u64,
+    ) -> anyhow::Result<bool> {
+        let updated = self
+            .with_conn(move |conn| {
+                conn.execute(
+                    "UPDATE watermarks SET pruner_hi = ?2 WHERE pipeline = ?1",
+                    params![pipeline, pruner_hi as i64],
+                )
+            })
+            .await?;
+
+        Ok(updated > 0)
+    }
+}
+
+#[async_trait]
+impl Store for SqliteStore {
+    type Connection<'c> = SqliteConnection<'c>;
+
+    async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>> {
+        let path = self.path.clone();
+        let busy_timeout = self.busy_timeout;
+        let conn = tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
+            let conn = rusqlite::Connection::open(path.as_path())
+                .with_context(|| format!("Failed to open SQLite database at {}", path.display()))?;
+
+            conn.busy_timeout(busy_timeout)
+                .context("Failed to set busy timeout")?;
+
+            Ok(conn)
+        })
+        .await
+        .context("SQLite task failed")??;
+
+        Ok(SqliteConnection {
+            conn: Arc::new(Mutex::new(conn)),
+            _store: PhantomData,
+        })
+    }
+}
+
+#[async_trait]
+impl TransactionalStore for SqliteStore {
+    async fn transaction<'a, R, F>(&self, f: F) -> anyhow::Result<R>
+    where
+        R: Send + 'a,
+        F: Send + 'a,
+        F: for<'r> FnOnce(
+            &'r mut Self::Connection<'_>,
+        ) -> ScopedBoxFuture<'a, 'r, anyhow::Result<R>>,
+    {
+        let mut conn = self.connect().await?;
+
+        // Take the write lock up-front, so that the transaction cannot fail part-way through
+        // because another connection started writing first.
+        conn.with_conn(|conn| conn.execute_batch("BEGIN IMMEDIATE"))
+            .await
+            .context("Failed to begin transaction")?;
+
+        match f(&mut conn).await {
+            Ok(r) => {
+                conn.with_conn(|conn| conn.execute_batch("COMMIT"))
+                    .await
+                    .context("Failed to commit transaction")?;
+                Ok(r)
+            }
+
+            Err(e) => {
+                // If the rollback fails as well, SQLite rolls the transaction back when the
+                // connection is closed, so surface the original error.
+                let _ = conn.with_conn(|conn| conn.execute_batch("ROLLBACK")).await;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scoped_futures::ScopedFutureExt;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_committer_watermark_only_raises() {
+        let dir = tempdir().unwrap();
+        let store = SqliteStore::new(dir.path().join("indexer.db"))
+            .await
+            .unwrap();
+
+        let mut conn = store.connect().await.unwrap();
+        assert!(conn.committer_watermark("p").await.unwrap().is_none());
+
+        let w10 = CommitterWatermark::new_for_testing(10);
+        let w5 = CommitterWatermark::new_for_testing(5);
+        assert!(conn.set_committer_watermark("p", w10).await.unwrap());
+        assert!(!conn.set_committer_watermark("p", w5).await.unwrap());
+
+        let watermark = conn.committer_watermark("p").await.unwrap().unwrap();
+        assert_eq!(watermark.checkpoint_hi_inclusive, 10);
+    }
+
+    #[tokio::test]
+    async fn test_reader_and_pruner_watermarks() {
+        let dir = tempdir().unwrap();
+        let store = SqliteStore::new(dir.path().join("indexer.db"))
+            .await
+            .unwrap();
+
+        let mut conn = store.connect().await.unwrap();
+
+        // Reader watermark can only be set on an existing entry.
+        assert!(!conn.set_reader_watermark("p", 5).await.unwrap());
+
+        let watermark = CommitterWatermark::new_for_testing(10);
+        assert!(conn.set_committer_watermark("p", watermark).await.unwrap());
+        assert!(conn.set_reader_watermark("p", 5).await.unwrap());
+        assert!(!conn.set_reader_watermark("p", 3).await.unwrap());
+
+        let reader = conn.reader_watermark("p").await.unwrap().unwrap();
+        assert_eq!(reader.reader_lo, 5);
+        assert_eq!(reader.checkpoint_hi_inclusive, 10);
+
+        // The reader watermark was just updated, so the pruner must wait out the delay.
+        let pruner = conn
+            .pruner_watermark("p", Duration::from_secs(60))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(pruner.wait_for_ms > 0);
+        assert_eq!(pruner.reader_lo, 5);
+        assert_eq!(pruner.pruner_hi, 0);
+
+        assert!(conn.set_pruner_watermark("p", 5).await.unwrap());
+        let pruner = conn
+            .pruner_watermark("p", Duration::ZERO)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pruner.wait_for_ms, 0);
+        assert_eq!(pruner.pruner_hi, 5);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_rollback() {
+        let dir = tempdir().unwrap();
+        let store = SqliteStore::new(dir.path().join("indexer.db"))
+            .await
+            .unwrap();
+
+        let result: anyhow::Result<()> = store
+            .transaction(|conn| {
+                async move {
+                    let watermark = CommitterWatermark::new_for_testing(10);
+                    conn.set_committer_watermark("p", watermark).await?;
+                    anyhow::bail!("Abort");
+                }
+                .scope_boxed()
+            })
+            .await;
+        assert!(result.is_err());
+
+        let mut conn = store.connect().await.unwrap();
+        assert!(conn.committer_watermark("p").await.unwrap().is_none());
+    }
+}
@@ -2,16 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Context as _;
-
+use diesel::{sql_types::BigInt as SqlBigInt, ExpressionMethods, QueryDsl};
+use futures::future;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use sui_json_rpc_types::Checkpoint;
+use sui_indexer_alt_schema::schema::cp_sequence_numbers;
+use sui_json_rpc_types::{Checkpoint, Page as PageResponse};
 use sui_open_rpc::Module;
 use sui_open_rpc_macros::open_rpc;
+use sui_sql_macro::sql;
 use sui_types::sui_serde::BigInt;
 
 use crate::{
     context::Context,
-    error::{invalid_params, InternalContext, RpcError},
+    error::{invalid_params, rpc_bail, InternalContext, RpcError},
+    paginate::{Cursor as _, JsonCursor, Page},
 };
 
 use super::rpc_module::RpcModule;
@@ -26,16 +30,37 @@ trait CheckpointsApi {
         /// Checkpoint sequence number.
         seq: BigInt<u64>,
     ) -> RpcResult<Checkpoint>;
+
+    /// Return a paginated list of checkpoints.
+    ///
+    /// If a cursor is provided, the query will start from the checkpoint after the one pointed to
+    /// by this cursor, otherwise pagination starts from the first checkpoint available (the
+    /// earliest unpruned checkpoint, or the latest checkpoint, depending on `descending_order`).
+    #[method(name = "getCheckpoints")]
+    async fn get_checkpoints(
+        &self,
+        /// Cursor to start paginating from.
+        cursor: Option<String>,
+        /// Maximum number of checkpoints to return per page.
+        limit: Option<usize>,
+        /// Order of results, defaulting to ascending order (false), by sequence number.
+        descending_order: Option<bool>,
+    ) -> RpcResult<PageResponse<Checkpoint, String>>;
 }
 
 pub(crate) struct Checkpoints(pub Context);
 
-#[derive(thiserror::Error, Debug, Clone)]
+#[derive(thiserror::Error, Debug)]
 enum Error {
     #[error("Checkpoint {0} not found")]
     NotFound(u64),
+
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
 }
 
+type Cursor = JsonCursor<u64>;
+
 #[async_trait::async_trait]
 impl CheckpointsApiServer for Checkpoints {
     async fn get_checkpoint(&self, seq: BigInt<u64>) -> RpcResult<Checkpoint> {
@@ -44,6 +69,55 @@ impl CheckpointsApiServer for Checkpoints {
             format!("Failed to fetch checkpoint at sequence number {seq:?}")
         })?)
     }
+
+    async fn get_checkpoints(
+        &self,
+        cursor: Option<String>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<PageResponse<Checkpoint, String>> {
+        let Self(ctx) = self;
+        let config = &ctx.config().checkpoints;
+
+        let page: Page<Cursor> = Page::from_params::<Error>(
+            config.default_page_size,
+            config.max_page_size,
+            cursor.clone(),
+            limit,
+            descending_order,
+        )?;
+
+        let PageResponse {
+            data: sequence_numbers,
+            next_cursor,
+            has_next_page,
+        } = checkpoints(ctx, &page)
+            .await
+            .internal_context("Failed to fetch checkpoint sequence numbers")?;
+
+        let checkpoint_futures = sequence_numbers.iter().map(|seq| response(ctx, *seq));
+
+        let data = future::join_all(checkpoint_futures)
+            .await
+            .into_iter()
+            .zip(sequence_numbers)
+            .map(|(r, seq)| {
+                if let Err(RpcError::InvalidParams(e @ Error::NotFound(_))) = r {
+                    rpc_bail!(e)
+                } else {
+                    r.with_internal_context(|| {
+                        format!("Failed to fetch checkpoint at sequence number {seq}")
+                    })
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(PageResponse {
+            data,
+            next_cursor: next_cursor.or(cursor),
+            has_next_page,
+        })
+    }
 }
 
 impl RpcModule for Checkpoints {
@@ -67,3 +141,61 @@ async fn response(ctx: &Context, seq: u64) -> Result<Checkpoint, RpcError<Error>
 
     Ok(Checkpoint::from((summary, contents, signature.signature)))
 }
+
+/// Fetch the sequence numbers for a page of checkpoints, bounded below by the reader low watermark
+/// of the `cp_sequence_numbers` pipeline, so that the page does not include checkpoints that have
+/// been pruned.
+async fn checkpoints(
+    ctx: &Context,
+    page: &Page<Cursor>,
+) -> Result<PageResponse<u64, String>, RpcError<Error>> {
+    use cp_sequence_numbers::dsl as c;
+
+    let mut query = c::cp_sequence_numbers
+        .select(c::cp_sequence_number)
+        .filter(c::cp_sequence_number.ge(sql!(as SqlBigInt,
+            "COALESCE((SELECT reader_lo FROM watermarks WHERE pipeline = 'cp_sequence_numbers'), 0)"
+        )))
+        .limit(page.limit + 1)
+        .into_boxed();
+
+    if let Some(JsonCursor(cp)) = page.cursor {
+        if page.descending {
+            query = query.filter(c::cp_sequence_number.lt(cp as i64));
+        } else {
+            query = query.filter(c::cp_sequence_number.gt(cp as i64));
+        }
+    }
+
+    if page.descending {
+        query = query.order(c::cp_sequence_number.desc());
+    } else {
+        query = query.order(c::cp_sequence_number.asc());
+    }
+
+    let mut rows: Vec<i64> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(query)
+        .await
+        .context("Failed to fetch checkpoint sequence numbers")?;
+
+    let has_next_page = rows.len() > page.limit as usize;
+    if has_next_page {
+        rows.truncate(page.limit as usize);
+    }
+
+    let next_cursor = rows
+        .last()
+        .map(|last| JsonCursor(*last as u64).encode())
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    Ok(PageResponse {
+        data: rows.into_iter().map(|seq| seq as u64).collect(),
+        next_cursor,
+        has_next_page,
+    })
+}
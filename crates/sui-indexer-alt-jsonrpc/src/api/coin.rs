@@ -18,14 +18,19 @@ use sui_open_rpc_macros::open_rpc;
 use sui_sql_macro::sql;
 use sui_types::object::Object;
 use sui_types::{
+    balance::Supply,
     base_types::{ObjectID, SuiAddress},
-    gas_coin::GAS,
+    coin::TreasuryCap,
+    gas_coin::{GAS, TOTAL_SUPPLY_MIST},
 };
 
 use crate::{
     config::NodeConfig,
     context::Context,
-    data::{coin_metadata::CoinMetadataKey, objects::load_latest},
+    data::{
+        coin_metadata::{CoinMetadataKey, TreasuryCapKey},
+        objects::{load_latest, load_latest_deserialized},
+    },
     error::{client_error_to_error_object, invalid_params, InternalContext, RpcError},
     paginate::{BcsCursor, Cursor as _, Page},
 };
@@ -50,6 +55,38 @@ trait CoinsApi {
         limit: Option<usize>,
     ) -> RpcResult<PageResponse<Coin, String>>;
 
+    /// Return all Coin objects owned by an address, regardless of their coin type.
+    #[method(name = "getAllCoins")]
+    async fn get_all_coins(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional paging cursor
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<Coin, String>>;
+
+    /// Return the total coin balance for one coin type, owned by the address owner. If no coin
+    /// type is specified, the balance of SUI coins is returned.
+    #[method(name = "getBalance")]
+    async fn get_balance(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional coin type
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance>;
+
+    /// Return the total supply for a coin. Note that if the coin's TreasuryCap is wrapped or
+    /// deleted, its supply will not be found.
+    #[method(name = "getTotalSupply")]
+    async fn get_total_supply(
+        &self,
+        /// type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC)
+        coin_type: String,
+    ) -> RpcResult<Supply>;
+
     /// Return metadata (e.g., symbol, decimals) for a coin. Note that if the coin's metadata was
     /// wrapped in the transaction that published its marker type, or the latest version of the
     /// metadata object is wrapped or deleted, it will not be found.
@@ -84,6 +121,9 @@ pub(crate) enum Error {
 
     #[error("Failed to parse type {0:?}: {1}")]
     BadType(String, anyhow::Error),
+
+    #[error("Could not find a TreasuryCap for {0:?}")]
+    NoTreasuryCap(String),
 }
 
 #[derive(Queryable, Debug, Serialize, Deserialize)]
@@ -112,41 +152,41 @@ impl CoinsApiServer for Coins {
         cursor: Option<String>,
         limit: Option<usize>,
     ) -> RpcResult<PageResponse<Coin, String>> {
-        let coin_type_tag = if let Some(coin_type) = coin_type {
-            sui_types::parse_sui_type_tag(&coin_type)
-                .map_err(|e| invalid_params(Error::BadType(coin_type, e)))?
-        } else {
-            GAS::type_tag()
-        };
+        let coin_type_tag = parse_coin_type(coin_type)?;
 
         let Self(ctx) = self;
-        let config = &ctx.config().coins;
+        Ok(coins_response(ctx, owner, Some(coin_type_tag), cursor, limit).await?)
+    }
+
+    async fn get_all_coins(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<Coin, String>> {
+        let Self(ctx) = self;
+        Ok(coins_response(ctx, owner, None, cursor, limit).await?)
+    }
 
-        let page: Page<Cursor> = Page::from_params::<Error>(
-            config.default_page_size,
-            config.max_page_size,
-            cursor,
-            limit,
-            None,
-        )?;
+    async fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance> {
+        let coin_type_tag = parse_coin_type(coin_type)?;
 
-        // We get all the qualified coin ids first.
-        let coin_id_page = filter_coins(ctx, owner, Some(coin_type_tag), Some(page)).await?;
+        let Self(ctx) = self;
+        Ok(balance_response(ctx, owner, coin_type_tag)
+            .await
+            .with_internal_context(|| format!("Failed to fetch balance for {owner}"))?)
+    }
 
-        let coin_futures = coin_id_page.data.iter().map(|id| coin_response(ctx, *id));
+    async fn get_total_supply(&self, coin_type: String) -> RpcResult<Supply> {
+        let Self(ctx) = self;
 
-        let coins = future::join_all(coin_futures)
+        Ok(total_supply_response(ctx, &coin_type)
             .await
-            .into_iter()
-            .zip(coin_id_page.data)
-            .map(|(r, id)| r.with_internal_context(|| format!("Failed to get object {id}")))
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(PageResponse {
-            data: coins,
-            next_cursor: coin_id_page.next_cursor,
-            has_next_page: coin_id_page.has_next_page,
-        })
+            .with_internal_context(|| format!("Failed to fetch total supply for {coin_type:?}"))?)
     }
 
     async fn get_coin_metadata(&self, coin_type: String) -> RpcResult<Option<SuiCoinMetadata>> {
@@ -190,6 +230,113 @@ impl RpcModule for DelegationCoins {
     }
 }
 
+/// Parse the coin type parameter from a request, defaulting to SUI if none is provided.
+fn parse_coin_type(coin_type: Option<String>) -> Result<TypeTag, RpcError<Error>> {
+    let Some(coin_type) = coin_type else {
+        return Ok(GAS::type_tag());
+    };
+
+    sui_types::parse_sui_type_tag(&coin_type)
+        .map_err(|e| invalid_params(Error::BadType(coin_type, e)))
+}
+
+/// Load a page of coins owned by `owner`, optionally limited to coins of type `coin_type_tag`.
+async fn coins_response(
+    ctx: &Context,
+    owner: SuiAddress,
+    coin_type_tag: Option<TypeTag>,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<PageResponse<Coin, String>, RpcError<Error>> {
+    let config = &ctx.config().coins;
+
+    let page: Page<Cursor> = Page::from_params::<Error>(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        None,
+    )?;
+
+    // We get all the qualified coin ids first.
+    let coin_id_page = filter_coins(ctx, owner, coin_type_tag, Some(page)).await?;
+
+    let coin_futures = coin_id_page.data.iter().map(|id| coin_response(ctx, *id));
+
+    let coins = future::join_all(coin_futures)
+        .await
+        .into_iter()
+        .zip(coin_id_page.data)
+        .map(|(r, id)| r.with_internal_context(|| format!("Failed to get object {id}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PageResponse {
+        data: coins,
+        next_cursor: coin_id_page.next_cursor,
+        has_next_page: coin_id_page.has_next_page,
+    })
+}
+
+/// Sum up the balances of all the coins of type `coin_type_tag` owned by `owner`.
+async fn balance_response(
+    ctx: &Context,
+    owner: SuiAddress,
+    coin_type_tag: TypeTag,
+) -> Result<Balance, RpcError<Error>> {
+    let coin_type = coin_type_tag.to_canonical_string(/* with_prefix */ true);
+    let coin_ids = filter_coins(ctx, owner, Some(coin_type_tag), None).await?;
+
+    let coin_futures = coin_ids
+        .data
+        .iter()
+        .map(|id| object_with_coin_data(ctx, *id));
+
+    let mut total_balance = 0u128;
+    for (r, id) in future::join_all(coin_futures)
+        .await
+        .into_iter()
+        .zip(&coin_ids.data)
+    {
+        let (_, _, balance) = r.with_internal_context(|| format!("Failed to get object {id}"))?;
+        total_balance += balance as u128;
+    }
+
+    Ok(Balance {
+        coin_type,
+        coin_object_count: coin_ids.data.len(),
+        total_balance,
+        locked_balance: Default::default(),
+    })
+}
+
+/// Look up the total supply of the coin with type `coin_type`. The supply of SUI is fixed, and the
+/// supply of other coins is read from their TreasuryCap.
+async fn total_supply_response(ctx: &Context, coin_type: &str) -> Result<Supply, RpcError<Error>> {
+    let coin_type_tag = StructTag::from_str(coin_type)
+        .map_err(|e| invalid_params(Error::BadType(coin_type.to_owned(), e)))?;
+
+    if GAS::is_gas(&coin_type_tag) {
+        return Ok(Supply {
+            value: TOTAL_SUPPLY_MIST,
+        });
+    }
+
+    let stored = ctx
+        .pg_loader()
+        .load_one(TreasuryCapKey(coin_type_tag))
+        .await
+        .context("Failed to load info for TreasuryCap")?
+        .ok_or_else(|| invalid_params(Error::NoTreasuryCap(coin_type.to_owned())))?;
+
+    let id = ObjectID::from_bytes(&stored.object_id).context("Failed to parse ObjectID")?;
+
+    let treasury_cap: TreasuryCap = load_latest_deserialized(ctx, id)
+        .await
+        .context("Failed to load latest version of TreasuryCap")?;
+
+    Ok(treasury_cap.total_supply)
+}
+
 async fn filter_coins(
     ctx: &Context,
     owner: SuiAddress,
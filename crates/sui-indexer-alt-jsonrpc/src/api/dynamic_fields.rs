@@ -2,24 +2,33 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::{anyhow, Context as _};
+use diesel::{sql_types::Bool, BoolExpressionMethods, ExpressionMethods, JoinOnDsl, QueryDsl};
+use futures::future;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use sui_indexer_alt_schema::{objects::StoredOwnerKind, schema::obj_info};
 use sui_json::SuiJsonValue;
-use sui_json_rpc_types::{SuiObjectDataOptions, SuiObjectResponse};
+use sui_json_rpc_types::{
+    DynamicFieldInfo as SuiDynamicFieldInfo, Page as PageResponse, SuiMoveValue,
+    SuiObjectDataOptions, SuiObjectResponse,
+};
 use sui_open_rpc::Module;
 use sui_open_rpc_macros::open_rpc;
+use sui_sql_macro::sql;
 use sui_types::{
     base_types::ObjectID,
-    dynamic_field::{derive_dynamic_field_id, DynamicFieldInfo, DynamicFieldName},
+    dynamic_field::{derive_dynamic_field_id, visitor as DFV, DynamicFieldInfo, DynamicFieldName},
     error::SuiObjectResponseError,
-    object::Object,
+    object::{bounded_visitor::BoundedVisitor, Object},
     TypeTag,
 };
 use tokio::try_join;
 
 use crate::{
     context::Context,
-    data::objects::load_live,
-    error::{invalid_params, rpc_bail, RpcError},
+    data::objects::{load_latest, load_live},
+    error::{invalid_params, rpc_bail, InternalContext, RpcError},
+    paginate::{BcsCursor, Cursor as _, Page},
 };
 
 use super::{objects, rpc_module::RpcModule};
@@ -36,6 +45,24 @@ trait DynamicFieldsApi {
         /// The Name of the dynamic field
         name: DynamicFieldName,
     ) -> RpcResult<SuiObjectResponse>;
+
+    /// Query the dynamic fields (and dynamic object fields) owned by a parent object. Returns a
+    /// paginated list of dynamic field info.
+    ///
+    /// If a cursor is provided, the query will start from the field after the one pointed to by
+    /// this cursor, otherwise pagination starts from the first page of fields owned by the parent.
+    /// As with owned objects, if the set of fields changes during pagination, the results may not
+    /// reflect the fields that the parent owned at a single point in time.
+    #[method(name = "getDynamicFields")]
+    async fn get_dynamic_fields(
+        &self,
+        /// The ID of the parent object
+        parent_object_id: ObjectID,
+        /// Cursor to start paginating from.
+        cursor: Option<String>,
+        /// Maximum number of fields to return per page.
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<SuiDynamicFieldInfo, String>>;
 }
 
 pub struct DynamicFields(pub Context);
@@ -48,6 +75,9 @@ enum Error {
     #[error("Invalid type {0}: {1}")]
     BadType(TypeTag, sui_package_resolver::error::Error),
 
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+
     #[error("Could not serialize dynamic field name as {0}: {1}")]
     TypeMismatch(TypeTag, anyhow::Error),
 }
@@ -62,8 +92,46 @@ impl DynamicFieldsApiServer for DynamicFields {
         let Self(ctx) = self;
         Ok(dynamic_field_object_response(ctx, parent_object_id, name).await?)
     }
+
+    async fn get_dynamic_fields(
+        &self,
+        parent_object_id: ObjectID,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<SuiDynamicFieldInfo, String>> {
+        let Self(ctx) = self;
+
+        let PageResponse {
+            data: field_ids,
+            next_cursor,
+            has_next_page,
+        } = dynamic_field_ids(ctx, parent_object_id, cursor.clone(), limit).await?;
+
+        let info_futures = field_ids.iter().map(|id| dynamic_field_info(ctx, *id));
+
+        let data = future::join_all(info_futures)
+            .await
+            .into_iter()
+            .zip(field_ids)
+            .map(|(r, id)| r.with_internal_context(|| format!("Failed to get dynamic field {id}")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(PageResponse {
+            data,
+            next_cursor: next_cursor.or(cursor),
+            has_next_page,
+        })
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct FieldCursor {
+    object_id: Vec<u8>,
+    cp_sequence_number: u64,
 }
 
+type Cursor = BcsCursor<FieldCursor>;
+
 impl RpcModule for DynamicFields {
     fn schema(&self) -> Module {
         DynamicFieldsApiOpenRpc::module_doc()
@@ -202,3 +270,182 @@ async fn load_dof(
         .await
         .context("Failed to load dynamic field object")?)
 }
+
+/// Fetch the IDs of the `Field` objects for a page of dynamic fields owned by `parent_id`, using
+/// the same pagination scheme as objects owned by addresses. Returns the IDs and a cursor pointing
+/// to the last result (if there are any results).
+async fn dynamic_field_ids(
+    ctx: &Context,
+    parent_id: ObjectID,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<PageResponse<ObjectID, String>, RpcError<Error>> {
+    use obj_info::dsl as o;
+
+    let (candidates, newer) = diesel::alias!(obj_info as candidates, obj_info as newer);
+
+    macro_rules! candidates {
+        ($($field:ident),*) => {
+            candidates.fields(($(o::$field),*))
+        };
+    }
+
+    macro_rules! newer {
+        ($($field:ident),*) => {
+            newer.fields(($(o::$field),*))
+        };
+    }
+
+    let config = &ctx.config().objects;
+    let page: Page<Cursor> = Page::from_params(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        None,
+    )?;
+
+    let mut query = candidates
+        .select(candidates!(object_id, cp_sequence_number))
+        .left_join(
+            newer.on(candidates!(object_id)
+                .eq(newer!(object_id))
+                .and(candidates!(cp_sequence_number).lt(newer!(cp_sequence_number)))),
+        )
+        .filter(newer!(object_id).is_null())
+        .filter(candidates!(owner_kind).eq(StoredOwnerKind::Object))
+        .filter(candidates!(owner_id).eq(parent_id.into_bytes()))
+        .order_by(candidates!(cp_sequence_number).desc())
+        .then_order_by(candidates!(object_id).desc())
+        .limit(page.limit + 1)
+        .into_boxed();
+
+    if let Some(c) = page.cursor {
+        query = query.filter(sql!(as Bool,
+            "(candidates.cp_sequence_number, candidates.object_id) < ({BigInt}, {Bytea})",
+            c.cp_sequence_number as i64,
+            c.object_id.clone(),
+        ));
+    }
+
+    let mut results: Vec<(Vec<u8>, i64)> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(query)
+        .await
+        .context("Failed to fetch object info")?;
+
+    let has_next_page = results.len() > page.limit as usize;
+    if has_next_page {
+        results.truncate(page.limit as usize);
+    }
+
+    let next_cursor = results
+        .last()
+        .map(|(o, c)| {
+            BcsCursor(FieldCursor {
+                object_id: o.clone(),
+                cp_sequence_number: *c as u64,
+            })
+            .encode()
+        })
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    let data: Vec<ObjectID> = results
+        .into_iter()
+        .map(|(o, _)| ObjectID::from_bytes(o))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to deserialize Object IDs")?;
+
+    Ok(PageResponse {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// Load the `Field` object at `id` and extract the information describing the dynamic field it
+/// represents. For dynamic object fields, the version, digest and type are taken from the object
+/// that the field points to, rather than the `Field` object itself.
+async fn dynamic_field_info(
+    ctx: &Context,
+    id: ObjectID,
+) -> Result<SuiDynamicFieldInfo, RpcError<Error>> {
+    let object = load_latest(ctx, id)
+        .await
+        .context("Failed to load dynamic field")?
+        .with_context(|| format!("Failed to find content for dynamic field {id}"))?;
+
+    let Some(move_object) = object.data.try_as_move() else {
+        rpc_bail!("Dynamic field at {id} is not a Move Object");
+    };
+
+    let type_: TypeTag = move_object.type_().clone().into();
+    let layout = ctx
+        .package_resolver()
+        .type_layout(type_.clone())
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to resolve type layout for {}",
+                type_.to_canonical_display(/* with_prefix */ true)
+            )
+        })?;
+
+    let field = DFV::FieldVisitor::deserialize(move_object.contents(), &layout)
+        .context("Failed to deserialize dynamic field")?;
+
+    let name_value = BoundedVisitor::deserialize_value(field.name_bytes, field.name_layout)
+        .context("Failed to deserialize dynamic field name")?;
+
+    let name = DynamicFieldName {
+        type_: field.name_layout.into(),
+        value: SuiMoveValue::from(name_value).to_json_value(),
+    };
+
+    let bcs_name = field.name_bytes.to_owned();
+
+    let value_metadata = field
+        .value_metadata()
+        .context("Failed to get dynamic field value metadata")?;
+
+    let info = match value_metadata {
+        DFV::ValueMetadata::DynamicField(object_type) => DynamicFieldInfo {
+            name,
+            bcs_name,
+            type_: field.kind,
+            object_type: object_type.to_canonical_string(/* with_prefix */ true),
+            object_id: object.id(),
+            version: object.version(),
+            digest: object.digest(),
+        },
+
+        DFV::ValueMetadata::DynamicObjectField(object_id) => {
+            let object = load_live(ctx, object_id)
+                .await
+                .context("Failed to load dynamic field object")?
+                .with_context(|| format!("Failed to find dynamic field object {object_id}"))?;
+
+            let object_type = object
+                .data
+                .type_()
+                .with_context(|| format!("Dynamic field object {object_id} is not a Move Object"))?
+                .to_canonical_string(/* with_prefix */ true);
+
+            DynamicFieldInfo {
+                name,
+                bcs_name,
+                type_: field.kind,
+                object_type,
+                object_id,
+                version: object.version(),
+                digest: object.digest(),
+            }
+        }
+    };
+
+    Ok(info.into())
+}
@@ -0,0 +1,13 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::digests::TransactionDigest;
+
+#[derive(thiserror::Error, Debug)]
+pub(super) enum Error {
+    #[error("Transaction {0} not found")]
+    NotFound(TransactionDigest),
+
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+}
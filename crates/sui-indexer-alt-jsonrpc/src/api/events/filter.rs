@@ -0,0 +1,415 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use diesel::{
+    expression::{
+        is_aggregate::{Never, No},
+        MixedAggregates, ValidGrouping,
+    },
+    pg::Pg,
+    query_builder::{BoxedSelectStatement, FromClause, QueryFragment},
+    sql_types::BigInt as SqlBigInt,
+    AppearsOnTable, Column, Expression, ExpressionMethods, QueryDsl, QuerySource,
+};
+use futures::future;
+use move_core_types::language_storage::StructTag;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sui_indexer_alt_schema::schema::{ev_emit_mod, ev_struct_inst, tx_digests};
+use sui_json_rpc_types::{Page as PageResponse, SuiEvent};
+use sui_sql_macro::sql;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    digests::TransactionDigest,
+    event::Event,
+    sui_serde::SuiStructTag,
+    Identifier,
+};
+
+use crate::{
+    context::Context,
+    data::tx_digests::TxDigestKey,
+    error::{invalid_params, RpcError},
+    paginate::{Cursor as _, JsonCursor, Page},
+};
+
+use super::{error::Error, response::event_response};
+
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+pub(crate) enum EventFilter {
+    /// Return all events.
+    All([Box<EventFilter>; 0]),
+    /// Query by sender address.
+    Sender(SuiAddress),
+    /// Return events emitted by the given transaction.
+    Transaction(TransactionDigest),
+    /// Return events emitted in a specified Move module. If the event is defined in Module A but
+    /// emitted in a tx with Module B, query `MoveModule` by module B returns the event. Query
+    /// `MoveEventModule` by module A returns the event too.
+    MoveModule {
+        /// The Move package ID.
+        package: ObjectID,
+        /// The module name.
+        #[schemars(with = "String")]
+        #[serde_as(as = "DisplayFromStr")]
+        module: Identifier,
+    },
+    /// Return events with the given Move event struct name (struct tag). For example, if the
+    /// event is defined in `0xabcd::MyModule`, and named `Foo`, then the struct tag is
+    /// `0xabcd::MyModule::Foo`.
+    MoveEventType(
+        #[schemars(with = "String")]
+        #[serde_as(as = "SuiStructTag")]
+        StructTag,
+    ),
+    /// Return events with the given Move module name where the event struct is defined. If the
+    /// event is defined in Module A but emitted in a tx with Module B, query `MoveEventModule` by
+    /// module A returns the event. Query `MoveModule` by module B returns the event too.
+    MoveEventModule {
+        /// The Move package ID.
+        package: ObjectID,
+        /// The module name.
+        #[schemars(with = "String")]
+        #[serde_as(as = "DisplayFromStr")]
+        module: Identifier,
+    },
+}
+
+/// Events are identified by the sequence number of the transaction that emitted them, and their
+/// position within that transaction's events.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct EventCursor {
+    tx: u64,
+    ev: u64,
+}
+
+type Cursor = JsonCursor<EventCursor>;
+type Events = PageResponse<SuiEvent, String>;
+
+/// Fetch a page of events that satisfy the given `filter` and pagination parameters. Returns the
+/// events and a cursor pointing to the last result (if there are any results).
+///
+/// Events are indexed by the transactions that emitted them, so this function first finds the
+/// transactions that could contain matching events, then loads those transactions' events and
+/// filters them in memory.
+pub(super) async fn events(
+    ctx: &Context,
+    filter: &EventFilter,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    descending_order: Option<bool>,
+) -> Result<Events, RpcError<Error>> {
+    let config = &ctx.config().events;
+    let page: Page<Cursor> = Page::from_params(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        descending_order,
+    )?;
+
+    use EventFilter as F;
+    let tx_sequence_numbers = match filter {
+        F::All([]) => ev_emit_mod(ctx, &page, None, None).await?,
+
+        F::Sender(sender) => ev_emit_mod(ctx, &page, Some(*sender), None).await?,
+
+        F::MoveModule { package, module } => {
+            ev_emit_mod(ctx, &page, None, Some((package, module))).await?
+        }
+
+        F::MoveEventModule { package, module } => {
+            ev_struct_inst(ctx, &page, package, module, None).await?
+        }
+
+        F::MoveEventType(tag) => {
+            let package = ObjectID::from(tag.address);
+            ev_struct_inst(ctx, &page, &package, &tag.module, Some(tag)).await?
+        }
+
+        F::Transaction(digest) => by_digest(ctx, *digest).await?,
+    };
+
+    from_sequence_numbers(ctx, filter, &page, tx_sequence_numbers).await
+}
+
+/// Fetch the sequence numbers of transactions that emitted events from a Move module, optionally
+/// limited to events from a given `sender`, or emitted by a particular `package` and `module`.
+async fn ev_emit_mod(
+    ctx: &Context,
+    page: &Page<Cursor>,
+    sender: Option<SuiAddress>,
+    module: Option<(&ObjectID, &Identifier)>,
+) -> Result<Vec<u64>, RpcError<Error>> {
+    use ev_emit_mod::dsl as e;
+
+    let mut query = e::ev_emit_mod
+        .select(e::tx_sequence_number)
+        .distinct()
+        .into_boxed();
+
+    if let Some(sender) = sender {
+        query = query.filter(e::sender.eq(sender.to_inner()));
+    }
+
+    if let Some((package, module)) = module {
+        query = query
+            .filter(e::package.eq(package.as_slice()))
+            .filter(e::module.eq(module.as_str()));
+    }
+
+    let results: Vec<i64> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(paginate(page, "ev_emit_mod", e::tx_sequence_number, query))
+        .await
+        .context("Failed to fetch transaction sequence numbers")?;
+
+    Ok(results.into_iter().map(|tx| tx as u64).collect())
+}
+
+/// Fetch the sequence numbers of transactions that emitted events whose types were defined in
+/// `package::module`, optionally limited to events of exactly the type `tag`.
+async fn ev_struct_inst(
+    ctx: &Context,
+    page: &Page<Cursor>,
+    package: &ObjectID,
+    module: &Identifier,
+    tag: Option<&StructTag>,
+) -> Result<Vec<u64>, RpcError<Error>> {
+    use ev_struct_inst::dsl as e;
+
+    let mut query = e::ev_struct_inst
+        .select(e::tx_sequence_number)
+        .distinct()
+        .filter(e::package.eq(package.as_slice()))
+        .filter(e::module.eq(module.as_str()))
+        .into_boxed();
+
+    if let Some(tag) = tag {
+        let instantiation = bcs::to_bytes(&tag.type_params)
+            .context("Failed to serialize type parameters for event filter")?;
+
+        query = query
+            .filter(e::name.eq(tag.name.as_str()))
+            .filter(e::instantiation.eq(instantiation));
+    }
+
+    let results: Vec<i64> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(paginate(
+            page,
+            "ev_struct_inst",
+            e::tx_sequence_number,
+            query,
+        ))
+        .await
+        .context("Failed to fetch transaction sequence numbers")?;
+
+    Ok(results.into_iter().map(|tx| tx as u64).collect())
+}
+
+/// Look up the sequence number of the transaction with the given `digest`. The transaction must
+/// exist, and will be treated as the only candidate for containing events.
+async fn by_digest(ctx: &Context, digest: TransactionDigest) -> Result<Vec<u64>, RpcError<Error>> {
+    use tx_digests::dsl as d;
+
+    let query = d::tx_digests
+        .select(d::tx_sequence_number)
+        .filter(d::tx_digest.eq(digest.inner().to_vec()));
+
+    let results: Vec<i64> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(query)
+        .await
+        .context("Failed to fetch transaction sequence number")?;
+
+    let Some(tx) = results.into_iter().next() else {
+        return Err(invalid_params(Error::NotFound(digest)));
+    };
+
+    Ok(vec![tx as u64])
+}
+
+/// Modify `query` to be paginated according to `page`, using `tx_sequence_number` as the column
+/// containing the sequence number. The query is also modified to limit results returned by the
+/// reader low-watermark from the `watermarks` table (using the `cp_sequence_numbers` table to
+/// translate a checkpoint bound into a transaction sequence number bound).
+///
+/// Unlike transaction pagination, the bound from the cursor is inclusive, because the transaction
+/// the cursor points into may contain further events. That transaction may contribute no further
+/// events to the page, and every other transaction contributes at least one, so the query fetches
+/// two more transactions than the limit, to determine if there is a next page.
+fn paginate<'q, TX, ST, QS>(
+    page: &Page<Cursor>,
+    pipeline: &'static str,
+    tx_sequence_number: TX,
+    mut query: BoxedSelectStatement<'q, ST, FromClause<QS>, Pg>,
+) -> BoxedSelectStatement<'q, ST, FromClause<QS>, Pg>
+where
+    QS: QuerySource,
+    TX: Copy + Send + Sync + 'q,
+    TX: ValidGrouping<()> + QueryFragment<Pg>,
+    TX: Column<Table = QS> + AppearsOnTable<QS>,
+    TX: ExpressionMethods + Expression<SqlType = SqlBigInt>,
+    TX::IsAggregate: MixedAggregates<Never, Output = No>,
+{
+    query = query.filter(tx_sequence_number.ge(sql!(as SqlBigInt,
+        "COALESCE(
+            (
+                SELECT
+                    MAX(tx_lo)
+                FROM
+                    watermarks w
+                INNER JOIN
+                    cp_sequence_numbers c
+                ON
+                    w.reader_lo = c.cp_sequence_number
+                WHERE
+                    w.pipeline IN ({Text}, 'tx_digests')
+            ),
+            0
+        )",
+        pipeline,
+    )));
+
+    if let Some(JsonCursor(EventCursor { tx, .. })) = page.cursor {
+        if page.descending {
+            query = query.filter(tx_sequence_number.le(tx as i64));
+        } else {
+            query = query.filter(tx_sequence_number.ge(tx as i64));
+        }
+    }
+
+    if page.descending {
+        query = query.order(tx_sequence_number.desc());
+    } else {
+        query = query.order(tx_sequence_number.asc());
+    }
+
+    query.limit(page.limit + 2)
+}
+
+/// Load the events emitted by the transactions with the given sequence numbers (in the order they
+/// are given), and gather a page of events that match `filter` and come after the cursor in
+/// `page`.
+async fn from_sequence_numbers(
+    ctx: &Context,
+    filter: &EventFilter,
+    page: &Page<Cursor>,
+    tx_sequence_numbers: Vec<u64>,
+) -> Result<Events, RpcError<Error>> {
+    let digests = ctx
+        .pg_loader()
+        .load_many(tx_sequence_numbers.iter().map(|&tx| TxDigestKey(tx)))
+        .await
+        .context("Failed to load transaction digests")?;
+
+    let mut txs = Vec::with_capacity(tx_sequence_numbers.len());
+    for tx in tx_sequence_numbers {
+        let bytes = digests
+            .get(&TxDigestKey(tx))
+            .with_context(|| format!("Missing transaction digest for transaction {tx}"))?
+            .tx_digest
+            .as_slice();
+
+        let digest = TransactionDigest::try_from(bytes)
+            .context("Failed to deserialize transaction digest")?;
+
+        txs.push((tx, digest));
+    }
+
+    let contents = future::join_all(
+        txs.iter()
+            .map(|(_, digest)| ctx.kv_loader().load_one_transaction(*digest)),
+    )
+    .await;
+
+    let limit = page.limit as usize;
+    let mut matches: Vec<(EventCursor, TransactionDigest, u64, Event)> = vec![];
+    'txs: for ((tx, digest), contents) in txs.into_iter().zip(contents) {
+        let contents = contents
+            .context("Failed to fetch transaction from store")?
+            .with_context(|| format!("Missing transaction contents for transaction {digest}"))?;
+
+        let timestamp_ms = contents.timestamp_ms();
+        let mut events: Vec<(u64, Event)> = contents
+            .events()?
+            .into_iter()
+            .enumerate()
+            .map(|(ix, event)| (ix as u64, event))
+            .collect();
+
+        if page.descending {
+            events.reverse();
+        }
+
+        for (ev, event) in events {
+            let cursor = EventCursor { tx, ev };
+            let after_cursor = match &page.cursor {
+                None => true,
+                Some(JsonCursor(c)) if page.descending => cursor < *c,
+                Some(JsonCursor(c)) => cursor > *c,
+            };
+
+            if !after_cursor || !matches_filter(filter, &event) {
+                continue;
+            }
+
+            matches.push((cursor, digest, timestamp_ms, event));
+            if matches.len() > limit {
+                break 'txs;
+            }
+        }
+    }
+
+    let has_next_page = matches.len() > limit;
+    if has_next_page {
+        matches.truncate(limit);
+    }
+
+    let next_cursor = matches
+        .last()
+        .map(|(cursor, _, _, _)| JsonCursor(*cursor).encode())
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    let mut data = Vec::with_capacity(matches.len());
+    for (EventCursor { ev, .. }, digest, timestamp_ms, event) in matches {
+        data.push(event_response(ctx, digest, ev, timestamp_ms, event).await?);
+    }
+
+    Ok(PageResponse {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// Whether `event` satisfies `filter`. The transactions being scanned were selected because they
+/// contain at least one matching event, but they may also contain events that do not match.
+fn matches_filter(filter: &EventFilter, event: &Event) -> bool {
+    use EventFilter as F;
+    match filter {
+        F::All([]) | F::Transaction(_) => true,
+        F::Sender(sender) => &event.sender == sender,
+        F::MoveModule { package, module } => {
+            &event.package_id == package && &event.transaction_module == module
+        }
+        F::MoveEventModule { package, module } => {
+            ObjectID::from(event.type_.address) == *package && &event.type_.module == module
+        }
+        F::MoveEventType(tag) => &event.type_ == tag,
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sui_json_rpc_types::{Page, SuiEvent};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::digests::TransactionDigest;
+
+use self::filter::EventFilter;
+
+use crate::{context::Context, error::InternalContext};
+
+use super::rpc_module::RpcModule;
+
+mod error;
+mod filter;
+mod response;
+
+#[open_rpc(namespace = "sui", tag = "Events API")]
+#[rpc(server, namespace = "sui")]
+trait EventsApi {
+    /// Fetch the events emitted by a transaction, identified by its transaction digest.
+    #[method(name = "getEvents")]
+    async fn get_events(
+        &self,
+        /// The digest of the transaction whose events are being fetched.
+        transaction_digest: TransactionDigest,
+    ) -> RpcResult<Vec<SuiEvent>>;
+}
+
+#[open_rpc(namespace = "suix", tag = "Query Events API")]
+#[rpc(server, namespace = "suix")]
+trait QueryEventsApi {
+    /// Query events based on their properties (sender, emitting module, type, etc). Returns a
+    /// paginated list of events.
+    ///
+    /// If a cursor is provided, the query will start from the event after the one pointed to by
+    /// this cursor, otherwise pagination starts from the first event that meets the query
+    /// criteria.
+    ///
+    /// The definition of "first" event is changed by the `descending_order` parameter, which is
+    /// optional, and defaults to false, meaning that the oldest event is shown first.
+    ///
+    /// The size of each page is controlled by the `limit` parameter.
+    #[method(name = "queryEvents")]
+    async fn query_events(
+        &self,
+        /// The event query criteria.
+        query: EventFilter,
+        /// Cursor to start paginating from.
+        cursor: Option<String>,
+        /// Maximum number of events to return per page.
+        limit: Option<usize>,
+        /// Order of results, defaulting to ascending order (false), by sequence on-chain.
+        descending_order: Option<bool>,
+    ) -> RpcResult<Page<SuiEvent, String>>;
+}
+
+pub(crate) struct Events(pub Context);
+
+pub(crate) struct QueryEvents(pub Context);
+
+#[async_trait::async_trait]
+impl EventsApiServer for Events {
+    async fn get_events(&self, transaction_digest: TransactionDigest) -> RpcResult<Vec<SuiEvent>> {
+        let Self(ctx) = self;
+        Ok(response::transaction_events(ctx, transaction_digest)
+            .await
+            .with_internal_context(|| {
+                format!("Failed to get events for transaction {transaction_digest}")
+            })?)
+    }
+}
+
+#[async_trait::async_trait]
+impl QueryEventsApiServer for QueryEvents {
+    async fn query_events(
+        &self,
+        query: EventFilter,
+        cursor: Option<String>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<Page<SuiEvent, String>> {
+        let Self(ctx) = self;
+
+        let Page {
+            data,
+            next_cursor,
+            has_next_page,
+        } = filter::events(ctx, &query, cursor.clone(), limit, descending_order)
+            .await
+            .with_internal_context(|| format!("Failed to query events with filter {query:?}"))?;
+
+        Ok(Page {
+            data,
+            next_cursor: next_cursor.or(cursor),
+            has_next_page,
+        })
+    }
+}
+
+impl RpcModule for Events {
+    fn schema(&self) -> Module {
+        EventsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+impl RpcModule for QueryEvents {
+    fn schema(&self) -> Module {
+        QueryEventsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
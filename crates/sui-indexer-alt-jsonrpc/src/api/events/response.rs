@@ -0,0 +1,71 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use move_core_types::annotated_value::{MoveDatatypeLayout, MoveTypeLayout};
+use sui_json_rpc_types::SuiEvent;
+use sui_types::{digests::TransactionDigest, event::Event};
+
+use crate::{
+    context::Context,
+    error::{invalid_params, rpc_bail, RpcError},
+};
+
+use super::error::Error;
+
+/// Fetch the events emitted by the transaction identified by `digest`, and convert them into
+/// JSON-RPC responses.
+pub(super) async fn transaction_events(
+    ctx: &Context,
+    digest: TransactionDigest,
+) -> Result<Vec<SuiEvent>, RpcError<Error>> {
+    let tx = ctx
+        .kv_loader()
+        .load_one_transaction(digest)
+        .await
+        .context("Failed to fetch transaction from store")?
+        .ok_or_else(|| invalid_params(Error::NotFound(digest)))?;
+
+    let timestamp_ms = tx.timestamp_ms();
+    let events: Vec<Event> = tx.events()?;
+
+    let mut sui_events = Vec::with_capacity(events.len());
+    for (ix, event) in events.into_iter().enumerate() {
+        sui_events.push(event_response(ctx, digest, ix as u64, timestamp_ms, event).await?);
+    }
+
+    Ok(sui_events)
+}
+
+/// Convert the `ix`-th event emitted by the transaction with digest `digest` into a JSON-RPC
+/// response, resolving its layout using the package resolver.
+pub(super) async fn event_response(
+    ctx: &Context,
+    digest: TransactionDigest,
+    ix: u64,
+    timestamp_ms: u64,
+    event: Event,
+) -> Result<SuiEvent, RpcError<Error>> {
+    let layout = match ctx
+        .package_resolver()
+        .type_layout(event.type_.clone().into())
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to resolve layout for {}",
+                event.type_.to_canonical_display(/* with_prefix */ true)
+            )
+        })? {
+        MoveTypeLayout::Struct(s) => MoveDatatypeLayout::Struct(s),
+        MoveTypeLayout::Enum(e) => MoveDatatypeLayout::Enum(e),
+        _ => rpc_bail!(
+            "Event {ix} from {digest} is not a struct or enum: {}",
+            event.type_.to_canonical_string(/* with_prefix */ true)
+        ),
+    };
+
+    Ok(
+        SuiEvent::try_from(event, digest, ix, Some(timestamp_ms), layout)
+            .with_context(|| format!("Failed to convert Event {ix} from {digest} into response"))?,
+    )
+}
@@ -1,29 +1,38 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Context as _;
-use diesel::{ExpressionMethods, QueryDsl};
+use std::collections::BTreeMap;
 
+use anyhow::Context as _;
+use diesel::{BoolExpressionMethods, ExpressionMethods, JoinOnDsl, QueryDsl};
+use futures::future;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use sui_indexer_alt_schema::schema::kv_epoch_starts;
+use sui_indexer_alt_schema::{
+    objects::StoredOwnerKind,
+    schema::{kv_epoch_starts, obj_info},
+};
+use sui_json_rpc_types::{DelegatedStake, Stake, StakeStatus};
 use sui_open_rpc::Module;
 use sui_open_rpc_macros::open_rpc;
 use sui_types::{
+    base_types::{ObjectID, SuiAddress},
     dynamic_field::{derive_dynamic_field_id, Field},
+    governance::StakedSui,
+    id::ID,
     sui_serde::BigInt,
     sui_system_state::{
-        sui_system_state_inner_v1::SuiSystemStateInnerV1,
+        sui_system_state_inner_v1::{SuiSystemStateInnerV1, ValidatorV1},
         sui_system_state_inner_v2::SuiSystemStateInnerV2,
-        sui_system_state_summary::SuiSystemStateSummary, SuiSystemStateTrait,
-        SuiSystemStateWrapper,
+        sui_system_state_summary::SuiSystemStateSummary,
+        PoolTokenExchangeRate, SuiSystemStateTrait, SuiSystemStateWrapper, ValidatorWrapper,
     },
-    TypeTag, SUI_SYSTEM_STATE_OBJECT_ID,
+    MoveTypeTagTrait, TypeTag, SUI_SYSTEM_STATE_OBJECT_ID,
 };
 
 use crate::{
     context::Context,
-    data::objects::load_latest_deserialized,
-    error::{rpc_bail, RpcError},
+    data::objects::{load_latest, load_latest_deserialized, load_live},
+    error::{invalid_params, rpc_bail, InternalContext, RpcError},
 };
 
 use super::rpc_module::RpcModule;
@@ -38,10 +47,37 @@ trait GovernanceApi {
     /// Return a summary of the latest version of the Sui System State object (0x5), on-chain.
     #[method(name = "getLatestSuiSystemState")]
     async fn get_latest_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary>;
+
+    /// Return the stakes owned by an address, grouped by the staking pool they belong to. Stakes
+    /// that have been active for at least one epoch include an estimate of their rewards so far.
+    #[method(name = "getStakes")]
+    async fn get_stakes(
+        &self,
+        /// The owner's Sui address
+        owner: SuiAddress,
+    ) -> RpcResult<Vec<DelegatedStake>>;
+
+    /// Return the stakes with the given IDs, grouped by the staking pool they belong to. If a
+    /// stake has been withdrawn, its status will be `Unstaked`.
+    #[method(name = "getStakesByIds")]
+    async fn get_stakes_by_ids(
+        &self,
+        /// The IDs of the StakedSui objects to query
+        staked_sui_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<DelegatedStake>>;
 }
 
 pub(crate) struct Governance(pub Context);
 
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Object {0} not found")]
+    NotFound(ObjectID),
+
+    #[error("Object {0} is not a StakedSui")]
+    NotAStake(ObjectID),
+}
+
 #[async_trait::async_trait]
 impl GovernanceApiServer for Governance {
     async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>> {
@@ -51,6 +87,19 @@ impl GovernanceApiServer for Governance {
     async fn get_latest_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary> {
         Ok(latest_sui_system_state_response(&self.0).await?)
     }
+
+    async fn get_stakes(&self, owner: SuiAddress) -> RpcResult<Vec<DelegatedStake>> {
+        Ok(stakes_response(&self.0, owner)
+            .await
+            .with_internal_context(|| format!("Failed to fetch stakes for {owner}"))?)
+    }
+
+    async fn get_stakes_by_ids(
+        &self,
+        staked_sui_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<DelegatedStake>> {
+        Ok(stakes_by_ids_response(&self.0, staked_sui_ids).await?)
+    }
 }
 
 impl RpcModule for Governance {
@@ -64,7 +113,7 @@ impl RpcModule for Governance {
 }
 
 /// Load data and generate response for `getReferenceGasPrice`.
-async fn rgp_response(ctx: &Context) -> Result<BigInt<u64>, RpcError> {
+async fn rgp_response(ctx: &Context) -> Result<BigInt<u64>, RpcError<Error>> {
     use kv_epoch_starts::dsl as e;
 
     let mut conn = ctx
@@ -88,7 +137,7 @@ async fn rgp_response(ctx: &Context) -> Result<BigInt<u64>, RpcError> {
 /// Load data and generate response for `getLatestSuiSystemState`.
 async fn latest_sui_system_state_response(
     ctx: &Context,
-) -> Result<SuiSystemStateSummary, RpcError> {
+) -> Result<SuiSystemStateSummary, RpcError<Error>> {
     let wrapper: SuiSystemStateWrapper = load_latest_deserialized(ctx, SUI_SYSTEM_STATE_OBJECT_ID)
         .await
         .context("Failed to fetch system state wrapper object")?;
@@ -114,3 +163,288 @@ async fn latest_sui_system_state_response(
         v => rpc_bail!("Unexpected inner system state version: {v}"),
     })
 }
+
+/// Load data and generate response for `getStakes`.
+async fn stakes_response(
+    ctx: &Context,
+    owner: SuiAddress,
+) -> Result<Vec<DelegatedStake>, RpcError<Error>> {
+    let ids = staked_sui_ids(ctx, owner).await?;
+    let stake_futures = ids.iter().map(|id| load_live(ctx, *id));
+
+    let mut stakes = vec![];
+    for (r, id) in future::join_all(stake_futures).await.into_iter().zip(ids) {
+        // The stake may have been withdrawn since its ID was fetched, in which case it is skipped.
+        let Some(object) = r.with_context(|| format!("Failed to load StakedSui {id}"))? else {
+            continue;
+        };
+
+        let stake = StakedSui::try_from(&object)
+            .with_context(|| format!("Failed to deserialize StakedSui {id}"))?;
+
+        stakes.push((stake, true));
+    }
+
+    delegated_stakes(ctx, stakes).await
+}
+
+/// Load data and generate response for `getStakesByIds`.
+async fn stakes_by_ids_response(
+    ctx: &Context,
+    ids: Vec<ObjectID>,
+) -> Result<Vec<DelegatedStake>, RpcError<Error>> {
+    let stake_futures = ids.iter().map(|id| stake_by_id(ctx, *id));
+
+    let stakes = future::join_all(stake_futures)
+        .await
+        .into_iter()
+        .zip(ids)
+        .map(|(r, id)| r.with_internal_context(|| format!("Failed to fetch stake {id}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    delegated_stakes(ctx, stakes)
+        .await
+        .internal_context("Failed to fetch delegated stakes")
+}
+
+/// Load the StakedSui object at `id`, and whether it is still live. A StakedSui object that is no
+/// longer live has been withdrawn, and is returned as it was before it was withdrawn.
+async fn stake_by_id(ctx: &Context, id: ObjectID) -> Result<(StakedSui, bool), RpcError<Error>> {
+    let (object, exists) = if let Some(object) = load_live(ctx, id)
+        .await
+        .context("Failed to load live object")?
+    {
+        (object, true)
+    } else {
+        let object = load_latest(ctx, id)
+            .await
+            .context("Failed to load latest object")?
+            .ok_or_else(|| invalid_params(Error::NotFound(id)))?;
+
+        (object, false)
+    };
+
+    let stake = StakedSui::try_from(&object).map_err(|_| invalid_params(Error::NotAStake(id)))?;
+    Ok((stake, exists))
+}
+
+/// Fetch the IDs of all the StakedSui objects owned by `owner`.
+async fn staked_sui_ids(
+    ctx: &Context,
+    owner: SuiAddress,
+) -> Result<Vec<ObjectID>, RpcError<Error>> {
+    use obj_info::dsl as o;
+
+    let (candidates, newer) = diesel::alias!(obj_info as candidates, obj_info as newer);
+
+    macro_rules! candidates {
+        ($($field:ident),*) => {
+            candidates.fields(($(o::$field),*))
+        };
+    }
+
+    macro_rules! newer {
+        ($($field:ident),*) => {
+            newer.fields(($(o::$field),*))
+        };
+    }
+
+    let type_ = StakedSui::type_();
+    let query = candidates
+        .select(candidates!(object_id))
+        .left_join(
+            newer.on(candidates!(object_id)
+                .eq(newer!(object_id))
+                .and(candidates!(cp_sequence_number).lt(newer!(cp_sequence_number)))),
+        )
+        .filter(newer!(object_id).is_null())
+        .filter(candidates!(owner_kind).eq(StoredOwnerKind::Address))
+        .filter(candidates!(owner_id).eq(owner.to_inner()))
+        .filter(candidates!(package).eq(type_.address.into_bytes()))
+        .filter(candidates!(module).eq(type_.module.as_str()))
+        .filter(candidates!(name).eq(type_.name.as_str()));
+
+    let results: Vec<Vec<u8>> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(query)
+        .await
+        .context("Failed to fetch StakedSui object info")?;
+
+    Ok(results
+        .into_iter()
+        .map(ObjectID::from_bytes)
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to deserialize Object IDs")?)
+}
+
+/// Group `stakes` by the staking pool they belong to, and calculate their status (and estimated
+/// rewards) based on the exchange rates of their staking pool. Each stake is accompanied by a flag
+/// indicating whether it still exists (has not been withdrawn).
+async fn delegated_stakes(
+    ctx: &Context,
+    stakes: Vec<(StakedSui, bool)>,
+) -> Result<Vec<DelegatedStake>, RpcError<Error>> {
+    if stakes.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut pools: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for (stake, exists) in stakes {
+        pools
+            .entry(stake.pool_id())
+            .or_default()
+            .push((stake, exists));
+    }
+
+    let summary = latest_sui_system_state_response(ctx)
+        .await
+        .internal_context("Failed to fetch latest system state")?;
+
+    let mut delegated_stakes = Vec::with_capacity(pools.len());
+    for (pool_id, stakes) in pools {
+        let Some((validator_address, exchange_rates_id)) = pool_validator(ctx, &summary, pool_id)
+            .await
+            .with_internal_context(|| format!("Failed to fetch validator for pool {pool_id}"))?
+        else {
+            rpc_bail!("Cannot find validator for staking pool {pool_id}");
+        };
+
+        let current_rate = exchange_rate(ctx, exchange_rates_id, summary.epoch).await?;
+
+        let mut delegations = Vec::with_capacity(stakes.len());
+        for (stake, exists) in stakes {
+            let status = if !exists {
+                StakeStatus::Unstaked
+            } else if summary.epoch >= stake.activation_epoch() {
+                let estimated_reward = if let Some(current_rate) = &current_rate {
+                    let stake_rate =
+                        exchange_rate(ctx, exchange_rates_id, stake.activation_epoch())
+                            .await?
+                            .unwrap_or_default();
+
+                    let estimated_reward = ((stake_rate.rate() / current_rate.rate()) - 1.0)
+                        * stake.principal() as f64;
+
+                    estimated_reward.round().max(0.0) as u64
+                } else {
+                    0
+                };
+
+                StakeStatus::Active { estimated_reward }
+            } else {
+                StakeStatus::Pending
+            };
+
+            delegations.push(Stake {
+                staked_sui_id: stake.id(),
+                stake_request_epoch: stake.request_epoch(),
+                stake_active_epoch: stake.activation_epoch(),
+                principal: stake.principal(),
+                status,
+            });
+        }
+
+        delegated_stakes.push(DelegatedStake {
+            validator_address,
+            staking_pool: pool_id,
+            stakes: delegations,
+        });
+    }
+
+    Ok(delegated_stakes)
+}
+
+/// Find the address of the validator that operates the staking pool at `pool_id`, and the ID of
+/// the pool's exchange rate table. The pool is first looked for among the active validators in the
+/// system state `summary` and then among the inactive pools.
+async fn pool_validator(
+    ctx: &Context,
+    summary: &SuiSystemStateSummary,
+    pool_id: ObjectID,
+) -> Result<Option<(SuiAddress, ObjectID)>, RpcError<Error>> {
+    if let Some(validator) = summary
+        .active_validators
+        .iter()
+        .find(|v| v.staking_pool_id == pool_id)
+    {
+        return Ok(Some((validator.sui_address, validator.exchange_rates_id)));
+    }
+
+    let wrapper_id = derive_dynamic_field_id(
+        summary.inactive_pools_id,
+        &ID::get_type_tag(),
+        &bcs::to_bytes(&pool_id).context("Failed to serialize pool ID")?,
+    )
+    .context("Failed to derive inactive validator field ID")?;
+
+    let Some(wrapper) = load_live(ctx, wrapper_id)
+        .await
+        .context("Failed to fetch inactive validator wrapper")?
+    else {
+        return Ok(None);
+    };
+
+    let wrapper: Field<ID, ValidatorWrapper> = bcs::from_bytes(
+        wrapper
+            .data
+            .try_as_move()
+            .context("Inactive validator wrapper is not a Move object")?
+            .contents(),
+    )
+    .context("Failed to deserialize inactive validator wrapper")?;
+
+    let versioned = wrapper.value.inner;
+    let inner_id = derive_dynamic_field_id(
+        versioned.id.id.bytes,
+        &TypeTag::U64,
+        &bcs::to_bytes(&versioned.version).context("Failed to serialize validator version")?,
+    )
+    .context("Failed to derive inner validator field ID")?;
+
+    let validator = match versioned.version {
+        1 => load_latest_deserialized::<Field<u64, ValidatorV1>>(ctx, inner_id)
+            .await
+            .context("Failed to fetch inner validator object")?
+            .value
+            .into_sui_validator_summary(),
+        v => rpc_bail!("Unexpected inner validator version: {v}"),
+    };
+
+    Ok(Some((validator.sui_address, validator.exchange_rates_id)))
+}
+
+/// Fetch the exchange rate for `epoch` from the exchange rate table at `exchange_rates_id`, if it
+/// exists.
+async fn exchange_rate(
+    ctx: &Context,
+    exchange_rates_id: ObjectID,
+    epoch: u64,
+) -> Result<Option<PoolTokenExchangeRate>, RpcError<Error>> {
+    let id = derive_dynamic_field_id(
+        exchange_rates_id,
+        &TypeTag::U64,
+        &bcs::to_bytes(&epoch).context("Failed to serialize epoch")?,
+    )
+    .context("Failed to derive exchange rate field ID")?;
+
+    let Some(object) = load_live(ctx, id)
+        .await
+        .with_context(|| format!("Failed to fetch exchange rate for epoch {epoch}"))?
+    else {
+        return Ok(None);
+    };
+
+    let field: Field<u64, PoolTokenExchangeRate> = bcs::from_bytes(
+        object
+            .data
+            .try_as_move()
+            .context("Exchange rate is not a Move object")?
+            .contents(),
+    )
+    .context("Failed to deserialize exchange rate")?;
+
+    Ok(Some(field.value))
+}
@@ -1,14 +1,28 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use sui_types::base_types::ObjectID;
+
 #[derive(thiserror::Error, Debug)]
 pub(super) enum Error {
     #[error("Invalid Move identifier: {0:?}")]
     BadIdentifier(String),
 
+    #[error("Module {0:?} not found")]
+    ModuleNotFound(String),
+
     #[error("{0}")]
     NotFound(sui_package_resolver::error::Error),
 
+    #[error("Object {0} is not a package")]
+    NotAPackage(ObjectID),
+
+    #[error("Package {0} not found")]
+    PackageNotFound(ObjectID),
+
+    #[error("Struct {0:?} not found")]
+    StructNotFound(String),
+
     #[error("Type resolution limit reached: {0}")]
     ResolutionLimit(sui_package_resolver::error::Error),
 }
@@ -1,8 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
+
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use sui_json_rpc_types::SuiMoveNormalizedFunction;
+use sui_json_rpc_types::{
+    MoveFunctionArgType, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
+    SuiMoveNormalizedStruct,
+};
 use sui_open_rpc::Module;
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::ObjectID;
@@ -24,6 +29,40 @@ trait MoveApi {
         module_name: String,
         function_name: String,
     ) -> RpcResult<SuiMoveNormalizedFunction>;
+
+    /// Return structured representations of all the modules in the given package.
+    #[method(name = "getNormalizedMoveModulesByPackage")]
+    async fn get_normalized_move_modules_by_package(
+        &self,
+        package: ObjectID,
+    ) -> RpcResult<BTreeMap<String, SuiMoveNormalizedModule>>;
+
+    /// Return a structured representation of a Move module.
+    #[method(name = "getNormalizedMoveModule")]
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> RpcResult<SuiMoveNormalizedModule>;
+
+    /// Return a structured representation of a Move struct.
+    #[method(name = "getNormalizedMoveStruct")]
+    async fn get_normalized_move_struct(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        struct_name: String,
+    ) -> RpcResult<SuiMoveNormalizedStruct>;
+
+    /// Return the argument types of a Move function, classified by whether they are pure values,
+    /// or objects (and if so, how they are passed).
+    #[method(name = "getMoveFunctionArgTypes")]
+    async fn get_move_function_arg_types(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+    ) -> RpcResult<Vec<MoveFunctionArgType>>;
 }
 
 pub(crate) struct MoveUtils(pub Context);
@@ -39,6 +78,43 @@ impl MoveApiServer for MoveUtils {
         let Self(ctx) = self;
         Ok(response::function(ctx, package, &module_name, &function_name).await?)
     }
+
+    async fn get_normalized_move_modules_by_package(
+        &self,
+        package: ObjectID,
+    ) -> RpcResult<BTreeMap<String, SuiMoveNormalizedModule>> {
+        let Self(ctx) = self;
+        Ok(response::modules(ctx, package).await?)
+    }
+
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> RpcResult<SuiMoveNormalizedModule> {
+        let Self(ctx) = self;
+        Ok(response::module(ctx, package, &module_name).await?)
+    }
+
+    async fn get_normalized_move_struct(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        struct_name: String,
+    ) -> RpcResult<SuiMoveNormalizedStruct> {
+        let Self(ctx) = self;
+        Ok(response::struct_(ctx, package, &module_name, &struct_name).await?)
+    }
+
+    async fn get_move_function_arg_types(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+    ) -> RpcResult<Vec<MoveFunctionArgType>> {
+        let Self(ctx) = self;
+        Ok(response::function_arg_types(ctx, package, &module, &function).await?)
+    }
 }
 
 impl RpcModule for MoveUtils {
@@ -1,17 +1,25 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::anyhow;
-use move_binary_format::file_format::{Ability, AbilitySet, Visibility};
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context as _};
+use move_binary_format::{
+    binary_config::BinaryConfig,
+    file_format::{Ability, AbilitySet, Visibility},
+    normalized,
+};
 use sui_json_rpc_types::{
-    SuiMoveAbility, SuiMoveAbilitySet, SuiMoveNormalizedFunction, SuiMoveNormalizedType,
-    SuiMoveVisibility,
+    MoveFunctionArgType, ObjectValueKind, SuiMoveAbility, SuiMoveAbilitySet,
+    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct,
+    SuiMoveNormalizedType, SuiMoveVisibility,
 };
 use sui_package_resolver::{FunctionDef, OpenSignature, OpenSignatureBody, Reference};
-use sui_types::{base_types::ObjectID, Identifier};
+use sui_types::{base_types::ObjectID, move_package::normalize_modules, object::Data, Identifier};
 
 use crate::{
     context::Context,
+    data::objects::load_latest,
     error::{invalid_params, RpcError},
 };
 
@@ -24,6 +32,109 @@ pub(super) async fn function(
     module: &str,
     name: &str,
 ) -> Result<SuiMoveNormalizedFunction, RpcError<Error>> {
+    let sig = function_signature(ctx, package, module, name).await?;
+    Ok(normalized_function(&sig))
+}
+
+/// Load a function's signature, and classify each of its parameters by whether they accept a pure
+/// value or an object (and if so, how that object is passed).
+pub(super) async fn function_arg_types(
+    ctx: &Context,
+    package: ObjectID,
+    module: &str,
+    name: &str,
+) -> Result<Vec<MoveFunctionArgType>, RpcError<Error>> {
+    let sig = function_signature(ctx, package, module, name).await?;
+    Ok(sig.parameters.iter().map(arg_type).collect())
+}
+
+/// Load all the modules in `package` and convert them into a JSON-RPC response.
+pub(super) async fn modules(
+    ctx: &Context,
+    package: ObjectID,
+) -> Result<BTreeMap<String, SuiMoveNormalizedModule>, RpcError<Error>> {
+    Ok(normalized_modules(ctx, package)
+        .await?
+        .iter()
+        .map(|(name, module)| (name.clone(), module.into()))
+        .collect())
+}
+
+/// Load the module called `name` from `package` and convert it into a JSON-RPC response.
+pub(super) async fn module(
+    ctx: &Context,
+    package: ObjectID,
+    name: &str,
+) -> Result<SuiMoveNormalizedModule, RpcError<Error>> {
+    let modules = normalized_modules(ctx, package).await?;
+    let module = modules
+        .get(name)
+        .ok_or_else(|| invalid_params(Error::ModuleNotFound(name.to_owned())))?;
+
+    Ok(module.into())
+}
+
+/// Load the struct called `name` from `module` in `package` and convert it into a JSON-RPC
+/// response.
+pub(super) async fn struct_(
+    ctx: &Context,
+    package: ObjectID,
+    module: &str,
+    name: &str,
+) -> Result<SuiMoveNormalizedStruct, RpcError<Error>> {
+    if !Identifier::is_valid(name) {
+        return Err(invalid_params(Error::BadIdentifier(name.to_owned())));
+    }
+
+    let modules = normalized_modules(ctx, package).await?;
+    let module = modules
+        .get(module)
+        .ok_or_else(|| invalid_params(Error::ModuleNotFound(module.to_owned())))?;
+
+    let struct_ = module
+        .structs
+        .iter()
+        .find(|(struct_name, _)| struct_name.to_string() == name)
+        .ok_or_else(|| invalid_params(Error::StructNotFound(name.to_owned())))?
+        .1;
+
+    Ok((&**struct_).into())
+}
+
+/// Load the package at `package` and produce normalized representations of its modules.
+async fn normalized_modules(
+    ctx: &Context,
+    package: ObjectID,
+) -> Result<BTreeMap<String, normalized::Module<normalized::RcIdentifier>>, RpcError<Error>> {
+    let object = load_latest(ctx, package)
+        .await
+        .context("Failed to load package")?
+        .ok_or_else(|| invalid_params(Error::PackageNotFound(package)))?;
+
+    let Data::Package(package) = &object.data else {
+        return Err(invalid_params(Error::NotAPackage(package)));
+    };
+
+    // We are on the read path, so it's OK to use the maximum supported binary format version.
+    let binary_config = BinaryConfig::with_extraneous_bytes_check(false);
+    let pool = &mut normalized::RcPool::new();
+
+    Ok(normalize_modules(
+        pool,
+        package.serialized_module_map().values(),
+        &binary_config,
+        /* include_code */ false,
+    )
+    .context("Failed to normalize package modules")?)
+}
+
+/// Load the signature of the function `module::name` in `package`, using the package resolver.
+async fn function_signature(
+    ctx: &Context,
+    package: ObjectID,
+    module: &str,
+    name: &str,
+) -> Result<FunctionDef, RpcError<Error>> {
     use Error as E;
 
     if !Identifier::is_valid(module) {
@@ -34,8 +145,7 @@ pub(super) async fn function(
         return Err(invalid_params(E::BadIdentifier(name.to_owned())));
     }
 
-    let sig = ctx
-        .package_resolver()
+    ctx.package_resolver()
         .function_signature(*package, module, name)
         .await
         .map_err(|e| {
@@ -72,9 +182,20 @@ pub(super) async fn function(
                     RpcError::from(anyhow!(e).context("Failed to resolve type layout"))
                 }
             }
-        })?;
+        })
+}
 
-    Ok(normalized_function(&sig))
+/// Classify a function parameter as a pure value, or an object passed by value or reference.
+fn arg_type(sig: &OpenSignature) -> MoveFunctionArgType {
+    use MoveFunctionArgType as A;
+    use ObjectValueKind as K;
+
+    match (&sig.ref_, &sig.body) {
+        (Some(Reference::Immutable), _) => A::Object(K::ByImmutableReference),
+        (Some(Reference::Mutable), _) => A::Object(K::ByMutableReference),
+        (None, OpenSignatureBody::Datatype(_, _)) => A::Object(K::ByValue),
+        (None, _) => A::Pure,
+    }
 }
 
 fn normalized_function(sig: &FunctionDef) -> SuiMoveNormalizedFunction {
@@ -12,7 +12,7 @@ use self::{error::Error, filter::SuiTransactionBlockResponseQuery};
 
 use crate::{
     context::Context,
-    error::{rpc_bail, InternalContext, RpcError},
+    error::{invalid_params, rpc_bail, InternalContext, RpcError},
 };
 
 use super::rpc_module::RpcModule;
@@ -33,6 +33,17 @@ trait TransactionsApi {
         /// Options controlling the output format.
         options: Option<SuiTransactionBlockResponseOptions>,
     ) -> RpcResult<SuiTransactionBlockResponse>;
+
+    /// Fetch multiple transactions by their transaction digests. The response contains the
+    /// transactions in the same order as the digests were supplied in.
+    #[method(name = "multiGetTransactionBlocks")]
+    async fn multi_get_transaction_blocks(
+        &self,
+        /// The digests of the queried transactions.
+        digests: Vec<TransactionDigest>,
+        /// Options controlling the output format.
+        options: Option<SuiTransactionBlockResponseOptions>,
+    ) -> RpcResult<Vec<SuiTransactionBlockResponse>>;
 }
 
 #[open_rpc(namespace = "suix", tag = "Query Transactions API")]
@@ -81,6 +92,35 @@ impl TransactionsApiServer for Transactions {
                 .with_internal_context(|| format!("Failed to get transaction {digest}"))?,
         )
     }
+
+    async fn multi_get_transaction_blocks(
+        &self,
+        digests: Vec<TransactionDigest>,
+        options: Option<SuiTransactionBlockResponseOptions>,
+    ) -> RpcResult<Vec<SuiTransactionBlockResponse>> {
+        let Self(ctx) = self;
+        let config = &ctx.config().transactions;
+        if digests.len() > config.max_multi_get_transactions {
+            return Err(invalid_params(Error::TooManyKeys {
+                requested: digests.len(),
+                max: config.max_multi_get_transactions,
+            })
+            .into());
+        }
+
+        let options = options.unwrap_or_default();
+
+        let tx_futures = digests
+            .iter()
+            .map(|d| response::transaction(ctx, *d, &options));
+
+        Ok(future::join_all(tx_futures)
+            .await
+            .into_iter()
+            .zip(digests)
+            .map(|(r, d)| r.with_internal_context(|| format!("Failed to get transaction {d}")))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
 }
 
 #[async_trait::async_trait]
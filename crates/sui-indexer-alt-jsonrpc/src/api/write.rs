@@ -4,11 +4,14 @@
 use fastcrypto::encoding::Base64;
 use jsonrpsee::{core::RpcResult, http_client::HttpClient, proc_macros::rpc};
 use sui_json_rpc_types::{
-    DryRunTransactionBlockResponse, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions,
 };
 use sui_open_rpc::Module;
 use sui_open_rpc_macros::open_rpc;
-use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::{
+    base_types::SuiAddress, quorum_driver_types::ExecuteTransactionRequestType, sui_serde::BigInt,
+};
 
 use crate::{
     config::NodeConfig,
@@ -36,6 +39,23 @@ pub trait WriteApi {
         request_type: Option<ExecuteTransactionRequestType>,
     ) -> RpcResult<SuiTransactionBlockResponse>;
 
+    /// Runs the transaction in dev-inspect mode. Which allows for nearly any
+    /// transaction (or Move call) with any arguments. Detailed results are
+    /// provided, including both the transaction effects and any return values.
+    #[method(name = "devInspectTransactionBlock")]
+    async fn dev_inspect_transaction_block(
+        &self,
+        sender_address: SuiAddress,
+        /// BCS encoded TransactionKind(as opposed to TransactionData, which include gasBudget and gasPrice)
+        tx_bytes: Base64,
+        /// Gas is not charged, but gas usage is still calculated. Default to use reference gas price
+        gas_price: Option<BigInt<u64>>,
+        /// The epoch to perform the call. Will be set from the system state object if not provided
+        epoch: Option<BigInt<u64>>,
+        /// Additional arguments including gas_budget, gas_objects, gas_sponsor and skip_checks.
+        additional_args: Option<DevInspectArgs>,
+    ) -> RpcResult<DevInspectResults>;
+
     /// Return transaction execution effects including the gas cost summary,
     /// while the effects are not committed to the chain.
     #[method(name = "dryRunTransactionBlock")]
@@ -78,6 +98,26 @@ impl WriteApiServer for Write {
             .map_err(client_error_to_error_object)
     }
 
+    async fn dev_inspect_transaction_block(
+        &self,
+        sender_address: SuiAddress,
+        tx_bytes: Base64,
+        gas_price: Option<BigInt<u64>>,
+        epoch: Option<BigInt<u64>>,
+        additional_args: Option<DevInspectArgs>,
+    ) -> RpcResult<DevInspectResults> {
+        self.0
+            .dev_inspect_transaction_block(
+                sender_address,
+                tx_bytes,
+                gas_price,
+                epoch,
+                additional_args,
+            )
+            .await
+            .map_err(client_error_to_error_object)
+    }
+
     async fn dry_run_transaction_block(
         &self,
         tx_bytes: Base64,
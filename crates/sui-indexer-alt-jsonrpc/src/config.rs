@@ -22,6 +22,12 @@ pub struct RpcConfig {
     /// Configuration for transaction-related RPC methods.
     pub transactions: TransactionsConfig,
 
+    /// Configuration for event-related RPC methods.
+    pub events: EventsConfig,
+
+    /// Configuration for checkpoint-related RPC methods.
+    pub checkpoints: CheckpointsConfig,
+
     /// Configuration for SuiNS related RPC methods.
     pub name_service: NameServiceConfig,
 
@@ -45,6 +51,12 @@ pub struct RpcLayer {
     /// Configuration for transaction-related RPC methods.
     pub transactions: TransactionsLayer,
 
+    /// Configuration for event-related RPC methods.
+    pub events: EventsLayer,
+
+    /// Configuration for checkpoint-related RPC methods.
+    pub checkpoints: CheckpointsLayer,
+
     /// Configuration for SuiNS related RPC methods.
     pub name_service: NameServiceLayer,
 
@@ -108,6 +120,9 @@ pub struct ObjectsLayer {
 
 #[derive(Debug, Clone)]
 pub struct TransactionsConfig {
+    /// The maximum number of keys that can be queried in a single multi-get request.
+    pub max_multi_get_transactions: usize,
+
     /// The default page size limit when querying transactions, if none is provided.
     pub default_page_size: usize,
 
@@ -119,6 +134,47 @@ pub struct TransactionsConfig {
 #[DefaultConfig]
 #[derive(Clone, Default, Debug)]
 pub struct TransactionsLayer {
+    pub max_multi_get_transactions: Option<usize>,
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventsConfig {
+    /// The default page size limit when querying events, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying events. Requesting a page larger than this
+    /// is a user error.
+    pub max_page_size: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct EventsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckpointsConfig {
+    /// The default page size limit when querying checkpoints, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying checkpoints. Requesting a page larger than
+    /// this is a user error.
+    pub max_page_size: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct CheckpointsLayer {
     pub default_page_size: Option<usize>,
     pub max_page_size: Option<usize>,
 
@@ -194,6 +250,8 @@ impl RpcLayer {
         Self {
             objects: ObjectsConfig::default().into(),
             transactions: TransactionsConfig::default().into(),
+            events: EventsConfig::default().into(),
+            checkpoints: CheckpointsConfig::default().into(),
             name_service: NameServiceConfig::default().into(),
             coins: CoinsConfig::default().into(),
             package_resolver: PackageResolverLayer::default(),
@@ -207,6 +265,8 @@ impl RpcLayer {
         RpcConfig {
             objects: self.objects.finish(ObjectsConfig::default()),
             transactions: self.transactions.finish(TransactionsConfig::default()),
+            events: self.events.finish(EventsConfig::default()),
+            checkpoints: self.checkpoints.finish(CheckpointsConfig::default()),
             name_service: self.name_service.finish(NameServiceConfig::default()),
             coins: self.coins.finish(CoinsConfig::default()),
             node: self.node.finish(NodeConfig::default()),
@@ -241,6 +301,29 @@ impl TransactionsLayer {
     pub fn finish(self, base: TransactionsConfig) -> TransactionsConfig {
         check_extra("transactions", self.extra);
         TransactionsConfig {
+            max_multi_get_transactions: self
+                .max_multi_get_transactions
+                .unwrap_or(base.max_multi_get_transactions),
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl EventsLayer {
+    pub fn finish(self, base: EventsConfig) -> EventsConfig {
+        check_extra("events", self.extra);
+        EventsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl CheckpointsLayer {
+    pub fn finish(self, base: CheckpointsConfig) -> CheckpointsConfig {
+        check_extra("checkpoints", self.extra);
+        CheckpointsConfig {
             default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
             max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
         }
@@ -311,6 +394,8 @@ impl Default for RpcConfig {
         Self {
             objects: ObjectsConfig::default(),
             transactions: TransactionsConfig::default(),
+            events: EventsConfig::default(),
+            checkpoints: CheckpointsConfig::default(),
             name_service: NameServiceConfig::default(),
             coins: CoinsConfig::default(),
             node: NodeConfig::default(),
@@ -335,6 +420,25 @@ impl Default for ObjectsConfig {
 }
 
 impl Default for TransactionsConfig {
+    fn default() -> Self {
+        Self {
+            max_multi_get_transactions: 50,
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+impl Default for CheckpointsConfig {
     fn default() -> Self {
         Self {
             default_page_size: 50,
@@ -397,6 +501,27 @@ impl From<ObjectsConfig> for ObjectsLayer {
 
 impl From<TransactionsConfig> for TransactionsLayer {
     fn from(config: TransactionsConfig) -> Self {
+        Self {
+            max_multi_get_transactions: Some(config.max_multi_get_transactions),
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl From<EventsConfig> for EventsLayer {
+    fn from(config: EventsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl From<CheckpointsConfig> for CheckpointsLayer {
+    fn from(config: CheckpointsConfig) -> Self {
         Self {
             default_page_size: Some(config.default_page_size),
             max_page_size: Some(config.max_page_size),
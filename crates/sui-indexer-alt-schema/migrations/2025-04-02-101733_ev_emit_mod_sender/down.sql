@@ -0,0 +1 @@
+DROP INDEX IF EXISTS ev_emit_mod_sender_tx_sequence_number;
@@ -0,0 +1,4 @@
+-- Supports paginating through the transactions that emitted events sent by a given address,
+-- without also filtering by the package or module that emitted them.
+CREATE INDEX IF NOT EXISTS ev_emit_mod_sender_tx_sequence_number
+ON ev_emit_mod (sender, tx_sequence_number);
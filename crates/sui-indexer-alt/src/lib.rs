@@ -107,7 +107,7 @@ pub async fn setup_indexer(
 
     let retry_interval = ingestion.retry_interval();
 
-    let mut indexer = Indexer::new(
+    let mut indexer = Indexer::new_from_pg(
         database_url,
         db_args,
         indexer_args,
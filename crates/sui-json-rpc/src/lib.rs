@@ -11,10 +11,8 @@ use jsonrpsee::RpcModule;
 use metrics::Metrics;
 use metrics::MetricsLayer;
 use prometheus::Registry;
-use sui_core::traffic_controller::metrics::TrafficControllerMetrics;
 use sui_core::traffic_controller::TrafficController;
-use sui_types::traffic_control::PolicyConfig;
-use sui_types::traffic_control::RemoteFirewallConfig;
+use sui_types::traffic_control::ClientIdSource;
 use tokio::runtime::Handle;
 use tokio_util::sync::CancellationToken;
 use tower::ServiceBuilder;
@@ -53,8 +51,8 @@ pub struct JsonRpcServerBuilder {
     module: RpcModule<()>,
     rpc_doc: Project,
     registry: Registry,
-    policy_config: Option<PolicyConfig>,
-    firewall_config: Option<RemoteFirewallConfig>,
+    traffic_controller: Option<Arc<TrafficController>>,
+    client_id_source: Option<ClientIdSource>,
 }
 
 pub fn sui_rpc_doc(version: &str) -> Project {
@@ -74,15 +72,15 @@ impl JsonRpcServerBuilder {
     pub fn new(
         version: &str,
         prometheus_registry: &Registry,
-        policy_config: Option<PolicyConfig>,
-        firewall_config: Option<RemoteFirewallConfig>,
+        traffic_controller: Option<Arc<TrafficController>>,
+        client_id_source: Option<ClientIdSource>,
     ) -> Self {
         Self {
             module: RpcModule::new(()),
             rpc_doc: sui_rpc_doc(version),
             registry: prometheus_registry.clone(),
-            policy_config,
-            firewall_config,
+            traffic_controller,
+            client_id_source,
         }
     }
 
@@ -136,18 +134,8 @@ impl JsonRpcServerBuilder {
         let methods_names = module.method_names().collect::<Vec<_>>();
 
         let metrics = Arc::new(Metrics::new(&self.registry, &methods_names));
-        let traffic_controller_metrics = TrafficControllerMetrics::new(&self.registry);
-        let traffic_controller = self.policy_config.clone().map(|policy| {
-            Arc::new(TrafficController::init(
-                policy,
-                traffic_controller_metrics,
-                self.firewall_config.clone(),
-            ))
-        });
-        let client_id_source = self
-            .policy_config
-            .clone()
-            .map(|policy| policy.client_id_source);
+        let traffic_controller = self.traffic_controller.clone();
+        let client_id_source = self.client_id_source.clone();
 
         let metrics_clone = metrics.clone();
         let middleware = ServiceBuilder::new()
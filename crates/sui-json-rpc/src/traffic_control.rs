@@ -6,10 +6,15 @@ use futures::FutureExt;
 use jsonrpsee::server::middleware::rpc::RpcServiceT;
 use jsonrpsee::types::{ErrorCode, ErrorObject, Id};
 use jsonrpsee::MethodResponse;
+use serde::Deserialize;
 use std::net::IpAddr;
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 use std::{net::SocketAddr, sync::Arc};
-use sui_core::traffic_controller::{parse_ip, policies::TrafficTally, TrafficController};
+use sui_core::traffic_controller::{
+    parse_ip,
+    policies::{RequestCost, TrafficTally},
+    TrafficController,
+};
 use sui_json_rpc_api::TRANSACTION_EXECUTION_CLIENT_ERROR_CODE;
 use sui_types::traffic_control::ClientIdSource;
 use sui_types::traffic_control::Weight;
@@ -17,6 +22,14 @@ use tracing::error;
 
 const TOO_MANY_REQUESTS_MSG: &str = "Too many requests";
 
+/// Methods whose responses may include the effects of a transaction they executed or dry-ran, and
+/// so the gas it used.
+const GAS_CHARGING_METHODS: &[&str] = &[
+    "sui_executeTransactionBlock",
+    "sui_dryRunTransactionBlock",
+    "sui_devInspectTransactionBlock",
+];
+
 #[derive(Clone)]
 pub struct TrafficControllerService<S> {
     inner: S,
@@ -49,8 +62,18 @@ where
                 if let Err(response) = handle_traffic_req(&traffic_controller, &client).await {
                     response
                 } else {
+                    // Parsing the gas out of the response is only worth it if a policy uses it.
+                    let charges_gas = GAS_CHARGING_METHODS.contains(&req.method_name())
+                        && traffic_controller.weighs_gas();
+                    let start = Instant::now();
                     let response = service.call(req).await;
-                    handle_traffic_resp(&traffic_controller, client, &response);
+                    handle_traffic_resp(
+                        &traffic_controller,
+                        client,
+                        &response,
+                        start.elapsed(),
+                        charges_gas,
+                    );
                     response
                 }
             } else {
@@ -79,8 +102,15 @@ fn handle_traffic_resp(
     traffic_controller: &TrafficController,
     client: Option<IpAddr>,
     response: &MethodResponse,
+    execution_time: Duration,
+    charges_gas: bool,
 ) {
     let error = response.as_error_code().map(ErrorCode::from);
+    let gas = if charges_gas && response.is_success() {
+        computation_cost(response.as_result())
+    } else {
+        0
+    };
     traffic_controller.tally(TrafficTally {
         direct: client,
         through_fullnode: None,
@@ -97,10 +127,47 @@ fn handle_traffic_resp(
         // suitable rpc provider (or run their own). Later we may want
         // to provide a weight distribution based on the method being called.
         spam_weight: Weight::one(),
+        cost: RequestCost {
+            execution_time,
+            gas,
+            response_bytes: response.as_result().len() as u64,
+        },
         timestamp: SystemTime::now(),
     });
 }
 
+/// Extract the computation cost of the transaction whose effects are in `response`, a JSON-RPC
+/// response from one of the [GAS_CHARGING_METHODS], or zero if it doesn't include effects.
+fn computation_cost(response: &str) -> u64 {
+    #[derive(Deserialize)]
+    struct Response {
+        result: MethodResult,
+    }
+
+    #[derive(Deserialize)]
+    struct MethodResult {
+        effects: Option<Effects>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Effects {
+        gas_used: GasUsed,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct GasUsed {
+        computation_cost: String,
+    }
+
+    serde_json::from_str::<Response>(response)
+        .ok()
+        .and_then(|r| r.result.effects)
+        .and_then(|e| e.gas_used.computation_cost.parse().ok())
+        .unwrap_or(0)
+}
+
 // TODO: refine error matching here
 fn normalize(err: ErrorCode) -> Weight {
     match err {
@@ -0,0 +1,624 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::keystore::{
+    read_aliases, validate_alias, write_aliases, AccountKeystore, Alias, FileBasedKeystore,
+};
+use crate::random_names::random_name;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, bail, ensure, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::collections::{BTreeMap, HashSet};
+use std::ffi::OsString;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, Signature, SuiKeyPair};
+use zeroize::Zeroizing;
+
+/// Environment variable holding the passphrase of an encrypted keystore, used when no other
+/// passphrase provider has been set.
+pub const KEYSTORE_PASSPHRASE_ENV_VAR: &str = "SUI_KEYSTORE_PASSPHRASE";
+
+const ENCRYPTED_KEYSTORE_VERSION: u32 = 1;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+/// Why an encrypted keystore needs its passphrase.
+pub enum PassphraseRequest<'a> {
+    /// To unlock the key of this address.
+    Unlock(&'a SuiAddress),
+    /// To encrypt the first key of a new keystore, which sets the passphrase of the keystore.
+    New,
+}
+
+/// Supplies the passphrase of an encrypted keystore, for example by prompting the user.
+pub type PassphraseProvider =
+    Arc<dyn Fn(PassphraseRequest<'_>) -> Result<String, anyhow::Error> + Send + Sync>;
+
+/// Parameters of the scrypt key derivation function used to derive a key encryption key from the
+/// passphrase.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            log_n: 17,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl KdfParams {
+    /// Parameters that make key derivation fast, and so offer little protection against brute
+    /// force. Only meant for tests.
+    pub fn insecure_for_tests() -> Self {
+        Self {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// A private key encrypted at rest: `flag || privkey` sealed with AES-256-GCM, under a key derived
+/// from the passphrase and a salt unique to this key. The public key is authenticated as
+/// associated data, so a sealed key cannot be moved to another address.
+#[derive(Serialize, Deserialize, Clone)]
+struct SealedKey {
+    public_key: String,
+    kdf: KdfParams,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// The contents of an encrypted keystore file.
+#[derive(Serialize, Deserialize)]
+struct EncryptedKeystoreFile {
+    version: u32,
+    keys: Vec<SealedKey>,
+}
+
+struct EncryptedKey {
+    public_key: PublicKey,
+    sealed: SealedKey,
+    /// The decrypted key, once it has been unlocked.
+    unlocked: OnceLock<SuiKeyPair>,
+}
+
+/// A keystore that keeps its keys encrypted under a passphrase, both on disk and in memory until
+/// each key is first used. Public keys and aliases are stored in the clear, so the keystore can
+/// list its addresses without the passphrase.
+///
+/// All keys in a keystore share one passphrase, which is requested from the passphrase provider
+/// the first time a key needs unlocking, and remembered afterwards.
+pub struct EncryptedFileKeystore {
+    keys: BTreeMap<SuiAddress, EncryptedKey>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+    path: Option<PathBuf>,
+    kdf: KdfParams,
+    passphrase: RwLock<Option<Zeroizing<String>>>,
+    passphrase_provider: PassphraseProvider,
+}
+
+impl SealedKey {
+    fn seal(keypair: &SuiKeyPair, passphrase: &str, kdf: KdfParams) -> Result<Self, anyhow::Error> {
+        let mut salt = [0u8; SALT_LENGTH];
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let public_key = keypair.public();
+        let cipher = cipher(passphrase, &salt, &kdf)?;
+        let plaintext = Zeroizing::new(keypair.to_bytes());
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: public_key.as_ref(),
+                },
+            )
+            .map_err(|_| {
+                anyhow!(
+                    "Cannot encrypt key for address: [{}]",
+                    SuiAddress::from(&public_key)
+                )
+            })?;
+
+        Ok(Self {
+            public_key: public_key.encode_base64(),
+            kdf,
+            salt: Base64::encode(salt),
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    fn open(&self, public_key: &PublicKey, passphrase: &str) -> Result<SuiKeyPair, anyhow::Error> {
+        let salt = Base64::decode(&self.salt).map_err(|e| anyhow!("Invalid key salt: {e}"))?;
+        let nonce = Base64::decode(&self.nonce).map_err(|e| anyhow!("Invalid key nonce: {e}"))?;
+        let ciphertext =
+            Base64::decode(&self.ciphertext).map_err(|e| anyhow!("Invalid key ciphertext: {e}"))?;
+        ensure!(nonce.len() == NONCE_LENGTH, "Invalid key nonce length");
+
+        let cipher = cipher(passphrase, &salt, &self.kdf)?;
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: &ciphertext,
+                        aad: public_key.as_ref(),
+                    },
+                )
+                .map_err(|_| anyhow!("Incorrect passphrase for the keystore"))?,
+        );
+
+        let keypair = SuiKeyPair::from_bytes(&plaintext)
+            .map_err(|e| anyhow!("Invalid decrypted key: {e}"))?;
+        ensure!(
+            &keypair.public() == public_key,
+            "Decrypted key does not match its public key"
+        );
+        Ok(keypair)
+    }
+}
+
+/// Derive the key encryption key from the passphrase, and make a cipher out of it.
+fn cipher(passphrase: &str, salt: &[u8], kdf: &KdfParams) -> Result<Aes256Gcm, anyhow::Error> {
+    let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p)
+        .map_err(|e| anyhow!("Invalid key derivation parameters: {e}"))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key[..])
+        .map_err(|e| anyhow!("Cannot derive key from passphrase: {e}"))?;
+    Aes256Gcm::new_from_slice(&key[..]).map_err(|e| anyhow!("Invalid key length: {e}"))
+}
+
+/// Where [`backup_keystore_file`] copies the keystore at `path`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = OsString::from(path.as_os_str());
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+/// Copy the keystore file at `path` to its [`backup_path`], readable only by its owner, before
+/// it is replaced by an encrypted keystore. The backup holds the keys in plaintext, so it should
+/// be removed once the encrypted keystore has been checked.
+pub fn backup_keystore_file(path: &Path) -> Result<PathBuf, anyhow::Error> {
+    let backup = backup_path(path);
+    let contents = Zeroizing::new(
+        fs::read(path)
+            .with_context(|| format!("Cannot read the keystore file: {}", path.display()))?,
+    );
+    write_private_file(&backup, &contents)?;
+    Ok(backup)
+}
+
+/// Replace the file at `path` with `contents`, readable only by its owner. The contents are
+/// written to a temporary file in the same directory and synced to disk, then renamed over
+/// `path`, so that `path` always holds either its old or its new contents.
+fn write_private_file(path: &Path, contents: &[u8]) -> Result<(), anyhow::Error> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow!("Invalid keystore path: {}", path.display()))?;
+    let mut temp_name = OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".tmp");
+    let temp_path = dir.join(temp_name);
+
+    let write = || -> Result<(), anyhow::Error> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        // A temporary file left behind by an interrupted save is stale.
+        let _ = fs::remove_file(&temp_path);
+        let mut file = options
+            .open(&temp_path)
+            .with_context(|| format!("Cannot create file: {}", temp_path.display()))?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Cannot replace file: {}", path.display()))?;
+
+        // Make the rename itself durable.
+        #[cfg(unix)]
+        fs::File::open(dir)?.sync_all()?;
+        Ok(())
+    };
+
+    write().inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
+/// The passphrase provider used until another is set: reads the passphrase from the
+/// `SUI_KEYSTORE_PASSPHRASE` environment variable.
+fn env_passphrase_provider() -> PassphraseProvider {
+    Arc::new(|_| {
+        std::env::var(KEYSTORE_PASSPHRASE_ENV_VAR).map_err(|_| {
+            anyhow!(
+                "The keystore is encrypted. Set {KEYSTORE_PASSPHRASE_ENV_VAR} to its passphrase to unlock it"
+            )
+        })
+    })
+}
+
+impl Serialize for EncryptedFileKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(
+            self.path
+                .as_ref()
+                .unwrap_or(&PathBuf::default())
+                .to_str()
+                .unwrap_or(""),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for EncryptedFileKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        EncryptedFileKeystore::new(&PathBuf::from(String::deserialize(deserializer)?))
+            .map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for EncryptedFileKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        Ok(Signature::new_hashed(
+            msg,
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
+        ))
+    }
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        Ok(Signature::new_secure(
+            &IntentMessage::new(intent, msg),
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
+        ))
+    }
+
+    fn add_key(&mut self, alias: Option<String>, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        let address: SuiAddress = (&keypair.public()).into();
+        let alias = self.create_alias(alias)?;
+        let passphrase = self.keystore_passphrase()?;
+        let sealed = SealedKey::seal(&keypair, &passphrase, self.kdf)?;
+
+        self.aliases.insert(
+            address,
+            Alias {
+                alias,
+                public_key_base64: keypair.public().encode_base64(),
+            },
+        );
+        self.keys.insert(
+            address,
+            EncryptedKey {
+                public_key: keypair.public(),
+                sealed,
+                unlocked: OnceLock::from(keypair),
+            },
+        );
+        self.save()?;
+        Ok(())
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        self.aliases.values().collect()
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        self.aliases.iter().collect::<Vec<_>>()
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        self.aliases.values_mut().collect()
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.keys
+            .values()
+            .map(|key| key.public_key.clone())
+            .collect()
+    }
+
+    /// This function returns an error if the provided alias already exists. If the alias
+    /// has not already been used, then it returns the alias.
+    /// If no alias has been passed, it will generate a new alias.
+    fn create_alias(&self, alias: Option<String>) -> Result<String, anyhow::Error> {
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .alias_names()
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    /// Get the address by its alias
+    fn get_address_by_alias(&self, alias: String) -> Result<&SuiAddress, anyhow::Error> {
+        self.addresses_with_alias()
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    /// Get the alias if it exists, or return an error if it does not exist.
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    /// Get the key of `address`, unlocking it first if this is its first use.
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        let Some(key) = self.keys.get(address) else {
+            bail!("Cannot find key for address: [{address}]");
+        };
+
+        if let Some(keypair) = key.unlocked.get() {
+            return Ok(keypair);
+        }
+
+        // Try the passphrase that unlocked other keys, before asking for it.
+        let cached = self.cached_passphrase();
+        let keypair = match cached.and_then(|p| key.sealed.open(&key.public_key, &p).ok()) {
+            Some(keypair) => keypair,
+            None => {
+                let passphrase = Zeroizing::new((self.passphrase_provider)(
+                    PassphraseRequest::Unlock(address),
+                )?);
+                let keypair = key.sealed.open(&key.public_key, &passphrase)?;
+                *self.passphrase.write().expect("Unable to lock passphrase") = Some(passphrase);
+                keypair
+            }
+        };
+
+        Ok(key.unlocked.get_or_init(|| keypair))
+    }
+
+    /// Updates an old alias to the new alias and saves it to the alias file.
+    /// If the new_alias is None, it will generate a new random alias.
+    fn update_alias(
+        &mut self,
+        old_alias: &str,
+        new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        let new_alias_name = self.update_alias_value(old_alias, new_alias)?;
+        self.save_aliases()?;
+        Ok(new_alias_name)
+    }
+}
+
+impl EncryptedFileKeystore {
+    /// Open the encrypted keystore at `path`, or start a new one if there is no file there. No
+    /// key is decrypted until it is used.
+    pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        let keys = if path.exists() {
+            let contents = fs::read(path)
+                .with_context(|| format!("Cannot open the keystore file: {}", path.display()))?;
+            let file: EncryptedKeystoreFile =
+                serde_json::from_slice(&contents).with_context(|| {
+                    format!(
+                        "Cannot deserialize the encrypted keystore file: {}",
+                        path.display(),
+                    )
+                })?;
+            ensure!(
+                file.version == ENCRYPTED_KEYSTORE_VERSION,
+                "Unsupported encrypted keystore version {} in {}",
+                file.version,
+                path.display()
+            );
+
+            file.keys
+                .into_iter()
+                .map(|sealed| {
+                    let public_key = PublicKey::decode_base64(&sealed.public_key)?;
+                    Ok((
+                        SuiAddress::from(&public_key),
+                        EncryptedKey {
+                            public_key,
+                            sealed,
+                            unlocked: OnceLock::new(),
+                        },
+                    ))
+                })
+                .collect::<Result<BTreeMap<_, _>, fastcrypto::error::FastCryptoError>>()
+                .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?
+        } else {
+            BTreeMap::new()
+        };
+
+        let aliases = read_aliases(
+            path,
+            keys.iter()
+                .map(|(address, key)| (*address, key.public_key.clone()))
+                .collect(),
+        )?;
+
+        Ok(Self {
+            keys,
+            aliases,
+            path: Some(path.to_path_buf()),
+            kdf: KdfParams::default(),
+            passphrase: RwLock::new(None),
+            passphrase_provider: env_passphrase_provider(),
+        })
+    }
+
+    /// Encrypt every key of a plaintext keystore under `passphrase`, keeping their aliases. The
+    /// new keystore is not saved until `save` is called.
+    pub fn encrypt(
+        keystore: &FileBasedKeystore,
+        path: &Path,
+        passphrase: String,
+        kdf: KdfParams,
+    ) -> Result<Self, anyhow::Error> {
+        ensure!(!passphrase.is_empty(), "The passphrase cannot be empty");
+
+        let keys = keystore
+            .key_pairs()
+            .into_iter()
+            .map(|keypair| {
+                let sealed = SealedKey::seal(keypair, &passphrase, kdf)?;
+                Ok((
+                    SuiAddress::from(&keypair.public()),
+                    EncryptedKey {
+                        public_key: keypair.public(),
+                        sealed,
+                        unlocked: OnceLock::from(keypair.copy()),
+                    },
+                ))
+            })
+            .collect::<Result<BTreeMap<_, _>, anyhow::Error>>()?;
+
+        let aliases = keystore
+            .addresses_with_alias()
+            .into_iter()
+            .map(|(address, alias)| (*address, alias.clone()))
+            .collect();
+
+        Ok(Self {
+            keys,
+            aliases,
+            path: Some(path.to_path_buf()),
+            kdf,
+            passphrase: RwLock::new(Some(Zeroizing::new(passphrase))),
+            passphrase_provider: env_passphrase_provider(),
+        })
+    }
+
+    /// Whether the file at `path` holds an encrypted keystore, rather than a plaintext one.
+    pub fn is_encrypted(path: &Path) -> bool {
+        fs::read(path)
+            .ok()
+            .and_then(|contents| serde_json::from_slice::<EncryptedKeystoreFile>(&contents).ok())
+            .is_some()
+    }
+
+    /// Set how the passphrase is obtained when a key needs unlocking.
+    pub fn set_passphrase_provider(&mut self, provider: PassphraseProvider) {
+        self.passphrase_provider = provider;
+    }
+
+    /// Set the key derivation parameters used to encrypt keys added from now on.
+    pub fn set_kdf_params(&mut self, kdf: KdfParams) {
+        self.kdf = kdf;
+    }
+
+    pub fn set_path(&mut self, path: &Path) {
+        self.path = Some(path.to_path_buf());
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn save_aliases(&self) -> Result<(), anyhow::Error> {
+        if let Some(path) = &self.path {
+            write_aliases(path, &self.aliases)?;
+        }
+        Ok(())
+    }
+
+    /// Keys are saved encrypted, alongside their public keys in the clear. The file is replaced
+    /// atomically, so that an interrupted save leaves the previous keystore intact.
+    pub fn save_keystore(&self) -> Result<(), anyhow::Error> {
+        if let Some(path) = &self.path {
+            let file = EncryptedKeystoreFile {
+                version: ENCRYPTED_KEYSTORE_VERSION,
+                keys: self.keys.values().map(|k| k.sealed.clone()).collect(),
+            };
+            let store = serde_json::to_string_pretty(&file).with_context(|| {
+                format!("Cannot serialize keystore to file: {}", path.display())
+            })?;
+            write_private_file(path, store.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        self.save_aliases()?;
+        self.save_keystore()?;
+        Ok(())
+    }
+
+    fn cached_passphrase(&self) -> Option<Zeroizing<String>> {
+        self.passphrase
+            .read()
+            .expect("Unable to lock passphrase")
+            .clone()
+    }
+
+    /// The passphrase of the keystore, checked against an existing key if there is one, or chosen
+    /// now if the keystore is empty.
+    fn keystore_passphrase(&self) -> Result<Zeroizing<String>, anyhow::Error> {
+        if let Some(passphrase) = self.cached_passphrase() {
+            return Ok(passphrase);
+        }
+
+        match self.keys.keys().next() {
+            Some(address) => {
+                // Unlocking a key remembers the passphrase that unlocked it.
+                self.get_key(address)?;
+                self.cached_passphrase()
+                    .ok_or_else(|| anyhow!("Cannot determine the keystore passphrase"))
+            }
+            None => {
+                let passphrase =
+                    Zeroizing::new((self.passphrase_provider)(PassphraseRequest::New)?);
+                ensure!(!passphrase.is_empty(), "The passphrase cannot be empty");
+                *self.passphrase.write().expect("Unable to lock passphrase") =
+                    Some(passphrase.clone());
+                Ok(passphrase)
+            }
+        }
+    }
+}
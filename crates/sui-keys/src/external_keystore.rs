@@ -0,0 +1,462 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A keystore whose keys never leave an external signer, such as an HSM or a custody service.
+//!
+//! The keystore talks to the signer through a plugin: a program that it runs once per request,
+//! writing a single JSON request to its standard input, and reading a single JSON response from
+//! its standard output. The plugin's standard error is passed through, so it can prompt the user
+//! (for example to touch a hardware token) and report progress. A plugin that exits with a
+//! non-zero status is considered to have failed, and one that does not exit within its configured
+//! timeout is killed.
+//!
+//! Every request carries the `version` of the protocol, currently `1`, and a `method`:
+//!
+//! * `keys` lists the keys the signer can sign with:
+//!
+//!   ```json
+//!   { "version": 1, "method": "keys" }
+//!   ```
+//!
+//!   The response lists the Base64 encoded `flag || pubkey` of each key, and optionally its
+//!   alias:
+//!
+//!   ```json
+//!   { "keys": [{ "public_key": "ALfj...", "alias": "treasury" }] }
+//!   ```
+//!
+//! * `sign` signs the digest of a message with the key of `address`:
+//!
+//!   ```json
+//!   {
+//!     "version": 1,
+//!     "method": "sign",
+//!     "address": "0x...",
+//!     "public_key": "ALfj...",
+//!     "digest": "k1z...",
+//!     "intent_message": "AAAA..."
+//!   }
+//!   ```
+//!
+//!   `digest` is the Base64 encoded message to sign, exactly as the key's signature scheme
+//!   signs a Sui message: the Blake2b-256 hash of the BCS serialized intent message.
+//!   `intent_message` is the Base64 encoded BCS serialized intent message itself, so the signer
+//!   can inspect (and refuse) what it is asked to sign. It is absent when the caller only has the
+//!   digest. The response holds the Base64 encoded `flag || signature || pubkey`:
+//!
+//!   ```json
+//!   { "signature": "ALGm..." }
+//!   ```
+//!
+//! A plugin that cannot serve a request answers with an error instead:
+//!
+//! ```json
+//! { "error": "Key is locked" }
+//! ```
+
+use crate::keystore::{validate_alias, AccountKeystore, Alias};
+use crate::random_names::random_name;
+use anyhow::{anyhow, bail, ensure, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::HashFunction;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{
+    DefaultHash, EncodeDecodeBase64, PublicKey, Signature, SuiKeyPair, SuiSignature, ToFromBytes,
+};
+
+/// The version of the signer plugin protocol spoken by this keystore.
+pub const EXTERNAL_SIGNER_PROTOCOL_VERSION: u32 = 1;
+
+/// How long the signer plugin is given to answer a request, if its config does not say.
+pub const DEFAULT_EXTERNAL_SIGNER_TIMEOUT_SECS: u64 = 120;
+
+/// How often to check whether the signer plugin has exited.
+const SIGNER_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How to run the signer plugin.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExternalSignerConfig {
+    /// The plugin program.
+    pub command: PathBuf,
+    /// Arguments passed to the plugin on every request.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Seconds the plugin is given to answer a request before it is killed. Defaults to
+    /// [`DEFAULT_EXTERNAL_SIGNER_TIMEOUT_SECS`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+impl ExternalSignerConfig {
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.timeout_secs
+                .unwrap_or(DEFAULT_EXTERNAL_SIGNER_TIMEOUT_SECS),
+        )
+    }
+}
+
+#[derive(Serialize)]
+struct SignerRequest<'a> {
+    version: u32,
+    #[serde(flatten)]
+    method: SignerMethod<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+enum SignerMethod<'a> {
+    Keys,
+    Sign {
+        address: &'a SuiAddress,
+        public_key: String,
+        digest: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        intent_message: Option<String>,
+    },
+}
+
+#[derive(Deserialize)]
+struct SignerError {
+    error: String,
+}
+
+#[derive(Deserialize)]
+struct SignerKey {
+    public_key: String,
+    alias: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct KeysResponse {
+    keys: Vec<SignerKey>,
+}
+
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+/// A keystore that delegates signing to an external signer plugin. Keys and their aliases are
+/// listed by the plugin when the keystore is opened, and private keys are never exposed: the
+/// keystore cannot add, import or export keys.
+pub struct ExternalKeystore {
+    config: ExternalSignerConfig,
+    keys: BTreeMap<SuiAddress, PublicKey>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.config.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        ExternalKeystore::new(ExternalSignerConfig::deserialize(deserializer)?)
+            .map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        self.sign(address, msg, None)
+            .map_err(|e| signature::Error::from_source(e.to_string()))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        let intent_msg = IntentMessage::new(intent, msg);
+        let bytes =
+            bcs::to_bytes(&intent_msg).map_err(|e| signature::Error::from_source(e.to_string()))?;
+        let mut hasher = DefaultHash::default();
+        hasher.update(&bytes);
+        let digest = hasher.finalize().digest;
+
+        let signature = self
+            .sign(address, &digest, Some(&bytes))
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+        signature
+            .verify_secure(&intent_msg, *address, signature.scheme())
+            .map_err(|e| {
+                signature::Error::from_source(format!(
+                    "External signer returned an invalid signature: {e}"
+                ))
+            })?;
+        Ok(signature)
+    }
+
+    fn add_key(
+        &mut self,
+        _alias: Option<String>,
+        keypair: SuiKeyPair,
+    ) -> Result<(), anyhow::Error> {
+        bail!(
+            "Cannot add key for address [{}]: keys of an external signer are managed by the signer",
+            SuiAddress::from(&keypair.public())
+        )
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        self.aliases.values().collect()
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        self.aliases.iter().collect::<Vec<_>>()
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        self.aliases.values_mut().collect()
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.keys.values().cloned().collect()
+    }
+
+    /// This function returns an error if the provided alias already exists. If the alias
+    /// has not already been used, then it returns the alias.
+    /// If no alias has been passed, it will generate a new alias.
+    fn create_alias(&self, alias: Option<String>) -> Result<String, anyhow::Error> {
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .alias_names()
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    /// Get the address by its alias
+    fn get_address_by_alias(&self, alias: String) -> Result<&SuiAddress, anyhow::Error> {
+        self.addresses_with_alias()
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    /// Get the alias if it exists, or return an error if it does not exist.
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    /// Private keys never leave the external signer, so this only fails.
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        if self.keys.contains_key(address) {
+            bail!("The key for address [{address}] is held by an external signer and cannot be exported")
+        }
+        bail!("Cannot find key for address: [{address}]")
+    }
+
+    /// Aliases are chosen by the external signer, so they cannot be updated here.
+    fn update_alias(
+        &mut self,
+        old_alias: &str,
+        _new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        bail!(
+            "Cannot update alias {old_alias}: aliases of an external signer are set by the signer"
+        )
+    }
+}
+
+impl ExternalKeystore {
+    /// Open the keystore of the signer plugin described by `config`, asking the plugin for its
+    /// keys.
+    pub fn new(config: ExternalSignerConfig) -> Result<Self, anyhow::Error> {
+        let response: KeysResponse = call_signer(&config, SignerMethod::Keys)?;
+
+        let mut keys = BTreeMap::new();
+        let mut aliases = BTreeMap::new();
+        for key in response.keys {
+            let public_key = PublicKey::decode_base64(&key.public_key).map_err(|e| {
+                anyhow!(
+                    "External signer returned an invalid public key {}: {e}",
+                    key.public_key
+                )
+            })?;
+            let address = SuiAddress::from(&public_key);
+            let alias = match key.alias {
+                Some(alias) => validate_alias(&alias)?,
+                None => default_alias(&address),
+            };
+            ensure!(
+                aliases.values().all(|a: &Alias| a.alias != alias),
+                "External signer returned the alias {alias} for more than one key"
+            );
+
+            aliases.insert(
+                address,
+                Alias {
+                    alias,
+                    public_key_base64: public_key.encode_base64(),
+                },
+            );
+            keys.insert(address, public_key);
+        }
+
+        Ok(Self {
+            config,
+            keys,
+            aliases,
+        })
+    }
+
+    pub fn config(&self) -> &ExternalSignerConfig {
+        &self.config
+    }
+
+    /// Ask the signer to sign `digest` with the key of `address`, and check that the signature
+    /// it returns is from that key.
+    fn sign(
+        &self,
+        address: &SuiAddress,
+        digest: &[u8],
+        intent_message: Option<&[u8]>,
+    ) -> Result<Signature, anyhow::Error> {
+        let Some(public_key) = self.keys.get(address) else {
+            bail!("Cannot find key for address: [{address}]");
+        };
+
+        let response: SignResponse = call_signer(
+            &self.config,
+            SignerMethod::Sign {
+                address,
+                public_key: public_key.encode_base64(),
+                digest: Base64::encode(digest),
+                intent_message: intent_message.map(Base64::encode),
+            },
+        )?;
+
+        let signature = Base64::decode(&response.signature)
+            .map_err(|e| anyhow!(e))
+            .and_then(|bytes| Signature::from_bytes(&bytes).map_err(|e| anyhow!(e)))
+            .map_err(|e| anyhow!("External signer returned an invalid signature: {e}"))?;
+        ensure!(
+            signature.scheme() == public_key.scheme()
+                && signature.public_key_bytes() == public_key.as_ref(),
+            "External signer signed with a different key than that of address [{address}]"
+        );
+        Ok(signature)
+    }
+}
+
+/// The alias of a key whose signer does not name it, derived from its address so that it is the
+/// same every time the keystore is opened.
+fn default_alias(address: &SuiAddress) -> String {
+    let hex = address.to_string();
+    format!("external-{}", &hex[2..10])
+}
+
+/// Run the signer plugin on a single request, and parse its response.
+fn call_signer<R: DeserializeOwned>(
+    config: &ExternalSignerConfig,
+    method: SignerMethod<'_>,
+) -> Result<R, anyhow::Error> {
+    let request = serde_json::to_vec(&SignerRequest {
+        version: EXTERNAL_SIGNER_PROTOCOL_VERSION,
+        method,
+    })?;
+
+    let command = config.command.display();
+    let mut child = Command::new(&config.command)
+        .args(&config.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| format!("Cannot run external signer {command}"))?;
+
+    // The request is written and the response read on their own threads, so that a plugin that
+    // starts answering before it has read the whole request cannot deadlock on a full pipe.
+    // Closing stdin marks the end of the request.
+    let mut stdin = child.stdin.take().expect("Signer stdin is piped");
+    let (written_tx, written) = mpsc::channel();
+    thread::spawn(move || written_tx.send(stdin.write_all(&request)));
+    let mut stdout = child.stdout.take().expect("Signer stdout is piped");
+    let (response_tx, response) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buf = vec![];
+        response_tx.send(stdout.read_to_end(&mut buf).map(|_| buf))
+    });
+
+    let deadline = Instant::now() + config.timeout();
+    let status = loop {
+        if let Some(status) = child
+            .try_wait()
+            .with_context(|| format!("Cannot wait for external signer {command}"))?
+        {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!(
+                "External signer {command} did not respond within {}s",
+                config.timeout().as_secs()
+            );
+        }
+        thread::sleep(SIGNER_POLL_INTERVAL);
+    };
+
+    // Anything the plugin left running could still hold its pipes open, so the deadline applies
+    // to them too.
+    let timed_out = || anyhow!("External signer {command} did not close its pipes in time");
+    let remaining = || {
+        deadline
+            .saturating_duration_since(Instant::now())
+            .max(SIGNER_POLL_INTERVAL)
+    };
+    let stdout = response
+        .recv_timeout(remaining())
+        .map_err(|_| timed_out())?
+        .with_context(|| format!("Cannot read response of external signer {command}"))?;
+    written
+        .recv_timeout(remaining())
+        .map_err(|_| timed_out())?
+        .with_context(|| format!("Cannot send request to external signer {command}"))?;
+
+    if let Ok(SignerError { error }) = serde_json::from_slice(&stdout) {
+        bail!("External signer {command} failed: {error}");
+    }
+    ensure!(
+        status.success(),
+        "External signer {command} failed with {status}"
+    );
+    serde_json::from_slice(&stdout)
+        .with_context(|| format!("Cannot parse response of external signer {command}"))
+}
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::encrypted_keystore::EncryptedFileKeystore;
+use crate::external_keystore::ExternalKeystore;
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 use crate::random_names::{random_name, random_names};
 use anyhow::{anyhow, bail, ensure, Context};
@@ -28,6 +30,8 @@ use sui_types::crypto::{
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    EncryptedFile(EncryptedFileKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -141,6 +145,16 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::EncryptedFile(file) => {
+                writeln!(writer, "Keystore Type : EncryptedFile")?;
+                write!(writer, "Keystore Path : {:?}", file.path())?;
+                write!(f, "{}", writer)
+            }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type : External")?;
+                write!(writer, "Signer Command : {:?}", external.config().command)?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -301,6 +315,12 @@ impl AccountKeystore for FileBasedKeystore {
 
 impl FileBasedKeystore {
     pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        ensure!(
+            !EncryptedFileKeystore::is_encrypted(path),
+            "The keystore file {} is encrypted. Use an `EncryptedFile` keystore to open it",
+            path.display()
+        );
+
         let keys = if path.exists() {
             let reader =
                 BufReader::new(File::open(path).with_context(|| {
@@ -321,67 +341,12 @@ impl FileBasedKeystore {
             BTreeMap::new()
         };
 
-        // check aliases
-        let mut aliases_path = path.clone();
-        aliases_path.set_extension("aliases");
-
-        let aliases = if aliases_path.exists() {
-            let reader = BufReader::new(File::open(&aliases_path).with_context(|| {
-                format!(
-                    "Cannot open aliases file in keystore: {}",
-                    aliases_path.display()
-                )
-            })?);
-
-            let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
-                format!(
-                    "Cannot deserialize aliases file in keystore: {}",
-                    aliases_path.display(),
-                )
-            })?;
-
-            aliases
-                .into_iter()
-                .map(|alias| {
-                    let key = PublicKey::decode_base64(&alias.public_key_base64);
-                    key.map(|k| (Into::<SuiAddress>::into(&k), alias))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| {
-                    anyhow!(
-                        "Invalid aliases file in keystore: {}. {}",
-                        aliases_path.display(),
-                        e
-                    )
-                })?
-        } else if keys.is_empty() {
-            BTreeMap::new()
-        } else {
-            let names: Vec<String> = random_names(HashSet::new(), keys.len());
-            let aliases = keys
-                .iter()
-                .zip(names)
-                .map(|((sui_address, skp), alias)| {
-                    let public_key_base64 = skp.public().encode_base64();
-                    (
-                        *sui_address,
-                        Alias {
-                            alias,
-                            public_key_base64,
-                        },
-                    )
-                })
-                .collect::<BTreeMap<_, _>>();
-            let aliases_store = serde_json::to_string_pretty(&aliases.values().collect::<Vec<_>>())
-                .with_context(|| {
-                    format!(
-                        "Cannot serialize aliases to file in keystore: {}",
-                        aliases_path.display()
-                    )
-                })?;
-            fs::write(aliases_path, aliases_store)?;
-            aliases
-        };
+        let aliases = read_aliases(
+            path,
+            keys.iter()
+                .map(|(address, kp)| (*address, kp.public()))
+                .collect(),
+        )?;
 
         Ok(Self {
             keys,
@@ -394,20 +359,13 @@ impl FileBasedKeystore {
         self.path = Some(path.to_path_buf());
     }
 
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
     pub fn save_aliases(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
-            let aliases_store =
-                serde_json::to_string_pretty(&self.aliases.values().collect::<Vec<_>>())
-                    .with_context(|| {
-                        format!(
-                            "Cannot serialize aliases to file in keystore: {}",
-                            path.display()
-                        )
-                    })?;
-
-            let mut aliases_path = path.clone();
-            aliases_path.set_extension("aliases");
-            fs::write(aliases_path, aliases_store)?
+            write_aliases(path, &self.aliases)?;
         }
         Ok(())
     }
@@ -593,7 +551,87 @@ impl InMemKeystore {
     }
 }
 
-fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
+/// Read the aliases file that sits next to the keystore at `path`. If there is none, generate a
+/// random alias for each of `keys` and write them to a new aliases file.
+pub(crate) fn read_aliases(
+    path: &Path,
+    keys: Vec<(SuiAddress, PublicKey)>,
+) -> Result<BTreeMap<SuiAddress, Alias>, anyhow::Error> {
+    let mut aliases_path = path.to_path_buf();
+    aliases_path.set_extension("aliases");
+
+    if aliases_path.exists() {
+        let reader = BufReader::new(File::open(&aliases_path).with_context(|| {
+            format!(
+                "Cannot open aliases file in keystore: {}",
+                aliases_path.display()
+            )
+        })?);
+
+        let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
+            format!(
+                "Cannot deserialize aliases file in keystore: {}",
+                aliases_path.display(),
+            )
+        })?;
+
+        aliases
+            .into_iter()
+            .map(|alias| {
+                let key = PublicKey::decode_base64(&alias.public_key_base64);
+                key.map(|k| (Into::<SuiAddress>::into(&k), alias))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()
+            .map_err(|e| {
+                anyhow!(
+                    "Invalid aliases file in keystore: {}. {}",
+                    aliases_path.display(),
+                    e
+                )
+            })
+    } else if keys.is_empty() {
+        Ok(BTreeMap::new())
+    } else {
+        let names: Vec<String> = random_names(HashSet::new(), keys.len());
+        let aliases = keys
+            .into_iter()
+            .zip(names)
+            .map(|((sui_address, public_key), alias)| {
+                let public_key_base64 = public_key.encode_base64();
+                (
+                    sui_address,
+                    Alias {
+                        alias,
+                        public_key_base64,
+                    },
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        write_aliases(path, &aliases)?;
+        Ok(aliases)
+    }
+}
+
+/// Write `aliases` to the aliases file that sits next to the keystore at `path`.
+pub(crate) fn write_aliases(
+    path: &Path,
+    aliases: &BTreeMap<SuiAddress, Alias>,
+) -> Result<(), anyhow::Error> {
+    let mut aliases_path = path.to_path_buf();
+    aliases_path.set_extension("aliases");
+
+    let aliases_store = serde_json::to_string_pretty(&aliases.values().collect::<Vec<_>>())
+        .with_context(|| {
+            format!(
+                "Cannot serialize aliases to file in keystore: {}",
+                aliases_path.display()
+            )
+        })?;
+    fs::write(aliases_path, aliases_store)?;
+    Ok(())
+}
+
+pub(crate) fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     let re = Regex::new(r"^[A-Za-z][A-Za-z0-9-_\.]*$")
         .map_err(|_| anyhow!("Cannot build the regex needed to validate the alias naming"))?;
     let alias = alias.trim();
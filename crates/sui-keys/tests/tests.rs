@@ -9,8 +9,15 @@ use fastcrypto::traits::EncodeDecodeBase64;
 use sui_keys::key_derive::generate_new_key;
 use tempfile::TempDir;
 
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::path::Path;
+use std::sync::Arc;
+use sui_keys::encrypted_keystore::{
+    backup_keystore_file, backup_path, EncryptedFileKeystore, KdfParams, PassphraseRequest,
+};
+use sui_keys::external_keystore::{ExternalKeystore, ExternalSignerConfig};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
-use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
+use sui_types::crypto::{DefaultHash, Signature, SignatureScheme, SuiKeyPair, SuiSignatureInner};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -279,3 +286,284 @@ fn get_alias_by_address_test() {
     let address = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
     assert!(keystore.get_alias_by_address(&address.0).is_err())
 }
+
+fn encrypted_keystore(path: &Path, passphrase: &'static str) -> EncryptedFileKeystore {
+    let mut keystore = EncryptedFileKeystore::new(&path.to_path_buf()).unwrap();
+    keystore.set_kdf_params(KdfParams::insecure_for_tests());
+    keystore.set_passphrase_provider(Arc::new(move |_| Ok(passphrase.to_string())));
+    keystore
+}
+
+#[test]
+fn encrypted_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(encrypted_keystore(&keystore_path, "passphrase"));
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(
+            SignatureScheme::ED25519,
+            Some("my_alias_test".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+
+    // No private key is written in the clear
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+    let key = keystore.get_key(&address).unwrap();
+    assert!(!contents.contains(&key.encode_base64()));
+    assert!(EncryptedFileKeystore::is_encrypted(&keystore_path));
+    assert!(FileBasedKeystore::new(&keystore_path).is_err());
+
+    // Addresses and aliases can be read without the passphrase, keys are unlocked on use
+    let reopened = Keystore::from(encrypted_keystore(&keystore_path, "passphrase"));
+    assert_eq!(vec![address], reopened.addresses());
+    assert_eq!(
+        "my_alias_test",
+        reopened.get_alias_by_address(&address).unwrap()
+    );
+    assert_eq!(key, reopened.get_key(&address).unwrap());
+}
+
+#[test]
+fn encrypted_keystore_wrong_passphrase_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(encrypted_keystore(&keystore_path, "passphrase"));
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+
+    let mut reopened = Keystore::from(encrypted_keystore(&keystore_path, "wrong passphrase"));
+    assert!(reopened.get_key(&address).is_err());
+    assert!(reopened.sign_hashed(&address, b"message").is_err());
+
+    // New keys must be encrypted under the passphrase of the keystore
+    assert!(reopened
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .is_err());
+}
+
+#[test]
+fn encrypted_keystore_requests_passphrase_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(encrypted_keystore(&keystore_path, "passphrase"));
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+
+    let requests = Arc::new(std::sync::Mutex::new(vec![]));
+    let mut reopened = encrypted_keystore(&keystore_path, "passphrase");
+    let recorded = requests.clone();
+    reopened.set_passphrase_provider(Arc::new(move |request| {
+        if let PassphraseRequest::Unlock(address) = request {
+            recorded.lock().unwrap().push(*address);
+        }
+        Ok("passphrase".to_string())
+    }));
+
+    // The passphrase is only requested the first time a key is unlocked
+    let reopened = Keystore::from(reopened);
+    reopened.sign_hashed(&address, b"message").unwrap();
+    reopened.sign_hashed(&address, b"message").unwrap();
+    assert_eq!(vec![address], *requests.lock().unwrap());
+}
+
+#[test]
+fn encrypt_file_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(
+            SignatureScheme::ED25519,
+            Some("my_alias_test".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    let key = keystore.get_key(&address).unwrap().copy();
+    let plaintext = fs::read(&keystore_path).unwrap();
+
+    let encrypted = EncryptedFileKeystore::encrypt(
+        &keystore,
+        &keystore_path,
+        "passphrase".to_string(),
+        KdfParams::insecure_for_tests(),
+    )
+    .unwrap();
+    let backup = backup_keystore_file(&keystore_path).unwrap();
+    encrypted.save().unwrap();
+
+    // The plaintext keystore is kept until it is explicitly removed.
+    assert_eq!(backup, backup_path(&keystore_path));
+    assert_eq!(plaintext, fs::read(&backup).unwrap());
+    assert!(EncryptedFileKeystore::is_encrypted(&keystore_path));
+
+    // The keystore was replaced through a temporary file, which is gone.
+    let entries: Vec<_> = fs::read_dir(temp_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert!(
+        entries.iter().all(|name| !name.ends_with(".tmp")),
+        "{entries:?}"
+    );
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        for path in [&keystore_path, &backup] {
+            let mode = fs::metadata(path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600, "{}", path.display());
+        }
+    }
+
+    let migrated = Keystore::from(encrypted_keystore(&keystore_path, "passphrase"));
+    assert_eq!(vec![address], migrated.addresses());
+    assert_eq!(
+        "my_alias_test",
+        migrated.get_alias_by_address(&address).unwrap()
+    );
+    assert_eq!(&key, migrated.get_key(&address).unwrap());
+}
+
+/// Set up a signer plugin that lists `keypair` under `alias`, and answers every signing request
+/// with `sign_response`.
+#[cfg(unix)]
+fn external_signer(
+    dir: &Path,
+    keypair: &SuiKeyPair,
+    alias: &str,
+    sign_response: serde_json::Value,
+) -> ExternalSignerConfig {
+    use std::os::unix::fs::PermissionsExt;
+
+    let keys = serde_json::json!({
+        "keys": [{ "public_key": keypair.public().encode_base64(), "alias": alias }]
+    });
+    fs::write(dir.join("keys.json"), keys.to_string()).unwrap();
+    fs::write(dir.join("sign.json"), sign_response.to_string()).unwrap();
+
+    let command = dir.join("signer.sh");
+    fs::write(
+        &command,
+        r#"#!/bin/sh
+request=$(cat)
+case "$request" in
+  *'"method":"keys"'*) cat "$1/keys.json" ;;
+  *) cat "$1/sign.json" ;;
+esac
+"#,
+    )
+    .unwrap();
+    fs::set_permissions(&command, fs::Permissions::from_mode(0o755)).unwrap();
+
+    ExternalSignerConfig {
+        command,
+        args: vec![dir.to_str().unwrap().to_string()],
+        timeout_secs: None,
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn external_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let (address, keypair, _, _) = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
+    let intent_msg = IntentMessage::new(Intent::sui_transaction(), b"message");
+    let signature = Signature::new_secure(&intent_msg, &keypair);
+    let config = external_signer(
+        temp_dir.path(),
+        &keypair,
+        "hsm_key",
+        serde_json::json!({ "signature": signature.encode_base64() }),
+    );
+
+    let mut keystore = Keystore::from(ExternalKeystore::new(config).unwrap());
+    assert_eq!(vec![address], keystore.addresses());
+    assert_eq!("hsm_key", keystore.get_alias_by_address(&address).unwrap());
+    assert_eq!(
+        signature,
+        keystore
+            .sign_secure(&address, b"message", Intent::sui_transaction())
+            .unwrap()
+    );
+
+    // Private keys stay with the signer
+    assert!(keystore.get_key(&address).is_err());
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .is_err());
+
+    // The keystore is configured by how to run its signer
+    let serialized = serde_json::to_string(&keystore).unwrap();
+    let reopened: Keystore = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(vec![address], reopened.addresses());
+}
+
+#[cfg(unix)]
+#[test]
+fn external_keystore_invalid_signature_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let (address, keypair, _, _) = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
+    let intent_msg = IntentMessage::new(Intent::sui_transaction(), b"other message");
+    let signature = Signature::new_secure(&intent_msg, &keypair);
+    let config = external_signer(
+        temp_dir.path(),
+        &keypair,
+        "hsm_key",
+        serde_json::json!({ "signature": signature.encode_base64() }),
+    );
+
+    let keystore = Keystore::from(ExternalKeystore::new(config).unwrap());
+    assert!(keystore
+        .sign_secure(&address, b"message", Intent::sui_transaction())
+        .is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn external_keystore_signer_error_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let (address, keypair, _, _) = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
+    let config = external_signer(
+        temp_dir.path(),
+        &keypair,
+        "hsm_key",
+        serde_json::json!({ "error": "Key is locked" }),
+    );
+
+    let keystore = Keystore::from(ExternalKeystore::new(config).unwrap());
+    let error = keystore
+        .sign_secure(&address, b"message", Intent::sui_transaction())
+        .unwrap_err();
+    assert!(error.to_string().contains("Key is locked"));
+}
+
+#[cfg(unix)]
+#[test]
+fn external_keystore_signer_timeout_test() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let command = temp_dir.path().join("signer.sh");
+    fs::write(&command, "#!/bin/sh\nsleep 30\n").unwrap();
+    fs::set_permissions(&command, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let config = ExternalSignerConfig {
+        command,
+        args: vec![],
+        timeout_secs: Some(1),
+    };
+    let start = std::time::Instant::now();
+    let Err(error) = ExternalKeystore::new(config) else {
+        panic!("The signer should have timed out");
+    };
+    assert!(
+        error.to_string().contains("did not respond within 1s"),
+        "{error}"
+    );
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+}
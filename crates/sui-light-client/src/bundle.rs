@@ -0,0 +1,227 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::checkpoint::{read_checkpoint, read_checkpoint_list};
+use crate::config::Config;
+use crate::construct::construct_proof;
+use crate::object_store::SuiObjectStore;
+use crate::proof::{verify_proof, Proof, ProofTarget};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiTransactionBlockResponseOptions};
+use sui_sdk::{SuiClient, SuiClientBuilder};
+use sui_types::base_types::{ObjectID, SequenceNumber, TransactionDigest};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::event::EventID;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, EndOfEpochData};
+use sui_types::object::Object;
+use tracing::info;
+
+/// The version of the proof bundle format produced by this crate.
+pub const PROOF_BUNDLE_VERSION: u8 = 1;
+
+/// A self-contained proof, that can be verified with no network access by anyone who trusts the
+/// committee of some epoch (typically the genesis committee).
+///
+/// Besides the proof itself, the bundle carries the chain of end-of-epoch checkpoints that hands
+/// authority from one committee to the next, from its first epoch up to the epoch of the proof.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProofBundle {
+    /// The version of the bundle format.
+    pub version: u8,
+
+    /// End-of-epoch checkpoints of consecutive epochs, ending with the epoch before the proof.
+    /// Each is certified by the committee announced by the one before it.
+    pub committee_chain: Vec<CertifiedCheckpointSummary>,
+
+    /// The proof of the targets, certified by the committee at the end of the chain.
+    pub proof: Proof,
+}
+
+impl ProofBundle {
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self).map_err(|e| anyhow!("Unable to serialize proof bundle: {e}"))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bundle: Self =
+            bcs::from_bytes(bytes).map_err(|e| anyhow!("Unable to parse proof bundle: {e}"))?;
+        anyhow::ensure!(
+            bundle.version == PROOF_BUNDLE_VERSION,
+            "Unsupported proof bundle version {}",
+            bundle.version
+        );
+        Ok(bundle)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        Ok(fs::write(path, self.to_bytes()?)?)
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+}
+
+/// Walk `chain` forward from `trusted`, and return the committee of the epoch after its last
+/// checkpoint. Checkpoints of epochs before that of `trusted` are skipped, so that a verifier who
+/// already trusts a later committee need not check the start of the chain.
+pub fn verify_committee_chain(
+    trusted: &Committee,
+    chain: &[CertifiedCheckpointSummary],
+) -> Result<Committee> {
+    let mut committee = trusted.clone();
+    for summary in chain.iter().filter(|s| s.epoch() >= trusted.epoch) {
+        anyhow::ensure!(
+            summary.epoch() == committee.epoch,
+            "Committee chain skips from epoch {} to epoch {}",
+            committee.epoch,
+            summary.epoch()
+        );
+
+        let Some(EndOfEpochData {
+            next_epoch_committee,
+            ..
+        }) = &summary.end_of_epoch_data
+        else {
+            return Err(anyhow!(
+                "Expected all checkpoints in the committee chain to be end-of-epoch checkpoints"
+            ));
+        };
+
+        // Make sure the committee we trust signed off on the next one
+        let next_committee = Committee::new(
+            summary.epoch().checked_add(1).unwrap(),
+            next_epoch_committee.iter().cloned().collect(),
+        );
+        let proof = Proof {
+            targets: ProofTarget::new().set_committee(next_committee.clone()),
+            checkpoint_summary: summary.clone(),
+            contents_proof: None,
+        };
+        verify_proof(&committee, &proof)?;
+
+        committee = next_committee;
+    }
+
+    Ok(committee)
+}
+
+/// Verify a proof bundle, given a committee the verifier trusts. Once this returns `Ok`, the
+/// targets of the proof in the bundle can be trusted.
+pub fn verify_bundle(trusted: &Committee, bundle: &ProofBundle) -> Result<()> {
+    let committee = verify_committee_chain(trusted, &bundle.committee_chain)?;
+    anyhow::ensure!(
+        committee.epoch == bundle.proof.checkpoint_summary.epoch(),
+        "Committee chain ends at epoch {}, but the proof is for epoch {}",
+        committee.epoch,
+        bundle.proof.checkpoint_summary.epoch()
+    );
+    verify_proof(&committee, &bundle.proof)
+}
+
+/// Bundle a proof with the synced end-of-epoch checkpoints that lead from `from_epoch` to the
+/// epoch of the proof.
+pub fn bundle_proof(config: &Config, from_epoch: EpochId, proof: Proof) -> Result<ProofBundle> {
+    let proof_epoch = proof.checkpoint_summary.epoch();
+    let mut committee_chain = vec![];
+    for seq in read_checkpoint_list(config)?.checkpoints {
+        let summary = read_checkpoint(config, seq)?;
+        if summary.epoch() >= from_epoch && summary.epoch() < proof_epoch {
+            committee_chain.push(summary);
+        }
+    }
+
+    let chain_epochs = committee_chain.len() as u64;
+    anyhow::ensure!(
+        from_epoch <= proof_epoch && chain_epochs == proof_epoch - from_epoch,
+        "Missing end-of-epoch checkpoints between epochs {from_epoch} and {proof_epoch}. Need to Sync."
+    );
+
+    Ok(ProofBundle {
+        version: PROOF_BUNDLE_VERSION,
+        committee_chain,
+        proof,
+    })
+}
+
+/// Generate a proof bundle certifying the event `event_id`.
+pub async fn generate_event_proof(
+    config: &Config,
+    from_epoch: EpochId,
+    event_id: EventID,
+) -> Result<ProofBundle> {
+    let client = full_node_client(config).await?;
+    let data = get_checkpoint_of(config, &client, event_id.tx_digest).await?;
+
+    let event = data
+        .transactions
+        .iter()
+        .find(|t| t.transaction.digest() == &event_id.tx_digest)
+        .and_then(|t| t.events.as_ref())
+        .and_then(|events| events.data.get(event_id.event_seq as usize))
+        .ok_or(anyhow!("Event not found in checkpoint data"))?
+        .clone();
+
+    info!("Constructing proof for event {:?}", event_id);
+    let proof = construct_proof(ProofTarget::new().add_event(event_id, event), &data)?;
+    bundle_proof(config, from_epoch, proof)
+}
+
+/// Generate a proof bundle certifying object `id`, at `version` if given, or at its latest version
+/// otherwise.
+pub async fn generate_object_proof(
+    config: &Config,
+    from_epoch: EpochId,
+    id: ObjectID,
+    version: Option<SequenceNumber>,
+) -> Result<ProofBundle> {
+    let client = full_node_client(config).await?;
+    let read_api = client.read_api();
+    let object = match version {
+        Some(version) => read_api
+            .try_get_parsed_past_object(id, version, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?,
+        None => read_api
+            .get_object_with_options(id, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?,
+    };
+    let object: Object = object.try_into()?;
+    let data = get_checkpoint_of(config, &client, object.previous_transaction).await?;
+
+    info!("Constructing proof for object {}", id);
+    let object_ref = object.compute_object_reference();
+    let proof = construct_proof(ProofTarget::new().add_object(object_ref, object), &data)?;
+    bundle_proof(config, from_epoch, proof)
+}
+
+async fn full_node_client(config: &Config) -> Result<SuiClient> {
+    Ok(SuiClientBuilder::default()
+        .build(config.full_node_url.as_str())
+        .await?)
+}
+
+/// Download the full checkpoint that includes transaction `tid`.
+async fn get_checkpoint_of(
+    config: &Config,
+    client: &SuiClient,
+    tid: TransactionDigest,
+) -> Result<CheckpointData> {
+    let seq = client
+        .read_api()
+        .get_transaction_with_options(tid, SuiTransactionBlockResponseOptions::new())
+        .await
+        .map_err(|e| anyhow!(format!("Cannot get transaction: {e}")))?
+        .checkpoint
+        .ok_or(anyhow!("Transaction not found"))?;
+
+    SuiObjectStore::new(config)?
+        .get_full_checkpoint(seq)
+        .await
+        .map_err(|e| anyhow!(format!("Cannot get full checkpoint: {e}")))
+}
@@ -0,0 +1,515 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::checkpoint::{
+    read_checkpoint, read_checkpoint_list, write_checkpoint, write_checkpoint_list, CheckpointsList,
+};
+use crate::config::Config;
+use crate::construct::construct_proof;
+use crate::graphql::query_last_checkpoint_of_epoch;
+use crate::object_store::SuiObjectStore;
+use crate::proof::{verify_proof, Proof, ProofTarget, TransactionProof};
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+use std::time::Duration;
+use sui_config::genesis::Genesis;
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiTransactionBlockResponseOptions};
+use sui_sdk::{SuiClient, SuiClientBuilder};
+use sui_types::base_types::{ObjectRef, TransactionDigest};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::digests::TransactionEffectsDigest;
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointSequenceNumber, EndOfEpochData,
+};
+use sui_types::object::Object;
+use tracing::{info, warn};
+
+/// The number of most recent checkpoint headers kept in the header store, on top of every
+/// end-of-epoch checkpoint.
+const MAX_RECENT_HEADERS: usize = 10_000;
+
+/// Verified checkpoint headers, and the committees they certify.
+///
+/// Every header is verified against the committee of its epoch before it is stored, and the
+/// committee of each epoch is only ever learned from the verified end-of-epoch checkpoint of the
+/// previous epoch (or from genesis), so everything in the store can be trusted.
+pub struct HeaderStore {
+    committees: BTreeMap<EpochId, Committee>,
+    end_of_epoch: BTreeMap<EpochId, CertifiedCheckpointSummary>,
+    recent: BTreeMap<CheckpointSequenceNumber, CertifiedCheckpointSummary>,
+}
+
+impl HeaderStore {
+    /// Create a header store that trusts `genesis_committee` for its epoch.
+    pub fn new(genesis_committee: Committee) -> Self {
+        Self {
+            committees: BTreeMap::from([(genesis_committee.epoch, genesis_committee)]),
+            end_of_epoch: BTreeMap::new(),
+            recent: BTreeMap::new(),
+        }
+    }
+
+    /// The committee of `epoch`, if the end-of-epoch checkpoint of the epoch before it has been
+    /// verified.
+    pub fn committee(&self, epoch: EpochId) -> Option<&Committee> {
+        self.committees.get(&epoch)
+    }
+
+    /// The latest epoch whose committee is known.
+    pub fn current_epoch(&self) -> EpochId {
+        *self
+            .committees
+            .keys()
+            .last()
+            .expect("Header store always holds the genesis committee")
+    }
+
+    /// The verified header of checkpoint `seq`, if it is stored.
+    pub fn header(&self, seq: CheckpointSequenceNumber) -> Option<&CertifiedCheckpointSummary> {
+        self.recent.get(&seq).or_else(|| {
+            self.end_of_epoch
+                .values()
+                .find(|summary| *summary.sequence_number() == seq)
+        })
+    }
+
+    /// The verified header with the highest sequence number.
+    pub fn latest(&self) -> Option<&CertifiedCheckpointSummary> {
+        let recent = self.recent.values().last();
+        let end_of_epoch = self.end_of_epoch.values().last();
+        match (recent, end_of_epoch) {
+            (Some(r), Some(e)) if e.sequence_number() > r.sequence_number() => Some(e),
+            (Some(r), _) => Some(r),
+            (None, e) => e,
+        }
+    }
+
+    /// Verify `summary` against the committee of its epoch and store it. If it is an end-of-epoch
+    /// checkpoint, the committee of the next epoch becomes known.
+    pub fn insert(&mut self, summary: CertifiedCheckpointSummary) -> Result<()> {
+        let committee = self.committee(summary.epoch()).ok_or_else(|| {
+            anyhow!(
+                "No committee known for epoch {}, the light client needs to sync",
+                summary.epoch()
+            )
+        })?;
+        summary.verify_with_contents(committee, None)?;
+
+        if let Some(EndOfEpochData {
+            next_epoch_committee,
+            ..
+        }) = &summary.end_of_epoch_data
+        {
+            let next_epoch = summary.epoch().checked_add(1).unwrap();
+            let next_committee = next_epoch_committee.iter().cloned().collect();
+            self.committees
+                .insert(next_epoch, Committee::new(next_epoch, next_committee));
+            self.end_of_epoch.insert(summary.epoch(), summary);
+        } else {
+            self.recent.insert(*summary.sequence_number(), summary);
+            while self.recent.len() > MAX_RECENT_HEADERS {
+                self.recent.pop_first();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A transaction whose execution, with the given effects, is certified by a checkpoint.
+#[derive(Debug)]
+pub struct VerifiedTransaction {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub effects: TransactionEffects,
+    pub events: Option<TransactionEvents>,
+}
+
+/// An object version whose reference and contents are certified by a checkpoint.
+#[derive(Debug)]
+pub struct VerifiedObject {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub object: Object,
+}
+
+/// Check that `tid` was executed with effects `effects_digest` in the checkpoint `data`, certified
+/// by `committee`.
+pub fn verify_transaction_in_checkpoint(
+    committee: &Committee,
+    data: &CheckpointData,
+    tid: TransactionDigest,
+    effects_digest: TransactionEffectsDigest,
+) -> Result<VerifiedTransaction> {
+    let tx = data
+        .transactions
+        .iter()
+        .find(|t| t.transaction.digest() == &tid)
+        .ok_or(anyhow!("Transaction not found in checkpoint data"))?;
+
+    let proof = Proof {
+        targets: ProofTarget::new(),
+        checkpoint_summary: data.checkpoint_summary.clone(),
+        contents_proof: Some(TransactionProof {
+            checkpoint_contents: data.checkpoint_contents.clone(),
+            transaction: tx.transaction.clone(),
+            effects: tx.effects.clone(),
+            events: tx.events.clone(),
+        }),
+    };
+    verify_proof(committee, &proof)?;
+
+    anyhow::ensure!(
+        tx.effects.digest() == effects_digest,
+        "Transaction was executed with effects {}, not {}",
+        tx.effects.digest(),
+        effects_digest
+    );
+
+    Ok(VerifiedTransaction {
+        checkpoint: *data.checkpoint_summary.sequence_number(),
+        effects: tx.effects.clone(),
+        events: tx.events.clone(),
+    })
+}
+
+/// Check that `object` is the version written at `object_ref` by a transaction in the checkpoint
+/// `data`, certified by `committee`.
+pub fn verify_object_in_checkpoint(
+    committee: &Committee,
+    data: &CheckpointData,
+    object_ref: ObjectRef,
+    object: Object,
+) -> Result<VerifiedObject> {
+    let proof = construct_proof(
+        ProofTarget::new().add_object(object_ref, object.clone()),
+        data,
+    )?;
+    verify_proof(committee, &proof)?;
+
+    Ok(VerifiedObject {
+        checkpoint: *data.checkpoint_summary.sequence_number(),
+        object,
+    })
+}
+
+/// A light client that follows the chain: it keeps its committees and checkpoint headers up to
+/// date, and answers queries about transactions and objects by verifying them against the headers.
+///
+/// Untrusted data (checkpoint contents, transactions and objects) is read from the full node and
+/// the checkpoint object store in `Config`, and is only returned once a proof for it verifies.
+pub struct LightClient {
+    config: Config,
+    client: SuiClient,
+    object_store: SuiObjectStore,
+    headers: RwLock<HeaderStore>,
+}
+
+impl LightClient {
+    /// Create a light client starting from the genesis committee and any end-of-epoch checkpoints
+    /// already synced to the checkpoint summary directory.
+    pub async fn new(config: Config) -> Result<Self> {
+        anyhow::ensure!(
+            config.graphql_url.is_some(),
+            "Following the chain requires a GraphQL URL to find end-of-epoch checkpoints"
+        );
+
+        let genesis_committee = Genesis::load(config.genesis_path())?
+            .committee()
+            .map_err(|e| anyhow!(format!("Cannot load Genesis: {e}")))?;
+        let mut headers = HeaderStore::new(genesis_committee);
+
+        let stored = read_checkpoint_list(&config)
+            .map(|list| list.checkpoints)
+            .unwrap_or_default();
+        for seq in stored {
+            headers
+                .insert(read_checkpoint(&config, seq)?)
+                .map_err(|e| anyhow!(format!("Cannot verify stored checkpoint {seq}: {e}")))?;
+        }
+        info!("Light client starting at epoch {}", headers.current_epoch());
+
+        let client = SuiClientBuilder::default()
+            .build(config.full_node_url.as_str())
+            .await?;
+        let object_store = SuiObjectStore::new(&config)?;
+
+        Ok(Self {
+            config,
+            client,
+            object_store,
+            headers: RwLock::new(headers),
+        })
+    }
+
+    /// The verified header with the highest sequence number.
+    pub fn latest_checkpoint(&self) -> Option<CertifiedCheckpointSummary> {
+        self.headers().latest().cloned()
+    }
+
+    /// The latest epoch whose committee is known.
+    pub fn current_epoch(&self) -> EpochId {
+        self.headers().current_epoch()
+    }
+
+    /// Verify the latest checkpoint, first verifying the end-of-epoch checkpoints of any epochs
+    /// that ended since the last sync. Returns the sequence number of the latest checkpoint.
+    pub async fn sync(&self) -> Result<CheckpointSequenceNumber> {
+        let latest_seq = self
+            .client
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await?;
+        if self
+            .latest_checkpoint()
+            .is_some_and(|latest| *latest.sequence_number() >= latest_seq)
+        {
+            return Ok(latest_seq);
+        }
+
+        let latest = self
+            .object_store
+            .download_checkpoint_summary(latest_seq)
+            .await?;
+
+        // Walk the committees forward, one epoch at a time, up to the epoch of the latest
+        // checkpoint.
+        loop {
+            let epoch = self.current_epoch();
+            if latest.epoch() <= epoch {
+                break;
+            }
+
+            let seq = query_last_checkpoint_of_epoch(&self.config, epoch).await?;
+            let summary = self.object_store.download_checkpoint_summary(seq).await?;
+            self.headers_mut().insert(summary.clone())?;
+            self.persist_end_of_epoch(&summary)?;
+            info!("Epoch: {} Last Checkpoint: {}", epoch, seq);
+        }
+
+        self.headers_mut().insert(latest)?;
+        Ok(latest_seq)
+    }
+
+    /// Keep syncing, every `interval`, forever.
+    pub async fn follow(&self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.sync().await {
+                warn!("Failed to sync light client: {e}");
+            }
+        }
+    }
+
+    /// Check that transaction `tid` was executed with effects `effects_digest`.
+    pub async fn verify_transaction(
+        &self,
+        tid: TransactionDigest,
+        effects_digest: TransactionEffectsDigest,
+    ) -> Result<VerifiedTransaction> {
+        let data = self.verified_checkpoint_of(tid).await?;
+        let committee = self.committee(data.checkpoint_summary.epoch())?;
+        verify_transaction_in_checkpoint(&committee, &data, tid, effects_digest)
+    }
+
+    /// Check that the object version at `object_ref` exists, and return its verified contents.
+    pub async fn verify_object(&self, object_ref: ObjectRef) -> Result<VerifiedObject> {
+        let (id, version, _) = object_ref;
+        let object: Object = self
+            .client
+            .read_api()
+            .try_get_parsed_past_object(id, version, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?
+            .try_into()?;
+
+        let data = self
+            .verified_checkpoint_of(object.previous_transaction)
+            .await?;
+        let committee = self.committee(data.checkpoint_summary.epoch())?;
+        verify_object_in_checkpoint(&committee, &data, object_ref, object)
+    }
+
+    /// Download the checkpoint that includes `tid`, and record its header once it verifies.
+    async fn verified_checkpoint_of(&self, tid: TransactionDigest) -> Result<CheckpointData> {
+        let seq = self
+            .client
+            .read_api()
+            .get_transaction_with_options(tid, SuiTransactionBlockResponseOptions::new())
+            .await
+            .map_err(|e| anyhow!(format!("Cannot get transaction: {e}")))?
+            .checkpoint
+            .ok_or(anyhow!("Transaction not found"))?;
+
+        let data = self
+            .object_store
+            .get_full_checkpoint(seq)
+            .await
+            .map_err(|e| anyhow!(format!("Cannot get full checkpoint: {e}")))?;
+
+        let header_digest = self.headers().header(seq).map(|header| *header.digest());
+        match header_digest {
+            Some(digest) => anyhow::ensure!(
+                &digest == data.checkpoint_summary.digest(),
+                "Checkpoint {seq} does not match its verified header"
+            ),
+            None => self.headers_mut().insert(data.checkpoint_summary.clone())?,
+        }
+
+        Ok(data)
+    }
+
+    fn committee(&self, epoch: EpochId) -> Result<Committee> {
+        self.headers().committee(epoch).cloned().ok_or_else(|| {
+            anyhow!("No committee known for epoch {epoch}, the light client needs to sync")
+        })
+    }
+
+    /// Save a verified end-of-epoch checkpoint, so that later runs need not download it again.
+    fn persist_end_of_epoch(&self, summary: &CertifiedCheckpointSummary) -> Result<()> {
+        let mut list = read_checkpoint_list(&self.config).unwrap_or(CheckpointsList {
+            checkpoints: vec![],
+        });
+        if !list.checkpoints.contains(summary.sequence_number()) {
+            list.checkpoints.push(*summary.sequence_number());
+            list.checkpoints.sort();
+        }
+        write_checkpoint(&self.config, summary)?;
+        write_checkpoint_list(&self.config, &list)
+    }
+
+    fn headers(&self) -> std::sync::RwLockReadGuard<'_, HeaderStore> {
+        self.headers.read().expect("Unable to lock header store")
+    }
+
+    fn headers_mut(&self) -> std::sync::RwLockWriteGuard<'_, HeaderStore> {
+        self.headers.write().expect("Unable to lock header store")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    fn read_data() -> (CertifiedCheckpointSummary, CheckpointData) {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("test_files/20873329.yaml");
+        let end_of_epoch: CertifiedCheckpointSummary =
+            bcs::from_bytes(&fs::read(d).unwrap()).unwrap();
+
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("test_files/20958462.bcs");
+        let full_checkpoint: CheckpointData = bcs::from_bytes(&fs::read(d).unwrap()).unwrap();
+
+        (end_of_epoch, full_checkpoint)
+    }
+
+    fn next_committee(summary: &CertifiedCheckpointSummary) -> Committee {
+        let next_committee = summary
+            .end_of_epoch_data
+            .as_ref()
+            .unwrap()
+            .next_epoch_committee
+            .iter()
+            .cloned()
+            .collect();
+        Committee::new(summary.epoch().checked_add(1).unwrap(), next_committee)
+    }
+
+    fn test_tid() -> TransactionDigest {
+        TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zi6cMVA9t4WhWk").unwrap()
+    }
+
+    #[test]
+    fn test_header_store_follows_committee() {
+        let (end_of_epoch, full_checkpoint) = read_data();
+        let mut headers = HeaderStore::new(next_committee(&end_of_epoch));
+
+        let summary = full_checkpoint.checkpoint_summary;
+        headers.insert(summary.clone()).unwrap();
+
+        assert_eq!(headers.latest().unwrap().digest(), summary.digest());
+        assert!(headers.header(*summary.sequence_number()).is_some());
+    }
+
+    #[test]
+    fn test_header_store_unknown_committee() {
+        let (end_of_epoch, full_checkpoint) = read_data();
+        let mut committee = next_committee(&end_of_epoch);
+        committee.epoch += 10;
+        let mut headers = HeaderStore::new(committee);
+
+        assert!(headers.insert(full_checkpoint.checkpoint_summary).is_err());
+        assert!(headers.latest().is_none());
+    }
+
+    #[test]
+    fn test_verify_transaction() {
+        let (end_of_epoch, full_checkpoint) = read_data();
+        let committee = next_committee(&end_of_epoch);
+        let effects_digest = full_checkpoint
+            .transactions
+            .iter()
+            .find(|t| t.transaction.digest() == &test_tid())
+            .unwrap()
+            .effects
+            .digest();
+
+        let verified = verify_transaction_in_checkpoint(
+            &committee,
+            &full_checkpoint,
+            test_tid(),
+            effects_digest,
+        )
+        .unwrap();
+        assert_eq!(
+            verified.checkpoint,
+            *full_checkpoint.checkpoint_summary.sequence_number()
+        );
+    }
+
+    #[test]
+    fn test_verify_transaction_wrong_effects() {
+        let (end_of_epoch, full_checkpoint) = read_data();
+        let committee = next_committee(&end_of_epoch);
+
+        assert!(verify_transaction_in_checkpoint(
+            &committee,
+            &full_checkpoint,
+            test_tid(),
+            TransactionEffectsDigest::ZERO,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_object() {
+        let (end_of_epoch, full_checkpoint) = read_data();
+        let committee = next_committee(&end_of_epoch);
+        let object = full_checkpoint
+            .transactions
+            .iter()
+            .find(|t| t.transaction.digest() == &test_tid())
+            .unwrap()
+            .output_objects
+            .first()
+            .unwrap()
+            .clone();
+
+        let object_ref = object.compute_object_reference();
+        verify_object_in_checkpoint(&committee, &full_checkpoint, object_ref, object.clone())
+            .unwrap();
+
+        let (id, version, _) = object_ref;
+        let wrong_ref = (id, version.next(), object_ref.2);
+        assert!(
+            verify_object_in_checkpoint(&committee, &full_checkpoint, wrong_ref, object).is_err()
+        );
+    }
+}
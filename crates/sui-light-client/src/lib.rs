@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod bundle;
 pub mod construct;
 pub mod proof;
 
@@ -15,6 +16,9 @@ pub mod graphql;
 
 pub mod verifier;
 
+pub mod follower;
+pub mod server;
+
 #[doc(inline)]
 pub use proof::*;
 
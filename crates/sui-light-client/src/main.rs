@@ -1,18 +1,24 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use sui_config::genesis::Genesis;
 use sui_types::{
-    base_types::ObjectID,
+    base_types::{ObjectID, SequenceNumber},
     digests::TransactionDigest,
+    event::EventID,
     object::{bounded_visitor::BoundedVisitor, Data},
 };
 
 use sui_package_resolver::Resolver;
 
 use clap::{Parser, Subcommand};
-use std::{fs, path::PathBuf, str::FromStr};
+use std::{fs, net::SocketAddr, path::PathBuf, str::FromStr, sync::Arc, time::Duration};
+use sui_light_client::bundle::{
+    generate_event_proof, generate_object_proof, verify_bundle, ProofBundle,
+};
 use sui_light_client::checkpoint::check_and_sync_checkpoints;
 use sui_light_client::config::Config;
+use sui_light_client::follower::LightClient;
 use sui_light_client::package_store::RemotePackageStore;
 use sui_light_client::verifier::{get_verified_effects_and_events, get_verified_object};
 
@@ -48,6 +54,62 @@ enum SCommands {
         #[arg(short, long, value_name = "OID")]
         oid: String,
     },
+
+    /// Generate a self-contained proof bundle for an event
+    ProveEvent {
+        /// Digest of the transaction that emitted the event
+        #[arg(short, long, value_name = "TID")]
+        tid: String,
+
+        /// Sequence number of the event within the transaction
+        #[arg(short, long, value_name = "SEQ")]
+        seq: u64,
+
+        /// First epoch of the committee chain, whose committee the verifier trusts
+        #[arg(long, value_name = "EPOCH", default_value_t = 0)]
+        from_epoch: u64,
+
+        /// File to write the proof bundle to
+        #[arg(long, value_name = "FILE")]
+        output: PathBuf,
+    },
+
+    /// Generate a self-contained proof bundle for an object
+    ProveObject {
+        /// Object ID
+        #[arg(short, long, value_name = "OID")]
+        oid: String,
+
+        /// Version of the object, defaults to its latest version
+        #[arg(long, value_name = "VERSION")]
+        version: Option<u64>,
+
+        /// First epoch of the committee chain, whose committee the verifier trusts
+        #[arg(long, value_name = "EPOCH", default_value_t = 0)]
+        from_epoch: u64,
+
+        /// File to write the proof bundle to
+        #[arg(long, value_name = "FILE")]
+        output: PathBuf,
+    },
+
+    /// Verify a proof bundle against the genesis committee, without network access
+    VerifyProof {
+        /// File to read the proof bundle from
+        #[arg(short, long, value_name = "FILE")]
+        proof: PathBuf,
+    },
+
+    /// Follow checkpoints continuously and serve verified reads over a local API
+    Follow {
+        /// Address to serve the local API on
+        #[arg(long, value_name = "ADDRESS", default_value = "127.0.0.1:9187")]
+        listen_address: SocketAddr,
+
+        /// Interval between polls for new checkpoints, in milliseconds
+        #[arg(long, value_name = "MS", default_value_t = 1000)]
+        poll_interval_ms: u64,
+    },
 }
 
 #[tokio::main]
@@ -146,6 +208,91 @@ pub async fn main() {
                 .await
                 .expect("Failed to sync checkpoints");
         }
+        Some(SCommands::ProveEvent {
+            tid,
+            seq,
+            from_epoch,
+            output,
+        }) => {
+            let event_id = EventID::from((TransactionDigest::from_str(&tid).unwrap(), seq));
+            let bundle = generate_event_proof(&config, from_epoch, event_id)
+                .await
+                .expect("Failed to generate event proof");
+            bundle.write(&output).expect("Failed to write proof bundle");
+            println!("Wrote proof bundle to {}", output.display());
+        }
+        Some(SCommands::ProveObject {
+            oid,
+            version,
+            from_epoch,
+            output,
+        }) => {
+            let oid = ObjectID::from_str(&oid).unwrap();
+            let bundle = generate_object_proof(
+                &config,
+                from_epoch,
+                oid,
+                version.map(SequenceNumber::from_u64),
+            )
+            .await
+            .expect("Failed to generate object proof");
+            bundle.write(&output).expect("Failed to write proof bundle");
+            println!("Wrote proof bundle to {}", output.display());
+        }
+        Some(SCommands::VerifyProof { proof }) => {
+            let bundle = ProofBundle::read(&proof).expect("Failed to read proof bundle");
+            let genesis_committee = Genesis::load(config.genesis_path())
+                .and_then(|genesis| genesis.committee().map_err(Into::into))
+                .expect("Cannot load Genesis");
+
+            match verify_bundle(&genesis_committee, &bundle) {
+                Ok(()) => {
+                    let summary = &bundle.proof.checkpoint_summary;
+                    println!(
+                        "Proof verified at checkpoint {} (epoch {})",
+                        summary.sequence_number(),
+                        summary.epoch()
+                    );
+                    for (object_ref, _) in &bundle.proof.targets.objects {
+                        println!(
+                            " - Object: {} Version: {} Hash: {}",
+                            object_ref.0, object_ref.1, object_ref.2
+                        );
+                    }
+                    for (event_id, event) in &bundle.proof.targets.events {
+                        println!(
+                            " - Event: {}:{} Type: {}",
+                            event_id.tx_digest, event_id.event_seq, event.type_
+                        );
+                    }
+                }
+                Err(e) => {
+                    println!("Proof is invalid: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(SCommands::Follow {
+            listen_address,
+            poll_interval_ms,
+        }) => {
+            let light_client = Arc::new(
+                LightClient::new(config)
+                    .await
+                    .expect("Failed to start light client"),
+            );
+
+            let follower = light_client.clone();
+            tokio::spawn(async move {
+                follower
+                    .follow(Duration::from_millis(poll_interval_ms))
+                    .await
+            });
+
+            sui_light_client::server::serve(light_client, listen_address)
+                .await
+                .expect("Light client API failed");
+        }
         _ => {
             println!("No command...");
         }
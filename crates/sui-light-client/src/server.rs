@@ -0,0 +1,110 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::follower::LightClient;
+use axum::{extract::Path, routing::get, Extension, Json, Router};
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::digests::{ObjectDigest, TransactionDigest, TransactionEffectsDigest};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tracing::info;
+
+// A small local API over a `LightClient`:
+// * `GET /checkpoint/latest` returns the latest verified checkpoint header.
+// * `GET /object/{id}/{version}/{digest}` checks that object `id` exists at `version` with `digest`.
+// * `GET /transaction/{digest}/{effects_digest}` checks that transaction `digest` was executed
+//   with effects `effects_digest`.
+//
+// Checks answer with a `Verification`: whether the claim was verified, the checkpoint that
+// certifies it if so, and the reason it could not be verified otherwise.
+
+#[derive(Debug, Serialize)]
+pub struct Verification {
+    pub verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LatestCheckpoint {
+    pub epoch: u64,
+    pub sequence_number: Option<CheckpointSequenceNumber>,
+    pub digest: Option<String>,
+}
+
+impl Verification {
+    fn from_result<T>(
+        result: anyhow::Result<T>,
+        checkpoint: impl FnOnce(&T) -> CheckpointSequenceNumber,
+    ) -> Self {
+        match result {
+            Ok(verified) => Self {
+                verified: true,
+                checkpoint: Some(checkpoint(&verified)),
+                error: None,
+            },
+            Err(e) => Self {
+                verified: false,
+                checkpoint: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+pub fn router(light_client: Arc<LightClient>) -> Router {
+    Router::new()
+        .route("/checkpoint/latest", get(latest_checkpoint))
+        .route("/object/{id}/{version}/{digest}", get(verify_object))
+        .route(
+            "/transaction/{digest}/{effects_digest}",
+            get(verify_transaction),
+        )
+        .layer(Extension(light_client))
+}
+
+/// Serve the local API for `light_client` on `address`, until the server fails.
+pub async fn serve(light_client: Arc<LightClient>, address: SocketAddr) -> anyhow::Result<()> {
+    info!("Light client API listening on {}", address);
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    axum::serve(listener, router(light_client)).await?;
+    Ok(())
+}
+
+async fn latest_checkpoint(
+    Extension(light_client): Extension<Arc<LightClient>>,
+) -> Json<LatestCheckpoint> {
+    let latest = light_client.latest_checkpoint();
+    Json(LatestCheckpoint {
+        epoch: light_client.current_epoch(),
+        sequence_number: latest.as_ref().map(|summary| *summary.sequence_number()),
+        digest: latest.map(|summary| summary.digest().to_string()),
+    })
+}
+
+async fn verify_object(
+    Extension(light_client): Extension<Arc<LightClient>>,
+    Path((id, version, digest)): Path<(ObjectID, u64, ObjectDigest)>,
+) -> Json<Verification> {
+    let object_ref = (id, SequenceNumber::from_u64(version), digest);
+    let result = light_client.verify_object(object_ref).await;
+    Json(Verification::from_result(result, |verified| {
+        verified.checkpoint
+    }))
+}
+
+async fn verify_transaction(
+    Extension(light_client): Extension<Arc<LightClient>>,
+    Path((digest, effects_digest)): Path<(TransactionDigest, TransactionEffectsDigest)>,
+) -> Json<Verification> {
+    let result = light_client
+        .verify_transaction(digest, effects_digest)
+        .await;
+    Json(Verification::from_result(result, |verified| {
+        verified.checkpoint
+    }))
+}
@@ -0,0 +1,118 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+
+use sui_light_client::bundle::{verify_bundle, ProofBundle, PROOF_BUNDLE_VERSION};
+use sui_light_client::construct::construct_proof;
+use sui_light_client::proof::ProofTarget;
+
+use sui_types::event::EventID;
+use sui_types::{committee::Committee, effects::TransactionEffectsAPI};
+
+use sui_types::full_checkpoint_content::CheckpointData;
+
+use std::io::Read;
+use std::{fs, path::PathBuf};
+
+fn read_full_checkpoint(seq: u64) -> CheckpointData {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("test_files/{}.chk", seq));
+    let mut reader = fs::File::open(d).unwrap();
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer).unwrap();
+    let (_, data): (u8, CheckpointData) = bcs::from_bytes(&buffer)
+        .map_err(|e| anyhow!("Unable to parse checkpoint file: {}", e))
+        .unwrap();
+    data
+}
+
+fn next_committee(data: &CheckpointData) -> Committee {
+    let next_committee = data
+        .checkpoint_summary
+        .end_of_epoch_data
+        .as_ref()
+        .unwrap()
+        .next_epoch_committee
+        .iter()
+        .cloned()
+        .collect();
+    Committee::new(
+        data.checkpoint_summary.epoch().checked_add(1).unwrap(),
+        next_committee,
+    )
+}
+
+/// A bundle proving the first event of the second transaction in checkpoint 16005062, along with
+/// the committee that certifies it.
+fn event_bundle() -> (Committee, ProofBundle) {
+    let committee_checkpoint = read_full_checkpoint(15918264);
+    let full_checkpoint = read_full_checkpoint(16005062);
+
+    let event = full_checkpoint.transactions[1]
+        .events
+        .as_ref()
+        .unwrap()
+        .data[0]
+        .clone();
+    let event_id = EventID::from((
+        *full_checkpoint.transactions[1].effects.transaction_digest(),
+        0,
+    ));
+    let proof = construct_proof(
+        ProofTarget::new().add_event(event_id, event),
+        &full_checkpoint,
+    )
+    .unwrap();
+
+    let bundle = ProofBundle {
+        version: PROOF_BUNDLE_VERSION,
+        committee_chain: vec![],
+        proof,
+    };
+    (next_committee(&committee_checkpoint), bundle)
+}
+
+#[test]
+fn test_bundle_success() {
+    let (committee, bundle) = event_bundle();
+    assert!(verify_bundle(&committee, &bundle).is_ok());
+}
+
+#[test]
+fn test_bundle_roundtrip() {
+    let (committee, bundle) = event_bundle();
+    let bundle = ProofBundle::from_bytes(&bundle.to_bytes().unwrap()).unwrap();
+    assert!(verify_bundle(&committee, &bundle).is_ok());
+}
+
+#[test]
+fn test_bundle_fail_unknown_version() {
+    let (_committee, mut bundle) = event_bundle();
+    bundle.version = PROOF_BUNDLE_VERSION + 1; // WRONG
+    assert!(ProofBundle::from_bytes(&bundle.to_bytes().unwrap()).is_err());
+}
+
+// Checkpoints of epochs before the trusted committee are skipped
+#[test]
+fn test_bundle_skips_trusted_epochs() {
+    let (committee, mut bundle) = event_bundle();
+    bundle.committee_chain = vec![read_full_checkpoint(15918264).checkpoint_summary];
+    assert!(verify_bundle(&committee, &bundle).is_ok());
+}
+
+// Fail if the committee chain runs past the epoch of the proof
+#[test]
+fn test_bundle_fail_chain_past_proof() {
+    let (committee, mut bundle) = event_bundle();
+    bundle.committee_chain = vec![read_full_checkpoint(16005062).checkpoint_summary]; // WRONG
+    assert!(verify_bundle(&committee, &bundle).is_err());
+}
+
+// Fail if the proof is not certified by the trusted committee
+#[test]
+fn test_bundle_fail_wrong_committee() {
+    let (_committee, bundle) = event_bundle();
+    let wrong_committee = next_committee(&read_full_checkpoint(16005062)); // WRONG
+    assert!(verify_bundle(&wrong_committee, &bundle).is_err());
+}
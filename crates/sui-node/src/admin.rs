@@ -9,17 +9,20 @@ use axum::{
     Router,
 };
 use base64::Engine;
-use humantime::parse_duration;
+use humantime::{format_duration, parse_duration};
 use serde::Deserialize;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     str::FromStr,
 };
+use sui_core::traffic_controller::{policies::IpSubnet, BlocklistType, TrafficController};
 use sui_types::{
-    base_types::AuthorityName,
+    base_types::{AuthorityName, TransactionDigest},
     crypto::{RandomnessPartialSignature, RandomnessRound, RandomnessSignature},
     error::SuiError,
+    traffic_control::PolicyConfig,
 };
 use telemetry_subscribers::TracingHandle;
 use tokio::sync::oneshot;
@@ -68,6 +71,36 @@ use tracing::info;
 // Inject a full signature from another node, bypassing validity checks.
 //
 //  $ curl 'http://127.0.0.1:1337/randomness-inject-full-sig?round=123&sigs=base64encodedsig'
+//
+// List the clients (and subnets of clients) currently blocked by the traffic controller, with
+// when they will be unblocked and why they were blocked:
+//
+//  $ curl 'http://127.0.0.1:1337/traffic-control-blocklist'
+//
+// Block a client address, or a subnet in CIDR notation, for 10 minutes. `blocklist` is either
+// `client` (the default) or `proxied-client`:
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/traffic-control-block?address=1.2.3.0/24&ttl=10m'
+//
+// Unblock a client address or subnet, from both blocklists unless `blocklist` is given:
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/traffic-control-unblock?address=1.2.3.4'
+//
+// View the highest recent request rates seen by the traffic control policies:
+//
+//  $ curl 'http://127.0.0.1:1337/traffic-control-top-rates'
+//
+// View the traffic control policy config in effect, or replace it (until the node restarts)
+// with the policy config in the request body, in the same YAML format as the node config:
+//
+//  $ curl 'http://127.0.0.1:1337/traffic-control-policy'
+//  $ curl -X POST 'http://127.0.0.1:1337/traffic-control-policy' --data-binary @policy.yaml
+//
+// View when a recently seen transaction reached each stage of its lifecycle on this node (e.g.
+// received, submitted to consensus, sequenced, deferred, executed, checkpointed), with the time
+// elapsed since the first event:
+//
+//  $ curl 'http://127.0.0.1:1337/transaction-lifecycle?digest=base58encodeddigest'
 
 const LOGGING_ROUTE: &str = "/logging";
 const TRACING_ROUTE: &str = "/enable-tracing";
@@ -80,6 +113,12 @@ const NODE_CONFIG: &str = "/node-config";
 const RANDOMNESS_PARTIAL_SIGS_ROUTE: &str = "/randomness-partial-sigs";
 const RANDOMNESS_INJECT_PARTIAL_SIGS_ROUTE: &str = "/randomness-inject-partial-sigs";
 const RANDOMNESS_INJECT_FULL_SIG_ROUTE: &str = "/randomness-inject-full-sig";
+const TRAFFIC_CONTROL_BLOCKLIST_ROUTE: &str = "/traffic-control-blocklist";
+const TRAFFIC_CONTROL_BLOCK_ROUTE: &str = "/traffic-control-block";
+const TRAFFIC_CONTROL_UNBLOCK_ROUTE: &str = "/traffic-control-unblock";
+const TRAFFIC_CONTROL_TOP_RATES_ROUTE: &str = "/traffic-control-top-rates";
+const TRAFFIC_CONTROL_POLICY_ROUTE: &str = "/traffic-control-policy";
+const TRANSACTION_LIFECYCLE_ROUTE: &str = "/transaction-lifecycle";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -119,6 +158,25 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
             RANDOMNESS_INJECT_FULL_SIG_ROUTE,
             post(randomness_inject_full_sig),
         )
+        .route(
+            TRAFFIC_CONTROL_BLOCKLIST_ROUTE,
+            get(traffic_control_blocklist),
+        )
+        .route(TRAFFIC_CONTROL_BLOCK_ROUTE, post(traffic_control_block))
+        .route(TRAFFIC_CONTROL_UNBLOCK_ROUTE, post(traffic_control_unblock))
+        .route(
+            TRAFFIC_CONTROL_TOP_RATES_ROUTE,
+            get(traffic_control_top_rates),
+        )
+        .route(
+            TRAFFIC_CONTROL_POLICY_ROUTE,
+            get(get_traffic_control_policy),
+        )
+        .route(
+            TRAFFIC_CONTROL_POLICY_ROUTE,
+            post(set_traffic_control_policy),
+        )
+        .route(TRANSACTION_LIFECYCLE_ROUTE, get(transaction_lifecycle))
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -443,3 +501,236 @@ async fn randomness_inject_full_sig(
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
     }
 }
+
+fn traffic_controller(state: &AppState) -> Result<&Arc<TrafficController>, (StatusCode, String)> {
+    state.node.traffic_controller().ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            "traffic control is not enabled on this node\n".to_string(),
+        )
+    })
+}
+
+async fn traffic_control_blocklist(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let controller = match traffic_controller(&state) {
+        Ok(controller) => controller,
+        Err(err) => return err,
+    };
+
+    let blocked = match controller.blocked_clients() {
+        Ok(blocked) => blocked,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    };
+
+    let now = SystemTime::now();
+    let mut output = String::new();
+    for client in blocked {
+        // Round to the second, to keep the output readable.
+        let expires_in = client.expiration.duration_since(now).unwrap_or_default();
+        let expires_in = Duration::from_secs(expires_in.as_secs());
+        output.push_str(&format!(
+            "{} {} reason={} expires_in={}\n",
+            client.blocklist,
+            client.subnet,
+            client.reason,
+            format_duration(expires_in),
+        ));
+    }
+
+    (StatusCode::OK, output)
+}
+
+#[derive(Deserialize)]
+struct Block {
+    address: String,
+    blocklist: Option<BlocklistType>,
+    ttl: String,
+}
+
+async fn traffic_control_block(
+    State(state): State<Arc<AppState>>,
+    args: Query<Block>,
+) -> (StatusCode, String) {
+    let Query(Block {
+        address,
+        blocklist,
+        ttl,
+    }) = args;
+
+    let controller = match traffic_controller(&state) {
+        Ok(controller) => controller,
+        Err(err) => return err,
+    };
+
+    let subnet = match IpSubnet::from_str(&address) {
+        Ok(subnet) => subnet,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    };
+
+    let Ok(ttl) = parse_duration(&ttl) else {
+        return (StatusCode::BAD_REQUEST, "invalid ttl\n".to_string());
+    };
+
+    let blocklist = blocklist.unwrap_or(BlocklistType::Client);
+    match controller.block(blocklist, subnet, ttl) {
+        Ok(()) => (
+            StatusCode::OK,
+            format!(
+                "blocked {blocklist} {subnet} for {}\n",
+                format_duration(ttl)
+            ),
+        ),
+        Err(err) => (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    }
+}
+
+#[derive(Deserialize)]
+struct Unblock {
+    address: String,
+    blocklist: Option<BlocklistType>,
+}
+
+async fn traffic_control_unblock(
+    State(state): State<Arc<AppState>>,
+    args: Query<Unblock>,
+) -> (StatusCode, String) {
+    let Query(Unblock { address, blocklist }) = args;
+
+    let controller = match traffic_controller(&state) {
+        Ok(controller) => controller,
+        Err(err) => return err,
+    };
+
+    let subnet = match IpSubnet::from_str(&address) {
+        Ok(subnet) => subnet,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    };
+
+    let blocklists = match blocklist {
+        Some(blocklist) => vec![blocklist],
+        None => vec![BlocklistType::Client, BlocklistType::ProxiedClient],
+    };
+
+    let mut output = String::new();
+    for blocklist in blocklists {
+        match controller.unblock(blocklist, subnet) {
+            Ok(true) => output.push_str(&format!("unblocked {blocklist} {subnet}\n")),
+            Ok(false) => output.push_str(&format!("{blocklist} {subnet} was not blocked\n")),
+            Err(err) => return (StatusCode::BAD_REQUEST, format!("{err}\n")),
+        }
+    }
+
+    (StatusCode::OK, output)
+}
+
+async fn traffic_control_top_rates(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let controller = match traffic_controller(&state) {
+        Ok(controller) => controller,
+        Err(err) => return err,
+    };
+
+    let top_rates = controller.top_rates();
+    let mut output = String::new();
+    for (policy, rates) in [
+        ("spam direct", top_rates.spam_direct),
+        ("spam proxied", top_rates.spam_proxied),
+        ("error direct", top_rates.error_direct),
+        ("error proxied", top_rates.error_proxied),
+    ] {
+        for (rate, ip) in rates {
+            output.push_str(&format!("{policy} {ip} {rate}\n"));
+        }
+    }
+
+    (StatusCode::OK, output)
+}
+
+async fn get_traffic_control_policy(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let controller = match traffic_controller(&state) {
+        Ok(controller) => controller,
+        Err(err) => return err,
+    };
+
+    match serde_yaml::to_string(&controller.policy_config()) {
+        Ok(policy_config) => (StatusCode::OK, policy_config),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{err}\n")),
+    }
+}
+
+async fn set_traffic_control_policy(
+    State(state): State<Arc<AppState>>,
+    policy_config: String,
+) -> (StatusCode, String) {
+    let controller = match traffic_controller(&state) {
+        Ok(controller) => controller,
+        Err(err) => return err,
+    };
+
+    let policy_config: PolicyConfig = match serde_yaml::from_str(&policy_config) {
+        Ok(policy_config) => policy_config,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    };
+
+    match controller.update_policy_config(policy_config) {
+        Ok(()) => (
+            StatusCode::OK,
+            "traffic control policy config updated\n".to_string(),
+        ),
+        Err(err) => (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    }
+}
+
+#[derive(Deserialize)]
+struct TransactionLifecycle {
+    digest: String,
+}
+
+async fn transaction_lifecycle(
+    State(state): State<Arc<AppState>>,
+    args: Query<TransactionLifecycle>,
+) -> (StatusCode, String) {
+    let Query(TransactionLifecycle { digest }) = args;
+
+    let digest = match TransactionDigest::from_str(&digest) {
+        Ok(digest) => digest,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    };
+
+    let authority = state.node.state();
+    let transaction_lifecycle = &authority.transaction_lifecycle;
+    if !transaction_lifecycle.is_enabled() {
+        return (
+            StatusCode::NOT_FOUND,
+            "transaction lifecycle tracking is disabled, set \
+             enable-transaction-lifecycle-tracking in the node config to enable it\n"
+                .to_string(),
+        );
+    }
+
+    let Some(events) = transaction_lifecycle.get(&digest) else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("transaction {digest} has not been seen recently by this node\n"),
+        );
+    };
+
+    let start = events.first().map(|event| event.timestamp);
+    let mut output = String::new();
+    for event in events {
+        let elapsed = start
+            .and_then(|start| event.timestamp.duration_since(start).ok())
+            .unwrap_or_default();
+        output.push_str(&format!(
+            "{} +{}ms {:?}",
+            humantime::format_rfc3339_millis(event.timestamp),
+            elapsed.as_millis(),
+            event.stage,
+        ));
+        if let Some(detail) = event.detail {
+            output.push_str(&format!(" {detail}"));
+        }
+        output.push('\n');
+    }
+
+    (StatusCode::OK, output)
+}
@@ -40,6 +40,7 @@ use sui_core::execution_cache::build_execution_cache;
 use sui_core::state_accumulator::StateAccumulatorMetrics;
 use sui_core::storage::RestReadStore;
 use sui_core::traffic_controller::metrics::TrafficControllerMetrics;
+use sui_core::traffic_controller::TrafficController;
 use sui_json_rpc::bridge_api::BridgeReadApi;
 use sui_json_rpc_api::JsonRpcMetrics;
 use sui_network::randomness;
@@ -283,6 +284,10 @@ pub struct SuiNode {
     auth_agg: Arc<ArcSwap<AuthorityAggregator<NetworkAuthorityClient>>>,
 
     subscription_service_checkpoint_sender: Option<tokio::sync::mpsc::Sender<CheckpointData>>,
+
+    /// Controls traffic to the validator gRPC server, or to the JSON-RPC server on fullnodes,
+    /// if a traffic control policy is configured.
+    traffic_controller: Option<Arc<TrafficController>>,
 }
 
 impl fmt::Debug for SuiNode {
@@ -810,6 +815,16 @@ impl SuiNode {
             None
         };
 
+        // Validators do not serve JSON-RPC, so a node's traffic controller only ever controls
+        // one of the two.
+        let traffic_controller = config.policy_config.clone().map(|policy| {
+            Arc::new(TrafficController::init(
+                policy,
+                TrafficControllerMetrics::new(&prometheus_registry),
+                config.firewall_config.clone(),
+            ))
+        });
+
         let (http_servers, subscription_service_checkpoint_sender) = build_http_servers(
             state.clone(),
             state_sync_store,
@@ -817,6 +832,7 @@ impl SuiNode {
             &config,
             &prometheus_registry,
             server_version,
+            traffic_controller.clone(),
         )
         .await?;
 
@@ -872,6 +888,7 @@ impl SuiNode {
                     connection_monitor_status.clone(),
                     &registry_service,
                     sui_node_metrics.clone(),
+                    traffic_controller.clone(),
                 ),
                 Self::reexecute_pending_consensus_certs(&epoch_store, &state,)
             );
@@ -921,6 +938,7 @@ impl SuiNode {
 
             auth_agg,
             subscription_service_checkpoint_sender,
+            traffic_controller,
         };
 
         info!("SuiNode started!");
@@ -1259,6 +1277,7 @@ impl SuiNode {
         connection_monitor_status: Arc<ConnectionMonitorStatus>,
         registry_service: &RegistryService,
         sui_node_metrics: Arc<SuiNodeMetrics>,
+        traffic_controller: Option<Arc<TrafficController>>,
     ) -> Result<ValidatorComponents> {
         let mut config_clone = config.clone();
         let consensus_config = config_clone
@@ -1297,6 +1316,7 @@ impl SuiNode {
             state.clone(),
             consensus_adapter.clone(),
             &registry_service.default_registry(),
+            traffic_controller,
         )
         .await?;
 
@@ -1374,6 +1394,7 @@ impl SuiNode {
         let low_scoring_authorities = Arc::new(ArcSwap::new(Arc::new(HashMap::new())));
 
         consensus_adapter.swap_low_scoring_authorities(low_scoring_authorities.clone());
+        consensus_adapter.swap_transaction_lifecycle(state.transaction_lifecycle.clone());
 
         if epoch_store.randomness_state_enabled() {
             let randomness_manager = RandomnessManager::try_new(
@@ -1554,14 +1575,17 @@ impl SuiNode {
         state: Arc<AuthorityState>,
         consensus_adapter: Arc<ConsensusAdapter>,
         prometheus_registry: &Registry,
+        traffic_controller: Option<Arc<TrafficController>>,
     ) -> Result<SpawnOnce> {
         let validator_service = ValidatorService::new(
             state.clone(),
             consensus_adapter,
             Arc::new(ValidatorServiceMetrics::new(prometheus_registry)),
-            TrafficControllerMetrics::new(prometheus_registry),
-            config.policy_config.clone(),
-            config.firewall_config.clone(),
+            traffic_controller,
+            config
+                .policy_config
+                .as_ref()
+                .map(|policy| policy.client_id_source.clone()),
         );
 
         let mut server_conf = mysten_network::config::Config::new();
@@ -1972,6 +1996,7 @@ impl SuiNode {
                         self.connection_monitor_status.clone(),
                         &self.registry_service,
                         self.metrics.clone(),
+                        self.traffic_controller.clone(),
                     )
                     .await?;
 
@@ -2079,6 +2104,10 @@ impl SuiNode {
     pub fn randomness_handle(&self) -> randomness::Handle {
         self.randomness_handle.clone()
     }
+
+    pub fn traffic_controller(&self) -> Option<&Arc<TrafficController>> {
+        self.traffic_controller.as_ref()
+    }
 }
 
 #[cfg(not(msim))]
@@ -2215,6 +2244,7 @@ async fn build_http_servers(
     config: &NodeConfig,
     prometheus_registry: &Registry,
     server_version: ServerVersion,
+    traffic_controller: Option<Arc<TrafficController>>,
 ) -> Result<(
     HttpServers,
     Option<tokio::sync::mpsc::Sender<CheckpointData>>,
@@ -2230,8 +2260,11 @@ async fn build_http_servers(
         let mut server = JsonRpcServerBuilder::new(
             env!("CARGO_PKG_VERSION"),
             prometheus_registry,
-            config.policy_config.clone(),
-            config.firewall_config.clone(),
+            traffic_controller,
+            config
+                .policy_config
+                .as_ref()
+                .map(|policy| policy.client_id_source.clone()),
         );
 
         let kv_store = build_kv_store(&state, config, prometheus_registry)?;
@@ -0,0 +1,1087 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A data source for replay that does not need a live RPC.
+//!
+//! Checkpoint archives only contain transactions and their effects, and formal snapshots only
+//! contain the live objects at the end of an epoch. [`ArchiveFetcher`] combines the two: starting
+//! from the objects in a snapshot, it re-executes every transaction in the archive that follows the
+//! snapshot, in checkpoint order, checking that each execution reproduces the archived effects.
+//! The objects written along the way make up every historical object version in the reconstructed
+//! range, which is what replaying a transaction needs.
+//!
+//! All state is kept in memory, so the range to reconstruct should be kept small on networks with a
+//! large live object set.
+
+use crate::chain_from_chain_id;
+use crate::data_fetcher::DataFetcher;
+use crate::replay::{diff_debug, ProtocolVersionSummary};
+use crate::types::ReplayEngineError;
+use async_trait::async_trait;
+use indicatif::MultiProgress;
+use prometheus::Registry;
+use rand::Rng;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+use sui_archival::reader::{ArchiveReader, ArchiveReaderMetrics};
+use sui_archival::Manifest;
+use sui_config::node::ArchiveReaderConfig;
+use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
+use sui_core::authority::authority_store_tables::LiveObject;
+use sui_execution::Executor;
+use sui_framework::BuiltInFramework;
+use sui_json_rpc_types::{
+    BcsEvent, Checkpoint, EventID, SuiEvent, SuiTransactionBlockEffects,
+    SuiTransactionBlockResponse,
+};
+use sui_protocol_config::ProtocolConfig;
+use sui_snapshot::reader::{download_bytes, LiveObjectIter, StateSnapshotReaderV1};
+use sui_types::base_types::{ObjectID, SequenceNumber, VersionNumber};
+use sui_types::committee::EpochId;
+use sui_types::digests::{ChainIdentifier, ObjectDigest, TransactionDigest};
+use sui_types::effects::{InputSharedObject, TransactionEffects, TransactionEffectsAPI};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::event::SystemEpochInfoEvent;
+use sui_types::gas::SuiGasStatus;
+use sui_types::message_envelope::Message;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::metrics::LimitsMetrics;
+use sui_types::object::{Object, Owner};
+use sui_types::storage::{
+    load_package_object_from_object_store, BackingPackageStore, ChildObjectResolver, ObjectKey,
+    ObjectStore, PackageObject, ParentSync, ReadStore, SharedInMemoryStore,
+};
+use sui_types::sui_system_state::{get_sui_system_state, SuiSystemStateTrait};
+use sui_types::transaction::{
+    CheckedInputObjects, InputObjectKind, InputObjects, ObjectReadResult, ObjectReadResultKind,
+    Transaction, TransactionData, TransactionDataAPI,
+};
+use tracing::info;
+
+const DOWNLOAD_CONCURRENCY: usize = 16;
+
+/// How many checkpoints to read from the archive at a time, while looking for a transaction.
+const TRANSACTION_SEARCH_BATCH: u64 = 1000;
+
+/// Where to find the data to replay from, on the local filesystem.
+#[derive(Clone, Debug)]
+pub struct ArchiveConfig {
+    /// Directory containing a checkpoint archive, as written by the archival service.
+    pub archive_path: PathBuf,
+    /// Directory containing formal snapshots, as `epoch_<N>` sub-directories.
+    pub snapshot_path: PathBuf,
+    /// Epoch of the snapshot to start from. If not specified, the latest snapshot that precedes
+    /// the checkpoints being replayed is used.
+    pub snapshot_epoch: Option<EpochId>,
+    /// The last checkpoint to reconstruct, if not specified by the replay command itself. Defaults
+    /// to the last checkpoint in the archive.
+    pub end_checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+/// Serves replay from state reconstructed from a local archive and formal snapshot. Cloning the
+/// fetcher is cheap, and clones share the reconstructed state.
+#[derive(Clone)]
+pub struct ArchiveFetcher {
+    state: Arc<ArchiveState>,
+}
+
+struct ArchiveState {
+    chain_id: String,
+    /// Checkpoints, transactions and effects read from the archive.
+    checkpoints: SharedInMemoryStore,
+    /// The checkpoints that were re-executed, and can therefore be replayed.
+    range: RangeInclusive<CheckpointSequenceNumber>,
+    tx_checkpoints: HashMap<TransactionDigest, CheckpointSequenceNumber>,
+    objects: VersionedObjectStore,
+    epochs: BTreeMap<EpochId, EpochInfo>,
+    epoch_change_events: Vec<SuiEvent>,
+}
+
+struct EpochInfo {
+    protocol_version: u64,
+    reference_gas_price: u64,
+    start_timestamp_ms: u64,
+    first_checkpoint: CheckpointSequenceNumber,
+    last_checkpoint: CheckpointSequenceNumber,
+    /// The transaction that started this epoch.
+    epoch_change_tx: TransactionDigest,
+    system_packages: BTreeMap<ObjectID, SequenceNumber>,
+}
+
+/// Every version of every object seen during reconstruction, as well as the versions at which
+/// objects were deleted or wrapped, and the transactions that did so.
+#[derive(Default)]
+struct VersionedObjectStore {
+    objects: BTreeMap<ObjectKey, Object>,
+    removed: BTreeMap<ObjectKey, TransactionDigest>,
+    latest: HashMap<ObjectID, SequenceNumber>,
+}
+
+/// The state of an object as of some version.
+enum ObjectAtVersion<'a> {
+    Live(&'a Object),
+    Removed(SequenceNumber),
+    Missing,
+}
+
+impl ArchiveFetcher {
+    /// Reconstruct the state needed to replay `checkpoints` (or, if not specified, the range
+    /// described by `config`) by re-executing the archive from the preceding formal snapshot.
+    #[allow(clippy::result_large_err)]
+    pub async fn new(
+        config: &ArchiveConfig,
+        checkpoints: Option<RangeInclusive<CheckpointSequenceNumber>>,
+    ) -> Result<Self, ReplayEngineError> {
+        let source =
+            ArchiveSource::open(config, checkpoints.as_ref().map(|range| *range.start())).await?;
+        let last_checkpoint = source.last_checkpoint(
+            checkpoints
+                .as_ref()
+                .map(|range| *range.end())
+                .or(config.end_checkpoint),
+        )?;
+
+        // The last checkpoint of the snapshot's epoch is read as well, but not executed, to find
+        // the transaction that started the first reconstructed epoch.
+        source
+            .read(source.first_checkpoint - 1..=last_checkpoint)
+            .await?;
+        source.reconstruct(last_checkpoint).await
+    }
+
+    /// Reconstruct the state needed to replay the transaction `digest`, stopping at the checkpoint
+    /// that contains it rather than at the end of the range described by `config`.
+    #[allow(clippy::result_large_err)]
+    pub async fn new_for_transaction(
+        config: &ArchiveConfig,
+        digest: TransactionDigest,
+    ) -> Result<Self, ReplayEngineError> {
+        let source = ArchiveSource::open(config, None).await?;
+        let last_checkpoint = source.last_checkpoint(config.end_checkpoint)?;
+
+        // Read the archive a batch at a time until the transaction turns up, so that nothing past
+        // its checkpoint is read or re-executed.
+        let mut next = source.first_checkpoint - 1;
+        let tx_checkpoint = loop {
+            if next > last_checkpoint {
+                return Err(ReplayEngineError::TransactionNotInArchive { digest });
+            }
+            let batch_end = next
+                .saturating_add(TRANSACTION_SEARCH_BATCH - 1)
+                .min(last_checkpoint);
+            source.read(next..=batch_end).await?;
+
+            let found = (next.max(source.first_checkpoint)..=batch_end).find(|sequence_number| {
+                source
+                    .checkpoints
+                    .get_full_checkpoint_contents_by_sequence_number(*sequence_number)
+                    .is_some_and(|contents| {
+                        contents
+                            .iter()
+                            .any(|data| *data.transaction.digest() == digest)
+                    })
+            });
+            if let Some(sequence_number) = found {
+                break sequence_number;
+            }
+            next = batch_end + 1;
+        };
+
+        source.reconstruct(tx_checkpoint).await
+    }
+
+    /// The checkpoints whose transactions can be replayed.
+    pub fn checkpoint_range(&self) -> RangeInclusive<CheckpointSequenceNumber> {
+        self.state.range.clone()
+    }
+
+    /// The checkpoints of `epoch` in the archive described by `config`. If the archive ends
+    /// mid-epoch, the range ends at the last checkpoint in the archive.
+    #[allow(clippy::result_large_err)]
+    pub async fn checkpoints_for_epoch(
+        config: &ArchiveConfig,
+        epoch: EpochId,
+    ) -> Result<RangeInclusive<CheckpointSequenceNumber>, ReplayEngineError> {
+        let (archive, manifest) = open_archive(&config.archive_path).await?;
+        let latest_checkpoint = archive.latest_available_checkpoint().await?;
+
+        let start = match epoch.checked_sub(1) {
+            Some(previous) => manifest.next_checkpoint_after_epoch(previous),
+            None => 0,
+        };
+        if start > latest_checkpoint {
+            return Err(ReplayEngineError::EpochNotInArchive { epoch });
+        }
+
+        let end = manifest
+            .next_checkpoint_after_epoch(epoch)
+            .saturating_sub(1)
+            .min(latest_checkpoint);
+        Ok(start..=end)
+    }
+
+    /// The protocol versions in the reconstructed range, and the epochs and checkpoints they span.
+    pub(crate) fn protocol_version_table(&self) -> BTreeMap<u64, ProtocolVersionSummary> {
+        let mut table: BTreeMap<u64, ProtocolVersionSummary> = BTreeMap::new();
+        for (epoch, info) in &self.state.epochs {
+            table
+                .entry(info.protocol_version)
+                .and_modify(|summary| {
+                    summary.epoch_end = *epoch;
+                    summary.checkpoint_end = Some(info.last_checkpoint);
+                })
+                .or_insert(ProtocolVersionSummary {
+                    protocol_version: info.protocol_version,
+                    epoch_start: *epoch,
+                    epoch_end: *epoch,
+                    checkpoint_start: Some(info.first_checkpoint),
+                    checkpoint_end: Some(info.last_checkpoint),
+                    epoch_change_tx: info.epoch_change_tx,
+                });
+        }
+        table
+    }
+
+    /// The versions of the system packages at the start of the first reconstructed epoch of each
+    /// protocol version.
+    pub(crate) fn system_package_table(&self) -> BTreeMap<u64, BTreeMap<ObjectID, SequenceNumber>> {
+        let mut table = BTreeMap::new();
+        for info in self.state.epochs.values() {
+            table
+                .entry(info.protocol_version)
+                .or_insert_with(|| info.system_packages.clone());
+        }
+        table
+    }
+}
+
+/// An open archive and the snapshot to reconstruct from, along with the checkpoints read from the
+/// archive so far.
+struct ArchiveSource {
+    archive: ArchiveReader,
+    snapshot_path: PathBuf,
+    snapshot_epoch: EpochId,
+    /// The first checkpoint after the snapshot's epoch.
+    first_checkpoint: CheckpointSequenceNumber,
+    latest_checkpoint: CheckpointSequenceNumber,
+    checkpoints: SharedInMemoryStore,
+}
+
+impl ArchiveSource {
+    /// Open the archive described by `config`, and pick the snapshot to start from: the one
+    /// configured, or else the latest one that precedes `start`.
+    #[allow(clippy::result_large_err)]
+    async fn open(
+        config: &ArchiveConfig,
+        start: Option<CheckpointSequenceNumber>,
+    ) -> Result<Self, ReplayEngineError> {
+        let (archive, manifest) = open_archive(&config.archive_path).await?;
+        let latest_checkpoint = archive.latest_available_checkpoint().await?;
+
+        let snapshot_epoch = match config.snapshot_epoch {
+            Some(epoch) => epoch,
+            None => snapshot_epochs(&config.snapshot_path)?
+                .into_iter()
+                .rev()
+                .find(|epoch| {
+                    start.is_none_or(|start| manifest.next_checkpoint_after_epoch(*epoch) <= start)
+                })
+                .ok_or_else(|| ReplayEngineError::GeneralError {
+                    err: format!(
+                        "No snapshot in {} precedes the checkpoints to replay",
+                        config.snapshot_path.display()
+                    ),
+                })?,
+        };
+
+        Ok(Self {
+            archive,
+            snapshot_path: config.snapshot_path.clone(),
+            snapshot_epoch,
+            first_checkpoint: manifest.next_checkpoint_after_epoch(snapshot_epoch),
+            latest_checkpoint,
+            checkpoints: SharedInMemoryStore::default(),
+        })
+    }
+
+    /// The last checkpoint to reconstruct, given the one requested (if any), bounded by the end
+    /// of the archive.
+    #[allow(clippy::result_large_err)]
+    fn last_checkpoint(
+        &self,
+        requested: Option<CheckpointSequenceNumber>,
+    ) -> Result<CheckpointSequenceNumber, ReplayEngineError> {
+        let last_checkpoint = requested
+            .unwrap_or(self.latest_checkpoint)
+            .min(self.latest_checkpoint);
+
+        if self.first_checkpoint > last_checkpoint {
+            return Err(ReplayEngineError::GeneralError {
+                err: format!(
+                    "Archive ends at checkpoint {}, before the end of the snapshot for epoch {}",
+                    self.latest_checkpoint, self.snapshot_epoch
+                ),
+            });
+        }
+        Ok(last_checkpoint)
+    }
+
+    /// Read `checkpoints` from the archive into the checkpoint store.
+    #[allow(clippy::result_large_err)]
+    async fn read(
+        &self,
+        checkpoints: RangeInclusive<CheckpointSequenceNumber>,
+    ) -> Result<(), ReplayEngineError> {
+        info!(
+            "Reading checkpoints {} to {} from archive",
+            checkpoints.start(),
+            checkpoints.end()
+        );
+        self.archive
+            .read(
+                self.checkpoints.clone(),
+                *checkpoints.start()..(*checkpoints.end() + 1),
+                Arc::new(AtomicU64::new(0)),
+                Arc::new(AtomicU64::new(0)),
+                false,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Load the snapshot and re-execute the checkpoints read so far, up to and including
+    /// `last_checkpoint`. Re-execution is CPU-bound, so it runs on a blocking thread.
+    #[allow(clippy::result_large_err)]
+    async fn reconstruct(
+        self,
+        last_checkpoint: CheckpointSequenceNumber,
+    ) -> Result<ArchiveFetcher, ReplayEngineError> {
+        let genesis = self
+            .archive
+            .get_summaries_for_list_no_verify(vec![0])
+            .await?
+            .into_iter()
+            .find(|summary| summary.sequence_number == 0)
+            .ok_or(ReplayEngineError::CheckpointNotInArchive { checkpoint: 0 })?;
+        let chain_id = ChainIdentifier::from(*genesis.digest()).to_string();
+
+        info!(
+            "Loading live objects from snapshot for epoch {}",
+            self.snapshot_epoch
+        );
+        let objects = load_snapshot(&self.snapshot_path, self.snapshot_epoch).await?;
+
+        let mut state = ArchiveState {
+            chain_id,
+            checkpoints: self.checkpoints,
+            range: self.first_checkpoint..=last_checkpoint,
+            tx_checkpoints: HashMap::new(),
+            objects,
+            epochs: BTreeMap::new(),
+            epoch_change_events: vec![],
+        };
+        let state = tokio::task::spawn_blocking(move || {
+            state.reconstruct()?;
+            Ok::<_, ReplayEngineError>(state)
+        })
+        .await
+        .map_err(|e| ReplayEngineError::GeneralError {
+            err: format!("Failed to reconstruct state from archive: {e}"),
+        })??;
+
+        Ok(ArchiveFetcher {
+            state: Arc::new(state),
+        })
+    }
+}
+
+impl ArchiveState {
+    /// Re-execute every checkpoint in the range, in order.
+    #[allow(clippy::result_large_err)]
+    fn reconstruct(&mut self) -> Result<(), ReplayEngineError> {
+        let chain = chain_from_chain_id(&self.chain_id);
+        let metrics = Arc::new(LimitsMetrics::new(&Registry::new()));
+        let mut epoch_executor: Option<(EpochId, ProtocolConfig, Arc<dyn Executor + Send + Sync>)> =
+            None;
+
+        for sequence_number in self.range.clone() {
+            let checkpoint = self
+                .checkpoints
+                .get_checkpoint_by_sequence_number(sequence_number)
+                .ok_or(ReplayEngineError::CheckpointNotInArchive {
+                    checkpoint: sequence_number,
+                })?;
+            let contents = self
+                .checkpoints
+                .get_full_checkpoint_contents_by_sequence_number(sequence_number)
+                .ok_or(ReplayEngineError::CheckpointNotInArchive {
+                    checkpoint: sequence_number,
+                })?;
+
+            let epoch = checkpoint.epoch();
+            if !self.epochs.contains_key(&epoch) {
+                self.start_epoch(epoch, sequence_number)?;
+            }
+
+            if !matches!(&epoch_executor, Some((e, _, _)) if *e == epoch) {
+                let protocol_config = ProtocolConfig::get_for_version(
+                    self.epoch(epoch)?.protocol_version.into(),
+                    chain,
+                );
+                let executor = sui_execution::executor(&protocol_config, true, None)?;
+                epoch_executor = Some((epoch, protocol_config, executor));
+            }
+            let Some((_, protocol_config, executor)) = &epoch_executor else {
+                unreachable!("Executor is set for the current epoch");
+            };
+
+            info!(
+                "Reconstructing checkpoint {sequence_number} ({} transactions)",
+                contents.size()
+            );
+            for data in contents.iter() {
+                self.execute(
+                    &data.transaction,
+                    &data.effects,
+                    epoch,
+                    checkpoint.timestamp_ms,
+                    protocol_config,
+                    executor.as_ref(),
+                    metrics.clone(),
+                )?;
+                self.tx_checkpoints
+                    .insert(*data.transaction.digest(), sequence_number);
+            }
+
+            if let Some(info) = self.epochs.get_mut(&epoch) {
+                info.last_checkpoint = sequence_number;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record the parameters of `epoch`, which starts at `first_checkpoint`, from the system state
+    /// left behind by the previous epoch.
+    #[allow(clippy::result_large_err)]
+    fn start_epoch(
+        &mut self,
+        epoch: EpochId,
+        first_checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), ReplayEngineError> {
+        let system_state = get_sui_system_state(&self.objects)?;
+        if system_state.epoch() != epoch {
+            return Err(ReplayEngineError::GeneralError {
+                err: format!(
+                    "Checkpoint {first_checkpoint} is in epoch {epoch}, but the system state is \
+                     at epoch {}",
+                    system_state.epoch()
+                ),
+            });
+        }
+
+        let epoch_change_tx = first_checkpoint
+            .checked_sub(1)
+            .and_then(|previous| {
+                self.checkpoints
+                    .get_full_checkpoint_contents_by_sequence_number(previous)
+            })
+            .and_then(|contents| {
+                contents
+                    .iter()
+                    .last()
+                    .map(|data| *data.transaction.digest())
+            })
+            .ok_or(ReplayEngineError::CheckpointNotInArchive {
+                checkpoint: first_checkpoint.saturating_sub(1),
+            })?;
+
+        let system_packages = BuiltInFramework::all_package_ids()
+            .into_iter()
+            .filter_map(|id| Some((id, *self.objects.latest.get(&id)?)))
+            .collect();
+
+        self.epochs.insert(
+            epoch,
+            EpochInfo {
+                protocol_version: system_state.protocol_version(),
+                reference_gas_price: system_state.reference_gas_price(),
+                start_timestamp_ms: system_state.epoch_start_timestamp_ms(),
+                first_checkpoint,
+                last_checkpoint: first_checkpoint,
+                epoch_change_tx,
+                system_packages,
+            },
+        );
+        Ok(())
+    }
+
+    /// Execute `transaction` against the reconstructed state, check that it produces the archived
+    /// `effects`, and record the objects it wrote.
+    #[allow(clippy::result_large_err, clippy::too_many_arguments)]
+    fn execute(
+        &mut self,
+        transaction: &Transaction,
+        effects: &TransactionEffects,
+        epoch: EpochId,
+        timestamp_ms: u64,
+        protocol_config: &ProtocolConfig,
+        executor: &(dyn Executor + Send + Sync),
+        metrics: Arc<LimitsMetrics>,
+    ) -> Result<(), ReplayEngineError> {
+        let digest = *transaction.digest();
+        let tx_data = transaction.data().transaction_data();
+        let info = self.epoch(epoch)?;
+        let input_objects = self.objects.input_objects(tx_data, effects)?;
+
+        let gas_status = if tx_data.kind().is_system_tx() {
+            SuiGasStatus::new_unmetered()
+        } else {
+            SuiGasStatus::new(
+                tx_data.gas_budget(),
+                tx_data.gas_price(),
+                info.reference_gas_price,
+                protocol_config,
+            )?
+        };
+
+        let (kind, signer, gas_data) = tx_data.execution_parts();
+        let (inner_store, _, local_effects, _timings, _) = executor.execute_transaction_to_effects(
+            &self.objects,
+            protocol_config,
+            metrics,
+            false,
+            &HashSet::new(),
+            &epoch,
+            info.start_timestamp_ms,
+            CheckedInputObjects::new_for_replay(input_objects),
+            gas_data,
+            gas_status,
+            kind,
+            signer,
+            digest,
+            &mut None,
+        );
+
+        if local_effects.digest() != effects.digest() {
+            return Err(ReplayEngineError::EffectsForked {
+                digest,
+                diff: diff_debug(effects, &local_effects),
+                on_chain: Box::new(effects.clone().try_into()?),
+                local: Box::new(local_effects.try_into()?),
+            });
+        }
+
+        for (event_seq, event) in inner_store.events.data.iter().enumerate() {
+            if event.is_system_epoch_info_event() {
+                let parsed: SystemEpochInfoEvent = bcs::from_bytes(&event.contents)
+                    .map_err(|e| ReplayEngineError::GeneralError { err: e.to_string() })?;
+                self.epoch_change_events.push(SuiEvent {
+                    id: EventID {
+                        tx_digest: digest,
+                        event_seq: event_seq as u64,
+                    },
+                    package_id: event.package_id,
+                    transaction_module: event.transaction_module.clone(),
+                    sender: event.sender,
+                    type_: event.type_.clone(),
+                    parsed_json: serde_json::to_value(parsed)
+                        .map_err(|e| ReplayEngineError::GeneralError { err: e.to_string() })?,
+                    bcs: BcsEvent::new(event.contents.clone()),
+                    timestamp_ms: Some(timestamp_ms),
+                });
+            }
+        }
+
+        self.objects
+            .apply(digest, &local_effects, inner_store.written);
+        Ok(())
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn epoch(&self, epoch: EpochId) -> Result<&EpochInfo, ReplayEngineError> {
+        self.epochs
+            .get(&epoch)
+            .ok_or(ReplayEngineError::EpochNotInArchive { epoch })
+    }
+}
+
+impl VersionedObjectStore {
+    fn insert(&mut self, object: Object) {
+        let (id, version) = (object.id(), object.version());
+        let latest = self.latest.entry(id).or_insert(version);
+        *latest = (*latest).max(version);
+        self.objects.insert(ObjectKey(id, version), object);
+    }
+
+    fn remove(&mut self, id: ObjectID, version: SequenceNumber, digest: TransactionDigest) {
+        self.latest.remove(&id);
+        self.removed.insert(ObjectKey(id, version), digest);
+    }
+
+    /// Record the outputs of a transaction: the objects it `written`, and the objects its
+    /// `effects` deleted or wrapped.
+    fn apply(
+        &mut self,
+        digest: TransactionDigest,
+        effects: &TransactionEffects,
+        written: BTreeMap<ObjectID, Object>,
+    ) {
+        for (id, version, _) in effects
+            .deleted()
+            .into_iter()
+            .chain(effects.wrapped())
+            .chain(effects.unwrapped_then_deleted())
+        {
+            self.remove(id, version, digest);
+        }
+
+        for object in written.into_values() {
+            self.insert(object);
+        }
+    }
+
+    fn get(&self, id: &ObjectID, version: SequenceNumber) -> ObjectAtVersion<'_> {
+        let key = ObjectKey(*id, version);
+        if let Some(object) = self.objects.get(&key) {
+            ObjectAtVersion::Live(object)
+        } else if self.removed.contains_key(&key) {
+            ObjectAtVersion::Removed(version)
+        } else {
+            ObjectAtVersion::Missing
+        }
+    }
+
+    fn get_latest(&self, id: &ObjectID) -> Option<&Object> {
+        let version = self.latest.get(id)?;
+        self.objects.get(&ObjectKey(*id, *version))
+    }
+
+    /// The state of the object `id` at the highest version that is at most `upper_bound`.
+    fn get_bounded(&self, id: &ObjectID, upper_bound: SequenceNumber) -> ObjectAtVersion<'_> {
+        let range = ObjectKey(*id, SequenceNumber::MIN)..=ObjectKey(*id, upper_bound);
+        let live = self.objects.range(range.clone()).next_back();
+        let removed = self.removed.range(range).next_back();
+        match (live, removed) {
+            (Some((live_key, _)), Some((removed_key, _))) if removed_key > live_key => {
+                ObjectAtVersion::Removed(removed_key.1)
+            }
+            (Some((_, object)), _) => ObjectAtVersion::Live(object),
+            (None, Some((removed_key, _))) => ObjectAtVersion::Removed(removed_key.1),
+            (None, None) => ObjectAtVersion::Missing,
+        }
+    }
+
+    /// The inputs of the transaction with `tx_data`, at the versions its `effects` say it read
+    /// them at.
+    #[allow(clippy::result_large_err)]
+    fn input_objects(
+        &self,
+        tx_data: &TransactionData,
+        effects: &TransactionEffects,
+    ) -> Result<InputObjects, ReplayEngineError> {
+        let shared: HashMap<_, _> = effects
+            .input_shared_objects()
+            .into_iter()
+            .map(|object| (object.id_and_version().0, object))
+            .collect();
+
+        let mut input_objects = vec![];
+        for kind in tx_data.input_objects()? {
+            let object = match &kind {
+                InputObjectKind::MovePackage(id) => ObjectReadResultKind::Object(
+                    self.get_latest(id)
+                        .cloned()
+                        .ok_or(ReplayEngineError::ObjectNotExist { id: *id })?,
+                ),
+                InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => {
+                    ObjectReadResultKind::Object(self.get_exact(id, *version)?)
+                }
+                InputObjectKind::SharedMoveObject { id, .. } => match shared.get(id) {
+                    Some(InputSharedObject::Mutate((id, version, _)))
+                    | Some(InputSharedObject::ReadOnly((id, version, _))) => {
+                        ObjectReadResultKind::Object(self.get_exact(id, *version)?)
+                    }
+                    Some(InputSharedObject::ReadConsensusStreamEnded(id, version))
+                    | Some(InputSharedObject::MutateConsensusStreamEnded(id, version)) => {
+                        let digest = self.removed.get(&ObjectKey(*id, *version)).ok_or(
+                            ReplayEngineError::ObjectVersionNotFound {
+                                id: *id,
+                                version: *version,
+                            },
+                        )?;
+                        ObjectReadResultKind::ObjectConsensusStreamEnded(*version, *digest)
+                    }
+                    Some(InputSharedObject::Cancelled(_, version)) => {
+                        ObjectReadResultKind::CancelledTransactionSharedObject(*version)
+                    }
+                    None => {
+                        return Err(ReplayEngineError::GeneralError {
+                            err: format!(
+                                "Shared input {id} of transaction {} is missing from its effects",
+                                effects.transaction_digest()
+                            ),
+                        })
+                    }
+                },
+            };
+            input_objects.push(ObjectReadResult::new(kind, object));
+        }
+
+        Ok(InputObjects::new(input_objects))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn get_exact(
+        &self,
+        id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Object, ReplayEngineError> {
+        match self.get(id, version) {
+            ObjectAtVersion::Live(object) => Ok(object.clone()),
+            ObjectAtVersion::Removed(version) => Err(ReplayEngineError::ObjectDeleted {
+                id: *id,
+                version,
+                digest: ObjectDigest::OBJECT_DIGEST_DELETED,
+            }),
+            ObjectAtVersion::Missing => {
+                Err(ReplayEngineError::ObjectVersionNotFound { id: *id, version })
+            }
+        }
+    }
+}
+
+// <---------------------  Implement the traits the executor needs to run against the store ----------------------->
+
+impl BackingPackageStore for VersionedObjectStore {
+    fn get_package_object(&self, package_id: &ObjectID) -> SuiResult<Option<PackageObject>> {
+        load_package_object_from_object_store(self, package_id)
+    }
+}
+
+impl ChildObjectResolver for VersionedObjectStore {
+    fn read_child_object(
+        &self,
+        parent: &ObjectID,
+        child: &ObjectID,
+        child_version_upper_bound: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        let ObjectAtVersion::Live(child_object) =
+            self.get_bounded(child, child_version_upper_bound)
+        else {
+            return Ok(None);
+        };
+
+        if child_object.owner != Owner::ObjectOwner((*parent).into()) {
+            return Err(SuiError::InvalidChildObjectAccess {
+                object: *child,
+                given_parent: *parent,
+                actual_owner: child_object.owner.clone(),
+            });
+        }
+
+        Ok(Some(child_object.clone()))
+    }
+
+    fn get_object_received_at_version(
+        &self,
+        owner: &ObjectID,
+        receiving_object_id: &ObjectID,
+        receive_object_at_version: SequenceNumber,
+        _epoch_id: EpochId,
+    ) -> SuiResult<Option<Object>> {
+        let ObjectAtVersion::Live(recv_object) =
+            self.get(receiving_object_id, receive_object_at_version)
+        else {
+            return Ok(None);
+        };
+
+        if recv_object.owner != Owner::AddressOwner((*owner).into()) {
+            return Ok(None);
+        }
+
+        Ok(Some(recv_object.clone()))
+    }
+}
+
+impl ObjectStore for VersionedObjectStore {
+    fn get_object(&self, object_id: &ObjectID) -> Option<Object> {
+        self.get_latest(object_id).cloned()
+    }
+
+    fn get_object_by_key(&self, object_id: &ObjectID, version: VersionNumber) -> Option<Object> {
+        self.objects.get(&ObjectKey(*object_id, version)).cloned()
+    }
+}
+
+impl ParentSync for VersionedObjectStore {
+    fn get_latest_parent_entry_ref_deprecated(
+        &self,
+        object_id: ObjectID,
+    ) -> Option<sui_types::base_types::ObjectRef> {
+        // Only needed by protocol versions that replay does not support.
+        self.get_latest(&object_id)
+            .map(|object| object.compute_object_reference())
+    }
+}
+
+#[async_trait]
+impl DataFetcher for ArchiveFetcher {
+    async fn multi_get_versioned(
+        &self,
+        objects: &[(ObjectID, SequenceNumber)],
+    ) -> Result<Vec<Object>, ReplayEngineError> {
+        objects
+            .iter()
+            .map(|(id, version)| self.state.objects.get_exact(id, *version))
+            .collect()
+    }
+
+    async fn multi_get_latest(
+        &self,
+        objects: &[ObjectID],
+    ) -> Result<Vec<Object>, ReplayEngineError> {
+        objects
+            .iter()
+            .map(|id| {
+                self.state
+                    .objects
+                    .get_latest(id)
+                    .cloned()
+                    .ok_or(ReplayEngineError::ObjectNotExist { id: *id })
+            })
+            .collect()
+    }
+
+    async fn get_checkpoint_txs(
+        &self,
+        id: u64,
+    ) -> Result<Vec<TransactionDigest>, ReplayEngineError> {
+        if !self.state.range.contains(&id) {
+            return Err(ReplayEngineError::CheckpointNotInArchive { checkpoint: id });
+        }
+
+        let contents = self
+            .state
+            .checkpoints
+            .get_full_checkpoint_contents_by_sequence_number(id)
+            .ok_or(ReplayEngineError::CheckpointNotInArchive { checkpoint: id })?;
+        Ok(contents
+            .iter()
+            .map(|data| *data.transaction.digest())
+            .collect())
+    }
+
+    async fn get_checkpoint(&self, id: u64) -> Result<Checkpoint, ReplayEngineError> {
+        let store = &self.state.checkpoints;
+        let (Some(checkpoint), Some(contents)) = (
+            store.get_checkpoint_by_sequence_number(id),
+            store.get_full_checkpoint_contents_by_sequence_number(id),
+        ) else {
+            return Err(ReplayEngineError::CheckpointNotInArchive { checkpoint: id });
+        };
+
+        let checkpoint = checkpoint.into_inner();
+        Ok(Checkpoint::from((
+            checkpoint.data().clone(),
+            contents.into_checkpoint_contents(),
+            checkpoint.auth_sig().signature.clone(),
+        )))
+    }
+
+    async fn get_transaction(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<SuiTransactionBlockResponse, ReplayEngineError> {
+        let not_found = || ReplayEngineError::TransactionNotInArchive { digest: *tx_digest };
+        let store = &self.state.checkpoints;
+        let checkpoint = *self
+            .state
+            .tx_checkpoints
+            .get(tx_digest)
+            .ok_or_else(not_found)?;
+        let transaction = store.get_transaction(tx_digest).ok_or_else(not_found)?;
+        let effects = store
+            .get_transaction_effects(tx_digest)
+            .ok_or_else(not_found)?;
+        let summary = store
+            .get_checkpoint_by_sequence_number(checkpoint)
+            .ok_or(ReplayEngineError::CheckpointNotInArchive { checkpoint })?;
+
+        let mut response = SuiTransactionBlockResponse::new(*tx_digest);
+        response.raw_transaction = bcs::to_bytes(transaction.data())
+            .map_err(|e| ReplayEngineError::GeneralError { err: e.to_string() })?;
+        response.effects = Some(SuiTransactionBlockEffects::try_from(effects)?);
+        response.checkpoint = Some(checkpoint);
+        response.timestamp_ms = Some(summary.timestamp_ms);
+        Ok(response)
+    }
+
+    async fn get_loaded_child_objects(
+        &self,
+        _: &TransactionDigest,
+    ) -> Result<Vec<(ObjectID, SequenceNumber)>, ReplayEngineError> {
+        // Child objects are read on demand, through `get_child_object`.
+        Ok(vec![])
+    }
+
+    async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64, ReplayEngineError> {
+        Ok(*self.state.range.end())
+    }
+
+    async fn fetch_random_transaction(
+        &self,
+        checkpoint_id_start: Option<u64>,
+        checkpoint_id_end: Option<u64>,
+    ) -> Result<TransactionDigest, ReplayEngineError> {
+        let range = &self.state.range;
+        let start = checkpoint_id_start
+            .unwrap_or(*range.start())
+            .max(*range.start());
+        let end = checkpoint_id_end.unwrap_or(*range.end()).min(*range.end());
+        let checkpoint_id = rand::thread_rng().gen_range(start..=end);
+
+        let txs = self.get_checkpoint_txs(checkpoint_id).await?;
+        let tx_idx = rand::thread_rng().gen_range(0..txs.len());
+
+        Ok(txs[tx_idx])
+    }
+
+    async fn get_epoch_start_timestamp_and_rgp(
+        &self,
+        epoch_id: u64,
+    ) -> Result<(u64, u64), ReplayEngineError> {
+        let info = self.state.epoch(epoch_id)?;
+        Ok((info.start_timestamp_ms, info.reference_gas_price))
+    }
+
+    async fn get_epoch_change_events(
+        &self,
+        reverse: bool,
+    ) -> Result<Vec<SuiEvent>, ReplayEngineError> {
+        let mut events = self.state.epoch_change_events.clone();
+        if reverse {
+            events.reverse();
+        }
+        Ok(events)
+    }
+
+    async fn get_chain_id(&self) -> Result<String, ReplayEngineError> {
+        Ok(self.state.chain_id.clone())
+    }
+
+    async fn get_child_object(
+        &self,
+        object_id: &ObjectID,
+        version_upper_bound: VersionNumber,
+    ) -> Result<Object, ReplayEngineError> {
+        match self
+            .state
+            .objects
+            .get_bounded(object_id, version_upper_bound)
+        {
+            ObjectAtVersion::Live(object) => Ok(object.clone()),
+            ObjectAtVersion::Removed(version) => Err(ReplayEngineError::ObjectDeleted {
+                id: *object_id,
+                version,
+                digest: ObjectDigest::OBJECT_DIGEST_DELETED,
+            }),
+            ObjectAtVersion::Missing => Err(ReplayEngineError::ObjectNotExist { id: *object_id }),
+        }
+    }
+}
+
+#[allow(clippy::result_large_err)]
+async fn open_archive(path: &Path) -> Result<(ArchiveReader, Manifest), ReplayEngineError> {
+    let archive = ArchiveReader::new(
+        ArchiveReaderConfig {
+            remote_store_config: local_store_config(path),
+            download_concurrency: NonZeroUsize::new(DOWNLOAD_CONCURRENCY).unwrap(),
+            use_for_pruning_watermark: false,
+        },
+        &ArchiveReaderMetrics::new(&Registry::new()),
+    )?;
+    // The reader syncs its manifest in the background, so make sure it is available before it is
+    // needed.
+    archive.sync_manifest_once().await?;
+    let manifest = archive.get_manifest().await?;
+    Ok((archive, manifest))
+}
+
+fn local_store_config(path: &Path) -> ObjectStoreConfig {
+    ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(path.to_path_buf()),
+        ..Default::default()
+    }
+}
+
+/// The epochs of the formal snapshots in `path`, in ascending order.
+#[allow(clippy::result_large_err)]
+fn snapshot_epochs(path: &Path) -> Result<Vec<EpochId>, ReplayEngineError> {
+    let entries = std::fs::read_dir(path).map_err(|e| ReplayEngineError::GeneralError {
+        err: format!("Unable to read snapshots from {}: {e}", path.display()),
+    })?;
+
+    let mut epochs: Vec<_> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            name.to_str()?.strip_prefix("epoch_")?.parse().ok()
+        })
+        .collect();
+    epochs.sort();
+    Ok(epochs)
+}
+
+/// Load the live objects from the formal snapshot taken at the end of `epoch`.
+#[allow(clippy::result_large_err)]
+async fn load_snapshot(
+    path: &Path,
+    epoch: EpochId,
+) -> Result<VersionedObjectStore, ReplayEngineError> {
+    // The reader stages the snapshot's reference files locally, which should not overwrite the
+    // snapshot itself.
+    let staging = tempfile::tempdir().map_err(|e| ReplayEngineError::GeneralError {
+        err: format!("Unable to create staging directory for snapshot: {e}"),
+    })?;
+    let mut reader = StateSnapshotReaderV1::new(
+        epoch,
+        &local_store_config(path),
+        &local_store_config(staging.path()),
+        NonZeroUsize::new(DOWNLOAD_CONCURRENCY).unwrap(),
+        MultiProgress::new(),
+        /* skip_reset_local_store */ true,
+    )
+    .await?;
+
+    let (sha3_digests, _) = reader.compute_checksum().await?;
+    let (input_files, epoch_dir, object_store, _) = reader.export_metadata().await?;
+
+    let mut objects = VersionedObjectStore::default();
+    for (bucket, (part_num, file_metadata)) in input_files {
+        let (bytes, _) = download_bytes(
+            object_store.clone(),
+            &file_metadata,
+            epoch_dir.clone(),
+            sha3_digests.clone(),
+            &bucket,
+            &part_num,
+            None,
+        )
+        .await;
+
+        for object in LiveObjectIter::new(&file_metadata, bytes)? {
+            // Wrapped objects can't be read by transactions until they are unwrapped, which writes
+            // a new version of them.
+            if let LiveObject::Normal(object) = object {
+                objects.insert(object);
+            }
+        }
+    }
+
+    Ok(objects)
+}
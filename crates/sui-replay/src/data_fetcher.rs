@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::archive_fetcher::ArchiveFetcher;
 use crate::types::ReplayEngineError;
 use crate::types::EPOCH_CHANGE_STRUCT_TAG;
 use async_trait::async_trait;
@@ -14,6 +15,7 @@ use std::num::NonZeroUsize;
 use std::str::FromStr;
 use sui_core::authority::NodeStateDump;
 use sui_json_rpc_api::QUERY_MAX_RESULT_LIMIT;
+use sui_json_rpc_types::Checkpoint;
 use sui_json_rpc_types::EventFilter;
 use sui_json_rpc_types::SuiEvent;
 use sui_json_rpc_types::SuiGetPastObjectRequest;
@@ -33,7 +35,7 @@ use sui_types::transaction::{EndOfEpochTransactionKind, TransactionKind};
 
 /// This trait defines the interfaces for fetching data from some local or remote store
 #[async_trait]
-pub(crate) trait DataFetcher {
+pub trait DataFetcher {
     #![allow(implied_bounds_entailment)]
     /// Fetch the specified versions of objects
     async fn multi_get_versioned(
@@ -53,6 +55,9 @@ pub(crate) trait DataFetcher {
         id: u64,
     ) -> Result<Vec<TransactionDigest>, ReplayEngineError>;
 
+    /// Fetch the summary of this checkpoint
+    async fn get_checkpoint(&self, id: u64) -> Result<Checkpoint, ReplayEngineError>;
+
     /// Fetch the transaction info for a given transaction digest
     async fn get_transaction(
         &self,
@@ -96,13 +101,14 @@ pub(crate) trait DataFetcher {
 pub enum Fetchers {
     Remote(RemoteFetcher),
     NodeStateDump(NodeStateDumpFetcher),
+    Archive(ArchiveFetcher),
 }
 
 impl Fetchers {
     pub fn as_remote(&self) -> &RemoteFetcher {
         match self {
             Fetchers::Remote(q) => q,
-            Fetchers::NodeStateDump(_) => panic!("not a remote fetcher"),
+            Fetchers::NodeStateDump(_) | Fetchers::Archive(_) => panic!("not a remote fetcher"),
         }
     }
 
@@ -114,13 +120,13 @@ impl Fetchers {
                 q.clear_cache_for_new_task();
                 q
             }
-            Fetchers::NodeStateDump(_) => panic!("not a remote fetcher"),
+            Fetchers::NodeStateDump(_) | Fetchers::Archive(_) => panic!("not a remote fetcher"),
         }
     }
 
     pub fn as_node_state_dump(&self) -> &NodeStateDumpFetcher {
         match self {
-            Fetchers::Remote(_) => panic!("not a node state dump fetcher"),
+            Fetchers::Remote(_) | Fetchers::Archive(_) => panic!("not a node state dump fetcher"),
             Fetchers::NodeStateDump(q) => q,
         }
     }
@@ -136,6 +142,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_versioned(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_versioned(objects).await,
+            Fetchers::Archive(q) => q.multi_get_versioned(objects).await,
         }
     }
 
@@ -146,6 +153,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_latest(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_latest(objects).await,
+            Fetchers::Archive(q) => q.multi_get_latest(objects).await,
         }
     }
 
@@ -156,6 +164,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_checkpoint_txs(id).await,
             Fetchers::NodeStateDump(q) => q.get_checkpoint_txs(id).await,
+            Fetchers::Archive(q) => q.get_checkpoint_txs(id).await,
         }
     }
 
@@ -166,6 +175,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_transaction(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_transaction(tx_digest).await,
+            Fetchers::Archive(q) => q.get_transaction(tx_digest).await,
         }
     }
 
@@ -176,6 +186,15 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_loaded_child_objects(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_loaded_child_objects(tx_digest).await,
+            Fetchers::Archive(q) => q.get_loaded_child_objects(tx_digest).await,
+        }
+    }
+
+    async fn get_checkpoint(&self, id: u64) -> Result<Checkpoint, ReplayEngineError> {
+        match self {
+            Fetchers::Remote(q) => q.get_checkpoint(id).await,
+            Fetchers::NodeStateDump(q) => q.get_checkpoint(id).await,
+            Fetchers::Archive(q) => q.get_checkpoint(id).await,
         }
     }
 
@@ -183,6 +202,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_latest_checkpoint_sequence_number().await,
             Fetchers::NodeStateDump(q) => q.get_latest_checkpoint_sequence_number().await,
+            Fetchers::Archive(q) => q.get_latest_checkpoint_sequence_number().await,
         }
     }
 
@@ -200,6 +220,10 @@ impl DataFetcher for Fetchers {
                 q.fetch_random_transaction(checkpoint_id_start, checkpoint_id_end)
                     .await
             }
+            Fetchers::Archive(q) => {
+                q.fetch_random_transaction(checkpoint_id_start, checkpoint_id_end)
+                    .await
+            }
         }
     }
 
@@ -210,6 +234,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
+            Fetchers::Archive(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
         }
     }
 
@@ -220,12 +245,14 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_change_events(reverse).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_change_events(reverse).await,
+            Fetchers::Archive(q) => q.get_epoch_change_events(reverse).await,
         }
     }
     async fn get_chain_id(&self) -> Result<String, ReplayEngineError> {
         match self {
             Fetchers::Remote(q) => q.get_chain_id().await,
             Fetchers::NodeStateDump(q) => q.get_chain_id().await,
+            Fetchers::Archive(q) => q.get_chain_id().await,
         }
     }
     async fn get_child_object(
@@ -236,6 +263,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_child_object(object_id, version_upper_bound).await,
             Fetchers::NodeStateDump(q) => q.get_child_object(object_id, version_upper_bound).await,
+            Fetchers::Archive(q) => q.get_child_object(object_id, version_upper_bound).await,
         }
     }
 }
@@ -455,6 +483,14 @@ impl DataFetcher for RemoteFetcher {
             .transactions)
     }
 
+    async fn get_checkpoint(&self, id: u64) -> Result<Checkpoint, ReplayEngineError> {
+        self.rpc_client
+            .read_api()
+            .get_checkpoint(id.into())
+            .await
+            .map_err(|q| ReplayEngineError::SuiRpcError { err: q.to_string() })
+    }
+
     async fn get_transaction(
         &self,
         tx_digest: &TransactionDigest,
@@ -766,6 +802,10 @@ impl DataFetcher for NodeStateDumpFetcher {
         unimplemented!("get_checkpoint_txs for state dump is not implemented")
     }
 
+    async fn get_checkpoint(&self, _id: u64) -> Result<Checkpoint, ReplayEngineError> {
+        unimplemented!("get_checkpoint for state dump is not implemented")
+    }
+
     async fn get_transaction(
         &self,
         _tx_digest: &TransactionDigest,
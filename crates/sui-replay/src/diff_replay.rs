@@ -0,0 +1,503 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::replay::{diff_debug, ExecutionSandboxState, LocalExec};
+use crate::types::ReplayEngineError;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use sui_config::node::ExpensiveSafetyCheckConfig;
+use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+use sui_types::base_types::{ObjectID, ObjectRef, TransactionDigest};
+use sui_types::gas::GasCostSummary;
+use tracing::{error, info, warn};
+
+/// Configures a differential replay, which executes every transaction twice: once with a baseline
+/// executor and once with a candidate executor, and compares their outputs.
+///
+/// Executor versions follow the same convention as elsewhere in the replay tool: `None` uses the
+/// execution version dictated by the protocol config the transaction originally ran under, and `-1`
+/// uses the latest executor, as built into this binary (including any local modifications).
+#[derive(Clone, Debug)]
+pub struct DiffReplayConfig {
+    pub baseline_executor_version: Option<i64>,
+    pub candidate_executor_version: Option<i64>,
+    pub expensive_safety_check_config: ExpensiveSafetyCheckConfig,
+    /// Stop at the first transaction that fails to replay, or whose outputs differ.
+    pub terminate_early: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct DiffReport {
+    /// The number of transactions that were replayed, including those that failed to replay.
+    pub transactions_replayed: u64,
+    /// Transactions whose outputs differ between the baseline and the candidate executor.
+    pub diffs: Vec<TransactionDiff>,
+    /// Transactions whose baseline execution did not reproduce the effects recorded on-chain.
+    /// Differences reported for these transactions may not be caused by the candidate executor
+    /// alone.
+    pub baseline_forks: Vec<TransactionDigest>,
+    /// Transactions that could not be replayed under one or both executors.
+    pub failures: Vec<ReplayFailure>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionDiff {
+    pub digest: TransactionDigest,
+    pub checkpoint: u64,
+    pub differences: Vec<Difference>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayFailure {
+    pub digest: TransactionDigest,
+    pub checkpoint: u64,
+    pub error: String,
+}
+
+/// A single way in which the outputs of the two executions differ. Textual diffs compare the
+/// baseline (`---`) to the candidate (`+++`).
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Difference {
+    Status {
+        baseline: SuiExecutionStatus,
+        candidate: SuiExecutionStatus,
+    },
+    Gas {
+        baseline: GasCostSummary,
+        candidate: GasCostSummary,
+    },
+    Events {
+        diff: String,
+    },
+    /// An object that was written by one execution but not the other, or written with different
+    /// contents by each.
+    Object {
+        id: ObjectID,
+        baseline: Option<ObjectRef>,
+        candidate: Option<ObjectRef>,
+        diff: String,
+    },
+    Effects {
+        diff: String,
+    },
+}
+
+impl DiffReport {
+    /// The number of transactions that replayed successfully and produced identical outputs under
+    /// both executors.
+    pub fn identical(&self) -> u64 {
+        self.transactions_replayed - self.diffs.len() as u64 - self.failures.len() as u64
+    }
+
+    /// Combine the report of a replay of other checkpoints into this one.
+    pub fn merge(&mut self, other: DiffReport) {
+        self.transactions_replayed += other.transactions_replayed;
+        self.diffs.extend(other.diffs);
+        self.baseline_forks.extend(other.baseline_forks);
+        self.failures.extend(other.failures);
+        self.diffs.sort_by_key(|diff| diff.checkpoint);
+        self.failures.sort_by_key(|failure| failure.checkpoint);
+    }
+}
+
+/// Replay every transaction in `checkpoints` under the two executors described by `config`, and
+/// report the transactions whose status, gas, events, written objects or effects differ.
+#[allow(clippy::result_large_err)]
+pub async fn diff_replay_checkpoints(
+    rpc_url: &str,
+    checkpoints: &[u64],
+    config: &DiffReplayConfig,
+) -> Result<DiffReport, ReplayEngineError> {
+    let mut executor = LocalExec::new_from_fn_url(rpc_url)
+        .await?
+        .init_for_execution()
+        .await?;
+
+    let mut report = DiffReport::default();
+    for checkpoint in checkpoints {
+        for digest in executor.get_checkpoint_txs(*checkpoint).await? {
+            info!("Comparing executions of transaction {digest} in checkpoint {checkpoint}");
+            report.transactions_replayed += 1;
+
+            let (differences, baseline_forked) =
+                match diff_transaction(&mut executor, &digest, config).await {
+                    Ok(result) => result,
+                    Err(e) => {
+                        error!("Error replaying tx: {digest}, {e:#?}");
+                        if config.terminate_early {
+                            return Err(e);
+                        }
+
+                        report.failures.push(ReplayFailure {
+                            digest,
+                            checkpoint: *checkpoint,
+                            error: e.to_string(),
+                        });
+                        continue;
+                    }
+                };
+
+            if baseline_forked {
+                warn!("Baseline execution of {digest} does not match its on-chain effects");
+                report.baseline_forks.push(digest);
+            }
+
+            if !differences.is_empty() {
+                warn!(
+                    "Executions of {digest} differ in {} ways",
+                    differences.len()
+                );
+                report.diffs.push(TransactionDiff {
+                    digest,
+                    checkpoint: *checkpoint,
+                    differences,
+                });
+
+                if config.terminate_early {
+                    return Ok(report);
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Execute the transaction with `digest` under both executors, and return the differences between
+/// the two executions, as well as whether the baseline execution forked from the chain.
+#[allow(clippy::result_large_err)]
+async fn diff_transaction(
+    executor: &mut LocalExec,
+    digest: &TransactionDigest,
+    config: &DiffReplayConfig,
+) -> Result<(Vec<Difference>, bool), ReplayEngineError> {
+    let baseline = executor
+        .execute_transaction(
+            digest,
+            config.expensive_safety_check_config.clone(),
+            false,
+            config.baseline_executor_version,
+            None,
+            None,
+            None,
+        )
+        .await?;
+
+    let candidate = executor
+        .execute_transaction(
+            digest,
+            config.expensive_safety_check_config.clone(),
+            false,
+            config.candidate_executor_version,
+            None,
+            None,
+            None,
+        )
+        .await?;
+
+    let baseline_forked = baseline.transaction_info.effects != baseline.local_exec_effects;
+    Ok((diff_executions(&baseline, &candidate), baseline_forked))
+}
+
+fn diff_executions(
+    baseline: &ExecutionSandboxState,
+    candidate: &ExecutionSandboxState,
+) -> Vec<Difference> {
+    let mut differences = vec![];
+    let (b_effects, c_effects) = (&baseline.local_exec_effects, &candidate.local_exec_effects);
+
+    if b_effects.status() != c_effects.status() {
+        differences.push(Difference::Status {
+            baseline: b_effects.status().clone(),
+            candidate: c_effects.status().clone(),
+        });
+    }
+
+    if b_effects.gas_cost_summary() != c_effects.gas_cost_summary() {
+        differences.push(Difference::Gas {
+            baseline: b_effects.gas_cost_summary().clone(),
+            candidate: c_effects.gas_cost_summary().clone(),
+        });
+    }
+
+    // Transactions executed through the execution engine always capture their temporary store.
+    if let (Some(b_store), Some(c_store)) = (
+        &baseline.local_exec_temporary_store,
+        &candidate.local_exec_temporary_store,
+    ) {
+        if b_store.events != c_store.events {
+            differences.push(Difference::Events {
+                diff: diff_debug(&b_store.events, &c_store.events),
+            });
+        }
+
+        let written: BTreeSet<_> = b_store
+            .written
+            .keys()
+            .chain(c_store.written.keys())
+            .collect();
+
+        for id in written {
+            let b_object = b_store.written.get(id);
+            let c_object = c_store.written.get(id);
+            if b_object != c_object {
+                differences.push(Difference::Object {
+                    id: *id,
+                    baseline: b_object.map(|o| o.compute_object_reference()),
+                    candidate: c_object.map(|o| o.compute_object_reference()),
+                    diff: diff_debug(&b_object, &c_object),
+                });
+            }
+        }
+    }
+
+    if b_effects != c_effects {
+        differences.push(Difference::Effects {
+            diff: diff_debug(b_effects, c_effects),
+        });
+    }
+
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OnChainTransactionInfo;
+    use move_binary_format::binary_config::BinaryConfig;
+    use std::collections::BTreeMap;
+    use sui_json_rpc_types::{SuiTransactionBlockEffects, SuiTransactionBlockEffectsV1};
+    use sui_protocol_config::{Chain, ProtocolVersion};
+    use sui_types::base_types::{SequenceNumber, SuiAddress};
+    use sui_types::effects::{TestEffectsBuilder, TransactionEvents};
+    use sui_types::event::Event;
+    use sui_types::inner_temporary_store::InnerTemporaryStore;
+    use sui_types::object::Object;
+    use sui_types::transaction::{SenderSignedData, TransactionData, TransactionDataAPI};
+
+    fn object(id: u8, gas: u64) -> Object {
+        Object::with_id_owner_gas_for_testing(ObjectID::new([id; 32]), SuiAddress::ZERO, gas)
+    }
+
+    /// The state of a sandbox that executed the same transfer, writing `written` and emitting
+    /// `events`.
+    fn execution(written: &[Object], events: &[Event]) -> ExecutionSandboxState {
+        let gas = object(0, 1_000_000_000);
+        let data = TransactionData::new_transfer_sui(
+            SuiAddress::ZERO,
+            SuiAddress::ZERO,
+            None,
+            gas.compute_object_reference(),
+            1_000_000,
+            1_000,
+        );
+
+        let signed = SenderSignedData::new(data.clone(), vec![]);
+        let effects = TestEffectsBuilder::new(&signed).build();
+        let effects = SuiTransactionBlockEffects::try_from(effects).unwrap();
+
+        ExecutionSandboxState {
+            transaction_info: OnChainTransactionInfo {
+                tx_digest: *effects.transaction_digest(),
+                sender_signed_data: signed,
+                sender: data.sender(),
+                input_objects: vec![],
+                kind: data.kind().clone(),
+                modified_at_versions: vec![],
+                shared_object_refs: vec![],
+                gas: vec![gas.compute_object_reference()],
+                gas_owner: None,
+                gas_budget: data.gas_budget(),
+                gas_price: data.gas_price(),
+                executed_epoch: 0,
+                dependencies: vec![],
+                receiving_objs: vec![],
+                config_objects: vec![],
+                effects: effects.clone(),
+                protocol_version: ProtocolVersion::MAX,
+                epoch_start_timestamp: 0,
+                reference_gas_price: 1_000,
+                chain: Chain::Unknown,
+            },
+            required_objects: vec![gas],
+            local_exec_temporary_store: Some(InnerTemporaryStore {
+                input_objects: BTreeMap::new(),
+                stream_ended_consensus_objects: BTreeMap::new(),
+                mutable_inputs: BTreeMap::new(),
+                written: written.iter().map(|o| (o.id(), o.clone())).collect(),
+                loaded_runtime_objects: BTreeMap::new(),
+                events: TransactionEvents {
+                    data: events.to_vec(),
+                },
+                binary_config: BinaryConfig::standard(),
+                runtime_packages_loaded_from_db: BTreeMap::new(),
+                lamport_version: SequenceNumber::from_u64(1),
+            }),
+            local_exec_effects: effects,
+            local_exec_status: Some(Ok(())),
+        }
+    }
+
+    fn effects_mut(state: &mut ExecutionSandboxState) -> &mut SuiTransactionBlockEffectsV1 {
+        let SuiTransactionBlockEffects::V1(effects) = &mut state.local_exec_effects;
+        effects
+    }
+
+    fn diff(digest: u8, checkpoint: u64) -> TransactionDiff {
+        TransactionDiff {
+            digest: TransactionDigest::new([digest; 32]),
+            checkpoint,
+            differences: vec![],
+        }
+    }
+
+    fn failure(digest: u8, checkpoint: u64) -> ReplayFailure {
+        ReplayFailure {
+            digest: TransactionDigest::new([digest; 32]),
+            checkpoint,
+            error: "failed".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_identical_executions() {
+        let written = [object(1, 100), object(2, 200)];
+        let events = [Event::random_for_testing()];
+
+        let baseline = execution(&written, &events);
+        let candidate = execution(&written, &events);
+        assert!(diff_executions(&baseline, &candidate).is_empty());
+    }
+
+    #[test]
+    fn test_status_difference() {
+        let baseline = execution(&[], &[]);
+        let mut candidate = execution(&[], &[]);
+        effects_mut(&mut candidate).status = SuiExecutionStatus::Failure {
+            error: "InsufficientGas".to_string(),
+        };
+
+        let differences = diff_executions(&baseline, &candidate);
+        assert!(
+            matches!(
+                differences.as_slice(),
+                [
+                    Difference::Status {
+                        baseline: SuiExecutionStatus::Success,
+                        candidate: SuiExecutionStatus::Failure { .. },
+                    },
+                    Difference::Effects { .. },
+                ]
+            ),
+            "{differences:#?}"
+        );
+    }
+
+    #[test]
+    fn test_gas_difference() {
+        let baseline = execution(&[], &[]);
+        let mut candidate = execution(&[], &[]);
+        effects_mut(&mut candidate).gas_used.computation_cost += 1;
+
+        let differences = diff_executions(&baseline, &candidate);
+        let [Difference::Gas {
+            baseline: b_gas,
+            candidate: c_gas,
+        }, Difference::Effects { .. }] = differences.as_slice()
+        else {
+            panic!("Unexpected differences: {differences:#?}");
+        };
+
+        assert_eq!(b_gas.computation_cost + 1, c_gas.computation_cost);
+    }
+
+    #[test]
+    fn test_events_difference() {
+        let event = Event::random_for_testing();
+        let baseline = execution(&[], &[event.clone()]);
+        let candidate = execution(&[], &[event, Event::random_for_testing()]);
+
+        // Events are compared through the temporary store, so the effects alone don't differ.
+        let differences = diff_executions(&baseline, &candidate);
+        let [Difference::Events { diff }] = differences.as_slice() else {
+            panic!("Unexpected differences: {differences:#?}");
+        };
+
+        assert!(diff.contains("+++"), "{diff}");
+        assert!(!diff.contains("---"), "{diff}");
+    }
+
+    #[test]
+    fn test_object_differences() {
+        let (changed, unchanged) = (object(1, 100), object(2, 200));
+        let (b_only, c_only) = (object(3, 300), object(4, 400));
+
+        let baseline = execution(&[changed.clone(), unchanged.clone(), b_only.clone()], &[]);
+        let candidate = execution(&[object(1, 101), unchanged, c_only.clone()], &[]);
+
+        let differences = diff_executions(&baseline, &candidate);
+        let objects: Vec<_> = differences
+            .iter()
+            .map(|difference| match difference {
+                Difference::Object {
+                    id,
+                    baseline,
+                    candidate,
+                    ..
+                } => (*id, *baseline, *candidate),
+                _ => panic!("Unexpected differences: {differences:#?}"),
+            })
+            .collect();
+
+        assert_eq!(
+            objects,
+            vec![
+                (
+                    changed.id(),
+                    Some(changed.compute_object_reference()),
+                    Some(object(1, 101).compute_object_reference()),
+                ),
+                (b_only.id(), Some(b_only.compute_object_reference()), None),
+                (c_only.id(), None, Some(c_only.compute_object_reference())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_temporary_store() {
+        let baseline = execution(&[object(1, 100)], &[Event::random_for_testing()]);
+        let mut candidate = execution(&[], &[]);
+        candidate.local_exec_temporary_store = None;
+
+        // Without both temporary stores, only the effects can be compared.
+        assert!(diff_executions(&baseline, &candidate).is_empty());
+    }
+
+    #[test]
+    fn test_merge_reports() {
+        let mut report = DiffReport {
+            transactions_replayed: 5,
+            diffs: vec![diff(1, 3)],
+            baseline_forks: vec![TransactionDigest::new([1; 32])],
+            failures: vec![failure(2, 4)],
+        };
+
+        report.merge(DiffReport {
+            transactions_replayed: 4,
+            diffs: vec![diff(3, 1), diff(4, 2)],
+            baseline_forks: vec![],
+            failures: vec![failure(5, 0)],
+        });
+
+        assert_eq!(report.transactions_replayed, 9);
+        assert_eq!(report.identical(), 4);
+        assert_eq!(report.baseline_forks, vec![TransactionDigest::new([1; 32])]);
+
+        let diffs: Vec<_> = report.diffs.iter().map(|d| d.checkpoint).collect();
+        assert_eq!(diffs, vec![1, 2, 3]);
+
+        let failures: Vec<_> = report.failures.iter().map(|f| f.checkpoint).collect();
+        assert_eq!(failures, vec![0, 4]);
+    }
+}
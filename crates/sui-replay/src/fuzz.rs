@@ -2,24 +2,29 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use sui_config::node::ExpensiveSafetyCheckConfig;
+use sui_protocol_config::ProtocolConfig;
 use sui_types::{
     digests::TransactionDigest, execution_status::ExecutionFailureStatus,
     transaction::TransactionKind,
 };
 use thiserror::Error;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::{
-    replay::{ExecutionSandboxState, LocalExec},
+    fuzz_coverage::{Corpus, Coverage, CoverageTracker},
+    fuzz_invariants::{ExecutedMutation, InvariantCheck},
+    replay::{get_executor, ExecutionSandboxState, LocalExec},
     transaction_provider::{TransactionProvider, TransactionSource},
     types::ReplayEngineError,
 };
 
 // Step 1: Get a transaction T from the network
 // Step 2: Create the sandbox and verify the TX does not fork locally
-// Step 3: Create desired mutations of T in set S
-// Step 4: For each mutation in S, replay the transaction with the sandbox state from T
-//         and verify no panic or invariant violation
+// Step 3: Pick a transaction from the corpus, which starts out as just T, and mutate it
+// Step 4: Replay the mutation with the sandbox state from T, and verify no panic, execution
+//         invariant violation, or violation of the configured invariant checks
+// Step 5: If the mutation reached Move code no previous execution reached, add it to the corpus,
+//         so that it is more likely to be mutated further
 
 pub struct ReplayFuzzerConfig {
     pub num_mutations_per_base: u64,
@@ -27,6 +32,11 @@ pub struct ReplayFuzzerConfig {
     pub tx_source: TransactionSource,
     pub fail_over_on_err: bool,
     pub expensive_safety_check_config: ExpensiveSafetyCheckConfig,
+    /// Prioritise mutations that reach new Move code. Coverage is only available when built with
+    /// the `tracing` feature; without it, each mutation is applied to the previous one.
+    pub coverage_guided: bool,
+    /// Checks that every successfully executed mutation must pass.
+    pub invariants: Vec<Box<dyn InvariantCheck + Send + Sync>>,
 }
 
 /// Provides the starting transaction for a fuzz session
@@ -35,6 +45,8 @@ pub struct ReplayFuzzer {
     pub sandbox_state: ExecutionSandboxState,
     pub config: ReplayFuzzerConfig,
     pub transaction_provider: TransactionProvider,
+    /// The Move code reached by every execution so far, across all base transactions.
+    pub coverage: Coverage,
 }
 
 pub trait TransactionKindMutator {
@@ -52,14 +64,27 @@ impl ReplayFuzzer {
 
         let mut tx_provider = TransactionProvider::new(&rpc_url, config.tx_source.clone()).await?;
 
-        Self::new_with_local_executor(local_exec, config, &mut tx_provider).await
+        if config.coverage_guided && !cfg!(feature = "tracing") {
+            warn!(
+                "Coverage feedback requires the `tracing` feature, falling back to blind mutation"
+            );
+        }
+
+        Self::new_with_local_executor(local_exec, config, &mut tx_provider, Coverage::default())
+            .await
     }
 
     pub async fn new_with_local_executor(
         mut local_exec: LocalExec,
         config: ReplayFuzzerConfig,
         transaction_provider: &mut TransactionProvider,
+        mut coverage: Coverage,
     ) -> Result<Self, anyhow::Error> {
+        // Without the `tracing` feature the VM produces no trace to collect coverage from.
+        if config.coverage_guided && cfg!(feature = "tracing") {
+            local_exec.coverage = Some(CoverageTracker::default());
+        }
+
         // Seed with the first transaction
         let base_transaction = transaction_provider.next().await?.unwrap_or_else(|| {
             panic!(
@@ -79,11 +104,17 @@ impl ReplayFuzzer {
             )
             .await?;
 
+        // Code reached by the base transaction is not new to its mutations.
+        if let Some(tracker) = &local_exec.coverage {
+            coverage.merge(tracker.take());
+        }
+
         Ok(Self {
             local_exec,
             sandbox_state,
             config,
             transaction_provider: transaction_provider.clone(),
+            coverage,
         })
     }
 
@@ -95,8 +126,13 @@ impl ReplayFuzzer {
         self.config
             .mutator
             .reset(self.config.num_mutations_per_base);
-        Self::new_with_local_executor(local_executor, self.config, &mut self.transaction_provider)
-            .await
+        Self::new_with_local_executor(
+            local_executor,
+            self.config,
+            &mut self.transaction_provider,
+            self.coverage,
+        )
+        .await
     }
 
     pub async fn execute_tx(
@@ -134,6 +170,82 @@ impl ReplayFuzzer {
         Ok(sandbox_state)
     }
 
+    /// Execute `transaction_kind`, check that its execution upholds every configured invariant,
+    /// and return its sandbox state along with the number of Move functions and instructions it
+    /// reached that no previous execution did.
+    pub async fn execute_mutation(
+        &mut self,
+        transaction_kind: &TransactionKind,
+    ) -> Result<(ExecutionSandboxState, usize), ReplayFuzzError> {
+        let sandbox_state = self.execute_tx_and_check_status(transaction_kind).await?;
+        let coverage = self.take_coverage();
+
+        let reexecution = if self.config.invariants.iter().any(|c| c.needs_reexecution()) {
+            let sandbox_state = self.execute_tx(transaction_kind).await?;
+            self.take_coverage();
+            Some(sandbox_state)
+        } else {
+            None
+        };
+
+        self.check_invariants(transaction_kind, &sandbox_state, reexecution.as_ref())?;
+        let new_coverage = self.coverage.merge(coverage);
+        Ok((sandbox_state, new_coverage))
+    }
+
+    fn check_invariants(
+        &mut self,
+        transaction_kind: &TransactionKind,
+        sandbox_state: &ExecutionSandboxState,
+        reexecution: Option<&ExecutionSandboxState>,
+    ) -> Result<(), ReplayFuzzError> {
+        let tx_info = &sandbox_state.transaction_info;
+        let protocol_config =
+            ProtocolConfig::get_for_version(tx_info.protocol_version, tx_info.chain);
+        let executor = get_executor(
+            self.local_exec.executor_version,
+            &protocol_config,
+            self.config.expensive_safety_check_config.clone(),
+            None,
+        );
+        let mut layout_resolver = executor.type_layout_resolver(Box::new(&self.local_exec));
+
+        let mut mutation = ExecutedMutation {
+            kind: transaction_kind,
+            execution: sandbox_state,
+            reexecution,
+            layout_resolver: layout_resolver.as_mut(),
+            store: &self.local_exec,
+        };
+
+        for check in self.config.invariants.iter_mut() {
+            if let Err(e) = check.check(&mut mutation) {
+                return Err(ReplayFuzzError::InvariantCheckFailed {
+                    tx_digest: tx_info.tx_digest,
+                    kind: transaction_kind.clone(),
+                    check: check.name().to_string(),
+                    reason: format!("{e:#}"),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether executions report the Move code they reach, so that mutations can be picked from
+    /// the corpus by coverage.
+    fn coverage_enabled(&self) -> bool {
+        self.local_exec.coverage.is_some()
+    }
+
+    fn take_coverage(&mut self) -> Coverage {
+        self.local_exec
+            .coverage
+            .as_ref()
+            .map(|tracker| tracker.take())
+            .unwrap_or_default()
+    }
+
     // Simple command and arg shuffle mutation
     // TODO: do more complicated mutations
     pub fn next_mutation(&mut self, transaction_kind: &TransactionKind) -> Option<TransactionKind> {
@@ -142,19 +254,38 @@ impl ReplayFuzzer {
 
     pub async fn run(mut self, mut num_base_tx: u64) -> Result<(), ReplayFuzzError> {
         while num_base_tx > 0 {
+            // With coverage, mutations are picked from a corpus of those that reached new code.
+            // Without it, each mutation is applied to the previous one.
+            let coverage_enabled = self.coverage_enabled();
             let mut tx_kind = self.sandbox_state.transaction_info.kind.clone();
+            let mut corpus = Corpus::new(tx_kind.clone());
 
             info!(
                 "Starting fuzz with new base TX {}, with at most {} mutations",
                 self.sandbox_state.transaction_info.tx_digest, self.config.num_mutations_per_base
             );
-            while let Some(mutation) = self.next_mutation(&tx_kind) {
+            loop {
+                let parent = if coverage_enabled {
+                    corpus.select()
+                } else {
+                    &tx_kind
+                };
+                let Some(mutation) = self.next_mutation(parent) else {
+                    break;
+                };
                 info!(
                     "Executing mutation: base tx {}, mutation {:?}",
                     self.sandbox_state.transaction_info.tx_digest, mutation
                 );
-                match self.execute_tx_and_check_status(&mutation).await {
-                    Ok(v) => tx_kind = v.transaction_info.kind.clone(),
+                match self.execute_mutation(&mutation).await {
+                    Ok((v, _)) if !coverage_enabled => tx_kind = v.transaction_info.kind.clone(),
+                    Ok((_, 0)) => (),
+                    Ok((_, new_coverage)) => {
+                        info!(
+                            "Mutation reached {new_coverage} new functions and instructions, adding it to the corpus"
+                        );
+                        corpus.add(mutation, new_coverage);
+                    }
                     Err(e) => {
                         error!(
                             "Error executing transaction: base tx: {}, mutation: {:?} with error{:?}",
@@ -168,8 +299,11 @@ impl ReplayFuzzer {
                 }
             }
             info!(
-                "Ended fuzz with for base TX {}\n",
-                self.sandbox_state.transaction_info.tx_digest
+                "Ended fuzz with for base TX {}: {} transactions in corpus, {} functions and {} instructions covered so far\n",
+                self.sandbox_state.transaction_info.tx_digest,
+                corpus.len(),
+                self.coverage.num_functions(),
+                self.coverage.num_instructions(),
             );
             self = self
                 .re_init()
@@ -196,6 +330,16 @@ pub enum ReplayFuzzError {
         exec_status: ExecutionFailureStatus,
     },
 
+    #[error(
+        "InvariantCheckFailed: digest: {tx_digest}, kind: {kind}, check: {check}, reason: {reason}"
+    )]
+    InvariantCheckFailed {
+        tx_digest: TransactionDigest,
+        kind: TransactionKind,
+        check: String,
+        reason: String,
+    },
+
     #[error(
         "LocalExecError: exec system error which may/not be related to fuzzing: {:?}.",
         err
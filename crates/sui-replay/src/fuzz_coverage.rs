@@ -0,0 +1,244 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::language_storage::ModuleId;
+use move_trace_format::format::TraceEvent;
+use move_trace_format::interface::{Tracer, Writer};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::SeedableRng;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use sui_types::transaction::TransactionKind;
+
+// Coverage feedback for the fuzzer:
+// * `Coverage` is the set of Move functions and instructions reached by one or more executions.
+// * `CoverageTracker` collects the coverage of executions run by a `LocalExec`, by listening to
+//   the Move VM's execution trace. The VM only produces traces when built with the `tracing`
+//   feature, so without it every execution reports empty coverage.
+// * `Corpus` holds the mutations that reached new code, and picks the next one to mutate,
+//   favouring those that discovered the most.
+
+/// The Move functions and instructions reached by one or more executions.
+#[derive(Clone, Debug, Default)]
+pub struct Coverage {
+    functions: BTreeSet<(ModuleId, String)>,
+    instructions: BTreeSet<(ModuleId, String, u16)>,
+}
+
+/// Collects the coverage of every execution run with it, until it is taken.
+#[derive(Clone, Default)]
+pub struct CoverageTracker {
+    coverage: Arc<Mutex<Coverage>>,
+}
+
+/// Records coverage from the events of a single execution, and hands it to its tracker when the
+/// execution's trace is dropped.
+pub(crate) struct CoverageTracer {
+    tracker: CoverageTracker,
+    coverage: Coverage,
+    frames: Vec<(ModuleId, String)>,
+}
+
+/// Mutations worth mutating further, each with the energy left to spend on it.
+pub struct Corpus {
+    rng: rand::rngs::StdRng,
+    entries: Vec<(TransactionKind, u64)>,
+}
+
+impl Coverage {
+    /// Add everything `other` covers to this coverage, and return how many functions and
+    /// instructions were not covered before.
+    pub fn merge(&mut self, other: Coverage) -> usize {
+        let mut new = 0;
+        for function in other.functions {
+            new += self.functions.insert(function) as usize;
+        }
+        for instruction in other.instructions {
+            new += self.instructions.insert(instruction) as usize;
+        }
+        new
+    }
+
+    pub fn num_functions(&self) -> usize {
+        self.functions.len()
+    }
+
+    pub fn num_instructions(&self) -> usize {
+        self.instructions.len()
+    }
+}
+
+impl CoverageTracker {
+    /// The coverage of every execution since the last call.
+    pub fn take(&self) -> Coverage {
+        std::mem::take(&mut *self.coverage.lock().expect("Unable to lock coverage"))
+    }
+
+    pub(crate) fn tracer(&self) -> CoverageTracer {
+        CoverageTracer {
+            tracker: self.clone(),
+            coverage: Coverage::default(),
+            frames: vec![],
+        }
+    }
+}
+
+impl Tracer for CoverageTracer {
+    fn notify(&mut self, event: &TraceEvent, _writer: Writer<'_>) {
+        match event {
+            TraceEvent::OpenFrame { frame, .. } => {
+                let function = (frame.module.clone(), frame.function_name.clone());
+                self.coverage.functions.insert(function.clone());
+                self.frames.push(function);
+            }
+            TraceEvent::CloseFrame { .. } => {
+                self.frames.pop();
+            }
+            TraceEvent::Instruction { pc, .. } => {
+                if let Some((module, function)) = self.frames.last() {
+                    self.coverage
+                        .instructions
+                        .insert((module.clone(), function.clone(), *pc));
+                }
+            }
+            TraceEvent::Effect(_) | TraceEvent::External(_) => (),
+        }
+    }
+}
+
+impl Drop for CoverageTracer {
+    fn drop(&mut self) {
+        self.tracker
+            .coverage
+            .lock()
+            .expect("Unable to lock coverage")
+            .merge(std::mem::take(&mut self.coverage));
+    }
+}
+
+impl Corpus {
+    /// A corpus seeded with the transaction being fuzzed.
+    pub fn new(base: TransactionKind) -> Self {
+        Self {
+            rng: rand::rngs::StdRng::from_seed([0u8; 32]),
+            entries: vec![(base, 1)],
+        }
+    }
+
+    /// Add a mutation that covered `new_coverage` functions and instructions for the first time.
+    pub fn add(&mut self, mutation: TransactionKind, new_coverage: usize) {
+        self.entries.push((mutation, new_coverage as u64));
+    }
+
+    /// Pick the next transaction to mutate, weighted by energy. Each pick halves the energy of the
+    /// transaction picked, so that the fuzzer moves on once a discovery has been explored, but
+    /// never below one, so that every transaction in the corpus can still be picked.
+    pub fn select(&mut self) -> &TransactionKind {
+        let index = WeightedIndex::new(self.entries.iter().map(|(_, energy)| *energy))
+            .map(|weights| weights.sample(&mut self.rng))
+            .unwrap_or(0);
+
+        let (kind, energy) = &mut self.entries[index];
+        *energy = (*energy / 2).max(1);
+        kind
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+    fn module(name: &str) -> ModuleId {
+        ModuleId::new(AccountAddress::ONE, Identifier::new(name).unwrap())
+    }
+
+    fn coverage(functions: &[&str], instructions: &[(&str, u16)]) -> Coverage {
+        Coverage {
+            functions: functions
+                .iter()
+                .map(|f| (module("m"), f.to_string()))
+                .collect(),
+            instructions: instructions
+                .iter()
+                .map(|(f, pc)| (module("m"), f.to_string(), *pc))
+                .collect(),
+        }
+    }
+
+    fn kind(n: u64) -> TransactionKind {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.pure(n).unwrap();
+        TransactionKind::ProgrammableTransaction(builder.finish())
+    }
+
+    #[test]
+    fn merge_counts_only_new_coverage() {
+        let mut total = Coverage::default();
+        assert_eq!(total.merge(coverage(&["f"], &[("f", 0), ("f", 1)])), 3);
+
+        // Only `g` and its instruction are new.
+        assert_eq!(total.merge(coverage(&["f", "g"], &[("f", 1), ("g", 0)])), 2);
+        assert_eq!(total.num_functions(), 2);
+        assert_eq!(total.num_instructions(), 3);
+
+        assert_eq!(total.merge(coverage(&["g"], &[("g", 0)])), 0);
+        assert_eq!(total.merge(Coverage::default()), 0);
+    }
+
+    #[test]
+    fn merge_distinguishes_modules() {
+        let mut total = coverage(&["f"], &[]);
+        let other = Coverage {
+            functions: BTreeSet::from([(module("n"), "f".to_string())]),
+            instructions: BTreeSet::new(),
+        };
+        assert_eq!(total.merge(other), 1);
+        assert_eq!(total.num_functions(), 2);
+    }
+
+    #[test]
+    fn corpus_starts_with_base() {
+        let mut corpus = Corpus::new(kind(0));
+        assert_eq!(corpus.len(), 1);
+        assert!(!corpus.is_empty());
+        for _ in 0..10 {
+            assert_eq!(corpus.select(), &kind(0));
+        }
+    }
+
+    #[test]
+    fn corpus_favours_discoveries() {
+        let mut corpus = Corpus::new(kind(0));
+        corpus.add(kind(1), 1000);
+        assert_eq!(corpus.len(), 2);
+
+        // The new mutation has almost all the energy to begin with.
+        let picks = (0..5).filter(|_| corpus.select() == &kind(1)).count();
+        assert!(picks >= 4, "Mutation picked {picks} times out of 5");
+    }
+
+    #[test]
+    fn corpus_energy_decays() {
+        let mut corpus = Corpus::new(kind(0));
+        corpus.add(kind(1), 1000);
+
+        // Each pick halves the energy of the mutation, so once it has been explored, both
+        // entries are equally likely to be picked.
+        for _ in 0..100 {
+            corpus.select();
+        }
+        let base_picks = (0..100).filter(|_| corpus.select() == &kind(0)).count();
+        assert!(base_picks > 0 && base_picks < 100);
+    }
+}
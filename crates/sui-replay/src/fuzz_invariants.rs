@@ -0,0 +1,47 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::layout_resolver::LayoutResolver;
+use sui_types::storage::ObjectStore;
+use sui_types::transaction::TransactionKind;
+
+use crate::replay::ExecutionSandboxState;
+
+pub mod deterministic_effects;
+pub mod sui_conservation;
+pub mod version_monotonicity;
+
+// Invariant checks flag mutations that execute "successfully" but whose outputs are semantically
+// wrong, which a crash-only fuzzer would miss. Each check is handed the execution of a mutation,
+// and the state it executed against, and returns an error describing any violation it finds.
+
+/// Everything an `InvariantCheck` can inspect about the execution of a mutation.
+pub struct ExecutedMutation<'a> {
+    pub kind: &'a TransactionKind,
+    pub execution: &'a ExecutionSandboxState,
+    /// A second, independent execution of the same mutation, present if any check needs it.
+    pub reexecution: Option<&'a ExecutionSandboxState>,
+    /// Resolves the layouts of Move types, to look inside the objects the mutation touched.
+    pub layout_resolver: &'a mut dyn LayoutResolver,
+    /// The objects the mutation executed against, as they were before it ran.
+    pub store: &'a dyn ObjectStore,
+}
+
+pub trait InvariantCheck {
+    fn name(&self) -> &'static str;
+
+    /// Whether the check needs every mutation to be executed a second time.
+    fn needs_reexecution(&self) -> bool {
+        false
+    }
+
+    fn check(&mut self, mutation: &mut ExecutedMutation<'_>) -> anyhow::Result<()>;
+}
+
+pub fn base_invariants() -> Vec<Box<dyn InvariantCheck + Send + Sync>> {
+    vec![
+        Box::new(sui_conservation::SuiConservation),
+        Box::new(version_monotonicity::VersionMonotonicity),
+        Box::new(deterministic_effects::DeterministicEffects),
+    ]
+}
@@ -0,0 +1,59 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::bail;
+
+use crate::fuzz_invariants::{ExecutedMutation, InvariantCheck};
+use crate::replay::diff_debug;
+
+/// Executing the same transaction against the same state twice produces the same effects, events
+/// and objects.
+pub struct DeterministicEffects;
+
+impl InvariantCheck for DeterministicEffects {
+    fn name(&self) -> &'static str {
+        "deterministic_effects"
+    }
+
+    fn needs_reexecution(&self) -> bool {
+        true
+    }
+
+    fn check(&mut self, mutation: &mut ExecutedMutation<'_>) -> anyhow::Result<()> {
+        let Some(reexecution) = mutation.reexecution else {
+            return Ok(());
+        };
+        let execution = mutation.execution;
+
+        if execution.local_exec_effects != reexecution.local_exec_effects {
+            bail!(
+                "Effects differ between executions:\n{}",
+                diff_debug(
+                    &execution.local_exec_effects,
+                    &reexecution.local_exec_effects
+                )
+            );
+        }
+
+        if let (Some(first), Some(second)) = (
+            &execution.local_exec_temporary_store,
+            &reexecution.local_exec_temporary_store,
+        ) {
+            if first.events != second.events {
+                bail!(
+                    "Events differ between executions:\n{}",
+                    diff_debug(&first.events, &second.events)
+                );
+            }
+
+            if first.written != second.written {
+                bail!(
+                    "Written objects differ between executions:\n{}",
+                    diff_debug(&first.written, &second.written)
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail};
+use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
+use tracing::warn;
+
+use crate::fuzz_invariants::{ExecutedMutation, InvariantCheck};
+
+/// SUI is neither created nor destroyed: all SUI in the objects a transaction modifies, including
+/// their storage rebates, ends up in the objects it writes, or is burned as computation fees or
+/// non-refundable storage fees.
+pub struct SuiConservation;
+
+impl InvariantCheck for SuiConservation {
+    fn name(&self) -> &'static str {
+        "sui_conservation"
+    }
+
+    fn check(&mut self, mutation: &mut ExecutedMutation<'_>) -> anyhow::Result<()> {
+        // System transactions mint and burn SUI at epoch boundaries.
+        if mutation.kind.is_system_tx() {
+            return Ok(());
+        }
+
+        let Some(inner_store) = &mutation.execution.local_exec_temporary_store else {
+            return Ok(());
+        };
+        let effects = &mutation.execution.local_exec_effects;
+
+        let mut total_input_sui = 0u64;
+        for (id, version) in effects.modified_at_versions() {
+            let input = match inner_store.input_objects.get(&id) {
+                Some(object) if object.version() == version => Some(object.clone()),
+                _ => mutation.store.get_object_by_key(&id, version),
+            };
+
+            let Some(input) = input else {
+                // This is a limitation of the replay, rather than a violation.
+                warn!("Skipping SUI conservation check: input {id} at version {version} not found");
+                return Ok(());
+            };
+
+            total_input_sui += input
+                .get_total_sui(mutation.layout_resolver)
+                .map_err(|e| anyhow!("Failed to count SUI in input {id}: {e}"))?;
+        }
+
+        let mut total_output_sui = 0u64;
+        for (id, output) in &inner_store.written {
+            total_output_sui += output
+                .get_total_sui(mutation.layout_resolver)
+                .map_err(|e| anyhow!("Failed to count SUI in output {id}: {e}"))?;
+        }
+
+        let gas = effects.gas_cost_summary();
+        let burned = gas.computation_cost + gas.non_refundable_storage_fee;
+        if total_input_sui != total_output_sui + burned {
+            bail!(
+                "{total_input_sui} SUI in inputs, but {total_output_sui} SUI in outputs and \
+                 {burned} SUI burned"
+            );
+        }
+
+        Ok(())
+    }
+}
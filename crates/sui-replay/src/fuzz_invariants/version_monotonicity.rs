@@ -0,0 +1,46 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::bail;
+use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
+
+use crate::fuzz_invariants::{ExecutedMutation, InvariantCheck};
+
+/// Every object a transaction writes is written at the transaction's lamport version, which is
+/// greater than the version of every object it modified. Packages are versioned independently, so
+/// are exempt.
+pub struct VersionMonotonicity;
+
+impl InvariantCheck for VersionMonotonicity {
+    fn name(&self) -> &'static str {
+        "version_monotonicity"
+    }
+
+    fn check(&mut self, mutation: &mut ExecutedMutation<'_>) -> anyhow::Result<()> {
+        let Some(inner_store) = &mutation.execution.local_exec_temporary_store else {
+            return Ok(());
+        };
+        let lamport_version = inner_store.lamport_version;
+
+        for (id, version) in mutation.execution.local_exec_effects.modified_at_versions() {
+            if version >= lamport_version {
+                bail!(
+                    "Object {id} was modified at version {version}, which is not before the \
+                     transaction's lamport version {lamport_version}"
+                );
+            }
+        }
+
+        for (id, object) in &inner_store.written {
+            if !object.is_package() && object.version() != lamport_version {
+                bail!(
+                    "Object {id} was written at version {}, instead of the transaction's lamport \
+                     version {lamport_version}",
+                    object.version()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use archive_fetcher::{ArchiveConfig, ArchiveFetcher};
 use async_recursion::async_recursion;
 use clap::Parser;
 use config::ReplayableNetworkConfigSet;
+use diff_replay::{DiffReplayConfig, DiffReport};
 use fuzz::ReplayFuzzer;
 use fuzz::ReplayFuzzerConfig;
+use fuzz_invariants::base_invariants;
 use fuzz_mutations::base_fuzzers;
 use std::cmp::max;
 use sui_types::base_types::ObjectID;
@@ -30,11 +33,15 @@ use sui_protocol_config::Chain;
 use sui_types::digests::TransactionDigest;
 use tracing::{error, info};
 
+pub mod archive_fetcher;
 pub mod batch_replay;
 pub mod config;
-mod data_fetcher;
+pub mod data_fetcher;
+pub mod diff_replay;
 mod displays;
 pub mod fuzz;
+pub mod fuzz_coverage;
+pub mod fuzz_invariants;
 pub mod fuzz_mutations;
 mod replay;
 #[cfg(test)]
@@ -182,6 +189,31 @@ pub enum ReplayToolCommand {
         max_tasks: u64,
     },
 
+    /// Replay all transactions in a range of checkpoints under two executors, and report every
+    /// transaction whose status, gas, events, written objects or effects differ between them
+    #[command(name = "diff")]
+    DiffCheckpoints {
+        #[arg(long, short)]
+        start: u64,
+        #[arg(long, short)]
+        end: u64,
+        /// Version of the executor to compare against the baseline, -1 for the latest executor in
+        /// this build.
+        #[arg(long, allow_hyphen_values = true)]
+        executor_version: i64,
+        /// Optional version of the executor to use as the baseline, if not specified defaults to
+        /// the one originally used for each transaction.
+        #[arg(long, allow_hyphen_values = true)]
+        baseline_executor_version: Option<i64>,
+        #[arg(long, short)]
+        terminate_early: bool,
+        #[arg(long, short, default_value = "16")]
+        max_tasks: u64,
+        /// Optional file to write the full report to, as JSON.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
     /// Run the replay based fuzzer
     #[command(name = "fz")]
     Fuzz {
@@ -191,6 +223,14 @@ pub enum ReplayToolCommand {
         num_mutations_per_base: u64,
         #[arg(long, short = 'b', default_value = "18446744073709551614")]
         num_base_transactions: u64,
+        /// Mutate the base transaction only, instead of prioritising mutations that reached new
+        /// Move code. Coverage feedback requires building with the `tracing` feature.
+        #[arg(long)]
+        blind: bool,
+        /// Only report crashes and execution invariant violations, without checking SUI
+        /// conservation, object versions or determinism.
+        #[arg(long)]
+        skip_invariant_checks: bool,
     },
 
     #[command(name = "report")]
@@ -256,6 +296,8 @@ pub async fn execute_replay_command(
             start,
             num_mutations_per_base,
             num_base_transactions,
+            blind,
+            skip_invariant_checks,
         } => {
             let config = ReplayFuzzerConfig {
                 num_mutations_per_base,
@@ -263,6 +305,12 @@ pub async fn execute_replay_command(
                 tx_source: TransactionSource::TailLatest { start },
                 fail_over_on_err: false,
                 expensive_safety_check_config: Default::default(),
+                coverage_guided: !blind,
+                invariants: if skip_invariant_checks {
+                    vec![]
+                } else {
+                    base_invariants()
+                },
             };
             let fuzzer = ReplayFuzzer::new(get_rpc_url(rpc_url, cfg_path, chain)?, config)
                 .await
@@ -519,6 +567,77 @@ pub async fn execute_replay_command(
             );
             Some((total_succeeded, total_tx))
         }
+        ReplayToolCommand::DiffCheckpoints {
+            start,
+            end,
+            executor_version,
+            baseline_executor_version,
+            terminate_early,
+            max_tasks,
+            output,
+        } => {
+            assert!(start <= end, "Start checkpoint must be <= end checkpoint");
+            assert!(max_tasks > 0, "Max tasks must be > 0");
+            let rpc_url = rpc_url.expect("Url must be provided");
+            let checkpoints_per_task = ((end - start + max_tasks) / max_tasks) as usize;
+            let config = DiffReplayConfig {
+                baseline_executor_version,
+                candidate_executor_version: Some(executor_version),
+                expensive_safety_check_config: safety,
+                terminate_early,
+            };
+
+            let range: Vec<_> = (start..=end).collect();
+            let handles = range.chunks(checkpoints_per_task).map(|checkpoints| {
+                let checkpoints = checkpoints.to_vec();
+                let rpc_url = rpc_url.clone();
+                let config = config.clone();
+                tokio::spawn(async move {
+                    diff_replay::diff_replay_checkpoints(&rpc_url, &checkpoints, &config).await
+                })
+            });
+
+            let mut report = DiffReport::default();
+            for result in futures::future::join_all(handles).await {
+                match result {
+                    Ok(Ok(task_report)) => report.merge(task_report),
+                    Ok(Err(e)) => {
+                        error!("Task failed: {:?}", e);
+                        if terminate_early {
+                            return Err(e.into());
+                        }
+                    }
+                    Err(e) => error!("Task failed: {:?}", e),
+                }
+            }
+
+            for diff in &report.diffs {
+                println!(
+                    "Transaction {} (checkpoint {}) differs:",
+                    diff.digest, diff.checkpoint
+                );
+                for difference in &diff.differences {
+                    println!("{}", serde_json::to_string_pretty(difference)?);
+                }
+            }
+
+            if let Some(output) = output {
+                std::fs::write(&output, serde_json::to_string_pretty(&report)?)?;
+                info!("Report written to {}", output.display());
+            }
+
+            info!(
+                "Compared {} transactions in checkpoints {} to {}: {} identical, {} differ, {} failed to replay, {} baseline forks",
+                report.transactions_replayed,
+                start,
+                end,
+                report.identical(),
+                report.diffs.len(),
+                report.failures.len(),
+                report.baseline_forks.len(),
+            );
+            Some((report.identical(), report.transactions_replayed))
+        }
         ReplayToolCommand::ReplayEpoch {
             epoch,
             terminate_early,
@@ -568,6 +687,128 @@ pub async fn execute_replay_command(
     })
 }
 
+/// Run a replay command against state reconstructed from a local archive and formal snapshot (see
+/// [`ArchiveFetcher`]), instead of an RPC. Only commands that replay transactions, checkpoints or
+/// epochs are supported.
+pub async fn execute_offline_replay_command(
+    config: ArchiveConfig,
+    safety_checks: bool,
+    use_authority: bool,
+    cmd: ReplayToolCommand,
+) -> anyhow::Result<Option<(u64, u64)>> {
+    let safety = if safety_checks {
+        ExpensiveSafetyCheckConfig::new_enable_all()
+    } else {
+        ExpensiveSafetyCheckConfig::default()
+    };
+
+    let (start, end, terminate_early, max_tasks) = match cmd {
+        ReplayToolCommand::ReplayTransaction {
+            tx_digest,
+            show_effects,
+            executor_version,
+            protocol_version,
+            config_objects,
+        } => {
+            let tx_digest = TransactionDigest::from_str(&tx_digest)?;
+            let fetcher = ArchiveFetcher::new_for_transaction(&config, tx_digest).await?;
+
+            info!("Executing tx: {}", tx_digest);
+            let sandbox_state = LocalExec::new_for_archive(fetcher)
+                .init_for_execution()
+                .await?
+                .execute_transaction(
+                    &tx_digest,
+                    safety,
+                    use_authority,
+                    executor_version,
+                    protocol_version,
+                    None,
+                    parse_configs_versions(config_objects),
+                )
+                .await?;
+
+            if show_effects {
+                println!("{}", sandbox_state.local_exec_effects);
+            }
+
+            sandbox_state.check_effects()?;
+
+            println!("Execution finished successfully. Local and archived effects match.");
+            return Ok(Some((1u64, 1u64)));
+        }
+
+        ReplayToolCommand::ReplayCheckpoints {
+            start,
+            end,
+            terminate_early,
+            max_tasks,
+        } => (start, end, terminate_early, max_tasks),
+
+        ReplayToolCommand::ReplayEpoch {
+            epoch,
+            terminate_early,
+            max_tasks,
+        } => {
+            let range = ArchiveFetcher::checkpoints_for_epoch(&config, epoch).await?;
+            info!(
+                "Executing epoch {} (checkpoint range {}-{})",
+                epoch,
+                range.start(),
+                range.end()
+            );
+            (*range.start(), *range.end(), terminate_early, max_tasks)
+        }
+
+        _ => anyhow::bail!("Command is not supported when replaying from an archive"),
+    };
+
+    assert!(start <= end, "Start checkpoint must be <= end checkpoint");
+    assert!(max_tasks > 0, "Max tasks must be > 0");
+    let fetcher = ArchiveFetcher::new(&config, Some(start..=end)).await?;
+
+    // The state is reconstructed once, and shared by the tasks replaying each chunk of the range.
+    let checkpoints_per_task = ((end - start + max_tasks) / max_tasks) as usize;
+    info!(
+        "Executing checkpoints {} to {} with at most {} tasks and at most {} checkpoints per task",
+        start, end, max_tasks, checkpoints_per_task
+    );
+
+    let time = std::time::Instant::now();
+    let range: Vec<_> = (start..=end).collect();
+    let handles = range.chunks(checkpoints_per_task).map(|checkpoints| {
+        let checkpoints = checkpoints.to_vec();
+        let fetcher = fetcher.clone();
+        let safety = safety.clone();
+        tokio::spawn(async move {
+            LocalExec::new_for_archive(fetcher)
+                .init_for_execution()
+                .await?
+                .execute_all_in_checkpoints(&checkpoints, &safety, terminate_early, use_authority)
+                .await
+        })
+    });
+
+    let mut succeeded = 0;
+    let mut total = 0;
+    for result in futures::future::join_all(handles).await {
+        let (task_succeeded, task_total) = result??;
+        succeeded += task_succeeded;
+        total += task_total;
+    }
+    let time_ms = time.elapsed().as_millis() as u64;
+
+    info!(
+        "Executed {} checkpoints @ {}/{} total TXs succeeded in {} ms ({}) avg TX/s",
+        end - start + 1,
+        succeeded,
+        total,
+        time_ms,
+        (total as f64) / (time_ms as f64 / 1000.0)
+    );
+    Ok(Some((succeeded, total)))
+}
+
 pub(crate) fn chain_from_chain_id(chain: &str) -> Chain {
     let mainnet_chain_id = format!("{}", get_mainnet_chain_identifier());
     // TODO: Since testnet periodically resets, we need to ensure that the chain id
@@ -3,6 +3,7 @@
 
 use crate::chain_from_chain_id;
 use crate::{
+    archive_fetcher::ArchiveFetcher,
     data_fetcher::{
         extract_epoch_and_version, DataFetcher, Fetchers, NodeStateDumpFetcher, RemoteFetcher,
     },
@@ -10,6 +11,7 @@ use crate::{
         transaction_displays::{transform_command_results_to_annotated, FullPTB},
         Pretty,
     },
+    fuzz_coverage::CoverageTracker,
     types::*,
 };
 use futures::executor::block_on;
@@ -20,6 +22,7 @@ use move_core_types::{
     language_storage::{ModuleId, StructTag},
     resolver::{ModuleResolver, ResourceResolver},
 };
+use move_trace_format::format::MoveTraceBuilder;
 use prometheus::Registry;
 use serde::{Deserialize, Serialize};
 use similar::{ChangeTag, TextDiff};
@@ -101,24 +104,27 @@ impl ExecutionSandboxState {
 
     /// Utility to diff effects in a human readable format
     pub fn diff_effects(&self) -> String {
-        let eff1 = &self.transaction_info.effects;
-        let eff2 = &self.local_exec_effects;
-        let on_chain_str = format!("{:#?}", eff1);
-        let local_chain_str = format!("{:#?}", eff2);
-        let mut res = vec![];
-
-        let diff = TextDiff::from_lines(&on_chain_str, &local_chain_str);
-        for change in diff.iter_all_changes() {
-            let sign = match change.tag() {
-                ChangeTag::Delete => "---",
-                ChangeTag::Insert => "+++",
-                ChangeTag::Equal => "   ",
-            };
-            res.push(format!("{}{}", sign, change));
-        }
+        diff_debug(&self.transaction_info.effects, &self.local_exec_effects)
+    }
+}
 
-        res.join("")
+/// Utility to diff the pretty-printed debug representations of two values, line by line.
+pub(crate) fn diff_debug<T: std::fmt::Debug>(old: &T, new: &T) -> String {
+    let old_str = format!("{:#?}", old);
+    let new_str = format!("{:#?}", new);
+    let mut res = vec![];
+
+    let diff = TextDiff::from_lines(&old_str, &new_str);
+    for change in diff.iter_all_changes() {
+        let sign = match change.tag() {
+            ChangeTag::Delete => "---",
+            ChangeTag::Insert => "+++",
+            ChangeTag::Equal => "   ",
+        };
+        res.push(format!("{}{}", sign, change));
     }
+
+    res.join("")
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -245,6 +251,9 @@ pub struct LocalExec {
     // filepath or the default current directory and name format for the profile output
     pub enable_profiler: Option<PathBuf>,
     pub config_and_versions: Option<Vec<(ObjectID, SequenceNumber)>>,
+    // If set, records the Move functions and instructions reached by every execution. Requires the
+    // `tracing` feature, as the Move VM does not trace executions otherwise
+    pub coverage: Option<CoverageTracker>,
     // Retry policies due to RPC errors
     pub num_retries_for_timeout: u32,
     pub sleep_period_for_timeout: std::time::Duration,
@@ -393,6 +402,7 @@ impl LocalExec {
             protocol_version: None,
             enable_profiler: None,
             config_and_versions: None,
+            coverage: None,
         })
     }
 
@@ -436,9 +446,37 @@ impl LocalExec {
             protocol_version: None,
             enable_profiler: None,
             config_and_versions: None,
+            coverage: None,
         })
     }
 
+    /// Replay against state reconstructed from a local archive and formal snapshot, without
+    /// contacting an RPC.
+    pub fn new_for_archive(fetcher: ArchiveFetcher) -> Self {
+        // Use a throwaway metrics registry for local execution.
+        let registry = prometheus::Registry::new();
+        let metrics = Arc::new(LimitsMetrics::new(&registry));
+
+        Self {
+            client: None,
+            protocol_version_epoch_table: BTreeMap::new(),
+            protocol_version_system_package_table: BTreeMap::new(),
+            current_protocol_version: 0,
+            exec_store_events: Arc::new(Mutex::new(Vec::new())),
+            metrics,
+            storage: Storage::default(),
+            fetcher: Fetchers::Archive(fetcher),
+            // TODO: make these configurable
+            num_retries_for_timeout: RPC_TIMEOUT_ERR_NUM_RETRIES,
+            sleep_period_for_timeout: RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
+            executor_version: None,
+            protocol_version: None,
+            enable_profiler: None,
+            config_and_versions: None,
+            coverage: None,
+        }
+    }
+
     pub async fn multi_download_and_store(
         &mut self,
         objs: &[(ObjectID, SequenceNumber)],
@@ -772,6 +810,11 @@ impl LocalExec {
             price: tx_info.gas_price,
             budget: tx_info.gas_budget,
         };
+        let mut trace_builder = self
+            .coverage
+            .as_ref()
+            .filter(|_| cfg!(feature = "tracing"))
+            .map(|coverage| MoveTraceBuilder::new_with_tracer(Box::new(coverage.tracer())));
         let (inner_store, gas_status, effects, _timings, result) = executor
             .execute_transaction_to_effects(
                 &self,
@@ -787,8 +830,10 @@ impl LocalExec {
                 transaction_kind.clone(),
                 tx_info.sender,
                 *tx_digest,
-                &mut None,
+                &mut trace_builder,
             );
+        // Hand the coverage of this execution to the tracker.
+        drop(trace_builder);
 
         if let Err(err) = self.pretty_print_for_tracing(
             &gas_status,
@@ -1110,8 +1155,10 @@ impl LocalExec {
         Ok(Some(o))
     }
 
+    /// Whether transactions are resolved through the fetcher, as opposed to from a state dump. The
+    /// archive fetcher serves transactions the same way an RPC does.
     pub fn is_remote_replay(&self) -> bool {
-        matches!(self.fetcher, Fetchers::Remote(_))
+        matches!(self.fetcher, Fetchers::Remote(_) | Fetchers::Archive(_))
     }
 
     /// Must be called after `populate_protocol_version_tables`
@@ -1121,7 +1168,7 @@ impl LocalExec {
         protocol_version: u64,
     ) -> Result<Vec<(ObjectID, SequenceNumber)>, ReplayEngineError> {
         match &self.fetcher {
-            Fetchers::Remote(_) => Ok(self
+            Fetchers::Remote(_) | Fetchers::Archive(_) => Ok(self
                 .protocol_version_system_package_table
                 .get(&protocol_version)
                 .ok_or(ReplayEngineError::FrameworkObjectVersionTableNotPopulated {
@@ -1240,6 +1287,14 @@ impl LocalExec {
     }
 
     pub async fn populate_protocol_version_tables(&mut self) -> Result<(), ReplayEngineError> {
+        // The archive fetcher already knows the system package versions of every epoch it
+        // reconstructed, so there is no need to derive them from the transaction history.
+        if let Fetchers::Archive(archive) = &self.fetcher {
+            self.protocol_version_epoch_table = archive.protocol_version_table();
+            self.protocol_version_system_package_table = archive.system_package_table();
+            return Ok(());
+        }
+
         self.protocol_version_epoch_table = self.protocol_ver_to_epoch_map().await?;
 
         let system_package_revisions = self.system_package_versions().await?;
@@ -1491,15 +1546,13 @@ impl LocalExec {
         assert!(self.is_remote_replay());
         // Fetch full transaction content
         let tx_info = self.fetcher.get_transaction(tx_digest).await?;
-        let sender = match tx_info.clone().transaction.unwrap().data {
-            sui_json_rpc_types::SuiTransactionBlockData::V1(tx) => tx.sender,
-        };
         let SuiTransactionBlockEffects::V1(effects) = tx_info.clone().effects.unwrap();
 
         let config_objects = self.add_config_objects_if_needed(effects.status());
 
         let raw_tx_bytes = tx_info.clone().raw_transaction;
         let orig_tx: SenderSignedData = bcs::from_bytes(&raw_tx_bytes).unwrap();
+        let sender = orig_tx.transaction_data().sender();
         let input_objs = orig_tx
             .transaction_data()
             .input_objects()
@@ -1522,14 +1575,8 @@ impl LocalExec {
                 }
             })
             .collect();
-        let gas_data = match tx_info.clone().transaction.unwrap().data {
-            sui_json_rpc_types::SuiTransactionBlockData::V1(tx) => tx.gas_data,
-        };
-        let gas_object_refs: Vec<_> = gas_data
-            .payment
-            .iter()
-            .map(|obj_ref| obj_ref.to_object_ref())
-            .collect();
+        let gas_data = orig_tx.transaction_data().gas_data().clone();
+        let gas_object_refs = gas_data.payment.clone();
         let receiving_objs = orig_tx
             .transaction_data()
             .receiving_objects()
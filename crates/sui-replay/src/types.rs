@@ -201,6 +201,15 @@ pub enum ReplayEngineError {
 
     #[error("Unable to get chain id: {}", err)]
     UnableToGetChainId { err: String },
+
+    #[error("Checkpoint {checkpoint} is not in the range reconstructed from the archive")]
+    CheckpointNotInArchive { checkpoint: u64 },
+
+    #[error("Transaction {digest} is not in the range reconstructed from the archive")]
+    TransactionNotInArchive { digest: TransactionDigest },
+
+    #[error("Epoch {epoch} is not in the range reconstructed from the archive")]
+    EpochNotInArchive { epoch: u64 },
 }
 
 impl From<SuiObjectResponseError> for ReplayEngineError {
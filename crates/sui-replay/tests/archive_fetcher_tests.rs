@@ -0,0 +1,319 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::hash::MultisetHash;
+use prometheus::Registry;
+use simulacrum::Simulacrum;
+use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_archival::reader::{ArchiveReader, ArchiveReaderMetrics};
+use sui_archival::writer::ArchiveWriter;
+use sui_config::node::ArchiveReaderConfig;
+use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::state_accumulator::StateAccumulator;
+use sui_replay::archive_fetcher::{ArchiveConfig, ArchiveFetcher};
+use sui_replay::types::ReplayEngineError;
+use sui_replay::{execute_offline_replay_command, ReplayToolCommand};
+use sui_snapshot::writer::StateSnapshotWriterV1;
+use sui_storage::{FileCompression, StorageFormat};
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::SuiAddress;
+use sui_types::digests::{ChainIdentifier, TransactionDigest};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::messages_checkpoint::{
+    CheckpointSequenceNumber, ECMHLiveObjectSetDigest, VerifiedCheckpointContents,
+};
+use sui_types::storage::{ObjectStore, ReadStore, SharedInMemoryStore, WriteStore};
+use tempfile::TempDir;
+
+/// A local archive and formal snapshot, written from a Simulacrum that ran through epoch 0 and then
+/// executed a few transfers in epoch 1.
+struct Fixture {
+    _dir: TempDir,
+    config: ArchiveConfig,
+    /// The first checkpoint of epoch 1.
+    first_checkpoint: CheckpointSequenceNumber,
+    /// The last checkpoint in the archive.
+    last_checkpoint: CheckpointSequenceNumber,
+    /// The transfers executed in epoch 1, and the checkpoints they were included in.
+    transfers: Vec<(TransactionDigest, CheckpointSequenceNumber)>,
+}
+
+impl Fixture {
+    async fn new() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sim = Simulacrum::new();
+
+        sim.advance_epoch(false);
+        let end_of_epoch = *sim
+            .store()
+            .get_highest_checkpint()
+            .unwrap()
+            .sequence_number();
+
+        // The live objects at the end of epoch 0 are the latest versions of every object written
+        // up to that point, that still exist.
+        let mut ids = BTreeSet::new();
+        for sequence_number in 0..=end_of_epoch {
+            let contents =
+                ReadStore::get_full_checkpoint_contents_by_sequence_number(&sim, sequence_number)
+                    .unwrap();
+            for data in contents.iter() {
+                ids.extend(
+                    data.effects
+                        .all_changed_objects()
+                        .into_iter()
+                        .map(|(o, _, _)| o.0),
+                );
+            }
+        }
+        let live_objects: Vec<_> = ids
+            .iter()
+            .filter_map(|id| ObjectStore::get_object(&sim, id))
+            .collect();
+
+        let mut transfers = vec![];
+        for _ in 0..3 {
+            let (transaction, _) = sim.transfer_txn(SuiAddress::random_for_testing_only());
+            let digest = *transaction.digest();
+            let (effects, _) = sim.execute_transaction(transaction).unwrap();
+            assert!(effects.status().is_ok());
+            let checkpoint = sim.create_checkpoint();
+            transfers.push((digest, *checkpoint.sequence_number()));
+        }
+        let last_checkpoint = transfers.last().unwrap().1;
+
+        // The archive only uploads a checkpoint once the one after it has been written.
+        sim.create_checkpoint();
+
+        let archive_path = dir.path().join("archive");
+        write_archive(
+            &sim,
+            &dir.path().join("archive_staging"),
+            &archive_path,
+            last_checkpoint,
+        )
+        .await;
+
+        let snapshot_path = dir.path().join("snapshot");
+        let genesis = ReadStore::get_checkpoint_by_sequence_number(&sim, 0).unwrap();
+        write_snapshot(
+            live_objects,
+            ChainIdentifier::from(*genesis.digest()),
+            dir.path(),
+            &snapshot_path,
+        )
+        .await;
+
+        Self {
+            _dir: dir,
+            config: ArchiveConfig {
+                archive_path,
+                snapshot_path,
+                snapshot_epoch: Some(0),
+                end_checkpoint: None,
+            },
+            first_checkpoint: end_of_epoch + 1,
+            last_checkpoint,
+            transfers,
+        }
+    }
+}
+
+fn store_config(path: &Path) -> ObjectStoreConfig {
+    ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(path.to_path_buf()),
+        ..Default::default()
+    }
+}
+
+/// Archive every checkpoint in `sim`, and wait until the archive reaches `last_checkpoint`.
+async fn write_archive(
+    sim: &Simulacrum,
+    staging: &Path,
+    remote: &Path,
+    last_checkpoint: CheckpointSequenceNumber,
+) {
+    let store = SharedInMemoryStore::default();
+    let genesis = ReadStore::get_checkpoint_by_sequence_number(sim, 0).unwrap();
+    store.inner_mut().insert_genesis_state(
+        genesis,
+        VerifiedCheckpointContents::new_unchecked(
+            ReadStore::get_full_checkpoint_contents_by_sequence_number(sim, 0).unwrap(),
+        ),
+        sim.store().get_committee_by_epoch(0).unwrap(),
+    );
+
+    let highest = *sim
+        .store()
+        .get_highest_checkpint()
+        .unwrap()
+        .sequence_number();
+    for sequence_number in 1..=highest {
+        let checkpoint =
+            ReadStore::get_checkpoint_by_sequence_number(sim, sequence_number).unwrap();
+        let contents =
+            ReadStore::get_full_checkpoint_contents_by_sequence_number(sim, sequence_number)
+                .unwrap();
+        store.insert_checkpoint(&checkpoint).unwrap();
+        store
+            .insert_checkpoint_contents(
+                &checkpoint,
+                VerifiedCheckpointContents::new_unchecked(contents),
+            )
+            .unwrap();
+        store.update_highest_synced_checkpoint(&checkpoint).unwrap();
+    }
+
+    let writer = ArchiveWriter::new(
+        store_config(staging),
+        store_config(remote),
+        FileCompression::Zstd,
+        StorageFormat::Blob,
+        Duration::ZERO,
+        1024 * 1024,
+        &Registry::default(),
+    )
+    .await
+    .unwrap();
+    let kill = writer.start(store).await.unwrap();
+
+    let reader = ArchiveReader::new(
+        ArchiveReaderConfig {
+            remote_store_config: store_config(remote),
+            download_concurrency: NonZeroUsize::new(1).unwrap(),
+            use_for_pruning_watermark: false,
+        },
+        &ArchiveReaderMetrics::new(&Registry::default()),
+    )
+    .unwrap();
+    tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            if reader.sync_manifest_once().await.is_ok()
+                && reader
+                    .latest_available_checkpoint()
+                    .await
+                    .is_ok_and(|latest| latest >= last_checkpoint)
+            {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("Archive was not written in time");
+
+    kill.send(()).unwrap();
+}
+
+/// Write a formal snapshot for epoch 0, containing `live_objects`.
+async fn write_snapshot(
+    live_objects: Vec<sui_types::object::Object>,
+    chain_identifier: ChainIdentifier,
+    dir: &Path,
+    remote: &Path,
+) {
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&dir.join("db"), None));
+    for object in live_objects {
+        perpetual_db.insert_object_test_only(object).unwrap();
+    }
+
+    let mut accumulator = Accumulator::default();
+    for live_object in perpetual_db.iter_live_object_set(false) {
+        StateAccumulator::accumulate_live_object(&mut accumulator, &live_object);
+    }
+    let root_state_hash = ECMHLiveObjectSetDigest::from(accumulator.digest());
+
+    StateSnapshotWriterV1::new(
+        &store_config(&dir.join("snapshot_staging")),
+        &store_config(remote),
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await
+    .unwrap()
+    .write(0, perpetual_db, root_state_hash, chain_identifier)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_replay_checkpoints_from_archive() {
+    let fixture = Fixture::new().await;
+
+    let fetcher = ArchiveFetcher::new(
+        &fixture.config,
+        Some(fixture.first_checkpoint..=fixture.last_checkpoint),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        fetcher.checkpoint_range(),
+        fixture.first_checkpoint..=fixture.last_checkpoint
+    );
+
+    let result = execute_offline_replay_command(
+        fixture.config.clone(),
+        false,
+        false,
+        ReplayToolCommand::ReplayCheckpoints {
+            start: fixture.first_checkpoint,
+            end: fixture.last_checkpoint,
+            terminate_early: true,
+            max_tasks: 2,
+        },
+    )
+    .await
+    .unwrap();
+    let (succeeded, total) = result.unwrap();
+    assert_eq!(succeeded, total);
+    assert!(total >= fixture.transfers.len() as u64);
+}
+
+#[tokio::test]
+async fn test_replay_transaction_stops_at_its_checkpoint() {
+    let fixture = Fixture::new().await;
+    let (digest, checkpoint) = fixture.transfers[0];
+    assert!(checkpoint < fixture.last_checkpoint);
+
+    let fetcher = ArchiveFetcher::new_for_transaction(&fixture.config, digest)
+        .await
+        .unwrap();
+    assert_eq!(
+        fetcher.checkpoint_range(),
+        fixture.first_checkpoint..=checkpoint
+    );
+
+    let result = execute_offline_replay_command(
+        fixture.config.clone(),
+        false,
+        false,
+        ReplayToolCommand::ReplayTransaction {
+            tx_digest: digest.to_string(),
+            show_effects: false,
+            executor_version: None,
+            protocol_version: None,
+            config_objects: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(result, Some((1, 1)));
+}
+
+#[tokio::test]
+async fn test_transaction_not_in_archive() {
+    let fixture = Fixture::new().await;
+    let digest = TransactionDigest::random();
+
+    let result = ArchiveFetcher::new_for_transaction(&fixture.config, digest).await;
+    assert!(matches!(
+        result,
+        Err(ReplayEngineError::TransactionNotInArchive { digest: d }) if d == digest
+    ));
+}
@@ -1,8 +1,6 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::pin::Pin;
-
 use tap::Pipe;
 
 use crate::field_mask::FieldMaskTree;
@@ -21,64 +19,34 @@ use crate::proto::rpc::v2alpha::ResolveTransactionResponse;
 use crate::proto::rpc::v2alpha::SimulateTransactionRequest;
 use crate::proto::rpc::v2alpha::SimulateTransactionResponse;
 use crate::proto::rpc::v2alpha::SubscribeCheckpointsRequest;
-use crate::proto::rpc::v2alpha::SubscribeCheckpointsResponse;
-use crate::proto::rpc::v2beta::Checkpoint;
 use crate::proto::rpc::v2beta::ExecutedTransaction;
 use crate::proto::rpc::v2beta::Transaction;
 use crate::proto::rpc::v2beta::TransactionEffects;
 use crate::proto::rpc::v2beta::TransactionEvents;
-use crate::subscription::SubscriptionServiceHandle;
 use crate::ErrorReason;
 use crate::RpcError;
 use crate::RpcService;
 
 #[tonic::async_trait]
-impl SubscriptionService for SubscriptionServiceHandle {
+impl SubscriptionService for RpcService {
     /// Server streaming response type for the SubscribeCheckpoints method.
-    type SubscribeCheckpointsStream = Pin<
-        Box<
-            dyn tokio_stream::Stream<Item = Result<SubscribeCheckpointsResponse, tonic::Status>>
-                + Send,
-        >,
-    >;
+    type SubscribeCheckpointsStream = subscribe_checkpoints::SubscribeCheckpointsStream;
 
     async fn subscribe_checkpoints(
         &self,
         request: tonic::Request<SubscribeCheckpointsRequest>,
     ) -> Result<tonic::Response<Self::SubscribeCheckpointsStream>, tonic::Status> {
-        let read_mask = request.into_inner().read_mask.unwrap_or_default();
-        let read_mask = FieldMaskTree::from(read_mask);
-
-        let Some(mut receiver) = self.register_subscription().await else {
-            return Err(tonic::Status::unavailable(
-                "too many existing subscriptions",
-            ));
-        };
-
-        let response = Box::pin(async_stream::stream! {
-            while let Some(checkpoint) = receiver.recv().await {
-                let Some(cursor) = checkpoint.sequence_number else {
-                    yield Err(tonic::Status::internal("unable to determine cursor"));
-                    break;
-                };
-
-                let checkpoint = Checkpoint::merge_from(checkpoint.as_ref(), &read_mask);
-                let response = SubscribeCheckpointsResponse {
-                    cursor: Some(cursor),
-                    checkpoint: Some(checkpoint),
-                };
-
-                yield Ok(response);
-            }
-        });
-
-        Ok(tonic::Response::new(response))
+        subscribe_checkpoints::subscribe_checkpoints(self, request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(Into::into)
     }
 }
 
 mod get_coin_info;
 mod list_dynamic_fields;
 mod list_owned_objects;
+mod subscribe_checkpoints;
 
 #[tonic::async_trait]
 impl LiveDataService for RpcService {
@@ -0,0 +1,409 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use sui_sdk_types::Address;
+use sui_sdk_types::StructTag;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tap::Pipe;
+
+use crate::error::CheckpointNotFoundError;
+use crate::field_mask::FieldMaskTree;
+use crate::grpc::v2beta::ledger_service::get_checkpoint::checkpoint_data_to_checkpoint_proto;
+use crate::message::MessageMergeFrom;
+use crate::proto::google::rpc::bad_request::FieldViolation;
+use crate::proto::rpc::v2alpha::SubscribeCheckpointsRequest;
+use crate::proto::rpc::v2alpha::SubscribeCheckpointsResponse;
+use crate::proto::rpc::v2beta::command::Command;
+use crate::proto::rpc::v2beta::transaction_kind::Kind;
+use crate::proto::rpc::v2beta::Checkpoint;
+use crate::proto::rpc::v2beta::Event;
+use crate::proto::rpc::v2beta::ExecutedTransaction;
+use crate::reader::StateReader;
+use crate::ErrorReason;
+use crate::Result;
+use crate::RpcError;
+use crate::RpcService;
+
+pub type SubscribeCheckpointsStream = Pin<
+    Box<
+        dyn tokio_stream::Stream<Item = Result<SubscribeCheckpointsResponse, tonic::Status>> + Send,
+    >,
+>;
+
+#[tracing::instrument(skip(service))]
+pub async fn subscribe_checkpoints(
+    service: &RpcService,
+    request: SubscribeCheckpointsRequest,
+) -> Result<SubscribeCheckpointsStream> {
+    let subscription_service_handle = service
+        .subscription_service_handle
+        .clone()
+        .ok_or_else(|| RpcError::new(tonic::Code::Unimplemented, "subscriptions not enabled"))?;
+
+    let read_mask = FieldMaskTree::from(request.read_mask.unwrap_or_default());
+    let filter = request
+        .filter
+        .map(CheckpointFilter::try_from)
+        .transpose()?
+        .filter(|filter| !filter.is_empty());
+
+    let reader = service.reader.clone();
+
+    // Hold a subscriber slot for the lifetime of the stream, including its backfill, so that the
+    // number of subscriptions reading from the store at once is bounded.
+    let slot = subscription_service_handle.reserve_slot().ok_or_else(|| {
+        RpcError::new(tonic::Code::Unavailable, "too many existing subscriptions")
+    })?;
+
+    // The next checkpoint that needs to be sent to the client
+    let mut next = match request.start_checkpoint {
+        Some(start) => {
+            // Backfilled checkpoints are loaded in full, including their objects, which are
+            // pruned independently of (and possibly more aggressively than) the rest of the
+            // checkpoint, so the backfill can only start from the lowest checkpoint whose objects
+            // are still available.
+            let lowest_available = reader
+                .inner()
+                .get_lowest_available_checkpoint()?
+                .max(reader.inner().get_lowest_available_checkpoint_objects()?);
+
+            if start < lowest_available {
+                return Err(RpcError::new(
+                    tonic::Code::NotFound,
+                    format!(
+                        "checkpoint {start} has been pruned; \
+                        lowest available checkpoint is {lowest_available}"
+                    ),
+                ));
+            }
+            Some(start)
+        }
+        None => None,
+    };
+
+    // Backfill from the store up to the latest executed checkpoint before registering with the
+    // subscription service, otherwise a long backfill could overflow the subscription channel and
+    // result in the subscription being dropped.
+    let mut backfill = None;
+    if let Some(start) = next {
+        let latest = reader.inner().get_latest_checkpoint_sequence_number()?;
+        backfill = Some(start..=latest);
+        next = Some(start.max(latest + 1));
+    }
+
+    let response = Box::pin(async_stream::stream! {
+        let _slot = slot;
+
+        for sequence_number in backfill.into_iter().flatten() {
+            match load_checkpoint(&reader, sequence_number).await {
+                Ok(checkpoint) => yield Ok(to_response(&checkpoint, filter.as_ref(), &read_mask)),
+                Err(e) => {
+                    yield Err(e.into());
+                    return;
+                }
+            }
+        }
+
+        let Some(mut receiver) = subscription_service_handle.register_subscription().await else {
+            yield Err(tonic::Status::unavailable("too many existing subscriptions"));
+            return;
+        };
+
+        while let Some(checkpoint) = receiver.recv().await {
+            let Some(cursor) = checkpoint.sequence_number else {
+                yield Err(tonic::Status::internal("unable to determine cursor"));
+                break;
+            };
+
+            let next = next.get_or_insert(cursor);
+
+            // Already sent to the client as a part of the backfill
+            if cursor < *next {
+                continue;
+            }
+
+            // Fill in any checkpoints which were executed between the end of the backfill and
+            // the start of the subscription.
+            while *next < cursor {
+                match load_checkpoint(&reader, *next).await {
+                    Ok(checkpoint) => {
+                        yield Ok(to_response(&checkpoint, filter.as_ref(), &read_mask))
+                    }
+                    Err(e) => {
+                        yield Err(e.into());
+                        return;
+                    }
+                }
+                *next += 1;
+            }
+
+            yield Ok(to_response(&checkpoint, filter.as_ref(), &read_mask));
+            *next = cursor + 1;
+        }
+    });
+
+    Ok(response)
+}
+
+/// Load a checkpoint from the store, on a blocking thread.
+async fn load_checkpoint(
+    reader: &StateReader,
+    sequence_number: CheckpointSequenceNumber,
+) -> Result<Arc<Checkpoint>> {
+    let reader = reader.clone();
+    tokio::task::spawn_blocking(move || load_checkpoint_blocking(&reader, sequence_number))
+        .await
+        .map_err(|e| RpcError::new(tonic::Code::Internal, e.to_string()))?
+}
+
+fn load_checkpoint_blocking(
+    reader: &StateReader,
+    sequence_number: CheckpointSequenceNumber,
+) -> Result<Arc<Checkpoint>> {
+    let summary = reader
+        .inner()
+        .get_checkpoint_by_sequence_number(sequence_number)
+        .ok_or(CheckpointNotFoundError::sequence_number(sequence_number))?;
+    let contents = reader
+        .inner()
+        .get_checkpoint_contents_by_sequence_number(sequence_number)
+        .ok_or(CheckpointNotFoundError::sequence_number(sequence_number))?;
+    let checkpoint_data = reader.inner().get_checkpoint_data(summary, contents)?;
+
+    checkpoint_data_to_checkpoint_proto(checkpoint_data, &FieldMaskTree::new_wildcard())
+        .map(Arc::new)
+}
+
+fn to_response(
+    checkpoint: &Checkpoint,
+    filter: Option<&CheckpointFilter>,
+    read_mask: &FieldMaskTree,
+) -> SubscribeCheckpointsResponse {
+    let cursor = checkpoint.sequence_number;
+    let checkpoint = match filter {
+        Some(filter) => Checkpoint::merge_from(&filter.apply(checkpoint), read_mask),
+        None => Checkpoint::merge_from(checkpoint, read_mask),
+    };
+
+    SubscribeCheckpointsResponse {
+        cursor,
+        checkpoint: Some(checkpoint),
+    }
+}
+
+/// Parsed and normalized form of a `CheckpointFilter` proto.
+///
+/// Addresses and object ids are stored in their canonical string representation so that they can
+/// be compared directly against the fields of the checkpoint protos.
+#[derive(Debug, Default)]
+struct CheckpointFilter {
+    senders: HashSet<String>,
+    objects: HashSet<String>,
+    packages: HashSet<String>,
+    event_types: Vec<EventTypeFilter>,
+}
+
+#[derive(Debug)]
+enum EventTypeFilter {
+    /// Matches all events defined in a module, stored as `<address>::<module>::`
+    Module(String),
+    /// Matches all instantiations of a struct, stored as `<address>::<module>::<name>`
+    Struct(String),
+    /// Matches a fully instantiated struct tag
+    Exact(String),
+}
+
+impl CheckpointFilter {
+    fn is_empty(&self) -> bool {
+        self.senders.is_empty()
+            && self.objects.is_empty()
+            && self.packages.is_empty()
+            && self.event_types.is_empty()
+    }
+
+    /// Returns a copy of `checkpoint` with only the transactions, and events, which match this
+    /// filter.
+    fn apply(&self, checkpoint: &Checkpoint) -> Checkpoint {
+        Checkpoint {
+            sequence_number: checkpoint.sequence_number,
+            digest: checkpoint.digest.clone(),
+            summary: checkpoint.summary.clone(),
+            signature: checkpoint.signature.clone(),
+            contents: checkpoint.contents.clone(),
+            transactions: checkpoint
+                .transactions
+                .iter()
+                .filter(|transaction| self.matches_transaction(transaction))
+                .map(|transaction| self.filter_events(transaction))
+                .collect(),
+        }
+    }
+
+    fn matches_transaction(&self, transaction: &ExecutedTransaction) -> bool {
+        (self.senders.is_empty() || self.matches_sender(transaction))
+            && (self.objects.is_empty() || self.matches_objects(transaction))
+            && (self.packages.is_empty() || self.matches_packages(transaction))
+            && (self.event_types.is_empty()
+                || events(transaction).any(|event| self.matches_event(event)))
+    }
+
+    fn matches_sender(&self, transaction: &ExecutedTransaction) -> bool {
+        transaction
+            .transaction
+            .as_ref()
+            .and_then(|transaction| transaction.sender.as_ref())
+            .is_some_and(|sender| self.senders.contains(sender))
+    }
+
+    fn matches_objects(&self, transaction: &ExecutedTransaction) -> bool {
+        let effects = transaction.effects.iter();
+
+        transaction
+            .input_objects
+            .iter()
+            .chain(&transaction.output_objects)
+            .filter_map(|object| object.object_id.as_ref())
+            .chain(
+                effects
+                    .clone()
+                    .flat_map(|effects| &effects.changed_objects)
+                    .filter_map(|object| object.object_id.as_ref()),
+            )
+            .chain(
+                effects
+                    .flat_map(|effects| &effects.unchanged_shared_objects)
+                    .filter_map(|object| object.object_id.as_ref()),
+            )
+            .any(|object_id| self.objects.contains(object_id))
+    }
+
+    fn matches_packages(&self, transaction: &ExecutedTransaction) -> bool {
+        let move_calls = transaction
+            .transaction
+            .as_ref()
+            .and_then(|transaction| transaction.kind.as_ref())
+            .and_then(|kind| match &kind.kind {
+                Some(Kind::ProgrammableTransaction(ptb)) => Some(&ptb.commands),
+                _ => None,
+            })
+            .into_iter()
+            .flatten()
+            .filter_map(|command| match &command.command {
+                Some(Command::MoveCall(move_call)) => move_call.package.as_ref(),
+                _ => None,
+            });
+
+        // Published and upgraded packages show up as changed objects with a type of `package`
+        let packages = transaction
+            .effects
+            .iter()
+            .flat_map(|effects| &effects.changed_objects)
+            .filter(|object| object.object_type.as_deref() == Some("package"))
+            .filter_map(|object| object.object_id.as_ref());
+
+        move_calls
+            .chain(packages)
+            .any(|package| self.packages.contains(package))
+    }
+
+    fn matches_event(&self, event: &Event) -> bool {
+        let Some(event_type) = event.event_type.as_deref() else {
+            return false;
+        };
+
+        self.event_types.iter().any(|filter| match filter {
+            EventTypeFilter::Module(prefix) => event_type.starts_with(prefix.as_str()),
+            EventTypeFilter::Struct(name) => event_type
+                .strip_prefix(name.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('<')),
+            EventTypeFilter::Exact(tag) => event_type == tag,
+        })
+    }
+
+    /// When filtering on event types, only the matching events of a transaction are returned.
+    fn filter_events(&self, transaction: &ExecutedTransaction) -> ExecutedTransaction {
+        let mut transaction = transaction.clone();
+
+        if !self.event_types.is_empty() {
+            if let Some(events) = transaction.events.as_mut() {
+                events.events.retain(|event| self.matches_event(event));
+            }
+        }
+
+        transaction
+    }
+}
+
+fn events(transaction: &ExecutedTransaction) -> impl Iterator<Item = &Event> {
+    transaction.events.iter().flat_map(|events| &events.events)
+}
+
+impl TryFrom<crate::proto::rpc::v2alpha::CheckpointFilter> for CheckpointFilter {
+    type Error = RpcError;
+
+    fn try_from(filter: crate::proto::rpc::v2alpha::CheckpointFilter) -> Result<Self> {
+        fn parse_addresses(field: &str, values: Vec<String>) -> Result<HashSet<String>> {
+            values
+                .iter()
+                .map(|value| {
+                    value
+                        .parse::<Address>()
+                        .map(|address| address.to_string())
+                        .map_err(|e| {
+                            FieldViolation::new(field)
+                                .with_description(format!("invalid address {value}: {e}"))
+                                .with_reason(ErrorReason::FieldInvalid)
+                                .pipe(RpcError::from)
+                        })
+                })
+                .collect()
+        }
+
+        let event_types = filter
+            .event_types
+            .iter()
+            .map(|event_type| {
+                EventTypeFilter::parse(event_type).ok_or_else(|| {
+                    FieldViolation::new("filter.event_types")
+                        .with_description(format!("invalid event type {event_type}"))
+                        .with_reason(ErrorReason::FieldInvalid)
+                        .pipe(RpcError::from)
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            senders: parse_addresses("filter.senders", filter.senders)?,
+            objects: parse_addresses("filter.objects", filter.objects)?,
+            packages: parse_addresses("filter.packages", filter.packages)?,
+            event_types,
+        })
+    }
+}
+
+impl EventTypeFilter {
+    fn parse(s: &str) -> Option<Self> {
+        if s.contains('<') {
+            return s
+                .parse::<StructTag>()
+                .ok()
+                .map(|tag| Self::Exact(tag.to_string()));
+        }
+
+        match s.split("::").collect::<Vec<_>>().as_slice() {
+            [address, module] if !module.is_empty() => {
+                let address = address.parse::<Address>().ok()?;
+                Some(Self::Module(format!("{address}::{module}::")))
+            }
+            [_, _, _] => s
+                .parse::<StructTag>()
+                .ok()
+                .map(|tag| Self::Struct(tag.to_string())),
+            _ => None,
+        }
+    }
+}
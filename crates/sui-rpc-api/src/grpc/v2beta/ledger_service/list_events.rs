@@ -0,0 +1,510 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::list_transactions::parse_address;
+use super::list_transactions::parse_identifier;
+use super::list_transactions::parse_object_id;
+use crate::proto::google::rpc::bad_request::FieldViolation;
+use crate::proto::rpc::v2beta::event_filter::Filter;
+use crate::proto::rpc::v2beta::EventFilter;
+use crate::proto::rpc::v2beta::IndexedEvent;
+use crate::proto::rpc::v2beta::ListEventsRequest;
+use crate::proto::rpc::v2beta::ListEventsResponse;
+use crate::proto::rpc::v2beta::MoveModuleFilter;
+use crate::proto::rpc::v2beta::TimeRangeFilter;
+use crate::proto::types::proto_to_timestamp_ms;
+use crate::proto::types::timestamp_ms_to_proto;
+use crate::reader::StateReader;
+use crate::Direction;
+use crate::ErrorReason;
+use crate::Result;
+use crate::RpcError;
+use crate::RpcService;
+use bytes::Bytes;
+use std::collections::HashMap;
+use sui_sdk_types::CheckpointSequenceNumber;
+use sui_types::digests::TransactionDigest;
+use sui_types::storage::EventCursor;
+use sui_types::storage::EventIndexFilter;
+use tap::Pipe;
+
+/// The maximum number of transactions whose events are scanned to serve a single request for all
+/// events, or events in a time range. If this limit is reached before the page is filled, the
+/// response contains the events found so far, and a page token to resume the scan from.
+const MAX_SCANNED_TRANSACTIONS: usize = 10_000;
+
+#[tracing::instrument(skip(service))]
+pub fn list_events(service: &RpcService, request: ListEventsRequest) -> Result<ListEventsResponse> {
+    let query = request
+        .filter
+        .as_ref()
+        .map(EventQuery::from_proto)
+        .transpose()?
+        .unwrap_or(EventQuery::All);
+
+    let page_size = request
+        .page_size
+        .map(|s| (s as usize).clamp(1, 1000))
+        .unwrap_or(50);
+    let page_token = request
+        .page_token
+        .map(|token| decode_page_token(&token))
+        .transpose()?;
+    let descending = request.descending.unwrap_or(false);
+
+    let reader = &service.reader;
+    let (mut events, resume_cursor) = match query {
+        EventQuery::All => {
+            let start = match page_token {
+                Some(cursor) => cursor.checkpoint,
+                None if descending => reader.inner().get_latest_checkpoint()?.sequence_number,
+                None => reader.inner().get_lowest_available_checkpoint()?,
+            };
+
+            collect_scan(
+                scan_events(reader, start, page_token, descending),
+                page_size + 1,
+            )?
+        }
+        EventQuery::TimeRange { start_ms, end_ms } => {
+            let start = match page_token {
+                Some(cursor) => cursor.checkpoint,
+                None if descending => {
+                    let latest = reader.inner().get_latest_checkpoint()?.sequence_number;
+                    match end_ms {
+                        Some(end_ms) => match first_checkpoint_at_or_after(reader, end_ms)? {
+                            Some(checkpoint) => checkpoint.saturating_sub(1),
+                            None => latest,
+                        },
+                        None => latest,
+                    }
+                }
+                None => match start_ms {
+                    Some(start_ms) => match first_checkpoint_at_or_after(reader, start_ms)? {
+                        Some(checkpoint) => checkpoint,
+                        // No checkpoints have been produced in the requested range yet
+                        None => {
+                            return Ok(ListEventsResponse {
+                                events: vec![],
+                                next_page_token: None,
+                            })
+                        }
+                    },
+                    None => reader.inner().get_lowest_available_checkpoint()?,
+                },
+            };
+
+            let entries = scan_events(reader, start, page_token, descending)
+                .take_while(|result| match result {
+                    Ok(entry) => {
+                        let timestamp_ms = entry.timestamp_ms();
+                        if descending {
+                            start_ms.is_none_or(|start_ms| timestamp_ms >= start_ms)
+                        } else {
+                            end_ms.is_none_or(|end_ms| timestamp_ms < end_ms)
+                        }
+                    }
+                    Err(_) => true,
+                })
+                .filter(|result| match result {
+                    Ok(ScanEntry::Event(_, _, timestamp_ms)) => {
+                        start_ms.is_none_or(|start_ms| *timestamp_ms >= start_ms)
+                            && end_ms.is_none_or(|end_ms| *timestamp_ms < end_ms)
+                    }
+                    Ok(ScanEntry::Limit(..)) | Err(_) => true,
+                });
+
+            collect_scan(entries, page_size + 1)?
+        }
+        EventQuery::Transaction(digest) => {
+            let checkpoint = reader.get_transaction_checkpoint(&digest).ok_or_else(|| {
+                RpcError::new(
+                    tonic::Code::NotFound,
+                    format!("Transaction {digest} not found"),
+                )
+            })?;
+            let contents = reader
+                .inner()
+                .get_checkpoint_contents_by_sequence_number(checkpoint)
+                .ok_or_else(|| {
+                    RpcError::new(
+                        tonic::Code::NotFound,
+                        format!("Checkpoint {checkpoint} not found"),
+                    )
+                })?;
+            let transaction_index = contents
+                .iter()
+                .position(|execution_digests| execution_digests.transaction == digest)
+                .ok_or_else(|| {
+                    RpcError::new(
+                        tonic::Code::Internal,
+                        format!("Transaction {digest} missing from checkpoint {checkpoint}"),
+                    )
+                })? as u64;
+
+            let start = page_token.unwrap_or(EventCursor {
+                checkpoint,
+                transaction_index,
+                event_index: if descending { u64::MAX } else { 0 },
+            });
+
+            let entries =
+                scan_events(reader, checkpoint, Some(start), descending).take_while(|result| {
+                    match result {
+                        Ok(entry) => {
+                            let cursor = entry.cursor();
+                            cursor.checkpoint == checkpoint
+                                && cursor.transaction_index == transaction_index
+                        }
+                        Err(_) => true,
+                    }
+                });
+
+            collect_scan(entries, page_size + 1)?
+        }
+        EventQuery::Indexed(filter) => {
+            let indexes = reader.inner().indexes().ok_or_else(RpcError::not_found)?;
+
+            let mut timestamps = HashMap::new();
+            let events = indexes
+                .events_iter(filter, page_token, descending)?
+                .take(page_size + 1)
+                .map(|result| {
+                    let (cursor, digest) = result
+                        .map_err(|err| RpcError::new(tonic::Code::Internal, err.to_string()))?;
+                    let timestamp_ms = match timestamps.get(&cursor.checkpoint) {
+                        Some(timestamp_ms) => *timestamp_ms,
+                        None => {
+                            let timestamp_ms = checkpoint_timestamp_ms(reader, cursor.checkpoint)?;
+                            timestamps.insert(cursor.checkpoint, timestamp_ms);
+                            timestamp_ms
+                        }
+                    };
+                    Ok((cursor, digest, timestamp_ms))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            (events, None)
+        }
+    };
+
+    let next_page_token = if events.len() > page_size {
+        // SAFETY: We've already verified that events is greater than limit, which is
+        // gaurenteed to be >= 1.
+        events.pop().unwrap().0.pipe(encode_page_token).pipe(Some)
+    } else {
+        resume_cursor.map(encode_page_token)
+    };
+
+    let mut transaction_events = HashMap::new();
+    let events = events
+        .into_iter()
+        .map(|(cursor, digest, timestamp_ms)| {
+            if !transaction_events.contains_key(&digest) {
+                transaction_events.insert(digest, load_events(reader, &digest)?);
+            }
+
+            let event = transaction_events[&digest]
+                .get(cursor.event_index as usize)
+                .cloned()
+                .ok_or_else(|| {
+                    RpcError::new(
+                        tonic::Code::Internal,
+                        format!(
+                            "event {} missing from transaction {digest}",
+                            cursor.event_index
+                        ),
+                    )
+                })?;
+
+            Ok(IndexedEvent {
+                transaction_digest: Some(digest.to_string()),
+                event_index: Some(cursor.event_index),
+                checkpoint: Some(cursor.checkpoint),
+                timestamp: Some(timestamp_ms_to_proto(timestamp_ms)),
+                event: Some(event.into()),
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(ListEventsResponse {
+        events,
+        next_page_token,
+    })
+}
+
+enum EventQuery {
+    All,
+    Transaction(TransactionDigest),
+    TimeRange {
+        start_ms: Option<u64>,
+        end_ms: Option<u64>,
+    },
+    Indexed(EventIndexFilter),
+}
+
+impl EventQuery {
+    fn from_proto(filter: &EventFilter) -> Result<Self> {
+        let filter = filter.filter.as_ref().ok_or_else(|| {
+            FieldViolation::new("filter")
+                .with_description("missing filter")
+                .with_reason(ErrorReason::FieldMissing)
+        })?;
+
+        let query = match filter {
+            Filter::Sender(sender) => EventQuery::Indexed(EventIndexFilter::Sender(parse_address(
+                "filter.sender",
+                Some(sender),
+            )?)),
+            Filter::Transaction(digest) => digest
+                .parse::<sui_sdk_types::TransactionDigest>()
+                .map_err(|e| {
+                    FieldViolation::new("filter.transaction")
+                        .with_description(format!("invalid digest: {e}"))
+                        .with_reason(ErrorReason::FieldInvalid)
+                })?
+                .pipe(Into::into)
+                .pipe(EventQuery::Transaction),
+            Filter::MoveModule(MoveModuleFilter { package, module }) => {
+                EventQuery::Indexed(EventIndexFilter::EmittingModule(
+                    parse_object_id("filter.move_module.package", package.as_ref())?,
+                    parse_module("filter.move_module.module", module.as_ref())?,
+                ))
+            }
+            Filter::MoveEventType(event_type) => sui_types::parse_sui_struct_tag(event_type)
+                .map_err(|e| {
+                    FieldViolation::new("filter.move_event_type")
+                        .with_description(format!("invalid event type: {e}"))
+                        .with_reason(ErrorReason::FieldInvalid)
+                })?
+                .pipe(EventIndexFilter::EventType)
+                .pipe(EventQuery::Indexed),
+            Filter::MoveEventModule(MoveModuleFilter { package, module }) => {
+                EventQuery::Indexed(EventIndexFilter::EventModule(
+                    parse_object_id("filter.move_event_module.package", package.as_ref())?,
+                    parse_module("filter.move_event_module.module", module.as_ref())?,
+                ))
+            }
+            Filter::TimeRange(TimeRangeFilter {
+                start_time,
+                end_time,
+            }) => {
+                let parse = |field: &str, timestamp: &Option<prost_types::Timestamp>| {
+                    timestamp
+                        .as_ref()
+                        .map(|timestamp| {
+                            proto_to_timestamp_ms(*timestamp).map_err(|e| {
+                                FieldViolation::new(field)
+                                    .with_description(format!("invalid timestamp: {e}"))
+                                    .with_reason(ErrorReason::FieldInvalid)
+                            })
+                        })
+                        .transpose()
+                };
+
+                EventQuery::TimeRange {
+                    start_ms: parse("filter.time_range.start_time", start_time)?,
+                    end_ms: parse("filter.time_range.end_time", end_time)?,
+                }
+            }
+        };
+
+        Ok(query)
+    }
+}
+
+fn parse_module(field: &str, module: Option<&String>) -> Result<String> {
+    let module = module.ok_or_else(|| {
+        FieldViolation::new(field)
+            .with_description(format!("missing {field}"))
+            .with_reason(ErrorReason::FieldMissing)
+    })?;
+    parse_identifier(field, module)
+}
+
+/// An entry produced by [scan_events].
+enum ScanEntry {
+    /// An event's cursor, along with its transaction and checkpoint timestamp.
+    Event(EventCursor, TransactionDigest, u64),
+
+    /// The scan reached [MAX_SCANNED_TRANSACTIONS] and stopped. It can be resumed from this
+    /// cursor, which points at the first event (in scan order) of the next transaction, whose
+    /// checkpoint timestamp is also included.
+    Limit(EventCursor, u64),
+}
+
+impl ScanEntry {
+    fn cursor(&self) -> EventCursor {
+        match self {
+            ScanEntry::Event(cursor, _, _) | ScanEntry::Limit(cursor, _) => *cursor,
+        }
+    }
+
+    fn timestamp_ms(&self) -> u64 {
+        match self {
+            ScanEntry::Event(_, _, timestamp_ms) | ScanEntry::Limit(_, timestamp_ms) => {
+                *timestamp_ms
+            }
+        }
+    }
+}
+
+/// Collect up to `limit` events from `entries`, along with the cursor to resume from if the scan
+/// stopped early because it reached [MAX_SCANNED_TRANSACTIONS].
+fn collect_scan(
+    entries: impl Iterator<Item = Result<ScanEntry>>,
+    limit: usize,
+) -> Result<(
+    Vec<(EventCursor, TransactionDigest, u64)>,
+    Option<EventCursor>,
+)> {
+    let mut events = vec![];
+    for entry in entries.take(limit) {
+        match entry? {
+            ScanEntry::Event(cursor, digest, timestamp_ms) => {
+                events.push((cursor, digest, timestamp_ms))
+            }
+            ScanEntry::Limit(cursor, _) => return Ok((events, Some(cursor))),
+        }
+    }
+
+    Ok((events, None))
+}
+
+/// Scan the events of every transaction, starting at `start` and resuming from `cursor` if
+/// provided. At most [MAX_SCANNED_TRANSACTIONS] transactions are scanned, after which the scan
+/// ends with a [ScanEntry::Limit].
+fn scan_events(
+    reader: &StateReader,
+    start: CheckpointSequenceNumber,
+    cursor: Option<EventCursor>,
+    descending: bool,
+) -> impl Iterator<Item = Result<ScanEntry>> + '_ {
+    let direction = if descending {
+        Direction::Descending
+    } else {
+        Direction::Ascending
+    };
+    let start = (
+        start,
+        cursor.map(|cursor| cursor.transaction_index as usize),
+    );
+
+    reader
+        .transaction_iter(direction, start)
+        .take(MAX_SCANNED_TRANSACTIONS + 1)
+        .enumerate()
+        .flat_map(move |(scanned, result)| {
+            let events = result
+                .map_err(RpcError::from)
+                .and_then(|(cursor_info, digest)| {
+                    if scanned == MAX_SCANNED_TRANSACTIONS {
+                        let cursor = EventCursor {
+                            checkpoint: cursor_info.checkpoint,
+                            transaction_index: cursor_info.index,
+                            event_index: if descending { u64::MAX } else { 0 },
+                        };
+                        return Ok(vec![ScanEntry::Limit(cursor, cursor_info.timestamp_ms)]);
+                    }
+
+                    let num_events = reader
+                        .inner()
+                        .get_events(&digest)
+                        .map(|events| events.data.len())
+                        .unwrap_or(0);
+
+                    let mut events = (0..num_events as u64)
+                        .map(|event_index| {
+                            ScanEntry::Event(
+                                EventCursor {
+                                    checkpoint: cursor_info.checkpoint,
+                                    transaction_index: cursor_info.index,
+                                    event_index,
+                                },
+                                digest,
+                                cursor_info.timestamp_ms,
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    if descending {
+                        events.reverse();
+                    }
+                    Ok(events)
+                });
+
+            match events {
+                Ok(events) => events.into_iter().map(Ok).collect::<Vec<_>>(),
+                Err(e) => vec![Err(e)],
+            }
+        })
+        .skip_while(move |result| match (result, cursor) {
+            // Skip the events of the first transaction which come before the cursor
+            (Ok(ScanEntry::Event(event_cursor, _, _)), Some(cursor)) => {
+                if descending {
+                    *event_cursor > cursor
+                } else {
+                    *event_cursor < cursor
+                }
+            }
+            _ => false,
+        })
+}
+
+/// Find the first checkpoint with a timestamp at or after `timestamp_ms`, if one exists.
+fn first_checkpoint_at_or_after(
+    reader: &StateReader,
+    timestamp_ms: u64,
+) -> Result<Option<CheckpointSequenceNumber>> {
+    let mut low = reader.inner().get_lowest_available_checkpoint()?;
+    let latest = reader.inner().get_latest_checkpoint()?;
+    if latest.timestamp_ms < timestamp_ms {
+        return Ok(None);
+    }
+    let mut high = latest.sequence_number;
+
+    // Checkpoint timestamps are monotonically non-decreasing so we can binary search for the
+    // first checkpoint in the range.
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if checkpoint_timestamp_ms(reader, mid)? < timestamp_ms {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    Ok(Some(low))
+}
+
+fn checkpoint_timestamp_ms(
+    reader: &StateReader,
+    checkpoint: CheckpointSequenceNumber,
+) -> Result<u64> {
+    reader
+        .inner()
+        .get_checkpoint_by_sequence_number(checkpoint)
+        .map(|checkpoint| checkpoint.timestamp_ms)
+        .ok_or_else(|| crate::error::CheckpointNotFoundError::sequence_number(checkpoint).into())
+}
+
+fn load_events(
+    reader: &StateReader,
+    digest: &TransactionDigest,
+) -> Result<Vec<sui_sdk_types::Event>> {
+    reader
+        .inner()
+        .get_events(digest)
+        .map(sui_sdk_types::TransactionEvents::try_from)
+        .transpose()?
+        .map(|events| events.0)
+        .unwrap_or_default()
+        .pipe(Ok)
+}
+
+fn decode_page_token(page_token: &[u8]) -> Result<EventCursor> {
+    bcs::from_bytes(page_token).map_err(Into::into)
+}
+
+fn encode_page_token(page_token: EventCursor) -> Bytes {
+    bcs::to_bytes(&page_token).unwrap().into()
+}
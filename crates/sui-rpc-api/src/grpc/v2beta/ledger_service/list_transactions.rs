@@ -0,0 +1,323 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::field_mask::FieldMaskTree;
+use crate::field_mask::FieldMaskUtil;
+use crate::message::MessageMergeFrom;
+use crate::proto::google::rpc::bad_request::FieldViolation;
+use crate::proto::rpc::v2beta::transaction_filter::Filter;
+use crate::proto::rpc::v2beta::ExecutedTransaction;
+use crate::proto::rpc::v2beta::ListTransactionsRequest;
+use crate::proto::rpc::v2beta::ListTransactionsResponse;
+use crate::proto::rpc::v2beta::MoveFunctionFilter;
+use crate::proto::rpc::v2beta::SenderAndRecipientFilter;
+use crate::proto::rpc::v2beta::TransactionFilter;
+use crate::reader::StateReader;
+use crate::Direction;
+use crate::ErrorReason;
+use crate::Result;
+use crate::RpcError;
+use crate::RpcService;
+use bytes::Bytes;
+use itertools::Itertools;
+use prost_types::FieldMask;
+use sui_sdk_types::Address;
+use sui_sdk_types::ObjectId;
+use sui_types::base_types::ObjectID;
+use sui_types::base_types::SuiAddress;
+use sui_types::digests::TransactionDigest;
+use sui_types::storage::TransactionCursor;
+use sui_types::storage::TransactionIndexFilter;
+use sui_types::Identifier;
+use tap::Pipe;
+
+#[tracing::instrument(skip(service))]
+pub fn list_transactions(
+    service: &RpcService,
+    request: ListTransactionsRequest,
+) -> Result<ListTransactionsResponse> {
+    let read_mask = {
+        let read_mask = request
+            .read_mask
+            .unwrap_or_else(|| FieldMask::from_str(ListTransactionsRequest::READ_MASK_DEFAULT));
+        read_mask
+            .validate::<ExecutedTransaction>()
+            .map_err(|path| {
+                FieldViolation::new("read_mask")
+                    .with_description(format!("invalid read_mask path: {path}"))
+                    .with_reason(ErrorReason::FieldInvalid)
+            })?;
+        FieldMaskTree::from(read_mask)
+    };
+
+    let query = request
+        .filter
+        .as_ref()
+        .map(TransactionQuery::from_proto)
+        .transpose()?
+        .unwrap_or(TransactionQuery::All);
+
+    let page_size = request
+        .page_size
+        .map(|s| (s as usize).clamp(1, 1000))
+        .unwrap_or(50);
+    let page_token = request
+        .page_token
+        .map(|token| decode_page_token(&token))
+        .transpose()?;
+    let descending = request.descending.unwrap_or(false);
+
+    let mut transactions = match query {
+        TransactionQuery::All => {
+            checkpoint_transactions(&service.reader, None, page_token, descending)?
+                .take(page_size + 1)
+                .collect::<Result<Vec<_>>>()?
+        }
+        TransactionQuery::Checkpoint(checkpoint) => {
+            checkpoint_transactions(&service.reader, Some(checkpoint), page_token, descending)?
+                .take(page_size + 1)
+                .collect::<Result<Vec<_>>>()?
+        }
+        TransactionQuery::Indexed(filters) => {
+            let indexes = service
+                .reader
+                .inner()
+                .indexes()
+                .ok_or_else(RpcError::not_found)?;
+
+            let iters = filters
+                .into_iter()
+                .map(|filter| indexes.transactions_iter(filter, page_token, descending))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Each index is already ordered by cursor so the combined result only needs to be
+            // merged, not sorted.
+            iters
+                .into_iter()
+                .kmerge_by(|a, b| match (a, b) {
+                    (Ok((a, _)), Ok((b, _))) => (a < b) != descending,
+                    // Surface errors as early as possible
+                    (Err(_), _) => true,
+                    (_, Err(_)) => false,
+                })
+                .take(page_size + 1)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| RpcError::new(tonic::Code::Internal, err.to_string()))?
+        }
+    };
+
+    let next_page_token = if transactions.len() > page_size {
+        // SAFETY: We've already verified that transactions is greater than limit, which is
+        // gaurenteed to be >= 1.
+        transactions
+            .pop()
+            .unwrap()
+            .0
+            .pipe(encode_page_token)
+            .pipe(Some)
+    } else {
+        None
+    };
+
+    let transactions = transactions
+        .into_iter()
+        .map(|(_, digest)| {
+            service
+                .reader
+                .get_transaction_read(digest.into())
+                .map(|transaction_read| {
+                    ExecutedTransaction::merge_from(transaction_read, &read_mask)
+                })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(ListTransactionsResponse {
+        transactions,
+        next_page_token,
+    })
+}
+
+enum TransactionQuery {
+    All,
+    Checkpoint(u64),
+    Indexed(Vec<TransactionIndexFilter>),
+}
+
+impl TransactionQuery {
+    fn from_proto(filter: &TransactionFilter) -> Result<Self> {
+        let filter = filter.filter.as_ref().ok_or_else(|| {
+            FieldViolation::new("filter")
+                .with_description("missing filter")
+                .with_reason(ErrorReason::FieldMissing)
+        })?;
+
+        let indexed = match filter {
+            Filter::Checkpoint(checkpoint) => return Ok(Self::Checkpoint(*checkpoint)),
+            Filter::MoveFunction(MoveFunctionFilter {
+                package,
+                module,
+                function,
+            }) => {
+                let package = parse_object_id("filter.move_function.package", package.as_ref())?;
+                let module = module
+                    .as_ref()
+                    .map(|module| parse_identifier("filter.move_function.module", module))
+                    .transpose()?;
+                let function = function
+                    .as_ref()
+                    .map(|function| parse_identifier("filter.move_function.function", function))
+                    .transpose()?;
+
+                match (module, function) {
+                    (None, None) => TransactionIndexFilter::MovePackage(package),
+                    (Some(module), None) => TransactionIndexFilter::MoveModule(package, module),
+                    (Some(module), Some(function)) => {
+                        TransactionIndexFilter::MoveFunction(package, module, function)
+                    }
+                    (None, Some(_)) => {
+                        return Err(FieldViolation::new("filter.move_function.module")
+                            .with_description("module is required when function is specified")
+                            .with_reason(ErrorReason::FieldMissing)
+                            .into())
+                    }
+                }
+            }
+            Filter::InputObject(object_id) => TransactionIndexFilter::InputObject(parse_object_id(
+                "filter.input_object",
+                Some(object_id),
+            )?),
+            Filter::ChangedObject(object_id) => TransactionIndexFilter::ChangedObject(
+                parse_object_id("filter.changed_object", Some(object_id))?,
+            ),
+            Filter::AffectedObject(object_id) => TransactionIndexFilter::AffectedObject(
+                parse_object_id("filter.affected_object", Some(object_id))?,
+            ),
+            Filter::Sender(sender) => {
+                TransactionIndexFilter::Sender(parse_address("filter.sender", Some(sender))?)
+            }
+            Filter::Recipient(recipient) => TransactionIndexFilter::Recipient(parse_address(
+                "filter.recipient",
+                Some(recipient),
+            )?),
+            Filter::SenderAndRecipient(SenderAndRecipientFilter { sender, recipient }) => {
+                TransactionIndexFilter::SenderAndRecipient(
+                    parse_address("filter.sender_and_recipient.sender", sender.as_ref())?,
+                    parse_address("filter.sender_and_recipient.recipient", recipient.as_ref())?,
+                )
+            }
+            Filter::Kind(kind) => {
+                if kind.kinds.is_empty() {
+                    return Err(FieldViolation::new("filter.kind.kinds")
+                        .with_description("at least one kind must be specified")
+                        .with_reason(ErrorReason::FieldMissing)
+                        .into());
+                }
+
+                return kind
+                    .kinds
+                    .iter()
+                    .unique()
+                    .map(|kind| TransactionIndexFilter::Kind(kind.to_owned()))
+                    .collect::<Vec<_>>()
+                    .pipe(Self::Indexed)
+                    .pipe(Ok);
+            }
+        };
+
+        Ok(Self::Indexed(vec![indexed]))
+    }
+}
+
+/// Iterate over transactions directly from the checkpoint store, optionally limited to the
+/// transactions of a single checkpoint.
+fn checkpoint_transactions(
+    reader: &StateReader,
+    checkpoint: Option<u64>,
+    cursor: Option<TransactionCursor>,
+    descending: bool,
+) -> Result<impl Iterator<Item = Result<(TransactionCursor, TransactionDigest)>>> {
+    let direction = if descending {
+        Direction::Descending
+    } else {
+        Direction::Ascending
+    };
+
+    let start = match (cursor, checkpoint) {
+        (Some(cursor), _) => (cursor.checkpoint, Some(cursor.transaction_index as usize)),
+        (None, Some(checkpoint)) => (checkpoint, None),
+        (None, None) if descending => (
+            reader.inner().get_latest_checkpoint()?.sequence_number,
+            None,
+        ),
+        (None, None) => (reader.inner().get_lowest_available_checkpoint()?, None),
+    };
+
+    reader
+        .transaction_iter(direction, start)
+        .map(|result| {
+            let (cursor_info, digest) = result?;
+            let cursor = TransactionCursor {
+                checkpoint: cursor_info.checkpoint,
+                transaction_index: cursor_info.index,
+            };
+            Ok((cursor, digest))
+        })
+        .take_while(move |result| match (result, checkpoint) {
+            (Ok((cursor, _)), Some(checkpoint)) => cursor.checkpoint == checkpoint,
+            _ => true,
+        })
+        .pipe(Ok)
+}
+
+pub(super) fn parse_address(field: &str, address: Option<&String>) -> Result<SuiAddress> {
+    address
+        .ok_or_else(|| {
+            FieldViolation::new(field)
+                .with_description(format!("missing {field}"))
+                .with_reason(ErrorReason::FieldMissing)
+        })?
+        .parse::<Address>()
+        .map(Into::into)
+        .map_err(|e| {
+            FieldViolation::new(field)
+                .with_description(format!("invalid address: {e}"))
+                .with_reason(ErrorReason::FieldInvalid)
+                .into()
+        })
+}
+
+pub(super) fn parse_object_id(field: &str, object_id: Option<&String>) -> Result<ObjectID> {
+    object_id
+        .ok_or_else(|| {
+            FieldViolation::new(field)
+                .with_description(format!("missing {field}"))
+                .with_reason(ErrorReason::FieldMissing)
+        })?
+        .parse::<ObjectId>()
+        .map(Into::into)
+        .map_err(|e| {
+            FieldViolation::new(field)
+                .with_description(format!("invalid object_id: {e}"))
+                .with_reason(ErrorReason::FieldInvalid)
+                .into()
+        })
+}
+
+pub(super) fn parse_identifier(field: &str, identifier: &str) -> Result<String> {
+    if Identifier::is_valid(identifier) {
+        Ok(identifier.to_owned())
+    } else {
+        Err(FieldViolation::new(field)
+            .with_description(format!("invalid identifier: {identifier}"))
+            .with_reason(ErrorReason::FieldInvalid)
+            .into())
+    }
+}
+
+fn decode_page_token(page_token: &[u8]) -> Result<TransactionCursor> {
+    bcs::from_bytes(page_token).map_err(Into::into)
+}
+
+fn encode_page_token(page_token: TransactionCursor) -> Bytes {
+    bcs::to_bytes(&page_token).unwrap().into()
+}
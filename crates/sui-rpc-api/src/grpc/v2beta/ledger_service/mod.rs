@@ -15,6 +15,10 @@ use crate::proto::rpc::v2beta::GetObjectRequest;
 use crate::proto::rpc::v2beta::GetServiceInfoRequest;
 use crate::proto::rpc::v2beta::GetServiceInfoResponse;
 use crate::proto::rpc::v2beta::GetTransactionRequest;
+use crate::proto::rpc::v2beta::ListEventsRequest;
+use crate::proto::rpc::v2beta::ListEventsResponse;
+use crate::proto::rpc::v2beta::ListTransactionsRequest;
+use crate::proto::rpc::v2beta::ListTransactionsResponse;
 use crate::proto::rpc::v2beta::Object;
 use crate::RpcService;
 
@@ -23,6 +27,8 @@ mod get_epoch;
 mod get_object;
 mod get_service_info;
 mod get_transaction;
+mod list_events;
+mod list_transactions;
 
 #[tonic::async_trait]
 impl LedgerService for RpcService {
@@ -88,4 +94,22 @@ impl LedgerService for RpcService {
             .map(tonic::Response::new)
             .map_err(Into::into)
     }
+
+    async fn list_transactions(
+        &self,
+        request: tonic::Request<ListTransactionsRequest>,
+    ) -> Result<tonic::Response<ListTransactionsResponse>, tonic::Status> {
+        list_transactions::list_transactions(self, request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn list_events(
+        &self,
+        request: tonic::Request<ListEventsRequest>,
+    ) -> Result<tonic::Response<ListEventsResponse>, tonic::Status> {
+        list_events::list_events(self, request.into_inner())
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
 }
@@ -187,9 +187,9 @@ impl RpcService {
                 .add_service(live_data_service)
                 .add_service(node_service);
 
-            if let Some(subscription_service_handle) = self.subscription_service_handle.clone() {
+            if self.subscription_service_handle.is_some() {
                 let subscription_service =
-crate::proto::rpc::v2alpha::subscription_service_server::SubscriptionServiceServer::new(subscription_service_handle);
+crate::proto::rpc::v2alpha::subscription_service_server::SubscriptionServiceServer::new(self.clone());
                 health_reporter
                     .set_service_status(
                         service_name(&subscription_service),
@@ -883,6 +883,47 @@ pub struct SubscribeCheckpointsRequest {
     /// SubscribeCheckpointsResponse should be returned.
     #[prost(message, optional, tag = "1")]
     pub read_mask: ::core::option::Option<::prost_types::FieldMask>,
+    /// Optional. The checkpoint to begin the stream at (inclusive).
+    ///
+    /// If unset the stream begins with the latest executed checkpoint. If the
+    /// requested checkpoint has been pruned from the server, the request will
+    /// fail with `NOT_FOUND`.
+    #[prost(uint64, optional, tag = "2")]
+    pub start_checkpoint: ::core::option::Option<u64>,
+    /// Optional. Filter for restricting which transactions and events are
+    /// returned for each checkpoint.
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<CheckpointFilter>,
+}
+/// Server-side filter applied to the transactions of each checkpoint in a
+/// subscription.
+///
+/// Each populated field restricts the set of transactions returned; a
+/// transaction must match every populated field, and matches a field if it
+/// matches any one of the values in that field. An empty filter matches every
+/// transaction.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckpointFilter {
+    /// Only include transactions sent by one of these addresses.
+    #[prost(string, repeated, tag = "1")]
+    pub senders: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Only include transactions which read, modify, create, or delete one of
+    /// these objects.
+    #[prost(string, repeated, tag = "2")]
+    pub objects: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Only include transactions which call into, publish, or upgrade one of
+    /// these packages.
+    #[prost(string, repeated, tag = "3")]
+    pub packages: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Only include transactions which emit an event matching one of these move
+    /// event types.
+    ///
+    /// Event types can be specified as a module (e.g. `0x2::coin`), a struct
+    /// (e.g. `0x2::coin::CoinMetadata`, matching any type parameters), or a
+    /// fully instantiated struct tag. When populated only the matching events
+    /// are returned for each transaction.
+    #[prost(string, repeated, tag = "4")]
+    pub event_types: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 /// Response message for SubscriptionService.SubscribeCheckpoints
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -989,14 +1030,21 @@ pub mod subscription_service_client {
         /// Subscribe to the stream of checkpoints.
         ///
         /// This API provides a subscription to the checkpoint stream for the Sui
-        /// blockchain. When a subscription is initialized the stream will begin with
-        /// the latest executed checkpoint as seen by the server. Responses are
-        /// gaurenteed to return checkpoints in-order and without gaps. This enables
-        /// clients to know exactly the last checkpoint they have processed and in the
-        /// event the subscription terminates (either by the client/server or by the
-        /// connection breaking), clients will be able to reinitailize a subscription
-        /// and then leverage other APIs in order to request data for the checkpoints
-        /// they missed.
+        /// blockchain. When a subscription is initialized without a `start_checkpoint`
+        /// the stream will begin with the latest executed checkpoint as seen by the
+        /// server. Responses are gaurenteed to return checkpoints in-order and without
+        /// gaps. This enables clients to know exactly the last checkpoint they have
+        /// processed and in the event the subscription terminates (either by the
+        /// client/server or by the connection breaking), clients will be able to
+        /// reinitailize a subscription with `start_checkpoint` set to the checkpoint
+        /// after their last seen cursor. The server will backfill any historical
+        /// checkpoints it has available before seamlessly transitioning to the live
+        /// stream.
+        ///
+        /// A `filter` can be provided in order to only receive the transactions (and
+        /// events) that a client is interested in. A response is still sent for every
+        /// checkpoint, even if no transactions in it matched the filter, so that the
+        /// cursor continues to advance.
         pub async fn subscribe_checkpoints(
             &mut self,
             request: impl tonic::IntoRequest<super::SubscribeCheckpointsRequest>,
@@ -1055,14 +1103,21 @@ pub mod subscription_service_server {
         /// Subscribe to the stream of checkpoints.
         ///
         /// This API provides a subscription to the checkpoint stream for the Sui
-        /// blockchain. When a subscription is initialized the stream will begin with
-        /// the latest executed checkpoint as seen by the server. Responses are
-        /// gaurenteed to return checkpoints in-order and without gaps. This enables
-        /// clients to know exactly the last checkpoint they have processed and in the
-        /// event the subscription terminates (either by the client/server or by the
-        /// connection breaking), clients will be able to reinitailize a subscription
-        /// and then leverage other APIs in order to request data for the checkpoints
-        /// they missed.
+        /// blockchain. When a subscription is initialized without a `start_checkpoint`
+        /// the stream will begin with the latest executed checkpoint as seen by the
+        /// server. Responses are gaurenteed to return checkpoints in-order and without
+        /// gaps. This enables clients to know exactly the last checkpoint they have
+        /// processed and in the event the subscription terminates (either by the
+        /// client/server or by the connection breaking), clients will be able to
+        /// reinitailize a subscription with `start_checkpoint` set to the checkpoint
+        /// after their last seen cursor. The server will backfill any historical
+        /// checkpoints it has available before seamlessly transitioning to the live
+        /// stream.
+        ///
+        /// A `filter` can be provided in order to only receive the transactions (and
+        /// events) that a client is interested in. A response is still sent for every
+        /// checkpoint, even if no transactions in it matched the filter, so that the
+        /// cursor continues to advance.
         async fn subscribe_checkpoints(
             &self,
             request: tonic::Request<super::SubscribeCheckpointsRequest>,
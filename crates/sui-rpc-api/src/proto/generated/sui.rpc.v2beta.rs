@@ -1437,6 +1437,215 @@ pub struct GetEpochRequest {
     #[prost(message, optional, tag = "2")]
     pub read_mask: ::core::option::Option<::prost_types::FieldMask>,
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTransactionsRequest {
+    /// Optional. Filter restricting which transactions are returned.
+    /// If no filter is specified, all transactions are returned.
+    #[prost(message, optional, tag = "1")]
+    pub filter: ::core::option::Option<TransactionFilter>,
+    /// The maximum number of transactions to return. The service may return fewer
+    /// than this value.
+    /// If unspecified, at most `50` entries will be returned.
+    /// The maximum value is `1000`; values above `1000` will be coerced to `1000`.
+    #[prost(uint32, optional, tag = "2")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListTransactions` call.
+    /// Provide this to retrieve the subsequent page.
+    ///
+    /// When paginating, all other parameters provided to `ListTransactions` must
+    /// match the call that provided the page token.
+    #[prost(bytes = "bytes", optional, tag = "3")]
+    pub page_token: ::core::option::Option<::prost::bytes::Bytes>,
+    /// Mask specifying which fields to read.
+    /// If no mask is specified, defaults to `digest`.
+    #[prost(message, optional, tag = "4")]
+    pub read_mask: ::core::option::Option<::prost_types::FieldMask>,
+    /// Return transactions from most to least recent. Defaults to `false`.
+    #[prost(bool, optional, tag = "5")]
+    pub descending: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTransactionsResponse {
+    /// Page of transactions.
+    #[prost(message, repeated, tag = "1")]
+    pub transactions: ::prost::alloc::vec::Vec<ExecutedTransaction>,
+    /// A token, which can be sent as `page_token` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[prost(bytes = "bytes", optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+/// Filter for selecting transactions.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionFilter {
+    #[prost(oneof = "transaction_filter::Filter", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9")]
+    pub filter: ::core::option::Option<transaction_filter::Filter>,
+}
+/// Nested message and enum types in `TransactionFilter`.
+pub mod transaction_filter {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Filter {
+        /// Transactions included in a checkpoint.
+        #[prost(uint64, tag = "1")]
+        Checkpoint(u64),
+        /// Transactions which call a move function.
+        #[prost(message, tag = "2")]
+        MoveFunction(super::MoveFunctionFilter),
+        /// Transactions which take an object as an input.
+        #[prost(string, tag = "3")]
+        InputObject(::prost::alloc::string::String),
+        /// Transactions which create, mutate, or unwrap an object.
+        #[prost(string, tag = "4")]
+        ChangedObject(::prost::alloc::string::String),
+        /// Transactions which touch an object in any way, including deleting,
+        /// wrapping, or reading it as a shared object.
+        #[prost(string, tag = "5")]
+        AffectedObject(::prost::alloc::string::String),
+        /// Transactions sent by an address.
+        #[prost(string, tag = "6")]
+        Sender(::prost::alloc::string::String),
+        /// Transactions which create, mutate, or unwrap an object owned by an
+        /// address.
+        #[prost(string, tag = "7")]
+        Recipient(::prost::alloc::string::String),
+        /// Transactions sent by one address to another.
+        #[prost(message, tag = "8")]
+        SenderAndRecipient(super::SenderAndRecipientFilter),
+        /// Transactions of one of a set of kinds.
+        #[prost(message, tag = "9")]
+        Kind(super::TransactionKindFilter),
+    }
+}
+/// Filter for transactions calling a move function.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MoveFunctionFilter {
+    /// Required. The package containing the function.
+    #[prost(string, optional, tag = "1")]
+    pub package: ::core::option::Option<::prost::alloc::string::String>,
+    /// Optional. The module containing the function. Required if `function` is
+    /// set.
+    #[prost(string, optional, tag = "2")]
+    pub module: ::core::option::Option<::prost::alloc::string::String>,
+    /// Optional. The name of the function.
+    #[prost(string, optional, tag = "3")]
+    pub function: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SenderAndRecipientFilter {
+    /// Required. Address of the sender of the transaction.
+    #[prost(string, optional, tag = "1")]
+    pub sender: ::core::option::Option<::prost::alloc::string::String>,
+    /// Required. Address of the recipient.
+    #[prost(string, optional, tag = "2")]
+    pub recipient: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionKindFilter {
+    /// Names of the transaction kinds to include, e.g. `ProgrammableTransaction`
+    /// or `ConsensusCommitPrologueV3`.
+    #[prost(string, repeated, tag = "1")]
+    pub kinds: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListEventsRequest {
+    /// Optional. Filter restricting which events are returned.
+    /// If no filter is specified, all events are returned.
+    #[prost(message, optional, tag = "1")]
+    pub filter: ::core::option::Option<EventFilter>,
+    /// The maximum number of events to return. The service may return fewer than
+    /// this value.
+    /// If unspecified, at most `50` entries will be returned.
+    /// The maximum value is `1000`; values above `1000` will be coerced to `1000`.
+    #[prost(uint32, optional, tag = "2")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListEvents` call.
+    /// Provide this to retrieve the subsequent page.
+    ///
+    /// When paginating, all other parameters provided to `ListEvents` must match
+    /// the call that provided the page token.
+    #[prost(bytes = "bytes", optional, tag = "3")]
+    pub page_token: ::core::option::Option<::prost::bytes::Bytes>,
+    /// Return events from most to least recent. Defaults to `false`.
+    #[prost(bool, optional, tag = "4")]
+    pub descending: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListEventsResponse {
+    /// Page of events.
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<IndexedEvent>,
+    /// A token, which can be sent as `page_token` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[prost(bytes = "bytes", optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+/// An event along with its position in the chain.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IndexedEvent {
+    /// The digest of the transaction which emitted this event.
+    #[prost(string, optional, tag = "1")]
+    pub transaction_digest: ::core::option::Option<::prost::alloc::string::String>,
+    /// The index of this event in the transaction's events.
+    #[prost(uint64, optional, tag = "2")]
+    pub event_index: ::core::option::Option<u64>,
+    /// The checkpoint the emitting transaction was included in.
+    #[prost(uint64, optional, tag = "3")]
+    pub checkpoint: ::core::option::Option<u64>,
+    /// Timestamp of the checkpoint the emitting transaction was included in.
+    #[prost(message, optional, tag = "4")]
+    pub timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    /// The event.
+    #[prost(message, optional, tag = "5")]
+    pub event: ::core::option::Option<Event>,
+}
+/// Filter for selecting events.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventFilter {
+    #[prost(oneof = "event_filter::Filter", tags = "1, 2, 3, 4, 5, 6")]
+    pub filter: ::core::option::Option<event_filter::Filter>,
+}
+/// Nested message and enum types in `EventFilter`.
+pub mod event_filter {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Filter {
+        /// Events emitted by transactions sent by an address.
+        #[prost(string, tag = "1")]
+        Sender(::prost::alloc::string::String),
+        /// Events emitted by a transaction.
+        #[prost(string, tag = "2")]
+        Transaction(::prost::alloc::string::String),
+        /// Events emitted by transactions calling into a module, regardless of
+        /// which module the event's type is defined in.
+        #[prost(message, tag = "3")]
+        MoveModule(super::MoveModuleFilter),
+        /// Events of exactly this type, e.g. `0x2::coin::CurrencyCreated<0x2::sui::SUI>`.
+        #[prost(string, tag = "4")]
+        MoveEventType(::prost::alloc::string::String),
+        /// Events whose type is defined in a module.
+        #[prost(message, tag = "5")]
+        MoveEventModule(super::MoveModuleFilter),
+        /// Events emitted in a time range.
+        #[prost(message, tag = "6")]
+        TimeRange(super::TimeRangeFilter),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MoveModuleFilter {
+    /// Required. The package containing the module.
+    #[prost(string, optional, tag = "1")]
+    pub package: ::core::option::Option<::prost::alloc::string::String>,
+    /// Required. The name of the module.
+    #[prost(string, optional, tag = "2")]
+    pub module: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct TimeRangeFilter {
+    /// Start of the time range, inclusive.
+    #[prost(message, optional, tag = "1")]
+    pub start_time: ::core::option::Option<::prost_types::Timestamp>,
+    /// End of the time range, exclusive.
+    #[prost(message, optional, tag = "2")]
+    pub end_time: ::core::option::Option<::prost_types::Timestamp>,
+}
 /// Generated client implementations.
 pub mod ledger_service_client {
     #![allow(
@@ -1701,6 +1910,62 @@ pub mod ledger_service_client {
                 .insert(GrpcMethod::new("sui.rpc.v2beta.LedgerService", "GetEpoch"));
             self.inner.unary(req, path, codec).await
         }
+        /// List transactions, in the order they were executed, which match a filter.
+        ///
+        /// Filtered queries require the server to have its rpc indexes enabled.
+        pub async fn list_transactions(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListTransactionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListTransactionsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.rpc.v2beta.LedgerService/ListTransactions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sui.rpc.v2beta.LedgerService", "ListTransactions"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// List events, in the order they were emitted, which match a filter.
+        ///
+        /// Filtered queries require the server to have its rpc indexes enabled.
+        pub async fn list_events(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListEventsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.rpc.v2beta.LedgerService/ListEvents",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.rpc.v2beta.LedgerService", "ListEvents"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -1757,6 +2022,26 @@ pub mod ledger_service_server {
             &self,
             request: tonic::Request<super::GetEpochRequest>,
         ) -> std::result::Result<tonic::Response<super::Epoch>, tonic::Status>;
+        /// List transactions, in the order they were executed, which match a filter.
+        ///
+        /// Filtered queries require the server to have its rpc indexes enabled.
+        async fn list_transactions(
+            &self,
+            request: tonic::Request<super::ListTransactionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListTransactionsResponse>,
+            tonic::Status,
+        >;
+        /// List events, in the order they were emitted, which match a filter.
+        ///
+        /// Filtered queries require the server to have its rpc indexes enabled.
+        async fn list_events(
+            &self,
+            request: tonic::Request<super::ListEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListEventsResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct LedgerServiceServer<T> {
@@ -2155,6 +2440,97 @@ pub mod ledger_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/sui.rpc.v2beta.LedgerService/ListTransactions" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListTransactionsSvc<T: LedgerService>(pub Arc<T>);
+                    impl<
+                        T: LedgerService,
+                    > tonic::server::UnaryService<super::ListTransactionsRequest>
+                    for ListTransactionsSvc<T> {
+                        type Response = super::ListTransactionsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListTransactionsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as LedgerService>::list_transactions(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListTransactionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.rpc.v2beta.LedgerService/ListEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListEventsSvc<T: LedgerService>(pub Arc<T>);
+                    impl<
+                        T: LedgerService,
+                    > tonic::server::UnaryService<super::ListEventsRequest>
+                    for ListEventsSvc<T> {
+                        type Response = super::ListEventsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as LedgerService>::list_events(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
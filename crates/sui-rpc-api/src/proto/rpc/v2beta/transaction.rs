@@ -1833,3 +1833,7 @@ impl super::GetTransactionRequest {
 impl super::BatchGetTransactionsRequest {
     pub const READ_MASK_DEFAULT: &str = super::GetTransactionRequest::READ_MASK_DEFAULT;
 }
+
+impl super::ListTransactionsRequest {
+    pub const READ_MASK_DEFAULT: &str = super::GetTransactionRequest::READ_MASK_DEFAULT;
+}
@@ -7,6 +7,8 @@ use std::sync::Arc;
 use sui_types::full_checkpoint_content::CheckpointData;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
 use tracing::error;
 use tracing::info;
 use tracing::trace;
@@ -23,9 +25,17 @@ struct SubscriptionRequest {
 #[derive(Clone)]
 pub struct SubscriptionServiceHandle {
     sender: mpsc::Sender<SubscriptionRequest>,
+    slots: Arc<Semaphore>,
 }
 
 impl SubscriptionServiceHandle {
+    /// Reserve one of the `MAX_SUBSCRIBERS` subscriber slots, for a subscription that has work to
+    /// do (e.g. backfilling from the store) before it registers. The slot is released when the
+    /// returned permit is dropped. Returns `None` if every slot is taken.
+    pub fn reserve_slot(&self) -> Option<OwnedSemaphorePermit> {
+        self.slots.clone().try_acquire_owned().ok()
+    }
+
     pub async fn register_subscription(&self) -> Option<mpsc::Receiver<Arc<Checkpoint>>> {
         let (sender, reciever) = oneshot::channel();
         let request = SubscriptionRequest { sender };
@@ -68,6 +78,7 @@ impl SubscriptionService {
             checkpoint_sender,
             SubscriptionServiceHandle {
                 sender: subscription_request_sender,
+                slots: Arc::new(Semaphore::new(MAX_SUBSCRIBERS)),
             },
         )
     }
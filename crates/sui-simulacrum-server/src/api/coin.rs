@@ -0,0 +1,203 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use move_core_types::language_storage::TypeTag;
+use rand::rngs::StdRng;
+use simulacrum::{Simulacrum, SimulatorStore};
+use sui_json_rpc_types::{Balance, Coin, CoinPage};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    gas_coin::GAS,
+    object::Object,
+    parse_sui_type_tag,
+};
+
+use crate::{
+    error::{invalid_params, RpcError},
+    handle::SimulacrumHandle,
+};
+
+use super::rpc_module::RpcModule;
+
+/// The default and maximum number of coins returned in a single page.
+const MAX_COINS_PER_PAGE: usize = 50;
+
+#[open_rpc(namespace = "suix", tag = "Coin Query API")]
+#[rpc(server, namespace = "suix")]
+trait CoinsApi {
+    /// Return all Coin<`coin_type`> objects owned by an address.
+    #[method(name = "getCoins")]
+    async fn get_coins(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+        /// optional paging cursor
+        cursor: Option<ObjectID>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+    ) -> RpcResult<CoinPage>;
+
+    /// Return the total coin balance for one coin type, owned by the address owner.
+    #[method(name = "getBalance")]
+    async fn get_balance(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional type names for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance>;
+
+    /// Return the total coin balance for all coin type, owned by the address owner.
+    #[method(name = "getAllBalances")]
+    async fn get_all_balances(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+    ) -> RpcResult<Vec<Balance>>;
+}
+
+pub(crate) struct Coins<S: SimulatorStore>(pub SimulacrumHandle<S>);
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Bad coin type {0:?}: {1}")]
+    BadType(String, anyhow::Error),
+}
+
+#[async_trait::async_trait]
+impl<S: SimulatorStore + Send + Sync + 'static> CoinsApiServer for Coins<S> {
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<CoinPage> {
+        Ok(self
+            .0
+            .read_blocking(move |sim| coins_response(sim, owner, coin_type, cursor, limit))
+            .await?)
+    }
+
+    async fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance> {
+        let coin_type = parse_coin_type(coin_type)?;
+        let balance = self
+            .0
+            .read_blocking(move |sim| -> Result<_, RpcError> { Ok(balances_response(sim, owner)) })
+            .await?
+            .remove(&coin_type)
+            .unwrap_or_else(|| {
+                Balance::zero(coin_type.to_canonical_string(/* with_prefix */ true))
+            });
+        Ok(balance)
+    }
+
+    async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
+        let balances = self
+            .0
+            .read_blocking(move |sim| -> Result<_, RpcError> { Ok(balances_response(sim, owner)) })
+            .await?;
+        Ok(balances.into_values().collect())
+    }
+}
+
+impl<S: SimulatorStore + Send + Sync + 'static> RpcModule for Coins<S> {
+    fn schema(&self) -> Module {
+        CoinsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Load data and generate response for `getCoins`. Coins are returned in ascending order of ID,
+/// starting after the one with ID `cursor`, if it is provided.
+fn coins_response<S: SimulatorStore>(
+    sim: &Simulacrum<StdRng, S>,
+    owner: SuiAddress,
+    coin_type: Option<String>,
+    cursor: Option<ObjectID>,
+    limit: Option<usize>,
+) -> Result<CoinPage, RpcError<Error>> {
+    let coin_type = parse_coin_type(coin_type)?;
+    let limit = limit.unwrap_or(MAX_COINS_PER_PAGE).min(MAX_COINS_PER_PAGE);
+
+    let coins: BTreeMap<ObjectID, Coin> = sim
+        .store()
+        .owned_objects(owner)
+        .filter(|o| o.coin_type_maybe().as_ref() == Some(&coin_type))
+        .filter(|o| cursor.is_none_or(|c| o.id() > c))
+        .map(|o| (o.id(), coin(&coin_type, &o)))
+        .collect();
+
+    let mut coins = coins.into_values();
+    let data: Vec<_> = coins.by_ref().take(limit).collect();
+    let has_next_page = coins.next().is_some();
+    let next_cursor = data.last().map(|c| c.coin_object_id.to_string());
+    Ok(CoinPage {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// Load data and generate the balances of all coin types owned by `owner`, for `getBalance` and
+/// `getAllBalances`.
+fn balances_response<S: SimulatorStore>(
+    sim: &Simulacrum<StdRng, S>,
+    owner: SuiAddress,
+) -> BTreeMap<TypeTag, Balance> {
+    let mut balances = BTreeMap::new();
+    for object in sim.store().owned_objects(owner) {
+        let Some(coin_type) = object.coin_type_maybe() else {
+            continue;
+        };
+
+        let balance = balances
+            .entry(coin_type.clone())
+            .or_insert_with(|| Balance {
+                coin_type: coin_type.to_canonical_string(/* with_prefix */ true),
+                coin_object_count: 0,
+                total_balance: 0,
+                locked_balance: HashMap::new(),
+            });
+
+        balance.coin_object_count += 1;
+        balance.total_balance += object.get_coin_value_unsafe() as u128;
+    }
+
+    balances
+}
+
+/// Parse the coin type parameter shared by the methods of this module, defaulting to SUI.
+fn parse_coin_type(coin_type: Option<String>) -> Result<TypeTag, RpcError<Error>> {
+    let Some(coin_type) = coin_type else {
+        return Ok(GAS::type_tag());
+    };
+
+    parse_sui_type_tag(&coin_type).map_err(|e| invalid_params(Error::BadType(coin_type, e)))
+}
+
+fn coin(coin_type: &TypeTag, object: &Object) -> Coin {
+    let (object_id, version, digest) = object.compute_object_reference();
+    Coin {
+        coin_type: coin_type.to_canonical_string(/* with_prefix */ true),
+        coin_object_id: object_id,
+        version,
+        digest,
+        balance: object.get_coin_value_unsafe(),
+        previous_transaction: object.previous_transaction,
+    }
+}
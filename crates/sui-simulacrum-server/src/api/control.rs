@@ -0,0 +1,162 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use rand::rngs::StdRng;
+use simulacrum::{Simulacrum, SimulatorStore};
+use sui_json_rpc_types::{Checkpoint, SuiTransactionBlockEffects};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{
+    base_types::SuiAddress, effects::TransactionEffects, messages_checkpoint::VerifiedCheckpoint,
+    sui_serde::BigInt,
+};
+
+use crate::{
+    error::{invalid_params, RpcError},
+    handle::SimulacrumHandle,
+};
+
+use super::rpc_module::RpcModule;
+
+#[open_rpc(namespace = "simulacrum", tag = "Simulacrum Control API")]
+#[rpc(server, namespace = "simulacrum")]
+trait ControlApi {
+    /// Advance the on-chain clock by `duration_ms`, by executing a consensus commit prologue
+    /// transaction. Returns the effects of that transaction.
+    #[method(name = "advanceClock")]
+    async fn advance_clock(
+        &self,
+        /// how far to move the clock forward, in milliseconds
+        duration_ms: BigInt<u64>,
+    ) -> RpcResult<SuiTransactionBlockEffects>;
+
+    /// Advance to the next epoch, by executing an end of epoch transaction and creating the final
+    /// checkpoint of the current epoch. Returns the ID of the new epoch.
+    #[method(name = "advanceEpoch")]
+    async fn advance_epoch(
+        &self,
+        /// whether to include a transaction that creates the on-chain randomness state object, if
+        /// it does not exist yet
+        create_random_state: Option<bool>,
+    ) -> RpcResult<BigInt<u64>>;
+
+    /// Create a checkpoint containing all the transactions executed since the last checkpoint.
+    #[method(name = "createCheckpoint")]
+    async fn create_checkpoint(&self) -> RpcResult<Checkpoint>;
+
+    /// Send `amount` MIST to `address` from one of the accounts funded at genesis, in a new coin.
+    /// Returns the effects of the transfer.
+    #[method(name = "requestGas")]
+    async fn request_gas(
+        &self,
+        /// the address to fund
+        address: SuiAddress,
+        /// the amount to send, in MIST
+        amount: BigInt<u64>,
+    ) -> RpcResult<SuiTransactionBlockEffects>;
+}
+
+pub(crate) struct Control<S: SimulatorStore>(pub SimulacrumHandle<S>);
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Failed to request gas: {0}")]
+    RequestGas(anyhow::Error),
+}
+
+#[async_trait::async_trait]
+impl<S: SimulatorStore + Send + Sync + 'static> ControlApiServer for Control<S> {
+    async fn advance_clock(
+        &self,
+        duration_ms: BigInt<u64>,
+    ) -> RpcResult<SuiTransactionBlockEffects> {
+        let duration = Duration::from_millis(*duration_ms);
+        let effects = self
+            .0
+            .write_blocking(move |handle, sim| -> Result<_, RpcError> {
+                let effects = sim.advance_clock(duration);
+                handle.checkpoint(sim);
+                Ok(effects)
+            })
+            .await?;
+
+        Ok(effects_response(effects)?)
+    }
+
+    async fn advance_epoch(&self, create_random_state: Option<bool>) -> RpcResult<BigInt<u64>> {
+        let epoch = self
+            .0
+            .write_blocking(move |_, sim| -> Result<_, RpcError> {
+                sim.advance_epoch(create_random_state.unwrap_or(false));
+                Ok(sim.epoch_start_state().epoch())
+            })
+            .await?;
+
+        Ok(epoch.into())
+    }
+
+    async fn create_checkpoint(&self) -> RpcResult<Checkpoint> {
+        Ok(self
+            .0
+            .write_blocking(|_, sim| {
+                let checkpoint = sim.create_checkpoint();
+                checkpoint_response(sim, checkpoint)
+            })
+            .await?)
+    }
+
+    async fn request_gas(
+        &self,
+        address: SuiAddress,
+        amount: BigInt<u64>,
+    ) -> RpcResult<SuiTransactionBlockEffects> {
+        let amount = *amount;
+        let effects = self
+            .0
+            .write_blocking(move |handle, sim| {
+                let effects = sim
+                    .request_gas(address, amount)
+                    .map_err(|e| invalid_params(Error::RequestGas(e)))?;
+                handle.checkpoint(sim);
+                Ok(effects)
+            })
+            .await?;
+
+        Ok(effects_response(effects)?)
+    }
+}
+
+impl<S: SimulatorStore + Send + Sync + 'static> RpcModule for Control<S> {
+    fn schema(&self) -> Module {
+        ControlApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+fn effects_response(effects: TransactionEffects) -> Result<SuiTransactionBlockEffects, RpcError> {
+    Ok(effects.try_into().context("Failed to convert effects")?)
+}
+
+/// Generate the response for `createCheckpoint`, from the checkpoint that was created.
+fn checkpoint_response<S: SimulatorStore>(
+    sim: &Simulacrum<StdRng, S>,
+    checkpoint: VerifiedCheckpoint,
+) -> Result<Checkpoint, RpcError> {
+    let contents = sim
+        .store()
+        .get_checkpoint_contents(&checkpoint.content_digest)
+        .context("Contents not found for checkpoint")?;
+
+    Ok(Checkpoint::from((
+        checkpoint.data().clone(),
+        contents,
+        checkpoint.auth_sig().signature.clone(),
+    )))
+}
@@ -0,0 +1,58 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use simulacrum::SimulatorStore;
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{
+    sui_serde::BigInt,
+    sui_system_state::{sui_system_state_summary::SuiSystemStateSummary, SuiSystemStateTrait},
+};
+
+use crate::{error::RpcError, handle::SimulacrumHandle};
+
+use super::rpc_module::RpcModule;
+
+#[open_rpc(namespace = "suix", tag = "Governance API")]
+#[rpc(server, namespace = "suix")]
+trait GovernanceApi {
+    /// Return the reference gas price for the network as of the latest epoch.
+    #[method(name = "getReferenceGasPrice")]
+    async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>>;
+
+    /// Return a summary of the latest version of the Sui System State object (0x5), on-chain.
+    #[method(name = "getLatestSuiSystemState")]
+    async fn get_latest_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary>;
+}
+
+pub(crate) struct Governance<S: SimulatorStore>(pub SimulacrumHandle<S>);
+
+#[async_trait::async_trait]
+impl<S: SimulatorStore + Send + Sync + 'static> GovernanceApiServer for Governance<S> {
+    async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>> {
+        let reference_gas_price = self
+            .0
+            .read_blocking(|sim| -> Result<_, RpcError> { Ok(sim.reference_gas_price()) })
+            .await?;
+        Ok(reference_gas_price.into())
+    }
+
+    async fn get_latest_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary> {
+        let system_state = self
+            .0
+            .read_blocking(|sim| -> Result<_, RpcError> { Ok(sim.store().get_system_state()) })
+            .await?;
+        Ok(system_state.into_sui_system_state_summary())
+    }
+}
+
+impl<S: SimulatorStore + Send + Sync + 'static> RpcModule for Governance<S> {
+    fn schema(&self) -> Module {
+        GovernanceApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
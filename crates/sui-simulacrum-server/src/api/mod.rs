@@ -0,0 +1,10 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+pub(crate) mod coin;
+pub(crate) mod control;
+pub(crate) mod governance;
+pub(crate) mod read;
+pub(crate) mod rpc_module;
+pub(crate) mod transactions;
+pub(crate) mod write;
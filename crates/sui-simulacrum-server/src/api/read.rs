@@ -0,0 +1,333 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use rand::rngs::StdRng;
+use simulacrum::{Simulacrum, SimulatorStore};
+use sui_json_rpc_types::{
+    Checkpoint, CheckpointId, SuiEvent, SuiObjectDataOptions, SuiObjectResponse,
+    SuiPastObjectResponse, SuiTransactionBlockEvents, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions,
+};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
+    digests::TransactionDigest,
+    object::ObjectRead,
+    storage::{ReadStore, RpcStateReader},
+    sui_serde::BigInt,
+};
+
+use crate::{
+    error::{invalid_params, InternalContext, RpcError},
+    handle::{ModuleResolver, SimulacrumHandle},
+};
+
+use super::{rpc_module::RpcModule, transactions::transaction_response};
+
+#[open_rpc(namespace = "sui", tag = "Read API")]
+#[rpc(server, namespace = "sui")]
+trait ReadApi {
+    /// Return the first four bytes of the chain's genesis checkpoint digest.
+    #[method(name = "getChainIdentifier")]
+    async fn get_chain_identifier(&self) -> RpcResult<String>;
+
+    /// Return the sequence number of the latest checkpoint that has been created.
+    #[method(name = "getLatestCheckpointSequenceNumber")]
+    async fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<BigInt<u64>>;
+
+    /// Return a checkpoint
+    #[method(name = "getCheckpoint")]
+    async fn get_checkpoint(
+        &self,
+        /// Checkpoint identifier, can use either checkpoint digest, or checkpoint sequence number as input.
+        id: CheckpointId,
+    ) -> RpcResult<Checkpoint>;
+
+    /// Return the object information for a specified object
+    #[method(name = "getObject")]
+    async fn get_object(
+        &self,
+        /// the ID of the queried object
+        object_id: ObjectID,
+        /// options for specifying the content to be returned
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiObjectResponse>;
+
+    /// Return the object data for a list of objects
+    #[method(name = "multiGetObjects")]
+    async fn multi_get_objects(
+        &self,
+        /// the IDs of the queried objects
+        object_ids: Vec<ObjectID>,
+        /// options for specifying the content to be returned
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<Vec<SuiObjectResponse>>;
+
+    /// Return the object information for a specified version
+    #[method(name = "tryGetPastObject")]
+    async fn try_get_past_object(
+        &self,
+        /// the ID of the queried object
+        object_id: ObjectID,
+        /// the version of the queried object
+        version: SequenceNumber,
+        /// options for specifying the content to be returned
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiPastObjectResponse>;
+
+    /// Return the transaction response object.
+    #[method(name = "getTransactionBlock")]
+    async fn get_transaction_block(
+        &self,
+        /// the digest of the queried transaction
+        digest: TransactionDigest,
+        /// options for specifying the content to be returned
+        options: Option<SuiTransactionBlockResponseOptions>,
+    ) -> RpcResult<SuiTransactionBlockResponse>;
+
+    /// Return transaction events.
+    #[method(name = "getEvents")]
+    async fn get_events(
+        &self,
+        /// the digest of the transaction whose events are being queried
+        transaction_digest: TransactionDigest,
+    ) -> RpcResult<Vec<SuiEvent>>;
+}
+
+pub(crate) struct Read<S: SimulatorStore>(pub SimulacrumHandle<S>);
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Checkpoint {0:?} not found")]
+    CheckpointNotFound(CheckpointId),
+
+    #[error("Transaction {0} not found")]
+    TransactionNotFound(TransactionDigest),
+}
+
+#[async_trait::async_trait]
+impl<S: SimulatorStore + Send + Sync + 'static> ReadApiServer for Read<S> {
+    async fn get_chain_identifier(&self) -> RpcResult<String> {
+        Ok(self.0.read_blocking(chain_identifier_response).await?)
+    }
+
+    async fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<BigInt<u64>> {
+        Ok(self.0.read_blocking(latest_checkpoint_response).await?)
+    }
+
+    async fn get_checkpoint(&self, id: CheckpointId) -> RpcResult<Checkpoint> {
+        Ok(self
+            .0
+            .read_blocking(move |sim| checkpoint_response(sim, id))
+            .await?)
+    }
+
+    async fn get_object(
+        &self,
+        object_id: ObjectID,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiObjectResponse> {
+        Ok(self
+            .0
+            .read_blocking(move |sim| {
+                object_response(sim.store(), object_id, options.unwrap_or_default())
+            })
+            .await
+            .with_internal_context(|| format!("Failed to load object {object_id}"))?)
+    }
+
+    async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<Vec<SuiObjectResponse>> {
+        let options = options.unwrap_or_default();
+        Ok(self
+            .0
+            .read_blocking(move |sim| {
+                object_ids
+                    .into_iter()
+                    .map(|id| {
+                        object_response(sim.store(), id, options.clone())
+                            .with_internal_context(|| format!("Failed to load object {id}"))
+                    })
+                    .collect::<Result<_, _>>()
+            })
+            .await?)
+    }
+
+    async fn try_get_past_object(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiPastObjectResponse> {
+        Ok(self
+            .0
+            .read_blocking(move |sim| {
+                past_object_response(sim, object_id, version, options.unwrap_or_default())
+            })
+            .await
+            .with_internal_context(|| {
+                format!("Failed to load object {object_id} at version {version}")
+            })?)
+    }
+
+    async fn get_transaction_block(
+        &self,
+        digest: TransactionDigest,
+        options: Option<SuiTransactionBlockResponseOptions>,
+    ) -> RpcResult<SuiTransactionBlockResponse> {
+        Ok(
+            transaction_response(&self.0, digest, options.unwrap_or_default(), None)
+                .await
+                .with_internal_context(|| format!("Failed to load transaction {digest}"))?,
+        )
+    }
+
+    async fn get_events(&self, transaction_digest: TransactionDigest) -> RpcResult<Vec<SuiEvent>> {
+        Ok(self
+            .0
+            .read_blocking(move |sim| events_response(sim, transaction_digest))
+            .await?)
+    }
+}
+
+impl<S: SimulatorStore + Send + Sync + 'static> RpcModule for Read<S> {
+    fn schema(&self) -> Module {
+        ReadApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Load data and generate response for `getChainIdentifier`.
+fn chain_identifier_response<S: SimulatorStore + Send + Sync>(
+    sim: &Simulacrum<StdRng, S>,
+) -> Result<String, RpcError<Error>> {
+    let chain_identifier = sim
+        .get_chain_identifier()
+        .context("Failed to get chain identifier")?;
+
+    Ok(chain_identifier.to_string())
+}
+
+/// Load data and generate response for `getLatestCheckpointSequenceNumber`.
+fn latest_checkpoint_response<S: SimulatorStore>(
+    sim: &Simulacrum<StdRng, S>,
+) -> Result<BigInt<u64>, RpcError<Error>> {
+    let sequence_number = sim
+        .get_latest_checkpoint_sequence_number()
+        .context("Failed to get latest checkpoint")?;
+
+    Ok(sequence_number.into())
+}
+
+/// Load data and generate response for `getCheckpoint`.
+fn checkpoint_response<S: SimulatorStore>(
+    sim: &Simulacrum<StdRng, S>,
+    id: CheckpointId,
+) -> Result<Checkpoint, RpcError<Error>> {
+    let store = sim.store();
+
+    let checkpoint = match id {
+        CheckpointId::SequenceNumber(seq) => store.get_checkpoint_by_sequence_number(seq),
+        CheckpointId::Digest(digest) => store.get_checkpoint_by_digest(&digest),
+    }
+    .ok_or_else(|| invalid_params(Error::CheckpointNotFound(id)))?;
+
+    let contents = store
+        .get_checkpoint_contents(&checkpoint.content_digest)
+        .context("Contents not found for checkpoint")?;
+
+    Ok(Checkpoint::from((
+        checkpoint.data().clone(),
+        contents,
+        checkpoint.auth_sig().signature.clone(),
+    )))
+}
+
+/// Load data and generate response for `getObject` and `multiGetObjects`.
+fn object_response(
+    store: &dyn SimulatorStore,
+    id: ObjectID,
+    options: SuiObjectDataOptions,
+) -> Result<SuiObjectResponse, RpcError<Error>> {
+    let read = match SimulatorStore::get_object(store, &id) {
+        None => ObjectRead::NotExists(id),
+        Some(object) => {
+            let layout = object
+                .get_layout(&ModuleResolver(store))
+                .context("Failed to resolve object layout")?;
+            ObjectRead::Exists(object.compute_object_reference(), object, layout)
+        }
+    };
+
+    Ok((read, options)
+        .try_into()
+        .context("Failed to convert object")?)
+}
+
+/// Load data and generate response for `tryGetPastObject`.
+fn past_object_response<S: SimulatorStore>(
+    sim: &Simulacrum<StdRng, S>,
+    id: ObjectID,
+    version: SequenceNumber,
+    options: SuiObjectDataOptions,
+) -> Result<SuiPastObjectResponse, RpcError<Error>> {
+    let store = sim.store();
+
+    let Some(latest) = SimulatorStore::get_object(store, &id) else {
+        return Ok(SuiPastObjectResponse::ObjectNotExists(id));
+    };
+
+    if version > latest.version() {
+        return Ok(SuiPastObjectResponse::VersionTooHigh {
+            object_id: id,
+            asked_version: version,
+            latest_version: latest.version(),
+        });
+    }
+
+    let Some(object) = store.get_object_at_version(&id, version) else {
+        return Ok(SuiPastObjectResponse::VersionNotFound(id, version));
+    };
+
+    let layout = object
+        .get_layout(&ModuleResolver(store))
+        .context("Failed to resolve object layout")?;
+
+    Ok(SuiPastObjectResponse::VersionFound(
+        (object.compute_object_reference(), object, layout, options)
+            .try_into()
+            .context("Failed to convert object")?,
+    ))
+}
+
+/// Load data and generate response for `getEvents`.
+fn events_response<S: SimulatorStore>(
+    sim: &Simulacrum<StdRng, S>,
+    digest: TransactionDigest,
+) -> Result<Vec<SuiEvent>, RpcError<Error>> {
+    let store = sim.store();
+
+    if store.get_transaction_effects(&digest).is_none() {
+        return Err(invalid_params(Error::TransactionNotFound(digest)));
+    }
+
+    let events = store.get_transaction_events(&digest).unwrap_or_default();
+    let events = SuiTransactionBlockEvents::try_from_using_module_resolver(
+        events,
+        digest,
+        None,
+        &ModuleResolver(store),
+    )
+    .context("Failed to convert events")?;
+
+    Ok(events.data)
+}
@@ -0,0 +1,11 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// A JSONRPC module implementation coupled with a description of its schema.
+pub trait RpcModule: Sized {
+    /// Docs to add to the schema usually generated by the `open_rpc` macro.
+    fn schema(&self) -> sui_open_rpc::Module;
+
+    /// The implementation of the JSONRPC module.
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self>;
+}
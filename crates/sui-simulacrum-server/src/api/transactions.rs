@@ -0,0 +1,217 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+use simulacrum::SimulatorStore;
+use sui_json_rpc::{get_balance_changes_from_effect, get_object_changes, ObjectProvider};
+use sui_json_rpc_types::{
+    BalanceChange, ObjectChange, SuiTransactionBlock, SuiTransactionBlockEvents,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber, SuiAddress},
+    digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI},
+    messages_checkpoint::VerifiedCheckpoint,
+    object::Object,
+    transaction::{InputObjectKind, TransactionDataAPI},
+};
+
+use crate::{
+    error::{invalid_params, RpcError},
+    handle::{ModuleResolver, SimulacrumHandle},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("Transaction {0} not found")]
+    NotFound(TransactionDigest),
+}
+
+/// The objects a transaction read and wrote, used to compute its object and balance changes.
+/// Objects that are not cached are looked up in the Simulacrum's store.
+pub(crate) struct TransactionObjects<S: SimulatorStore> {
+    handle: SimulacrumHandle<S>,
+    objects: BTreeMap<(ObjectID, SequenceNumber), Object>,
+}
+
+impl<S: SimulatorStore> TransactionObjects<S> {
+    pub(crate) fn new(
+        handle: SimulacrumHandle<S>,
+        objects: impl IntoIterator<Item = Object>,
+    ) -> Self {
+        Self {
+            handle,
+            objects: objects
+                .into_iter()
+                .map(|o| ((o.id(), o.version()), o))
+                .collect(),
+        }
+    }
+
+    /// Compute the object and balance changes of a transaction with the given `effects`, sent by
+    /// `sender`, with inputs `input_objs`. `mock_gas` is the ID of the mock gas coin used to pay
+    /// for the transaction, if it was simulated without one.
+    pub(crate) async fn changes(
+        &self,
+        effects: &TransactionEffects,
+        sender: SuiAddress,
+        input_objs: Vec<InputObjectKind>,
+        mock_gas: Option<ObjectID>,
+    ) -> anyhow::Result<(Vec<ObjectChange>, Vec<BalanceChange>)> {
+        let object_changes = get_object_changes(
+            self,
+            effects,
+            sender,
+            effects.modified_at_versions(),
+            effects.all_changed_objects(),
+            effects.all_removed_objects(),
+        )
+        .await
+        .context("Failed to compute object changes")?;
+
+        let balance_changes = get_balance_changes_from_effect(self, effects, input_objs, mock_gas)
+            .await
+            .context("Failed to compute balance changes")?;
+
+        Ok((object_changes, balance_changes))
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: SimulatorStore + Send + Sync + 'static> ObjectProvider for TransactionObjects<S> {
+    type Error = anyhow::Error;
+
+    async fn get_object(
+        &self,
+        id: &ObjectID,
+        version: &SequenceNumber,
+    ) -> Result<Object, Self::Error> {
+        if let Some(o) = self.objects.get(&(*id, *version)) {
+            return Ok(o.clone());
+        }
+
+        let (id, version) = (*id, *version);
+        self.handle
+            .read_blocking(move |sim| {
+                sim.store()
+                    .get_object_at_version(&id, version)
+                    .with_context(|| format!("Object {id} not found at version {version}"))
+            })
+            .await
+    }
+
+    async fn find_object_lt_or_eq_version(
+        &self,
+        id: &ObjectID,
+        version: &SequenceNumber,
+    ) -> Result<Option<Object>, Self::Error> {
+        if let Some((_, o)) = self
+            .objects
+            .range((*id, SequenceNumber::MIN)..=(*id, *version))
+            .next_back()
+        {
+            return Ok(Some(o.clone()));
+        }
+
+        let (id, version) = (*id, *version);
+        self.handle
+            .read_blocking(move |sim| {
+                Ok(sim
+                    .store()
+                    .get_object(&id)
+                    .filter(|o| o.version() <= version))
+            })
+            .await
+    }
+}
+
+/// Build the response for `digest`, an executed transaction, including the parts requested by
+/// `options`. `checkpoint` is the checkpoint the transaction was included in, if it is known.
+pub(crate) async fn transaction_response<S: SimulatorStore + Send + Sync + 'static>(
+    handle: &SimulacrumHandle<S>,
+    digest: TransactionDigest,
+    options: SuiTransactionBlockResponseOptions,
+    checkpoint: Option<&VerifiedCheckpoint>,
+) -> Result<SuiTransactionBlockResponse, RpcError<Error>> {
+    let mut response = SuiTransactionBlockResponse::new(digest);
+    response.checkpoint = checkpoint.map(|c| *c.sequence_number());
+    response.timestamp_ms = checkpoint.map(|c| c.timestamp_ms);
+
+    // Read everything that's needed from the store up-front, so that the lock isn't held while
+    // computing object and balance changes.
+    let (mut response, transaction, effects, inputs) = handle
+        .read_blocking(move |sim| -> Result<_, RpcError<Error>> {
+            let store = sim.store();
+            let resolver = ModuleResolver(store);
+
+            let transaction = store
+                .get_transaction(&digest)
+                .ok_or_else(|| invalid_params(Error::NotFound(digest)))?;
+            let effects = store
+                .get_transaction_effects(&digest)
+                .context("Effects not found for executed transaction")?;
+
+            if options.show_input {
+                response.transaction = Some(
+                    SuiTransactionBlock::try_from(transaction.data().clone(), &resolver)
+                        .context("Failed to convert transaction")?,
+                );
+            }
+
+            if options.show_raw_input {
+                response.raw_transaction =
+                    bcs::to_bytes(transaction.data()).context("Failed to serialize transaction")?;
+            }
+
+            if options.show_events {
+                let events = store.get_transaction_events(&digest).unwrap_or_default();
+                response.events = Some(
+                    SuiTransactionBlockEvents::try_from_using_module_resolver(
+                        events,
+                        digest,
+                        response.timestamp_ms,
+                        &resolver,
+                    )
+                    .context("Failed to convert events")?,
+                );
+            }
+
+            let inputs: Vec<_> = effects
+                .modified_at_versions()
+                .into_iter()
+                .filter_map(|(id, version)| store.get_object_at_version(&id, version))
+                .collect();
+
+            Ok((response, transaction, effects, inputs))
+        })
+        .await?;
+
+    if options.show_object_changes || options.show_balance_changes {
+        let data = transaction.data().transaction_data();
+        let objects = TransactionObjects::new(handle.clone(), inputs);
+        let (object_changes, balance_changes) = objects
+            .changes(
+                &effects,
+                data.sender(),
+                data.input_objects().unwrap_or_default(),
+                None,
+            )
+            .await?;
+
+        response.object_changes = options.show_object_changes.then_some(object_changes);
+        response.balance_changes = options.show_balance_changes.then_some(balance_changes);
+    }
+
+    if options.show_raw_effects {
+        response.raw_effects = bcs::to_bytes(&effects).context("Failed to serialize effects")?;
+    }
+
+    if options.show_effects {
+        response.effects = Some(effects.try_into().context("Failed to convert effects")?);
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,225 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use fastcrypto::encoding::Base64;
+use fastcrypto::traits::ToFromBytes;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use simulacrum::SimulatorStore;
+use sui_json_rpc_types::{
+    DryRunTransactionBlockResponse, SuiTransactionBlockData, SuiTransactionBlockEvents,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{
+    digests::TransactionDigest,
+    error::SuiError,
+    messages_checkpoint::VerifiedCheckpoint,
+    quorum_driver_types::ExecuteTransactionRequestType,
+    signature::GenericSignature,
+    transaction::{Transaction, TransactionData, TransactionDataAPI},
+};
+
+use crate::{
+    error::{invalid_params, InternalContext, RpcError},
+    handle::{ModuleResolver, SimulacrumHandle},
+};
+
+use super::{
+    rpc_module::RpcModule,
+    transactions::{transaction_response, TransactionObjects},
+};
+
+#[open_rpc(namespace = "sui", tag = "Write API")]
+#[rpc(server, namespace = "sui")]
+trait WriteApi {
+    /// Execute the transaction with options to show different information in the response.
+    /// Transactions are executed and committed immediately, regardless of the request type.
+    #[method(name = "executeTransactionBlock")]
+    async fn execute_transaction_block(
+        &self,
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        tx_bytes: Base64,
+        /// A list of signatures (`flag || signature || pubkey` bytes, as base-64 encoded string). Signature is committed to the intent message of the transaction data, as base-64 encoded string.
+        signatures: Vec<Base64>,
+        /// options for specifying the content to be returned
+        options: Option<SuiTransactionBlockResponseOptions>,
+        /// The request type, which is ignored: all transactions are executed locally.
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> RpcResult<SuiTransactionBlockResponse>;
+
+    /// Return transaction execution effects including the gas cost summary,
+    /// while the effects are not committed to the chain.
+    #[method(name = "dryRunTransactionBlock")]
+    async fn dry_run_transaction_block(
+        &self,
+        tx_bytes: Base64,
+    ) -> RpcResult<DryRunTransactionBlockResponse>;
+}
+
+pub(crate) struct Write<S: SimulatorStore>(pub SimulacrumHandle<S>);
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Invalid transaction bytes: {0}")]
+    BadTransaction(String),
+
+    #[error("Invalid signature: {0}")]
+    BadSignature(String),
+
+    #[error("Transaction rejected: {0}")]
+    Rejected(SuiError),
+}
+
+#[async_trait::async_trait]
+impl<S: SimulatorStore + Send + Sync + 'static> WriteApiServer for Write<S> {
+    async fn execute_transaction_block(
+        &self,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+        options: Option<SuiTransactionBlockResponseOptions>,
+        _request_type: Option<ExecuteTransactionRequestType>,
+    ) -> RpcResult<SuiTransactionBlockResponse> {
+        let (digest, checkpoint) = execute(&self.0, tx_bytes, signatures).await?;
+
+        let mut response = transaction_response(
+            &self.0,
+            digest,
+            options.unwrap_or_default(),
+            checkpoint.as_ref(),
+        )
+        .await
+        .with_internal_context(|| format!("Failed to load executed transaction {digest}"))?;
+
+        response.confirmed_local_execution = Some(true);
+        Ok(response)
+    }
+
+    async fn dry_run_transaction_block(
+        &self,
+        tx_bytes: Base64,
+    ) -> RpcResult<DryRunTransactionBlockResponse> {
+        Ok(dry_run_response(&self.0, tx_bytes).await?)
+    }
+}
+
+impl<S: SimulatorStore + Send + Sync + 'static> RpcModule for Write<S> {
+    fn schema(&self) -> Module {
+        WriteApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Execute the transaction for `executeTransactionBlock`, returning its digest and the checkpoint
+/// it was included in, if it was checkpointed straight away.
+async fn execute<S: SimulatorStore + Send + Sync + 'static>(
+    handle: &SimulacrumHandle<S>,
+    tx_bytes: Base64,
+    signatures: Vec<Base64>,
+) -> Result<(TransactionDigest, Option<VerifiedCheckpoint>), RpcError<Error>> {
+    let tx_data = decode_transaction(tx_bytes)?;
+
+    let signatures = signatures
+        .into_iter()
+        .map(|sig| {
+            let bytes = sig
+                .to_vec()
+                .map_err(|e| invalid_params(Error::BadSignature(e.to_string())))?;
+            GenericSignature::from_bytes(&bytes)
+                .map_err(|e| invalid_params(Error::BadSignature(e.to_string())))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let transaction = Transaction::from_generic_sig_data(tx_data, signatures);
+    let digest = *transaction.digest();
+
+    let (_, checkpoint) = handle
+        .execute_transaction(transaction)
+        .await
+        .map_err(rejected)?;
+
+    Ok((digest, checkpoint))
+}
+
+/// Simulate the transaction and generate the response for `dryRunTransactionBlock`.
+async fn dry_run_response<S: SimulatorStore + Send + Sync + 'static>(
+    handle: &SimulacrumHandle<S>,
+    tx_bytes: Base64,
+) -> Result<DryRunTransactionBlockResponse, RpcError<Error>> {
+    let tx_data = decode_transaction(tx_bytes)?;
+    let sender = tx_data.sender();
+    let digest = tx_data.digest();
+    let input_objs = tx_data
+        .input_objects()
+        .map_err(|e| invalid_params(Error::Rejected(e.into())))?;
+
+    // Simulate and convert the parts of the response that need the store up-front, so that the
+    // lock isn't held while computing object and balance changes.
+    let (result, input, events) = handle
+        .read_blocking(move |sim| -> Result<_, RpcError<Error>> {
+            let resolver = ModuleResolver(sim.store());
+
+            let input =
+                SuiTransactionBlockData::try_from_with_module_cache(tx_data.clone(), &resolver)
+                    .context("Failed to convert transaction")?;
+
+            let result = sim.simulate_transaction(tx_data).map_err(rejected)?;
+
+            let events = SuiTransactionBlockEvents::try_from_using_module_resolver(
+                result.events.clone().unwrap_or_default(),
+                digest,
+                None,
+                &resolver,
+            )
+            .context("Failed to convert events")?;
+
+            Ok((result, input, events))
+        })
+        .await?;
+
+    let objects = TransactionObjects::new(
+        handle.clone(),
+        result
+            .input_objects
+            .into_values()
+            .chain(result.output_objects.into_values()),
+    );
+
+    let (object_changes, balance_changes) = objects
+        .changes(&result.effects, sender, input_objs, result.mock_gas_id)
+        .await?;
+
+    Ok(DryRunTransactionBlockResponse {
+        effects: result
+            .effects
+            .try_into()
+            .context("Failed to convert effects")?,
+        events,
+        object_changes,
+        balance_changes,
+        input,
+        execution_error_source: None,
+        suggested_gas_price: None,
+    })
+}
+
+fn decode_transaction(tx_bytes: Base64) -> Result<TransactionData, RpcError<Error>> {
+    let bytes = tx_bytes
+        .to_vec()
+        .map_err(|e| invalid_params(Error::BadTransaction(e.to_string())))?;
+
+    bcs::from_bytes(&bytes).map_err(|e| invalid_params(Error::BadTransaction(e.to_string())))
+}
+
+/// Transactions that fail with a `SuiError` were rejected by the checks that precede execution,
+/// which is the user's fault. Any other error is an internal error.
+fn rejected(error: anyhow::Error) -> RpcError<Error> {
+    match error.downcast::<SuiError>() {
+        Ok(error) => invalid_params(Error::Rejected(error)),
+        Err(error) => RpcError::InternalError(error),
+    }
+}
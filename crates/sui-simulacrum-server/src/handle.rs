@@ -0,0 +1,351 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
+use move_core_types::language_storage::ModuleId;
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use rand::rngs::StdRng;
+use simulacrum::{InMemoryStore, Simulacrum, SimulatorStore};
+use sui_types::{
+    base_types::{ObjectID, VersionNumber},
+    committee::{Committee, EpochId},
+    digests::{ChainIdentifier, CheckpointContentsDigest, CheckpointDigest, TransactionDigest},
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    error::SuiError,
+    messages_checkpoint::{
+        CheckpointContents, CheckpointSequenceNumber, FullCheckpointContents, VerifiedCheckpoint,
+    },
+    object::Object,
+    quorum_driver_types::{
+        EffectsFinalityInfo, ExecuteTransactionRequestV3, ExecuteTransactionResponseV3,
+        FinalizedEffects, QuorumDriverError,
+    },
+    storage::{error::Result as StorageResult, ObjectStore, ReadStore, RpcIndexes, RpcStateReader},
+    transaction::{Transaction, TransactionData, VerifiedTransaction},
+    transaction_executor::{SimulateTransactionResult, TransactionExecutor},
+};
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// A Simulacrum shared between all the services fronting it.
+///
+/// Reads take a shared lock on the Simulacrum, while transactions and control operations take an
+/// exclusive one, so every request observes the chain either before or after another request's
+/// changes, never in between. Access is only granted on a blocking thread (see
+/// [`SimulacrumHandle::read_blocking`] and [`SimulacrumHandle::write_blocking`]), so that waiting
+/// for the lock, and executing or reading while holding it, does not stall the async runtime.
+/// Reads can block on more than the lock: a forked store fetches objects from the network it
+/// forked the first time they are read.
+pub struct SimulacrumHandle<S: SimulatorStore = InMemoryStore> {
+    simulacrum: Arc<RwLock<Simulacrum<StdRng, S>>>,
+
+    /// Whether every transaction (including the ones issued by control methods) is immediately
+    /// followed by a checkpoint, rather than waiting for checkpoints to be explicitly requested.
+    checkpoint_every_transaction: bool,
+}
+
+/// Resolves Move modules from the packages in a Simulacrum's store.
+pub(crate) struct ModuleResolver<'s>(pub &'s dyn SimulatorStore);
+
+impl<S: SimulatorStore> SimulacrumHandle<S> {
+    pub fn new(simulacrum: Simulacrum<StdRng, S>, checkpoint_every_transaction: bool) -> Self {
+        Self {
+            simulacrum: Arc::new(RwLock::new(simulacrum)),
+            checkpoint_every_transaction,
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Simulacrum<StdRng, S>> {
+        self.simulacrum.read()
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Simulacrum<StdRng, S>> {
+        self.simulacrum.write()
+    }
+
+    /// Create a checkpoint of the transactions executed since the last one, if the handle is
+    /// configured to checkpoint every transaction.
+    pub(crate) fn checkpoint(&self, sim: &mut Simulacrum<StdRng, S>) -> Option<VerifiedCheckpoint> {
+        self.checkpoint_every_transaction
+            .then(|| sim.create_checkpoint())
+    }
+
+    /// Run `f` with shared access to the Simulacrum, from synchronous code that can't move to a
+    /// blocking thread (the implementations of the storage traits). On a multi-threaded runtime,
+    /// the runtime is told to move its other tasks off this thread first.
+    fn read_in_place<T>(&self, f: impl FnOnce(&Simulacrum<StdRng, S>) -> T) -> T {
+        let multi_threaded = Handle::try_current()
+            .is_ok_and(|runtime| runtime.runtime_flavor() == RuntimeFlavor::MultiThread);
+
+        if multi_threaded {
+            tokio::task::block_in_place(|| f(&self.read()))
+        } else {
+            f(&self.read())
+        }
+    }
+}
+
+impl<S: SimulatorStore + Send + Sync + 'static> SimulacrumHandle<S> {
+    /// Run `f` with shared access to the Simulacrum, on a blocking thread.
+    pub(crate) async fn read_blocking<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        T: Send + 'static,
+        E: From<anyhow::Error> + Send + 'static,
+        F: FnOnce(&Simulacrum<StdRng, S>) -> Result<T, E> + Send + 'static,
+    {
+        let handle = self.clone();
+        tokio::task::spawn_blocking(move || f(&handle.read()))
+            .await
+            .context("Failed to access Simulacrum")?
+    }
+
+    /// Run `f` with exclusive access to the Simulacrum, on a blocking thread. `f` is also passed
+    /// this handle, to access its configuration.
+    pub(crate) async fn write_blocking<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        T: Send + 'static,
+        E: From<anyhow::Error> + Send + 'static,
+        F: FnOnce(&Self, &mut Simulacrum<StdRng, S>) -> Result<T, E> + Send + 'static,
+    {
+        let handle = self.clone();
+        tokio::task::spawn_blocking(move || f(&handle, &mut handle.write()))
+            .await
+            .context("Failed to access Simulacrum")?
+    }
+
+    /// Execute `transaction`, checkpointing it straight away if the handle is configured to
+    /// checkpoint every transaction. Returns the transaction's effects, and the checkpoint it was
+    /// included in, if any.
+    pub async fn execute_transaction(
+        &self,
+        transaction: Transaction,
+    ) -> anyhow::Result<(TransactionEffects, Option<VerifiedCheckpoint>)> {
+        self.write_blocking(move |handle, sim| {
+            let (effects, _) = sim.execute_transaction(transaction)?;
+            Ok((effects, handle.checkpoint(sim)))
+        })
+        .await
+    }
+}
+
+impl<S: SimulatorStore> Clone for SimulacrumHandle<S> {
+    fn clone(&self) -> Self {
+        Self {
+            simulacrum: self.simulacrum.clone(),
+            checkpoint_every_transaction: self.checkpoint_every_transaction,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: SimulatorStore + Send + Sync + 'static> TransactionExecutor for SimulacrumHandle<S> {
+    async fn execute_transaction(
+        &self,
+        request: ExecuteTransactionRequestV3,
+        _client_addr: Option<std::net::SocketAddr>,
+    ) -> Result<ExecuteTransactionResponseV3, QuorumDriverError> {
+        let (effects, checkpoint) =
+            SimulacrumHandle::execute_transaction(self, request.transaction)
+                .await
+                .map_err(quorum_driver_error)?;
+
+        let (effects, events, input_objects, output_objects) = self
+            .read_blocking(move |sim| -> anyhow::Result<_> {
+                let store = sim.store();
+
+                let events = request.include_events.then(|| {
+                    store
+                        .get_transaction_events(effects.transaction_digest())
+                        .unwrap_or_default()
+                });
+
+                let input_objects = request.include_input_objects.then(|| {
+                    effects
+                        .modified_at_versions()
+                        .into_iter()
+                        .filter_map(|(id, version)| store.get_object_at_version(&id, version))
+                        .collect::<Vec<_>>()
+                });
+
+                let output_objects = request.include_output_objects.then(|| {
+                    effects
+                        .all_changed_objects()
+                        .into_iter()
+                        .filter_map(|((id, version, _), _, _)| {
+                            store.get_object_at_version(&id, version)
+                        })
+                        .collect::<Vec<_>>()
+                });
+
+                Ok((effects, events, input_objects, output_objects))
+            })
+            .await
+            .map_err(quorum_driver_error)?;
+
+        let finality_info = match checkpoint {
+            Some(checkpoint) => {
+                EffectsFinalityInfo::Checkpointed(checkpoint.epoch(), *checkpoint.sequence_number())
+            }
+            None => EffectsFinalityInfo::QuorumExecuted(effects.executed_epoch()),
+        };
+
+        Ok(ExecuteTransactionResponseV3 {
+            effects: FinalizedEffects {
+                effects,
+                finality_info,
+            },
+            events,
+            input_objects,
+            output_objects,
+            auxiliary_data: None,
+        })
+    }
+
+    fn simulate_transaction(
+        &self,
+        transaction: TransactionData,
+    ) -> Result<SimulateTransactionResult, SuiError> {
+        self.read_in_place(|sim| sim.simulate_transaction(transaction))
+            .map_err(|e| match e.downcast::<SuiError>() {
+                Ok(e) => e,
+                Err(e) => SuiError::Unknown(e.to_string()),
+            })
+    }
+}
+
+impl<S: SimulatorStore> ObjectStore for SimulacrumHandle<S> {
+    fn get_object(&self, object_id: &ObjectID) -> Option<Object> {
+        self.read_in_place(|sim| ObjectStore::get_object(sim, object_id))
+    }
+
+    fn get_object_by_key(&self, object_id: &ObjectID, version: VersionNumber) -> Option<Object> {
+        self.read_in_place(|sim| sim.get_object_by_key(object_id, version))
+    }
+}
+
+impl<S: SimulatorStore> ReadStore for SimulacrumHandle<S> {
+    fn get_committee(&self, epoch: EpochId) -> Option<Arc<Committee>> {
+        self.read_in_place(|sim| sim.get_committee(epoch))
+    }
+
+    fn get_latest_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+        self.read_in_place(|sim| sim.get_latest_checkpoint())
+    }
+
+    fn get_highest_verified_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+        self.read_in_place(|sim| sim.get_highest_verified_checkpoint())
+    }
+
+    fn get_highest_synced_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+        self.read_in_place(|sim| sim.get_highest_synced_checkpoint())
+    }
+
+    fn get_lowest_available_checkpoint(&self) -> StorageResult<CheckpointSequenceNumber> {
+        self.read_in_place(|sim| sim.get_lowest_available_checkpoint())
+    }
+
+    fn get_checkpoint_by_digest(&self, digest: &CheckpointDigest) -> Option<VerifiedCheckpoint> {
+        self.read_in_place(|sim| ReadStore::get_checkpoint_by_digest(sim, digest))
+    }
+
+    fn get_checkpoint_by_sequence_number(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Option<VerifiedCheckpoint> {
+        self.read_in_place(|sim| ReadStore::get_checkpoint_by_sequence_number(sim, sequence_number))
+    }
+
+    fn get_checkpoint_contents_by_digest(
+        &self,
+        digest: &CheckpointContentsDigest,
+    ) -> Option<CheckpointContents> {
+        self.read_in_place(|sim| sim.get_checkpoint_contents_by_digest(digest))
+    }
+
+    fn get_checkpoint_contents_by_sequence_number(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Option<CheckpointContents> {
+        self.read_in_place(|sim| sim.get_checkpoint_contents_by_sequence_number(sequence_number))
+    }
+
+    fn get_transaction(&self, tx_digest: &TransactionDigest) -> Option<Arc<VerifiedTransaction>> {
+        self.read_in_place(|sim| ReadStore::get_transaction(sim, tx_digest))
+    }
+
+    fn get_transaction_effects(&self, tx_digest: &TransactionDigest) -> Option<TransactionEffects> {
+        self.read_in_place(|sim| ReadStore::get_transaction_effects(sim, tx_digest))
+    }
+
+    fn get_events(&self, event_digest: &TransactionDigest) -> Option<TransactionEvents> {
+        self.read_in_place(|sim| sim.get_events(event_digest))
+    }
+
+    fn get_full_checkpoint_contents_by_sequence_number(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Option<FullCheckpointContents> {
+        self.read_in_place(|sim| {
+            sim.get_full_checkpoint_contents_by_sequence_number(sequence_number)
+        })
+    }
+
+    fn get_full_checkpoint_contents(
+        &self,
+        digest: &CheckpointContentsDigest,
+    ) -> Option<FullCheckpointContents> {
+        self.read_in_place(|sim| sim.get_full_checkpoint_contents(digest))
+    }
+}
+
+impl<S: SimulatorStore + Send + Sync> RpcStateReader for SimulacrumHandle<S> {
+    fn get_lowest_available_checkpoint_objects(&self) -> StorageResult<CheckpointSequenceNumber> {
+        self.read_in_place(|sim| sim.get_lowest_available_checkpoint_objects())
+    }
+
+    fn get_chain_identifier(&self) -> StorageResult<ChainIdentifier> {
+        self.read_in_place(|sim| sim.get_chain_identifier())
+    }
+
+    fn indexes(&self) -> Option<&dyn RpcIndexes> {
+        None
+    }
+}
+
+impl GetModule for ModuleResolver<'_> {
+    type Error = SuiError;
+    type Item = CompiledModule;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> Result<Option<Self::Item>, Self::Error> {
+        sui_types::storage::get_module(self.0, id)?
+            .map(|bytes| {
+                CompiledModule::deserialize_with_defaults(&bytes).map_err(|e| {
+                    SuiError::ModuleDeserializationFailure {
+                        error: e.to_string(),
+                    }
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Classify a failure to execute a transaction the way the quorum driver would: errors that can't
+/// be fixed by retrying are the user's fault, the rest are internal.
+fn quorum_driver_error(error: anyhow::Error) -> QuorumDriverError {
+    match error.downcast::<SuiError>() {
+        Ok(error)
+            if matches!(error, SuiError::UserInputError { .. }) || !error.is_retryable().0 =>
+        {
+            QuorumDriverError::NonRecoverableTransactionError {
+                errors: vec![(error, 0, vec![])],
+            }
+        }
+        Ok(error) => QuorumDriverError::QuorumDriverInternalError(error),
+        Err(error) => {
+            QuorumDriverError::QuorumDriverInternalError(SuiError::Unknown(error.to_string()))
+        }
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Serves a [`Simulacrum`](simulacrum::Simulacrum) over JSON-RPC and gRPC, so that clients in any
+//! language can drive an instant, deterministic, local chain.
+//!
+//! Alongside the read and write methods of a fullnode, the JSON-RPC service exposes methods under
+//! the `simulacrum_` namespace to control the chain: advancing the clock or the epoch, creating
+//! checkpoints, and funding addresses.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use api::coin::Coins;
+use api::control::Control;
+use api::governance::Governance;
+use api::read::Read;
+use api::rpc_module::RpcModule;
+use api::write::Write;
+use jsonrpsee::server::ServerBuilder;
+use serde_json::json;
+use simulacrum::SimulatorStore;
+use sui_open_rpc::Project;
+use sui_rpc_api::ServerVersion;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+pub use handle::SimulacrumHandle;
+
+mod api;
+mod error;
+mod handle;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ServerArgs {
+    /// Address to listen to for incoming JSON-RPC connections.
+    #[clap(long, default_value_t = Self::default().rpc_listen_address)]
+    pub rpc_listen_address: SocketAddr,
+
+    /// Address to listen to for incoming gRPC connections.
+    #[clap(long, default_value_t = Self::default().grpc_listen_address)]
+    pub grpc_listen_address: SocketAddr,
+}
+
+struct RpcService {
+    /// The address that the server will start listening for requests on, when it is run.
+    rpc_listen_address: SocketAddr,
+
+    /// All the methods added to the server so far.
+    modules: jsonrpsee::RpcModule<()>,
+
+    /// Description of the schema served by this service.
+    schema: Project,
+
+    /// Cancellation token controlling all services.
+    cancel: CancellationToken,
+}
+
+impl RpcService {
+    /// Create a new instance of the JSON-RPC service, listening on `rpc_listen_address`. The
+    /// service will not accept connections until [Self::run] is called.
+    fn new(rpc_listen_address: SocketAddr, cancel: CancellationToken) -> Self {
+        let schema = Project::new(
+            env!("CARGO_PKG_VERSION"),
+            "Sui Simulacrum JSON-RPC",
+            "A JSON-RPC API for interacting with a simulated Sui blockchain.",
+            "Mysten Labs",
+            "https://mystenlabs.com",
+            "build@mystenlabs.com",
+            "Apache-2.0",
+            "https://raw.githubusercontent.com/MystenLabs/sui/main/LICENSE",
+        );
+
+        Self {
+            rpc_listen_address,
+            modules: jsonrpsee::RpcModule::new(()),
+            schema,
+            cancel,
+        }
+    }
+
+    /// Add an `RpcModule` to the service. The module's methods are combined with the existing
+    /// methods registered on the service, and the operation will fail if there is any overlap.
+    fn add_module(&mut self, module: impl RpcModule) -> anyhow::Result<()> {
+        self.schema.add_module(module.schema());
+        self.modules
+            .merge(module.into_impl().remove_context())
+            .context("Failed to add module because of a name conflict")
+    }
+
+    /// Start the service (it will accept connections) and return a handle that will resolve when
+    /// the service stops.
+    async fn run(self) -> anyhow::Result<JoinHandle<()>> {
+        let Self {
+            rpc_listen_address,
+            mut modules,
+            schema,
+            cancel,
+        } = self;
+
+        info!("Starting JSON-RPC service on {rpc_listen_address}");
+
+        // Add a method to serve the schema to clients.
+        modules
+            .register_method("rpc.discover", move |_, _, _| json!(schema.clone()))
+            .context("Failed to add schema discovery method")?;
+
+        let handle = ServerBuilder::new()
+            .http_only()
+            .max_response_body_size(u32::MAX)
+            .set_http_middleware(
+                tower::builder::ServiceBuilder::new().layer(
+                    tower_http::cors::CorsLayer::new()
+                        .allow_methods([http::Method::GET, http::Method::POST])
+                        .allow_origin(tower_http::cors::Any)
+                        .allow_headers(tower_http::cors::Any),
+                ),
+            )
+            .build(rpc_listen_address)
+            .await
+            .context("Failed to bind JSON-RPC service")?
+            .start(modules);
+
+        // Set-up a helper task that will tear down the RPC service when the cancellation token is
+        // triggered.
+        let cancel_handle = handle.clone();
+        let cancel_cancel = cancel.clone();
+        let h_cancel = tokio::spawn(async move {
+            cancel_cancel.cancelled().await;
+            cancel_handle.stop()
+        });
+
+        Ok(tokio::spawn(async move {
+            handle.stopped().await;
+            cancel.cancel();
+            let _ = h_cancel.await;
+        }))
+    }
+}
+
+impl Default for ServerArgs {
+    fn default() -> Self {
+        Self {
+            rpc_listen_address: "127.0.0.1:9000".parse().unwrap(),
+            grpc_listen_address: "127.0.0.1:9001".parse().unwrap(),
+        }
+    }
+}
+
+/// Set-up and run the JSON-RPC and gRPC services in front of the Simulacrum behind `handle`. The
+/// services will continue to run until the cancellation token is triggered, and will signal
+/// cancellation on the token when they are shutting down.
+pub async fn start_server<S: SimulatorStore + Send + Sync + 'static>(
+    handle: SimulacrumHandle<S>,
+    server_args: ServerArgs,
+    cancel: CancellationToken,
+) -> anyhow::Result<JoinHandle<()>> {
+    let mut rpc = RpcService::new(server_args.rpc_listen_address, cancel.child_token());
+
+    rpc.add_module(Coins(handle.clone()))?;
+    rpc.add_module(Control(handle.clone()))?;
+    rpc.add_module(Governance(handle.clone()))?;
+    rpc.add_module(Read(handle.clone()))?;
+    rpc.add_module(Write(handle.clone()))?;
+
+    let h_rpc = rpc.run().await?;
+
+    let grpc_listen_address = server_args.grpc_listen_address;
+    let mut grpc = sui_rpc_api::RpcService::new(Arc::new(handle.clone()));
+    grpc.with_server_version(ServerVersion::new(
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+    ));
+    grpc.with_executor(Arc::new(handle));
+
+    info!("Starting gRPC service on {grpc_listen_address}");
+    let listener = tokio::net::TcpListener::bind(grpc_listen_address)
+        .await
+        .context("Failed to bind gRPC service")?;
+    let router = grpc.into_router().await;
+
+    let grpc_cancel = cancel.child_token();
+    let h_grpc = tokio::spawn(async move {
+        axum::serve(listener, router)
+            .with_graceful_shutdown(async move { grpc_cancel.cancelled().await })
+            .await
+    });
+
+    Ok(tokio::spawn(async move {
+        let _ = h_rpc.await;
+        cancel.cancel();
+        let _ = h_grpc.await;
+    }))
+}
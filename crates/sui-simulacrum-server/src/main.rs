@@ -0,0 +1,160 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use rand::{rngs::StdRng, SeedableRng};
+use simulacrum::{Simulacrum, SimulatorStore};
+use sui_replay::archive_fetcher::{ArchiveConfig, ArchiveFetcher};
+use sui_replay::data_fetcher::{Fetchers, RemoteFetcher};
+use sui_sdk::SuiClientBuilder;
+use sui_simulacrum_server::{start_server, ServerArgs, SimulacrumHandle};
+use tokio::signal;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+use url::Url;
+
+#[derive(Parser, Debug)]
+#[command(name = "sui-simulacrum-server")]
+struct Args {
+    /// Seed for the simulation's source of randomness. Starting two servers with the same seed
+    /// produces the same genesis, accounts, and keys. Defaults to a random seed.
+    #[clap(long)]
+    seed: Option<u64>,
+
+    /// Fork the network served by this fullnode RPC, at its latest checkpoint, instead of
+    /// starting from a fresh genesis. Objects are fetched from the network lazily, the first time
+    /// they are read.
+    #[clap(long, conflicts_with = "fork_archive_path")]
+    fork_rpc_url: Option<Url>,
+
+    /// Fork the network whose checkpoint archive is in this directory, instead of starting from a
+    /// fresh genesis. The state of the network is reconstructed from the archive and the formal
+    /// snapshots in `--fork-snapshot-path`, up to `--fork-checkpoint`.
+    #[clap(long, requires = "fork_snapshot_path")]
+    fork_archive_path: Option<PathBuf>,
+
+    /// Directory containing the formal snapshots to reconstruct the network state from, as
+    /// `epoch_<N>` sub-directories.
+    #[clap(long, requires = "fork_archive_path")]
+    fork_snapshot_path: Option<PathBuf>,
+
+    /// The checkpoint to fork an archive at. Defaults to the last checkpoint in the archive.
+    #[clap(long, requires = "fork_archive_path")]
+    fork_checkpoint: Option<u64>,
+
+    /// Write every checkpoint that is created to this directory, for indexers to ingest.
+    #[clap(long)]
+    data_ingestion_path: Option<PathBuf>,
+
+    /// Create a checkpoint after every transaction, instead of only when one is requested through
+    /// `simulacrum_createCheckpoint`.
+    #[clap(long)]
+    checkpoint_every_transaction: bool,
+
+    #[command(flatten)]
+    server_args: ServerArgs,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    // Enable tracing, configured by environment variables.
+    let _guard = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .init();
+
+    let rng = match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let cancel = CancellationToken::new();
+
+    let fork_source = if let Some(fork_rpc_url) = &args.fork_rpc_url {
+        let client = SuiClientBuilder::default()
+            .build(fork_rpc_url.as_str())
+            .await
+            .context("Failed to connect to the network to fork")?;
+        Some((
+            Fetchers::Remote(RemoteFetcher::new(client)),
+            fork_rpc_url.to_string(),
+        ))
+    } else if let (Some(archive_path), Some(snapshot_path)) =
+        (&args.fork_archive_path, &args.fork_snapshot_path)
+    {
+        let config = ArchiveConfig {
+            archive_path: archive_path.clone(),
+            snapshot_path: snapshot_path.clone(),
+            snapshot_epoch: None,
+            end_checkpoint: args.fork_checkpoint,
+        };
+        let fetcher = ArchiveFetcher::new(&config, None)
+            .await
+            .context("Failed to reconstruct the network to fork from its archive")?;
+        Some((
+            Fetchers::Archive(fetcher),
+            archive_path.display().to_string(),
+        ))
+    } else {
+        None
+    };
+
+    let h_server = if let Some((source, source_name)) = fork_source {
+        // Forking reads from the network synchronously, so it must not block the runtime.
+        let sim = tokio::task::spawn_blocking(move || {
+            Simulacrum::new_fork(source, args.fork_checkpoint, rng)
+        })
+        .await?
+        .context("Failed to fork the network")?;
+
+        info!(
+            "Forked {source_name} at checkpoint {}",
+            sim.fork_checkpoint().unwrap_or_default()
+        );
+
+        serve(sim, &args, cancel.child_token()).await?
+    } else {
+        let sim = Simulacrum::new_with_rng(rng);
+        serve(sim, &args, cancel.child_token()).await?
+    };
+
+    let h_ctrl_c = tokio::spawn({
+        let cancel = cancel.clone();
+        async move {
+            tokio::select! {
+                _ = cancel.cancelled() => {}
+                _ = signal::ctrl_c() => {
+                    info!("Received Ctrl-C, shutting down...");
+                    cancel.cancel();
+                }
+            }
+        }
+    });
+
+    let _ = h_server.await;
+    cancel.cancel();
+    let _ = h_ctrl_c.await;
+
+    Ok(())
+}
+
+async fn serve<S: SimulatorStore + Send + Sync + 'static>(
+    mut sim: Simulacrum<StdRng, S>,
+    args: &Args,
+    cancel: CancellationToken,
+) -> anyhow::Result<tokio::task::JoinHandle<()>> {
+    if let Some(path) = &args.data_ingestion_path {
+        sim.set_data_ingestion_path(path.clone());
+    }
+
+    for (address, _) in sim.keystore().accounts() {
+        info!("Funded account: {address}");
+    }
+
+    let handle = SimulacrumHandle::new(sim, args.checkpoint_every_transaction);
+    start_server(handle, args.server_args.clone(), cancel).await
+}
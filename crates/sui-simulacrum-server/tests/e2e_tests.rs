@@ -0,0 +1,211 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::traits::KeyPair;
+use jsonrpsee::{
+    core::client::ClientT,
+    http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use simulacrum::Simulacrum;
+use sui_config::local_ip_utils::new_local_tcp_socket_for_testing;
+use sui_json_rpc_types::{
+    Checkpoint, CoinPage, SuiExecutionStatus, SuiTransactionBlockEffects,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_simulacrum_server::{start_server, ServerArgs, SimulacrumHandle};
+use sui_types::{
+    base_types::SuiAddress,
+    crypto::AccountKeyPair,
+    sui_serde::BigInt,
+    transaction::{Transaction, TransactionData},
+};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// A server running in front of a fresh Simulacrum, with a JSON-RPC client connected to it.
+struct TestServer {
+    client: HttpClient,
+    /// An account that was funded at genesis.
+    sender: SuiAddress,
+    key: AccountKeyPair,
+    cancel: CancellationToken,
+    handle: JoinHandle<()>,
+}
+
+impl TestServer {
+    async fn start() -> Self {
+        let sim = Simulacrum::new_with_rng(StdRng::seed_from_u64(0));
+        let (sender, key) = sim
+            .keystore()
+            .accounts()
+            .next()
+            .map(|(address, key)| (*address, key.copy()))
+            .unwrap();
+
+        let server_args = ServerArgs {
+            rpc_listen_address: new_local_tcp_socket_for_testing(),
+            grpc_listen_address: new_local_tcp_socket_for_testing(),
+        };
+
+        let client = HttpClientBuilder::default()
+            .build(format!("http://{}", server_args.rpc_listen_address))
+            .unwrap();
+
+        let cancel = CancellationToken::new();
+        let handle = start_server(
+            SimulacrumHandle::new(sim, false),
+            server_args,
+            cancel.child_token(),
+        )
+        .await
+        .unwrap();
+
+        Self {
+            client,
+            sender,
+            key,
+            cancel,
+            handle,
+        }
+    }
+
+    async fn stop(self) {
+        self.cancel.cancel();
+        self.handle.await.unwrap();
+    }
+
+    async fn balance(&self, owner: SuiAddress) -> u64 {
+        let coins: CoinPage = self
+            .client
+            .request("suix_getCoins", rpc_params![owner])
+            .await
+            .unwrap();
+
+        coins.data.iter().map(|coin| coin.balance).sum()
+    }
+}
+
+#[tokio::test]
+async fn test_read_and_execute() {
+    let server = TestServer::start().await;
+    let client = &server.client;
+
+    let chain_id: String = client
+        .request("sui_getChainIdentifier", rpc_params![])
+        .await
+        .unwrap();
+    assert!(!chain_id.is_empty());
+
+    let coins: CoinPage = client
+        .request("suix_getCoins", rpc_params![server.sender])
+        .await
+        .unwrap();
+    let gas_price: BigInt<u64> = client
+        .request("suix_getReferenceGasPrice", rpc_params![])
+        .await
+        .unwrap();
+
+    let recipient = SuiAddress::random_for_testing_only();
+    let tx_data = TransactionData::new_transfer_sui(
+        recipient,
+        server.sender,
+        Some(1000),
+        coins.data[0].object_ref(),
+        1_000_000_000,
+        *gas_price,
+    );
+
+    let tx = Transaction::from_data_and_signer(tx_data, vec![&server.key]);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+
+    let executed: SuiTransactionBlockResponse = client
+        .request(
+            "sui_executeTransactionBlock",
+            rpc_params![
+                tx_bytes,
+                signatures,
+                SuiTransactionBlockResponseOptions::new().with_effects()
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(&executed.digest, tx.digest());
+    assert_eq!(executed.confirmed_local_execution, Some(true));
+    assert_eq!(
+        executed.effects.unwrap().status(),
+        &SuiExecutionStatus::Success
+    );
+
+    // The transaction and its changes are immediately visible to reads.
+    let read: SuiTransactionBlockResponse = client
+        .request("sui_getTransactionBlock", rpc_params![executed.digest])
+        .await
+        .unwrap();
+    assert_eq!(read.digest, executed.digest);
+    assert_eq!(server.balance(recipient).await, 1000);
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn test_control_methods() {
+    let server = TestServer::start().await;
+    let client = &server.client;
+
+    let effects: SuiTransactionBlockEffects = client
+        .request(
+            "simulacrum_advanceClock",
+            rpc_params![BigInt::from(1000u64)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(effects.status(), &SuiExecutionStatus::Success);
+
+    let epoch: BigInt<u64> = client
+        .request("simulacrum_advanceEpoch", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(*epoch, 1);
+
+    let recipient = SuiAddress::random_for_testing_only();
+    let effects: SuiTransactionBlockEffects = client
+        .request(
+            "simulacrum_requestGas",
+            rpc_params![recipient, BigInt::from(1_000_000u64)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(effects.status(), &SuiExecutionStatus::Success);
+    assert_eq!(server.balance(recipient).await, 1_000_000);
+
+    // Asking for more gas than the genesis accounts hold is the caller's fault.
+    let err = client
+        .request::<SuiTransactionBlockEffects, _>(
+            "simulacrum_requestGas",
+            rpc_params![recipient, BigInt::from(u64::MAX / 2)],
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Failed to request gas"), "{err}");
+
+    // The gas request is included in the next checkpoint, which becomes the latest one.
+    let checkpoint: Checkpoint = client
+        .request("simulacrum_createCheckpoint", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(checkpoint.epoch, 1);
+    assert!(checkpoint
+        .transactions
+        .contains(effects.transaction_digest()));
+
+    let latest: BigInt<u64> = client
+        .request("sui_getLatestCheckpointSequenceNumber", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(*latest, checkpoint.sequence_number);
+
+    server.stop().await;
+}
@@ -7,7 +7,7 @@ mod tests;
 
 pub mod reader;
 pub mod uploader;
-mod writer;
+pub mod writer;
 
 use anyhow::Result;
 use fastcrypto::hash::MultisetHash;
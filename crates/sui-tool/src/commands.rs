@@ -16,7 +16,8 @@ use std::{collections::BTreeMap, env, sync::Arc};
 use sui_config::genesis::Genesis;
 use sui_core::authority_client::AuthorityAPI;
 use sui_protocol_config::Chain;
-use sui_replay::{execute_replay_command, ReplayToolCommand};
+use sui_replay::archive_fetcher::ArchiveConfig;
+use sui_replay::{execute_offline_replay_command, execute_replay_command, ReplayToolCommand};
 use sui_sdk::{rpc_types::SuiTransactionBlockResponseOptions, SuiClient, SuiClientBuilder};
 use telemetry_subscribers::TracingHandle;
 
@@ -400,6 +401,22 @@ pub enum ToolCommand {
             If not specified, mainnet will be used by default"
         )]
         chain: Option<String>,
+        /// Replay offline, from a local checkpoint archive instead of an RPC. Requires
+        /// --snapshot-path.
+        #[arg(long = "archive-path", requires = "snapshot_path")]
+        archive_path: Option<PathBuf>,
+        /// Directory containing the formal snapshots to start reconstructing state from, when
+        /// replaying from an archive.
+        #[arg(long = "snapshot-path", requires = "archive_path")]
+        snapshot_path: Option<PathBuf>,
+        /// Epoch of the formal snapshot to start from. Defaults to the latest snapshot preceding
+        /// the checkpoints being replayed.
+        #[arg(long = "snapshot-epoch", requires = "archive_path")]
+        snapshot_epoch: Option<u64>,
+        /// Last checkpoint to reconstruct from the archive when replaying a single transaction.
+        /// Defaults to the end of the archive.
+        #[arg(long = "end-checkpoint", requires = "archive_path")]
+        end_checkpoint: Option<u64>,
         #[command(subcommand)]
         cmd: ReplayToolCommand,
     },
@@ -1039,10 +1056,33 @@ impl ToolCommand {
                 use_authority,
                 cfg_path,
                 chain,
-            } => {
-                execute_replay_command(rpc_url, safety_checks, use_authority, cfg_path, chain, cmd)
+                archive_path,
+                snapshot_path,
+                snapshot_epoch,
+                end_checkpoint,
+            } => match (archive_path, snapshot_path) {
+                (Some(archive_path), Some(snapshot_path)) => {
+                    let config = ArchiveConfig {
+                        archive_path,
+                        snapshot_path,
+                        snapshot_epoch,
+                        end_checkpoint,
+                    };
+                    execute_offline_replay_command(config, safety_checks, use_authority, cmd)
+                        .await?;
+                }
+                _ => {
+                    execute_replay_command(
+                        rpc_url,
+                        safety_checks,
+                        use_authority,
+                        cfg_path,
+                        chain,
+                        cmd,
+                    )
                     .await?;
-            }
+                }
+            },
             ToolCommand::VerifyArchive {
                 genesis,
                 object_store_config,
@@ -5,7 +5,9 @@ use anyhow::{anyhow, Ok};
 use clap::{Parser, ValueEnum};
 use comfy_table::{Cell, ContentArrangement, Row, Table};
 use prometheus::Registry;
+use serde_json::Value;
 use std::collections::{BTreeMap, HashMap};
+use std::ops::ControlFlow;
 use std::path::PathBuf;
 use std::str;
 use std::sync::Arc;
@@ -24,9 +26,10 @@ use sui_core::jsonrpc_index::IndexStoreTables;
 use sui_core::rpc_index::RpcIndexStore;
 use sui_types::base_types::{EpochId, ObjectID};
 use tracing::info;
-use typed_store::rocks::{default_db_options, MetricConf};
+use typed_store::rocks::{default_db_options, JsonEntry, MetricConf};
 use typed_store::rocksdb::MultiThreaded;
 use typed_store::traits::{Map, TableSummary};
+use typed_store::DbIterator;
 
 #[derive(EnumString, Clone, Parser, Debug, ValueEnum)]
 pub enum StoreName {
@@ -295,6 +298,70 @@ pub fn dump_table(
     .map_err(|err| anyhow!(err.to_string()))
 }
 
+/// Decodes every entry of a table with the table's types, converts it to JSON and passes it to
+/// `visit`, until `visit` breaks or the table is exhausted.
+pub fn query_table(
+    store_name: StoreName,
+    epoch: Option<EpochId>,
+    db_path: PathBuf,
+    table_name: &str,
+    mut visit: impl FnMut(Value, Value) -> anyhow::Result<ControlFlow<()>>,
+) -> anyhow::Result<()> {
+    let json_iter_err = |err: eyre::Report| anyhow!(err.to_string());
+    match store_name {
+        StoreName::Validator => {
+            let epoch_tables = AuthorityEpochTables::describe_tables();
+            if epoch_tables.contains_key(table_name) {
+                let epoch = epoch.ok_or_else(|| anyhow!("--epoch is required"))?;
+                let tables = AuthorityEpochTables::open_readonly(epoch, &db_path);
+                visit_entries(
+                    tables.json_iter(table_name).map_err(json_iter_err)?,
+                    &mut visit,
+                )
+            } else {
+                let tables = AuthorityPerpetualTables::open_readonly(&db_path);
+                visit_entries(
+                    tables.json_iter(table_name).map_err(json_iter_err)?,
+                    &mut visit,
+                )
+            }
+        }
+        StoreName::Index => {
+            let tables =
+                IndexStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default());
+            visit_entries(
+                tables.json_iter(table_name).map_err(json_iter_err)?,
+                &mut visit,
+            )
+        }
+        StoreName::Epoch => {
+            let tables = CommitteeStoreTables::get_read_only_handle(
+                db_path,
+                None,
+                None,
+                MetricConf::default(),
+            );
+            visit_entries(
+                tables.json_iter(table_name).map_err(json_iter_err)?,
+                &mut visit,
+            )
+        }
+    }
+}
+
+fn visit_entries(
+    iter: DbIterator<'_, JsonEntry>,
+    visit: &mut impl FnMut(Value, Value) -> anyhow::Result<ControlFlow<()>>,
+) -> anyhow::Result<()> {
+    for item in iter {
+        let (key, value) = item?;
+        if visit(key, value)?.is_break() {
+            break;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use sui_core::authority::authority_per_epoch_store::AuthorityEpochTables;
@@ -0,0 +1,306 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail};
+use clap::{Parser, ValueEnum};
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::checkpoints::CheckpointStoreTables;
+use typed_store::export::{TableExporter, TableImporter};
+use typed_store::memstore::InMemoryDB;
+#[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+use typed_store::rocks::open_tidehunter_with_key_sizes;
+use typed_store::rocks::{open_in_memory, Database, MetricConf};
+
+/// TideHunter key spaces have a fixed key size, which is inferred from the exported keys. Tables
+/// that are empty in the source store don't have any keys to infer it from, so they get key spaces
+/// for 32-byte keys (the size of a digest), which is only relevant if the store is written to
+/// after the migration.
+#[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+const EMPTY_TABLE_KEY_SIZE: usize = 32;
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum MigratedStore {
+    /// The authority perpetual tables, under `<db-path>/store/perpetual`
+    Perpetual,
+    /// The checkpoint store, under `<db-path>/checkpoints`
+    Checkpoints,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum TargetBackend {
+    RocksDb,
+    /// Import into a throwaway in-memory database. Nothing is written to the target path; this
+    /// checks that every entry of the source store can be exported and imported.
+    InMemory,
+    /// Import into a TideHunter database, with one key space per table.
+    #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+    TideHunter,
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct MigrateBackendOptions {
+    /// The store to migrate
+    #[arg(long = "store", value_enum)]
+    store: MigratedStore,
+    /// The DB path to migrate the store into. The store is created with the same layout as under
+    /// the source DB path, and must not exist yet.
+    #[arg(long = "target-path")]
+    target_path: PathBuf,
+    /// The storage backend of the migrated store
+    #[arg(long = "target-backend", value_enum, default_value = "rocks-db")]
+    target_backend: TargetBackend,
+    /// Keep the intermediate export of the store at this path. By default, the export is written
+    /// to a temporary file which is removed once the migration completes.
+    #[arg(long = "export-path")]
+    export_path: Option<PathBuf>,
+}
+
+/// Copies every table of a store into a new database, by exporting the store and importing the
+/// export into the target backend. The source store is opened as a secondary instance, which sees
+/// the store as of when it was opened, so it can be migrated while the node that owns it is
+/// running: writes made during the migration are not included.
+/// Run with (for example):
+/// cargo run --package sui-tool -- db-tool --db-path /opt/sui/db/authorities_db/live migrate-backend --store perpetual --target-path /opt/sui/db/migrated
+pub fn migrate_backend(db_path: &Path, opts: MigrateBackendOptions) -> anyhow::Result<()> {
+    let (source_store_path, target_store_path) = match opts.store {
+        MigratedStore::Perpetual => (
+            AuthorityPerpetualTables::path(&db_path.join("store")),
+            AuthorityPerpetualTables::path(&opts.target_path.join("store")),
+        ),
+        MigratedStore::Checkpoints => (
+            db_path.join("checkpoints"),
+            opts.target_path.join("checkpoints"),
+        ),
+    };
+    if !source_store_path.exists() {
+        bail!("{} does not exist", source_store_path.display());
+    }
+    if !matches!(opts.target_backend, TargetBackend::InMemory) && target_store_path.exists() {
+        bail!(
+            "{} already exists, refusing to migrate into an existing store",
+            target_store_path.display()
+        );
+    }
+
+    let export_file = match &opts.export_path {
+        Some(path) => OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?,
+        None => tempfile::tempfile()?,
+    };
+
+    let mut exporter =
+        TableExporter::new(BufWriter::new(export_file)).map_err(|err| anyhow!(err.to_string()))?;
+    match opts.store {
+        MigratedStore::Perpetual => AuthorityPerpetualTables::open_readonly(&db_path.join("store"))
+            .export_tables(&mut exporter),
+        MigratedStore::Checkpoints => {
+            CheckpointStoreTables::open_readonly(&source_store_path).export_tables(&mut exporter)
+        }
+    }
+    .map_err(|err| anyhow!(err.to_string()))?;
+
+    let mut export_file = exporter
+        .finish()
+        .map_err(|err| anyhow!(err.to_string()))?
+        .into_inner()?;
+    export_file.seek(SeekFrom::Start(0))?;
+
+    // RocksDB stores are opened with their own options, other backends are opened up front.
+    let target_db = target_database(opts.target_backend, &target_store_path, &mut export_file)?;
+    export_file.seek(SeekFrom::Start(0))?;
+
+    let mut importer =
+        TableImporter::new(BufReader::new(export_file)).map_err(|err| anyhow!(err.to_string()))?;
+    match (opts.store, &target_db) {
+        (MigratedStore::Perpetual, None) => {
+            AuthorityPerpetualTables::open(&opts.target_path.join("store"), None)
+                .import_tables(&mut importer)
+        }
+        (MigratedStore::Perpetual, Some(db)) => {
+            AuthorityPerpetualTables::open_tables_with_database(db).import_tables(&mut importer)
+        }
+        (MigratedStore::Checkpoints, None) => {
+            CheckpointStoreTables::new(&target_store_path, "checkpoint")
+                .import_tables(&mut importer)
+        }
+        (MigratedStore::Checkpoints, Some(db)) => {
+            CheckpointStoreTables::open_tables_with_database(db).import_tables(&mut importer)
+        }
+    }
+    .map_err(|err| anyhow!(err.to_string()))?;
+
+    match opts.target_backend {
+        TargetBackend::InMemory => {
+            println!("Migrated {:?} store to an in-memory database", opts.store)
+        }
+        _ => println!(
+            "Migrated {:?} store to {}",
+            opts.store,
+            target_store_path.display()
+        ),
+    }
+    Ok(())
+}
+
+/// Opens the database to import into, for backends other than RocksDB. `export_file` is read if
+/// the backend needs to know about the exported tables up front.
+#[cfg_attr(
+    not(all(not(target_os = "windows"), feature = "tide_hunter")),
+    allow(unused_variables)
+)]
+fn target_database(
+    backend: TargetBackend,
+    path: &Path,
+    export_file: &mut File,
+) -> anyhow::Result<Option<Arc<Database>>> {
+    let metric_conf = MetricConf::new("migrate_backend");
+    match backend {
+        TargetBackend::RocksDb => Ok(None),
+        TargetBackend::InMemory => Ok(Some(open_in_memory(InMemoryDB::default(), metric_conf))),
+        #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+        TargetBackend::TideHunter => {
+            let key_sizes = TableImporter::new(BufReader::new(export_file))
+                .and_then(|importer| importer.key_sizes())
+                .map_err(|err| anyhow!(err.to_string()))?
+                .into_iter()
+                .map(|(name, size)| (name, size.unwrap_or(EMPTY_TABLE_KEY_SIZE)))
+                .collect();
+            std::fs::create_dir_all(path)?;
+            Ok(Some(open_tidehunter_with_key_sizes(
+                path,
+                &key_sizes,
+                metric_conf,
+            )?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_core::checkpoints::CheckpointStore;
+    use sui_types::messages_checkpoint::VerifiedCheckpoint;
+    use sui_types::test_checkpoint_data_builder::TestCheckpointDataBuilder;
+
+    /// Creates a checkpoint store under `db_path` with a few checkpoints in it. The store is
+    /// returned open, like the store of a running node.
+    fn populate_checkpoint_store(db_path: &Path) -> (Arc<CheckpointStore>, VerifiedCheckpoint) {
+        let store = CheckpointStore::new(&db_path.join("checkpoints"));
+        let mut builder = TestCheckpointDataBuilder::new(0);
+        let mut checkpoint = None;
+        for _ in 0..3 {
+            let data = builder
+                .start_transaction(0)
+                .finish_transaction()
+                .build_checkpoint();
+            let verified = VerifiedCheckpoint::new_unchecked(data.checkpoint_summary);
+            store.insert_verified_checkpoint(&verified).unwrap();
+            checkpoint = Some(verified);
+        }
+        (store, checkpoint.unwrap())
+    }
+
+    fn export(tables: &CheckpointStoreTables) -> Vec<u8> {
+        let mut exporter = TableExporter::new(Vec::new()).unwrap();
+        tables.export_tables(&mut exporter).unwrap();
+        exporter.finish().unwrap()
+    }
+
+    fn migrate(db_path: &Path, target_backend: TargetBackend) -> (PathBuf, Vec<u8>) {
+        let target_path = tempfile::tempdir().unwrap().into_path();
+        let export_path = tempfile::tempdir().unwrap().into_path().join("export");
+        migrate_backend(
+            db_path,
+            MigrateBackendOptions {
+                store: MigratedStore::Checkpoints,
+                target_path: target_path.clone(),
+                target_backend,
+                export_path: Some(export_path.clone()),
+            },
+        )
+        .unwrap();
+        (target_path, std::fs::read(export_path).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_migrate_to_rocksdb() {
+        let db_path = tempfile::tempdir().unwrap().into_path();
+        let (_source, checkpoint) = populate_checkpoint_store(&db_path);
+
+        let (target_path, exported) = migrate(&db_path, TargetBackend::RocksDb);
+
+        // Migrating into an existing store is refused.
+        let err = migrate_backend(
+            &db_path,
+            MigrateBackendOptions {
+                store: MigratedStore::Checkpoints,
+                target_path: target_path.clone(),
+                target_backend: TargetBackend::RocksDb,
+                export_path: None,
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+
+        let migrated = CheckpointStoreTables::new(&target_path.join("checkpoints"), "migrated");
+        assert_eq!(export(&migrated), exported);
+        drop(migrated);
+
+        let migrated = CheckpointStore::new(&target_path.join("checkpoints"));
+        assert_eq!(
+            migrated.get_highest_verified_checkpoint().unwrap(),
+            Some(checkpoint)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_to_in_memory() {
+        let db_path = tempfile::tempdir().unwrap().into_path();
+        let (_source, _) = populate_checkpoint_store(&db_path);
+
+        let (target_path, exported) = migrate(&db_path, TargetBackend::InMemory);
+
+        let mut exporter = TableExporter::new(Vec::new()).unwrap();
+        CheckpointStoreTables::open_readonly(&db_path.join("checkpoints"))
+            .export_tables(&mut exporter)
+            .unwrap();
+        assert_eq!(exporter.finish().unwrap(), exported);
+
+        // Nothing is written to the target path.
+        assert!(!target_path.join("checkpoints").exists());
+    }
+
+    #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+    #[tokio::test]
+    async fn test_migrate_to_tidehunter() {
+        let db_path = tempfile::tempdir().unwrap().into_path();
+        let (_source, _) = populate_checkpoint_store(&db_path);
+
+        let (target_path, exported) = migrate(&db_path, TargetBackend::TideHunter);
+
+        let key_sizes = TableImporter::new(exported.as_slice())
+            .unwrap()
+            .key_sizes()
+            .unwrap()
+            .into_iter()
+            .map(|(name, size)| (name, size.unwrap_or(EMPTY_TABLE_KEY_SIZE)))
+            .collect();
+        let db = open_tidehunter_with_key_sizes(
+            &target_path.join("checkpoints"),
+            &key_sizes,
+            MetricConf::new("migrated"),
+        )
+        .unwrap();
+        let migrated = CheckpointStoreTables::open_tables_with_database(&db);
+        assert_eq!(export(&migrated), exported);
+    }
+}
@@ -3,6 +3,9 @@
 
 use self::db_dump::{dump_table, duplicate_objects_summary, list_tables, table_summary, StoreName};
 use self::index_search::{search_index, SearchRange};
+use self::migrate_backend::{migrate_backend, MigrateBackendOptions};
+use self::query::{query, QueryOptions};
+use self::repair_indexes::{repair_indexes, RepairIndexesOptions};
 use crate::db_tool::db_dump::{compact, print_table_metadata, prune_checkpoints, prune_objects};
 use anyhow::{anyhow, bail};
 use clap::Parser;
@@ -17,12 +20,16 @@ use sui_types::messages_checkpoint::{CheckpointDigest, CheckpointSequenceNumber}
 use typed_store::rocks::MetricConf;
 pub mod db_dump;
 mod index_search;
+mod migrate_backend;
+mod query;
+mod repair_indexes;
 
 #[derive(Parser)]
 #[command(rename_all = "kebab-case")]
 pub enum DbToolCommand {
     ListTables,
     Dump(Options),
+    Query(QueryOptions),
     IndexSearchKeyRange(IndexSearchKeyRangeOptions),
     IndexSearchCount(IndexSearchCountOptions),
     TableSummary(Options),
@@ -40,6 +47,8 @@ pub enum DbToolCommand {
     PruneObjects,
     PruneCheckpoints,
     SetCheckpointWatermark(SetCheckpointWatermarkOptions),
+    MigrateBackend(MigrateBackendOptions),
+    RepairIndexes(RepairIndexesOptions),
 }
 
 #[derive(Parser)]
@@ -187,6 +196,7 @@ pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> an
             d.page_size,
             d.page_number,
         ),
+        DbToolCommand::Query(q) => query(db_path, q),
         DbToolCommand::TableSummary(d) => {
             print_db_table_summary(d.store_name, d.epoch, db_path, &d.table_name)
         }
@@ -232,6 +242,8 @@ pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> an
             Ok(())
         }
         DbToolCommand::SetCheckpointWatermark(d) => set_checkpoint_watermark(&db_path, d),
+        DbToolCommand::MigrateBackend(d) => migrate_backend(&db_path, d),
+        DbToolCommand::RepairIndexes(d) => repair_indexes(&db_path, d).await,
     }
 }
 
@@ -0,0 +1,291 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::db_tool::db_dump::{query_table, StoreName};
+use anyhow::{anyhow, bail};
+use clap::Parser;
+use serde_json::{json, Value};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::ControlFlow;
+use std::path::PathBuf;
+use std::str::FromStr;
+use sui_types::base_types::EpochId;
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct QueryOptions {
+    /// The type of store to query
+    #[arg(long = "store", short = 's', value_enum)]
+    store_name: StoreName,
+    /// The name of the table to query
+    #[arg(long = "table-name", short = 't')]
+    table_name: String,
+    /// The epoch to use when loading AuthorityEpochTables.
+    #[arg(long = "epoch", short = 'e')]
+    epoch: Option<EpochId>,
+    /// Only output entries matching this predicate. Can be repeated, in which case entries must
+    /// match all predicates.
+    ///
+    /// Predicates have the form `<path><op><value>`. The path starts at `key` or `value` and
+    /// selects fields of the decoded entry with `.`-separated object keys or array indices, where
+    /// `*` selects every field. The operator is one of `==` (a selected field is equal to the
+    /// value, which is parsed as JSON if possible and as a string otherwise), `!=` (no selected
+    /// field is equal to the value), or `~=` (a selected field contains the value as a
+    /// substring of its JSON representation).
+    ///
+    /// For example: `value.*.Value.owner.AddressOwner==0x0123...`
+    #[arg(long = "filter", short = 'f')]
+    filters: Vec<Predicate>,
+    /// Stop after this many matching entries
+    #[arg(long = "limit", short = 'l')]
+    limit: Option<usize>,
+    /// Write the matching entries to this file instead of stdout
+    #[arg(long = "output", short = 'o')]
+    output: Option<PathBuf>,
+}
+
+/// Prints the entries of a table that match all the given predicates as JSON lines, with each
+/// line of the form `{"key": ..., "value": ...}`.
+/// Run with (for example):
+/// cargo run --package sui-tool -- db-tool --db-path /opt/sui/db/authorities_db/live/store query --store validator --table-name objects --filter 'value.*.Value.owner.AddressOwner==0x0123...' --limit 10
+pub fn query(db_path: PathBuf, opts: QueryOptions) -> anyhow::Result<()> {
+    let mut output: Box<dyn Write> = match &opts.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(std::io::stdout().lock())),
+    };
+
+    let limit = opts.limit.unwrap_or(usize::MAX);
+    let mut matched = 0;
+    if limit > 0 {
+        query_table(
+            opts.store_name,
+            opts.epoch,
+            db_path,
+            &opts.table_name,
+            |key, value| {
+                let entry = json!({ "key": key, "value": value });
+                if opts.filters.iter().all(|filter| filter.matches(&entry)) {
+                    serde_json::to_writer(&mut output, &entry)?;
+                    writeln!(output)?;
+                    matched += 1;
+                }
+
+                Ok(if matched >= limit {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                })
+            },
+        )?;
+    }
+
+    output.flush()?;
+    eprintln!("{matched} matching entries");
+    Ok(())
+}
+
+/// A condition on a field of a decoded table entry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Predicate {
+    path: Vec<PathSegment>,
+    op: Op,
+    value: Value,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum PathSegment {
+    Field(String),
+    Any,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Contains,
+}
+
+impl Predicate {
+    /// Whether `entry`, of the form `{"key": ..., "value": ...}`, satisfies the predicate.
+    fn matches(&self, entry: &Value) -> bool {
+        let mut selected = vec![entry];
+        for segment in &self.path {
+            selected = selected
+                .into_iter()
+                .flat_map(|node| select(node, segment))
+                .collect();
+        }
+
+        match self.op {
+            Op::Eq => selected.iter().any(|node| self.equals(node)),
+            Op::Ne => !selected.iter().any(|node| self.equals(node)),
+            Op::Contains => {
+                let needle = match &self.value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                selected
+                    .iter()
+                    .any(|node| node.to_string().contains(&needle))
+            }
+        }
+    }
+
+    fn equals(&self, node: &Value) -> bool {
+        if *node == self.value {
+            return true;
+        }
+
+        // Numbers that don't fit in a JSON number are often serialized as strings, so compare
+        // values as strings too.
+        match (node, &self.value) {
+            (Value::String(s), Value::Number(n)) | (Value::Number(n), Value::String(s)) => {
+                *s == n.to_string()
+            }
+            _ => false,
+        }
+    }
+}
+
+fn select<'a>(node: &'a Value, segment: &PathSegment) -> Vec<&'a Value> {
+    match (node, segment) {
+        (Value::Object(map), PathSegment::Field(field)) => map.get(field).into_iter().collect(),
+        (Value::Array(items), PathSegment::Field(field)) => field
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| items.get(index))
+            .into_iter()
+            .collect(),
+        (Value::Object(map), PathSegment::Any) => map.values().collect(),
+        (Value::Array(items), PathSegment::Any) => items.iter().collect(),
+        _ => vec![],
+    }
+}
+
+impl FromStr for Predicate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let eq = s
+            .find('=')
+            .ok_or_else(|| anyhow!("expected `<path><op><value>`, with op one of ==, != or ~="))?;
+
+        let (path, op, value) = if s[eq + 1..].starts_with('=') {
+            (&s[..eq], Op::Eq, &s[eq + 2..])
+        } else if s[..eq].ends_with('!') {
+            (&s[..eq - 1], Op::Ne, &s[eq + 1..])
+        } else if s[..eq].ends_with('~') {
+            (&s[..eq - 1], Op::Contains, &s[eq + 1..])
+        } else {
+            bail!("unknown operator in `{s}`, expected one of ==, != or ~=");
+        };
+
+        let path: Vec<_> = path
+            .trim()
+            .split('.')
+            .map(|segment| match segment {
+                "*" => PathSegment::Any,
+                field => PathSegment::Field(field.to_owned()),
+            })
+            .collect();
+        match path.first() {
+            Some(PathSegment::Field(root)) if root == "key" || root == "value" => {}
+            _ => bail!("predicate paths must start at `key` or `value`"),
+        }
+        if path.iter().any(|s| *s == PathSegment::Field(String::new())) {
+            bail!("empty field in predicate path");
+        }
+
+        let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_owned()));
+        Ok(Self { path, op, value })
+    }
+}
+
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.path.iter().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+            match segment {
+                PathSegment::Field(field) => write!(f, "{field}")?,
+                PathSegment::Any => write!(f, "*")?,
+            }
+        }
+        let op = match self.op {
+            Op::Eq => "==",
+            Op::Ne => "!=",
+            Op::Contains => "~=",
+        };
+        write!(f, "{op}{}", self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> Value {
+        json!({
+            "key": ["0x0000000000000000000000000000000000000000000000000000000000000abc", 7],
+            "value": {
+                "V1": {
+                    "Value": {
+                        "owner": { "AddressOwner": "0x0000000000000000000000000000000000000000000000000000000000000def" },
+                        "type": "0x2::coin::Coin<0x2::sui::SUI>",
+                        "balance": "18446744073709551615",
+                    }
+                }
+            }
+        })
+    }
+
+    fn matches(predicate: &str) -> bool {
+        predicate.parse::<Predicate>().unwrap().matches(&entry())
+    }
+
+    #[test]
+    fn test_parse_predicate() {
+        assert_eq!(
+            "value.*.owner~=0xdef".parse::<Predicate>().unwrap(),
+            Predicate {
+                path: vec![
+                    PathSegment::Field("value".to_owned()),
+                    PathSegment::Any,
+                    PathSegment::Field("owner".to_owned()),
+                ],
+                op: Op::Contains,
+                value: Value::String("0xdef".to_owned()),
+            }
+        );
+        assert_eq!(
+            "key.1!=7".parse::<Predicate>().unwrap().to_string(),
+            "key.1!=7"
+        );
+
+        assert!("key.1".parse::<Predicate>().is_err());
+        assert!("key.1<7".parse::<Predicate>().is_err());
+        assert!("owner==0x1".parse::<Predicate>().is_err());
+        assert!("value..owner==0x1".parse::<Predicate>().is_err());
+    }
+
+    #[test]
+    fn test_predicate_matches() {
+        assert!(matches("key.1==7"));
+        assert!(!matches("key.1!=7"));
+        assert!(matches("key.0~=abc"));
+        assert!(matches(
+            "value.V1.Value.owner.AddressOwner==0x0000000000000000000000000000000000000000000000000000000000000def"
+        ));
+        assert!(matches("value.*.*.type~=::coin::Coin<"));
+        assert!(matches("value.*.Value.balance==18446744073709551615"));
+        assert!(matches("value~=AddressOwner"));
+
+        // Paths that don't select anything
+        assert!(!matches("value.V2.Value.type~=Coin"));
+        assert!(!matches("key.2==7"));
+        assert!(matches("key.2!=7"));
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use clap::{Parser, ValueEnum};
+use prometheus::Registry;
+use std::path::Path;
+use std::sync::Arc;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::AuthorityStore;
+use sui_core::checkpoints::CheckpointStore;
+use sui_core::index_repair::RepairProgress;
+use sui_core::jsonrpc_index::IndexStore;
+use sui_core::rpc_index::RpcIndexStore;
+use sui_core::verify_indexes::verify_indexes;
+use sui_execution::Executor;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_types::base_types::ObjectID;
+use sui_types::digests::ChainIdentifier;
+use sui_types::error::SuiResult;
+use sui_types::storage::{
+    load_package_object_from_object_store, BackingPackageStore, PackageObject,
+};
+use sui_types::sui_system_state::SuiSystemStateTrait;
+use tracing::info;
+
+const PROGRESS_FILE_NAME: &str = "repair-progress.json";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum RepairedIndex {
+    /// The owner, dynamic field and coin indexes of the JSON-RPC index store, under
+    /// `<db-path>/indexes`
+    JsonRpc,
+    /// The RPC index store, under `<db-path>/rpc-index`
+    Rpc,
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct RepairIndexesOptions {
+    /// The indexes to rebuild. Defaults to all of them.
+    #[arg(long = "index", value_enum)]
+    indexes: Vec<RepairedIndex>,
+    /// Discard the progress of a previous, interrupted, repair and start over
+    #[arg(long = "restart")]
+    restart: bool,
+    /// Once the JSON-RPC indexes have been rebuilt, check them against the live object set
+    #[arg(long = "verify")]
+    verify: bool,
+}
+
+/// Rebuilds the fullnode indexes of a store from its live object set and checkpoint history. The
+/// node must not be running.
+///
+/// The repair is resumable: progress is recorded in a `<index>-repair-progress.json` file next to
+/// each index, and running the command again after an interruption picks up where the previous
+/// run stopped. The file is removed once the index has been rebuilt.
+/// Run with (for example):
+/// cargo run --package sui-tool -- db-tool --db-path /opt/sui/db/authorities_db/live repair-indexes --index json-rpc --verify
+pub async fn repair_indexes(db_path: &Path, opts: RepairIndexesOptions) -> anyhow::Result<()> {
+    let indexes = if opts.indexes.is_empty() {
+        vec![RepairedIndex::JsonRpc, RepairedIndex::Rpc]
+    } else {
+        opts.indexes
+    };
+
+    let registry = Registry::default();
+    let perpetual_tables = Arc::new(AuthorityPerpetualTables::open(&db_path.join("store"), None));
+    let authority_store = AuthorityStore::open_no_genesis(perpetual_tables, false, &registry)?;
+    let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+
+    let protocol_config = current_protocol_config(&authority_store, &checkpoint_store)?;
+    let executor: Arc<dyn Executor + Send + Sync> =
+        sui_execution::executor(&protocol_config, /* silent */ true, None)
+            .map_err(|err| anyhow!(err.to_string()))?;
+    let package_store: Arc<dyn BackingPackageStore + Send + Sync> =
+        Arc::new(PerpetualPackageStore(authority_store.clone()));
+
+    if indexes.contains(&RepairedIndex::JsonRpc) {
+        let index_path = db_path.join("indexes");
+        let progress = load_progress(&index_path, opts.restart)?;
+        let index_store = Arc::new(IndexStore::new_without_init(
+            index_path,
+            &registry,
+            protocol_config.max_move_identifier_len_as_option(),
+            false,
+        ));
+
+        index_store.repair_live_object_indexes(
+            &authority_store,
+            &executor,
+            &package_store,
+            &progress,
+        )?;
+        progress.finish()?;
+        println!("Repaired JSON-RPC indexes");
+
+        if opts.verify {
+            verify_indexes(&*authority_store, index_store)?;
+            println!("Verified JSON-RPC indexes");
+        }
+    }
+
+    if indexes.contains(&RepairedIndex::Rpc) {
+        let progress = load_progress(&db_path.join("rpc-index"), opts.restart)?;
+        RpcIndexStore::repair(
+            db_path,
+            &authority_store,
+            &checkpoint_store,
+            &executor,
+            &package_store,
+            &progress,
+        )
+        .await?;
+        progress.finish()?;
+        println!("Repaired RPC indexes");
+    }
+
+    Ok(())
+}
+
+/// Loads the progress of a previous repair of the index at `index_path`, if any.
+///
+/// The progress file lives next to the index rather than inside it, so that it survives the index
+/// being dropped and recreated.
+fn load_progress(index_path: &Path, restart: bool) -> anyhow::Result<RepairProgress> {
+    let progress_path = index_path.with_file_name(format!(
+        "{}-{PROGRESS_FILE_NAME}",
+        index_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+    ));
+
+    if restart {
+        RepairProgress::load_or_default(&progress_path)?.finish()?;
+    }
+
+    let progress = RepairProgress::load_or_default(progress_path)?;
+    if progress.is_resumed() {
+        info!(
+            "Resuming repair of {} from {}",
+            index_path.display(),
+            progress.path().display()
+        );
+    }
+    Ok(progress)
+}
+
+/// The protocol config in effect for the epoch of the store, used to pick the executor whose
+/// layout resolver matches the one the node would use.
+fn current_protocol_config(
+    authority_store: &AuthorityStore,
+    checkpoint_store: &CheckpointStore,
+) -> anyhow::Result<ProtocolConfig> {
+    let genesis_checkpoint = checkpoint_store
+        .get_checkpoint_by_sequence_number(0)?
+        .ok_or_else(|| anyhow!("genesis checkpoint not found, can't determine the chain"))?;
+    let chain = ChainIdentifier::from(*genesis_checkpoint.digest()).chain();
+
+    let system_state = authority_store.get_sui_system_state_object_unsafe()?;
+    Ok(ProtocolConfig::get_for_version(
+        ProtocolVersion::new(system_state.protocol_version()),
+        chain,
+    ))
+}
+
+/// Serves packages straight from the perpetual tables, as there is no execution cache when
+/// running offline.
+struct PerpetualPackageStore(Arc<AuthorityStore>);
+
+impl BackingPackageStore for PerpetualPackageStore {
+    fn get_package_object(&self, package_id: &ObjectID) -> SuiResult<Option<PackageObject>> {
+        load_package_object_from_object_store(&*self.0, package_id)
+    }
+}
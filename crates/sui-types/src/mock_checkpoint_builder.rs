@@ -22,7 +22,7 @@ pub trait ValidatorKeypairProvider {
 
 /// A utility to build consecutive checkpoints by adding transactions to the checkpoint builder.
 /// It's mostly used by simulations, tests and benchmarks.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct MockCheckpointBuilder {
     previous_checkpoint: Option<VerifiedCheckpoint>,
     transactions: Vec<VerifiedExecutionData>,
@@ -44,6 +44,36 @@ impl MockCheckpointBuilder {
         }
     }
 
+    /// Recreate a builder from the state exposed by [Self::previous_checkpoint],
+    /// [Self::pending_transactions], [Self::epoch_rolling_gas_cost_summary] and [Self::epoch], so
+    /// that it picks up building checkpoints where the original builder left off.
+    pub fn from_parts(
+        previous_checkpoint: Option<VerifiedCheckpoint>,
+        transactions: Vec<VerifiedExecutionData>,
+        epoch_rolling_gas_cost_summary: GasCostSummary,
+        epoch: u64,
+    ) -> Self {
+        Self {
+            previous_checkpoint,
+            transactions,
+            epoch_rolling_gas_cost_summary,
+            epoch,
+        }
+    }
+
+    pub fn previous_checkpoint(&self) -> Option<&VerifiedCheckpoint> {
+        self.previous_checkpoint.as_ref()
+    }
+
+    /// Transactions that have been pushed since the last checkpoint was built.
+    pub fn pending_transactions(&self) -> &[VerifiedExecutionData] {
+        &self.transactions
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
     pub fn size(&self) -> usize {
         self.transactions.len()
     }
@@ -179,7 +209,9 @@ impl MockCheckpointBuilder {
         (checkpoint, contents, full_contents)
     }
 
-    fn create_certified_checkpoint(
+    /// Certifies `checkpoint` with the signatures of every validator in the committee of
+    /// `validator_keys`.
+    pub fn create_certified_checkpoint(
         validator_keys: &impl ValidatorKeypairProvider,
         checkpoint: CheckpointSummary,
     ) -> VerifiedCheckpoint {
@@ -31,11 +31,17 @@ pub use read_store::CoinInfo;
 pub use read_store::DynamicFieldIndexInfo;
 pub use read_store::DynamicFieldKey;
 pub use read_store::EpochInfo;
+pub use read_store::EventCursor;
+pub use read_store::EventIndexFilter;
+pub use read_store::EventIteratorItem;
 pub use read_store::OwnedObjectInfo;
 pub use read_store::ReadStore;
 pub use read_store::RpcIndexes;
 pub use read_store::RpcStateReader;
+pub use read_store::TransactionCursor;
+pub use read_store::TransactionIndexFilter;
 pub use read_store::TransactionInfo;
+pub use read_store::TransactionIteratorItem;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 pub use shared_in_memory_store::SharedInMemoryStore;
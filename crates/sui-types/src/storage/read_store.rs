@@ -597,6 +597,85 @@ pub trait RpcIndexes: Send + Sync {
     ) -> Result<Box<dyn Iterator<Item = DynamicFieldIteratorItem> + '_>>;
 
     fn get_coin_info(&self, coin_type: &StructTag) -> Result<Option<CoinInfo>>;
+
+    /// Iterate over the transactions matching `filter` in the order they were executed, starting
+    /// at (and including) `cursor` if provided.
+    fn transactions_iter(
+        &self,
+        filter: TransactionIndexFilter,
+        cursor: Option<TransactionCursor>,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = TransactionIteratorItem> + '_>>;
+
+    /// Iterate over the events matching `filter` in the order they were emitted, starting at (and
+    /// including) `cursor` if provided.
+    fn events_iter(
+        &self,
+        filter: EventIndexFilter,
+        cursor: Option<EventCursor>,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = EventIteratorItem> + '_>>;
+}
+
+pub type TransactionIteratorItem = Result<(TransactionCursor, TransactionDigest), TypedStoreError>;
+pub type EventIteratorItem = Result<(EventCursor, TransactionDigest), TypedStoreError>;
+
+/// The position of a transaction in the chain.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct TransactionCursor {
+    pub checkpoint: CheckpointSequenceNumber,
+    /// Index of the transaction in its checkpoint's contents
+    pub transaction_index: u64,
+}
+
+/// The position of an event in the chain.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct EventCursor {
+    pub checkpoint: CheckpointSequenceNumber,
+    /// Index of the emitting transaction in its checkpoint's contents
+    pub transaction_index: u64,
+    /// Index of the event in the transaction's events
+    pub event_index: u64,
+}
+
+/// A dimension on which transactions are indexed.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum TransactionIndexFilter {
+    /// Transactions sent by an address.
+    Sender(SuiAddress),
+    /// Transactions which created, mutated, or unwrapped an object owned by an address.
+    Recipient(SuiAddress),
+    /// Transactions sent by the first address to the second.
+    SenderAndRecipient(SuiAddress, SuiAddress),
+    /// Transactions which took an object as an input.
+    InputObject(ObjectID),
+    /// Transactions which created, mutated, or unwrapped an object.
+    ChangedObject(ObjectID),
+    /// Transactions which touched an object in any way, including deleting, wrapping, or reading
+    /// it as a shared object.
+    AffectedObject(ObjectID),
+    /// Transactions which called a function in a package.
+    MovePackage(ObjectID),
+    /// Transactions which called a function in a module.
+    MoveModule(ObjectID, String),
+    /// Transactions which called a function.
+    MoveFunction(ObjectID, String, String),
+    /// Transactions of a particular kind, e.g. `ProgrammableTransaction`.
+    Kind(String),
+}
+
+/// A dimension on which events are indexed.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum EventIndexFilter {
+    /// Events emitted by transactions sent by an address.
+    Sender(SuiAddress),
+    /// Events emitted by a transaction calling into a module, regardless of where the event's
+    /// type is defined.
+    EmittingModule(ObjectID, String),
+    /// Events whose type is defined in a module.
+    EventModule(ObjectID, String),
+    /// Events of exactly this type.
+    EventType(StructTag),
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
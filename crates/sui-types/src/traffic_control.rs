@@ -63,7 +63,7 @@ const TRAFFIC_SINK_TIMEOUT_SEC: u64 = 300;
 ///     [<known client IP>] <--- number of hops is 1
 ///     ["1.2.3.4", <known client IP>, "5.6.7.8", "9.10.11.12"] <--- number of hops is 3
 /// ```
-#[derive(Clone, Debug, Deserialize, Serialize, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum ClientIdSource {
     #[default]
@@ -202,6 +202,103 @@ fn default_sketch_tolerance() -> f64 {
     DEFAULT_SKETCH_TOLERANCE
 }
 
+/// Weights of the dimensions of a request's cost, used by cost-weighted policies to turn each
+/// request into an amount to tally. A request's cost is the weighted sum of its dimensions, so the
+/// default weights (one per request, and nothing for anything else) reduce to counting requests.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CostWeights {
+    /// Cost of each request, regardless of what it took to serve it.
+    #[serde(default = "default_request_cost_weight")]
+    pub request: f64,
+    /// Cost per millisecond spent serving the request.
+    #[serde(default)]
+    pub execution_time_ms: f64,
+    /// Cost per MIST of computation charged for a transaction the request executed or dry-ran.
+    ///
+    /// Only measured for JSON-RPC requests to fullnodes: validators do not extract gas from their
+    /// responses, so this weight has no effect on their policies.
+    #[serde(default)]
+    pub gas: f64,
+    /// Cost per KiB of response sent to the client.
+    ///
+    /// Only measured for JSON-RPC requests to fullnodes: validators do not measure the size of
+    /// their responses, so this weight has no effect on their policies.
+    #[serde(default)]
+    pub response_kib: f64,
+}
+
+impl Default for CostWeights {
+    fn default() -> Self {
+        Self {
+            request: default_request_cost_weight(),
+            execution_time_ms: 0.0,
+            gas: 0.0,
+            response_kib: 0.0,
+        }
+    }
+}
+
+fn default_request_cost_weight() -> f64 {
+    1.0
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CostThresholdConfig {
+    /// Cost per second, averaged over the window, above which a client's subnet is blocked.
+    #[serde(default = "default_client_threshold")]
+    pub client_threshold: u64,
+    #[serde(default = "default_proxied_client_threshold")]
+    pub proxied_client_threshold: u64,
+    #[serde(default)]
+    pub cost_weights: CostWeights,
+    /// Length of the prefix that IPv4 clients are grouped by: all clients in the same subnet of
+    /// this size share a tally, and are blocked together. 32 treats each address separately.
+    #[serde(default = "default_ipv4_prefix_len")]
+    pub ipv4_prefix_len: u8,
+    /// Length of the prefix that IPv6 clients are grouped by. 128 treats each address separately.
+    #[serde(default = "default_ipv6_prefix_len")]
+    pub ipv6_prefix_len: u8,
+    #[serde(default = "default_window_size_secs")]
+    pub window_size_secs: u64,
+    #[serde(default = "default_update_interval_secs")]
+    pub update_interval_secs: u64,
+    #[serde(default = "default_sketch_capacity")]
+    pub sketch_capacity: usize,
+    #[serde(default = "default_sketch_probability")]
+    pub sketch_probability: f64,
+    #[serde(default = "default_sketch_tolerance")]
+    pub sketch_tolerance: f64,
+}
+
+impl Default for CostThresholdConfig {
+    fn default() -> Self {
+        Self {
+            client_threshold: default_client_threshold(),
+            proxied_client_threshold: default_proxied_client_threshold(),
+            cost_weights: CostWeights::default(),
+            ipv4_prefix_len: default_ipv4_prefix_len(),
+            ipv6_prefix_len: default_ipv6_prefix_len(),
+            window_size_secs: default_window_size_secs(),
+            update_interval_secs: default_update_interval_secs(),
+            sketch_capacity: default_sketch_capacity(),
+            sketch_probability: default_sketch_probability(),
+            sketch_tolerance: default_sketch_tolerance(),
+        }
+    }
+}
+
+fn default_ipv4_prefix_len() -> u8 {
+    32
+}
+
+fn default_ipv6_prefix_len() -> u8 {
+    128
+}
+
 // Serializable representation of policy types, used in config
 // in order to easily change in tests or to killswitch
 #[derive(Clone, Serialize, Deserialize, Debug, Default)]
@@ -215,6 +312,11 @@ pub enum PolicyType {
     /// with granularity of `update_interval_secs`
     FreqThreshold(FreqThresholdConfig),
 
+    /// Blocks the subnet of connection_ip (of the configured prefix length) after reaching a
+    /// tally rate of `threshold` cost per second, where each request is weighted by what it cost
+    /// to serve, and the rate is averaged over a window as for `FreqThreshold`
+    CostThreshold(CostThresholdConfig),
+
     /* Below this point are test policies, and thus should not be used in production */
     ///
     /// Simple policy that adds connection_ip to blocklist when the same connection_ip
@@ -36,6 +36,10 @@ use std::fmt::{Debug, Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use sui_keys::encrypted_keystore::{
+    backup_keystore_file, EncryptedFileKeystore, KdfParams, PassphraseProvider, PassphraseRequest,
+    KEYSTORE_PASSPHRASE_ENV_VAR,
+};
 use sui_keys::key_derive::generate_new_key;
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
@@ -102,6 +106,14 @@ pub enum KeyToolCommand {
         #[clap(long, default_value = "0")]
         cur_epoch: u64,
     },
+    /// Encrypt the keys in Sui CLI Keystore with a passphrase. The plaintext keystore file is
+    /// replaced by an encrypted one, and the client config using it is updated to match. Keys
+    /// are unlocked with the passphrase when they are first used, which is prompted for, or
+    /// read from the SUI_KEYSTORE_PASSPHRASE environment variable if it is set.
+    ///
+    /// The plaintext keystore is kept in a `.bak` file next to it until you confirm it can be
+    /// deleted.
+    EncryptKeystore,
     /// Generate a new keypair with key scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word
@@ -342,6 +354,15 @@ pub struct Key {
     peer_id: Option<String>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedKeystore {
+    keystore_path: PathBuf,
+    encrypted_keys: usize,
+    /// The plaintext keystore, if it was kept.
+    plaintext_backup: Option<PathBuf>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportedKey {
@@ -461,6 +482,7 @@ pub enum CommandOutput {
     Convert(ConvertOutput),
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeOrVerifyTx(DecodeOrVerifyTxOutput),
+    EncryptKeystore(EncryptedKeystore),
     Error(String),
     Generate(Key),
     Import(Key),
@@ -667,6 +689,50 @@ impl KeyToolCommand {
                     }
                 }
             }
+            KeyToolCommand::EncryptKeystore => {
+                let Keystore::File(file) = keystore else {
+                    return Err(anyhow!("Only a plaintext file keystore can be encrypted"));
+                };
+                let keystore_path = file
+                    .path()
+                    .ok_or_else(|| anyhow!("The keystore has no file to encrypt"))?
+                    .to_path_buf();
+
+                let passphrase = (passphrase_prompt())(PassphraseRequest::New)?;
+                let mut encrypted = EncryptedFileKeystore::encrypt(
+                    file,
+                    &keystore_path,
+                    passphrase,
+                    KdfParams::default(),
+                )?;
+                let backup = backup_keystore_file(&keystore_path)?;
+                encrypted.save()?;
+                encrypted.set_passphrase_provider(passphrase_prompt());
+
+                // Keep the plaintext keys around unless the user explicitly lets them go. Without
+                // a terminal to ask on, the backup is kept.
+                let remove_backup = inquire::Confirm::new(&format!(
+                    "The keystore is now encrypted. Delete the plaintext backup at {}?",
+                    backup.display()
+                ))
+                .with_default(false)
+                .prompt()
+                .unwrap_or(false);
+                let plaintext_backup = if remove_backup {
+                    fs::remove_file(&backup)?;
+                    None
+                } else {
+                    Some(backup)
+                };
+
+                let encrypted_keys = encrypted.keys().len();
+                *keystore = Keystore::EncryptedFile(encrypted);
+                CommandOutput::EncryptKeystore(EncryptedKeystore {
+                    keystore_path,
+                    encrypted_keys,
+                    plaintext_backup,
+                })
+            }
             KeyToolCommand::Export { key_identity } => {
                 let address = get_identity_address_from_keystore(key_identity, keystore)?;
                 let skp = keystore.get_key(&address)?;
@@ -1320,6 +1386,34 @@ impl From<PublicKey> for Key {
     }
 }
 
+/// Obtain the passphrase of an encrypted keystore from the SUI_KEYSTORE_PASSPHRASE environment
+/// variable if it is set, or by prompting for it otherwise. A new passphrase must be entered twice.
+pub fn passphrase_prompt() -> PassphraseProvider {
+    Arc::new(|request| {
+        if let Ok(passphrase) = std::env::var(KEYSTORE_PASSPHRASE_ENV_VAR) {
+            return Ok(passphrase);
+        }
+
+        let message = match &request {
+            PassphraseRequest::Unlock(address) => {
+                format!("Enter the keystore passphrase to unlock {address}:")
+            }
+            PassphraseRequest::New => "Choose a passphrase for the keystore:".to_string(),
+        };
+        let prompt = inquire::Password::new(&message)
+            .with_display_mode(inquire::PasswordDisplayMode::Hidden);
+        let prompt = match request {
+            PassphraseRequest::Unlock(_) => prompt.without_confirmation(),
+            PassphraseRequest::New => {
+                prompt.with_custom_confirmation_message("Confirm the passphrase:")
+            }
+        };
+        prompt
+            .prompt()
+            .map_err(|e| anyhow!("Cannot read the keystore passphrase: {e}"))
+    })
+}
+
 impl Display for CommandOutput {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -6,7 +6,7 @@ use crate::client_commands::{
 };
 use crate::fire_drill::{run_fire_drill, FireDrill};
 use crate::genesis_ceremony::{run, Ceremony};
-use crate::keytool::KeyToolCommand;
+use crate::keytool::{passphrase_prompt, KeyToolCommand};
 use crate::validator_commands::SuiValidatorCommand;
 use anyhow::{anyhow, bail, ensure, Context};
 use clap::*;
@@ -45,6 +45,7 @@ use sui_graphql_rpc::{
 };
 
 use serde_json::json;
+use sui_keys::encrypted_keystore::EncryptedFileKeystore;
 use sui_keys::keypair_file::read_key;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_move::manage_package::resolve_lock_file_path;
@@ -432,8 +433,23 @@ impl SuiCommand {
             } => {
                 let keystore_path =
                     keystore_path.unwrap_or(sui_config_dir()?.join(SUI_KEYSTORE_FILENAME));
-                let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+                let mut keystore = open_keystore(&keystore_path)?;
+
+                // Read the client config before the keystore it uses is encrypted, as it can no
+                // longer be read as a plaintext keystore afterwards.
+                let client_config = if matches!(cmd, KeyToolCommand::EncryptKeystore) {
+                    client_config_using_keystore(&keystore_path)?
+                } else {
+                    None
+                };
+
                 cmd.execute(&mut keystore).await?.print(!json);
+
+                if let Some(mut client_config) = client_config {
+                    client_config.keystore =
+                        Keystore::from(EncryptedFileKeystore::new(&keystore_path)?);
+                    client_config.save()?;
+                }
                 Ok(())
             }
             SuiCommand::Client {
@@ -446,6 +462,7 @@ impl SuiCommand {
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 if let Some(cmd) = cmd {
                     let mut context = WalletContext::new(&config_path, None, None)?;
+                    prompt_for_keystore_passphrase(&mut context);
                     if let Ok(client) = context.get_client().await {
                         if let Err(e) = client.check_api_version() {
                             eprintln!("{}", format!("[warning] {e}").yellow().bold());
@@ -469,6 +486,7 @@ impl SuiCommand {
                 let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 let mut context = WalletContext::new(&config_path, None, None)?;
+                prompt_for_keystore_passphrase(&mut context);
                 if let Some(cmd) = cmd {
                     if let Ok(client) = context.get_client().await {
                         if let Err(e) = client.check_api_version() {
@@ -1346,6 +1364,46 @@ async fn prompt_if_no_config(
     Ok(())
 }
 
+/// Open the keystore at `path`, whether it is encrypted or not.
+fn open_keystore(path: &PathBuf) -> Result<Keystore, anyhow::Error> {
+    Ok(if EncryptedFileKeystore::is_encrypted(path) {
+        let mut keystore = EncryptedFileKeystore::new(path)?;
+        keystore.set_passphrase_provider(passphrase_prompt());
+        Keystore::from(keystore)
+    } else {
+        Keystore::from(FileBasedKeystore::new(path)?)
+    })
+}
+
+/// Prompt for the passphrase of the wallet's keystore, if it is encrypted, when a key needs
+/// unlocking.
+fn prompt_for_keystore_passphrase(context: &mut WalletContext) {
+    if let Keystore::EncryptedFile(keystore) = &mut context.config.keystore {
+        keystore.set_passphrase_provider(passphrase_prompt());
+    }
+}
+
+/// The client config in the default Sui config directory, if it uses the plaintext keystore at
+/// `keystore_path`.
+fn client_config_using_keystore(
+    keystore_path: &Path,
+) -> Result<Option<PersistedConfig<SuiClientConfig>>, anyhow::Error> {
+    let config_path = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
+    if !config_path.exists() {
+        return Ok(None);
+    }
+
+    let config: SuiClientConfig = PersistedConfig::read(&config_path)?;
+    let uses_keystore = match &config.keystore {
+        Keystore::File(file) => file
+            .path()
+            .is_some_and(|path| path.canonicalize().ok() == keystore_path.canonicalize().ok()),
+        _ => false,
+    };
+
+    Ok(uses_keystore.then(|| config.persisted(&config_path)))
+}
+
 fn read_line() -> Result<String, anyhow::Error> {
     let mut s = String::new();
     let _ = stdout().flush();
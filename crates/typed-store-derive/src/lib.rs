@@ -335,6 +335,16 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 }
             }
 
+            /// Opens the tables on top of an already open database, regardless of its storage backend
+            #[allow(unused_parens)]
+            pub fn open_tables_with_database(db: &std::sync::Arc<typed_store::rocks::Database>) -> Self {
+                Self {
+                    #(
+                        #field_names: DBMap::#inner_types::reopen(db, Some(stringify!(#cf_names)), &typed_store::rocks::ReadWriteOptions::default(), false).expect(&format!("Cannot open {} CF.", stringify!(#cf_names))[..]),
+                    )*
+                }
+            }
+
             /// Returns a list of the tables name and type pairs
             pub fn describe_tables() -> std::collections::BTreeMap<String, (String, String)> {
                 vec![#(
@@ -342,6 +352,32 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 )*].into_iter().collect()
             }
 
+            /// Streams the entries of all tables, along with their key and value types, to `exporter`
+            /// Tables are exported one after the other, so the database must not be written to during the export
+            pub fn export_tables<W: std::io::Write>(&self, exporter: &mut typed_store::export::TableExporter<W>) -> eyre::Result<()> {
+                #(
+                    exporter.export_table(stringify!(#cf_names), stringify!(#key_names), stringify!(#value_names), &self.#field_names)?;
+                )*
+                Ok(())
+            }
+
+            /// Writes the tables read from `importer` into the tables with the same name
+            /// Fails if the export contains a table that doesn't exist here, or whose key or value types differ
+            pub fn import_tables<R: std::io::Read>(&self, importer: &mut typed_store::export::TableImporter<R>) -> eyre::Result<()> {
+                while let Some(header) = importer.next_table()? {
+                    match header.name.as_str() {
+                        #(
+                            stringify!(#cf_names) => {
+                                header.ensure_types(stringify!(#key_names), stringify!(#value_names))?;
+                                importer.import_table(&self.#field_names)?;
+                            }
+                        )*
+                        name => eyre::bail!("No such table name: {}", name),
+                    }
+                }
+                Ok(())
+            }
+
             /// This opens the DB in read only mode and returns a struct which exposes debug features
             pub fn get_read_only_handle (
                 primary_path: std::path::PathBuf,
@@ -445,6 +481,30 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 )*].into_iter().collect()
             }
 
+            /// Iterate over all key-value pairs of the given table, decoded with the table's types and converted to JSON
+            /// Tables must be opened in read only mode using `open_tables_read_only`
+            pub fn json_iter(&self, table_name: &str) -> eyre::Result<typed_store::DbIterator<'_, typed_store::rocks::JsonEntry>> {
+                Ok(match table_name {
+                    #(
+                        stringify!(#field_names) => {
+                            typed_store::traits::Map::try_catch_up_with_primary(&self.#field_names)?;
+                            self.#field_names.safe_json_iter()
+                        }
+                    )*
+
+                    _ => eyre::bail!("No such table name: {}", table_name),
+                })
+            }
+
+            /// Streams the entries of all tables, along with their key and value types, to `exporter`
+            /// Tables are not caught up with the primary during the export, so they are exported as of when they were opened (or last caught up), even if the primary is written to in the meantime
+            pub fn export_tables<W: std::io::Write>(&self, exporter: &mut typed_store::export::TableExporter<W>) -> eyre::Result<()> {
+                #(
+                    exporter.export_table(stringify!(#cf_names), stringify!(#key_names), stringify!(#value_names), &self.#field_names)?;
+                )*
+                Ok(())
+            }
+
             /// Try catch up with primary for all tables. This can be a slow operation
             /// Tables must be opened in read only mode using `open_tables_read_only`
             pub fn try_catch_up_with_primary_all(&self) -> eyre::Result<()> {
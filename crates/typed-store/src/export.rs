@@ -0,0 +1,306 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A backend-agnostic stream format for moving the contents of a set of tables from one database
+//! to another.
+//!
+//! An export starts with a magic number and a format version, followed by one section per table.
+//! Each section consists of a header naming the table and its key and value types, the table's
+//! entries in key order (grouped into chunks), and a trailer with the number of entries, so that
+//! a truncated export is detected on import. Keys and values are written in the encoding used by
+//! `DBMap` to store them, so an export doesn't depend on the backend it was taken from.
+//!
+//! Tables derived with `DBMapUtils` get `export_tables` and `import_tables` methods that stream
+//! all of their tables through a [`TableExporter`] and a [`TableImporter`] respectively.
+//!
+//! Each table is read through its own iterator, one table after the other, so an export is only
+//! consistent across tables if nothing writes to the database while it is taken. Export from a
+//! database opened as the primary, after stopping the node that owns it (which also guarantees
+//! that the node can't be running), rather than through a secondary handle.
+
+use crate::rocks::DBMap;
+use crate::traits::Map;
+use crate::util::be_fix_int_ser;
+use bincode::Options;
+use eyre::{bail, ensure, eyre};
+use itertools::Itertools;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use tracing::info;
+
+const EXPORT_MAGIC: [u8; 8] = *b"TSEXPORT";
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Number of entries grouped into a single record on export, and written in a single batch on
+/// import.
+const ENTRIES_PER_CHUNK: usize = 1024;
+
+/// Upper bound on the size of a single record, to avoid allocating arbitrary amounts of memory
+/// when reading a corrupted export.
+const MAX_RECORD_SIZE: usize = 1 << 30;
+
+/// Describes a table in an export.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableHeader {
+    pub name: String,
+    pub key_type: String,
+    pub value_type: String,
+}
+
+impl TableHeader {
+    /// Checks that the table was exported with the given key and value types.
+    pub fn ensure_types(&self, key_type: &str, value_type: &str) -> eyre::Result<()> {
+        ensure!(
+            self.key_type == key_type && self.value_type == value_type,
+            "table {} was exported as <{}, {}>, but is <{}, {}> in the target database",
+            self.name,
+            self.key_type,
+            self.value_type,
+            key_type,
+            value_type,
+        );
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum Record {
+    Table(TableHeader),
+    Entries(Vec<(Vec<u8>, Vec<u8>)>),
+    EndOfTable { num_entries: u64 },
+    EndOfExport,
+}
+
+/// Writes tables to an export stream.
+pub struct TableExporter<W> {
+    writer: W,
+}
+
+impl<W: Write> TableExporter<W> {
+    pub fn new(mut writer: W) -> eyre::Result<Self> {
+        writer.write_all(&EXPORT_MAGIC)?;
+        writer.write_all(&EXPORT_FORMAT_VERSION.to_le_bytes())?;
+        Ok(Self { writer })
+    }
+
+    /// Writes all the entries of `table` to the export, under the given name. Returns the number
+    /// of entries exported.
+    ///
+    /// Entries are read through a single iterator, so the exported table is consistent with the
+    /// point in time at which the export of the table started. Other tables are read at other
+    /// points in time, see the module documentation.
+    pub fn export_table<K, V>(
+        &mut self,
+        name: &str,
+        key_type: &str,
+        value_type: &str,
+        table: &DBMap<K, V>,
+    ) -> eyre::Result<u64>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        self.write_record(&Record::Table(TableHeader {
+            name: name.to_owned(),
+            key_type: key_type.to_owned(),
+            value_type: value_type.to_owned(),
+        }))?;
+
+        let mut num_entries = 0;
+        for chunk in &table.safe_iter().chunks(ENTRIES_PER_CHUNK) {
+            let entries = chunk
+                .map(|item| {
+                    let (key, value) = item?;
+                    Ok((be_fix_int_ser(&key), bcs::to_bytes(&value)?))
+                })
+                .collect::<eyre::Result<Vec<_>>>()?;
+            num_entries += entries.len() as u64;
+            self.write_record(&Record::Entries(entries))?;
+        }
+
+        self.write_record(&Record::EndOfTable { num_entries })?;
+        info!(table = name, num_entries, "Exported table");
+        Ok(num_entries)
+    }
+
+    /// Marks the end of the export and returns the underlying writer.
+    pub fn finish(mut self) -> eyre::Result<W> {
+        self.write_record(&Record::EndOfExport)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_record(&mut self, record: &Record) -> eyre::Result<()> {
+        let bytes = bcs::to_bytes(record)?;
+        ensure!(
+            bytes.len() <= MAX_RECORD_SIZE,
+            "export record of {} bytes exceeds the maximum record size",
+            bytes.len(),
+        );
+        self.writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+/// Reads tables from an export stream, written by a [`TableExporter`].
+pub struct TableImporter<R> {
+    reader: R,
+    /// The table whose entries are next in the stream, if any.
+    current: Option<TableHeader>,
+}
+
+impl<R: Read> TableImporter<R> {
+    pub fn new(mut reader: R) -> eyre::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        ensure!(magic == EXPORT_MAGIC, "not a typed-store export");
+
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        ensure!(
+            version == EXPORT_FORMAT_VERSION,
+            "unsupported export format version {version}, expected {EXPORT_FORMAT_VERSION}",
+        );
+
+        Ok(Self {
+            reader,
+            current: None,
+        })
+    }
+
+    /// Returns the header of the next table in the export, or `None` once all tables have been
+    /// read. The table's entries must be consumed with [`Self::import_table`] or
+    /// [`Self::skip_table`] before moving on to the next table.
+    pub fn next_table(&mut self) -> eyre::Result<Option<TableHeader>> {
+        if let Some(current) = &self.current {
+            bail!(
+                "the entries of table {} have not been consumed",
+                current.name
+            );
+        }
+
+        match self.read_record()? {
+            Record::Table(header) => {
+                self.current = Some(header.clone());
+                Ok(Some(header))
+            }
+            Record::EndOfExport => Ok(None),
+            _ => bail!("malformed export: expected a table header"),
+        }
+    }
+
+    /// Writes the entries of the current table into `table`, in batches. Returns the number of
+    /// entries imported.
+    ///
+    /// Every key and value is decoded as `K` and `V` before it is written, so an export can't be
+    /// imported into a table with an incompatible schema.
+    pub fn import_table<K, V>(&mut self, table: &DBMap<K, V>) -> eyre::Result<u64>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        let key_config = bincode::DefaultOptions::new()
+            .with_big_endian()
+            .with_fixint_encoding();
+
+        let name = table.cf_name().to_owned();
+        let num_entries = self.read_entries(|entries| {
+            let entries = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let key: K = key_config.deserialize(&key)?;
+                    let value: V = bcs::from_bytes(&value)?;
+                    Ok((key, value))
+                })
+                .collect::<eyre::Result<Vec<_>>>()?;
+
+            let mut batch = table.batch();
+            batch.insert_batch(table, entries)?;
+            batch.write()?;
+            Ok(())
+        })?;
+
+        info!(table = name, num_entries, "Imported table");
+        Ok(num_entries)
+    }
+
+    /// Skips over the entries of the current table. Returns the number of entries skipped.
+    pub fn skip_table(&mut self) -> eyre::Result<u64> {
+        self.read_entries(|_| Ok(()))
+    }
+
+    /// Reads the rest of the export, and returns the size of the keys of each table, or `None`
+    /// for tables without entries. This is needed to import into storage backends that only
+    /// support fixed-size keys, and fails if the keys of a table don't all have the same size.
+    pub fn key_sizes(mut self) -> eyre::Result<BTreeMap<String, Option<usize>>> {
+        let mut key_sizes = BTreeMap::new();
+        while let Some(header) = self.next_table()? {
+            let mut key_size = None;
+            self.read_entries(|entries| {
+                for (key, _) in entries {
+                    let size = *key_size.get_or_insert(key.len());
+                    ensure!(
+                        size == key.len(),
+                        "table {} has keys of different sizes ({size} and {} bytes)",
+                        header.name,
+                        key.len(),
+                    );
+                }
+                Ok(())
+            })?;
+            key_sizes.insert(header.name, key_size);
+        }
+        Ok(key_sizes)
+    }
+
+    fn read_entries(
+        &mut self,
+        mut f: impl FnMut(Vec<(Vec<u8>, Vec<u8>)>) -> eyre::Result<()>,
+    ) -> eyre::Result<u64> {
+        let header = self
+            .current
+            .take()
+            .ok_or_else(|| eyre!("no table to read entries from"))?;
+
+        let mut num_entries = 0;
+        loop {
+            match self.read_record()? {
+                Record::Entries(entries) => {
+                    num_entries += entries.len() as u64;
+                    f(entries)?;
+                }
+                Record::EndOfTable {
+                    num_entries: expected,
+                } => {
+                    ensure!(
+                        expected == num_entries,
+                        "malformed export: table {} should have {expected} entries, found {num_entries}",
+                        header.name,
+                    );
+                    return Ok(num_entries);
+                }
+                _ => bail!(
+                    "malformed export: unexpected record in the entries of table {}",
+                    header.name
+                ),
+            }
+        }
+    }
+
+    fn read_record(&mut self) -> eyre::Result<Record> {
+        let mut len = [0u8; 4];
+        self.reader.read_exact(&mut len)?;
+        let len = u32::from_le_bytes(len) as usize;
+        ensure!(
+            len <= MAX_RECORD_SIZE,
+            "malformed export: record of {len} bytes exceeds the maximum record size",
+        );
+
+        let mut bytes = vec![0u8; len];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(bcs::from_bytes(&bytes)?)
+    }
+}
@@ -13,6 +13,7 @@ pub use rocksdb;
 
 pub mod traits;
 pub use traits::{DbIterator, Map};
+pub mod export;
 pub mod memstore;
 pub mod metrics;
 pub mod rocks;
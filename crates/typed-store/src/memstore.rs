@@ -4,49 +4,193 @@
 use bincode::Options;
 use serde::de::DeserializeOwned;
 use std::collections::{BTreeMap, Bound, HashMap};
+use std::fmt;
 use std::sync::{Arc, RwLock};
 use typed_store_error::TypedStoreError;
 
-type InMemoryStoreInternal = Arc<RwLock<HashMap<String, BTreeMap<Vec<u8>, Vec<u8>>>>>;
+type InMemoryColumnFamily = BTreeMap<Vec<u8>, Vec<u8>>;
+type InMemoryStoreInternal = Arc<RwLock<HashMap<String, InMemoryColumnFamily>>>;
 
-#[derive(Clone, Debug)]
+/// A full merge function, mirroring RocksDB's associative merge operators: given the key, the
+/// existing value (if any) and the operands to merge in (oldest first), returns the new value, or
+/// `None` to fail the merge (which leaves the existing value in place).
+pub type InMemoryMergeFn =
+    Arc<dyn Fn(&[u8], Option<&[u8]>, &[Vec<u8>]) -> Option<Vec<u8>> + Send + Sync>;
+
+#[derive(Clone)]
+struct MergeOperator {
+    name: String,
+    merge: InMemoryMergeFn,
+}
+
+impl fmt::Debug for MergeOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MergeOperator({})", self.name)
+    }
+}
+
+/// A purely in-memory backend for `DBMap`, with the same observable behaviour as RocksDB:
+/// batches are applied atomically across column families, iterators read from a consistent
+/// snapshot taken when they are created, and iterator upper bounds are exclusive.
+#[derive(Clone, Debug, Default)]
 pub struct InMemoryDB {
     data: InMemoryStoreInternal,
+    merge_operators: Arc<RwLock<HashMap<String, MergeOperator>>>,
 }
 
 #[derive(Clone, Debug)]
 enum InMemoryChange {
     Delete((String, Vec<u8>)),
+    DeleteRange((String, Vec<u8>, Vec<u8>)),
+    Merge((String, Vec<u8>, Vec<u8>)),
     Put((String, Vec<u8>, Vec<u8>)),
 }
 
+/// The changes a batch makes to a single column family, before they are applied: the final state
+/// of each key it writes to (`None` if the key is deleted), and the ranges it deletes.
+#[derive(Default)]
+struct StagedColumnFamily {
+    ranges: Vec<(Vec<u8>, Vec<u8>)>,
+    keys: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl StagedColumnFamily {
+    /// The value of `key` as of the changes staged so far, on top of `base`.
+    fn get<'a>(&'a self, base: Option<&'a InMemoryColumnFamily>, key: &[u8]) -> Option<&'a [u8]> {
+        if let Some(value) = self.keys.get(key) {
+            return value.as_deref();
+        }
+
+        if self
+            .ranges
+            .iter()
+            .any(|(from, to)| from.as_slice() <= key && key < to.as_slice())
+        {
+            return None;
+        }
+
+        base.and_then(|cf| cf.get(key)).map(|v| v.as_slice())
+    }
+
+    fn delete_range(&mut self, from: Vec<u8>, to: Vec<u8>) {
+        if from >= to {
+            return;
+        }
+
+        for value in self
+            .keys
+            .range_mut(from.clone()..to.clone())
+            .map(|(_, v)| v)
+        {
+            *value = None;
+        }
+
+        self.ranges.push((from, to));
+    }
+
+    /// Apply the staged changes to `cf`. Ranges are deleted first, because every write the batch
+    /// made after deleting a range is reflected in `keys`.
+    fn apply(self, cf: &mut InMemoryColumnFamily) {
+        for (from, to) in self.ranges {
+            let mut deleted = cf.split_off(&from);
+            let mut rest = deleted.split_off(&to);
+            cf.append(&mut rest);
+        }
+
+        for (key, value) in self.keys {
+            match value {
+                Some(value) => cf.insert(key, value),
+                None => cf.remove(&key),
+            };
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct InMemoryBatch {
     data: Vec<InMemoryChange>,
+    size_in_bytes: usize,
 }
 
 impl InMemoryBatch {
     pub fn delete_cf<K: AsRef<[u8]>>(&mut self, cf_name: &str, key: K) {
+        self.size_in_bytes += key.as_ref().len();
         self.data.push(InMemoryChange::Delete((
             cf_name.to_string(),
             key.as_ref().to_vec(),
         )));
     }
 
+    /// Deletes all keys in the range `[from, to)`.
+    pub fn delete_range_cf<K: AsRef<[u8]>>(&mut self, cf_name: &str, from: K, to: K) {
+        self.size_in_bytes += from.as_ref().len() + to.as_ref().len();
+        self.data.push(InMemoryChange::DeleteRange((
+            cf_name.to_string(),
+            from.as_ref().to_vec(),
+            to.as_ref().to_vec(),
+        )));
+    }
+
+    pub fn merge_cf<K, V>(&mut self, cf_name: &str, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.size_in_bytes += key.as_ref().len() + value.as_ref().len();
+        self.data.push(InMemoryChange::Merge((
+            cf_name.to_string(),
+            key.as_ref().to_vec(),
+            value.as_ref().to_vec(),
+        )));
+    }
+
     pub fn put_cf<K, V>(&mut self, cf_name: &str, key: K, value: V)
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
     {
+        self.size_in_bytes += key.as_ref().len() + value.as_ref().len();
         self.data.push(InMemoryChange::Put((
             cf_name.to_string(),
             key.as_ref().to_vec(),
             value.as_ref().to_vec(),
         )));
     }
+
+    pub fn size_in_bytes(&self) -> usize {
+        self.size_in_bytes
+    }
 }
 
 impl InMemoryDB {
+    /// Creates an empty database with the given column families.
+    pub fn new<I, N>(cf_names: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+        N: AsRef<str>,
+    {
+        let db = Self::default();
+        for name in cf_names {
+            db.create_cf(name.as_ref());
+        }
+        db
+    }
+
+    /// Registers the merge operator used to resolve merges into `cf_name`, the equivalent of
+    /// setting a merge operator on a RocksDB column family's options.
+    pub fn set_merge_operator(&self, cf_name: &str, name: &str, merge: InMemoryMergeFn) {
+        self.merge_operators
+            .write()
+            .expect("can't write merge operators")
+            .insert(
+                cf_name.to_string(),
+                MergeOperator {
+                    name: name.to_string(),
+                    merge,
+                },
+            );
+    }
+
     pub fn get<K: AsRef<[u8]>>(&self, cf_name: &str, key: K) -> Option<Vec<u8>> {
         let data = self.data.read().expect("can't read data");
         match data.get(cf_name) {
@@ -61,13 +205,10 @@ impl InMemoryDB {
         K: AsRef<[u8]>,
     {
         let data = self.data.read().expect("can't read data");
-        match data.get(cf_name) {
-            Some(cf) => keys
-                .into_iter()
-                .map(|k| cf.get(k.as_ref()).cloned())
-                .collect(),
-            None => vec![],
-        }
+        let cf = data.get(cf_name);
+        keys.into_iter()
+            .map(|k| cf.and_then(|cf| cf.get(k.as_ref()).cloned()))
+            .collect()
     }
 
     pub fn delete(&self, cf_name: &str, key: &[u8]) {
@@ -82,19 +223,95 @@ impl InMemoryDB {
             .insert(key, value);
     }
 
-    pub fn write(&self, batch: InMemoryBatch) {
+    /// Applies all the changes in `batch` atomically: concurrent readers observe either none or
+    /// all of its changes, across all the column families it touches.
+    pub fn write(&self, batch: InMemoryBatch) -> Result<(), TypedStoreError> {
+        let merge_operators = self
+            .merge_operators
+            .read()
+            .expect("can't read merge operators");
+
+        // Stage the batch's changes to each column family first, so that a failed merge leaves
+        // the database untouched. Only the keys and ranges that the batch touches are staged.
+        let mut data = self.data.write().expect("can't write data");
+        let mut staged: HashMap<String, StagedColumnFamily> = HashMap::new();
         for change in batch.data {
+            let cf_name = match &change {
+                InMemoryChange::Delete((cf_name, _))
+                | InMemoryChange::DeleteRange((cf_name, _, _))
+                | InMemoryChange::Merge((cf_name, _, _))
+                | InMemoryChange::Put((cf_name, _, _)) => cf_name.clone(),
+            };
+
+            let cf = staged.entry(cf_name.clone()).or_default();
+
             match change {
-                InMemoryChange::Delete((cf_name, key)) => self.delete(&cf_name, &key),
-                InMemoryChange::Put((cf_name, key, value)) => self.put(&cf_name, key, value),
+                InMemoryChange::Delete((_, key)) => {
+                    cf.keys.insert(key, None);
+                }
+                InMemoryChange::DeleteRange((_, from, to)) => {
+                    cf.delete_range(from, to);
+                }
+                InMemoryChange::Merge((_, key, operand)) => {
+                    let Some(operator) = merge_operators.get(&cf_name) else {
+                        return Err(TypedStoreError::RocksDBError(format!(
+                            "No merge operator configured for column family {cf_name}"
+                        )));
+                    };
+
+                    let existing = cf.get(data.get(&cf_name), &key);
+                    let Some(merged) = (operator.merge)(&key, existing, &[operand]) else {
+                        return Err(TypedStoreError::RocksDBError(format!(
+                            "Merge operator {} failed for column family {cf_name}",
+                            operator.name,
+                        )));
+                    };
+
+                    cf.keys.insert(key, Some(merged));
+                }
+                InMemoryChange::Put((_, key, value)) => {
+                    cf.keys.insert(key, Some(value));
+                }
             }
         }
+
+        for (cf_name, changes) in staged {
+            changes.apply(data.entry(cf_name).or_default());
+        }
+
+        Ok(())
+    }
+
+    pub fn create_cf(&self, name: &str) {
+        self.data
+            .write()
+            .expect("can't write data")
+            .entry(name.to_string())
+            .or_default();
     }
 
     pub fn drop_cf(&self, name: &str) {
         self.data.write().expect("can't write data").remove(name);
     }
 
+    pub fn cf_names(&self) -> Vec<String> {
+        self.data
+            .read()
+            .expect("can't read data")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// A consistent copy of the contents of every column family.
+    pub fn snapshot(&self) -> HashMap<String, BTreeMap<Vec<u8>, Vec<u8>>> {
+        self.data.read().expect("can't read data").clone()
+    }
+
+    /// Iterate over the key-value pairs in `cf_name` whose keys are in `[lower_bound,
+    /// upper_bound)`, matching the semantics of RocksDB's iterate bounds. The iterator reads from
+    /// a snapshot of the column family taken when it is created, so it is not affected by
+    /// concurrent writes.
     pub fn iterator<K, V>(
         &self,
         cf_name: &str,
@@ -109,8 +326,16 @@ impl InMemoryDB {
         let config = bincode::DefaultOptions::new()
             .with_big_endian()
             .with_fixint_encoding();
+
+        // An empty range is not an error, but `BTreeMap::range` panics on it.
+        if let (Some(lo), Some(hi)) = (&lower_bound, &upper_bound) {
+            if lo >= hi {
+                return Box::new(std::iter::empty());
+            }
+        }
+
         let lower_bound = lower_bound.map(Bound::Included).unwrap_or(Bound::Unbounded);
-        let upper_bound = upper_bound.map(Bound::Included).unwrap_or(Bound::Unbounded);
+        let upper_bound = upper_bound.map(Bound::Excluded).unwrap_or(Bound::Unbounded);
 
         let data = self.data.read().expect("can't read data");
         let mut section: Vec<_> = data
@@ -123,8 +348,11 @@ impl InMemoryDB {
             section.reverse();
         }
         Box::new(section.into_iter().map(move |(raw_key, raw_value)| {
-            let key = config.deserialize(&raw_key).unwrap();
-            let value = bcs::from_bytes(&raw_value).unwrap();
+            let key = config
+                .deserialize(&raw_key)
+                .map_err(|e| TypedStoreError::SerializationError(e.to_string()))?;
+            let value = bcs::from_bytes(&raw_value)
+                .map_err(|e| TypedStoreError::SerializationError(e.to_string()))?;
             Ok((key, value))
         }))
     }
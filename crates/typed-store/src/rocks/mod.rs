@@ -131,6 +131,10 @@ impl std::fmt::Debug for Storage {
 pub struct Database {
     storage: Storage,
     metric_conf: MetricConf,
+    /// The key space of each table, by name, for TideHunter databases (which have no named column
+    /// families).
+    #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+    key_spaces: std::collections::HashMap<String, KeySpace>,
 }
 
 impl Drop for Database {
@@ -164,6 +168,8 @@ impl Database {
         Self {
             storage,
             metric_conf,
+            #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+            key_spaces: Default::default(),
         }
     }
 
@@ -245,7 +251,10 @@ impl Database {
     ) -> Result<(), rocksdb::Error> {
         match &self.storage {
             Storage::Rocks(db) => db.underlying.create_cf(name, opts),
-            Storage::InMemory(_) => Ok(()),
+            Storage::InMemory(db) => {
+                db.create_cf(name.as_ref());
+                Ok(())
+            }
             #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
             Storage::TideHunter(_) => {
                 unimplemented!("TideHunter: recreation of column family on a fly not implemented")
@@ -364,10 +373,7 @@ impl Database {
                 .underlying
                 .write(batch)
                 .map_err(typed_store_err_from_rocks_err),
-            (Storage::InMemory(db), StorageWriteBatch::InMemory(batch)) => {
-                db.write(batch);
-                Ok(())
-            }
+            (Storage::InMemory(db), StorageWriteBatch::InMemory(batch)) => db.write(batch),
             #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
             (Storage::TideHunter(db), StorageWriteBatch::TideHunter(batch)) => db
                 .write_batch(batch)
@@ -409,13 +415,45 @@ impl Database {
     }
 
     pub fn checkpoint(&self, path: &Path) -> Result<(), TypedStoreError> {
-        // TODO: implement for other storage types
-        if let Storage::Rocks(rocks) = &self.storage {
-            let checkpoint =
-                Checkpoint::new(&rocks.underlying).map_err(typed_store_err_from_rocks_err)?;
-            checkpoint
-                .create_checkpoint(path)
-                .map_err(|e| TypedStoreError::RocksDBError(e.to_string()))?;
+        match &self.storage {
+            Storage::Rocks(rocks) => {
+                let checkpoint =
+                    Checkpoint::new(&rocks.underlying).map_err(typed_store_err_from_rocks_err)?;
+                checkpoint
+                    .create_checkpoint(path)
+                    .map_err(|e| TypedStoreError::RocksDBError(e.to_string()))?;
+            }
+            // An in-memory database is checkpointed into a RocksDB database with the same column
+            // families, so that checkpoints can be opened the same way regardless of the backend.
+            Storage::InMemory(db) => {
+                let snapshot = db.snapshot();
+                let opt_cfs: Vec<_> = snapshot
+                    .keys()
+                    .map(|name| (name.as_str(), rocksdb::Options::default()))
+                    .collect();
+                let checkpoint = open_cf_opts(path, None, MetricConf::default(), &opt_cfs)?;
+                let Storage::Rocks(rocks) = &checkpoint.storage else {
+                    unreachable!("open_cf_opts always opens a RocksDB database");
+                };
+                let mut batch = WriteBatch::default();
+                for (cf_name, cf) in &snapshot {
+                    let handle = rocks_cf(rocks, cf_name);
+                    for (key, value) in cf {
+                        batch.put_cf(&handle, key, value);
+                    }
+                }
+                rocks
+                    .underlying
+                    .write(batch)
+                    .map_err(typed_store_err_from_rocks_err)?;
+                rocks
+                    .underlying
+                    .flush()
+                    .map_err(typed_store_err_from_rocks_err)?;
+            }
+            // TODO: implement for TideHunter
+            #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+            Storage::TideHunter(_) => {}
         }
         Ok(())
     }
@@ -508,6 +546,9 @@ impl MetricConf {
 const CF_METRICS_REPORT_PERIOD_SECS: u64 = 30;
 const METRICS_ERROR: i64 = -1;
 
+/// A key-value pair converted to JSON, see `DBMap::safe_json_iter`.
+pub type JsonEntry = (serde_json::Value, serde_json::Value);
+
 /// An interface to a rocksDB database, keyed by a columnfamily
 #[derive(Clone, Debug)]
 pub struct DBMap<K, V> {
@@ -591,11 +632,21 @@ impl<K, V> DBMap<K, V> {
         let cf_key = opt_cf
             .unwrap_or(rocksdb::DEFAULT_COLUMN_FAMILY_NAME)
             .to_owned();
+        let column_family = match &db.storage {
+            Storage::InMemory(_) => ColumnFamily::InMemory(cf_key.to_string()),
+            #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+            Storage::TideHunter(_) => ColumnFamily::TideHunter(
+                *db.key_spaces
+                    .get(&cf_key)
+                    .ok_or_else(|| TypedStoreError::UnregisteredColumn(cf_key.clone()))?,
+            ),
+            _ => ColumnFamily::Rocks(cf_key.to_string()),
+        };
         Ok(DBMap::new(
             db.clone(),
             rw_options,
             &cf_key,
-            ColumnFamily::Rocks(cf_key.to_string()),
+            column_family,
             is_deprecated,
         ))
     }
@@ -957,6 +1008,25 @@ impl<K, V> DBMap<K, V> {
         })
     }
 
+    /// Iterates over all entries with their keys and values converted to JSON, for tools that
+    /// inspect tables generically.
+    pub fn safe_json_iter(&self) -> DbIterator<'_, JsonEntry>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        Box::new(self.safe_iter().map(|item| {
+            let (key, value) = item?;
+            let to_json = |v: serde_json::Result<serde_json::Value>| {
+                v.map_err(|e| TypedStoreError::SerializationError(e.to_string()))
+            };
+            Ok((
+                to_json(serde_json::to_value(&key))?,
+                to_json(serde_json::to_value(&value))?,
+            ))
+        }))
+    }
+
     // Creates metrics and context for tracking an iterator usage and performance.
     fn create_iter_context(
         &self,
@@ -1189,7 +1259,7 @@ impl DBBatch {
     pub fn size_in_bytes(&self) -> usize {
         match self.batch {
             StorageWriteBatch::Rocks(ref b) => b.size_in_bytes(),
-            StorageWriteBatch::InMemory(_) => 0,
+            StorageWriteBatch::InMemory(ref b) => b.size_in_bytes(),
             // TODO: implement size_in_bytes method
             #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
             StorageWriteBatch::TideHunter(_) => 0,
@@ -1251,12 +1321,15 @@ impl DBBatch {
         let from_buf = be_fix_int_ser(from);
         let to_buf = be_fix_int_ser(to);
 
-        if let StorageWriteBatch::Rocks(b) = &mut self.batch {
-            b.delete_range_cf(
+        match &mut self.batch {
+            StorageWriteBatch::Rocks(b) => b.delete_range_cf(
                 &rocks_cf_from_db(&self.database, db.cf_name())?,
                 from_buf,
                 to_buf,
-            );
+            ),
+            StorageWriteBatch::InMemory(b) => b.delete_range_cf(db.cf_name(), from_buf, to_buf),
+            #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+            StorageWriteBatch::TideHunter(_) => {}
         }
         Ok(())
     }
@@ -1318,7 +1391,9 @@ impl DBBatch {
                     StorageWriteBatch::Rocks(b) => {
                         b.merge_cf(&rocks_cf_from_db(&self.database, db.cf_name())?, k_buf, v)
                     }
-                    _ => unimplemented!("merge operator is only implemented for RocksDB"),
+                    StorageWriteBatch::InMemory(b) => b.merge_cf(db.cf_name(), k_buf, v),
+                    #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+                    _ => unimplemented!("merge operator is not implemented for TideHunter"),
                 }
                 Ok(())
             })?;
@@ -1496,6 +1571,8 @@ where
         if let Some((first_key, last_key)) = first_key.zip(last_key) {
             let mut batch = self.batch();
             batch.schedule_delete_range(self, &first_key, &last_key)?;
+            // The range deletion excludes its upper bound, so the last key is deleted separately.
+            batch.delete_batch(self, [last_key])?;
             batch.write()?;
         }
         Ok(())
@@ -1768,6 +1845,57 @@ pub fn open_cf_opts_secondary<P: AsRef<Path>>(
     })
 }
 
+/// Opens a database backed by `db`, which lives purely in memory. Column families and merge
+/// operators are configured on `db` before it is opened.
+pub fn open_in_memory(db: InMemoryDB, metric_conf: MetricConf) -> Arc<Database> {
+    Arc::new(Database::new(Storage::InMemory(db), metric_conf))
+}
+
+/// Wraps a TideHunter database. Tables are opened on the key space registered under their name in
+/// `key_spaces`.
+#[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+pub fn open_tidehunter(
+    db: Arc<TideHunterDb>,
+    key_spaces: std::collections::HashMap<String, KeySpace>,
+    metric_conf: MetricConf,
+) -> Arc<Database> {
+    let mut database = Database::new(Storage::TideHunter(db), metric_conf);
+    database.key_spaces = key_spaces;
+    Arc::new(database)
+}
+
+/// Opens (or creates) a TideHunter database at `path`, with one key space per table, for keys of
+/// the given size.
+#[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+pub fn open_tidehunter_with_key_sizes(
+    path: &Path,
+    key_sizes: &std::collections::BTreeMap<String, usize>,
+    metric_conf: MetricConf,
+) -> Result<Arc<Database>, TypedStoreError> {
+    use tidehunter::{config::Config, key_shape::KeyShapeBuilder, metrics::Metrics};
+
+    const TH_MUTEXES: usize = 16;
+    const TH_ENTRIES_PER_MUTEX: usize = 16;
+
+    let mut builder = KeyShapeBuilder::new();
+    let key_spaces = key_sizes
+        .iter()
+        .map(|(name, key_size)| {
+            let ks = builder.add_key_space(name, *key_size, TH_MUTEXES, TH_ENTRIES_PER_MUTEX);
+            (name.clone(), ks)
+        })
+        .collect();
+
+    let db = TideHunterDb::open(
+        path,
+        builder.build(),
+        Arc::new(Config::default()),
+        Metrics::new(),
+    )
+    .map_err(typed_store_error_from_th_error)?;
+    Ok(open_tidehunter(db, key_spaces, metric_conf))
+}
+
 // Drops a database if there is no other handle to it, with retries and timeout.
 pub async fn safe_drop_db(path: PathBuf, timeout: Duration) -> Result<(), rocksdb::Error> {
     let mut backoff = backoff::ExponentialBackoff {
@@ -103,9 +103,16 @@ async fn test_reopen_macro() {
     assert!(db_map_2.multi_insert(keys_vals_cf2).is_ok());
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_contains_key() {
-    let db = open_map(temp_dir(), None);
+async fn test_contains_key(#[case] backend: Backend) {
+    let db = backend.open_map(None);
 
     db.insert(&123456789, &"123456789".to_string())
         .expect("Failed to insert");
@@ -131,9 +138,16 @@ async fn test_safe_drop_db() {
         .unwrap();
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_multi_contain() {
-    let db = open_map(temp_dir(), None);
+async fn test_multi_contain(#[case] backend: Backend) {
+    let db = backend.open_map(None);
 
     db.insert(&123, &"123".to_string())
         .expect("Failed to insert");
@@ -160,9 +174,16 @@ async fn test_multi_contain() {
     assert!(result[2]);
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_get() {
-    let db = open_map(temp_dir(), None);
+async fn test_get(#[case] backend: Backend) {
+    let db = backend.open_map(None);
 
     db.insert(&123456789, &"123456789".to_string())
         .expect("Failed to insert");
@@ -173,9 +194,16 @@ async fn test_get() {
     assert_eq!(None, db.get(&000000000).expect("Failed to get"));
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_multi_get() {
-    let db = open_map(temp_dir(), None);
+async fn test_multi_get(#[case] backend: Backend) {
+    let db = backend.open_map(None);
 
     db.insert(&123, &"123".to_string())
         .expect("Failed to insert");
@@ -190,9 +218,16 @@ async fn test_multi_get() {
     assert_eq!(result[2], None);
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_skip() {
-    let db = open_map(temp_dir(), None);
+async fn test_skip(#[case] backend: Backend) {
+    let db = backend.open_map(None);
 
     db.insert(&123, &"123".to_string())
         .expect("Failed to insert");
@@ -221,9 +256,16 @@ async fn test_skip() {
     assert_eq!(get_iter_with_bounds(&db, Some(000), None).count(), 3);
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_reverse_iter_with_bounds() {
-    let db = open_map(temp_dir(), None);
+async fn test_reverse_iter_with_bounds(#[case] backend: Backend) {
+    let db = backend.open_map(None);
     db.insert(&123, &"123".to_string())
         .expect("Failed to insert");
     db.insert(&456, &"456".to_string())
@@ -243,9 +285,16 @@ async fn test_reverse_iter_with_bounds() {
     assert_eq!(iter.next().unwrap(), Ok((999, "999".to_string())));
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_remove() {
-    let db = open_map(temp_dir(), None);
+async fn test_remove(#[case] backend: Backend) {
+    let db = backend.open_map(None);
 
     db.insert(&123456789, &"123456789".to_string())
         .expect("Failed to insert");
@@ -255,9 +304,16 @@ async fn test_remove() {
     assert!(db.get(&123456789).expect("Failed to get").is_none());
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_iter() {
-    let db = open_map(temp_dir(), None);
+async fn test_iter(#[case] backend: Backend) {
+    let db = backend.open_map(None);
     db.insert(&123456789, &"123456789".to_string())
         .expect("Failed to insert");
     db.insert(&987654321, &"987654321".to_string())
@@ -270,9 +326,16 @@ async fn test_iter() {
     assert_eq!(None, iter.next());
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_iter_reverse() {
-    let db = open_map(temp_dir(), None);
+async fn test_iter_reverse(#[case] backend: Backend) {
+    let db = backend.open_map(None);
 
     db.insert(&1, &"1".to_string()).expect("Failed to insert");
     db.insert(&2, &"2".to_string()).expect("Failed to insert");
@@ -289,9 +352,16 @@ async fn test_iter_reverse() {
     assert_eq!(Some((2, "2".to_string())), iter.next());
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_insert_batch() {
-    let db = open_map(temp_dir(), None);
+async fn test_insert_batch(#[case] backend: Backend) {
+    let db = backend.open_map(None);
     let keys_vals = (1..100).map(|i| (i, i.to_string()));
     let mut insert_batch = db.batch();
     insert_batch
@@ -304,9 +374,12 @@ async fn test_insert_batch() {
     }
 }
 
+#[rstest]
 #[tokio::test]
-async fn test_insert_batch_across_cf() {
-    let rocks = open_rocksdb(temp_dir(), &["First_CF", "Second_CF"]);
+async fn test_insert_batch_across_cf(
+    #[values(Backend::RocksDB, Backend::InMemory)] backend: Backend,
+) {
+    let rocks = backend.open(&["First_CF", "Second_CF"]);
 
     let db_cf_1 = DBMap::reopen(
         &rocks,
@@ -345,10 +418,13 @@ async fn test_insert_batch_across_cf() {
     }
 }
 
+#[rstest]
 #[tokio::test]
-async fn test_insert_batch_across_different_db() {
-    let rocks = open_rocksdb(temp_dir(), &["First_CF", "Second_CF"]);
-    let rocks2 = open_rocksdb(temp_dir(), &["First_CF", "Second_CF"]);
+async fn test_insert_batch_across_different_db(
+    #[values(Backend::RocksDB, Backend::InMemory)] backend: Backend,
+) {
+    let rocks = backend.open(&["First_CF", "Second_CF"]);
+    let rocks2 = backend.open(&["First_CF", "Second_CF"]);
 
     let db_cf_1: DBMap<i32, String> = DBMap::reopen(
         &rocks,
@@ -376,9 +452,16 @@ async fn test_insert_batch_across_different_db() {
         .is_err());
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_delete_batch() {
-    let db = open_map::<_, u32, String>(temp_dir(), None);
+async fn test_delete_batch(#[case] backend: Backend) {
+    let db = backend.open_map::<u32, String>(None);
 
     let keys_vals = (1..100).map(|i| (i, i.to_string()));
     let mut batch = db.batch();
@@ -399,11 +482,12 @@ async fn test_delete_batch() {
     }
 }
 
+#[rstest]
 #[tokio::test]
-async fn test_delete_range() {
+async fn test_delete_range(#[values(Backend::RocksDB, Backend::InMemory)] backend: Backend) {
     let options = ReadWriteOptions::default().set_ignore_range_deletions(false);
     let db: DBMap<i32, String> = DBMap::reopen(
-        &open_rocksdb(temp_dir(), &[rocksdb::DEFAULT_COLUMN_FAMILY_NAME]),
+        &backend.open(&[rocksdb::DEFAULT_COLUMN_FAMILY_NAME]),
         None,
         &options,
         false,
@@ -434,9 +518,58 @@ async fn test_delete_range() {
     assert!(db.contains_key(&100).expect("Failed to query legal key"));
 }
 
+#[rstest]
 #[tokio::test]
-async fn test_clear() {
-    let db: DBMap<i32, String> = open_map(temp_dir(), Some("table"));
+async fn test_batch_applies_changes_in_order(
+    #[values(Backend::RocksDB, Backend::InMemory)] backend: Backend,
+) {
+    let options = ReadWriteOptions::default().set_ignore_range_deletions(false);
+    let db: DBMap<i32, String> = DBMap::reopen(
+        &backend.open(&[rocksdb::DEFAULT_COLUMN_FAMILY_NAME]),
+        None,
+        &options,
+        false,
+    )
+    .unwrap();
+
+    db.multi_insert((0..10).map(|i| (i, i.to_string())))
+        .expect("Failed to multi-insert");
+
+    // Writes before a range delete in the same batch are deleted along with it, and writes after
+    // it are kept.
+    let mut batch = db.batch();
+    batch
+        .insert_batch(&db, [(3, "three".to_string()), (20, "20".to_string())])
+        .expect("Failed to batch insert");
+    batch
+        .schedule_delete_range(&db, &2, &6)
+        .expect("Failed to delete range");
+    batch
+        .insert_batch(&db, [(4, "four".to_string())])
+        .expect("Failed to batch insert");
+    batch
+        .delete_batch(&db, [8])
+        .expect("Failed to batch delete");
+    batch.write().expect("Failed to execute batch");
+
+    assert_eq!(
+        get_iter(&db).collect::<Vec<_>>(),
+        vec![
+            (0, "0".to_string()),
+            (1, "1".to_string()),
+            (4, "four".to_string()),
+            (6, "6".to_string()),
+            (7, "7".to_string()),
+            (9, "9".to_string()),
+            (20, "20".to_string()),
+        ]
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_clear(#[values(Backend::RocksDB, Backend::InMemory)] backend: Backend) {
+    let db: DBMap<i32, String> = backend.open_map(Some("table"));
     // Test clear of empty map
     let _ = db.unsafe_clear();
 
@@ -462,9 +595,16 @@ async fn test_clear() {
     assert_eq!(db.safe_iter().count(), 0);
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_iter_with_bounds() {
-    let db = open_map(temp_dir(), None);
+async fn test_iter_with_bounds(#[case] backend: Backend) {
+    let db = backend.open_map(None);
 
     // Add [1, 50) and (50, 100) in the db
     for i in 1..100 {
@@ -526,9 +666,15 @@ async fn test_iter_with_bounds() {
 }
 
 #[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_range_iter() {
-    let db = open_map(temp_dir(), None);
+async fn test_range_iter(#[case] backend: Backend) {
+    let db = backend.open_map(None);
 
     // Add [1, 50) and (50, 100) in the db
     for i in 1..100 {
@@ -566,9 +712,10 @@ async fn test_range_iter() {
     );
 }
 
+#[rstest]
 #[tokio::test]
-async fn test_is_empty() {
-    let db: DBMap<i32, String> = open_map(temp_dir(), Some("table"));
+async fn test_is_empty(#[values(Backend::RocksDB, Backend::InMemory)] backend: Backend) {
+    let db: DBMap<i32, String> = backend.open_map(Some("table"));
 
     // Test empty map is truly empty
     assert!(db.is_empty());
@@ -593,10 +740,17 @@ async fn test_is_empty() {
     assert!(db.is_empty());
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_multi_insert() {
+async fn test_multi_insert(#[case] backend: Backend) {
     // Init a DB
-    let db: DBMap<i32, String> = open_map(temp_dir(), Some("table"));
+    let db: DBMap<i32, String> = backend.open_map(Some("table"));
     // Create kv pairs
     let keys_vals = (0..101).map(|i| (i, i.to_string()));
 
@@ -609,11 +763,11 @@ async fn test_multi_insert() {
     }
 }
 
+#[rstest]
 #[tokio::test]
-async fn test_checkpoint() {
+async fn test_checkpoint(#[values(Backend::RocksDB, Backend::InMemory)] backend: Backend) {
     let path_prefix = temp_dir();
-    let db_path = path_prefix.join("db");
-    let db: DBMap<i32, String> = open_map(db_path, Some("table"));
+    let db: DBMap<i32, String> = backend.open_map(Some("table"));
     // Create kv pairs
     let keys_vals = (0..101).map(|i| (i, i.to_string()));
 
@@ -645,10 +799,17 @@ async fn test_checkpoint() {
     }
 }
 
+#[rstest]
+#[case::rocksdb(Backend::RocksDB)]
+#[case::in_memory(Backend::InMemory)]
+#[cfg_attr(
+    all(not(target_os = "windows"), feature = "tide_hunter"),
+    case::tide_hunter(Backend::TideHunter)
+)]
 #[tokio::test]
-async fn test_multi_remove() {
+async fn test_multi_remove(#[case] backend: Backend) {
     // Init a DB
-    let db: DBMap<i32, String> = open_map(temp_dir(), Some("table"));
+    let db: DBMap<i32, String> = backend.open_map(Some("table"));
 
     // Create kv pairs
     let keys_vals = (0..101).map(|i| (i, i.to_string()));
@@ -674,6 +835,185 @@ async fn test_multi_remove() {
     }
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_iter_reads_snapshot(#[values(Backend::RocksDB, Backend::InMemory)] backend: Backend) {
+    let db: DBMap<i32, String> = backend.open_map(None);
+    db.multi_insert((0..10).map(|i| (i, i.to_string())))
+        .expect("Failed to multi-insert");
+
+    let iter = db.safe_iter();
+    let rev_iter = get_reverse_iter(&db, None, None);
+
+    // Writes after the iterators are created are not visible through them.
+    db.multi_insert((10..20).map(|i| (i, i.to_string())))
+        .expect("Failed to multi-insert");
+    db.remove(&0).expect("Failed to remove");
+
+    assert_eq!(
+        (0..10).map(|i| (i, i.to_string())).collect::<Vec<_>>(),
+        iter.map(|item| item.unwrap()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        (0..10)
+            .rev()
+            .map(|i| (i, i.to_string()))
+            .collect::<Vec<_>>(),
+        rev_iter.map(|item| item.unwrap()).collect::<Vec<_>>()
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_reverse_iter_bounds_are_inclusive(
+    #[values(Backend::RocksDB, Backend::InMemory)] backend: Backend,
+) {
+    let db: DBMap<i32, String> = backend.open_map(None);
+    db.multi_insert((1..100).map(|i| (i, i.to_string())))
+        .expect("Failed to multi-insert");
+
+    let items: Vec<_> = get_reverse_iter(&db, Some(20), Some(30))
+        .map(|item| item.unwrap())
+        .collect();
+    assert_eq!(
+        (20..=30)
+            .rev()
+            .map(|i| (i, i.to_string()))
+            .collect::<Vec<_>>(),
+        items
+    );
+
+    // Bounds outside of the dataset.
+    assert_eq!(get_reverse_iter(&db, Some(200), Some(300)).count(), 0);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_schedule_delete_all(#[values(Backend::RocksDB, Backend::InMemory)] backend: Backend) {
+    let options = ReadWriteOptions::default().set_ignore_range_deletions(false);
+    let db: DBMap<i32, String> = DBMap::reopen(
+        &backend.open(&[rocksdb::DEFAULT_COLUMN_FAMILY_NAME]),
+        None,
+        &options,
+        false,
+    )
+    .unwrap();
+
+    // Deleting from an empty map is a no-op.
+    db.schedule_delete_all().expect("Failed to delete all");
+    assert!(db.is_empty());
+
+    db.multi_insert((0..101).map(|i| (i, i.to_string())))
+        .expect("Failed to multi-insert");
+    db.schedule_delete_all().expect("Failed to delete all");
+
+    assert!(db.is_empty());
+    assert!(!db.contains_key(&100).expect("Failed to query legal key"));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_batch_across_cf_is_atomic(
+    #[values(Backend::RocksDB, Backend::InMemory)] backend: Backend,
+) {
+    let db = backend.open(&["First_CF", "Second_CF"]);
+    let db_cf_1: DBMap<i32, String> =
+        DBMap::reopen(&db, Some("First_CF"), &ReadWriteOptions::default(), false).unwrap();
+    let db_cf_2: DBMap<i32, String> =
+        DBMap::reopen(&db, Some("Second_CF"), &ReadWriteOptions::default(), false).unwrap();
+
+    db_cf_1.insert(&0, &"0".to_string()).unwrap();
+
+    let mut batch = db_cf_1.batch();
+    batch
+        .insert_batch(&db_cf_1, (1..10).map(|i| (i, i.to_string())))
+        .expect("Failed to batch insert")
+        .insert_batch(&db_cf_2, (1..10).map(|i| (i, i.to_string())))
+        .expect("Failed to batch insert");
+    batch
+        .delete_batch(&db_cf_1, [0])
+        .expect("Failed to batch delete");
+    assert!(batch.size_in_bytes() > 0);
+
+    // Nothing is visible until the batch is written.
+    assert!(db_cf_1.contains_key(&0).unwrap());
+    assert!(db_cf_2.is_empty());
+
+    batch.write().expect("Failed to execute batch");
+
+    assert!(!db_cf_1.contains_key(&0).unwrap());
+    assert_eq!(db_cf_1.safe_iter().count(), 9);
+    assert_eq!(db_cf_2.safe_iter().count(), 9);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_partial_merge(#[values(Backend::RocksDB, Backend::InMemory)] backend: Backend) {
+    const CF: &str = "counters";
+
+    // Values are BCS-encoded u64s (little-endian), and merging adds them together.
+    fn add(existing: Option<&[u8]>, operands: impl Iterator<Item = Vec<u8>>) -> Option<Vec<u8>> {
+        let mut total = existing.map_or(0, |v| u64::from_le_bytes(v.try_into().unwrap()));
+        for operand in operands {
+            total += u64::from_le_bytes(operand.as_slice().try_into().ok()?);
+        }
+        Some(total.to_le_bytes().to_vec())
+    }
+
+    let database = match backend {
+        Backend::RocksDB => {
+            let mut opts = rocksdb::Options::default();
+            opts.set_merge_operator_associative("add", |_, existing, operands| {
+                add(existing, operands.iter().map(|o| o.to_vec()))
+            });
+            open_cf_opts(temp_dir(), None, MetricConf::default(), &[(CF, opts)]).unwrap()
+        }
+        Backend::InMemory => {
+            let db = InMemoryDB::new([CF]);
+            db.set_merge_operator(
+                CF,
+                "add",
+                Arc::new(|_: &[u8], existing: Option<&[u8]>, operands: &[Vec<u8>]| {
+                    add(existing, operands.iter().cloned())
+                }),
+            );
+            open_in_memory(db, MetricConf::default())
+        }
+    };
+
+    let db: DBMap<i32, u64> =
+        DBMap::reopen(&database, Some(CF), &ReadWriteOptions::default(), false).unwrap();
+    db.insert(&1, &10).unwrap();
+
+    let mut batch = db.batch();
+    batch
+        .partial_merge_batch(&db, [(1, 5u64.to_le_bytes()), (2, 7u64.to_le_bytes())])
+        .expect("Failed to batch merge")
+        .partial_merge_batch(&db, [(1, 1u64.to_le_bytes())])
+        .expect("Failed to batch merge");
+    batch.write().expect("Failed to execute batch");
+
+    assert_eq!(db.get(&1).unwrap(), Some(16));
+    assert_eq!(db.get(&2).unwrap(), Some(7));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_safe_json_iter(#[values(Backend::RocksDB, Backend::InMemory)] backend: Backend) {
+    let db: DBMap<(u64, String), Vec<u32>> = backend.open_map(None);
+    db.insert(&(1, "one".to_string()), &vec![1]).unwrap();
+    db.insert(&(2, "two".to_string()), &vec![2, 2]).unwrap();
+
+    let entries: Vec<_> = db.safe_json_iter().map(Result::unwrap).collect();
+    assert_eq!(
+        entries,
+        vec![
+            (serde_json::json!([1, "one"]), serde_json::json!([1])),
+            (serde_json::json!([2, "two"]), serde_json::json!([2, 2])),
+        ]
+    );
+}
+
 #[tokio::test]
 async fn open_as_secondary_test() {
     let primary_path = temp_dir();
@@ -723,6 +1063,48 @@ async fn open_as_secondary_test() {
     assert_eq!(secondary_db.get(&0).unwrap(), Some("10".to_string()));
 }
 
+/// The storage backends that the conformance tests are run against. Every backend must behave
+/// identically from the perspective of a `DBMap`.
+///
+/// TideHunter only supports fixed-size keys and does not implement every operation (e.g. range
+/// deletes and merges), so it is only run against the tests that use 4-byte keys and the
+/// operations it supports.
+#[derive(Clone, Copy, Debug)]
+enum Backend {
+    RocksDB,
+    InMemory,
+    #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+    TideHunter,
+}
+
+impl Backend {
+    fn open(self, opt_cfs: &[&str]) -> Arc<Database> {
+        match self {
+            Backend::RocksDB => open_rocksdb(temp_dir(), opt_cfs),
+            Backend::InMemory => open_in_memory(InMemoryDB::new(opt_cfs), MetricConf::default()),
+            #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+            Backend::TideHunter => unimplemented!("TideHunter databases are opened per key space"),
+        }
+    }
+
+    fn open_map<K, V>(self, opt_cf: Option<&str>) -> DBMap<K, V> {
+        let cf_key = opt_cf.unwrap_or(rocksdb::DEFAULT_COLUMN_FAMILY_NAME);
+
+        #[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+        if let Backend::TideHunter = self {
+            return open_tidehunter_map(cf_key);
+        }
+
+        DBMap::<K, V>::reopen(
+            &self.open(&[cf_key]),
+            opt_cf,
+            &ReadWriteOptions::default(),
+            false,
+        )
+        .expect("failed to open map")
+    }
+}
+
 fn open_map<P: AsRef<Path>, K, V>(path: P, opt_cf: Option<&str>) -> DBMap<K, V> {
     let cf_key = opt_cf.unwrap_or(rocksdb::DEFAULT_COLUMN_FAMILY_NAME);
     DBMap::<K, V>::reopen(
@@ -734,6 +1116,19 @@ fn open_map<P: AsRef<Path>, K, V>(path: P, opt_cf: Option<&str>) -> DBMap<K, V>
     .expect("failed to open rocksdb")
 }
 
+/// Opens a TideHunter database with a single key space, for 4-byte keys.
+#[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+fn open_tidehunter_map<K, V>(cf_name: &str) -> DBMap<K, V> {
+    let db = open_tidehunter_with_key_sizes(
+        &temp_dir(),
+        &std::collections::BTreeMap::from([(cf_name.to_owned(), 4)]),
+        MetricConf::default(),
+    )
+    .expect("failed to open tidehunter");
+    DBMap::reopen(&db, Some(cf_name), &ReadWriteOptions::default(), false)
+        .expect("failed to open map")
+}
+
 fn open_rocksdb<P: AsRef<Path>>(path: P, opt_cfs: &[&str]) -> Arc<Database> {
     let opts = rocksdb::Options::default();
     open_cf_opts(
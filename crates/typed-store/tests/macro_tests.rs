@@ -10,7 +10,10 @@ use std::collections::HashSet;
 use std::fmt::Debug;
 use std::sync::Mutex;
 use std::time::Duration;
+use typed_store::export::{TableExporter, TableImporter};
+use typed_store::memstore::InMemoryDB;
 use typed_store::metrics::SamplingInterval;
+use typed_store::rocks::open_in_memory;
 use typed_store::rocks::DBMap;
 use typed_store::rocks::MetricConf;
 use typed_store::traits::Map;
@@ -181,6 +184,190 @@ async fn rename_test() {
     }
 }
 
+/// Same table names as `Tables`, but with a different key type for `table2`
+#[derive(DBMapUtils)]
+struct MismatchedTables {
+    table1: DBMap<String, String>,
+    table2: DBMap<u64, String>,
+}
+
+#[tokio::test]
+async fn export_import_test() {
+    let source = Tables::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+    // Enough entries to span several chunks of the export
+    for i in 0..2500 {
+        source
+            .table1
+            .insert(&i.to_string(), &i.to_string())
+            .unwrap();
+        source.table2.insert(&i, &i.to_string()).unwrap();
+    }
+
+    let mut exporter = TableExporter::new(Vec::new()).unwrap();
+    source.export_tables(&mut exporter).unwrap();
+    let export = exporter.finish().unwrap();
+
+    let rocksdb = Tables::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+    let in_memory = Tables::open_tables_with_database(&open_in_memory(
+        InMemoryDB::default(),
+        MetricConf::default(),
+    ));
+    for target in [&rocksdb, &in_memory] {
+        let mut importer = TableImporter::new(export.as_slice()).unwrap();
+        target.import_tables(&mut importer).unwrap();
+
+        let entries =
+            |t: &DBMap<String, String>| -> Vec<_> { t.safe_iter().map(Result::unwrap).collect() };
+        assert_eq!(entries(&source.table1), entries(&target.table1));
+        assert_eq!(
+            source
+                .table2
+                .safe_iter()
+                .map(Result::unwrap)
+                .collect::<Vec<_>>(),
+            target
+                .table2
+                .safe_iter()
+                .map(Result::unwrap)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    // Tables that don't exist in the target, or that have different types, are rejected
+    let single =
+        TablesSingle::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+    let err = single
+        .import_tables(&mut TableImporter::new(export.as_slice()).unwrap())
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("No such table name: table2"),
+        "{err}"
+    );
+
+    let mismatched =
+        MismatchedTables::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+    let err = mismatched
+        .import_tables(&mut TableImporter::new(export.as_slice()).unwrap())
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("table table2 was exported as"),
+        "{err}"
+    );
+
+    // Keys of different sizes are detected when inferring key sizes
+    let err = TableImporter::new(export.as_slice())
+        .unwrap()
+        .key_sizes()
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("has keys of different sizes"),
+        "{err}"
+    );
+
+    // A truncated export is detected
+    let truncated = &export[..export.len() / 2];
+    let target = Tables::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+    assert!(target
+        .import_tables(&mut TableImporter::new(truncated).unwrap())
+        .is_err());
+}
+
+/// Tables with fixed-size keys, which can be stored in TideHunter
+#[derive(DBMapUtils)]
+struct FixedKeyTables {
+    table1: DBMap<u64, String>,
+    #[rename = "renamed_table"]
+    table2: DBMap<u32, u64>,
+    empty: DBMap<u64, u64>,
+}
+
+#[tokio::test]
+async fn export_key_sizes_test() {
+    let source =
+        FixedKeyTables::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+    for i in 0..2500 {
+        source.table1.insert(&i, &i.to_string()).unwrap();
+        source.table2.insert(&(i as u32), &i).unwrap();
+    }
+
+    let mut exporter = TableExporter::new(Vec::new()).unwrap();
+    source.export_tables(&mut exporter).unwrap();
+    let export = exporter.finish().unwrap();
+
+    // Tables are exported under their column family names
+    let key_sizes = TableImporter::new(export.as_slice())
+        .unwrap()
+        .key_sizes()
+        .unwrap();
+    assert_eq!(
+        key_sizes,
+        [
+            ("empty".to_owned(), None),
+            ("renamed_table".to_owned(), Some(4)),
+            ("table1".to_owned(), Some(8)),
+        ]
+        .into_iter()
+        .collect()
+    );
+}
+
+#[cfg(all(not(target_os = "windows"), feature = "tide_hunter"))]
+#[tokio::test]
+async fn export_import_tidehunter_test() {
+    use typed_store::rocks::open_tidehunter_with_key_sizes;
+
+    let source =
+        FixedKeyTables::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+    for i in 0..2500 {
+        source.table1.insert(&i, &i.to_string()).unwrap();
+        source.table2.insert(&(i as u32), &i).unwrap();
+    }
+
+    let mut exporter = TableExporter::new(Vec::new()).unwrap();
+    source.export_tables(&mut exporter).unwrap();
+    let export = exporter.finish().unwrap();
+
+    let key_sizes = TableImporter::new(export.as_slice())
+        .unwrap()
+        .key_sizes()
+        .unwrap()
+        .into_iter()
+        .map(|(name, size)| (name, size.unwrap_or(8)))
+        .collect();
+    let db =
+        open_tidehunter_with_key_sizes(&temp_dir(), &key_sizes, MetricConf::default()).unwrap();
+    let target = FixedKeyTables::open_tables_with_database(&db);
+    target
+        .import_tables(&mut TableImporter::new(export.as_slice()).unwrap())
+        .unwrap();
+
+    assert_eq!(
+        source
+            .table1
+            .safe_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>(),
+        target
+            .table1
+            .safe_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        source
+            .table2
+            .safe_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>(),
+        target
+            .table2
+            .safe_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+    );
+    assert!(target.empty.is_empty());
+}
+
 #[derive(DBMapUtils)]
 struct DeprecatedTables {
     table1: DBMap<String, String>,